@@ -0,0 +1,139 @@
+//! Fixed-rate load test against a running Ranvier server.
+//!
+//! ```text
+//! cargo run -p ranvier-bench --bin ranvier_load -- \
+//!     --url http://127.0.0.1:3000 --route /math --method POST \
+//!     --rps 500 --duration 60s --fixtures fixtures/math.json --outcome-field kind
+//! ```
+//!
+//! `--fixtures` points to a JSON array of request bodies — the same typed
+//! inputs the circuit's unit tests use. Without it, a bodiless request is sent.
+
+use clap::Parser;
+use http::Method;
+use ranvier_http::{LoadTest, RemoteTarget, TestRequest, TestResponse};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Base URL of the server (http only)
+    #[arg(short, long)]
+    url: String,
+
+    /// Route to call, e.g. /math
+    #[arg(short, long, default_value = "/")]
+    route: String,
+
+    /// HTTP method
+    #[arg(short, long, default_value = "GET")]
+    method: Method,
+
+    /// Target requests per second
+    #[arg(long, default_value_t = 100)]
+    rps: u32,
+
+    /// Run length, e.g. 500ms, 30s, 2m
+    #[arg(short, long, default_value = "10s", value_parser = parse_duration)]
+    duration: Duration,
+
+    /// JSON file with an array of request bodies, sent round-robin
+    #[arg(short, long)]
+    fixtures: Option<std::path::PathBuf>,
+
+    /// Extra request header as `name: value` (repeatable)
+    #[arg(short = 'H', long = "header")]
+    headers: Vec<String>,
+
+    /// Maximum concurrent requests
+    #[arg(long, default_value_t = 256)]
+    max_in_flight: usize,
+
+    /// Group responses by this top-level JSON body field instead of status
+    #[arg(long)]
+    outcome_field: Option<String>,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration `{value}`"))?;
+    match unit {
+        "ms" => Ok(Duration::from_millis(amount)),
+        "" | "s" => Ok(Duration::from_secs(amount)),
+        "m" => Ok(Duration::from_secs(amount * 60)),
+        _ => Err(format!("unknown duration unit `{unit}` (use ms, s or m)")),
+    }
+}
+
+fn outcome_label(response: &TestResponse, field: &str) -> String {
+    response
+        .json::<serde_json::Value>()
+        .ok()
+        .and_then(|body| match body.get(field)? {
+            serde_json::Value::String(value) => Some(value.clone()),
+            other => Some(other.to_string()),
+        })
+        .unwrap_or_else(|| response.status().as_u16().to_string())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let mut base = TestRequest::new(args.method.clone(), args.route.clone());
+    for header in &args.headers {
+        let (name, value) = header
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("header `{header}` must be `name: value`"))?;
+        base = base.header(name.trim(), value.trim());
+    }
+
+    let fixtures = match &args.fixtures {
+        Some(path) => {
+            let bodies: Vec<serde_json::Value> = serde_json::from_slice(&std::fs::read(path)?)?;
+            anyhow::ensure!(!bodies.is_empty(), "{} has no fixtures", path.display());
+            bodies
+                .iter()
+                .map(|body| base.clone().json(body))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        None => vec![base],
+    };
+
+    let mut load = LoadTest::new(fixtures)
+        .rps(args.rps)
+        .duration(args.duration)
+        .max_in_flight(args.max_in_flight);
+    if let Some(field) = args.outcome_field.clone() {
+        load = load.classify(move |response| outcome_label(response, &field));
+    }
+
+    eprintln!(
+        "Load testing {} {}{} at {} rps for {:?} ({} requests)",
+        args.method,
+        args.url,
+        args.route,
+        args.rps,
+        args.duration,
+        load.planned_requests()
+    );
+    let report = load.run(Arc::new(RemoteTarget::new(&args.url)?)).await;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{report}");
+    }
+    Ok(())
+}
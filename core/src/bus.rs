@@ -22,11 +22,17 @@
 
 use std::any::{Any, TypeId, type_name};
 use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 
 use ahash::AHashMap;
+use async_trait::async_trait;
+use parking_lot::Mutex;
 use uuid::Uuid;
 
+use crate::bus_registry;
+use crate::bus_snapshot::{BusSnapshot, BusSnapshotEntry};
 use crate::cancellation::CancellationToken;
 
 /// Type reference used by bus access policy declarations.
@@ -118,6 +124,11 @@ pub enum BusAccessError {
     NotFound {
         resource: &'static str,
     },
+    /// A [`register_factory`](Bus::register_factory) factory failed.
+    Unavailable {
+        resource: &'static str,
+        reason: String,
+    },
 }
 
 impl std::fmt::Display for BusAccessError {
@@ -144,12 +155,60 @@ impl std::fmt::Display for BusAccessError {
             BusAccessError::NotFound { resource } => {
                 write!(f, "Bus resource not found: `{resource}`")
             }
+            BusAccessError::Unavailable { resource, reason } => {
+                write!(
+                    f,
+                    "Bus resource `{resource}` could not be created: {reason}"
+                )
+            }
         }
     }
 }
 
 impl std::error::Error for BusAccessError {}
 
+/// Fault category recorded when a node faults after a failed
+/// [`Bus::require`].
+pub const MISSING_RESOURCE: &str = "missing_resource";
+
+/// A resource a node required but the Bus did not provide.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MissingResource {
+    pub resource: &'static str,
+    /// The node that asked, when the Bus was running one.
+    pub node_id: Option<String>,
+}
+
+impl MissingResource {
+    pub fn category(&self) -> &'static str {
+        MISSING_RESOURCE
+    }
+}
+
+impl std::fmt::Display for MissingResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{MISSING_RESOURCE}: `{}`", self.resource)?;
+        if let Some(node_id) = &self.node_id {
+            write!(f, " required by node {node_id}")?;
+        }
+        write!(f, " is not on the Bus")
+    }
+}
+
+impl std::error::Error for MissingResource {}
+
+impl From<MissingResource> for String {
+    fn from(missing: MissingResource) -> Self {
+        missing.to_string()
+    }
+}
+
+impl From<MissingResource> for crate::error::RanvierError {
+    fn from(missing: MissingResource) -> Self {
+        Self::not_found(missing.resource)
+    }
+}
+
 /// Type-indexed per-execution resource and context container.
 ///
 /// The requested `T` is statically typed, while presence and authorization are
@@ -161,9 +220,23 @@ pub struct Bus {
     resources: AHashMap<std::any::TypeId, Box<dyn Any + Send + Sync>>,
     /// Explicitly shareable local entries. Parallel forks inherit cloned
     /// handles to these values as read-only context.
-    shared_resources: AHashMap<std::any::TypeId, Arc<dyn Any + Send + Sync>>,
-    /// Read-only entries inherited from a parent parallel context.
-    inherited_resources: AHashMap<std::any::TypeId, Arc<dyn Any + Send + Sync>>,
+    shared_resources: SharedEntries,
+    /// Read-only layers inherited from parent parallel contexts, outermost
+    /// first. Forks share these maps instead of copying them.
+    inherited_resources: Vec<SharedEntries>,
+    /// Resources built on first [`resolve`](Bus::resolve). Parallel forks
+    /// share the slots, so a value is built once per execution.
+    lazy_resources: Arc<AHashMap<TypeId, Arc<dyn LazySlot>>>,
+    /// Local entries of the enclosing scopes while a [`BusScope`] is open,
+    /// innermost last.
+    outer_scopes: Vec<ScopeLayer>,
+    /// Execution-local entries stored under a name, so that several values of
+    /// one type can coexist.
+    named_resources: NamedResources,
+    /// Type names of everything inserted, for [`snapshot`](Bus::snapshot).
+    type_names: Arc<AHashMap<TypeId, &'static str>>,
+    /// Resources added with [`attach`](Bus::attach), in attach order.
+    lifecycle: Vec<LifecycleEntry>,
     /// Optional unique identifier for this Bus instance
     pub id: Uuid,
     /// Optional transition-scoped access guard (M143 opt-in)
@@ -172,6 +245,12 @@ pub struct Bus {
     /// from type-erased application context so transition access policy and
     /// isolated parallel application Bus semantics cannot hide it.
     cancellation_token: Option<CancellationToken>,
+    /// Key in the [`bus_registry`](crate::bus_registry) while tracking is on.
+    registry_key: Option<u64>,
+    /// Node the runtime is executing, for [`MissingResource::node_id`].
+    current_node: Option<String>,
+    /// Last failed [`require`](Bus::require), until the runtime takes it.
+    missing_resource: Mutex<Option<MissingResource>>,
 }
 
 impl Bus {
@@ -181,14 +260,68 @@ impl Bus {
     /// optional [`BusAccessPolicy`] installs a runtime guard during execution.
     #[inline]
     pub fn new() -> Self {
+        let id = Uuid::new_v4();
         Self {
             resources: AHashMap::new(),
-            shared_resources: AHashMap::new(),
-            inherited_resources: AHashMap::new(),
-            id: Uuid::new_v4(),
+            shared_resources: SharedEntries::default(),
+            inherited_resources: Vec::new(),
+            lazy_resources: Arc::default(),
+            outer_scopes: Vec::new(),
+            named_resources: AHashMap::new(),
+            type_names: Arc::default(),
+            lifecycle: Vec::new(),
+            id,
             access_guard: None,
             cancellation_token: None,
+            registry_key: bus_registry::register(id),
+            current_node: None,
+            missing_resource: Mutex::new(None),
+        }
+    }
+
+    /// Create a request Bus on top of application-level resources.
+    ///
+    /// The [`ResourceSet`] is shared, not copied, so building a Bus per
+    /// request costs the same however many pools and clients the application
+    /// holds. See [`use_app`](Bus::use_app).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ranvier_core::{Bus, ResourceSet};
+    /// struct DbPool(&'static str);
+    ///
+    /// let app = ResourceSet::new().with(DbPool("postgres://primary"));
+    /// let mut bus = Bus::with_app(&app);
+    /// bus.insert(42u64); // request-scoped
+    /// assert_eq!(bus.read::<DbPool>().unwrap().0, "postgres://primary");
+    /// assert!(bus.get_mut::<DbPool>().is_err());
+    /// ```
+    pub fn with_app(app: &ResourceSet) -> Self {
+        let mut bus = Self::new();
+        bus.use_app(app);
+        bus
+    }
+
+    /// Make application-level resources visible to this Bus.
+    ///
+    /// The entries are read-only here and are inherited by parallel forks.
+    /// Entries inserted on the Bus take precedence over them, and a set
+    /// added later takes precedence over one added earlier.
+    pub fn use_app(&mut self, app: &ResourceSet) {
+        if app.is_empty() {
+            return;
+        }
+        self.inherited_resources.insert(0, Arc::clone(&app.entries));
+        if self.type_names.is_empty() {
+            self.type_names = Arc::clone(&app.type_names);
+        } else {
+            let type_names = Arc::make_mut(&mut self.type_names);
+            for (type_id, name) in app.type_names.iter() {
+                type_names.entry(*type_id).or_insert(name);
+            }
         }
+        self.publish_to_registry();
     }
 
     /// Insert a resource into the Bus.
@@ -206,8 +339,10 @@ impl Bus {
     #[inline]
     pub fn insert<T: Any + Send + Sync + 'static>(&mut self, resource: T) {
         let type_id = std::any::TypeId::of::<T>();
-        self.shared_resources.remove(&type_id);
+        self.record_type_name::<T>();
+        take_shared(&mut self.shared_resources, &type_id);
         self.resources.insert(type_id, Box::new(resource));
+        self.publish_to_registry();
     }
 
     /// Insert a value that explicit parallel Bus forks may inherit.
@@ -219,8 +354,10 @@ impl Bus {
     #[inline]
     pub fn insert_shared<T: Any + Send + Sync + 'static>(&mut self, resource: T) {
         let type_id = TypeId::of::<T>();
+        self.record_type_name::<T>();
         self.resources.remove(&type_id);
-        self.shared_resources.insert(type_id, Arc::new(resource));
+        Arc::make_mut(&mut self.shared_resources).insert(type_id, Arc::new(resource));
+        self.publish_to_registry();
     }
 
     /// Read a resource from the Bus.
@@ -292,7 +429,24 @@ impl Bus {
                     resource: type_name::<T>(),
                 });
         }
-        if let Some(resource) = self.inherited_resources.get(&type_id) {
+        if let Some(resource) = self
+            .outer_scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&type_id))
+        {
+            return resource
+                .downcast_ref::<T>()
+                .ok_or(BusAccessError::NotFound {
+                    resource: type_name::<T>(),
+                });
+        }
+        if let Some(resource) = self
+            .inherited_resources
+            .iter()
+            .rev()
+            .find_map(|layer| layer.get(&type_id))
+        {
             return resource
                 .as_ref()
                 .downcast_ref::<T>()
@@ -300,9 +454,13 @@ impl Bus {
                     resource: type_name::<T>(),
                 });
         }
-        Err(BusAccessError::NotFound {
-            resource: type_name::<T>(),
-        })
+        self.lazy_resources
+            .get(&type_id)
+            .and_then(|slot| slot.value())
+            .and_then(|resource| resource.downcast_ref::<T>())
+            .ok_or(BusAccessError::NotFound {
+                resource: type_name::<T>(),
+            })
     }
 
     /// Read a mutable resource with explicit policy/not-found error details.
@@ -317,14 +475,37 @@ impl Bus {
                     resource: type_name::<T>(),
                 });
         }
-        if let Some(resource) = self.shared_resources.get_mut(&type_id) {
-            return Arc::get_mut(resource)
+        if self.shared_resources.contains_key(&type_id) {
+            return shared_mut(&mut self.shared_resources, &type_id)
                 .and_then(|resource| resource.downcast_mut::<T>())
                 .ok_or_else(Self::aliased_shared_mutation_error::<T>);
         }
-        if self.inherited_resources.contains_key(&type_id) {
+        for scope in self.outer_scopes.iter_mut().rev() {
+            if let Some(resource) = scope.resources.get_mut(&type_id) {
+                return resource
+                    .downcast_mut::<T>()
+                    .ok_or(BusAccessError::NotFound {
+                        resource: type_name::<T>(),
+                    });
+            }
+            if scope.shared_resources.contains_key(&type_id) {
+                return shared_mut(&mut scope.shared_resources, &type_id)
+                    .and_then(|resource| resource.downcast_mut::<T>())
+                    .ok_or_else(Self::aliased_shared_mutation_error::<T>);
+            }
+        }
+        if self
+            .inherited_resources
+            .iter()
+            .any(|layer| layer.contains_key(&type_id))
+        {
             return Err(Self::inherited_mutation_error::<T>());
         }
+        if let Some(slot) = self.lazy_resources.get(&type_id)
+            && slot.value().is_some()
+        {
+            return Err(Self::lazy_mutation_error::<T>());
+        }
         Err(BusAccessError::NotFound {
             resource: type_name::<T>(),
         })
@@ -343,15 +524,31 @@ impl Bus {
         let type_id = std::any::TypeId::of::<T>();
         self.resources.contains_key(&type_id)
             || self.shared_resources.contains_key(&type_id)
-            || self.inherited_resources.contains_key(&type_id)
+            || self
+                .outer_scopes
+                .iter()
+                .any(|scope| scope.get(&type_id).is_some())
+            || self.is_inherited(&type_id)
+            || self
+                .lazy_resources
+                .get(&type_id)
+                .is_some_and(|slot| slot.value().is_some())
     }
 
     /// Remove a resource from the Bus.
     ///
-    /// Returns the resource if it was present, `None` otherwise.
+    /// Returns the resource if it was present, `None` otherwise. Inside a
+    /// [`child_scope`](Bus::child_scope) only the scope's own entries can be
+    /// removed.
     /// Returns `None` if access is denied by an active policy (logged via
     /// `tracing::error!`).
     pub fn remove<T: Any + Send + Sync + 'static>(&mut self) -> Option<T> {
+        let removed = self.remove_entry::<T>();
+        self.publish_to_registry();
+        removed
+    }
+
+    fn remove_entry<T: Any + Send + Sync + 'static>(&mut self) -> Option<T> {
         if let Err(err) = self.ensure_access::<T>() {
             tracing::error!("{err}");
             return None;
@@ -360,24 +557,24 @@ impl Bus {
         if let Some(resource) = self.resources.remove(&type_id) {
             return resource.downcast::<T>().ok().map(|resource| *resource);
         }
-        if let Some(resource) = self.shared_resources.remove(&type_id) {
+        if let Some(resource) = take_shared(&mut self.shared_resources, &type_id) {
             return match Arc::downcast::<T>(resource) {
                 Ok(resource) => match Arc::try_unwrap(resource) {
                     Ok(resource) => Some(resource),
                     Err(resource) => {
                         let resource: Arc<dyn Any + Send + Sync> = resource;
-                        self.shared_resources.insert(type_id, resource);
+                        Arc::make_mut(&mut self.shared_resources).insert(type_id, resource);
                         tracing::error!("{}", Self::aliased_shared_mutation_error::<T>());
                         None
                     }
                 },
                 Err(resource) => {
-                    self.shared_resources.insert(type_id, resource);
+                    Arc::make_mut(&mut self.shared_resources).insert(type_id, resource);
                     None
                 }
             };
         }
-        if self.inherited_resources.contains_key(&type_id) {
+        if self.is_inherited(&type_id) {
             tracing::error!("{}", Self::inherited_mutation_error::<T>());
         }
         None
@@ -388,17 +585,45 @@ impl Bus {
         self.resources
             .keys()
             .chain(self.shared_resources.keys())
-            .chain(self.inherited_resources.keys())
+            .chain(
+                self.outer_scopes
+                    .iter()
+                    .flat_map(|scope| scope.resources.keys().chain(scope.shared_resources.keys())),
+            )
+            .chain(
+                self.inherited_resources
+                    .iter()
+                    .flat_map(|layer| layer.keys()),
+            )
+            .chain(
+                self.lazy_resources
+                    .iter()
+                    .filter(|(_, slot)| slot.value().is_some())
+                    .map(|(type_id, _)| type_id),
+            )
             .copied()
             .collect::<HashSet<_>>()
             .len()
+            + self.named_len()
     }
 
     /// Check if the Bus is empty.
     pub fn is_empty(&self) -> bool {
         self.resources.is_empty()
             && self.shared_resources.is_empty()
-            && self.inherited_resources.is_empty()
+            && self
+                .outer_scopes
+                .iter()
+                .all(|scope| scope.resources.is_empty() && scope.shared_resources.is_empty())
+            && self
+                .inherited_resources
+                .iter()
+                .all(|layer| layer.is_empty())
+            && self
+                .lazy_resources
+                .values()
+                .all(|slot| slot.value().is_none())
+            && self.named_len() == 0
     }
 
     /// Provide a resource to the Bus.
@@ -434,21 +659,262 @@ impl Bus {
     /// [`provide_shared`](Bus::provide_shared) are inherited. Local branch
     /// writes are discarded with the returned Bus, inherited entries are
     /// structurally read-only, and the new Bus receives a distinct id.
+    ///
+    /// Forking shares the parent's entry maps rather than copying them, so
+    /// its cost does not grow with the number of resources. The parent copies
+    /// a map on its next shared write while forks still hold it.
     pub fn fork_for_parallel(&self) -> Self {
-        let mut inherited_resources = self.inherited_resources.clone();
-        inherited_resources.extend(
-            self.shared_resources
-                .iter()
-                .map(|(type_id, resource)| (*type_id, Arc::clone(resource))),
-        );
-        Self {
+        let inherited_resources = self
+            .inherited_resources
+            .iter()
+            .chain(
+                self.outer_scopes
+                    .iter()
+                    .map(|scope| &scope.shared_resources),
+            )
+            .chain(std::iter::once(&self.shared_resources))
+            .filter(|layer| !layer.is_empty())
+            .map(Arc::clone)
+            .collect();
+        let id = Uuid::new_v4();
+        let fork = Self {
             resources: AHashMap::new(),
-            shared_resources: AHashMap::new(),
+            shared_resources: SharedEntries::default(),
             inherited_resources,
-            id: Uuid::new_v4(),
+            lazy_resources: Arc::clone(&self.lazy_resources),
+            outer_scopes: Vec::new(),
+            named_resources: AHashMap::new(),
+            type_names: Arc::clone(&self.type_names),
+            lifecycle: Vec::new(),
+            id,
             access_guard: None,
             cancellation_token: self.cancellation_token.clone(),
+            registry_key: bus_registry::register(id),
+            current_node: self.current_node.clone(),
+            missing_resource: Mutex::new(None),
+        };
+        fork.publish_to_registry();
+        fork
+    }
+
+    /// Open a scope whose writes are discarded when it ends.
+    ///
+    /// The returned [`BusScope`] dereferences to this Bus, so it can be
+    /// passed wherever a `&mut Bus` is expected. Inside the scope every
+    /// existing entry stays readable and mutable in place, while inserts land
+    /// in the scope and shadow same-typed parent entries. When the scope is
+    /// dropped, its entries are dropped too, except those moved out with
+    /// [`BusScope::promote`]:
+    ///
+    /// ```rust
+    /// # use ranvier_core::Bus;
+    /// # struct Draft(u32);
+    /// let mut bus = Bus::new();
+    /// bus.insert(1u8);
+    /// {
+    ///     let mut scope = bus.child_scope();
+    ///     assert_eq!(*scope.read::<u8>().unwrap(), 1);
+    ///     scope.insert(Draft(7));
+    ///     scope.insert(String::from("kept"));
+    ///     scope.promote::<String>();
+    /// }
+    /// assert!(bus.read::<Draft>().is_none());
+    /// assert_eq!(bus.read::<String>().unwrap(), "kept");
+    /// ```
+    ///
+    /// Scopes nest. The access policy and cancellation token are those of the
+    /// Bus itself and are not scoped.
+    pub fn child_scope(&mut self) -> BusScope<'_> {
+        self.outer_scopes.push(ScopeLayer {
+            resources: std::mem::take(&mut self.resources),
+            shared_resources: std::mem::take(&mut self.shared_resources),
+            named_resources: std::mem::take(&mut self.named_resources),
+        });
+        BusScope { bus: self }
+    }
+
+    /// Replace `T` until the returned guard is dropped.
+    ///
+    /// The guard dereferences to this Bus. When it is dropped, the `T` held
+    /// before the call is put back, or `T` is removed if there was none, so
+    /// a test or experiment can swap a Synapse or a config value without
+    /// leaking the swap:
+    ///
+    /// ```rust
+    /// # use ranvier_core::Bus;
+    /// #[derive(Debug, PartialEq)]
+    /// struct Flags { new_checkout: bool }
+    ///
+    /// let mut bus = Bus::new();
+    /// bus.insert(Flags { new_checkout: false });
+    /// {
+    ///     let bus = bus.override_scoped(Flags { new_checkout: true });
+    ///     assert!(bus.read::<Flags>().unwrap().new_checkout);
+    /// }
+    /// assert_eq!(bus.read::<Flags>(), Some(&Flags { new_checkout: false }));
+    /// ```
+    ///
+    /// Only entries held directly by the Bus, or by the innermost
+    /// [`child_scope`](Bus::child_scope), are saved; inherited and
+    /// application entries are shadowed and never modified.
+    pub fn override_scoped<T: Any + Send + Sync + 'static>(
+        &mut self,
+        value: T,
+    ) -> BusOverride<'_, T> {
+        let type_id = TypeId::of::<T>();
+        let previous = match self.resources.remove(&type_id) {
+            Some(owned) => Some(Saved::Owned(owned)),
+            None => take_shared(&mut self.shared_resources, &type_id).map(Saved::Shared),
+        };
+        self.insert(value);
+        BusOverride {
+            bus: self,
+            previous,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Register a factory that builds `T` on first use.
+    ///
+    /// Nothing runs until [`resolve`](Bus::resolve) asks for `T`; the factory
+    /// then runs once, and concurrent callers, including parallel branches
+    /// forked from this Bus, wait for that one result. Once built, `T` is
+    /// visible to [`read`](Bus::read) and [`get`](Bus::get) like any shared
+    /// entry, but it cannot be mutated. A failed build is reported as
+    /// [`BusAccessError::Unavailable`] and retried on the next `resolve`.
+    /// Entries inserted directly take precedence over the factory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ranvier_core::Bus;
+    /// # #[tokio::main(flavor = "current_thread")] async fn main() {
+    /// struct Pool(&'static str);
+    ///
+    /// let mut bus = Bus::new();
+    /// bus.register_factory(|| async { Ok(Pool("postgres://primary")) });
+    /// assert!(bus.read::<Pool>().is_none());
+    /// assert_eq!(bus.resolve::<Pool>().await.unwrap().0, "postgres://primary");
+    /// assert!(bus.read::<Pool>().is_some());
+    /// # }
+    /// ```
+    pub fn register_factory<T, F, Fut>(&mut self, factory: F)
+    where
+        T: Any + Send + Sync + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, String>> + Send + 'static,
+    {
+        let type_id = TypeId::of::<T>();
+        self.record_type_name::<T>();
+        Arc::make_mut(&mut self.lazy_resources).insert(
+            type_id,
+            Arc::new(LazyResource {
+                cell: tokio::sync::OnceCell::new(),
+                factory: Box::new(move || Box::pin(factory())),
+            }),
+        );
+    }
+
+    /// Read `T`, building it with its registered factory if needed.
+    ///
+    /// Behaves like [`get`](Bus::get) for entries that already exist.
+    pub async fn resolve<T: Any + Send + Sync + 'static>(&self) -> Result<&T, BusAccessError> {
+        match self.get::<T>() {
+            Err(BusAccessError::NotFound { .. }) => {}
+            found => return found,
+        }
+        let Some(lazy) = self
+            .lazy_resources
+            .get(&TypeId::of::<T>())
+            .and_then(|slot| slot.as_any().downcast_ref::<LazyResource<T>>())
+        else {
+            return Err(BusAccessError::NotFound {
+                resource: type_name::<T>(),
+            });
+        };
+        let resource = lazy
+            .cell
+            .get_or_try_init(|| (lazy.factory)())
+            .await
+            .map_err(|reason| BusAccessError::Unavailable {
+                resource: type_name::<T>(),
+                reason,
+            })?;
+        self.publish_to_registry();
+        Ok(resource)
+    }
+
+    /// Insert a resource whose lifecycle the Bus manages.
+    ///
+    /// Calls [`ResourceLifecycle::on_attach`], then inserts the resource like
+    /// [`insert`](Bus::insert). [`shutdown`](Bus::shutdown) later calls
+    /// [`ResourceLifecycle::on_shutdown`] on it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ranvier_core::bus::{Bus, ResourceLifecycle};
+    /// # #[tokio::main(flavor = "current_thread")] async fn main() {
+    /// struct Exporter;
+    ///
+    /// #[async_trait::async_trait]
+    /// impl ResourceLifecycle for Exporter {
+    ///     async fn on_shutdown(&self) -> Result<(), String> {
+    ///         // flush buffered spans
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut bus = Bus::new();
+    /// bus.attach(Exporter);
+    /// let report = bus.shutdown().await;
+    /// assert!(report[0].error.is_none());
+    /// assert!(!bus.has::<Exporter>());
+    /// # }
+    /// ```
+    pub fn attach<T: ResourceLifecycle>(&mut self, resource: T) {
+        resource.on_attach();
+        let type_id = TypeId::of::<T>();
+        self.lifecycle.retain(|entry| entry.type_id != type_id);
+        self.lifecycle.push(LifecycleEntry {
+            type_id,
+            resource: type_name::<T>(),
+            shut_down: |resource| {
+                Box::pin(async move {
+                    match resource.downcast::<T>() {
+                        Ok(resource) => resource.on_shutdown().await,
+                        Err(_) => Ok(()),
+                    }
+                })
+            },
+        });
+        self.insert(resource);
+    }
+
+    /// Shut down attached resources, most recently attached first.
+    ///
+    /// Each resource is removed from the Bus, given
+    /// [`ResourceLifecycle::on_shutdown`] and dropped. A failing resource does
+    /// not stop later ones. Resources already removed or moved into a closed
+    /// [`child_scope`](Bus::child_scope) are skipped.
+    pub async fn shutdown(&mut self) -> Vec<ResourceShutdown> {
+        let mut report = Vec::new();
+        while let Some(entry) = self.lifecycle.pop() {
+            let resource = match self.resources.remove(&entry.type_id) {
+                Some(resource) => resource,
+                None => continue,
+            };
+            let error = (entry.shut_down)(resource).await.err();
+            if let Some(error) = &error {
+                tracing::warn!(resource = entry.resource, %error, "Resource shutdown failed");
+            }
+            report.push(ResourceShutdown {
+                resource: entry.resource.to_string(),
+                error,
+            });
         }
+        self.publish_to_registry();
+        report
     }
 
     /// Install the cooperative cancellation token for this execution.
@@ -464,16 +930,19 @@ impl Bus {
         self.cancellation_token.as_ref()
     }
 
-    /// Require a resource from the Bus, panicking if it is missing or denied.
+    /// Require a resource from the Bus.
     ///
     /// Use this when the resource is expected to always be present (e.g., injected
     /// at startup). For optional resources, use [`try_require`](Bus::try_require).
     ///
-    /// # Panics
+    /// A type hidden by an active policy counts as missing; the denial is
+    /// logged as in [`read`](Bus::read). Use [`get`](Bus::get) to tell the two
+    /// cases apart.
     ///
-    /// Panics if the resource type `T` has not been inserted or an active policy
-    /// denies access. Production paths that need to distinguish those cases
-    /// should use [`get`](Bus::get).
+    /// When a node faults after a failed `require`, the runtime records the
+    /// fault under the [`MISSING_RESOURCE`] category with the resource's type
+    /// name. `String` and [`RanvierError`](crate::error::RanvierError) errors
+    /// convert from [`MissingResource`].
     ///
     /// # Example
     ///
@@ -481,19 +950,46 @@ impl Bus {
     /// # use ranvier_core::Bus;
     /// let mut bus = Bus::new();
     /// bus.provide(42i32);
-    /// let value: &i32 = bus.require::<i32>();
+    /// let value: &i32 = bus.require::<i32>().unwrap();
     /// assert_eq!(*value, 42);
+    /// let missing = bus.require::<String>().unwrap_err();
+    /// assert_eq!(missing.resource, "alloc::string::String");
     /// ```
-    #[inline]
-    pub fn require<T: Any + Send + Sync + 'static>(&self) -> &T {
-        self.read::<T>().unwrap_or_else(|| {
-            panic!(
-                "Bus: required resource `{}` not found. Did you forget to call bus.provide()?",
-                std::any::type_name::<T>()
-            )
+    pub fn require<T: Any + Send + Sync + 'static>(&self) -> Result<&T, MissingResource> {
+        self.read::<T>().ok_or_else(|| {
+            let missing = MissingResource {
+                resource: type_name::<T>(),
+                node_id: self.current_node.clone(),
+            };
+            *self.missing_resource.lock() = Some(missing.clone());
+            missing
         })
     }
 
+    /// Build a [`FromBus`](crate::from_bus::FromBus) value, usually a
+    /// `#[derive(FromBus)]` struct, from this Bus's resources.
+    pub fn extract<T: crate::from_bus::FromBus>(
+        &self,
+    ) -> Result<T, crate::from_bus::MissingResources> {
+        T::from_bus(self)
+    }
+
+    /// Mark the node the runtime is about to run, returning the previous one.
+    ///
+    /// Set by the Axon executor; [`require`](Bus::require) reports it.
+    pub fn set_current_node(&mut self, node_id: Option<String>) -> Option<String> {
+        std::mem::replace(&mut self.current_node, node_id)
+    }
+
+    pub fn current_node(&self) -> Option<&str> {
+        self.current_node.as_deref()
+    }
+
+    /// Take the most recent failed [`require`](Bus::require), if any.
+    pub fn take_missing_resource(&mut self) -> Option<MissingResource> {
+        self.missing_resource.get_mut().take()
+    }
+
     /// Try to require a resource from the Bus, returning `None` if missing.
     ///
     /// Semantic alias for [`read`](Bus::read) that pairs with [`provide`](Bus::provide)
@@ -533,6 +1029,321 @@ impl Bus {
         self.get::<T>().cloned()
     }
 
+    /// Clone `T` out of the Bus, or return `default` when it is absent.
+    ///
+    /// For dependencies a transition can do without. Like
+    /// [`read`](Bus::read), a policy denial counts as absent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ranvier_core::Bus;
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct PageSize(usize);
+    ///
+    /// let bus = Bus::new();
+    /// assert_eq!(bus.read_or(PageSize(50)), PageSize(50));
+    /// ```
+    #[inline]
+    pub fn read_or<T: Any + Send + Sync + Clone + 'static>(&self, default: T) -> T {
+        self.read::<T>().cloned().unwrap_or(default)
+    }
+
+    /// Clone `T` out of the Bus if it is there.
+    ///
+    /// Unlike [`require`](Bus::require), a miss is not recorded against the
+    /// running node.
+    #[inline]
+    pub fn read_opt_cloned<T: Any + Send + Sync + Clone + 'static>(&self) -> Option<T> {
+        self.read::<T>().cloned()
+    }
+
+    /// Insert a resource under `name`.
+    ///
+    /// Named entries sit beside the unnamed ones: `insert_named("replica",
+    /// pool)` neither replaces nor is seen by `read::<PgPool>()`, and another
+    /// name holds another instance of the same type. A resource already
+    /// stored under this type and name is replaced. Access policies apply by
+    /// type, as for unnamed entries.
+    ///
+    /// Named entries are execution-local: [`fork_for_parallel`](Bus::fork_for_parallel)
+    /// does not pass them on, and a [`child_scope`](Bus::child_scope) reads
+    /// its parent's but drops its own.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ranvier_core::Bus;
+    /// # #[derive(Debug, PartialEq)] struct Pool(&'static str);
+    /// let mut bus = Bus::new();
+    /// bus.insert_named("primary_db", Pool("postgres://primary"));
+    /// bus.insert_named("analytics_db", Pool("postgres://analytics"));
+    /// assert_eq!(bus.read_named::<Pool>("analytics_db"), Some(&Pool("postgres://analytics")));
+    /// assert!(bus.read::<Pool>().is_none());
+    /// ```
+    pub fn insert_named<T: Any + Send + Sync + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        resource: T,
+    ) {
+        self.record_type_name::<T>();
+        self.named_resources
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .insert(name.into(), Box::new(resource));
+        self.publish_to_registry();
+    }
+
+    /// Provide a resource under `name`.
+    ///
+    /// Dependency-injection alias for [`insert_named`](Bus::insert_named).
+    #[inline]
+    pub fn provide_named<T: Any + Send + Sync + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        resource: T,
+    ) {
+        self.insert_named(name, resource);
+    }
+
+    /// Read the resource stored under `name`.
+    ///
+    /// Like [`read`](Bus::read), returns `None` when it is missing or access
+    /// is denied, logging denials via `tracing::error!`.
+    #[inline]
+    pub fn read_named<T: Any + Send + Sync + 'static>(&self, name: &str) -> Option<&T> {
+        match self.get_named::<T>(name) {
+            Ok(value) => Some(value),
+            Err(BusAccessError::NotFound { .. }) => None,
+            Err(err) => {
+                tracing::error!("{err}");
+                None
+            }
+        }
+    }
+
+    /// Read the resource stored under `name` mutably.
+    #[inline]
+    pub fn read_named_mut<T: Any + Send + Sync + 'static>(&mut self, name: &str) -> Option<&mut T> {
+        match self.get_named_mut::<T>(name) {
+            Ok(value) => Some(value),
+            Err(BusAccessError::NotFound { .. }) => None,
+            Err(err) => {
+                tracing::error!("{err}");
+                None
+            }
+        }
+    }
+
+    /// Read the resource stored under `name` with explicit error details.
+    pub fn get_named<T: Any + Send + Sync + 'static>(
+        &self,
+        name: &str,
+    ) -> Result<&T, BusAccessError> {
+        self.ensure_access::<T>()?;
+        let type_id = TypeId::of::<T>();
+        std::iter::once(&self.named_resources)
+            .chain(
+                self.outer_scopes
+                    .iter()
+                    .rev()
+                    .map(|scope| &scope.named_resources),
+            )
+            .find_map(|named| named.get(&type_id)?.get(name))
+            .and_then(|resource| resource.downcast_ref::<T>())
+            .ok_or(BusAccessError::NotFound {
+                resource: type_name::<T>(),
+            })
+    }
+
+    /// Mutable variant of [`get_named`](Bus::get_named).
+    pub fn get_named_mut<T: Any + Send + Sync + 'static>(
+        &mut self,
+        name: &str,
+    ) -> Result<&mut T, BusAccessError> {
+        self.ensure_access::<T>()?;
+        let type_id = TypeId::of::<T>();
+        std::iter::once(&mut self.named_resources)
+            .chain(
+                self.outer_scopes
+                    .iter_mut()
+                    .rev()
+                    .map(|scope| &mut scope.named_resources),
+            )
+            .find_map(|named| named.get_mut(&type_id)?.get_mut(name))
+            .and_then(|resource| resource.downcast_mut::<T>())
+            .ok_or(BusAccessError::NotFound {
+                resource: type_name::<T>(),
+            })
+    }
+
+    /// Check if a resource is stored under `name`.
+    pub fn has_named<T: Any + Send + Sync + 'static>(&self, name: &str) -> bool {
+        self.read_named::<T>(name).is_some()
+    }
+
+    /// Remove the resource stored under `name`.
+    ///
+    /// Inside a [`child_scope`](Bus::child_scope) only the scope's own
+    /// entries can be removed.
+    pub fn remove_named<T: Any + Send + Sync + 'static>(&mut self, name: &str) -> Option<T> {
+        if let Err(err) = self.ensure_access::<T>() {
+            tracing::error!("{err}");
+            return None;
+        }
+        let type_id = TypeId::of::<T>();
+        let named = self.named_resources.get_mut(&type_id)?;
+        let resource = named.remove(name)?;
+        if named.is_empty() {
+            self.named_resources.remove(&type_id);
+        }
+        self.publish_to_registry();
+        resource.downcast::<T>().ok().map(|resource| *resource)
+    }
+
+    /// Names under which a `T` is stored, sorted.
+    pub fn names_of<T: Any + Send + Sync + 'static>(&self) -> Vec<&str> {
+        if let Err(err) = self.ensure_access::<T>() {
+            tracing::error!("{err}");
+            return Vec::new();
+        }
+        let type_id = TypeId::of::<T>();
+        let mut names: Vec<&str> = std::iter::once(&self.named_resources)
+            .chain(self.outer_scopes.iter().map(|scope| &scope.named_resources))
+            .filter_map(|named| named.get(&type_id))
+            .flat_map(|named| named.keys().map(String::as_str))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// List what the Bus currently holds, for inspection and replay tooling.
+    ///
+    /// Entries are the ones [`read`](Bus::read) and
+    /// [`read_named`](Bus::read_named) would see, including those inherited
+    /// from a parallel parent or an enclosing scope. Values are included for
+    /// types registered with
+    /// [`register_snapshot_type`](crate::bus_snapshot::register_snapshot_type),
+    /// unless the active access policy denies the type. Compare two snapshots
+    /// with [`BusSnapshot::diff`].
+    pub fn snapshot(&self) -> BusSnapshot {
+        BusSnapshot::new(
+            self.visible_entries()
+                .into_iter()
+                .map(|(type_id, name, resource)| BusSnapshotEntry {
+                    type_name: self.type_name_of(type_id).to_string(),
+                    name: name.map(str::to_string),
+                    value: if self.denies(type_id) {
+                        None
+                    } else {
+                        crate::bus_snapshot::snapshot_value(type_id, resource)
+                    },
+                })
+                .collect(),
+        )
+    }
+
+    /// Every entry `read` and `read_named` can see, with its name if any.
+    fn visible_entries(&self) -> Vec<(TypeId, Option<&str>, &(dyn Any + Send + Sync))> {
+        // Lowest precedence first, so that shadowing entries overwrite.
+        let mut visible: AHashMap<TypeId, &(dyn Any + Send + Sync)> = AHashMap::new();
+        visible.extend(
+            self.lazy_resources
+                .iter()
+                .filter_map(|(type_id, slot)| Some((*type_id, slot.value()?))),
+        );
+        for layer in &self.inherited_resources {
+            visible.extend(
+                layer
+                    .iter()
+                    .map(|(type_id, resource)| (*type_id, resource.as_ref())),
+            );
+        }
+        let layers = self
+            .outer_scopes
+            .iter()
+            .map(|scope| (&scope.resources, &scope.shared_resources))
+            .chain(std::iter::once((&self.resources, &self.shared_resources)));
+        for (resources, shared) in layers {
+            visible.extend(
+                shared
+                    .iter()
+                    .map(|(type_id, resource)| (*type_id, resource.as_ref())),
+            );
+            visible.extend(
+                resources
+                    .iter()
+                    .map(|(type_id, resource)| (*type_id, resource.as_ref())),
+            );
+        }
+        let mut named: AHashMap<(TypeId, &str), &(dyn Any + Send + Sync)> = AHashMap::new();
+        for layer in self
+            .outer_scopes
+            .iter()
+            .map(|scope| &scope.named_resources)
+            .chain(std::iter::once(&self.named_resources))
+        {
+            for (type_id, entries) in layer {
+                named.extend(
+                    entries
+                        .iter()
+                        .map(|(name, resource)| ((*type_id, name.as_str()), resource.as_ref())),
+                );
+            }
+        }
+
+        visible
+            .into_iter()
+            .map(|(type_id, resource)| (type_id, None, resource))
+            .chain(
+                named
+                    .into_iter()
+                    .map(|((type_id, name), resource)| (type_id, Some(name), resource)),
+            )
+            .collect()
+    }
+
+    fn type_name_of(&self, type_id: TypeId) -> &'static str {
+        self.type_names
+            .get(&type_id)
+            .copied()
+            .unwrap_or("<unknown>")
+    }
+
+    fn publish_to_registry(&self) {
+        if let Some(key) = self.registry_key {
+            bus_registry::publish(
+                key,
+                self.visible_entries()
+                    .into_iter()
+                    .map(|(type_id, name, _)| (self.type_name_of(type_id), name)),
+            );
+        }
+    }
+
+    fn denies(&self, type_id: TypeId) -> bool {
+        self.access_guard.as_ref().is_some_and(|guard| {
+            guard.deny.contains(&type_id)
+                || guard
+                    .allow
+                    .as_ref()
+                    .is_some_and(|allow| !allow.contains(&type_id))
+        })
+    }
+
+    fn named_len(&self) -> usize {
+        let mut keys = HashSet::new();
+        for named in std::iter::once(&self.named_resources)
+            .chain(self.outer_scopes.iter().map(|scope| &scope.named_resources))
+        {
+            for (type_id, entries) in named {
+                keys.extend(entries.keys().map(|name| (*type_id, name.as_str())));
+            }
+        }
+        keys.len()
+    }
+
     /// Set transition-scoped policy. `None` keeps access unrestricted.
     pub fn set_access_policy(
         &mut self,
@@ -582,9 +1393,31 @@ impl Bus {
         Ok(())
     }
 
+    fn record_type_name<T: Any + Send + Sync + 'static>(&mut self) {
+        let type_id = TypeId::of::<T>();
+        if !self.type_names.contains_key(&type_id) {
+            Arc::make_mut(&mut self.type_names).insert(type_id, type_name::<T>());
+        }
+    }
+
+    fn is_inherited(&self, type_id: &TypeId) -> bool {
+        self.inherited_resources
+            .iter()
+            .any(|layer| layer.contains_key(type_id))
+    }
+
+    fn lazy_mutation_error<T: Any + Send + Sync + 'static>() -> BusAccessError {
+        BusAccessError::Unauthorized {
+            transition: "lazily built Bus resource is shared".to_string(),
+            resource: type_name::<T>(),
+            allow: None,
+            deny: vec![type_name::<T>()],
+        }
+    }
+
     fn inherited_mutation_error<T: Any + Send + Sync + 'static>() -> BusAccessError {
         BusAccessError::Unauthorized {
-            transition: "parallel inherited context is read-only".to_string(),
+            transition: "inherited or application context is read-only".to_string(),
             resource: type_name::<T>(),
             allow: None,
             deny: vec![type_name::<T>()],
@@ -601,12 +1434,348 @@ impl Bus {
     }
 }
 
+/// Answers whether a Bus entry of a given type will be available, for
+/// startup checks such as `Axon::check_resources`.
+///
+/// Implemented by [`Bus`], counting unnamed entries and registered factories,
+/// and by [`BusSnapshot`].
+pub trait ResourceRegistry {
+    /// `type_name` as produced by [`std::any::type_name`].
+    fn provides(&self, type_name: &str) -> bool;
+}
+
+impl ResourceRegistry for Bus {
+    fn provides(&self, type_name: &str) -> bool {
+        self.visible_entries()
+            .iter()
+            .any(|(type_id, name, _)| name.is_none() && self.type_name_of(*type_id) == type_name)
+            || self
+                .lazy_resources
+                .keys()
+                .any(|type_id| self.type_name_of(*type_id) == type_name)
+    }
+}
+
+impl ResourceRegistry for BusSnapshot {
+    fn provides(&self, type_name: &str) -> bool {
+        self.get(type_name, None).is_some()
+    }
+}
+
+/// Application-level resources shared by every request Bus.
+///
+/// Pools, configuration and long-lived clients are built once into a
+/// `ResourceSet` and layered under each per-execution Bus with
+/// [`Bus::with_app`]. Cloning the set is cheap, and inserting into a clone
+/// does not affect Buses already built from the original.
+#[derive(Clone, Default)]
+pub struct ResourceSet {
+    entries: SharedEntries,
+    type_names: Arc<AHashMap<TypeId, &'static str>>,
+}
+
+impl ResourceSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a resource, replacing any previous one of the same type.
+    pub fn insert<T: Any + Send + Sync + 'static>(&mut self, resource: T) {
+        let type_id = TypeId::of::<T>();
+        Arc::make_mut(&mut self.type_names).insert(type_id, type_name::<T>());
+        Arc::make_mut(&mut self.entries).insert(type_id, Arc::new(resource));
+    }
+
+    /// Builder form of [`insert`](ResourceSet::insert).
+    pub fn with<T: Any + Send + Sync + 'static>(mut self, resource: T) -> Self {
+        self.insert(resource);
+        self
+    }
+
+    pub fn get<T: Any + Send + Sync + 'static>(&self) -> Option<&T> {
+        self.entries
+            .get(&TypeId::of::<T>())
+            .and_then(|resource| resource.downcast_ref::<T>())
+    }
+
+    pub fn contains<T: Any + Send + Sync + 'static>(&self) -> bool {
+        self.entries.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl std::fmt::Debug for ResourceSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&str> = self.type_names.values().copied().collect();
+        names.sort_unstable();
+        f.debug_struct("ResourceSet")
+            .field("resources", &names)
+            .finish()
+    }
+}
+
+impl ResourceRegistry for ResourceSet {
+    fn provides(&self, type_name: &str) -> bool {
+        self.type_names.values().any(|name| *name == type_name)
+    }
+}
+
 impl Default for Bus {
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl Drop for Bus {
+    fn drop(&mut self) {
+        if let Some(key) = self.registry_key {
+            bus_registry::forget(key);
+        }
+        let pending: Vec<&str> = self
+            .lifecycle
+            .iter()
+            .filter(|entry| self.resources.contains_key(&entry.type_id))
+            .map(|entry| entry.resource)
+            .collect();
+        if !pending.is_empty() {
+            tracing::warn!(
+                resources = ?pending,
+                "Bus dropped without shutdown(); attached resources were not shut down"
+            );
+        }
+    }
+}
+
+/// Startup and shutdown callbacks for a resource held on a [`Bus`].
+///
+/// Attach the resource with [`Bus::attach`] and call [`Bus::shutdown`] when
+/// the Bus is done, so pools and background clients can flush and close
+/// instead of being dropped mid-flight. Both callbacks default to no-ops.
+#[async_trait]
+pub trait ResourceLifecycle: Send + Sync + 'static {
+    /// Called once, just before the resource is inserted.
+    fn on_attach(&self) {}
+
+    /// Called by [`Bus::shutdown`] before the resource is dropped.
+    async fn on_shutdown(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: ResourceLifecycle> ResourceLifecycle for Arc<T> {
+    fn on_attach(&self) {
+        T::on_attach(self)
+    }
+
+    async fn on_shutdown(&self) -> Result<(), String> {
+        T::on_shutdown(self).await
+    }
+}
+
+/// Result of shutting down one resource in [`Bus::shutdown`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceShutdown {
+    pub resource: String,
+    pub error: Option<String>,
+}
+
+type ShutdownFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+type FactoryFn<T> =
+    Box<dyn Fn() -> Pin<Box<dyn Future<Output = Result<T, String>> + Send>> + Send + Sync>;
+
+/// A [`Bus::register_factory`] entry: the factory and the value it built.
+struct LazyResource<T> {
+    cell: tokio::sync::OnceCell<T>,
+    factory: FactoryFn<T>,
+}
+
+/// Type-erased view of a [`LazyResource`].
+trait LazySlot: Send + Sync {
+    fn value(&self) -> Option<&(dyn Any + Send + Sync)>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + Send + Sync> LazySlot for LazyResource<T> {
+    fn value(&self) -> Option<&(dyn Any + Send + Sync)> {
+        self.cell
+            .get()
+            .map(|value| value as &(dyn Any + Send + Sync))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+struct LifecycleEntry {
+    type_id: TypeId,
+    resource: &'static str,
+    shut_down: fn(Box<dyn Any + Send + Sync>) -> ShutdownFuture,
+}
+
+/// Local entries of a Bus set aside while a child scope is open.
+struct ScopeLayer {
+    resources: AHashMap<std::any::TypeId, Box<dyn Any + Send + Sync>>,
+    shared_resources: SharedEntries,
+    named_resources: NamedResources,
+}
+
+/// Entries a parallel fork can inherit. The map is copied on write while a
+/// fork still holds it, so forking never copies entries.
+type SharedEntries = Arc<AHashMap<TypeId, Arc<dyn Any + Send + Sync>>>;
+
+fn take_shared(
+    entries: &mut SharedEntries,
+    type_id: &TypeId,
+) -> Option<Arc<dyn Any + Send + Sync>> {
+    if entries.contains_key(type_id) {
+        Arc::make_mut(entries).remove(type_id)
+    } else {
+        None
+    }
+}
+
+/// Mutable access to a shared entry, unless a fork still holds it.
+fn shared_mut<'a>(
+    entries: &'a mut SharedEntries,
+    type_id: &TypeId,
+) -> Option<&'a mut (dyn Any + Send + Sync)> {
+    Arc::get_mut(entries)?
+        .get_mut(type_id)
+        .and_then(Arc::get_mut)
+}
+
+type NamedResources = AHashMap<TypeId, AHashMap<String, Box<dyn Any + Send + Sync>>>;
+
+impl ScopeLayer {
+    fn get(&self, type_id: &TypeId) -> Option<&(dyn Any + Send + Sync)> {
+        self.resources
+            .get(type_id)
+            .map(|resource| resource.as_ref())
+            .or_else(|| {
+                self.shared_resources
+                    .get(type_id)
+                    .map(|resource| resource.as_ref())
+            })
+    }
+}
+
+/// A child scope of a [`Bus`], opened with [`Bus::child_scope`].
+///
+/// Writes made through the scope are dropped with it unless promoted.
+pub struct BusScope<'a> {
+    bus: &'a mut Bus,
+}
+
+impl BusScope<'_> {
+    /// Move this scope's `T` into the enclosing scope so it outlives this one.
+    ///
+    /// Returns `false` if the scope itself holds no `T`, or if access is
+    /// denied by an active policy (logged via `tracing::error!`). A value
+    /// inserted with [`Bus::insert_shared`] stays shared after promotion.
+    pub fn promote<T: Any + Send + Sync + 'static>(&mut self) -> bool {
+        if let Err(err) = self.bus.ensure_access::<T>() {
+            tracing::error!("{err}");
+            return false;
+        }
+        let type_id = TypeId::of::<T>();
+        let Some(parent) = self.bus.outer_scopes.last_mut() else {
+            return false;
+        };
+        if let Some(resource) = self.bus.resources.remove(&type_id) {
+            take_shared(&mut parent.shared_resources, &type_id);
+            parent.resources.insert(type_id, resource);
+            return true;
+        }
+        if let Some(resource) = take_shared(&mut self.bus.shared_resources, &type_id) {
+            parent.resources.remove(&type_id);
+            Arc::make_mut(&mut parent.shared_resources).insert(type_id, resource);
+            return true;
+        }
+        false
+    }
+}
+
+impl std::ops::Deref for BusScope<'_> {
+    type Target = Bus;
+
+    fn deref(&self) -> &Self::Target {
+        self.bus
+    }
+}
+
+impl std::ops::DerefMut for BusScope<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.bus
+    }
+}
+
+impl Drop for BusScope<'_> {
+    fn drop(&mut self) {
+        if let Some(parent) = self.bus.outer_scopes.pop() {
+            self.bus.resources = parent.resources;
+            self.bus.shared_resources = parent.shared_resources;
+            self.bus.named_resources = parent.named_resources;
+            self.bus.publish_to_registry();
+        }
+    }
+}
+
+/// A temporary replacement of `T`, returned by [`Bus::override_scoped`].
+///
+/// Restores the previous `T` when dropped.
+pub struct BusOverride<'a, T: Any + Send + Sync + 'static> {
+    bus: &'a mut Bus,
+    previous: Option<Saved>,
+    _type: std::marker::PhantomData<fn() -> T>,
+}
+
+enum Saved {
+    Owned(Box<dyn Any + Send + Sync>),
+    Shared(Arc<dyn Any + Send + Sync>),
+}
+
+impl<T: Any + Send + Sync + 'static> std::ops::Deref for BusOverride<'_, T> {
+    type Target = Bus;
+
+    fn deref(&self) -> &Self::Target {
+        self.bus
+    }
+}
+
+impl<T: Any + Send + Sync + 'static> std::ops::DerefMut for BusOverride<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.bus
+    }
+}
+
+impl<T: Any + Send + Sync + 'static> Drop for BusOverride<'_, T> {
+    fn drop(&mut self) {
+        let type_id = TypeId::of::<T>();
+        self.bus.resources.remove(&type_id);
+        take_shared(&mut self.bus.shared_resources, &type_id);
+        match self.previous.take() {
+            Some(Saved::Owned(owned)) => {
+                self.bus.resources.insert(type_id, owned);
+            }
+            Some(Saved::Shared(shared)) => {
+                Arc::make_mut(&mut self.bus.shared_resources).insert(type_id, shared);
+            }
+            None => {}
+        }
+        self.bus.publish_to_registry();
+    }
+}
+
 /// Unique identifier for a connection (e.g., WebSocket connection).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ConnectionId(pub Uuid);
@@ -736,6 +1905,295 @@ mod tests {
         assert!(err.to_string().contains("DenyString"));
     }
 
+    #[test]
+    fn child_scope_reads_parent_and_drops_its_own_writes() {
+        let mut bus = Bus::new();
+        bus.insert(1u8);
+        bus.insert(String::from("request"));
+        {
+            let mut scope = bus.child_scope();
+            *scope.read_mut::<u8>().unwrap() += 1;
+            scope.insert(String::from("sub-flow"));
+            scope.insert(3u16);
+            scope.insert(4u32);
+            assert_eq!(scope.read::<String>().unwrap(), "sub-flow");
+            assert_eq!(scope.remove::<String>().as_deref(), Some("sub-flow"));
+            assert_eq!(scope.read::<String>().unwrap(), "request");
+            assert!(scope.remove::<String>().is_none());
+            {
+                let mut inner = scope.child_scope();
+                inner.insert(5u64);
+                assert!(inner.promote::<u64>());
+                assert!(!inner.promote::<u8>());
+            }
+            assert!(scope.promote::<u32>());
+            assert_eq!(scope.len(), 5);
+        }
+        assert_eq!(*bus.read::<u8>().unwrap(), 2);
+        assert_eq!(bus.read::<String>().unwrap(), "request");
+        assert!(!bus.has::<u16>());
+        assert!(!bus.has::<u64>());
+        assert_eq!(*bus.read::<u32>().unwrap(), 4);
+    }
+
+    #[test]
+    fn named_resources_hold_several_instances_of_one_type() {
+        let mut bus = Bus::new();
+        bus.insert(String::from("unnamed"));
+        bus.provide_named("primary_db", String::from("postgres://primary"));
+        bus.insert_named("analytics_db", String::from("postgres://analytics"));
+
+        assert_eq!(bus.read::<String>().unwrap(), "unnamed");
+        assert_eq!(
+            bus.read_named::<String>("analytics_db").unwrap(),
+            "postgres://analytics"
+        );
+        assert!(bus.read_named::<i32>("analytics_db").is_none());
+        assert!(matches!(
+            bus.get_named::<String>("cache"),
+            Err(BusAccessError::NotFound { .. })
+        ));
+        assert_eq!(bus.names_of::<String>(), vec!["analytics_db", "primary_db"]);
+        assert_eq!(bus.len(), 3);
+
+        {
+            let mut scope = bus.child_scope();
+            scope.insert_named("scratch_db", String::from("sqlite::memory:"));
+            scope
+                .read_named_mut::<String>("primary_db")
+                .unwrap()
+                .push_str("?sslmode=require");
+            assert!(scope.has_named::<String>("scratch_db"));
+            assert!(scope.remove_named::<String>("analytics_db").is_none());
+        }
+        assert!(!bus.has_named::<String>("scratch_db"));
+        assert_eq!(
+            bus.read_named::<String>("primary_db").unwrap(),
+            "postgres://primary?sslmode=require"
+        );
+
+        bus.set_access_policy(
+            "NoStrings",
+            Some(BusAccessPolicy::deny_only(vec![BusTypeRef::of::<String>()])),
+        );
+        assert!(bus.read_named::<String>("primary_db").is_none());
+        bus.clear_access_policy();
+
+        assert_eq!(
+            bus.remove_named::<String>("analytics_db").as_deref(),
+            Some("postgres://analytics")
+        );
+        assert_eq!(bus.names_of::<String>(), vec!["primary_db"]);
+    }
+
+    #[test]
+    fn snapshot_diff_shows_what_changed() {
+        #[derive(serde::Serialize)]
+        struct Cart(u32);
+        struct Secret;
+        crate::bus_snapshot::register_snapshot_type::<Cart>();
+
+        let mut bus = Bus::new();
+        bus.insert(Cart(1));
+        bus.insert(Secret);
+        let before = bus.snapshot();
+        assert_eq!(before.entries.len(), 2);
+        let secret = before.get(type_name::<Secret>(), None).unwrap();
+        assert_eq!(secret.value, None);
+
+        bus.insert(Cart(2));
+        bus.remove::<Secret>();
+        bus.insert_named("retries", 3u8);
+        let diff = before.diff(&bus.snapshot());
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].type_name, "u8");
+        assert_eq!(diff.added[0].name.as_deref(), Some("retries"));
+        assert_eq!(diff.removed[0].type_name, type_name::<Secret>());
+        assert_eq!(diff.changed[0].before.value, Some(serde_json::json!(1)));
+        assert_eq!(diff.changed[0].after.value, Some(serde_json::json!(2)));
+
+        bus.set_access_policy(
+            "NoCart",
+            Some(BusAccessPolicy::deny_only(vec![BusTypeRef::of::<Cart>()])),
+        );
+        assert_eq!(
+            bus.snapshot().get(type_name::<Cart>(), None).unwrap().value,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn shutdown_runs_attached_resources_in_reverse_order() {
+        use std::sync::Mutex;
+
+        struct Pool(Arc<Mutex<Vec<&'static str>>>);
+        struct Client(Arc<Mutex<Vec<&'static str>>>);
+
+        #[async_trait]
+        impl ResourceLifecycle for Pool {
+            fn on_attach(&self) {
+                self.0.lock().unwrap().push("pool attached");
+            }
+
+            async fn on_shutdown(&self) -> Result<(), String> {
+                self.0.lock().unwrap().push("pool closed");
+                Ok(())
+            }
+        }
+
+        #[async_trait]
+        impl ResourceLifecycle for Client {
+            async fn on_shutdown(&self) -> Result<(), String> {
+                self.0.lock().unwrap().push("client flushed");
+                Err("2 events lost".into())
+            }
+        }
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = Bus::new();
+        bus.attach(Pool(log.clone()));
+        bus.attach(Arc::new(Client(log.clone())));
+        assert!(bus.has::<Pool>());
+
+        let report = bus.shutdown().await;
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["pool attached", "client flushed", "pool closed"]
+        );
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].error.as_deref(), Some("2 events lost"));
+        assert_eq!(report[1].error, None);
+        assert!(bus.is_empty());
+        assert!(bus.shutdown().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn factory_builds_once_on_first_resolve() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Cache(usize);
+
+        let builds = Arc::new(AtomicUsize::new(0));
+        let counter = builds.clone();
+        let mut bus = Bus::new();
+        bus.register_factory(move || {
+            let counter = counter.clone();
+            async move {
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                Ok(Cache(counter.fetch_add(1, Ordering::SeqCst) + 1))
+            }
+        });
+        assert!(!bus.has::<Cache>());
+        assert_eq!(builds.load(Ordering::SeqCst), 0);
+
+        let left = bus.fork_for_parallel();
+        let right = bus.fork_for_parallel();
+        let (a, b) = tokio::join!(left.resolve::<Cache>(), right.resolve::<Cache>());
+        assert_eq!((a.unwrap().0, b.unwrap().0), (1, 1));
+        assert_eq!(bus.read::<Cache>().unwrap().0, 1);
+        assert!(bus.get_mut::<Cache>().is_err());
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+
+        bus.register_factory::<u8, _, _>(|| async { Err("connection refused".to_string()) });
+        let err = bus.resolve::<u8>().await.unwrap_err();
+        assert!(err.to_string().contains("connection refused"));
+        assert!(matches!(
+            bus.resolve::<u16>().await,
+            Err(BusAccessError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn forks_share_layers_and_copy_on_write() {
+        let mut bus = Bus::new();
+        bus.insert_shared(1u8);
+        bus.insert_shared(String::from("config"));
+        bus.insert(7u64);
+
+        let fork = bus.fork_for_parallel();
+        assert!(Arc::ptr_eq(
+            &bus.shared_resources,
+            &fork.inherited_resources[0]
+        ));
+        let grandchild = fork.fork_for_parallel();
+        assert_eq!(grandchild.inherited_resources.len(), 1);
+        assert!(grandchild.read::<u64>().is_none());
+
+        // The parent's writes after forking are invisible to the fork.
+        bus.insert_shared(2u8);
+        assert_eq!(*fork.read::<u8>().unwrap(), 1);
+        assert_eq!(*bus.read::<u8>().unwrap(), 2);
+        // String is still aliased by the fork, so the parent cannot mutate it.
+        assert!(bus.get_mut::<String>().is_err());
+        drop((fork, grandchild));
+        bus.get_mut::<String>().unwrap().push_str("-v2");
+        assert_eq!(bus.read::<String>().unwrap(), "config-v2");
+    }
+
+    #[test]
+    fn app_resources_are_shared_under_request_buses() {
+        struct DbPool(&'static str);
+
+        let app = ResourceSet::new()
+            .with(DbPool("postgres://primary"))
+            .with(String::from("config"));
+        let mut first = Bus::with_app(&app);
+        let second = Bus::with_app(&app);
+        assert!(Arc::ptr_eq(
+            &first.inherited_resources[0],
+            &second.inherited_resources[0]
+        ));
+        assert_eq!(first.read::<DbPool>().unwrap().0, "postgres://primary");
+        assert!(first.get_mut::<DbPool>().is_err());
+        assert!(first.remove::<DbPool>().is_none());
+        assert!(first.provides(type_name::<DbPool>()));
+
+        // Request entries shadow application ones without touching them.
+        first.insert(String::from("override"));
+        assert_eq!(first.read::<String>().unwrap(), "override");
+        assert_eq!(second.read::<String>().unwrap(), "config");
+
+        let fork = first.fork_for_parallel();
+        assert_eq!(fork.read::<DbPool>().unwrap().0, "postgres://primary");
+
+        let mut later = app.clone();
+        later.insert(7u8);
+        assert!(!first.has::<u8>());
+        assert_eq!(Bus::with_app(&later).read::<u8>(), Some(&7));
+    }
+
+    #[test]
+    fn override_scoped_restores_previous_entry() {
+        let mut bus = Bus::new();
+        bus.insert_shared(String::from("prod"));
+        {
+            let mut guard = bus.override_scoped(String::from("test"));
+            assert_eq!(guard.read::<String>().unwrap(), "test");
+            // Writes through the guard are discarded with the override.
+            guard.insert(String::from("test-2"));
+            let nested = guard.override_scoped(5u8);
+            assert_eq!(nested.read::<String>().unwrap(), "test-2");
+        }
+        assert!(!bus.has::<u8>());
+        assert_eq!(bus.read::<String>().unwrap(), "prod");
+        // The restored entry is still shared with forks.
+        let fork = bus.fork_for_parallel();
+        assert_eq!(fork.read::<String>().unwrap(), "prod");
+    }
+
+    #[test]
+    fn optional_reads_fall_back_without_recording_a_miss() {
+        let mut bus = Bus::new();
+        assert_eq!(bus.read_or(10u16), 10);
+        assert_eq!(bus.read_opt_cloned::<String>(), None);
+        assert!(bus.take_missing_resource().is_none());
+
+        bus.insert(3u16);
+        bus.insert(String::from("eu-west"));
+        assert_eq!(bus.read_or(10u16), 3);
+        assert_eq!(bus.read_opt_cloned::<String>().as_deref(), Some("eu-west"));
+    }
+
     #[test]
     fn test_connection_bus() {
         let id = ConnectionId::new();
@@ -748,14 +2206,21 @@ mod tests {
     fn provide_and_require_round_trip() {
         let mut bus = Bus::new();
         bus.provide(42i32);
-        assert_eq!(*bus.require::<i32>(), 42);
+        assert_eq!(*bus.require::<i32>().unwrap(), 42);
+        assert!(bus.take_missing_resource().is_none());
     }
 
     #[test]
-    #[should_panic(expected = "required resource")]
-    fn require_panics_with_helpful_message_when_missing() {
-        let bus = Bus::new();
-        let _ = bus.require::<String>();
+    fn require_reports_missing_type_and_node() {
+        let mut bus = Bus::new();
+        bus.set_current_node(Some("node-1".to_string()));
+        let missing = bus.require::<String>().unwrap_err();
+        assert_eq!(missing.resource, type_name::<String>());
+        assert_eq!(missing.node_id.as_deref(), Some("node-1"));
+        assert_eq!(missing.category(), MISSING_RESOURCE);
+        assert!(missing.to_string().contains("required by node node-1"));
+        assert_eq!(bus.take_missing_resource(), Some(missing));
+        assert!(bus.take_missing_resource().is_none());
     }
 
     #[test]
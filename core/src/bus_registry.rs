@@ -0,0 +1,231 @@
+//! Process-wide view of the resources held by live Buses.
+//!
+//! Tracking is off by default and costs nothing until
+//! [`enable_tracking`] is called. From then on, every new [`Bus`](crate::Bus)
+//! reports the type names it can see whenever its contents change, and is
+//! forgotten when dropped. [`resource_report`] aggregates those reports so
+//! that a "missing resource" fault can be traced to the Buses that do, or do
+//! not, hold a type. The Inspector serves the report at `/resources`.
+//!
+//! Only type names and names are recorded, never values.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Buses are spread over this many independently locked shards, so that
+/// Buses written on different threads rarely contend.
+const SHARDS: usize = 16;
+
+type Shard = Mutex<HashMap<u64, LiveBus>>;
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+static NEXT_KEY: AtomicU64 = AtomicU64::new(1);
+static LIVE_BUSES: OnceLock<[Shard; SHARDS]> = OnceLock::new();
+
+/// Start tracking Buses created from now on. Existing Buses stay untracked.
+pub fn enable_tracking() {
+    TRACKING.store(true, Ordering::Relaxed);
+}
+
+/// Stop tracking new Buses and forget the ones already tracked.
+pub fn disable_tracking() {
+    TRACKING.store(false, Ordering::Relaxed);
+    if let Some(shards) = LIVE_BUSES.get() {
+        for shard in shards {
+            shard.lock().clear();
+        }
+    }
+}
+
+pub fn is_tracking() -> bool {
+    TRACKING.load(Ordering::Relaxed)
+}
+
+struct LiveBus {
+    bus_id: Uuid,
+    created_at: Instant,
+    /// First time each `(type name, name)` entry was seen on this Bus.
+    resources: HashMap<(&'static str, Option<String>), Instant>,
+}
+
+fn shard(shards: &[Shard; SHARDS], key: u64) -> &Shard {
+    &shards[key as usize % SHARDS]
+}
+
+/// Register a new Bus if tracking is on, returning its registry key.
+pub(crate) fn register(bus_id: Uuid) -> Option<u64> {
+    if !is_tracking() {
+        return None;
+    }
+    let key = NEXT_KEY.fetch_add(1, Ordering::Relaxed);
+    let shards = LIVE_BUSES.get_or_init(|| std::array::from_fn(|_| Mutex::default()));
+    shard(shards, key).lock().insert(
+        key,
+        LiveBus {
+            bus_id,
+            created_at: Instant::now(),
+            resources: HashMap::new(),
+        },
+    );
+    Some(key)
+}
+
+/// Replace the entries recorded for a Bus, keeping the age of those it
+/// still holds.
+pub(crate) fn publish<'a>(
+    key: u64,
+    entries: impl IntoIterator<Item = (&'static str, Option<&'a str>)>,
+) {
+    let Some(shards) = LIVE_BUSES.get() else {
+        return;
+    };
+    let mut live = shard(shards, key).lock();
+    let Some(bus) = live.get_mut(&key) else {
+        return;
+    };
+    let now = Instant::now();
+    let mut previous = std::mem::take(&mut bus.resources);
+    for (type_name, name) in entries {
+        let entry = (type_name, name.map(str::to_string));
+        let seen = previous.remove(&entry).unwrap_or(now);
+        bus.resources.insert(entry, seen);
+    }
+}
+
+pub(crate) fn forget(key: u64) {
+    if let Some(shards) = LIVE_BUSES.get() {
+        shard(shards, key).lock().remove(&key);
+    }
+}
+
+/// One resource type, or one named entry, across all tracked Buses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResourceUsage {
+    pub type_name: String,
+    /// Set for entries added with [`Bus::insert_named`](crate::Bus::insert_named).
+    pub name: Option<String>,
+    /// Number of live Buses holding the entry.
+    pub buses: usize,
+    /// Age of the longest-held instance.
+    pub oldest_ms: u64,
+}
+
+/// One tracked Bus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LiveBusSummary {
+    pub bus_id: Uuid,
+    pub age_ms: u64,
+    pub resources: usize,
+}
+
+/// Snapshot of every tracked Bus, returned by [`resource_report`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BusResourceReport {
+    pub tracking: bool,
+    /// Sorted by type name, then name.
+    pub resources: Vec<ResourceUsage>,
+    /// Oldest first.
+    pub buses: Vec<LiveBusSummary>,
+}
+
+/// Aggregate the resources held by the Buses tracked right now.
+pub fn resource_report() -> BusResourceReport {
+    let mut report = BusResourceReport {
+        tracking: is_tracking(),
+        ..Default::default()
+    };
+    let Some(shards) = LIVE_BUSES.get() else {
+        return report;
+    };
+    let now = Instant::now();
+    let age_ms = |since: Instant| now.duration_since(since).as_millis() as u64;
+    let mut usage: HashMap<(&'static str, Option<String>), ResourceUsage> = HashMap::new();
+    for shard in shards {
+        for bus in shard.lock().values() {
+            report.buses.push(LiveBusSummary {
+                bus_id: bus.bus_id,
+                age_ms: age_ms(bus.created_at),
+                resources: bus.resources.len(),
+            });
+            for ((type_name, name), seen) in &bus.resources {
+                let entry =
+                    usage
+                        .entry((type_name, name.clone()))
+                        .or_insert_with(|| ResourceUsage {
+                            type_name: type_name.to_string(),
+                            name: name.clone(),
+                            buses: 0,
+                            oldest_ms: 0,
+                        });
+                entry.buses += 1;
+                entry.oldest_ms = entry.oldest_ms.max(age_ms(*seen));
+            }
+        }
+    }
+    report.resources = usage.into_values().collect();
+    report
+        .resources
+        .sort_by(|a, b| (&a.type_name, &a.name).cmp(&(&b.type_name, &b.name)));
+    report
+        .buses
+        .sort_by_key(|bus| std::cmp::Reverse(bus.age_ms));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bus;
+
+    /// Turns tracking back off when the test ends, even if it fails.
+    struct TrackingGuard;
+
+    impl Drop for TrackingGuard {
+        fn drop(&mut self) {
+            disable_tracking();
+        }
+    }
+
+    #[test]
+    fn report_follows_live_bus_contents() {
+        enable_tracking();
+        let _tracking = TrackingGuard;
+        let mut bus = Bus::new();
+        bus.insert(7u32);
+        bus.insert_named("primary", String::from("postgres://primary"));
+        let fork = bus.fork_for_parallel();
+
+        let held_by = |report: &BusResourceReport, id: Uuid| {
+            report
+                .buses
+                .iter()
+                .find(|b| b.bus_id == id)
+                .map(|b| b.resources)
+        };
+        let report = resource_report();
+        assert!(report.tracking);
+        assert_eq!(held_by(&report, bus.id), Some(2));
+        assert_eq!(held_by(&report, fork.id), Some(0));
+        assert!(
+            report
+                .resources
+                .iter()
+                .any(|usage| usage.type_name == "u32")
+        );
+        assert!(report.resources.iter().any(|usage| {
+            usage.name.as_deref() == Some("primary") && usage.type_name.contains("String")
+        }));
+
+        bus.remove::<u32>();
+        assert_eq!(held_by(&resource_report(), bus.id), Some(1));
+        let id = bus.id;
+        drop(bus);
+        assert_eq!(held_by(&resource_report(), id), None);
+    }
+}
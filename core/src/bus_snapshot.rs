@@ -0,0 +1,220 @@
+//! Point-in-time views of a [`Bus`](crate::bus::Bus) for debugging.
+//!
+//! [`Bus::snapshot`](crate::bus::Bus::snapshot) lists what the Bus holds by
+//! type name (and name, for named entries). The Bus cannot tell on its own
+//! whether a type is serializable, so values are only included for types
+//! registered with [`register_snapshot_type`]. Two snapshots taken around a
+//! node give what the node added, removed or changed:
+//!
+//! ```rust
+//! # use ranvier_core::Bus;
+//! # use ranvier_core::bus_snapshot::register_snapshot_type;
+//! register_snapshot_type::<u32>();
+//! let mut bus = Bus::new();
+//! bus.insert(1u32);
+//! let before = bus.snapshot();
+//! bus.insert(2u32);
+//! bus.insert_named("audit", String::from("on"));
+//! let diff = before.diff(&bus.snapshot());
+//! assert_eq!(diff.added[0].name.as_deref(), Some("audit"));
+//! assert_eq!(diff.changed[0].after.value, Some(serde_json::json!(2)));
+//! ```
+//!
+//! Types registered with [`register_replay_type`] can also be read back:
+//! [`BusSnapshot::restore`] rebuilds a Bus from a snapshot, which is how
+//! `ReplayEngine` recreates the resources a captured run saw.
+
+use crate::bus::Bus;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::any::{Any, TypeId, type_name};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{OnceLock, RwLock};
+
+type SnapshotFn = fn(&(dyn Any + Send + Sync)) -> Option<Value>;
+type RestoreFn = fn(&mut Bus, Option<&str>, &Value) -> bool;
+
+static RESTORE_TYPES: OnceLock<RwLock<HashMap<&'static str, RestoreFn>>> = OnceLock::new();
+
+fn restore_types() -> &'static RwLock<HashMap<&'static str, RestoreFn>> {
+    RESTORE_TYPES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+static SNAPSHOT_TYPES: OnceLock<RwLock<HashMap<TypeId, SnapshotFn>>> = OnceLock::new();
+
+fn snapshot_types() -> &'static RwLock<HashMap<TypeId, SnapshotFn>> {
+    SNAPSHOT_TYPES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Include values of `T` in Bus snapshots taken anywhere in this process.
+pub fn register_snapshot_type<T>()
+where
+    T: Serialize + Send + Sync + 'static,
+{
+    fn to_value<T: Serialize + 'static>(resource: &(dyn Any + Send + Sync)) -> Option<Value> {
+        serde_json::to_value(resource.downcast_ref::<T>()?).ok()
+    }
+    let to_value: SnapshotFn = to_value::<T>;
+    match snapshot_types().write() {
+        Ok(mut types) => types.insert(TypeId::of::<T>(), to_value),
+        Err(poisoned) => poisoned.into_inner().insert(TypeId::of::<T>(), to_value),
+    };
+}
+
+/// Like [`register_snapshot_type`], and let [`BusSnapshot::restore`] rebuild
+/// `T` from its captured value.
+pub fn register_replay_type<T>()
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    fn restore<T: DeserializeOwned + Send + Sync + 'static>(
+        bus: &mut Bus,
+        name: Option<&str>,
+        value: &Value,
+    ) -> bool {
+        let Ok(resource) = T::deserialize(value) else {
+            return false;
+        };
+        match name {
+            Some(name) => bus.insert_named(name, resource),
+            None => bus.insert(resource),
+        }
+        true
+    }
+    register_snapshot_type::<T>();
+    let restore: RestoreFn = restore::<T>;
+    match restore_types().write() {
+        Ok(mut types) => types.insert(type_name::<T>(), restore),
+        Err(poisoned) => poisoned.into_inner().insert(type_name::<T>(), restore),
+    };
+}
+
+pub(crate) fn snapshot_value(type_id: TypeId, resource: &(dyn Any + Send + Sync)) -> Option<Value> {
+    let to_value = match snapshot_types().read() {
+        Ok(types) => types.get(&type_id).copied(),
+        Err(poisoned) => poisoned.into_inner().get(&type_id).copied(),
+    }?;
+    to_value(resource)
+}
+
+/// One entry of a [`BusSnapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BusSnapshotEntry {
+    pub type_name: String,
+    /// Set for entries inserted with [`Bus::insert_named`](crate::bus::Bus::insert_named).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The serialized value, for types registered with [`register_snapshot_type`]
+    /// that the active access policy does not deny.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+}
+
+/// The entries a Bus held at one point, sorted by type name and name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BusSnapshot {
+    pub entries: Vec<BusSnapshotEntry>,
+}
+
+impl BusSnapshot {
+    pub(crate) fn new(mut entries: Vec<BusSnapshotEntry>) -> Self {
+        entries.sort_by(|a, b| (&a.type_name, &a.name).cmp(&(&b.type_name, &b.name)));
+        Self { entries }
+    }
+
+    pub fn get(&self, type_name: &str, name: Option<&str>) -> Option<&BusSnapshotEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.type_name == type_name && entry.name.as_deref() == name)
+    }
+
+    /// Rebuild a Bus holding the entries that can be restored.
+    ///
+    /// See [`restore_into`](BusSnapshot::restore_into).
+    pub fn restore(&self) -> Bus {
+        let mut bus = Bus::new();
+        self.restore_into(&mut bus);
+        bus
+    }
+
+    /// Insert the captured values into `bus`, returning the entries that were
+    /// skipped: those captured without a value, those whose type was not
+    /// registered with [`register_replay_type`] in this process, and those
+    /// whose value no longer deserializes.
+    pub fn restore_into(&self, bus: &mut Bus) -> Vec<&BusSnapshotEntry> {
+        let types = match restore_types().read() {
+            Ok(types) => types.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        self.entries
+            .iter()
+            .filter(|entry| {
+                let restored = entry.value.as_ref().is_some_and(|value| {
+                    types
+                        .get(entry.type_name.as_str())
+                        .is_some_and(|restore| restore(bus, entry.name.as_deref(), value))
+                });
+                !restored
+            })
+            .collect()
+    }
+
+    /// What changed between this snapshot and `after`.
+    ///
+    /// An entry counts as changed when both snapshots hold a value for it and
+    /// the values differ; entries without values only show up as added or
+    /// removed.
+    pub fn diff(&self, after: &BusSnapshot) -> BusDiff {
+        let before: BTreeMap<_, _> = self.entries.iter().map(|e| (e.key(), e)).collect();
+        let after: BTreeMap<_, _> = after.entries.iter().map(|e| (e.key(), e)).collect();
+        let mut diff = BusDiff::default();
+        for (key, entry) in &after {
+            match before.get(key) {
+                None => diff.added.push((*entry).clone()),
+                Some(old) if old.value.is_some() && entry.value.is_some() && old != entry => {
+                    diff.changed.push(BusEntryChange {
+                        before: (*old).clone(),
+                        after: (*entry).clone(),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = before
+            .iter()
+            .filter(|(key, _)| !after.contains_key(*key))
+            .map(|(_, entry)| (*entry).clone())
+            .collect();
+        diff
+    }
+}
+
+impl BusSnapshotEntry {
+    fn key(&self) -> (&str, Option<&str>) {
+        (&self.type_name, self.name.as_deref())
+    }
+}
+
+/// An entry present in both snapshots with different values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BusEntryChange {
+    pub before: BusSnapshotEntry,
+    pub after: BusSnapshotEntry,
+}
+
+/// Result of [`BusSnapshot::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BusDiff {
+    pub added: Vec<BusSnapshotEntry>,
+    pub removed: Vec<BusSnapshotEntry>,
+    pub changed: Vec<BusEntryChange>,
+}
+
+impl BusDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
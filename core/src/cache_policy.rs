@@ -0,0 +1,141 @@
+//! Declarative HTTP cacheability for circuits.
+//!
+//! A [`CachePolicy`] is stored on the circuit's [`Schematic`](crate::schematic::Schematic)
+//! so the ingress serving the circuit can emit `Cache-Control` and `Vary`
+//! headers without per-handler code:
+//!
+//! ```rust,ignore
+//! let catalog = Axon::new("Catalog")
+//!     .then(ListProducts)
+//!     .with_cache_policy(
+//!         CachePolicy::public(Duration::from_secs(60))
+//!             .stale_while_revalidate(Duration::from_secs(300))
+//!             .vary("Accept-Language"),
+//!     );
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Who may store a cached response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CacheVisibility {
+    /// Browsers, CDNs and shared caches.
+    Public,
+    /// The requesting client only.
+    Private,
+}
+
+/// Cacheability of a circuit's successful responses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CachePolicy {
+    pub visibility: CacheVisibility,
+    pub max_age_secs: u64,
+    /// Overrides `max_age_secs` for shared caches (`s-maxage`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared_max_age_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_while_revalidate_secs: Option<u64>,
+    /// Request headers that select between cached variants.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vary: Vec<String>,
+}
+
+impl CachePolicy {
+    pub fn public(max_age: Duration) -> Self {
+        Self::new(CacheVisibility::Public, max_age)
+    }
+
+    pub fn private(max_age: Duration) -> Self {
+        Self::new(CacheVisibility::Private, max_age)
+    }
+
+    fn new(visibility: CacheVisibility, max_age: Duration) -> Self {
+        Self {
+            visibility,
+            max_age_secs: max_age.as_secs(),
+            shared_max_age_secs: None,
+            stale_while_revalidate_secs: None,
+            vary: Vec::new(),
+        }
+    }
+
+    pub fn shared_max_age(mut self, max_age: Duration) -> Self {
+        self.shared_max_age_secs = Some(max_age.as_secs());
+        self
+    }
+
+    pub fn stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.stale_while_revalidate_secs = Some(window.as_secs());
+        self
+    }
+
+    /// Add a request header to `Vary`; duplicates are ignored case-insensitively.
+    pub fn vary(mut self, header: impl Into<String>) -> Self {
+        let header = header.into();
+        if !self.vary.iter().any(|h| h.eq_ignore_ascii_case(&header)) {
+            self.vary.push(header);
+        }
+        self
+    }
+
+    /// Freshness lifetime for a shared cache.
+    pub fn shared_ttl(&self) -> Duration {
+        Duration::from_secs(self.shared_max_age_secs.unwrap_or(self.max_age_secs))
+    }
+
+    /// Value for the `Cache-Control` response header.
+    pub fn cache_control(&self) -> String {
+        let mut directives = vec![
+            match self.visibility {
+                CacheVisibility::Public => "public".to_string(),
+                CacheVisibility::Private => "private".to_string(),
+            },
+            format!("max-age={}", self.max_age_secs),
+        ];
+        if let Some(secs) = self.shared_max_age_secs {
+            directives.push(format!("s-maxage={secs}"));
+        }
+        if let Some(secs) = self.stale_while_revalidate_secs {
+            directives.push(format!("stale-while-revalidate={secs}"));
+        }
+        directives.join(", ")
+    }
+
+    /// Value for the `Vary` response header, if any headers are listed.
+    pub fn vary_header(&self) -> Option<String> {
+        (!self.vary.is_empty()).then(|| self.vary.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_cache_control_and_vary() {
+        let policy = CachePolicy::public(Duration::from_secs(60))
+            .shared_max_age(Duration::from_secs(600))
+            .stale_while_revalidate(Duration::from_secs(30))
+            .vary("Accept-Language")
+            .vary("accept-language")
+            .vary("Accept-Encoding");
+
+        assert_eq!(
+            policy.cache_control(),
+            "public, max-age=60, s-maxage=600, stale-while-revalidate=30"
+        );
+        assert_eq!(
+            policy.vary_header().as_deref(),
+            Some("Accept-Language, Accept-Encoding")
+        );
+        assert_eq!(policy.shared_ttl(), Duration::from_secs(600));
+        assert_eq!(
+            CachePolicy::private(Duration::from_secs(5)).cache_control(),
+            "private, max-age=5"
+        );
+    }
+}
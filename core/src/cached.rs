@@ -0,0 +1,396 @@
+//! # Cached: Memoization Decorator
+//!
+//! [`Cached`] wraps a pure or idempotent Transition and reuses its `Next`
+//! value for inputs that map to the same cache key:
+//!
+//! ```rust,ignore
+//! let axon = Axon::new("Pricing")
+//!     .then(
+//!         Cached::new(LoadTenantConfig)
+//!             .key(|req: &PriceRequest| req.tenant_id.clone())
+//!             .ttl(Duration::from_secs(300))
+//!             .backend(Arc::new(InMemoryLruCache::new(1_000))),
+//!     )
+//!     .then(ComputePrice);
+//! ```
+//!
+//! Values are stored as JSON through a [`CacheBackend`], so a shared backend
+//! (e.g. Redis) can be plugged in later without touching the circuit. Keys
+//! are namespaced by the inner Transition's label. Every lookup is recorded
+//! as a [`TimelineEvent::CacheLookup`] when a [`Timeline`] is in the Bus.
+//! Only `Next` outcomes are cached, and backend errors fall back to running
+//! the inner Transition.
+
+use crate::bus::{Bus, BusAccessPolicy};
+use crate::outcome::Outcome;
+use crate::redaction::RedactionRules;
+use crate::retry::current_node_id;
+use crate::timeline::{Timeline, TimelineEvent, Timestamp};
+use crate::transition::{SideEffect, Transition};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+type CacheKeyFn<From> = Arc<dyn Fn(&From) -> String + Send + Sync>;
+
+/// Storage for [`Cached`] values.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<Value>, String>;
+
+    /// Store `value`, expiring it after `ttl` when one is given.
+    async fn set(&self, key: &str, value: Value, ttl: Option<Duration>) -> Result<(), String>;
+
+    async fn invalidate(&self, key: &str) -> Result<(), String>;
+}
+
+struct LruEntry {
+    value: Value,
+    expires_at: Option<Instant>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct LruState {
+    entries: HashMap<String, LruEntry>,
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl LruState {
+    fn touch(&mut self, key: &str) {
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.recency.remove(&entry.last_used);
+            entry.last_used = self.tick;
+            self.recency.insert(self.tick, key.to_string());
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.last_used);
+        }
+    }
+}
+
+/// Process-local [`CacheBackend`] that evicts the least recently used entry
+/// once `capacity` is reached.
+pub struct InMemoryLruCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+impl InMemoryLruCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl CacheBackend for InMemoryLruCache {
+    async fn get(&self, key: &str) -> Result<Option<Value>, String> {
+        let mut state = self.state.lock();
+        let expired = match state.entries.get(key) {
+            None => return Ok(None),
+            Some(entry) => entry.expires_at.is_some_and(|at| at <= Instant::now()),
+        };
+        if expired {
+            state.remove(key);
+            return Ok(None);
+        }
+        state.touch(key);
+        Ok(state.entries.get(key).map(|entry| entry.value.clone()))
+    }
+
+    async fn set(&self, key: &str, value: Value, ttl: Option<Duration>) -> Result<(), String> {
+        let mut state = self.state.lock();
+        state.remove(key);
+        while state.entries.len() >= self.capacity {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+        state.entries.insert(
+            key.to_string(),
+            LruEntry {
+                value,
+                expires_at: ttl.map(|ttl| Instant::now() + ttl),
+                last_used: 0,
+            },
+        );
+        state.touch(key);
+        Ok(())
+    }
+
+    async fn invalidate(&self, key: &str) -> Result<(), String> {
+        self.state.lock().remove(key);
+        Ok(())
+    }
+}
+
+/// A wrapper Transition that memoizes the `Next` value of the inner
+/// Transition per cache key.
+pub struct Cached<T, From> {
+    inner: T,
+    key: CacheKeyFn<From>,
+    ttl: Option<Duration>,
+    backend: Arc<dyn CacheBackend>,
+    _input: PhantomData<fn(From)>,
+}
+
+impl<T: Clone, From> Clone for Cached<T, From> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            key: self.key.clone(),
+            ttl: self.ttl,
+            backend: self.backend.clone(),
+            _input: PhantomData,
+        }
+    }
+}
+
+impl<T, From: Serialize> Cached<T, From> {
+    /// Defaults: the input's JSON encoding as the key, no TTL, and a
+    /// private 1024-entry [`InMemoryLruCache`].
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            key: Arc::new(|input: &From| {
+                serde_json::to_string(input).unwrap_or_else(|_| String::new())
+            }),
+            ttl: None,
+            backend: Arc::new(InMemoryLruCache::new(1024)),
+            _input: PhantomData,
+        }
+    }
+}
+
+impl<T, From> Cached<T, From> {
+    /// Derive the cache key from the input.
+    pub fn key<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&From) -> String + Send + Sync + 'static,
+    {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// Expire cached values `ttl` after they were stored.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Store values in `backend`, e.g. to share one cache between circuits.
+    pub fn backend(mut self, backend: Arc<dyn CacheBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+}
+
+#[async_trait]
+impl<T, From, To> Transition<From, To> for Cached<T, From>
+where
+    T: Transition<From, To>,
+    From: Send + Sync + 'static,
+    To: Serialize + DeserializeOwned + Send + 'static,
+{
+    type Error = T::Error;
+    type Resources = T::Resources;
+
+    async fn run(
+        &self,
+        input: From,
+        resources: &Self::Resources,
+        bus: &mut Bus,
+    ) -> Outcome<To, Self::Error> {
+        let label = self.inner.label();
+        let key = format!("{label}:{}", (self.key)(&input));
+
+        let cached = match self.backend.get(&key).await {
+            Ok(value) => value.and_then(|value| serde_json::from_value::<To>(value).ok()),
+            Err(error) => {
+                tracing::warn!(node = %label, key = %key, error = %error, "Cache lookup failed");
+                None
+            }
+        };
+        let mut logged_key = key.clone();
+        if let Some(rules) = bus.read::<RedactionRules>() {
+            rules.redact_text(&mut logged_key);
+        }
+        if let Some(timeline) = bus.read_mut::<Timeline>() {
+            let node_id = current_node_id(timeline).unwrap_or_else(|| label.clone());
+            timeline.push(TimelineEvent::CacheLookup {
+                node_id,
+                key: logged_key,
+                hit: cached.is_some(),
+                timestamp: Timestamp::now(),
+                trace: None,
+            });
+        }
+        if let Some(value) = cached {
+            tracing::debug!(node = %label, key = %key, "Cache hit");
+            return Outcome::Next(value);
+        }
+
+        let outcome = self.inner.run(input, resources, bus).await;
+        if let Outcome::Next(value) = &outcome {
+            match serde_json::to_value(value) {
+                Ok(value) => {
+                    if let Err(error) = self.backend.set(&key, value, self.ttl).await {
+                        tracing::warn!(node = %label, key = %key, error = %error, "Cache store failed");
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(node = %label, error = %error, "Cached value is not serializable");
+                }
+            }
+        }
+        outcome
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.inner.description()
+    }
+
+    fn position(&self) -> Option<(f32, f32)> {
+        self.inner.position()
+    }
+
+    fn bus_access_policy(&self) -> Option<BusAccessPolicy> {
+        self.inner.bus_access_policy()
+    }
+
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        self.inner.input_schema()
+    }
+
+    fn describe(&self) -> Vec<SideEffect> {
+        self.inner.describe()
+    }
+
+    fn estimate(
+        &self,
+        state: &From,
+        resources: &Self::Resources,
+        bus: &Bus,
+    ) -> Option<Outcome<To, Self::Error>> {
+        self.inner.estimate(state, resources, bus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Clone)]
+    struct LoadConfig {
+        calls: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl Transition<u32, String> for LoadConfig {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, tenant: u32, _res: &(), _bus: &mut Bus) -> Outcome<String, String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if tenant == 0 {
+                Outcome::Fault("unknown tenant".to_string())
+            } else {
+                Outcome::Next(format!("config-{tenant}"))
+            }
+        }
+    }
+
+    fn lookups(bus: &Bus) -> Vec<bool> {
+        bus.read::<Timeline>()
+            .unwrap()
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                TimelineEvent::CacheLookup { hit, .. } => Some(*hit),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn reuses_next_values_and_records_hits_and_misses() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let cached = Cached::new(LoadConfig {
+            calls: calls.clone(),
+        });
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+
+        for tenant in [7, 7, 8, 0, 0] {
+            let _ = cached.run(tenant, &(), &mut bus).await;
+        }
+
+        // Faults are not cached, so tenant 0 runs twice.
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        assert_eq!(lookups(&bus), [false, true, false, false, false]);
+        let outcome = cached.run(7, &(), &mut bus).await;
+        assert!(matches!(outcome, Outcome::Next(ref config) if config == "config-7"));
+    }
+
+    #[tokio::test]
+    async fn custom_keys_share_entries_and_ttl_expires_them() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let cached = Cached::new(LoadConfig {
+            calls: calls.clone(),
+        })
+        .key(|tenant: &u32| (tenant % 2).to_string())
+        .ttl(Duration::from_millis(20));
+
+        let _ = cached.run(1, &(), &mut Bus::new()).await;
+        let outcome = cached.run(3, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Next(ref config) if config == "config-1"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let outcome = cached.run(3, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Next(ref config) if config == "config-3"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn lru_evicts_the_least_recently_used_entry() {
+        let cache = InMemoryLruCache::new(2);
+        cache.set("a", Value::from(1), None).await.unwrap();
+        cache.set("b", Value::from(2), None).await.unwrap();
+        cache.get("a").await.unwrap();
+        cache.set("c", Value::from(3), None).await.unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("a").await.unwrap(), Some(Value::from(1)));
+        assert_eq!(cache.get("b").await.unwrap(), None);
+        cache.invalidate("a").await.unwrap();
+        assert_eq!(cache.get("a").await.unwrap(), None);
+    }
+}
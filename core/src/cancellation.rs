@@ -17,6 +17,7 @@ use tokio::time::Instant;
 /// The fixed, secret-free source of a cancellation request.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CancellationReason {
     /// A managed process or server is shutting down.
     OperatorShutdown,
@@ -274,6 +275,11 @@ impl CancellationToken {
         selected
     }
 
+    /// The earliest deadline of this token and its ancestors, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.closest_deadline()
+    }
+
     fn closest_deadline(&self) -> Option<Instant> {
         let mut cursor = Some(self.clone());
         let mut selected: Option<Instant> = None;
@@ -0,0 +1,397 @@
+//! Payload capture formats for Timeline state snapshots.
+//!
+//! A [`CapturePolicy`] decides whether a circuit records its input and output
+//! payloads into the Timeline, and in which [`CaptureFormat`]. JSON keeps
+//! captures human-readable; MessagePack and CBOR trade readability for size.
+//!
+//! The chosen format is written to [`TimelineHeader`](crate::timeline::TimelineHeader)
+//! and carried by every [`CapturedPayload`], so replay and inspection tools
+//! decode captures without being told the format out of band.
+//!
+//! A [`NodeCapturePolicy`] on the Bus additionally records the input and
+//! output of individual nodes. Only allowlisted nodes are captured, and
+//! payloads above the policy's size cap are kept as a truncated preview.
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// Wire format used to encode captured payloads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CaptureFormat {
+    /// Plain JSON, stored inline and readable as-is.
+    #[default]
+    Json,
+    /// MessagePack, stored base64-encoded.
+    #[serde(rename = "msgpack")]
+    MessagePack,
+    /// CBOR (RFC 8949), stored base64-encoded.
+    Cbor,
+}
+
+impl CaptureFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::MessagePack => "msgpack",
+            Self::Cbor => "cbor",
+        }
+    }
+}
+
+impl std::fmt::Display for CaptureFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for CaptureFormat {
+    type Err = CaptureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "msgpack" | "messagepack" => Ok(Self::MessagePack),
+            "cbor" => Ok(Self::Cbor),
+            other => Err(CaptureError::UnknownFormat(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CaptureError {
+    #[error("unknown capture format '{0}'")]
+    UnknownFormat(String),
+    #[error("failed to encode {format} capture: {message}")]
+    Encode {
+        format: CaptureFormat,
+        message: String,
+    },
+    #[error("failed to decode {format} capture: {message}")]
+    Decode {
+        format: CaptureFormat,
+        message: String,
+    },
+    #[error("{format} capture was truncated from {original_len} bytes and cannot be decoded")]
+    Truncated {
+        format: CaptureFormat,
+        original_len: usize,
+    },
+}
+
+/// Per-circuit input/output capture settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapturePolicy {
+    pub format: CaptureFormat,
+    /// Record the circuit input on entry.
+    pub input: bool,
+    /// Record the circuit output when it completes with `Outcome::Next`.
+    pub output: bool,
+    /// Record a [`BusSnapshot`](crate::bus_snapshot::BusSnapshot) of the Bus
+    /// on entry, so replay can rebuild the resources the run saw.
+    #[serde(default)]
+    pub bus: bool,
+}
+
+impl Default for CapturePolicy {
+    fn default() -> Self {
+        Self::new(CaptureFormat::Json)
+    }
+}
+
+impl CapturePolicy {
+    /// Capture both input and output in `format`.
+    pub const fn new(format: CaptureFormat) -> Self {
+        Self {
+            format,
+            input: true,
+            output: true,
+            bus: false,
+        }
+    }
+
+    pub const fn inputs_only(mut self) -> Self {
+        self.output = false;
+        self
+    }
+
+    pub const fn outputs_only(mut self) -> Self {
+        self.input = false;
+        self
+    }
+
+    /// Also capture the Bus on entry.
+    ///
+    /// Values are recorded only for types registered with
+    /// [`register_replay_type`](crate::bus_snapshot::register_replay_type)
+    /// (or [`register_snapshot_type`](crate::bus_snapshot::register_snapshot_type));
+    /// other entries are listed by type name.
+    pub const fn with_bus(mut self) -> Self {
+        self.bus = true;
+        self
+    }
+}
+
+/// Per-node input/output capture settings.
+///
+/// Insert into the Bus before execution. Nodes are matched by id or label;
+/// an empty allowlist captures nothing. Captures are only recorded when a
+/// Timeline is collected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeCapturePolicy {
+    pub format: CaptureFormat,
+    /// Ids or labels of the nodes whose payloads are recorded.
+    pub nodes: Vec<String>,
+    /// Largest encoded payload recorded in full; larger ones are truncated.
+    pub max_bytes: usize,
+}
+
+impl NodeCapturePolicy {
+    /// Default cap on the encoded size of a node payload.
+    pub const DEFAULT_MAX_BYTES: usize = 4096;
+
+    /// Capture no nodes yet, in `format`, with the default size cap.
+    pub fn new(format: CaptureFormat) -> Self {
+        Self {
+            format,
+            nodes: Vec::new(),
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+        }
+    }
+
+    /// Add a node, by id or label, to the allowlist.
+    pub fn node(mut self, node: impl Into<String>) -> Self {
+        self.nodes.push(node.into());
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Whether the node with this id and label is allowlisted.
+    pub fn captures(&self, node_id: &str, node_label: &str) -> bool {
+        self.nodes
+            .iter()
+            .any(|node| node == node_id || node == node_label)
+    }
+
+    /// Encode `value`, truncated to the size cap.
+    pub fn encode<T: Serialize + ?Sized>(
+        &self,
+        value: &T,
+    ) -> Result<CapturedPayload, CaptureError> {
+        let payload = CapturedPayload::encode(self.format, value)?;
+        Ok(if payload.encoded_len() > self.max_bytes {
+            payload.truncate(self.max_bytes)
+        } else {
+            payload
+        })
+    }
+}
+
+/// An encoded payload snapshot.
+///
+/// JSON captures hold the value inline; binary formats hold a base64 string.
+/// A truncated capture holds the beginning of the value's JSON text instead
+/// and can only be displayed, not decoded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CapturedPayload {
+    pub format: CaptureFormat,
+    pub data: serde_json::Value,
+    /// Encoded size of the full payload, when it was truncated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated_from: Option<usize>,
+}
+
+impl CapturedPayload {
+    pub fn encode<T: Serialize + ?Sized>(
+        format: CaptureFormat,
+        value: &T,
+    ) -> Result<Self, CaptureError> {
+        let encode_err = |message: String| CaptureError::Encode { format, message };
+        let data = match format {
+            CaptureFormat::Json => {
+                serde_json::to_value(value).map_err(|e| encode_err(e.to_string()))?
+            }
+            CaptureFormat::MessagePack => {
+                let bytes =
+                    rmp_serde::to_vec_named(value).map_err(|e| encode_err(e.to_string()))?;
+                serde_json::Value::String(BASE64.encode(bytes))
+            }
+            CaptureFormat::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|e| encode_err(e.to_string()))?;
+                serde_json::Value::String(BASE64.encode(bytes))
+            }
+        };
+        Ok(Self {
+            format,
+            data,
+            truncated_from: None,
+        })
+    }
+
+    /// Replace the payload with a preview of at most `max_bytes` of its JSON
+    /// text.
+    pub fn truncate(self, max_bytes: usize) -> Self {
+        if self.truncated_from.is_some() {
+            return self;
+        }
+        let original_len = self.encoded_len();
+        let mut preview = match self.to_json() {
+            Ok(json) => json.to_string(),
+            Err(_) => String::new(),
+        };
+        let mut end = max_bytes.min(preview.len());
+        while !preview.is_char_boundary(end) {
+            end -= 1;
+        }
+        preview.truncate(end);
+        Self {
+            format: self.format,
+            data: serde_json::Value::String(preview),
+            truncated_from: Some(original_len),
+        }
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_from.is_some()
+    }
+
+    /// Decode into a typed value.
+    ///
+    /// Fails with [`CaptureError::Truncated`] for truncated captures.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, CaptureError> {
+        if let Some(original_len) = self.truncated_from {
+            return Err(CaptureError::Truncated {
+                format: self.format,
+                original_len,
+            });
+        }
+        let decode_err = |message: String| CaptureError::Decode {
+            format: self.format,
+            message,
+        };
+        match self.format {
+            CaptureFormat::Json => {
+                serde_json::from_value(self.data.clone()).map_err(|e| decode_err(e.to_string()))
+            }
+            CaptureFormat::MessagePack => {
+                rmp_serde::from_slice(&self.binary()?).map_err(|e| decode_err(e.to_string()))
+            }
+            CaptureFormat::Cbor => ciborium::from_reader(self.binary()?.as_slice())
+                .map_err(|e| decode_err(e.to_string())),
+        }
+    }
+
+    /// Decode into JSON regardless of the stored format (for display).
+    ///
+    /// A truncated capture yields its preview as a JSON string.
+    pub fn to_json(&self) -> Result<serde_json::Value, CaptureError> {
+        match self.format {
+            _ if self.is_truncated() => Ok(self.data.clone()),
+            CaptureFormat::Json => Ok(self.data.clone()),
+            _ => self.decode(),
+        }
+    }
+
+    /// Size of the encoded payload in bytes.
+    pub fn encoded_len(&self) -> usize {
+        match self.format {
+            _ if self.is_truncated() => self.data.as_str().map_or(0, str::len),
+            CaptureFormat::Json => self.data.to_string().len(),
+            _ => self.binary().map(|bytes| bytes.len()).unwrap_or(0),
+        }
+    }
+
+    fn binary(&self) -> Result<Vec<u8>, CaptureError> {
+        let decode_err = |message: String| CaptureError::Decode {
+            format: self.format,
+            message,
+        };
+        let encoded = self
+            .data
+            .as_str()
+            .ok_or_else(|| decode_err("expected base64 string".to_string()))?;
+        BASE64
+            .decode(encoded)
+            .map_err(|e| decode_err(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        id: u64,
+        items: Vec<String>,
+    }
+
+    fn order() -> Order {
+        Order {
+            id: 7,
+            items: vec!["book".into(), "pen".into()],
+        }
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        for format in [
+            CaptureFormat::Json,
+            CaptureFormat::MessagePack,
+            CaptureFormat::Cbor,
+        ] {
+            let captured = CapturedPayload::encode(format, &order()).unwrap();
+            assert_eq!(captured.decode::<Order>().unwrap(), order(), "{format}");
+            assert_eq!(captured.to_json().unwrap()["items"][1], "pen", "{format}");
+        }
+    }
+
+    #[test]
+    fn binary_formats_are_smaller_than_json() {
+        let json = CapturedPayload::encode(CaptureFormat::Json, &order()).unwrap();
+        let msgpack = CapturedPayload::encode(CaptureFormat::MessagePack, &order()).unwrap();
+        assert!(msgpack.data.is_string());
+        assert!(msgpack.encoded_len() < json.encoded_len());
+    }
+
+    #[test]
+    fn node_policy_truncates_payloads_over_the_cap() {
+        let policy = NodeCapturePolicy::new(CaptureFormat::Cbor)
+            .node("charge")
+            .with_max_bytes(10);
+        assert!(policy.captures("n1", "charge"));
+        assert!(!policy.captures("n2", "ship"));
+
+        let captured = policy.encode(&order()).unwrap();
+        assert!(captured.is_truncated());
+        assert_eq!(captured.to_json().unwrap(), "{\"id\":7,\"i");
+        assert!(matches!(
+            captured.decode::<Order>(),
+            Err(CaptureError::Truncated { .. })
+        ));
+
+        let full = policy.with_max_bytes(1024).encode(&order()).unwrap();
+        assert_eq!(full.decode::<Order>().unwrap(), order());
+    }
+
+    #[test]
+    fn format_names_parse() {
+        assert_eq!(
+            "MessagePack".parse::<CaptureFormat>().unwrap(),
+            CaptureFormat::MessagePack
+        );
+        assert_eq!(
+            serde_json::to_value(CaptureFormat::MessagePack).unwrap(),
+            "msgpack"
+        );
+        assert!("yaml".parse::<CaptureFormat>().is_err());
+    }
+}
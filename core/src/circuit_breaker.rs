@@ -0,0 +1,57 @@
+//! Observable circuit-breaker state.
+//!
+//! Breakers (e.g. `ranvier_runtime::CircuitBreaker`) publish their live
+//! state through [`CircuitBreakerStateReader`], which the Inspector serves at
+//! `/breakers`.
+
+use crate::timeline::Timestamp;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Position of a breaker in its Closed → Open → HalfOpen cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls pass through and outcomes are counted.
+    Closed,
+    /// Calls fail fast until the cool-down elapses.
+    Open,
+    /// One trial call is let through to decide whether to close again.
+    HalfOpen,
+}
+
+/// Snapshot of one breaker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitBreakerState {
+    /// Label of the guarded node.
+    pub node: String,
+    pub state: CircuitState,
+    /// Calls in the current window.
+    pub calls: u32,
+    /// Faults in the current window.
+    pub failures: u32,
+    pub failure_threshold: f64,
+    pub cool_down_ms: u64,
+    /// When the breaker last opened, while it is Open or HalfOpen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opened_at: Option<Timestamp>,
+    /// Calls rejected while Open, since the breaker was created.
+    pub rejected: u64,
+}
+
+impl CircuitBreakerState {
+    /// Fraction of faulted calls in the current window (0.0 when empty).
+    pub fn failure_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            f64::from(self.failures) / f64::from(self.calls)
+        }
+    }
+}
+
+/// Read-side interface for observing breaker state (served at Inspector `/breakers`).
+#[async_trait]
+pub trait CircuitBreakerStateReader: Send + Sync {
+    async fn circuit_breaker_state(&self) -> CircuitBreakerState;
+}
@@ -30,6 +30,16 @@
 //! service_name  = "my-api"
 //! sample_ratio  = 1.0
 //!
+//! [rate_limits]
+//! default = { max_requests = 100, window_ms = 60000 }
+//! tiers.free = { max_requests = 30, window_ms = 60000 }
+//! routes."/api/orders".default = { max_requests = 10, window_ms = 1000 }
+//!
+//! [nodes.charge_card]
+//! max_retries = 5
+//! timeout_ms = 2000
+//! backoff = { strategy = "exponential", initial_ms = 100, multiplier = 2.0, max_ms = 5000 }
+//!
 //! [profile.prod]
 //! logging.format = "json"
 //! logging.level = "warn"
@@ -37,6 +47,8 @@
 //! inspector.enabled = false
 //! ```
 
+use crate::node_policy::NodePoliciesConfig;
+use crate::rate_limit::RateLimitsConfig;
 use crate::runtime_policy::{
     ComponentPolicyReport, PolicyComponent, PolicyField, PolicyObservation, PolicyValue,
     RuntimeProfile, RuntimeProfileSource, StartupPolicyCode, StartupPolicyError,
@@ -58,6 +70,10 @@ pub struct RanvierConfig {
     pub tls: TlsConfig,
     pub inspector: InspectorConfig,
     pub telemetry: TelemetryConfig,
+    /// Per-route and per-tenant-tier rate limits (see [`crate::rate_limit`]).
+    pub rate_limits: RateLimitsConfig,
+    /// Retry/backoff/timeout overrides keyed by node label (see [`crate::node_policy`]).
+    pub nodes: NodePoliciesConfig,
     /// Profile-specific overrides keyed by profile name (e.g., "dev", "staging", "prod").
     #[serde(default)]
     pub profile: HashMap<String, ProfileOverride>,
@@ -953,6 +969,12 @@ impl RanvierConfig {
         if self.tls.enabled && self.tls.key_path.trim().is_empty() {
             violations.push(invalid_config_value(PolicyField::TLS_KEY_CONFIGURED));
         }
+        if !self.rate_limits.invalid_rules().is_empty() {
+            violations.push(invalid_config_value(PolicyField::RATE_LIMITS));
+        }
+        if !self.nodes.invalid_rules().is_empty() {
+            violations.push(invalid_config_value(PolicyField::NODES));
+        }
         violations
     }
 
@@ -1326,6 +1348,50 @@ port = 4000
         assert_eq!(cfg.logging.level, cfg2.logging.level);
     }
 
+    #[test]
+    fn parse_rate_limits_toml() {
+        let toml_str = r#"
+[rate_limits]
+default = { max_requests = 100, window_ms = 60000 }
+
+[rate_limits.routes."/api/orders"]
+tiers.premium = { max_requests = 50, window_ms = 1000 }
+"#;
+        let cfg: RanvierConfig = toml::from_str(toml_str).unwrap();
+        let hit = cfg
+            .rate_limits
+            .resolve(Some("/api/orders"), Some("premium"))
+            .unwrap();
+        assert_eq!(hit.rule.max_requests, 50);
+        assert!(cfg.value_violations().is_empty());
+    }
+
+    #[test]
+    fn zero_rate_limit_is_a_value_violation() {
+        let toml_str = r#"
+[rate_limits.tiers.free]
+max_requests = 0
+window_ms = 1000
+"#;
+        let cfg: RanvierConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.value_violations().len(), 1);
+    }
+
+    #[test]
+    fn parse_node_policies_toml() {
+        let toml_str = r#"
+[nodes.charge_card]
+max_retries = 5
+timeout_ms = 2000
+
+[nodes.bad]
+timeout_ms = 0
+"#;
+        let cfg: RanvierConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.nodes.get("charge_card").unwrap().max_retries, Some(5));
+        assert_eq!(cfg.value_violations().len(), 1);
+    }
+
     #[test]
     fn telemetry_defaults() {
         let cfg = RanvierConfig::default();
@@ -0,0 +1,106 @@
+//! Request deadlines carried in the Bus.
+//!
+//! A [`Deadline`] is the point in time after which the caller no longer
+//! waits for a result. Ingress adapters insert one from their request
+//! timeout, and the Axon executor checks it before entering each node: once
+//! it has passed, the next node is not run and the execution faults with
+//! [`DeadlineExceeded`] instead.
+//!
+//! ```rust,ignore
+//! let mut bus = Bus::new();
+//! bus.insert(Deadline::after(Duration::from_millis(800)));
+//! let outcome = axon.execute(request, &resources, &mut bus).await;
+//! ```
+//!
+//! Transitions can read the remaining budget to size their own calls, e.g.
+//! `bus.read::<Deadline>().map(Deadline::remaining)`.
+
+use serde::Serialize;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Fault category recorded when a node is skipped because the deadline passed.
+pub const DEADLINE_EXCEEDED: &str = "deadline_exceeded";
+
+/// Overall time budget of one execution. Deadlines order by time, so
+/// `a.min(b)` is the tighter of two budgets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Deadline(Instant);
+
+impl Deadline {
+    pub fn at(instant: Instant) -> Self {
+        Self(instant)
+    }
+
+    pub fn after(budget: Duration) -> Self {
+        Self(Instant::now() + budget)
+    }
+
+    pub fn instant(&self) -> Instant {
+        self.0
+    }
+
+    /// Time left, or `Duration::ZERO` once the deadline has passed.
+    pub fn remaining(&self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.0
+    }
+}
+
+/// A node that was not started because the execution's deadline had passed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeadlineExceeded {
+    pub node_label: String,
+    /// How long ago the deadline passed when the node was reached.
+    pub overrun_ms: u64,
+}
+
+impl DeadlineExceeded {
+    pub fn category(&self) -> &'static str {
+        DEADLINE_EXCEEDED
+    }
+}
+
+impl std::fmt::Display for DeadlineExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{DEADLINE_EXCEEDED}: '{}' skipped, deadline passed {}ms ago",
+            self.node_label, self.overrun_ms
+        )
+    }
+}
+
+impl std::error::Error for DeadlineExceeded {}
+
+impl From<DeadlineExceeded> for String {
+    fn from(exceeded: DeadlineExceeded) -> Self {
+        exceeded.to_string()
+    }
+}
+
+impl From<DeadlineExceeded> for crate::error::RanvierError {
+    fn from(exceeded: DeadlineExceeded) -> Self {
+        Self::internal(exceeded.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_is_zero_once_expired_and_min_picks_the_tighter_budget() {
+        let loose = Deadline::after(Duration::from_secs(60));
+        assert!(!loose.is_expired());
+        assert!(loose.remaining() > Duration::from_secs(59));
+
+        let passed = Deadline::after(Duration::ZERO);
+        assert!(passed.is_expired());
+        assert_eq!(passed.remaining(), Duration::ZERO);
+        assert_eq!(loose.min(passed), passed);
+    }
+}
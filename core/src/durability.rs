@@ -0,0 +1,290 @@
+//! Shared storage contract for durable executions.
+//!
+//! Suspension, checkpoints and idempotency all persist the same two things:
+//! a *run* (one execution of a circuit, with its input, status and final
+//! output) and the *steps* it went through (a node and the state it left
+//! behind). [`DurabilityStore`] is that contract, so a single backend can
+//! serve all of them. `ranvier-runtime` adapts it to each feature's store
+//! and ships a Postgres implementation behind `persistence-postgres`.
+
+use crate::timeline::Timestamp;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Lifecycle of a durable run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Running,
+    Suspended,
+    Completed,
+    Failed,
+}
+
+impl RunStatus {
+    /// Running and suspended runs still have work left.
+    pub fn is_pending(self) -> bool {
+        matches!(self, Self::Running | Self::Suspended)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Suspended => "suspended",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "running" => Some(Self::Running),
+            "suspended" => Some(Self::Suspended),
+            "completed" => Some(Self::Completed),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One execution of a circuit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DurableRun {
+    pub run_id: String,
+    pub circuit: String,
+    pub status: RunStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<Value>,
+    /// Digest of the input, for detecting a run id reused with other input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_digest: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+    pub started_at: Timestamp,
+    pub updated_at: Timestamp,
+    /// How long a running run holds its id, counted from `updated_at`. Once
+    /// it has run out, [`DurabilityStore::insert_run`] may replace the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_ms: Option<u64>,
+}
+
+impl DurableRun {
+    pub fn new(run_id: impl Into<String>, circuit: impl Into<String>, status: RunStatus) -> Self {
+        let now = Timestamp::now();
+        Self {
+            run_id: run_id.into(),
+            circuit: circuit.into(),
+            status,
+            input: None,
+            input_digest: None,
+            output: None,
+            started_at: now,
+            updated_at: now,
+            lease_ms: None,
+        }
+    }
+
+    /// A running run whose lease ran out before `now`.
+    pub fn lease_expired(&self, now: Timestamp) -> bool {
+        self.status == RunStatus::Running
+            && self.lease_ms.is_some_and(|lease_ms| {
+                now.duration_since(self.updated_at).as_millis() > u128::from(lease_ms)
+            })
+    }
+}
+
+/// State left behind by one node of a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DurableStep {
+    pub run_id: String,
+    pub step: u64,
+    pub node_id: String,
+    pub node_label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<Value>,
+    pub recorded_at: Timestamp,
+}
+
+/// Backend for durable runs and their steps.
+#[async_trait]
+pub trait DurabilityStore: Send + Sync {
+    /// Insert a run or replace the stored one with the same id.
+    async fn save_run(&self, run: DurableRun) -> Result<(), String>;
+
+    /// Insert a run unless one with the same id exists, atomically: when
+    /// several callers insert the same id at once, only one of them gets
+    /// `true`. A running run whose [lease](DurableRun::lease_ms) has expired
+    /// does not count and is replaced.
+    async fn insert_run(&self, run: DurableRun) -> Result<bool, String>;
+
+    /// Append a step to its run.
+    async fn save_step(&self, step: DurableStep) -> Result<(), String>;
+
+    async fn load_run(&self, run_id: &str) -> Result<Option<DurableRun>, String>;
+
+    /// Steps of a run, in the order they were saved.
+    async fn load_steps(&self, run_id: &str) -> Result<Vec<DurableStep>, String>;
+
+    /// Running and suspended runs, oldest first.
+    async fn load_pending_runs(&self) -> Result<Vec<DurableRun>, String>;
+
+    /// Remove a run together with its steps.
+    async fn delete_run(&self, run_id: &str) -> Result<(), String>;
+
+    /// Replace a run and all of its steps in one atomic write.
+    async fn replace_run(&self, run: DurableRun, steps: Vec<DurableStep>) -> Result<(), String>;
+
+    /// Remove a run with its steps and return them, atomically: when several
+    /// callers take the same run at once, only one of them gets it.
+    async fn take_run(
+        &self,
+        run_id: &str,
+    ) -> Result<Option<(DurableRun, Vec<DurableStep>)>, String>;
+}
+
+/// Process-local [`DurabilityStore`] for tests and single-process tooling.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryDurabilityStore {
+    inner: Arc<Mutex<InMemoryRuns>>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryRuns {
+    runs: HashMap<String, DurableRun>,
+    steps: HashMap<String, Vec<DurableStep>>,
+}
+
+impl InMemoryDurabilityStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl DurabilityStore for InMemoryDurabilityStore {
+    async fn save_run(&self, run: DurableRun) -> Result<(), String> {
+        self.inner.lock().runs.insert(run.run_id.clone(), run);
+        Ok(())
+    }
+
+    async fn insert_run(&self, run: DurableRun) -> Result<bool, String> {
+        let mut inner = self.inner.lock();
+        if let Some(existing) = inner.runs.get(&run.run_id)
+            && !existing.lease_expired(Timestamp::now())
+        {
+            return Ok(false);
+        }
+        inner.runs.insert(run.run_id.clone(), run);
+        Ok(true)
+    }
+
+    async fn save_step(&self, step: DurableStep) -> Result<(), String> {
+        self.inner
+            .lock()
+            .steps
+            .entry(step.run_id.clone())
+            .or_default()
+            .push(step);
+        Ok(())
+    }
+
+    async fn load_run(&self, run_id: &str) -> Result<Option<DurableRun>, String> {
+        Ok(self.inner.lock().runs.get(run_id).cloned())
+    }
+
+    async fn load_steps(&self, run_id: &str) -> Result<Vec<DurableStep>, String> {
+        Ok(self
+            .inner
+            .lock()
+            .steps
+            .get(run_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn load_pending_runs(&self) -> Result<Vec<DurableRun>, String> {
+        let mut runs: Vec<_> = self
+            .inner
+            .lock()
+            .runs
+            .values()
+            .filter(|run| run.status.is_pending())
+            .cloned()
+            .collect();
+        runs.sort_by_key(|run| run.started_at);
+        Ok(runs)
+    }
+
+    async fn delete_run(&self, run_id: &str) -> Result<(), String> {
+        let mut inner = self.inner.lock();
+        inner.runs.remove(run_id);
+        inner.steps.remove(run_id);
+        Ok(())
+    }
+
+    async fn replace_run(&self, run: DurableRun, steps: Vec<DurableStep>) -> Result<(), String> {
+        let mut inner = self.inner.lock();
+        inner.steps.insert(run.run_id.clone(), steps);
+        inner.runs.insert(run.run_id.clone(), run);
+        Ok(())
+    }
+
+    async fn take_run(
+        &self,
+        run_id: &str,
+    ) -> Result<Option<(DurableRun, Vec<DurableStep>)>, String> {
+        let mut inner = self.inner.lock();
+        let steps = inner.steps.remove(run_id).unwrap_or_default();
+        Ok(inner.runs.remove(run_id).map(|run| (run, steps)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn pending_runs_exclude_finished_ones_and_delete_drops_steps() {
+        let store = InMemoryDurabilityStore::new();
+        store
+            .save_run(DurableRun::new("a", "Orders", RunStatus::Running))
+            .await
+            .unwrap();
+        store
+            .save_run(DurableRun::new("b", "Orders", RunStatus::Suspended))
+            .await
+            .unwrap();
+        store
+            .save_run(DurableRun::new("c", "Orders", RunStatus::Completed))
+            .await
+            .unwrap();
+        store
+            .save_step(DurableStep {
+                run_id: "a".into(),
+                step: 1,
+                node_id: "n1".into(),
+                node_label: "Reserve".into(),
+                state: Some(serde_json::json!(1)),
+                recorded_at: Timestamp::now(),
+            })
+            .await
+            .unwrap();
+
+        let pending: Vec<_> = store
+            .load_pending_runs()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|run| run.run_id)
+            .collect();
+        assert_eq!(pending, vec!["a", "b"]);
+
+        store.delete_run("a").await.unwrap();
+        assert!(store.load_run("a").await.unwrap().is_none());
+        assert!(store.load_steps("a").await.unwrap().is_empty());
+    }
+}
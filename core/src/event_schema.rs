@@ -0,0 +1,440 @@
+//! Compatibility checks for emitted event payloads.
+//!
+//! Circuits declare the payload schema of each event they `Emit` (see
+//! `Axon::with_emitted_event_schema` in `ranvier-runtime`); the schemas end
+//! up in each node's [`StepMetadata::emits`](crate::metadata::StepMetadata::emits).
+//! A snapshot of those schemas is stored as one `<event_type>.json` file per
+//! event, and [`check_event_compatibility`] compares a new build against it:
+//!
+//! ```rust,ignore
+//! let old = EventSchemas::load_dir("old-schemas/".as_ref())?;
+//! let new = EventSchemas::from_schematic(&axon.schematic);
+//! let report = check_event_compatibility(&old, &new);
+//! if !report.is_compatible() {
+//!     eprintln!("{report}");
+//!     std::process::exit(1);
+//! }
+//! ```
+//!
+//! Changes are judged from the consumer's side: anything an existing consumer
+//! could fail on is breaking. That covers a removed event, a removed field, a
+//! field that is no longer required, a field that became nullable, and a type
+//! change other than narrowing (`number` → `integer`). Added events and fields
+//! are reported but compatible.
+
+use crate::schematic::Schematic;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+
+/// `$ref` chains deeper than this are treated as opaque.
+const MAX_REF_DEPTH: usize = 32;
+
+/// Payload JSON Schemas keyed by event type.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EventSchemas(pub BTreeMap<String, Value>);
+
+impl EventSchemas {
+    /// Collect the event schemas declared by every node of a schematic.
+    pub fn from_schematic(schematic: &Schematic) -> Self {
+        let mut events = BTreeMap::new();
+        for node in &schematic.nodes {
+            for (event_type, schema) in &node.metadata.emits {
+                events.insert(event_type.clone(), schema.clone());
+            }
+        }
+        Self(events)
+    }
+
+    /// Read every `<event_type>.json` file in `dir`.
+    pub fn load_dir(dir: &Path) -> anyhow::Result<Self> {
+        let mut events = BTreeMap::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(event_type) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let content = std::fs::read_to_string(&path)?;
+            let schema = serde_json::from_str(&content)
+                .map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))?;
+            events.insert(event_type.to_string(), schema);
+        }
+        Ok(Self(events))
+    }
+
+    /// Write one pretty-printed `<event_type>.json` file per event into `dir`.
+    pub fn write_dir(&self, dir: &Path) -> anyhow::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        for (event_type, schema) in &self.0 {
+            let json = serde_json::to_string_pretty(schema)?;
+            std::fs::write(dir.join(format!("{event_type}.json")), json + "\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Kind of difference between two versions of an event payload schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SchemaChangeKind {
+    EventRemoved,
+    EventAdded,
+    FieldRemoved,
+    FieldAdded,
+    /// A required field is now optional.
+    BecameOptional,
+    BecameNullable,
+    TypeChanged {
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+}
+
+/// One difference found by [`check_event_compatibility`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaChange {
+    pub event_type: String,
+    /// JSON path of the field, `$` for the payload root.
+    pub path: String,
+    #[serde(flatten)]
+    pub kind: SchemaChangeKind,
+    pub breaking: bool,
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = if self.breaking { "BREAKING" } else { "ok" };
+        write!(f, "{severity} {} {}: ", self.event_type, self.path)?;
+        match &self.kind {
+            SchemaChangeKind::EventRemoved => write!(f, "event removed"),
+            SchemaChangeKind::EventAdded => write!(f, "event added"),
+            SchemaChangeKind::FieldRemoved => write!(f, "field removed"),
+            SchemaChangeKind::FieldAdded => write!(f, "field added"),
+            SchemaChangeKind::BecameOptional => write!(f, "field is no longer required"),
+            SchemaChangeKind::BecameNullable => write!(f, "field became nullable"),
+            SchemaChangeKind::TypeChanged { from, to } => {
+                write!(f, "type changed {} -> {}", from.join("|"), to.join("|"))
+            }
+        }
+    }
+}
+
+/// Result of comparing two [`EventSchemas`] snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventCompatibilityReport {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl EventCompatibilityReport {
+    pub fn breaking(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.breaking().next().is_none()
+    }
+}
+
+impl fmt::Display for EventCompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare the payload schemas of a new build against a previous snapshot.
+pub fn check_event_compatibility(
+    old: &EventSchemas,
+    new: &EventSchemas,
+) -> EventCompatibilityReport {
+    let mut report = EventCompatibilityReport::default();
+    for (event_type, old_schema) in &old.0 {
+        let Some(new_schema) = new.0.get(event_type) else {
+            report.changes.push(SchemaChange {
+                event_type: event_type.clone(),
+                path: "$".into(),
+                kind: SchemaChangeKind::EventRemoved,
+                breaking: true,
+            });
+            continue;
+        };
+        let mut diff = SchemaDiff {
+            event_type,
+            old_root: old_schema,
+            new_root: new_schema,
+            changes: &mut report.changes,
+        };
+        diff.compare(old_schema, new_schema, "$", 0);
+    }
+    for event_type in new.0.keys().filter(|key| !old.0.contains_key(*key)) {
+        report.changes.push(SchemaChange {
+            event_type: event_type.clone(),
+            path: "$".into(),
+            kind: SchemaChangeKind::EventAdded,
+            breaking: false,
+        });
+    }
+    report
+}
+
+struct SchemaDiff<'a> {
+    event_type: &'a str,
+    old_root: &'a Value,
+    new_root: &'a Value,
+    changes: &'a mut Vec<SchemaChange>,
+}
+
+impl SchemaDiff<'_> {
+    fn push(&mut self, path: &str, kind: SchemaChangeKind, breaking: bool) {
+        self.changes.push(SchemaChange {
+            event_type: self.event_type.to_string(),
+            path: path.to_string(),
+            kind,
+            breaking,
+        });
+    }
+
+    fn compare(&mut self, old: &Value, new: &Value, path: &str, depth: usize) {
+        if depth > MAX_REF_DEPTH {
+            return;
+        }
+        let old = resolve(self.old_root, old);
+        let new = resolve(self.new_root, new);
+
+        let (old_types, old_nullable) = shape(self.old_root, old);
+        let (new_types, new_nullable) = shape(self.new_root, new);
+        if !old_types.is_empty() && !new_types.is_empty() && !narrows(&old_types, &new_types) {
+            self.push(
+                path,
+                SchemaChangeKind::TypeChanged {
+                    from: old_types.iter().cloned().collect(),
+                    to: new_types.iter().cloned().collect(),
+                },
+                true,
+            );
+            return;
+        }
+        if new_nullable && !old_nullable {
+            self.push(path, SchemaChangeKind::BecameNullable, true);
+        }
+
+        let old = non_null_variant(self.old_root, old);
+        let new = non_null_variant(self.new_root, new);
+
+        let old_props = properties(old);
+        let new_props = properties(new);
+        if old_props.is_some() || new_props.is_some() {
+            let old_props = old_props.cloned().unwrap_or_default();
+            let new_props = new_props.cloned().unwrap_or_default();
+            let old_required = required(old);
+            let new_required = required(new);
+            for (name, old_field) in &old_props {
+                let field_path = format!("{path}.{name}");
+                match new_props.get(name) {
+                    None => self.push(&field_path, SchemaChangeKind::FieldRemoved, true),
+                    Some(new_field) => {
+                        if old_required.contains(name.as_str())
+                            && !new_required.contains(name.as_str())
+                        {
+                            self.push(&field_path, SchemaChangeKind::BecameOptional, true);
+                        }
+                        self.compare(old_field, new_field, &field_path, depth + 1);
+                    }
+                }
+            }
+            for name in new_props
+                .keys()
+                .filter(|name| !old_props.contains_key(*name))
+            {
+                self.push(
+                    &format!("{path}.{name}"),
+                    SchemaChangeKind::FieldAdded,
+                    false,
+                );
+            }
+        }
+
+        if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+            self.compare(old_items, new_items, &format!("{path}[]"), depth + 1);
+        }
+    }
+}
+
+/// Follow local `$ref`s (`#/$defs/X`, `#/definitions/X`) within `root`.
+fn resolve<'a>(root: &'a Value, mut schema: &'a Value) -> &'a Value {
+    for _ in 0..MAX_REF_DEPTH {
+        let Some(reference) = schema.get("$ref").and_then(Value::as_str) else {
+            break;
+        };
+        let Some(target) = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+        else {
+            break;
+        };
+        schema = target;
+    }
+    schema
+}
+
+/// Non-null JSON types a schema admits, and whether it admits `null`.
+fn shape(root: &Value, schema: &Value) -> (BTreeSet<String>, bool) {
+    let mut types = BTreeSet::new();
+    let mut nullable = false;
+    match schema.get("type") {
+        Some(Value::String(name)) => {
+            types.insert(name.clone());
+        }
+        Some(Value::Array(names)) => {
+            types.extend(names.iter().filter_map(Value::as_str).map(str::to_string));
+        }
+        _ => {
+            for variant in variants(schema) {
+                let (variant_types, variant_nullable) = shape(root, resolve(root, variant));
+                types.extend(variant_types);
+                nullable |= variant_nullable;
+            }
+        }
+    }
+    nullable |= types.remove("null");
+    (types, nullable)
+}
+
+fn variants(schema: &Value) -> impl Iterator<Item = &Value> {
+    ["anyOf", "oneOf"]
+        .into_iter()
+        .filter_map(|key| schema.get(key).and_then(Value::as_array))
+        .flatten()
+}
+
+/// For `Option<T>`-style `anyOf: [T, null]`, the `T` branch.
+fn non_null_variant<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    let mut non_null = variants(schema)
+        .map(|variant| resolve(root, variant))
+        .filter(|variant| variant.get("type").and_then(Value::as_str) != Some("null"));
+    match (non_null.next(), non_null.next()) {
+        (Some(only), None) => only,
+        _ => schema,
+    }
+}
+
+fn properties(schema: &Value) -> Option<&serde_json::Map<String, Value>> {
+    schema.get("properties").and_then(Value::as_object)
+}
+
+fn required(schema: &Value) -> BTreeSet<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Every new type is one the old schema already admitted.
+fn narrows(old: &BTreeSet<String>, new: &BTreeSet<String>) -> bool {
+    new.iter()
+        .all(|ty| old.contains(ty) || (ty == "integer" && old.contains("number")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schemas(event: &str, schema: Value) -> EventSchemas {
+        EventSchemas(BTreeMap::from([(event.to_string(), schema)]))
+    }
+
+    #[test]
+    fn flags_removed_fields_and_type_changes() {
+        let old = schemas(
+            "order_placed",
+            json!({
+                "type": "object",
+                "required": ["id", "total", "customer"],
+                "properties": {
+                    "id": {"type": "string"},
+                    "total": {"type": "number"},
+                    "note": {"type": "string"},
+                    "customer": {"$ref": "#/$defs/Customer"}
+                },
+                "$defs": {
+                    "Customer": {
+                        "type": "object",
+                        "required": ["email"],
+                        "properties": {"email": {"type": "string"}}
+                    }
+                }
+            }),
+        );
+        let new = schemas(
+            "order_placed",
+            json!({
+                "type": "object",
+                "required": ["id", "total"],
+                "properties": {
+                    "id": {"type": "integer"},
+                    "total": {"type": "integer"},
+                    "customer": {"$ref": "#/$defs/Customer"},
+                    "currency": {"type": "string"}
+                },
+                "$defs": {
+                    "Customer": {
+                        "type": "object",
+                        "required": ["email"],
+                        "properties": {"email": {"type": ["string", "null"]}}
+                    }
+                }
+            }),
+        );
+
+        let report = check_event_compatibility(&old, &new);
+        assert!(!report.is_compatible());
+        let lines: Vec<String> = report.changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "BREAKING order_placed $.customer: field is no longer required",
+                "BREAKING order_placed $.customer.email: field became nullable",
+                "BREAKING order_placed $.id: type changed string -> integer",
+                "BREAKING order_placed $.note: field removed",
+                "ok order_placed $.currency: field added",
+            ]
+        );
+    }
+
+    #[test]
+    fn additive_changes_are_compatible() {
+        let old = schemas(
+            "shipped",
+            json!({"type": "object", "properties": {"items": {"type": "array", "items": {"type": "string"}}}}),
+        );
+        let mut new = schemas(
+            "shipped",
+            json!({"type": "object", "properties": {
+                "items": {"type": "array", "items": {"type": "string"}},
+                "carrier": {"type": "string"}
+            }}),
+        );
+        new.0.insert("delivered".into(), json!({"type": "object"}));
+
+        let report = check_event_compatibility(&old, &new);
+        assert!(report.is_compatible());
+        assert_eq!(report.changes.len(), 2);
+
+        let removed = check_event_compatibility(&new, &old);
+        assert!(
+            removed
+                .breaking()
+                .any(|c| c.kind == SchemaChangeKind::EventRemoved)
+        );
+    }
+}
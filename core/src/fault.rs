@@ -0,0 +1,207 @@
+//! Structured fault cause chains.
+//!
+//! `Outcome::Fault(E)` carries only the last error. The runtime additionally
+//! records a [`FaultCause`] for every faulting node into the [`FaultChain`]
+//! Bus resource. Each new cause adopts the previously recorded one as its
+//! underlying cause, so the chain survives recovery via
+//! [`Outcome::or_else`](crate::outcome::Outcome::or_else) and compensation:
+//! when a compensation step fails, its cause points back at the fault that
+//! triggered it.
+//!
+//! ```rust,ignore
+//! let outcome = axon.execute(input, &res, &mut bus).await;
+//! if let Some(chain) = bus.read::<FaultChain>().and_then(FaultChain::latest) {
+//!     eprintln!("{chain}");
+//!     // charge_card: "card declined" (after 3 retries)
+//!     //   caused by: reserve_stock: "warehouse offline"
+//! }
+//! ```
+
+use crate::timeline::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A failed attempt that was retried before the node gave up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FaultRetry {
+    /// 1-based attempt number that faulted.
+    pub attempt: u32,
+    pub error: String,
+    /// Delay before the next attempt.
+    pub backoff_ms: u64,
+    pub timestamp: Timestamp,
+}
+
+/// One node's fault, with the faults that led to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FaultCause {
+    pub node_id: String,
+    pub node_label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_index: Option<u64>,
+    /// Machine-readable fault class, e.g. `contract_violation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Debug rendering of the node's error value.
+    pub error: String,
+    pub timestamp: Timestamp,
+    /// Earlier attempts of this node, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retries: Vec<FaultRetry>,
+    /// Underlying faults, most recent first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub causes: Vec<FaultCause>,
+}
+
+impl FaultCause {
+    pub fn new(
+        node_id: impl Into<String>,
+        node_label: impl Into<String>,
+        error: impl Into<String>,
+    ) -> Self {
+        Self {
+            node_id: node_id.into(),
+            node_label: node_label.into(),
+            step_index: None,
+            category: None,
+            error: error.into(),
+            timestamp: Timestamp::now(),
+            retries: Vec::new(),
+            causes: Vec::new(),
+        }
+    }
+
+    pub fn with_step_index(mut self, step_index: u64) -> Self {
+        self.step_index = Some(step_index);
+        self
+    }
+
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    pub fn with_retries(mut self, retries: Vec<FaultRetry>) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    pub fn caused_by(mut self, cause: FaultCause) -> Self {
+        self.causes.push(cause);
+        self
+    }
+
+    /// The deepest fault reached by following the first cause.
+    pub fn root_cause(&self) -> &FaultCause {
+        let mut current = self;
+        while let Some(next) = current.causes.first() {
+            current = next;
+        }
+        current
+    }
+
+    /// Depth-first walk over this cause and everything beneath it.
+    pub fn iter(&self) -> impl Iterator<Item = &FaultCause> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let next = stack.pop()?;
+            stack.extend(next.causes.iter().rev());
+            Some(next)
+        })
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{}: {}", self.node_label, self.error)?;
+        if !self.retries.is_empty() {
+            write!(f, " (after {} retries)", self.retries.len())?;
+        }
+        for cause in &self.causes {
+            write!(f, "\n{:indent$}caused by: ", "", indent = (depth + 1) * 2)?;
+            cause.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FaultCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// Bus resource accumulating the fault causes of one execution.
+#[derive(Debug, Clone, Default)]
+pub struct FaultChain {
+    latest: Option<FaultCause>,
+}
+
+impl FaultChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new fault; the previous latest fault becomes its cause.
+    pub fn record(&mut self, mut cause: FaultCause) -> &FaultCause {
+        if let Some(previous) = self.latest.take() {
+            cause.causes.insert(0, previous);
+        }
+        self.latest.insert(cause)
+    }
+
+    pub fn latest(&self) -> Option<&FaultCause> {
+        self.latest.as_ref()
+    }
+
+    pub fn latest_mut(&mut self) -> Option<&mut FaultCause> {
+        self.latest.as_mut()
+    }
+
+    pub fn take(&mut self) -> Option<FaultCause> {
+        self.latest.take()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.latest.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_nests_previous_faults() {
+        let mut chain = FaultChain::new();
+        chain.record(FaultCause::new("n1", "reserve_stock", "warehouse offline"));
+        chain.record(
+            FaultCause::new("n2", "charge_card", "card declined").with_retries(vec![FaultRetry {
+                attempt: 1,
+                error: "timeout".into(),
+                backoff_ms: 10,
+                timestamp: Timestamp::from_millis(1),
+            }]),
+        );
+
+        let latest = chain.latest().unwrap();
+        assert_eq!(latest.node_label, "charge_card");
+        assert_eq!(latest.root_cause().node_label, "reserve_stock");
+        let labels: Vec<_> = latest.iter().map(|c| c.node_label.as_str()).collect();
+        assert_eq!(labels, ["charge_card", "reserve_stock"]);
+        assert_eq!(
+            latest.to_string(),
+            "charge_card: card declined (after 1 retries)\n  caused by: reserve_stock: warehouse offline"
+        );
+    }
+
+    #[test]
+    fn serialization_omits_empty_history() {
+        let cause = FaultCause::new("n1", "validate", "bad input");
+        let json = serde_json::to_value(&cause).unwrap();
+        assert!(json.get("retries").is_none());
+        assert!(json.get("causes").is_none());
+        let back: FaultCause = serde_json::from_value(json).unwrap();
+        assert_eq!(back, cause);
+    }
+}
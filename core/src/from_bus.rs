@@ -0,0 +1,172 @@
+//! Materialize a struct of Bus resources in one call.
+//!
+//! `#[derive(FromBus)]` (from `ranvier-macros`) implements [`FromBus`] for a
+//! struct with named fields, cloning each field's type out of the Bus:
+//!
+//! ```rust,ignore
+//! use ranvier::prelude::*;
+//!
+//! #[derive(FromBus)]
+//! struct Deps {
+//!     db: PgPool,
+//!     cfg: AppConfig,
+//! }
+//!
+//! let deps: Deps = bus.extract()?;
+//! ```
+//!
+//! Every field is looked up before failing, so a [`MissingResources`] lists
+//! all absent members at once. A field typed [`OptionalResource<T>`] never
+//! fails and is left out of [`FromBus::requirements`], so startup checks do
+//! not flag it.
+
+use crate::bus::{Bus, BusTypeRef, MISSING_RESOURCE};
+
+/// A value built from resources held on a [`Bus`].
+pub trait FromBus: Sized {
+    fn from_bus(bus: &Bus) -> Result<Self, MissingResources>;
+
+    /// The Bus entries [`from_bus`](FromBus::from_bus) reads, for startup
+    /// checks such as `Axon::check_resources`.
+    fn requirements() -> Vec<BusTypeRef>;
+}
+
+/// A dependency a transition can run without.
+///
+/// Holds `Some` when the Bus has a `T`. As a `#[derive(FromBus)]` field it is
+/// filled with [`OptionalResource::read`] and is not reported as a
+/// requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionalResource<T>(pub Option<T>);
+
+impl<T: std::any::Any + Send + Sync + Clone + 'static> OptionalResource<T> {
+    pub fn read(bus: &Bus) -> Self {
+        Self(bus.read_opt_cloned::<T>())
+    }
+}
+
+impl<T> OptionalResource<T> {
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> Default for OptionalResource<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> std::ops::Deref for OptionalResource<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The members of a [`FromBus`] value the Bus did not provide.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MissingResources {
+    /// The type being built.
+    pub target: &'static str,
+    /// Type names of the absent members, in field order.
+    pub resources: Vec<&'static str>,
+    /// The node that asked, when the Bus was running one.
+    pub node_id: Option<String>,
+}
+
+impl MissingResources {
+    pub fn category(&self) -> &'static str {
+        MISSING_RESOURCE
+    }
+}
+
+impl std::fmt::Display for MissingResources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{MISSING_RESOURCE}: `{}` needs ", self.target)?;
+        for (i, resource) in self.resources.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "`{resource}`")?;
+        }
+        if let Some(node_id) = &self.node_id {
+            write!(f, " in node {node_id}")?;
+        }
+        write!(f, ", not on the Bus")
+    }
+}
+
+impl std::error::Error for MissingResources {}
+
+impl From<MissingResources> for String {
+    fn from(missing: MissingResources) -> Self {
+        missing.to_string()
+    }
+}
+
+impl From<MissingResources> for crate::error::RanvierError {
+    fn from(missing: MissingResources) -> Self {
+        Self::not_found(missing.resources.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Deps {
+        pool: u32,
+        config: String,
+    }
+
+    // What `#[derive(FromBus)]` expands to.
+    impl FromBus for Deps {
+        fn from_bus(bus: &Bus) -> Result<Self, MissingResources> {
+            let mut missing = Vec::new();
+            let pool = bus
+                .require::<u32>()
+                .map_err(|err| missing.push(err.resource))
+                .ok()
+                .cloned();
+            let config = bus
+                .require::<String>()
+                .map_err(|err| missing.push(err.resource))
+                .ok()
+                .cloned();
+            match (pool, config) {
+                (Some(pool), Some(config)) => Ok(Self { pool, config }),
+                _ => Err(MissingResources {
+                    target: std::any::type_name::<Self>(),
+                    resources: missing,
+                    node_id: bus.current_node().map(str::to_string),
+                }),
+            }
+        }
+
+        fn requirements() -> Vec<BusTypeRef> {
+            vec![BusTypeRef::of::<u32>(), BusTypeRef::of::<String>()]
+        }
+    }
+
+    #[test]
+    fn extract_lists_every_missing_member() {
+        let mut bus = Bus::new();
+        bus.set_current_node(Some("load-order".into()));
+        let missing = bus.extract::<Deps>().unwrap_err();
+        assert_eq!(missing.resources, vec!["u32", "alloc::string::String"]);
+        assert_eq!(
+            missing.to_string(),
+            "missing_resource: `ranvier_core::from_bus::tests::Deps` needs `u32`, \
+             `alloc::string::String` in node load-order, not on the Bus"
+        );
+        assert!(bus.take_missing_resource().is_some());
+
+        bus.insert(5u32);
+        bus.insert(String::from("cfg"));
+        let deps = bus.extract::<Deps>().unwrap();
+        assert_eq!((deps.pool, deps.config.as_str()), (5, "cfg"));
+    }
+}
@@ -0,0 +1,320 @@
+//! `ranvier-hydrate`: a generated TypeScript client for static build output.
+//!
+//! The generator reads the [`StaticManifest`] written by a static build and
+//! emits a small TypeScript package that:
+//!
+//! - fetches `manifest.json` once and resolves state files through it,
+//! - fetches per-page state JSON with `If-None-Match` / `If-Modified-Since`
+//!   revalidation and an in-memory response cache,
+//! - exposes one typed accessor per state (`landing_page` → `getLandingPage()`),
+//!   typed with the `StaticAxon::Output` type recorded in the manifest and
+//!   documented with the state's description as JSDoc.
+//!
+//! Output types are imported from a types module (by default `./types`),
+//! which is where `ts-rs` exports are expected to live. States without a
+//! recorded type are typed as `unknown`.
+//!
+//! ```rust,no_run
+//! use ranvier_core::hydrate::HydrateGenerator;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! HydrateGenerator::from_manifest_file("dist/static/manifest.json".as_ref())?
+//!     .with_types_module("../bindings")
+//!     .with_base_url("/static")
+//!     .write_package("web/ranvier-hydrate".as_ref())?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::static_gen::StaticManifest;
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Package name used in the generated `package.json`.
+pub const HYDRATE_PACKAGE_NAME: &str = "ranvier-hydrate";
+
+/// Generates the `ranvier-hydrate` TypeScript package for a static build.
+#[derive(Debug, Clone)]
+pub struct HydrateGenerator {
+    manifest: StaticManifest,
+    types_module: String,
+    base_url: String,
+}
+
+impl HydrateGenerator {
+    pub fn new(manifest: StaticManifest) -> Self {
+        Self {
+            manifest,
+            types_module: "./types".to_string(),
+            base_url: String::new(),
+        }
+    }
+
+    /// Read `manifest.json` from a static build output directory.
+    pub fn from_manifest_file(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self::new(serde_json::from_str(&content)?))
+    }
+
+    /// Module the output types are imported from (default `./types`).
+    pub fn with_types_module(mut self, module: impl Into<String>) -> Self {
+        self.types_module = module.into();
+        self
+    }
+
+    /// URL prefix the static files are served under (default: same origin root).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Render `index.ts`.
+    pub fn render_module(&self) -> String {
+        let states: Vec<(&str, String, Option<&str>)> = self
+            .manifest
+            .states
+            .iter()
+            .map(|entry| {
+                let ts_type = entry
+                    .output_type
+                    .as_deref()
+                    .filter(|name| is_ts_identifier(name))
+                    .unwrap_or("unknown");
+                (
+                    entry.name.as_str(),
+                    ts_type.to_string(),
+                    entry.description.as_deref(),
+                )
+            })
+            .collect();
+        let imports: BTreeSet<&str> = states
+            .iter()
+            .map(|(_, ts_type, _)| ts_type.as_str())
+            .filter(|ts_type| *ts_type != "unknown")
+            .collect();
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "// Generated by {HYDRATE_PACKAGE_NAME} from manifest.json (build {}). Do not edit.",
+            self.manifest.version
+        );
+        if !imports.is_empty() {
+            let _ = writeln!(
+                out,
+                "import type {{ {} }} from {};",
+                imports.into_iter().collect::<Vec<_>>().join(", "),
+                ts_string(&self.types_module)
+            );
+        }
+        out.push('\n');
+
+        out.push_str("export interface StaticStateMap {\n");
+        for (name, ts_type, _) in &states {
+            let _ = writeln!(out, "  {}: {ts_type};", ts_string(name));
+        }
+        out.push_str("}\n\nexport type StaticStateName = keyof StaticStateMap;\n\n");
+
+        let _ = writeln!(
+            out,
+            "let baseUrl = {};\n{RUNTIME}",
+            ts_string(&self.base_url)
+        );
+
+        for (name, ts_type, description) in &states {
+            out.push('\n');
+            if let Some(description) = description {
+                out.push_str(&jsdoc(description));
+            }
+            let _ = writeln!(
+                out,
+                "export const get{} = (): Promise<{ts_type}> => loadState({});",
+                pascal_case(name),
+                ts_string(name)
+            );
+        }
+        out
+    }
+
+    /// Render `package.json`.
+    pub fn render_package_json(&self) -> String {
+        let package = serde_json::json!({
+            "name": HYDRATE_PACKAGE_NAME,
+            "version": self.manifest.version,
+            "private": true,
+            "type": "module",
+            "main": "index.ts",
+            "types": "index.ts",
+        });
+        serde_json::to_string_pretty(&package).unwrap_or_default() + "\n"
+    }
+
+    /// Write `index.ts` and `package.json` into `dir`.
+    pub fn write_package(&self, dir: &Path) -> anyhow::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("index.ts"), self.render_module())?;
+        std::fs::write(dir.join("package.json"), self.render_package_json())?;
+        Ok(())
+    }
+}
+
+const RUNTIME: &str = r#"let fetchImpl: typeof fetch = (input, init) => fetch(input, init);
+
+export interface StaticManifestEntry {
+  name: string;
+  file: string;
+  content_type: string;
+  output_type?: string;
+  description?: string;
+}
+
+export interface StaticManifest {
+  version: string;
+  generated_at: string;
+  states: StaticManifestEntry[];
+}
+
+export interface HydrateOptions {
+  /** URL prefix the static files are served under. */
+  baseUrl?: string;
+  /** Custom fetch, e.g. for SSR or tests. */
+  fetch?: typeof fetch;
+}
+
+export function configureHydration(options: HydrateOptions): void {
+  if (options.baseUrl !== undefined) baseUrl = options.baseUrl.replace(/\/+$/, "");
+  if (options.fetch !== undefined) fetchImpl = options.fetch;
+  manifestPromise = null;
+  responses.clear();
+}
+
+interface CachedResponse {
+  etag: string | null;
+  lastModified: string | null;
+  body: unknown;
+}
+
+const responses = new Map<string, CachedResponse>();
+let manifestPromise: Promise<StaticManifest> | null = null;
+
+async function fetchJson<T>(path: string): Promise<T> {
+  const url = `${baseUrl}/${path.replace(/^\/+/, "")}`;
+  const cached = responses.get(url);
+  const headers: Record<string, string> = { Accept: "application/json" };
+  if (cached?.etag) headers["If-None-Match"] = cached.etag;
+  if (cached?.lastModified) headers["If-Modified-Since"] = cached.lastModified;
+
+  const response = await fetchImpl(url, { headers, cache: "no-cache" });
+  if (response.status === 304 && cached) return cached.body as T;
+  if (!response.ok) {
+    throw new Error(`ranvier-hydrate: GET ${url} failed with ${response.status}`);
+  }
+  const body = (await response.json()) as T;
+  responses.set(url, {
+    etag: response.headers.get("ETag"),
+    lastModified: response.headers.get("Last-Modified"),
+    body,
+  });
+  return body;
+}
+
+export function loadManifest(): Promise<StaticManifest> {
+  if (!manifestPromise) {
+    manifestPromise = fetchJson<StaticManifest>("manifest.json").catch((error) => {
+      manifestPromise = null;
+      throw error;
+    });
+  }
+  return manifestPromise;
+}
+
+export async function loadState<K extends StaticStateName>(name: K): Promise<StaticStateMap[K]> {
+  const manifest = await loadManifest();
+  const entry = manifest.states.find((state) => state.name === name);
+  if (!entry) {
+    throw new Error(`ranvier-hydrate: state "${String(name)}" is not in manifest.json`);
+  }
+  return fetchJson<StaticStateMap[K]>(entry.file);
+}"#;
+
+fn ts_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+fn jsdoc(text: &str) -> String {
+    let mut out = String::from("/**\n");
+    for line in text.replace("*/", "*\\/").lines() {
+        let _ = writeln!(out, " *{}{line}", if line.is_empty() { "" } else { " " });
+    }
+    out.push_str(" */\n");
+    out
+}
+
+fn is_ts_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// `landing_page` / `landing-page` / `docs.index` → `LandingPage` / `DocsIndex`.
+fn pascal_case(name: &str) -> String {
+    let mut out = String::new();
+    for word in name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars);
+        }
+    }
+    if out.chars().next().is_none_or(|c| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_typed_accessors_from_manifest() {
+        let mut manifest = StaticManifest::new();
+        manifest.add_typed_state(
+            "landing_page",
+            "landing_page.json",
+            "app::state::LandingState",
+        );
+        manifest.add_typed_state("pricing-page", "pricing.json", "app::PricingState");
+        manifest.add_state("docs_index", "docs_index.json");
+        manifest.states[0].description = Some("Hero and feature list.\n\nRebuilt nightly.".into());
+
+        let module = HydrateGenerator::new(manifest)
+            .with_types_module("../bindings")
+            .with_base_url("/static/")
+            .render_module();
+
+        assert!(
+            module.contains("import type { LandingState, PricingState } from \"../bindings\";")
+        );
+        assert!(module.contains("  \"landing_page\": LandingState;\n"));
+        assert!(module.contains("  \"docs_index\": unknown;\n"));
+        assert!(module.contains("let baseUrl = \"/static\";"));
+        assert!(module.contains(
+            "/**\n * Hero and feature list.\n *\n * Rebuilt nightly.\n */\nexport const getLandingPage"
+        ));
+        assert!(module.contains(
+            "export const getLandingPage = (): Promise<LandingState> => loadState(\"landing_page\");"
+        ));
+        assert!(module.contains(
+            "export const getPricingPage = (): Promise<PricingState> => loadState(\"pricing-page\");"
+        ));
+        assert!(module.contains("export const getDocsIndex = (): Promise<unknown>"));
+        assert!(module.contains("If-None-Match"));
+    }
+}
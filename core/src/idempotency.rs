@@ -0,0 +1,21 @@
+//! Caller-supplied idempotency keys.
+//!
+//! The HTTP ingress extracts the `Idempotency-Key` header into the Bus as an
+//! [`IdempotencyKey`]. Guards use it to replay cached responses and the Axon
+//! executor uses it to skip executions that already completed.
+
+use serde::{Deserialize, Serialize};
+
+/// Key identifying one logical request across client retries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IdempotencyKey(pub String);
+
+impl IdempotencyKey {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
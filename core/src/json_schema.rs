@@ -0,0 +1,569 @@
+//! Published JSON Schemas for Ranvier artifacts.
+//!
+//! Schematics, timelines and inspector projections are exchanged with tools
+//! that do not link Ranvier (Studio, CI checks, dashboards). The schemas for
+//! these artifacts ship with this crate under `schemas/` and can be used to
+//! validate a document before it is consumed:
+//!
+//! ```rust
+//! use ranvier_core::json_schema::SchemaArtifact;
+//! use ranvier_core::schematic::Schematic;
+//!
+//! let schematic = serde_json::to_value(Schematic::new("checkout")).unwrap();
+//! assert!(SchemaArtifact::Schematic.validate(&schematic).is_ok());
+//! ```
+//!
+//! The schematic and timeline schemas are generated from the Rust types with
+//! `schemars` (feature `schema`); the projection schemas are maintained by
+//! hand because projections are assembled as JSON by `ranvier-inspector`.
+//!
+//! [`validate`] implements the subset of JSON Schema 2020-12 used by these
+//! files: `$ref` within the document, `type`, `enum`, `const`, object and
+//! array keywords, `oneOf`/`anyOf`/`allOf` and numeric bounds. Formats and
+//! other annotations are ignored.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Error category for documents that do not match their published schema.
+pub const SCHEMA_VIOLATION: &str = "schema_violation";
+
+/// An artifact format with a published JSON Schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaArtifact {
+    /// A [`Schematic`](crate::schematic::Schematic) as written by `schematic.json` exports.
+    Schematic,
+    /// A recorded [`Timeline`](crate::timeline::Timeline).
+    Timeline,
+    /// The inspector's public projection, including regional aggregates.
+    PublicProjection,
+    /// The inspector's internal (per-trace) projection.
+    InternalProjection,
+}
+
+impl SchemaArtifact {
+    pub const ALL: [SchemaArtifact; 4] = [
+        SchemaArtifact::Schematic,
+        SchemaArtifact::Timeline,
+        SchemaArtifact::PublicProjection,
+        SchemaArtifact::InternalProjection,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SchemaArtifact::Schematic => "schematic",
+            SchemaArtifact::Timeline => "timeline",
+            SchemaArtifact::PublicProjection => "public_projection",
+            SchemaArtifact::InternalProjection => "internal_projection",
+        }
+    }
+
+    /// File name of the schema under the crate's `schemas/` directory.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            SchemaArtifact::Schematic => "schematic.schema.json",
+            SchemaArtifact::Timeline => "timeline.schema.json",
+            SchemaArtifact::PublicProjection => "public_projection.schema.json",
+            SchemaArtifact::InternalProjection => "internal_projection.schema.json",
+        }
+    }
+
+    /// The published schema text.
+    pub fn schema_str(self) -> &'static str {
+        match self {
+            SchemaArtifact::Schematic => include_str!("../schemas/schematic.schema.json"),
+            SchemaArtifact::Timeline => include_str!("../schemas/timeline.schema.json"),
+            SchemaArtifact::PublicProjection => {
+                include_str!("../schemas/public_projection.schema.json")
+            }
+            SchemaArtifact::InternalProjection => {
+                include_str!("../schemas/internal_projection.schema.json")
+            }
+        }
+    }
+
+    /// The published schema.
+    pub fn schema(self) -> Value {
+        serde_json::from_str(self.schema_str()).expect("published schemas are valid JSON")
+    }
+
+    /// Validate `document` against the published schema.
+    pub fn validate(self, document: &Value) -> Result<(), SchemaViolations> {
+        let violations = validate(&self.schema(), document);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(SchemaViolations {
+                artifact: self,
+                violations,
+            })
+        }
+    }
+
+    /// Generate the schema from the Rust types.
+    ///
+    /// Returns `None` for projections, whose schemas are not derived from a
+    /// Rust type. The published files must match this output; regenerate them
+    /// with `RANVIER_BLESS_SCHEMAS=1 cargo test -p ranvier-core --features schema`.
+    #[cfg(feature = "schema")]
+    pub fn generate(self) -> Option<Value> {
+        let schema = match self {
+            SchemaArtifact::Schematic => schemars::schema_for!(crate::schematic::Schematic),
+            SchemaArtifact::Timeline => schemars::schema_for!(crate::timeline::Timeline),
+            SchemaArtifact::PublicProjection | SchemaArtifact::InternalProjection => {
+                return None;
+            }
+        };
+        serde_json::to_value(schema).ok()
+    }
+}
+
+impl std::fmt::Display for SchemaArtifact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// One place where a document does not match its schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON Pointer to the offending value; empty for the document root.
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{path}: {}", self.message)
+    }
+}
+
+/// A document rejected by [`SchemaArtifact::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolations {
+    pub artifact: SchemaArtifact,
+    pub violations: Vec<SchemaViolation>,
+}
+
+impl SchemaViolations {
+    pub fn category(&self) -> &'static str {
+        SCHEMA_VIOLATION
+    }
+}
+
+impl std::fmt::Display for SchemaViolations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{SCHEMA_VIOLATION}: document is not a valid {}",
+            self.artifact
+        )?;
+        for (i, violation) in self.violations.iter().enumerate() {
+            let sep = if i == 0 { " (" } else { "; " };
+            write!(f, "{sep}{violation}")?;
+        }
+        if !self.violations.is_empty() {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaViolations {}
+
+impl From<SchemaViolations> for String {
+    fn from(violations: SchemaViolations) -> Self {
+        violations.to_string()
+    }
+}
+
+impl From<SchemaViolations> for crate::error::RanvierError {
+    fn from(violations: SchemaViolations) -> Self {
+        Self::validation(violations.to_string())
+    }
+}
+
+/// Validate `document` against a JSON Schema, returning every violation.
+///
+/// `$ref`s are resolved as JSON Pointers into `schema` itself.
+pub fn validate(schema: &Value, document: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    Validator { root: schema }.check(schema, document, &mut String::new(), &mut violations);
+    violations
+}
+
+struct Validator<'s> {
+    root: &'s Value,
+}
+
+impl Validator<'_> {
+    fn check(
+        &self,
+        schema: &Value,
+        value: &Value,
+        path: &mut String,
+        out: &mut Vec<SchemaViolation>,
+    ) {
+        let schema = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => return violation(out, path, "no value is allowed here"),
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.root.pointer(pointer))
+            {
+                Some(target) => self.check(target, value, path, out),
+                None => violation(out, path, format!("unresolved $ref '{reference}'")),
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let matches = match expected {
+                Value::String(ty) => has_type(value, ty),
+                Value::Array(types) => types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .any(|ty| has_type(value, ty)),
+                _ => true,
+            };
+            if !matches {
+                return violation(
+                    out,
+                    path,
+                    format!(
+                        "expected {}, found {}",
+                        type_list(expected),
+                        type_name(value)
+                    ),
+                );
+            }
+        }
+        if let Some(Value::Array(allowed)) = schema.get("enum")
+            && !allowed.contains(value)
+        {
+            violation(
+                out,
+                path,
+                format!("{value} is not one of {}", Value::Array(allowed.clone())),
+            );
+        }
+        if let Some(expected) = schema.get("const")
+            && expected != value
+        {
+            violation(out, path, format!("expected {expected}, found {value}"));
+        }
+
+        if let Some(number) = value.as_f64() {
+            if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+                && number < minimum
+            {
+                violation(out, path, format!("{value} is less than {minimum}"));
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64)
+                && number > maximum
+            {
+                violation(out, path, format!("{value} is greater than {maximum}"));
+            }
+        }
+
+        if let Value::Object(object) = value {
+            self.check_object(schema, object, path, out);
+        }
+        if let Value::Array(items) = value {
+            self.check_array(schema, items, path, out);
+        }
+
+        if let Some(Value::Array(all)) = schema.get("allOf") {
+            for sub in all {
+                self.check(sub, value, path, out);
+            }
+        }
+        if let Some(Value::Array(any)) = schema.get("anyOf")
+            && !any.iter().any(|sub| self.matches(sub, value))
+        {
+            violation(
+                out,
+                path,
+                "does not match any of the allowed schemas (anyOf)",
+            );
+        }
+        if let Some(Value::Array(one)) = schema.get("oneOf") {
+            let matched = one.iter().filter(|sub| self.matches(sub, value)).count();
+            if matched != 1 {
+                violation(
+                    out,
+                    path,
+                    format!("matches {matched} of the oneOf schemas, expected exactly 1"),
+                );
+            }
+        }
+    }
+
+    fn check_object(
+        &self,
+        schema: &serde_json::Map<String, Value>,
+        object: &serde_json::Map<String, Value>,
+        path: &mut String,
+        out: &mut Vec<SchemaViolation>,
+    ) {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    violation(out, path, format!("missing required property '{key}'"));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, child) in object {
+            let sub = match properties.and_then(|properties| properties.get(key)) {
+                Some(sub) => sub,
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        violation(out, path, format!("unexpected property '{key}'"));
+                        continue;
+                    }
+                    Some(sub) => sub,
+                    None => continue,
+                },
+            };
+            let len = path.len();
+            push_segment(path, key);
+            self.check(sub, child, path, out);
+            path.truncate(len);
+        }
+    }
+
+    fn check_array(
+        &self,
+        schema: &serde_json::Map<String, Value>,
+        items: &[Value],
+        path: &mut String,
+        out: &mut Vec<SchemaViolation>,
+    ) {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+            && (items.len() as u64) < min
+        {
+            violation(out, path, format!("expected at least {min} items"));
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+            && (items.len() as u64) > max
+        {
+            violation(out, path, format!("expected at most {max} items"));
+        }
+        let prefix = schema
+            .get("prefixItems")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for (i, item) in items.iter().enumerate() {
+            let Some(sub) = prefix.get(i).or_else(|| schema.get("items")) else {
+                continue;
+            };
+            let len = path.len();
+            push_segment(path, &i.to_string());
+            self.check(sub, item, path, out);
+            path.truncate(len);
+        }
+    }
+
+    fn matches(&self, schema: &Value, value: &Value) -> bool {
+        let mut out = Vec::new();
+        self.check(schema, value, &mut String::new(), &mut out);
+        out.is_empty()
+    }
+}
+
+fn violation(out: &mut Vec<SchemaViolation>, path: &str, message: impl Into<String>) {
+    out.push(SchemaViolation {
+        path: path.to_string(),
+        message: message.into(),
+    });
+}
+
+fn push_segment(path: &mut String, segment: &str) {
+    path.push('/');
+    path.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+}
+
+fn has_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_list(expected: &Value) -> String {
+    match expected {
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        other => other.as_str().unwrap_or_default().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{Edge, EdgeType, Node, NodeKind, Schematic, SourceLocation};
+    use crate::timeline::{Timeline, TimelineEvent, Timestamp};
+    use serde_json::json;
+
+    fn schematic() -> Schematic {
+        let mut schematic = Schematic::new("checkout");
+        for (id, label, kind) in [
+            ("a", "Start", NodeKind::Ingress),
+            ("b", "Charge", NodeKind::Atom),
+        ] {
+            schematic.nodes.push(Node {
+                id: id.to_string(),
+                kind,
+                label: label.to_string(),
+                description: None,
+                input_type: "()".to_string(),
+                output_type: "()".to_string(),
+                resource_type: "()".to_string(),
+                metadata: Default::default(),
+                bus_capability: None,
+                source_location: Some(SourceLocation::with_column("src/main.rs", 10, 5)),
+                position: None,
+                compensation_node_id: None,
+                input_schema: None,
+                output_schema: None,
+                item_type: None,
+                terminal: None,
+            });
+        }
+        schematic.edges.push(Edge {
+            from: "a".to_string(),
+            to: "b".to_string(),
+            kind: EdgeType::Linear,
+            label: None,
+        });
+        schematic
+    }
+
+    #[test]
+    fn published_schemas_accept_artifacts_they_describe() {
+        let schematic = serde_json::to_value(schematic()).unwrap();
+        SchemaArtifact::Schematic.validate(&schematic).unwrap();
+
+        let mut timeline = Timeline::new();
+        timeline.push(TimelineEvent::NodeEnter {
+            node_id: "b".to_string(),
+            node_label: "Charge".to_string(),
+            timestamp: Timestamp::from_millis(1),
+            trace: None,
+        });
+        let timeline = serde_json::to_value(&timeline).unwrap();
+        SchemaArtifact::Timeline.validate(&timeline).unwrap();
+
+        for artifact in SchemaArtifact::ALL {
+            assert_eq!(
+                artifact.schema()["$schema"],
+                "https://json-schema.org/draft/2020-12/schema"
+            );
+        }
+    }
+
+    #[test]
+    fn violations_point_at_the_offending_value() {
+        let mut document = serde_json::to_value(schematic()).unwrap();
+        document["nodes"][1]["kind"] = json!("Teleport");
+        document.as_object_mut().unwrap().remove("edges");
+
+        let err = SchemaArtifact::Schematic.validate(&document).unwrap_err();
+        assert_eq!(err.category(), SCHEMA_VIOLATION);
+        let paths: Vec<_> = err.violations.iter().map(|v| v.path.as_str()).collect();
+        assert!(paths.contains(&""), "{err}");
+        assert!(paths.contains(&"/nodes/1/kind"), "{err}");
+        assert!(
+            err.to_string()
+                .contains("missing required property 'edges'")
+        );
+    }
+
+    #[test]
+    fn validator_supports_refs_combinators_and_bounds() {
+        let schema = json!({
+            "$defs": { "rate": { "type": "number", "minimum": 0, "maximum": 1 } },
+            "type": "object",
+            "properties": {
+                "rate": { "$ref": "#/$defs/rate" },
+                "status": { "oneOf": [{ "const": "up" }, { "const": "down" }] },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "additionalProperties": false
+        });
+        assert!(
+            validate(
+                &schema,
+                &json!({ "rate": 0.5, "status": "up", "tags": ["a"] })
+            )
+            .is_empty()
+        );
+
+        let violations = validate(
+            &schema,
+            &json!({ "rate": 1.5, "status": "sideways", "tags": [1], "extra": true }),
+        );
+        let paths: Vec<_> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(paths, ["", "/rate", "/status", "/tags/0"]);
+    }
+
+    /// Keeps the published files in sync with the Rust types. Set
+    /// `RANVIER_BLESS_SCHEMAS=1` to rewrite them after changing a type.
+    #[cfg(feature = "schema")]
+    #[test]
+    fn published_schemas_match_generated() {
+        for artifact in SchemaArtifact::ALL {
+            let Some(generated) = artifact.generate() else {
+                continue;
+            };
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("schemas")
+                .join(artifact.file_name());
+            if std::env::var_os("RANVIER_BLESS_SCHEMAS").is_some() {
+                let text = serde_json::to_string_pretty(&generated).unwrap() + "\n";
+                std::fs::write(&path, text).unwrap();
+                continue;
+            }
+            assert_eq!(
+                artifact.schema(),
+                generated,
+                "{} is stale; rerun with RANVIER_BLESS_SCHEMAS=1",
+                path.display()
+            );
+        }
+    }
+}
@@ -11,26 +11,50 @@ pub mod __macro_support {
 }
 
 pub mod bus;
+pub mod bus_registry;
+pub mod bus_snapshot;
+pub mod cache_policy;
+pub mod cached;
 pub mod cancellation;
+pub mod capture;
+pub mod circuit_breaker;
 pub mod cluster;
 pub mod config;
+pub mod deadline;
 pub mod debug;
+pub mod durability;
 pub mod error;
 pub mod event;
+pub mod event_schema;
+pub mod fault;
+pub mod from_bus;
+pub mod hydrate;
 pub mod iam;
+pub mod idempotency;
+pub mod json_schema;
 pub mod metadata;
 pub mod never;
+pub mod node_policy;
 pub mod outcome;
 pub mod policy;
+pub mod rate_limit;
+pub mod redaction;
+pub mod retry;
 pub mod runtime_policy;
 pub mod saga;
 pub mod schematic;
+pub mod schematic_diff;
+pub mod schematic_merge;
+pub mod schematic_registry;
+mod serde_helpers;
 pub mod static_gen;
 pub mod synapse;
 pub mod telemetry;
 pub mod tenant;
 pub mod timeline;
+pub mod timeline_store;
 pub mod transition;
+pub mod validation;
 
 #[cfg(feature = "streaming")]
 pub mod streaming;
@@ -39,6 +63,7 @@ pub mod streaming;
 // For Ingress adapters, use: ranvier_http
 
 // Static generation exports
+pub use hydrate::HydrateGenerator;
 #[allow(deprecated)]
 pub use static_gen::{
     StaticAxon, StaticBuildConfig, StaticBuildResult, StaticManifest, StaticNode, StaticStateEntry,
@@ -47,28 +72,64 @@ pub use static_gen::{
 
 // Prelude module for convenient imports
 pub mod prelude {
-    pub use crate::bus::{Bus, BusAccessError, BusAccessPolicy, BusTypeRef};
+    pub use crate::bus::{
+        Bus, BusAccessError, BusAccessPolicy, BusOverride, BusScope, BusTypeRef, MissingResource,
+        ResourceLifecycle, ResourceSet,
+    };
+    pub use crate::bus_snapshot::{BusDiff, BusSnapshot};
+    pub use crate::cache_policy::{CachePolicy, CacheVisibility};
+    pub use crate::cached::{CacheBackend, Cached, InMemoryLruCache};
     pub use crate::cancellation::{CancellationContext, CancellationReason, CancellationToken};
+    pub use crate::capture::{CaptureFormat, CapturePolicy, CapturedPayload, NodeCapturePolicy};
+    pub use crate::circuit_breaker::{
+        CircuitBreakerState, CircuitBreakerStateReader, CircuitState,
+    };
     pub use crate::config::{
         ConfigError, InspectorConfig, LogFormat, LoggingConfig, OtlpProtocol, RanvierConfig,
         ResolvedConfigError, ResolvedRuntimeConfig, ServerConfig, TelemetryConfig, TlsConfig,
     };
+    pub use crate::deadline::{Deadline, DeadlineExceeded};
     pub use crate::debug::{DebugControl, DebugState};
+    pub use crate::durability::{
+        DurabilityStore, DurableRun, DurableStep, InMemoryDurabilityStore, RunStatus,
+    };
     pub use crate::error::{RanvierError, TransitionErrorContext};
     pub use crate::event::{DeadLetter, DlqPolicy, DlqReader, DlqSink, EventSink, EventSource};
+    pub use crate::fault::{FaultCause, FaultChain, FaultRetry};
+    pub use crate::from_bus::{FromBus, MissingResources, OptionalResource};
     pub use crate::iam::{
         AuthContext, AuthScheme, IamError, IamHandle, IamIdentity, IamPolicy, IamToken, IamVerifier,
     };
-    pub use crate::metadata::StepMetadata;
+    pub use crate::json_schema::SchemaArtifact;
+    pub use crate::metadata::{NodeAnnotations, StepMetadata};
     pub use crate::never::Never;
-    pub use crate::outcome::{BranchId, NodeId, Outcome};
+    pub use crate::node_policy::{
+        AppliedNodePolicy, BackoffConfig, NodePoliciesConfig, NodePolicyConfig, NodePolicySource,
+    };
+    pub use crate::outcome::{BranchId, BranchKey, NodeId, Outcome};
     pub use crate::policy::{DynamicPolicy, PolicyRegistry};
+    pub use crate::rate_limit::{
+        RateLimitRule, RateLimitState, RateLimitStateReader, RateLimitsConfig,
+    };
+    pub use crate::retry::Retry;
     pub use crate::runtime_policy::{RuntimeProfile, StartupPolicyStatus};
-    pub use crate::saga::{SagaCompensationRegistry, SagaPolicy, SagaStack, SagaTask};
-    pub use crate::schematic::{Edge, EdgeType, Node, NodeKind, SchemaMigrationMapper, Schematic};
-    pub use crate::tenant::{IsolationPolicy, TenantExtractor, TenantId, TenantResolver};
-    pub use crate::timeline::{Timeline, TimelineEvent};
-    pub use crate::transition::{ResourceRequirement, Transition};
+    pub use crate::saga::{
+        SagaCompensationRegistry, SagaPolicy, SagaRollbackReport, SagaRollbackStep, SagaStack,
+        SagaStepStatus, SagaTask,
+    };
+    pub use crate::schematic::{
+        Edge, EdgeType, Node, NodeKind, SchemaMigrationMapper, Schematic, SchematicFingerprint,
+    };
+    pub use crate::schematic_diff::SchematicDiff;
+    pub use crate::schematic_registry::{SchematicBundle, SchematicRegistry};
+    pub use crate::tenant::{
+        IsolationPolicy, TenantExtractor, TenantId, TenantResolver, TenantTier,
+    };
+    pub use crate::timeline::{Timeline, TimelineEvent, Timestamp};
+    pub use crate::timeline_store::{
+        InMemoryTimelineStore, TimelineRun, TimelineRunQuery, TimelineRunSummary, TimelineStore,
+    };
+    pub use crate::transition::{ResourceRequirement, SideEffect, Transition};
 
     // Macros re-exported for convenient access via `use ranvier_core::prelude::*`
     pub use crate::try_outcome;
@@ -79,13 +140,17 @@ pub mod prelude {
 // pub mod circuit;
 // pub mod service; // Moved to ranvier-http
 
-pub use bus::{Bus, BusAccessError, BusAccessPolicy, BusTypeRef};
+pub use bus::{
+    Bus, BusAccessError, BusAccessPolicy, BusOverride, BusScope, BusTypeRef, MissingResource,
+    ResourceSet,
+};
 pub use cancellation::{CancellationContext, CancellationReason, CancellationToken};
 pub use cluster::{ClusterBus, ClusterError, DistributedLock};
+pub use from_bus::{FromBus, MissingResources, OptionalResource};
 pub use never::Never;
 pub use outcome::Outcome;
 pub use schematic::Schematic;
-pub use timeline::{Timeline, TimelineEvent};
+pub use timeline::{Timeline, TimelineEvent, Timestamp};
 pub use transition::Transition;
 
 /// Convert a fallible expression into an `Outcome` early-return inside a `#[transition]`.
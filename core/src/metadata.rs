@@ -1,13 +1,38 @@
+use crate::node_policy::AppliedNodePolicy;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct StepMetadata {
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
     pub id: Uuid,
     pub label: String,
     pub description: Option<String>,
     pub inputs: Vec<TypeInfo>,
     pub outputs: Vec<TypeInfo>,
+    /// Effective retry/backoff/timeout policy, when the node declares one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<AppliedNodePolicy>,
+    /// Names of the output contracts checked after this node.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contracts: Vec<String>,
+    /// Payload JSON Schemas of the events this node emits, keyed by event type.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub emits: BTreeMap<String, serde_json::Value>,
+    /// Labels or ids of the nodes this node may return `Outcome::Jump` to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jump_targets: Vec<String>,
+    /// Type names of the Bus entries the node's transition requires.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_resources: Vec<String>,
+    /// Branch ids the node's transition declares it may return.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<String>,
+    /// Ownership and SLA annotations, used to route alerts for the node.
+    #[serde(default, skip_serializing_if = "NodeAnnotations::is_empty")]
+    pub annotations: NodeAnnotations,
 }
 
 impl StepMetadata {
@@ -16,7 +41,63 @@ impl StepMetadata {
     }
 }
 
+/// Who owns a node and what it promises.
+///
+/// Set with `#[transition(owner = "...", team = "...", sla_ms = 250, tags = ["..."])]`
+/// or the Axon builder's `owner`, `team`, `sla_ms` and `tag` methods, which
+/// apply to the last node and take precedence over the transition's values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NodeAnnotations {
+    /// Person or rotation to page when the node misbehaves.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+    /// Latency the node is expected to stay under, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sla_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl NodeAnnotations {
+    pub fn is_empty(&self) -> bool {
+        self.owner.is_none() && self.team.is_none() && self.sla_ms.is_none() && self.tags.is_empty()
+    }
+
+    pub fn with_owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    pub fn with_team(mut self, team: impl Into<String>) -> Self {
+        self.team = Some(team.into());
+        self
+    }
+
+    pub fn with_sla_ms(mut self, sla_ms: u64) -> Self {
+        self.sla_ms = Some(sla_ms);
+        self
+    }
+
+    /// Add a tag unless the node already carries it.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+        self
+    }
+
+    /// Whether a run of `latency_ms` exceeded [`sla_ms`](Self::sla_ms).
+    pub fn sla_breached(&self, latency_ms: f64) -> bool {
+        self.sla_ms.is_some_and(|sla| latency_ms > sla as f64)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TypeInfo {
     pub name: String,
     /// Optional JSON Schema describing this type's structure.
@@ -44,6 +125,24 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn empty_annotations_are_not_serialized() {
+        let mut metadata = StepMetadata::default();
+        assert!(metadata.to_json().get("annotations").is_none());
+
+        metadata.annotations = NodeAnnotations::default()
+            .with_owner("payments-oncall")
+            .with_sla_ms(250)
+            .with_tag("pii")
+            .with_tag("pii");
+        assert_eq!(
+            metadata.to_json()["annotations"],
+            json!({"owner": "payments-oncall", "sla_ms": 250, "tags": ["pii"]})
+        );
+        assert!(metadata.annotations.sla_breached(300.0));
+        assert!(!metadata.annotations.sla_breached(250.0));
+    }
+
     #[test]
     fn type_info_new_has_no_schema() {
         let info = TypeInfo::new("i32");
@@ -0,0 +1,189 @@
+//! Declarative retry, backoff and timeout overrides for named nodes.
+//!
+//! Operators tune resilience settings in the `[nodes]` section of
+//! `ranvier.toml` without touching code:
+//!
+//! ```toml
+//! [nodes.charge_card]
+//! max_retries = 5
+//! timeout_ms = 2000
+//! backoff = { strategy = "exponential", initial_ms = 100, multiplier = 2.0, max_ms = 5000 }
+//!
+//! [nodes.send_receipt]
+//! backoff = { strategy = "fixed", delay_ms = 250 }
+//! ```
+//!
+//! Keys are transition labels. Every field is optional; a field left unset
+//! keeps the value the circuit declared in code. Overrides are attached to
+//! an Axon with `Axon::with_node_policies` and apply to all of its nodes,
+//! wherever in the chain that call is made; the effective policy is
+//! recorded as [`AppliedNodePolicy`] in each node's
+//! [`StepMetadata`](crate::metadata::StepMetadata) so the Schematic always
+//! shows what is actually running.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Delay strategy between retry attempts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum BackoffConfig {
+    /// The same delay before every retry.
+    Fixed { delay_ms: u64 },
+    /// `initial_ms * multiplier^attempt`, capped at `max_ms`.
+    Exponential {
+        initial_ms: u64,
+        multiplier: f64,
+        max_ms: u64,
+    },
+}
+
+impl BackoffConfig {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Fixed { .. } => true,
+            Self::Exponential {
+                initial_ms,
+                multiplier,
+                max_ms,
+            } => multiplier.is_finite() && *multiplier >= 1.0 && initial_ms <= max_ms,
+        }
+    }
+}
+
+/// Resilience overrides for a single node.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodePolicyConfig {
+    /// Retry attempts after the first failure.
+    pub max_retries: Option<u32>,
+    pub backoff: Option<BackoffConfig>,
+    /// Per-attempt execution budget in milliseconds.
+    pub timeout_ms: Option<u64>,
+}
+
+impl NodePolicyConfig {
+    pub fn is_empty(&self) -> bool {
+        self.max_retries.is_none() && self.backoff.is_none() && self.timeout_ms.is_none()
+    }
+}
+
+/// The `[nodes]` section of `ranvier.toml`, keyed by transition label.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NodePoliciesConfig(pub HashMap<String, NodePolicyConfig>);
+
+impl NodePoliciesConfig {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, label: &str) -> Option<&NodePolicyConfig> {
+        self.0.get(label)
+    }
+
+    pub fn insert(&mut self, label: impl Into<String>, policy: NodePolicyConfig) {
+        self.0.insert(label.into(), policy);
+    }
+
+    /// Entries with a zero timeout or an unusable backoff.
+    pub fn invalid_rules(&self) -> Vec<String> {
+        let mut invalid = Vec::new();
+        for (label, policy) in &self.0 {
+            if policy.timeout_ms == Some(0) {
+                invalid.push(format!("nodes.{label}.timeout_ms"));
+            }
+            if policy.backoff.as_ref().is_some_and(|b| !b.is_valid()) {
+                invalid.push(format!("nodes.{label}.backoff"));
+            }
+        }
+        invalid.sort();
+        invalid
+    }
+}
+
+/// Where the effective node policy came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum NodePolicySource {
+    /// Declared in code (`then_with_retry`, `then_with_timeout`).
+    Code,
+    /// At least one field was overridden by `ranvier.toml`.
+    Config,
+}
+
+/// Effective resilience policy of a node, as recorded in its metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AppliedNodePolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backoff: Option<BackoffConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    pub source: NodePolicySource,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_node_sections() {
+        let config: NodePoliciesConfig = toml::from_str(
+            r#"
+            [charge_card]
+            max_retries = 5
+            timeout_ms = 2000
+            backoff = { strategy = "exponential", initial_ms = 100, multiplier = 2.0, max_ms = 5000 }
+
+            [send_receipt]
+            backoff = { strategy = "fixed", delay_ms = 250 }
+            "#,
+        )
+        .unwrap();
+
+        let charge = config.get("charge_card").unwrap();
+        assert_eq!(charge.max_retries, Some(5));
+        assert_eq!(charge.timeout_ms, Some(2000));
+        assert_eq!(
+            charge.backoff,
+            Some(BackoffConfig::Exponential {
+                initial_ms: 100,
+                multiplier: 2.0,
+                max_ms: 5000
+            })
+        );
+        let receipt = config.get("send_receipt").unwrap();
+        assert_eq!(receipt.max_retries, None);
+        assert_eq!(
+            receipt.backoff,
+            Some(BackoffConfig::Fixed { delay_ms: 250 })
+        );
+        assert!(config.invalid_rules().is_empty());
+    }
+
+    #[test]
+    fn zero_timeout_and_shrinking_backoff_are_reported() {
+        let mut config = NodePoliciesConfig::default();
+        config.insert(
+            "flaky",
+            NodePolicyConfig {
+                timeout_ms: Some(0),
+                backoff: Some(BackoffConfig::Exponential {
+                    initial_ms: 100,
+                    multiplier: 0.5,
+                    max_ms: 1000,
+                }),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            config.invalid_rules(),
+            vec!["nodes.flaky.backoff", "nodes.flaky.timeout_ms"]
+        );
+    }
+}
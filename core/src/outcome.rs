@@ -12,6 +12,7 @@
 //!   schema-described JSON values validated at their adapter boundary
 
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use uuid::Uuid;
 
 // Import anyhow for the into_result conversion
@@ -25,6 +26,34 @@ mod __private {
 pub type BranchId = String;
 pub type NodeId = Uuid;
 
+/// A closed set of branch IDs, typically a fieldless enum.
+///
+/// `#[derive(BranchKey)]` (from `ranvier-macros`) implements this trait and
+/// `From<Key> for BranchId`, so a key can be passed wherever a branch ID is
+/// expected and a typo becomes a compile error:
+///
+/// ```rust,ignore
+/// #[derive(Debug, Clone, Copy, PartialEq, BranchKey)]
+/// enum Route {
+///     Admin,                         // "admin"
+///     #[branch_key(rename = "api_route")]
+///     Api,
+/// }
+///
+/// Outcome::branch(Route::Admin, None);
+/// axon.branch(|b| b.on(Route::Admin, admin_axon).otherwise(default_axon));
+/// ```
+pub trait BranchKey: Sized {
+    /// The branch ID this key stands for.
+    fn branch_id(&self) -> &'static str;
+
+    /// Parse a branch ID back into its key.
+    fn from_branch_id(id: &str) -> Option<Self>;
+
+    /// Every branch ID of the key type, in declaration order.
+    fn branch_ids() -> &'static [&'static str];
+}
+
 /// The explicit result of a transition in the Axon.
 ///
 /// Every transition returns an `Outcome` that determines:
@@ -38,13 +67,15 @@ pub type NodeId = Uuid;
 /// * **Branch(id, payload)** - Branch to a named path with serialized payload
 /// * **Jump(id, payload)** - Jump to a specific Node ID (loop/goto)
 /// * **Emit(event_type, payload)** - Emit a side-effect event
+/// * **Suspend(token, state)** - Pause until resumed with `token`
+/// * **Retry { after }** - Transient failure; run the same node again after `after`
 /// * **Fault(E)** - An error occurred (error path)
 ///
 /// ## Serialization
 ///
 /// All variants are serializable to support Schematic JSON export.
-/// `Next(T)` and `Fault(E)` retain their Rust types. `Branch`, `Jump`, and
-/// `Emit` intentionally use `serde_json::Value` for cross-boundary control
+/// `Next(T)` and `Fault(E)` retain their Rust types. `Branch`, `Jump`,
+/// `Emit`, and `Suspend` intentionally use `serde_json::Value` for cross-boundary control
 /// payloads. Callers that need a domain type must deserialize and validate the
 /// payload at the receiving boundary; the compiler cannot prove that schema.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +95,16 @@ pub enum Outcome<T, E> {
     /// This acts as a signal carrier without breaking the flow.
     Emit(String, Option<serde_json::Value>),
 
+    /// Pause the execution until it is resumed with the given token
+    /// (e.g. waiting for a webhook or a human approval).
+    /// The state is handed back to the suspended node as its input on resume.
+    Suspend(String, Option<serde_json::Value>),
+
+    /// Run the same node again after the delay (transient failure).
+    /// The runtime honors this when an `OutcomeRetryPolicy` is in the Bus;
+    /// otherwise, or once its retries are used up, it ends the execution.
+    Retry { after: Duration },
+
     /// A structural fault (Error path)
     Fault(E),
 }
@@ -71,13 +112,15 @@ pub enum Outcome<T, E> {
 impl<T, E> Outcome<T, E> {
     /// Map the success value through a function.
     ///
-    /// Preserves control flow variants (Branch, Jump, Emit, Fault) unchanged.
+    /// Preserves control flow variants (Branch, Jump, Emit, Suspend, Retry, Fault) unchanged.
     pub fn map<U, F: FnOnce(T) -> U>(self, op: F) -> Outcome<U, E> {
         match self {
             Outcome::Next(t) => Outcome::Next(op(t)),
             Outcome::Branch(id, payload) => Outcome::Branch(id, payload),
             Outcome::Jump(id, payload) => Outcome::Jump(id, payload),
             Outcome::Emit(evt, payload) => Outcome::Emit(evt, payload),
+            Outcome::Suspend(token, state) => Outcome::Suspend(token, state),
+            Outcome::Retry { after } => Outcome::Retry { after },
             Outcome::Fault(e) => Outcome::Fault(e),
         }
     }
@@ -91,14 +134,16 @@ impl<T, E> Outcome<T, E> {
             Outcome::Branch(id, payload) => Outcome::Branch(id, payload),
             Outcome::Jump(id, payload) => Outcome::Jump(id, payload),
             Outcome::Emit(evt, payload) => Outcome::Emit(evt, payload),
+            Outcome::Suspend(token, state) => Outcome::Suspend(token, state),
+            Outcome::Retry { after } => Outcome::Retry { after },
             Outcome::Fault(e) => Outcome::Fault(op(e)),
         }
     }
 
     /// Convert a linear outcome to a `Result`.
     ///
-    /// `Next` and `Fault` preserve their values. `Branch`, `Jump`, and `Emit`
-    /// become generic early-termination errors and their identifier and payload
+    /// `Next` and `Fault` preserve their values. `Branch`, `Jump`, `Emit`,
+    /// `Suspend` and `Retry` become generic early-termination errors and their identifier and payload
     /// are discarded. This is a compatibility adapter for callers that only
     /// understand linear success/failure; code that must preserve Ranvier
     /// control flow should pattern-match on `Outcome` instead.
@@ -117,6 +162,8 @@ impl<T, E> Outcome<T, E> {
             Outcome::Branch(_, _) => Err(anyhow::anyhow!("Early termination: Branch").into()),
             Outcome::Jump(_, _) => Err(anyhow::anyhow!("Early termination: Jump").into()),
             Outcome::Emit(_, _) => Err(anyhow::anyhow!("Early termination: Emit").into()),
+            Outcome::Suspend(_, _) => Err(anyhow::anyhow!("Early termination: Suspend").into()),
+            Outcome::Retry { .. } => Err(anyhow::anyhow!("Early termination: Retry").into()),
         }
     }
 
@@ -145,6 +192,27 @@ impl<T, E> Outcome<T, E> {
         matches!(self, Outcome::Emit(_, _))
     }
 
+    /// The branch taken, as a typed [`BranchKey`].
+    ///
+    /// `None` for non-Branch outcomes and for branch IDs the key type does
+    /// not know.
+    pub fn branch_key<K: BranchKey>(&self) -> Option<K> {
+        match self {
+            Outcome::Branch(id, _) => K::from_branch_id(id),
+            _ => None,
+        }
+    }
+
+    /// Check if this outcome suspends the execution.
+    pub fn is_suspend(&self) -> bool {
+        matches!(self, Outcome::Suspend(_, _))
+    }
+
+    /// Check if this outcome asks for the node to be run again.
+    pub fn is_retry(&self) -> bool {
+        matches!(self, Outcome::Retry { .. })
+    }
+
     /// Map the fault (error) value through a function.
     ///
     /// Alias for [`map_err`](Outcome::map_err) using Ranvier's `Fault` naming convention.
@@ -156,17 +224,37 @@ impl<T, E> Outcome<T, E> {
     /// Chain a computation that may produce another Outcome.
     ///
     /// If `self` is `Next(t)`, applies `f(t)` and returns the result.
-    /// All other variants (Branch, Jump, Emit, Fault) are passed through unchanged.
+    /// All other variants (Branch, Jump, Emit, Suspend, Retry, Fault) are passed through unchanged.
     pub fn and_then<U, F: FnOnce(T) -> Outcome<U, E>>(self, op: F) -> Outcome<U, E> {
         match self {
             Outcome::Next(t) => op(t),
             Outcome::Branch(id, payload) => Outcome::Branch(id, payload),
             Outcome::Jump(id, payload) => Outcome::Jump(id, payload),
             Outcome::Emit(evt, payload) => Outcome::Emit(evt, payload),
+            Outcome::Suspend(token, state) => Outcome::Suspend(token, state),
+            Outcome::Retry { after } => Outcome::Retry { after },
             Outcome::Fault(e) => Outcome::Fault(e),
         }
     }
 
+    /// Recover from a fault by computing a new Outcome from the error.
+    ///
+    /// Non-Fault variants are passed through unchanged. Fault causes the
+    /// runtime recorded in the Bus [`FaultChain`](crate::fault::FaultChain)
+    /// are untouched, so a later fault still reports the recovered one as its
+    /// underlying cause.
+    pub fn or_else<F, G: FnOnce(E) -> Outcome<T, F>>(self, op: G) -> Outcome<T, F> {
+        match self {
+            Outcome::Next(t) => Outcome::Next(t),
+            Outcome::Branch(id, payload) => Outcome::Branch(id, payload),
+            Outcome::Jump(id, payload) => Outcome::Jump(id, payload),
+            Outcome::Emit(evt, payload) => Outcome::Emit(evt, payload),
+            Outcome::Suspend(token, state) => Outcome::Suspend(token, state),
+            Outcome::Retry { after } => Outcome::Retry { after },
+            Outcome::Fault(e) => op(e),
+        }
+    }
+
     /// Extract the `Next` value, or return `default` for any other variant.
     pub fn unwrap_or(self, default: T) -> T {
         match self {
@@ -237,6 +325,16 @@ impl<T, E> Outcome<T, E> {
         Self::Emit(event_type.into(), payload)
     }
 
+    /// Create a Suspend outcome with optional JSON state
+    pub fn suspend(token: impl Into<String>, state: Option<serde_json::Value>) -> Self {
+        Self::Suspend(token.into(), state)
+    }
+
+    /// Create a Retry outcome asking to run the node again after `after`
+    pub fn retry_after(after: Duration) -> Self {
+        Self::Retry { after }
+    }
+
     /// Create a Fault outcome
     pub fn fault(error: E) -> Self {
         Self::Fault(error)
@@ -304,6 +402,48 @@ mod tests {
         assert!(mapped.is_emit());
     }
 
+    #[test]
+    fn test_outcome_map_preserves_suspend() {
+        let outcome: Outcome<i32, String> = Outcome::suspend("await-approval", None);
+        let mapped = outcome.map(|x| x * 2);
+        assert!(mapped.is_suspend());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Route {
+        Admin,
+        Api,
+    }
+
+    impl BranchKey for Route {
+        fn branch_id(&self) -> &'static str {
+            match self {
+                Route::Admin => "admin",
+                Route::Api => "api_route",
+            }
+        }
+
+        fn from_branch_id(id: &str) -> Option<Self> {
+            match id {
+                "admin" => Some(Route::Admin),
+                "api_route" => Some(Route::Api),
+                _ => None,
+            }
+        }
+
+        fn branch_ids() -> &'static [&'static str] {
+            &["admin", "api_route"]
+        }
+    }
+
+    #[test]
+    fn test_outcome_branch_key_parses_known_ids() {
+        let outcome: Outcome<(), String> = Outcome::branch(Route::Api.branch_id(), None);
+        assert_eq!(outcome.branch_key::<Route>(), Some(Route::Api));
+        let unknown: Outcome<(), String> = Outcome::branch("admin_route", None);
+        assert_eq!(unknown.branch_key::<Route>(), None);
+    }
+
     #[test]
     fn test_outcome_map_err_transforms_fault() {
         let outcome: Outcome<i32, String> = Outcome::fault("original_error".to_string());
@@ -590,6 +730,17 @@ mod tests {
         assert!(matches!(result, Outcome::Next(30)));
     }
 
+    #[test]
+    fn test_or_else_recovers_fault() {
+        let outcome: Outcome<i32, String> = Outcome::fault("missing".into());
+        let recovered: Outcome<i32, u8> = outcome.or_else(|e| Outcome::Next(e.len() as i32));
+        assert!(matches!(recovered, Outcome::Next(7)));
+
+        let passed: Outcome<i32, u8> =
+            Outcome::<i32, String>::next(1).or_else(|_| Outcome::Fault(0));
+        assert!(matches!(passed, Outcome::Next(1)));
+    }
+
     // ── M342: unwrap_or / unwrap_or_else ──────────────────────────
 
     #[test]
@@ -0,0 +1,280 @@
+//! Declarative rate-limit configuration and observable limiter state.
+//!
+//! Rate limits are declared in the `[rate_limits]` section of `ranvier.toml`
+//! and resolved per request from the route and the caller's tenant tier:
+//!
+//! ```toml
+//! [rate_limits]
+//! default = { max_requests = 100, window_ms = 60000 }
+//!
+//! [rate_limits.tiers.free]
+//! max_requests = 30
+//! window_ms = 60000
+//!
+//! [rate_limits.routes."/api/orders"]
+//! default = { max_requests = 10, window_ms = 1000 }
+//! tiers.premium = { max_requests = 50, window_ms = 1000 }
+//!
+//! [rate_limits.routes."/api/reports/*"]
+//! default = { max_requests = 2, window_ms = 1000 }
+//! ```
+//!
+//! Resolution order (first match wins):
+//! 1. route + tier
+//! 2. route default
+//! 3. global tier
+//! 4. global default
+//!
+//! Route keys match exactly, or by prefix when they end in `/*`. The longest
+//! matching key is preferred.
+//!
+//! Limiters publish their live state through [`RateLimitStateReader`], which
+//! the Inspector serves at `/limits`.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A single token-bucket limit: `max_requests` per `window_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitRule {
+    pub max_requests: u64,
+    pub window_ms: u64,
+}
+
+impl RateLimitRule {
+    pub const fn new(max_requests: u64, window_ms: u64) -> Self {
+        Self {
+            max_requests,
+            window_ms,
+        }
+    }
+
+    /// A rule is usable only when both the budget and the window are non-zero.
+    pub const fn is_valid(&self) -> bool {
+        self.max_requests > 0 && self.window_ms > 0
+    }
+}
+
+/// Per-route limits, optionally refined by tenant tier.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RouteRateLimits {
+    /// Limit applied to callers whose tier has no route-specific entry.
+    pub default: Option<RateLimitRule>,
+    /// Tier-specific limits for this route, keyed by tier name.
+    pub tiers: HashMap<String, RateLimitRule>,
+}
+
+/// The `[rate_limits]` section of `ranvier.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitsConfig {
+    /// Fallback limit when neither route nor tier match.
+    pub default: Option<RateLimitRule>,
+    /// Global limits keyed by tenant tier.
+    pub tiers: HashMap<String, RateLimitRule>,
+    /// Route-specific limits keyed by path (exact, or prefix ending in `/*`).
+    pub routes: HashMap<String, RouteRateLimits>,
+}
+
+/// Result of resolving a request against [`RateLimitsConfig`].
+///
+/// `route` and `tier` carry the configuration keys that produced the match;
+/// they are `None` when resolution fell back past that level. Limiters use
+/// them as the bucket scope so clients hitting distinct configured routes do
+/// not share a budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitMatch {
+    pub route: Option<String>,
+    pub tier: Option<String>,
+    pub rule: RateLimitRule,
+}
+
+impl RateLimitsConfig {
+    /// Returns `true` when no limit is declared anywhere.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.tiers.is_empty() && self.routes.is_empty()
+    }
+
+    /// Resolve the effective limit for a request.
+    pub fn resolve(&self, route: Option<&str>, tier: Option<&str>) -> Option<RateLimitMatch> {
+        if let Some((route_key, route_limits)) = route.and_then(|path| self.match_route(path)) {
+            if let Some((tier_key, rule)) =
+                tier.and_then(|tier| route_limits.tiers.get_key_value(tier))
+            {
+                return Some(RateLimitMatch {
+                    route: Some(route_key.clone()),
+                    tier: Some(tier_key.clone()),
+                    rule: *rule,
+                });
+            }
+            if let Some(rule) = route_limits.default {
+                return Some(RateLimitMatch {
+                    route: Some(route_key.clone()),
+                    tier: None,
+                    rule,
+                });
+            }
+        }
+
+        if let Some((tier_key, rule)) = tier.and_then(|tier| self.tiers.get_key_value(tier)) {
+            return Some(RateLimitMatch {
+                route: None,
+                tier: Some(tier_key.clone()),
+                rule: *rule,
+            });
+        }
+
+        self.default.map(|rule| RateLimitMatch {
+            route: None,
+            tier: None,
+            rule,
+        })
+    }
+
+    /// Every declared rule must have a non-zero budget and window.
+    pub fn invalid_rules(&self) -> Vec<String> {
+        let mut invalid = Vec::new();
+        if self.default.is_some_and(|rule| !rule.is_valid()) {
+            invalid.push("rate_limits.default".to_string());
+        }
+        for (tier, rule) in &self.tiers {
+            if !rule.is_valid() {
+                invalid.push(format!("rate_limits.tiers.{tier}"));
+            }
+        }
+        for (route, limits) in &self.routes {
+            if limits.default.is_some_and(|rule| !rule.is_valid()) {
+                invalid.push(format!("rate_limits.routes.\"{route}\".default"));
+            }
+            for (tier, rule) in &limits.tiers {
+                if !rule.is_valid() {
+                    invalid.push(format!("rate_limits.routes.\"{route}\".tiers.{tier}"));
+                }
+            }
+        }
+        invalid.sort();
+        invalid
+    }
+
+    fn match_route(&self, path: &str) -> Option<(&String, &RouteRateLimits)> {
+        if let Some(exact) = self.routes.get_key_value(path) {
+            return Some(exact);
+        }
+        self.routes
+            .iter()
+            .filter(|(key, _)| {
+                key.strip_suffix("/*")
+                    .is_some_and(|prefix| path == prefix || path.starts_with(&format!("{prefix}/")))
+            })
+            .max_by_key(|(key, _)| key.len())
+    }
+}
+
+/// Live state of one token bucket.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitBucketState {
+    pub route: Option<String>,
+    pub tier: Option<String>,
+    pub client: String,
+    pub tokens_remaining: f64,
+    pub max_requests: u64,
+    pub window_ms: u64,
+}
+
+/// Snapshot of a limiter: the configuration in force plus its active buckets.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitState {
+    pub limiter: String,
+    pub config: RateLimitsConfig,
+    pub buckets: Vec<RateLimitBucketState>,
+}
+
+/// Read-side interface for observing limiter state (served at Inspector `/limits`).
+#[async_trait]
+pub trait RateLimitStateReader: Send + Sync {
+    async fn rate_limit_state(&self) -> RateLimitState;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> RateLimitsConfig {
+        toml::from_str(
+            r#"
+            default = { max_requests = 100, window_ms = 60000 }
+
+            [tiers.free]
+            max_requests = 30
+            window_ms = 60000
+
+            [routes."/api/orders"]
+            default = { max_requests = 10, window_ms = 1000 }
+            tiers.premium = { max_requests = 50, window_ms = 1000 }
+
+            [routes."/api/reports/*"]
+            default = { max_requests = 2, window_ms = 1000 }
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn route_tier_beats_route_default() {
+        let config = sample();
+        let hit = config
+            .resolve(Some("/api/orders"), Some("premium"))
+            .unwrap();
+        assert_eq!(hit.rule, RateLimitRule::new(50, 1000));
+        assert_eq!(hit.route.as_deref(), Some("/api/orders"));
+        assert_eq!(hit.tier.as_deref(), Some("premium"));
+
+        let hit = config.resolve(Some("/api/orders"), Some("free")).unwrap();
+        assert_eq!(hit.rule, RateLimitRule::new(10, 1000));
+        assert_eq!(hit.tier, None);
+    }
+
+    #[test]
+    fn unmatched_route_falls_back_to_tier_then_default() {
+        let config = sample();
+        let hit = config.resolve(Some("/other"), Some("free")).unwrap();
+        assert_eq!(hit.rule, RateLimitRule::new(30, 60000));
+        assert_eq!(hit.route, None);
+
+        let hit = config.resolve(Some("/other"), None).unwrap();
+        assert_eq!(hit.rule, RateLimitRule::new(100, 60000));
+        assert_eq!(hit.tier, None);
+    }
+
+    #[test]
+    fn wildcard_route_matches_prefix() {
+        let config = sample();
+        let hit = config.resolve(Some("/api/reports/42"), None).unwrap();
+        assert_eq!(hit.route.as_deref(), Some("/api/reports/*"));
+        assert!(
+            config
+                .resolve(Some("/api/reportsx"), None)
+                .unwrap()
+                .route
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn empty_config_resolves_nothing() {
+        let config = RateLimitsConfig::default();
+        assert!(config.is_empty());
+        assert!(config.resolve(Some("/"), Some("free")).is_none());
+    }
+
+    #[test]
+    fn zero_rules_are_reported() {
+        let mut config = sample();
+        config
+            .tiers
+            .insert("broken".into(), RateLimitRule::new(0, 1000));
+        assert_eq!(config.invalid_rules(), vec!["rate_limits.tiers.broken"]);
+    }
+}
@@ -0,0 +1,461 @@
+//! Field redaction for captured payloads and timelines.
+//!
+//! [`RedactionRules`] replaces sensitive values with [`REDACTED`] before they
+//! are written anywhere:
+//!
+//! - field name patterns match object keys case-insensitively by substring,
+//!   at any depth (`email` matches `contact_email`);
+//! - type hooks rewrite the JSON of one payload type before the patterns
+//!   apply, for values whose sensitivity does not show in their field names.
+//!
+//! ```rust
+//! # use ranvier_core::redaction::RedactionRules;
+//! # #[derive(serde::Serialize)]
+//! # struct Card { holder: String, number: String }
+//! let rules = RedactionRules::default()
+//!     .field("iban")
+//!     .for_type::<Card>(|value| value["number"] = "****".into());
+//! let json = rules
+//!     .redact(&Card { holder: "Ada".into(), number: "4111111111111111".into() })
+//!     .unwrap();
+//! assert_eq!(json["number"], "****");
+//! ```
+//!
+//! With rules on the Bus, the Axon executor redacts every payload, Bus
+//! snapshot, fault message and cache key it records into the Timeline, so
+//! timeline files, stores and writers never see the raw values.
+//! [`Timeline::redact`] applies the rules to an existing timeline, e.g. one
+//! loaded from disk before it is served.
+
+use crate::bus_snapshot::BusSnapshot;
+use crate::capture::CapturedPayload;
+use crate::fault::FaultCause;
+use crate::timeline::{Timeline, TimelineEvent};
+use serde::Serialize;
+use serde_json::Value;
+use std::any::{TypeId, type_name};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Replacement for redacted values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Environment variable with extra comma-separated field patterns, read by
+/// [`RedactionRules::from_env`].
+pub const REDACT_KEYS_ENV: &str = "RANVIER_TELEMETRY_REDACT_KEYS";
+
+/// Field names redacted by [`RedactionRules::default`].
+pub const DEFAULT_SENSITIVE_FIELDS: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "authorization",
+    "cookie",
+    "session",
+    "api_key",
+    "credit_card",
+    "ssn",
+    "email",
+    "phone",
+];
+
+type TypeHook = Arc<dyn Fn(&mut Value) + Send + Sync>;
+
+/// Field patterns and type hooks applied when payloads are serialized.
+#[derive(Clone)]
+pub struct RedactionRules {
+    patterns: Vec<String>,
+    type_hooks: HashMap<TypeId, (&'static str, TypeHook)>,
+}
+
+impl Default for RedactionRules {
+    /// [`DEFAULT_SENSITIVE_FIELDS`], no type hooks.
+    fn default() -> Self {
+        DEFAULT_SENSITIVE_FIELDS
+            .iter()
+            .fold(Self::empty(), |rules, field| rules.field(*field))
+    }
+}
+
+impl fmt::Debug for RedactionRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut types: Vec<_> = self.type_hooks.values().map(|(name, _)| *name).collect();
+        types.sort_unstable();
+        f.debug_struct("RedactionRules")
+            .field("patterns", &self.patterns)
+            .field("types", &types)
+            .finish()
+    }
+}
+
+impl RedactionRules {
+    /// No patterns and no hooks.
+    pub fn empty() -> Self {
+        Self {
+            patterns: Vec::new(),
+            type_hooks: HashMap::new(),
+        }
+    }
+
+    /// The defaults plus the comma-separated fields in
+    /// `RANVIER_TELEMETRY_REDACT_KEYS`.
+    pub fn from_env() -> Self {
+        Self::try_from_env().unwrap_or_default()
+    }
+
+    /// [`RedactionRules::from_env`], failing when the variable is set but is
+    /// not valid Unicode.
+    pub fn try_from_env() -> Result<Self, std::env::VarError> {
+        let extra = match std::env::var(REDACT_KEYS_ENV) {
+            Ok(extra) => extra,
+            Err(std::env::VarError::NotPresent) => String::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(extra
+            .split(',')
+            .map(str::trim)
+            .fold(Self::default(), |rules, field| rules.field(field)))
+    }
+
+    /// Redact fields whose name contains `pattern`, ignoring case.
+    pub fn field(mut self, pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into().to_ascii_lowercase();
+        if !pattern.is_empty() && !self.patterns.contains(&pattern) {
+            self.patterns.push(pattern);
+        }
+        self
+    }
+
+    /// Rewrite the JSON of every `T` payload with `hook` before the field
+    /// patterns apply. Replaces an earlier hook for `T`.
+    pub fn for_type<T: 'static>(
+        mut self,
+        hook: impl Fn(&mut Value) + Send + Sync + 'static,
+    ) -> Self {
+        self.type_hooks
+            .insert(TypeId::of::<T>(), (type_name::<T>(), Arc::new(hook)));
+        self
+    }
+
+    /// Add the patterns and type hooks of `other`; its hooks replace ours for
+    /// the same type.
+    pub fn merge(mut self, other: &RedactionRules) -> Self {
+        for pattern in &other.patterns {
+            self = self.field(pattern.clone());
+        }
+        self.type_hooks.extend(
+            other
+                .type_hooks
+                .iter()
+                .map(|(id, hook)| (*id, hook.clone())),
+        );
+        self
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    pub fn is_sensitive(&self, field: &str) -> bool {
+        let field = field.to_ascii_lowercase();
+        self.patterns.iter().any(|pattern| field.contains(pattern))
+    }
+
+    /// Serialize `value` to JSON with its type hook and the field patterns
+    /// applied.
+    pub fn redact<T: Serialize + 'static>(&self, value: &T) -> Result<Value, serde_json::Error> {
+        let mut json = serde_json::to_value(value)?;
+        if let Some((_, hook)) = self.type_hooks.get(&TypeId::of::<T>()) {
+            hook(&mut json);
+        }
+        self.redact_value(&mut json);
+        Ok(json)
+    }
+
+    /// Apply the field patterns to `value` in place.
+    pub fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    if self.is_sensitive(key) {
+                        *child = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_value(child);
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|v| self.redact_value(v)),
+            _ => {}
+        }
+    }
+
+    /// Apply the field patterns to a captured payload, keeping its format.
+    ///
+    /// A truncated preview cannot be parsed; it is replaced entirely when it
+    /// mentions a sensitive field.
+    pub fn redact_payload(&self, payload: &mut CapturedPayload) {
+        if payload.is_truncated() {
+            let preview = payload.data.as_str().unwrap_or_default();
+            if self.mentions_sensitive_field(preview) {
+                payload.data = Value::String(REDACTED.to_string());
+            }
+            return;
+        }
+        let Ok(mut json) = payload.to_json() else {
+            return redact_entirely(payload);
+        };
+        let before = json.clone();
+        self.redact_value(&mut json);
+        if json == before {
+            return;
+        }
+        match CapturedPayload::encode(payload.format, &json) {
+            Ok(redacted) => *payload = redacted,
+            Err(_) => redact_entirely(payload),
+        }
+    }
+
+    /// Apply the type hook registered for each entry's type, then the field
+    /// patterns, to the values of a Bus snapshot.
+    pub fn redact_snapshot(&self, snapshot: &mut BusSnapshot) {
+        for entry in &mut snapshot.entries {
+            let Some(value) = entry.value.as_mut() else {
+                continue;
+            };
+            if let Some((_, hook)) = self
+                .type_hooks
+                .values()
+                .find(|(name, _)| *name == entry.type_name)
+            {
+                hook(value);
+            }
+            self.redact_value(value);
+        }
+    }
+
+    /// Apply the field patterns to free text such as an error message.
+    ///
+    /// JSON text is redacted field by field; any other text is replaced
+    /// entirely when it mentions a sensitive field.
+    pub fn redact_text(&self, text: &mut String) {
+        if let Ok(mut json @ (Value::Object(_) | Value::Array(_))) =
+            serde_json::from_str::<Value>(text)
+        {
+            let before = json.clone();
+            self.redact_value(&mut json);
+            if json != before {
+                *text = json.to_string();
+            }
+        } else if self.mentions_sensitive_field(text) {
+            *text = REDACTED.to_string();
+        }
+    }
+
+    /// Apply the field patterns to the error text of a fault cause, its
+    /// retries and its underlying causes.
+    pub fn redact_fault(&self, cause: &mut FaultCause) {
+        self.redact_text(&mut cause.error);
+        for retry in &mut cause.retries {
+            self.redact_text(&mut retry.error);
+        }
+        for cause in &mut cause.causes {
+            self.redact_fault(cause);
+        }
+    }
+
+    /// Apply the field patterns to the payload, Bus snapshot, fault text or
+    /// cache key of `event`.
+    pub fn redact_event(&self, event: &mut TimelineEvent) {
+        match event {
+            TimelineEvent::PayloadCaptured { payload, .. } => self.redact_payload(payload),
+            TimelineEvent::BusCaptured { snapshot, .. } => self.redact_snapshot(snapshot),
+            TimelineEvent::FaultRecorded { cause, .. } => self.redact_fault(cause),
+            TimelineEvent::CacheLookup { key, .. } => self.redact_text(key),
+            _ => {}
+        }
+    }
+
+    fn mentions_sensitive_field(&self, text: &str) -> bool {
+        let text = text.to_ascii_lowercase();
+        self.patterns.iter().any(|pattern| text.contains(pattern))
+    }
+}
+
+/// Replace an undecodable payload with a display-only placeholder.
+fn redact_entirely(payload: &mut CapturedPayload) {
+    payload.truncated_from = Some(payload.encoded_len());
+    payload.data = Value::String(REDACTED.to_string());
+}
+
+impl Timeline {
+    /// Apply the field patterns of `rules` to every captured payload, Bus
+    /// snapshot, fault and cache key in the timeline.
+    pub fn redact(&mut self, rules: &RedactionRules) {
+        for event in &mut self.events {
+            rules.redact_event(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::CaptureFormat;
+    use crate::timeline::{CaptureDirection, Timestamp};
+
+    #[derive(Serialize)]
+    struct Signup {
+        user: String,
+        contact: Contact,
+        referral_code: String,
+    }
+
+    #[derive(Serialize)]
+    struct Contact {
+        #[serde(rename = "Email")]
+        email: String,
+        city: String,
+    }
+
+    #[test]
+    fn rules_redact_fields_types_and_timeline_payloads() {
+        let signup = Signup {
+            user: "ada".into(),
+            contact: Contact {
+                email: "ada@example.com".into(),
+                city: "London".into(),
+            },
+            referral_code: "FRIEND-42".into(),
+        };
+        let rules = RedactionRules::default()
+            .for_type::<Signup>(|value| value["referral_code"] = REDACTED.into());
+        let json = rules.redact(&signup).unwrap();
+        assert_eq!(json["contact"]["Email"], REDACTED);
+        assert_eq!(json["contact"]["city"], "London");
+        assert_eq!(json["referral_code"], REDACTED);
+        assert_eq!(json["user"], "ada");
+
+        let mut timeline = Timeline::new();
+        for format in [CaptureFormat::Json, CaptureFormat::MessagePack] {
+            timeline.push(TimelineEvent::PayloadCaptured {
+                node_id: "n1".into(),
+                direction: CaptureDirection::Input,
+                payload: CapturedPayload::encode(format, &signup).unwrap(),
+                timestamp: Timestamp::from_millis(1),
+                trace: None,
+            });
+        }
+        timeline.push(TimelineEvent::PayloadCaptured {
+            node_id: "n1".into(),
+            direction: CaptureDirection::Output,
+            payload: CapturedPayload::encode(CaptureFormat::Json, &signup)
+                .unwrap()
+                .truncate(40),
+            timestamp: Timestamp::from_millis(2),
+            trace: None,
+        });
+        timeline.redact(&RedactionRules::empty().field("email"));
+
+        let payloads: Vec<_> = timeline
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                TimelineEvent::PayloadCaptured { payload, .. } => Some(payload),
+                _ => None,
+            })
+            .collect();
+        for payload in &payloads[..2] {
+            let json = payload.to_json().unwrap();
+            assert_eq!(json["contact"]["Email"], REDACTED);
+            assert_eq!(json["referral_code"], "FRIEND-42");
+        }
+        assert_eq!(payloads[1].format, CaptureFormat::MessagePack);
+        assert_eq!(payloads[2].data, REDACTED);
+    }
+
+    #[test]
+    fn merged_rules_keep_both_patterns_and_hooks() {
+        let merged = RedactionRules::empty().field("iban").merge(
+            &RedactionRules::empty()
+                .field("IBAN")
+                .field("email")
+                .for_type::<Signup>(|value| value["user"] = REDACTED.into()),
+        );
+        assert_eq!(merged.patterns(), ["iban", "email"]);
+        let json = merged
+            .redact(&Signup {
+                user: "ada".into(),
+                contact: Contact {
+                    email: "ada@example.com".into(),
+                    city: "London".into(),
+                },
+                referral_code: "FRIEND-42".into(),
+            })
+            .unwrap();
+        assert_eq!(json["user"], REDACTED);
+        assert_eq!(json["contact"]["Email"], REDACTED);
+    }
+
+    #[test]
+    fn faults_cache_keys_and_bus_snapshots_are_redacted() {
+        use crate::bus_snapshot::BusSnapshotEntry;
+
+        let rules = RedactionRules::default()
+            .for_type::<Signup>(|value| value["referral_code"] = REDACTED.into());
+        let mut cause = FaultCause::new("n1", "Signup", r#"{"email":"ada@example.com","code":7}"#);
+        cause.causes.push(FaultCause::new(
+            "n1",
+            "Signup",
+            "Rejected { password: \"hunter2\" }",
+        ));
+        let mut timeline = Timeline::new();
+        timeline.push(TimelineEvent::FaultRecorded {
+            node_id: "n1".into(),
+            cause,
+            timestamp: Timestamp::from_millis(1),
+            trace: None,
+        });
+        timeline.push(TimelineEvent::CacheLookup {
+            node_id: "n1".into(),
+            key: "Lookup:session=abc".into(),
+            hit: false,
+            timestamp: Timestamp::from_millis(2),
+            trace: None,
+        });
+        timeline.push(TimelineEvent::BusCaptured {
+            node_id: "n1".into(),
+            snapshot: BusSnapshot::new(vec![BusSnapshotEntry {
+                type_name: type_name::<Signup>().to_string(),
+                name: None,
+                value: Some(serde_json::json!({
+                    "user": "ada",
+                    "referral_code": "FRIEND-42",
+                    "contact": { "Email": "ada@example.com", "city": "London" },
+                })),
+            }]),
+            timestamp: Timestamp::from_millis(3),
+            trace: None,
+        });
+        timeline.redact(&rules);
+
+        let TimelineEvent::FaultRecorded { cause, .. } = &timeline.events[0] else {
+            panic!("fault event");
+        };
+        let error: Value = serde_json::from_str(&cause.error).unwrap();
+        assert_eq!(error["email"], REDACTED);
+        assert_eq!(error["code"], 7);
+        assert_eq!(cause.causes[0].error, REDACTED);
+        let TimelineEvent::CacheLookup { key, .. } = &timeline.events[1] else {
+            panic!("cache event");
+        };
+        assert_eq!(key, REDACTED);
+        let TimelineEvent::BusCaptured { snapshot, .. } = &timeline.events[2] else {
+            panic!("bus event");
+        };
+        let value = snapshot.entries[0].value.as_ref().unwrap();
+        assert_eq!(value["referral_code"], REDACTED);
+        assert_eq!(value["contact"]["Email"], REDACTED);
+        assert_eq!(value["user"], "ada");
+    }
+}
@@ -0,0 +1,385 @@
+//! # Retry: Fault Retry Decorator
+//!
+//! [`Retry`] wraps any Transition and re-runs it when it returns
+//! `Outcome::Fault`, waiting an exponentially growing, jittered delay between
+//! attempts:
+//!
+//! ```rust,ignore
+//! let axon = Axon::new("Checkout")
+//!     .then(
+//!         Retry::new(ChargeCard)
+//!             .max_attempts(4)
+//!             .backoff(Duration::from_millis(50), 2.0, Duration::from_secs(2))
+//!             .retry_if(|err: &PaymentError| err.is_transient()),
+//!     );
+//! ```
+//!
+//! Each retry is recorded as a [`TimelineEvent::NodeRetry`] when a
+//! [`Timeline`] is in the Bus. Only faults are retried; `Next`, `Branch`,
+//! `Jump` and `Emit` are returned immediately. The last fault is also
+//! returned once the Bus cancellation token fires, or when the next attempt
+//! would start after the execution's [`Deadline`].
+
+use crate::bus::{Bus, BusAccessPolicy};
+use crate::deadline::Deadline;
+use crate::outcome::Outcome;
+use crate::timeline::{Timeline, TimelineEvent, Timestamp};
+use crate::transition::{SideEffect, Transition};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+type RetryPredicate<E> = Arc<dyn Fn(&E) -> bool + Send + Sync>;
+
+/// A wrapper Transition that retries faults of the inner Transition.
+pub struct Retry<T, E> {
+    inner: T,
+    max_attempts: u32,
+    initial_delay: Duration,
+    multiplier: f64,
+    max_delay: Duration,
+    jitter: f64,
+    predicate: Option<RetryPredicate<E>>,
+}
+
+impl<T: Clone, E> Clone for Retry<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            max_attempts: self.max_attempts,
+            initial_delay: self.initial_delay,
+            multiplier: self.multiplier,
+            max_delay: self.max_delay,
+            jitter: self.jitter,
+            predicate: self.predicate.clone(),
+        }
+    }
+}
+
+impl<T, E> Retry<T, E> {
+    /// Defaults: 3 attempts, 100 ms initial delay doubling up to 10 s,
+    /// 50% jitter, every fault retried.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(100),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(10),
+            jitter: 0.5,
+            predicate: None,
+        }
+    }
+
+    /// Total number of runs, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Delay before retry `n` (0-based) is `initial * multiplier^n`, capped
+    /// at `max`.
+    pub fn backoff(mut self, initial: Duration, multiplier: f64, max: Duration) -> Self {
+        self.initial_delay = initial;
+        self.multiplier = multiplier.max(1.0);
+        self.max_delay = max;
+        self
+    }
+
+    /// Randomly shorten each delay by up to `fraction` of it (`0.0..=1.0`),
+    /// so concurrent executions don't retry in lockstep.
+    pub fn jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Only retry faults for which `predicate` returns `true`.
+    pub fn retry_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Delay before retry `retry` (0-based), without jitter.
+    pub fn base_delay(&self, retry: u32) -> Duration {
+        let scaled = self.initial_delay.as_secs_f64() * self.multiplier.powi(retry as i32);
+        Duration::from_secs_f64(scaled.min(self.max_delay.as_secs_f64()))
+    }
+
+    fn jittered_delay(&self, retry: u32) -> Duration {
+        let base = self.base_delay(retry);
+        if self.jitter == 0.0 {
+            return base;
+        }
+        let random =
+            (uuid::Uuid::new_v4().as_u128() & ((1 << 53) - 1)) as f64 / (1u64 << 53) as f64;
+        base.mul_f64(1.0 - self.jitter * random)
+    }
+}
+
+/// The node currently executing, from the most recent `NodeEnter`.
+pub(crate) fn current_node_id(timeline: &Timeline) -> Option<String> {
+    timeline.events.iter().rev().find_map(|event| match event {
+        TimelineEvent::NodeEnter { node_id, .. } => Some(node_id.clone()),
+        _ => None,
+    })
+}
+
+#[async_trait]
+impl<T, From, To> Transition<From, To> for Retry<T, T::Error>
+where
+    T: Transition<From, To>,
+    From: Clone + Send + Sync + 'static,
+    To: Send + 'static,
+{
+    type Error = T::Error;
+    type Resources = T::Resources;
+
+    async fn run(
+        &self,
+        input: From,
+        resources: &Self::Resources,
+        bus: &mut Bus,
+    ) -> Outcome<To, Self::Error> {
+        let mut retry = 0;
+        loop {
+            let result = self.inner.run(input.clone(), resources, bus).await;
+            let Outcome::Fault(error) = &result else {
+                return result;
+            };
+            let retryable = self.predicate.as_ref().is_none_or(|p| p(error));
+            if !retryable || retry + 1 >= self.max_attempts {
+                return result;
+            }
+
+            let delay = self.jittered_delay(retry);
+            let cancellation = bus.cancellation_token().cloned();
+            if cancellation
+                .as_ref()
+                .is_some_and(|token| token.is_cancelled())
+                || bus
+                    .read::<Deadline>()
+                    .is_some_and(|deadline| deadline.remaining() <= delay)
+            {
+                return result;
+            }
+            retry += 1;
+            let label = self.inner.label();
+            tracing::warn!(
+                node = %label,
+                attempt = retry,
+                max_attempts = self.max_attempts,
+                error = ?error,
+                backoff_ms = delay.as_millis() as u64,
+                "Transition faulted, retrying"
+            );
+            let node_id = bus.current_node().map_or(label, str::to_string);
+            if let Some(timeline) = bus.read_mut::<Timeline>() {
+                timeline.push(TimelineEvent::NodeRetry {
+                    node_id,
+                    attempt: retry,
+                    max_attempts: self.max_attempts - 1,
+                    backoff_ms: delay.as_millis() as u64,
+                    timestamp: Timestamp::now(),
+                    trace: None,
+                });
+            }
+            match cancellation {
+                Some(token) => tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = token.cancelled() => return result,
+                },
+                None => tokio::time::sleep(delay).await,
+            }
+        }
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.inner.description()
+    }
+
+    fn position(&self) -> Option<(f32, f32)> {
+        self.inner.position()
+    }
+
+    fn bus_access_policy(&self) -> Option<BusAccessPolicy> {
+        self.inner.bus_access_policy()
+    }
+
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        self.inner.input_schema()
+    }
+
+    fn describe(&self) -> Vec<SideEffect> {
+        self.inner.describe()
+    }
+
+    fn estimate(
+        &self,
+        state: &From,
+        resources: &Self::Resources,
+        bus: &Bus,
+    ) -> Option<Outcome<To, Self::Error>> {
+        self.inner.estimate(state, resources, bus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Clone)]
+    struct Flaky {
+        calls: Arc<AtomicU32>,
+        succeed_on: u32,
+    }
+
+    #[async_trait]
+    impl Transition<u32, u32> for Flaky {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, input: u32, _res: &(), _bus: &mut Bus) -> Outcome<u32, String> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call >= self.succeed_on {
+                Outcome::Next(input + call)
+            } else if call == 1 {
+                Outcome::Fault("transient".to_string())
+            } else {
+                Outcome::Fault("fatal".to_string())
+            }
+        }
+    }
+
+    fn flaky(succeed_on: u32) -> (Flaky, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        (
+            Flaky {
+                calls: calls.clone(),
+                succeed_on,
+            },
+            calls,
+        )
+    }
+
+    #[tokio::test]
+    async fn retries_faults_and_records_timeline_events() {
+        let (inner, calls) = flaky(3);
+        let retry = Retry::new(inner).max_attempts(5).backoff(
+            Duration::from_millis(1),
+            2.0,
+            Duration::from_millis(4),
+        );
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+
+        let outcome = retry.run(10, &(), &mut bus).await;
+
+        assert!(matches!(outcome, Outcome::Next(13)));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let retries: Vec<_> = bus
+            .read::<Timeline>()
+            .unwrap()
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                TimelineEvent::NodeRetry {
+                    node_id, attempt, ..
+                } => Some((node_id.clone(), *attempt)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            retries,
+            [("Flaky".to_string(), 1), ("Flaky".to_string(), 2)]
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_at_max_attempts_and_on_non_retryable_errors() {
+        let (inner, calls) = flaky(10);
+        let retry = Retry::new(inner)
+            .max_attempts(2)
+            .backoff(Duration::ZERO, 2.0, Duration::ZERO);
+        let outcome = retry.run(0, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Fault(ref e) if e == "fatal"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let (inner, calls) = flaky(10);
+        let retry = Retry::new(inner)
+            .max_attempts(5)
+            .backoff(Duration::ZERO, 2.0, Duration::ZERO)
+            .retry_if(|err: &String| err == "transient");
+        let outcome = retry.run(0, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Fault(ref e) if e == "fatal"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn cancellation_and_deadline_end_the_retries() {
+        use crate::cancellation::{CancellationReason, CancellationToken};
+        use std::time::Instant;
+
+        let (inner, calls) = flaky(10);
+        let retry = Retry::new(inner)
+            .max_attempts(5)
+            .backoff(Duration::from_secs(30), 2.0, Duration::from_secs(30))
+            .jitter(0.0);
+        let token = CancellationToken::new();
+        let mut bus = Bus::new();
+        bus.set_cancellation_token(token.clone());
+        bus.insert(Timeline::new());
+        bus.set_current_node(Some("node-1".to_string()));
+        let started = Instant::now();
+        let (outcome, _) = tokio::join!(retry.run(0, &(), &mut bus), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            token.cancel(CancellationReason::Explicit);
+        });
+        assert!(matches!(outcome, Outcome::Fault(ref e) if e == "transient"));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let retried_node = bus
+            .read::<Timeline>()
+            .unwrap()
+            .events
+            .iter()
+            .find_map(|event| match event {
+                TimelineEvent::NodeRetry { node_id, .. } => Some(node_id.clone()),
+                _ => None,
+            });
+        assert_eq!(retried_node.as_deref(), Some("node-1"));
+
+        let (inner, calls) = flaky(10);
+        let retry = Retry::new(inner).max_attempts(5).backoff(
+            Duration::from_secs(30),
+            2.0,
+            Duration::from_secs(30),
+        );
+        let mut bus = Bus::new();
+        bus.insert(Deadline::after(Duration::from_secs(1)));
+        let outcome = retry.run(0, &(), &mut bus).await;
+        assert!(matches!(outcome, Outcome::Fault(ref e) if e == "transient"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn backoff_grows_to_the_cap_and_jitter_only_shortens() {
+        let retry: Retry<(), String> = Retry::new(())
+            .backoff(Duration::from_millis(100), 2.0, Duration::from_millis(500))
+            .jitter(0.5);
+        assert_eq!(retry.base_delay(0), Duration::from_millis(100));
+        assert_eq!(retry.base_delay(2), Duration::from_millis(400));
+        assert_eq!(retry.base_delay(5), Duration::from_millis(500));
+        for _ in 0..32 {
+            let delay = retry.jittered_delay(1);
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+        }
+    }
+}
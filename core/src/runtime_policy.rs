@@ -171,6 +171,8 @@ impl PolicyField {
     pub(crate) const TELEMETRY_SERVICE_NAME_CONFIGURED: Self =
         Self("telemetry_service_name_configured");
     pub(crate) const TELEMETRY_SAMPLE_RATIO: Self = Self("telemetry_sample_ratio");
+    pub(crate) const RATE_LIMITS: Self = Self("rate_limits");
+    pub(crate) const NODES: Self = Self("nodes");
     pub(crate) const ACKNOWLEDGEMENT_CODE: Self = Self("acknowledgement_code");
     pub(crate) const ACKNOWLEDGEMENT_ID: Self = Self("acknowledgement_id");
     pub(crate) const ACKNOWLEDGEMENT_OWNER: Self = Self("acknowledgement_owner");
@@ -17,12 +17,15 @@ pub type SagaCompensationFn<E, Res> = Arc<
 pub struct SagaCompensationRegistry<E, Res> {
     /// Maps node ID to its compensation handler.
     pub handlers: HashMap<String, SagaCompensationFn<E, Res>>,
+    /// Maps node ID to a handler that undoes the node from its output.
+    pub output_handlers: HashMap<String, SagaCompensationFn<E, Res>>,
 }
 
 impl<E, Res> SagaCompensationRegistry<E, Res> {
     pub fn new() -> Self {
         Self {
             handlers: HashMap::new(),
+            output_handlers: HashMap::new(),
         }
     }
 
@@ -33,6 +36,16 @@ impl<E, Res> SagaCompensationRegistry<E, Res> {
     pub fn get(&self, node_id: &str) -> Option<SagaCompensationFn<E, Res>> {
         self.handlers.get(node_id).cloned()
     }
+
+    /// Register a handler that receives the node's serialized output
+    /// ([`SagaTask::output_snapshot`]) instead of its input.
+    pub fn register_output(&mut self, node_id: String, handler: SagaCompensationFn<E, Res>) {
+        self.output_handlers.insert(node_id, handler);
+    }
+
+    pub fn get_output(&self, node_id: &str) -> Option<SagaCompensationFn<E, Res>> {
+        self.output_handlers.get(node_id).cloned()
+    }
 }
 
 /// Defines how the runtime should handle Saga compensations.
@@ -58,6 +71,10 @@ pub struct SagaTask {
     /// JSON-serialized input that was passed to this node.
     /// This is used as the input for the compensation node.
     pub input_snapshot: Vec<u8>,
+    /// JSON-serialized output of this node, for compensations that undo it
+    /// from its result.
+    #[serde(default)]
+    pub output_snapshot: Option<Vec<u8>>,
 }
 
 /// A stack of completed tasks that defines the compensation order (LIFO).
@@ -79,6 +96,23 @@ impl SagaStack {
             node_id,
             node_label,
             input_snapshot,
+            output_snapshot: None,
+        });
+    }
+
+    /// Record a successful step together with its serialized output.
+    pub fn push_with_output(
+        &mut self,
+        node_id: String,
+        node_label: String,
+        input_snapshot: Vec<u8>,
+        output_snapshot: Vec<u8>,
+    ) {
+        self.tasks.push(SagaTask {
+            node_id,
+            node_label,
+            input_snapshot,
+            output_snapshot: Some(output_snapshot),
         });
     }
 
@@ -97,3 +131,55 @@ impl SagaStack {
         self.tasks.clear();
     }
 }
+
+/// What happened to one completed step during a saga rollback.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SagaStepStatus {
+    Compensated,
+    /// The compensation faulted or could not decode its snapshot.
+    Failed {
+        error: String,
+    },
+    /// The step has no compensation registered.
+    NoCompensation,
+}
+
+/// One step visited by a saga rollback.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SagaRollbackStep {
+    pub node_id: String,
+    pub node_label: String,
+    #[serde(flatten)]
+    pub status: SagaStepStatus,
+}
+
+/// Summary of a saga rollback, inserted into the Bus once the rollback ends.
+///
+/// Steps are listed in the order they were compensated (most recent first).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SagaRollbackReport {
+    pub steps: Vec<SagaRollbackStep>,
+}
+
+impl SagaRollbackReport {
+    pub fn compensated(&self) -> usize {
+        self.count(|status| matches!(status, SagaStepStatus::Compensated))
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(|status| matches!(status, SagaStepStatus::Failed { .. }))
+    }
+
+    /// Whether every step that had a compensation was undone.
+    pub fn is_complete(&self) -> bool {
+        self.failed() == 0
+    }
+
+    fn count(&self, predicate: impl Fn(&SagaStepStatus) -> bool) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| predicate(&step.status))
+            .count()
+    }
+}
@@ -1,9 +1,14 @@
+use crate::cache_policy::CachePolicy;
 use crate::metadata::StepMetadata;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+pub mod analysis;
+pub mod bpmn;
+pub mod scaffold;
+
 /// 스키마 버전 상수
 pub const SCHEMA_VERSION: &str = "1.0";
 
@@ -36,11 +41,101 @@ pub fn is_supported_schema_version(version: &str) -> bool {
     major == supported_major
 }
 
+/// Error category for artifacts whose schema versions cannot be combined.
+pub const SCHEMA_VERSION_MISMATCH: &str = "schema_version_mismatch";
+
+/// An artifact whose schema version is not compatible with the one it is
+/// combined with (see [`is_supported_schema_version`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaVersionMismatch {
+    /// What carried the version, e.g. a circuit or projection name.
+    pub artifact: String,
+    pub expected: String,
+    pub found: String,
+}
+
+impl SchemaVersionMismatch {
+    pub fn category(&self) -> &'static str {
+        SCHEMA_VERSION_MISMATCH
+    }
+
+    /// `Err` unless `found` has the same major version as `expected`.
+    pub fn check(
+        artifact: impl Into<String>,
+        expected: &str,
+        found: &str,
+    ) -> Result<(), SchemaVersionMismatch> {
+        match (parse_schema_version(expected), parse_schema_version(found)) {
+            (Some((expected_major, _)), Some((found_major, _)))
+                if expected_major == found_major =>
+            {
+                Ok(())
+            }
+            _ => Err(SchemaVersionMismatch {
+                artifact: artifact.into(),
+                expected: expected.to_string(),
+                found: found.to_string(),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaVersionMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{SCHEMA_VERSION_MISMATCH}: '{}' has schema version {}, expected {}",
+            self.artifact, self.found, self.expected
+        )
+    }
+}
+
+impl std::error::Error for SchemaVersionMismatch {}
+
+impl From<SchemaVersionMismatch> for String {
+    fn from(mismatch: SchemaVersionMismatch) -> Self {
+        mismatch.to_string()
+    }
+}
+
+impl From<SchemaVersionMismatch> for crate::error::RanvierError {
+    fn from(mismatch: SchemaVersionMismatch) -> Self {
+        Self::validation(mismatch.to_string())
+    }
+}
+
+/// Identity of one build of a circuit: its schema version and
+/// [`Schematic::structural_hash`].
+///
+/// Artifacts derived from a schematic (projections, bundles, generated
+/// clients) embed both so they can be matched back to the build they came
+/// from. Displays as `1.0:3f2a…`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SchematicFingerprint {
+    pub schema_version: String,
+    pub hash: String,
+}
+
+impl SchematicFingerprint {
+    /// Whether artifacts of both fingerprints use the same schema major
+    /// version and can be read together.
+    pub fn is_compatible_with(&self, other: &SchematicFingerprint) -> bool {
+        SchemaVersionMismatch::check("", &self.schema_version, &other.schema_version).is_ok()
+    }
+}
+
+impl std::fmt::Display for SchematicFingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.schema_version, self.hash)
+    }
+}
+
 /// The Static Analysis View of a Circuit.
 ///
 /// `Schematic` is the graph representation extracted from the Axon Builder.
 /// It is used for visualization, documentation, and verification.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Schematic {
     /// 스키마 버전 (호환성 체크용)
     #[serde(default = "default_schema_version")]
@@ -54,11 +149,15 @@ pub struct Schematic {
     pub description: Option<String>,
     /// 생성 시각
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
     pub generated_at: Option<DateTime<Utc>>,
     /// 노드 목록
     pub nodes: Vec<Node>,
     /// 엣지 목록
     pub edges: Vec<Edge>,
+    /// HTTP 응답 캐시 정책
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_policy: Option<CachePolicy>,
 }
 
 impl Default for Schematic {
@@ -71,6 +170,7 @@ impl Default for Schematic {
             generated_at: Some(Utc::now()),
             nodes: Vec::new(),
             edges: Vec::new(),
+            cache_policy: None,
         }
     }
 }
@@ -87,6 +187,291 @@ impl Schematic {
         is_supported_schema_version(&self.schema_version)
     }
 
+    /// Stable identity of this build: the schema version together with the
+    /// [`structural_hash`](Self::structural_hash).
+    pub fn fingerprint(&self) -> SchematicFingerprint {
+        SchematicFingerprint {
+            schema_version: self.schema_version.clone(),
+            hash: self.structural_hash(),
+        }
+    }
+
+    /// ID for a node labelled `label` about to be appended to this circuit.
+    ///
+    /// See [`stable_node_id`].
+    pub fn next_node_id(&self, label: &str) -> String {
+        stable_node_id(&self.name, label, self.nodes.len())
+    }
+
+    /// Build-stable fingerprint of the circuit structure.
+    ///
+    /// Node IDs can be overridden and change whenever a step is inserted
+    /// upstream, so the hash covers what does not change between identical
+    /// builds: schema version, name, node kinds,
+    /// labels and I/O types, and edges expressed as node positions. Projection
+    /// artifacts embed it as `schematic_hash` so consumers can detect skew.
+    pub fn structural_hash(&self) -> String {
+        let mut hasher = Fnv1a::default();
+        self.feed_structure(&mut hasher);
+        format!("{:016x}", hasher.0)
+    }
+
+    fn feed_structure(&self, hasher: &mut Fnv1a) {
+        hasher.write(&self.schema_version);
+        hasher.write(&self.name);
+        let index: HashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.as_str(), i))
+            .collect();
+        for node in &self.nodes {
+            match &node.kind {
+                NodeKind::Subgraph(inner) => {
+                    hasher.write("Subgraph");
+                    inner.feed_structure(hasher);
+                }
+                kind => hasher.write(&format!("{kind:?}")),
+            }
+            hasher.write(&node.label);
+            hasher.write(&node.input_type);
+            hasher.write(&node.output_type);
+        }
+        for edge in &self.edges {
+            let from = index.get(edge.from.as_str());
+            let to = index.get(edge.to.as_str());
+            hasher.write(&format!("{from:?}>{to:?}:{:?}", edge.kind));
+            hasher.write(edge.label.as_deref().unwrap_or_default());
+        }
+    }
+
+    /// Resolve a jump target, given as a node label or id, to the node id
+    /// that `Outcome::Jump` carries. Nested subgraphs are searched too.
+    pub fn jump_target(&self, target: &str) -> Option<Uuid> {
+        self.nodes.iter().find_map(|node| {
+            if node.id == target || node.label == target {
+                return Uuid::parse_str(&node.id).ok();
+            }
+            match &node.kind {
+                NodeKind::Subgraph(inner) => inner.jump_target(target),
+                _ => None,
+            }
+        })
+    }
+
+    /// Declared jump targets (`StepMetadata::jump_targets`) that name no node.
+    pub fn unresolved_jump_targets(&self) -> Vec<UnresolvedJumpTarget> {
+        let mut unresolved = Vec::new();
+        self.collect_unresolved_jump_targets(self, &mut unresolved);
+        unresolved
+    }
+
+    fn collect_unresolved_jump_targets(
+        &self,
+        root: &Schematic,
+        out: &mut Vec<UnresolvedJumpTarget>,
+    ) {
+        for node in &self.nodes {
+            for target in &node.metadata.jump_targets {
+                if root.jump_target(target).is_none() {
+                    out.push(UnresolvedJumpTarget {
+                        node_id: node.id.clone(),
+                        node_label: node.label.clone(),
+                        target: target.clone(),
+                    });
+                }
+            }
+            if let NodeKind::Subgraph(inner) = &node.kind {
+                inner.collect_unresolved_jump_targets(root, out);
+            }
+        }
+    }
+
+    /// Render the circuit as a Graphviz `digraph`.
+    ///
+    /// Each node kind gets its own shape, branch edges carry their branch id,
+    /// and jump, fault and parallel edges are drawn dashed, red and bold. A
+    /// subgraph node is followed by a cluster holding its inner circuit.
+    ///
+    /// ```rust
+    /// # use ranvier_core::Schematic;
+    /// let dot = Schematic::new("Checkout").to_dot();
+    /// assert!(dot.starts_with("digraph \"Checkout\" {"));
+    /// ```
+    ///
+    /// Render with `dot -Tsvg checkout.dot -o checkout.svg`.
+    pub fn to_dot(&self) -> String {
+        let mut out = format!("digraph {} {{\n", dot_quote(&self.name));
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [fontname=\"Helvetica\", style=filled, fillcolor=\"#ffffff\"];\n");
+        out.push_str("    edge [fontname=\"Helvetica\", fontsize=10];\n");
+        self.write_dot_body(&mut out, 1);
+        out.push_str("}\n");
+        out
+    }
+
+    fn write_dot_body(&self, out: &mut String, depth: usize) {
+        use std::fmt::Write;
+
+        let indent = "    ".repeat(depth);
+        for node in &self.nodes {
+            let (shape, fill) = match &node.kind {
+                NodeKind::Ingress => ("invhouse", "#d5f5e3"),
+                NodeKind::Atom => ("box", "#ffffff"),
+                NodeKind::Synapse => ("diamond", "#fdebd0"),
+                NodeKind::Egress => ("house", "#d6eaf8"),
+                NodeKind::Subgraph(_) => ("box3d", "#e8daef"),
+                NodeKind::FanOut => ("trapezium", "#fcf3cf"),
+                NodeKind::FanIn => ("invtrapezium", "#fcf3cf"),
+                NodeKind::StreamingTransition => ("parallelogram", "#d1f2eb"),
+                NodeKind::Tap => ("note", "#f2f3f4"),
+            };
+            let tooltip = node
+                .description
+                .as_ref()
+                .or(node.metadata.description.as_ref())
+                .map(|description| format!(", tooltip={}", dot_quote(description)))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "{indent}{} [label={}, shape={shape}, fillcolor=\"{fill}\"{tooltip}];",
+                dot_quote(&node.id),
+                dot_quote(&node.label),
+            );
+            if let NodeKind::Subgraph(inner) = &node.kind {
+                let _ = writeln!(
+                    out,
+                    "{indent}subgraph {} {{",
+                    dot_quote(&format!("cluster_{}", node.id))
+                );
+                let _ = writeln!(out, "{indent}    label={};", dot_quote(&inner.name));
+                let _ = writeln!(out, "{indent}    style=dashed;");
+                inner.write_dot_body(out, depth + 1);
+                let _ = writeln!(out, "{indent}}}");
+                if let Some(first) = inner.nodes.first() {
+                    let _ = writeln!(
+                        out,
+                        "{indent}{} -> {} [style=dotted, arrowhead=none];",
+                        dot_quote(&node.id),
+                        dot_quote(&first.id),
+                    );
+                }
+            }
+        }
+        for edge in &self.edges {
+            let mut attrs = Vec::new();
+            let label = match (&edge.label, &edge.kind) {
+                (Some(label), _) => Some(label.as_str()),
+                (None, EdgeType::Branch(id)) => Some(id.as_str()),
+                (None, EdgeType::Fault) => Some("fault"),
+                _ => None,
+            };
+            if let Some(label) = label {
+                attrs.push(format!("label={}", dot_quote(label)));
+            }
+            match edge.kind {
+                EdgeType::Linear | EdgeType::Branch(_) => {}
+                EdgeType::Jump => attrs.push("style=dashed".into()),
+                EdgeType::Fault => attrs.push("style=dashed, color=\"#c0392b\"".into()),
+                EdgeType::Parallel => attrs.push("style=bold".into()),
+            }
+            let attrs = if attrs.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attrs.join(", "))
+            };
+            let _ = writeln!(
+                out,
+                "{indent}{} -> {}{attrs};",
+                dot_quote(&edge.from),
+                dot_quote(&edge.to),
+            );
+        }
+    }
+
+    /// Render the circuit as a Mermaid `flowchart`, ready to paste into a
+    /// ` ```mermaid ` block.
+    ///
+    /// Node ids are replaced by short `n0`, `n1`, ... ids. Subgraph nodes
+    /// become nested `subgraph` blocks, and branch edges carry their branch
+    /// id as label.
+    ///
+    /// ```rust
+    /// # use ranvier_core::Schematic;
+    /// let mermaid = Schematic::new("Checkout").to_mermaid();
+    /// assert!(mermaid.starts_with("flowchart LR"));
+    /// ```
+    pub fn to_mermaid(&self) -> String {
+        let mut ids = HashMap::new();
+        self.assign_mermaid_ids(&mut ids);
+        let mut out = String::from("flowchart LR\n");
+        self.write_mermaid_body(&mut out, 1, &ids);
+        out
+    }
+
+    fn assign_mermaid_ids<'a>(&'a self, ids: &mut HashMap<&'a str, String>) {
+        for node in &self.nodes {
+            let next = format!("n{}", ids.len());
+            ids.entry(node.id.as_str()).or_insert(next);
+            if let NodeKind::Subgraph(inner) = &node.kind {
+                inner.assign_mermaid_ids(ids);
+            }
+        }
+    }
+
+    fn write_mermaid_body(&self, out: &mut String, depth: usize, ids: &HashMap<&str, String>) {
+        use std::fmt::Write;
+
+        let indent = "    ".repeat(depth);
+        let id = |node_id: &str| {
+            ids.get(node_id)
+                .cloned()
+                .unwrap_or_else(|| mermaid_quote(node_id))
+        };
+        for node in &self.nodes {
+            let label = mermaid_quote(&node.label);
+            let shape = match &node.kind {
+                NodeKind::Subgraph(inner) => {
+                    let _ = writeln!(out, "{indent}subgraph {} [{label}]", id(&node.id));
+                    inner.write_mermaid_body(out, depth + 1, ids);
+                    let _ = writeln!(out, "{indent}end");
+                    continue;
+                }
+                NodeKind::Ingress => format!("([{label}])"),
+                NodeKind::Atom => format!("[{label}]"),
+                NodeKind::Synapse => format!("{{{label}}}"),
+                NodeKind::Egress => format!("(({label}))"),
+                NodeKind::FanOut => format!("[/{label}\\]"),
+                NodeKind::FanIn => format!("[\\{label}/]"),
+                NodeKind::StreamingTransition => format!("[/{label}/]"),
+                NodeKind::Tap => format!(">{label}]"),
+            };
+            let _ = writeln!(out, "{indent}{}{shape}", id(&node.id));
+        }
+        for edge in &self.edges {
+            let label = match (&edge.label, &edge.kind) {
+                (Some(label), _) => Some(label.as_str()),
+                (None, EdgeType::Branch(id)) => Some(id.as_str()),
+                (None, EdgeType::Fault) => Some("fault"),
+                _ => None,
+            };
+            let arrow = match edge.kind {
+                EdgeType::Linear | EdgeType::Branch(_) => "-->",
+                EdgeType::Jump | EdgeType::Fault => "-.->",
+                EdgeType::Parallel => "==>",
+            };
+            let label = label
+                .map(|label| format!("|{}|", mermaid_quote(label)))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "{indent}{} {arrow}{label} {}",
+                id(&edge.from),
+                id(&edge.to)
+            );
+        }
+    }
+
     /// 기존 ID를 유지하면서 새 Schematic 생성
     pub fn with_id(name: impl Into<String>, id: impl Into<String>) -> Self {
         Self {
@@ -97,8 +482,107 @@ impl Schematic {
     }
 }
 
+/// Quote a Graphviz ID or label.
+fn dot_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Quote a Mermaid label. Mermaid has no backslash escapes, so quotes and
+/// line breaks use its entity codes.
+fn mermaid_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("#quot;"),
+            '\n' => quoted.push_str("<br/>"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A declared jump target that names no node of the circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresolvedJumpTarget {
+    pub node_id: String,
+    pub node_label: String,
+    pub target: String,
+}
+
+impl std::fmt::Display for UnresolvedJumpTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}' declares jump target '{}', which is not a node of this circuit",
+            self.node_label, self.target
+        )
+    }
+}
+
+impl std::error::Error for UnresolvedJumpTarget {}
+
+/// 64-bit FNV-1a; fixed constants keep hashes stable across toolchains.
+struct Fnv1a(u64);
+
+/// Deterministic node ID from the circuit (or owning node) `scope`, the
+/// node's `label` and its `position` in the circuit.
+///
+/// Identical builds produce identical IDs, so schematic diffs, traces and
+/// golden files line up across runs and deploys. The ID is a UUID (version 8)
+/// holding a 128-bit FNV-1a hash of the three parts.
+pub fn stable_node_id(scope: &str, label: &str, position: usize) -> String {
+    let mut hash: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    for field in [scope, label, &position.to_string()] {
+        for byte in (field.len() as u64)
+            .to_le_bytes()
+            .iter()
+            .chain(field.as_bytes())
+        {
+            hash ^= u128::from(*byte);
+            hash = hash.wrapping_mul(0x0000_0000_0100_0000_0000_0000_0000_013b);
+        }
+    }
+    uuid::Builder::from_custom_bytes(hash.to_be_bytes())
+        .into_uuid()
+        .to_string()
+}
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, field: &str) {
+        // Length prefix keeps ("ab", "c") distinct from ("a", "bc").
+        for byte in (field.len() as u64)
+            .to_le_bytes()
+            .iter()
+            .chain(field.as_bytes())
+        {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
 /// 소스 코드 위치 정보 (Studio Code↔Node 매핑용)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SourceLocation {
     /// 파일 경로 (프로젝트 루트 기준 상대 경로)
     pub file: String,
@@ -107,6 +591,9 @@ pub struct SourceLocation {
     /// 컬럼 번호 (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column: Option<u32>,
+    /// 모듈 경로 (`module_path!()`, optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module_path: Option<String>,
 }
 
 impl SourceLocation {
@@ -115,6 +602,7 @@ impl SourceLocation {
             file: file.into(),
             line,
             column: None,
+            module_path: None,
         }
     }
 
@@ -123,17 +611,32 @@ impl SourceLocation {
             file: file.into(),
             line,
             column: Some(column),
+            module_path: None,
         }
     }
+
+    pub fn with_module_path(mut self, module_path: impl Into<String>) -> Self {
+        self.module_path = Some(module_path.into());
+        self
+    }
+}
+
+/// The call site captured by a `#[track_caller]` builder method.
+impl From<&std::panic::Location<'_>> for SourceLocation {
+    fn from(location: &std::panic::Location<'_>) -> Self {
+        Self::with_column(location.file(), location.line(), location.column())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Position {
     pub x: f32,
     pub y: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Node {
     pub id: String, // Uuid typically
     pub kind: NodeKind,
@@ -176,6 +679,7 @@ pub struct Node {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BusCapabilitySchema {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub allow: Vec<String>,
@@ -184,6 +688,7 @@ pub struct BusCapabilitySchema {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum NodeKind {
     Ingress,                  // Handler / Start
     Atom,                     // Single action
@@ -193,9 +698,11 @@ pub enum NodeKind {
     FanOut,                   // Parallel split point
     FanIn,                    // Parallel join point
     StreamingTransition,      // Streaming data producer (terminal node)
+    Tap,                      // Side-effect-only observer, state passes through
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EdgeType {
     Linear,         // Outcome::Next
     Branch(String), // Outcome::Branch(id)
@@ -204,7 +711,8 @@ pub enum EdgeType {
     Parallel,       // Parallel branch (FanOut -> branch)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Edge {
     pub from: String,
     pub to: String,
@@ -349,6 +857,116 @@ impl MigrationRegistry {
 mod tests {
     use super::*;
 
+    fn linear(name: &str, labels: &[&str]) -> Schematic {
+        let mut schematic = Schematic::new(name);
+        for label in labels {
+            schematic.nodes.push(Node {
+                id: Uuid::new_v4().to_string(),
+                kind: NodeKind::Atom,
+                label: label.to_string(),
+                description: None,
+                input_type: "i32".into(),
+                output_type: "i32".into(),
+                resource_type: "()".into(),
+                metadata: StepMetadata::default(),
+                bus_capability: None,
+                source_location: None,
+                position: None,
+                compensation_node_id: None,
+                input_schema: None,
+                output_schema: None,
+                item_type: None,
+                terminal: None,
+            });
+        }
+        for pair in schematic.nodes.clone().windows(2) {
+            schematic.edges.push(Edge {
+                from: pair[0].id.clone(),
+                to: pair[1].id.clone(),
+                kind: EdgeType::Linear,
+                label: None,
+            });
+        }
+        schematic
+    }
+
+    #[test]
+    fn structural_hash_ignores_ids_but_tracks_shape() {
+        let a = linear("orders", &["validate", "charge"]);
+        let b = linear("orders", &["validate", "charge"]);
+        assert_ne!(a.id, b.id);
+        assert_eq!(a.structural_hash(), b.structural_hash());
+        assert_ne!(
+            a.structural_hash(),
+            linear("orders", &["validate", "charge", "ship"]).structural_hash()
+        );
+    }
+
+    #[test]
+    fn to_dot_styles_kinds_and_labels_branches() {
+        let mut schematic = linear("orders", &["validate", "charge \"card\""]);
+        schematic.nodes[0].kind = NodeKind::Ingress;
+        schematic.nodes[1].description = Some("Charges the card on file".into());
+        let mut refund = linear("refund", &["refund"]);
+        refund.nodes[0].kind = NodeKind::Synapse;
+        let mut sub = linear("sub", &["Refunds"]).nodes.remove(0);
+        sub.kind = NodeKind::Subgraph(Box::new(refund.clone()));
+        schematic.edges.push(Edge {
+            from: schematic.nodes[1].id.clone(),
+            to: sub.id.clone(),
+            kind: EdgeType::Branch("declined".into()),
+            label: None,
+        });
+        schematic.nodes.push(sub.clone());
+
+        let dot = schematic.to_dot();
+        assert!(dot.starts_with("digraph \"orders\" {\n"));
+        assert!(dot.contains("[label=\"validate\", shape=invhouse"));
+        assert!(dot.contains("[label=\"charge \\\"card\\\"\", shape=box"));
+        assert!(dot.contains("tooltip=\"Charges the card on file\"]"));
+        assert!(dot.contains("shape=box3d"));
+        assert!(dot.contains(&format!("subgraph \"cluster_{}\" {{", sub.id)));
+        assert!(dot.contains("shape=diamond"));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [label=\"declined\"];",
+            schematic.nodes[1].id, sub.id
+        )));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\";",
+            schematic.nodes[0].id, schematic.nodes[1].id
+        )));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn to_mermaid_nests_subgraphs_and_labels_branches() {
+        let mut schematic = linear("orders", &["validate", "charge \"card\""]);
+        schematic.nodes[0].kind = NodeKind::Ingress;
+        let mut sub = linear("sub", &["Refunds"]).nodes.remove(0);
+        sub.kind = NodeKind::Subgraph(Box::new(linear("refund", &["refund", "notify"])));
+        schematic.edges.push(Edge {
+            from: schematic.nodes[1].id.clone(),
+            to: sub.id.clone(),
+            kind: EdgeType::Branch("declined".into()),
+            label: None,
+        });
+        schematic.nodes.push(sub);
+
+        assert_eq!(
+            schematic.to_mermaid(),
+            "flowchart LR\n\
+             \x20   n0([\"validate\"])\n\
+             \x20   n1[\"charge #quot;card#quot;\"]\n\
+             \x20   subgraph n2 [\"Refunds\"]\n\
+             \x20       n3[\"refund\"]\n\
+             \x20       n4[\"notify\"]\n\
+             \x20       n3 --> n4\n\
+             \x20   end\n\
+             \x20   n0 --> n1\n\
+             \x20   n1 -->|\"declined\"| n2\n"
+        );
+    }
+
     #[test]
     fn test_schematic_default_has_version_and_id() {
         let schematic = Schematic::new("Test Circuit");
@@ -377,6 +995,48 @@ mod tests {
         assert!(!json.contains("description"));
     }
 
+    #[test]
+    fn fingerprint_pairs_schema_version_with_structure() {
+        let a = linear("orders", &["validate", "charge"]);
+        let fingerprint = a.fingerprint();
+        assert_eq!(fingerprint.schema_version, SCHEMA_VERSION);
+        assert_eq!(fingerprint.hash, a.structural_hash());
+        assert_eq!(
+            fingerprint.to_string(),
+            format!("1.0:{}", a.structural_hash())
+        );
+        assert_eq!(
+            fingerprint,
+            linear("orders", &["validate", "charge"]).fingerprint()
+        );
+
+        let mut next_major = a.clone();
+        next_major.schema_version = "2.0".into();
+        assert_ne!(next_major.fingerprint(), fingerprint);
+        assert!(!next_major.fingerprint().is_compatible_with(&fingerprint));
+        let mut next_minor = a;
+        next_minor.schema_version = "1.3".into();
+        assert!(next_minor.fingerprint().is_compatible_with(&fingerprint));
+    }
+
+    #[test]
+    fn stable_node_id_depends_only_on_its_parts() {
+        let id = stable_node_id("Checkout", "Charge", 2);
+        assert_eq!(id, stable_node_id("Checkout", "Charge", 2));
+        assert_ne!(id, stable_node_id("Checkout", "Charge", 3));
+        assert_ne!(id, stable_node_id("Checkout", "Refund", 2));
+        // Length-prefixed parts: moving characters across the boundary matters.
+        assert_ne!(stable_node_id("ab", "c", 0), stable_node_id("a", "bc", 0));
+        let uuid = Uuid::parse_str(&id).unwrap();
+        assert_eq!(uuid.get_version_num(), 8);
+
+        let schematic = linear("Checkout", &["Start"]);
+        assert_eq!(
+            schematic.next_node_id("Charge"),
+            stable_node_id("Checkout", "Charge", 1)
+        );
+    }
+
     #[test]
     fn test_source_location_creation() {
         let loc = SourceLocation::new("src/main.rs", 42);
@@ -386,6 +1046,13 @@ mod tests {
 
         let loc_with_col = SourceLocation::with_column("src/lib.rs", 10, 5);
         assert_eq!(loc_with_col.column, Some(5));
+
+        let here =
+            SourceLocation::from(std::panic::Location::caller()).with_module_path(module_path!());
+        assert_eq!(here.file, file!());
+        assert!(here.column.is_some());
+        let json = serde_json::to_value(&here).unwrap();
+        assert_eq!(json["module_path"], "ranvier_core::schematic::tests");
     }
 
     #[test]
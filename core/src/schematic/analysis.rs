@@ -0,0 +1,443 @@
+//! Graph algorithms over one level of a [`Schematic`].
+//!
+//! The graph is the schematic's edges plus one link from every compensated
+//! node to its compensation node, which the builder records without an edge.
+//! Edges naming a missing node are ignored (`validate_schematic` reports
+//! them). Subgraph nodes are opaque; analyse `NodeKind::Subgraph` contents
+//! separately.
+//!
+//! `Jump` edges are how loops and declared jumps point back into a circuit,
+//! so [`topological_order`] skips them and [`find_cycles`] classifies a
+//! cycle as a [`CycleKind::Loop`] when it needs one, and as
+//! [`CycleKind::Structural`] when it exists without any.
+//!
+//! ```rust,ignore
+//! let analysis = GraphAnalysis::of(&schematic);
+//! for cycle in analysis.cycles.iter().filter(|c| c.kind == CycleKind::Structural) {
+//!     eprintln!("cycle through {:?}", cycle.nodes);
+//! }
+//! ```
+
+use super::{EdgeType, Schematic};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// Whether a cycle goes through a `Jump` edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CycleKind {
+    /// Closed by a `Jump` edge, as loops and declared jumps are.
+    Loop,
+    /// Closed by `Next`, branch, fault or parallel edges alone.
+    Structural,
+}
+
+/// A strongly connected set of nodes, in schematic order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cycle {
+    pub kind: CycleKind,
+    /// Ids of the nodes on the cycle.
+    pub nodes: Vec<String>,
+}
+
+/// Returned by [`topological_order`] when the graph is not acyclic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleError {
+    /// Ids of the nodes that could not be ordered.
+    pub remaining: Vec<String>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "schematic has a cycle through {} node(s): {}",
+            self.remaining.len(),
+            self.remaining.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Edges entering and leaving a node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeDegree {
+    pub node_id: String,
+    pub label: String,
+    pub in_degree: usize,
+    pub out_degree: usize,
+}
+
+impl NodeDegree {
+    /// No edge touches the node.
+    pub fn is_isolated(&self) -> bool {
+        self.in_degree == 0 && self.out_degree == 0
+    }
+}
+
+/// Every analysis of this module for one schematic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphAnalysis {
+    pub circuit: String,
+    /// `None` when the graph has a [`CycleKind::Structural`] cycle.
+    pub topological_order: Option<Vec<String>>,
+    pub cycles: Vec<Cycle>,
+    pub unreachable: Vec<String>,
+    pub degrees: Vec<NodeDegree>,
+}
+
+impl GraphAnalysis {
+    pub fn of(schematic: &Schematic) -> Self {
+        let graph = Graph::new(schematic);
+        Self {
+            circuit: schematic.name.clone(),
+            topological_order: graph.topological_order().ok().map(|order| graph.ids(order)),
+            cycles: graph.cycles(),
+            unreachable: graph.ids(graph.unreachable()),
+            degrees: graph.degrees(),
+        }
+    }
+}
+
+/// Node ids in an order where every node follows its predecessors, ignoring
+/// `Jump` edges. Ties keep schematic order.
+pub fn topological_order(schematic: &Schematic) -> Result<Vec<String>, CycleError> {
+    let graph = Graph::new(schematic);
+    graph
+        .topological_order()
+        .map(|order| graph.ids(order))
+        .map_err(|remaining| CycleError {
+            remaining: graph.ids(remaining),
+        })
+}
+
+/// Every cycle of the graph, one per strongly connected component.
+pub fn find_cycles(schematic: &Schematic) -> Vec<Cycle> {
+    Graph::new(schematic).cycles()
+}
+
+/// Ids of the nodes no path reaches from the start node (the first node).
+pub fn unreachable_nodes(schematic: &Schematic) -> Vec<String> {
+    let graph = Graph::new(schematic);
+    graph.ids(graph.unreachable())
+}
+
+/// In- and out-degree of every node, in schematic order.
+pub fn degrees(schematic: &Schematic) -> Vec<NodeDegree> {
+    Graph::new(schematic).degrees()
+}
+
+/// Index-based adjacency of one schematic level.
+pub(crate) struct Graph<'a> {
+    schematic: &'a Schematic,
+    /// `(to, is_jump)` per node.
+    successors: Vec<Vec<(usize, bool)>>,
+}
+
+impl<'a> Graph<'a> {
+    pub(crate) fn new(schematic: &'a Schematic) -> Self {
+        let index: HashMap<&str, usize> = schematic
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.as_str(), i))
+            .collect();
+        let mut successors = vec![Vec::new(); schematic.nodes.len()];
+        for edge in &schematic.edges {
+            if let (Some(&from), Some(&to)) =
+                (index.get(edge.from.as_str()), index.get(edge.to.as_str()))
+            {
+                successors[from].push((to, matches!(edge.kind, EdgeType::Jump)));
+            }
+        }
+        for (i, node) in schematic.nodes.iter().enumerate() {
+            if let Some(&comp) = node
+                .compensation_node_id
+                .as_deref()
+                .and_then(|id| index.get(id))
+            {
+                successors[i].push((comp, false));
+            }
+        }
+        Self {
+            schematic,
+            successors,
+        }
+    }
+
+    fn ids(&self, indices: Vec<usize>) -> Vec<String> {
+        indices
+            .into_iter()
+            .map(|i| self.schematic.nodes[i].id.clone())
+            .collect()
+    }
+
+    /// Kahn's algorithm over non-`Jump` edges; `Err` holds the nodes left
+    /// on or behind a cycle.
+    fn topological_order(&self) -> Result<Vec<usize>, Vec<usize>> {
+        let n = self.successors.len();
+        let mut in_degree = vec![0usize; n];
+        for &(to, _) in self.successors.iter().flatten().filter(|(_, jump)| !jump) {
+            in_degree[to] += 1;
+        }
+        let mut ready: std::collections::BTreeSet<usize> =
+            (0..n).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(n);
+        while let Some(i) = ready.pop_first() {
+            order.push(i);
+            for &(to, jump) in &self.successors[i] {
+                if !jump {
+                    in_degree[to] -= 1;
+                    if in_degree[to] == 0 {
+                        ready.insert(to);
+                    }
+                }
+            }
+        }
+        if order.len() == n {
+            Ok(order)
+        } else {
+            Err((0..n).filter(|&i| in_degree[i] > 0).collect())
+        }
+    }
+
+    /// Nodes reachable from the start node.
+    pub(crate) fn reachable(&self) -> Vec<bool> {
+        let mut reached = vec![false; self.successors.len()];
+        let mut queue = VecDeque::new();
+        if !reached.is_empty() {
+            reached[0] = true;
+            queue.push_back(0);
+        }
+        while let Some(i) = queue.pop_front() {
+            for &(next, _) in &self.successors[i] {
+                if !reached[next] {
+                    reached[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        reached
+    }
+
+    fn unreachable(&self) -> Vec<usize> {
+        self.reachable()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, reached)| (!reached).then_some(i))
+            .collect()
+    }
+
+    pub(crate) fn degrees(&self) -> Vec<NodeDegree> {
+        let mut in_degree = vec![0usize; self.successors.len()];
+        for &(to, _) in self.successors.iter().flatten() {
+            in_degree[to] += 1;
+        }
+        self.schematic
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| NodeDegree {
+                node_id: node.id.clone(),
+                label: node.label.clone(),
+                in_degree: in_degree[i],
+                out_degree: self.successors[i].len(),
+            })
+            .collect()
+    }
+
+    pub(crate) fn cycles(&self) -> Vec<Cycle> {
+        let structural = self.components(false);
+        self.components(true)
+            .into_iter()
+            .flat_map(|component| {
+                // A component of the full graph contains the structural
+                // cycles among its nodes; whatever remains needs a jump.
+                let inner: Vec<_> = structural
+                    .iter()
+                    .filter(|c| c.iter().all(|i| component.contains(i)))
+                    .cloned()
+                    .collect();
+                let covered: usize = inner.iter().map(Vec::len).sum();
+                let mut cycles: Vec<Cycle> = inner
+                    .into_iter()
+                    .map(|nodes| Cycle {
+                        kind: CycleKind::Structural,
+                        nodes: self.ids(nodes),
+                    })
+                    .collect();
+                if covered < component.len() || cycles.is_empty() {
+                    cycles.push(Cycle {
+                        kind: CycleKind::Loop,
+                        nodes: self.ids(component),
+                    });
+                }
+                cycles
+            })
+            .collect()
+    }
+
+    /// Strongly connected components that form a cycle (more than one node,
+    /// or a self edge), each sorted, in order of their first node.
+    fn components(&self, with_jumps: bool) -> Vec<Vec<usize>> {
+        let n = self.successors.len();
+        let successors = |i: usize| {
+            self.successors[i]
+                .iter()
+                .filter(move |(_, jump)| with_jumps || !jump)
+                .map(|&(to, _)| to)
+        };
+
+        // Iterative Tarjan.
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut next_index = 0;
+        let mut components = Vec::new();
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            let mut work: Vec<(usize, usize)> = vec![(root, 0)];
+            while let Some(&(v, child)) = work.last() {
+                if child == 0 {
+                    index[v] = next_index;
+                    low[v] = next_index;
+                    next_index += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                }
+                if let Some(w) = successors(v).nth(child) {
+                    if let Some(top) = work.last_mut() {
+                        top.1 += 1;
+                    }
+                    if index[w] == usize::MAX {
+                        work.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+                work.pop();
+                if let Some(&(parent, _)) = work.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if low[v] == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    if component.len() > 1 || successors(v).any(|w| w == v) {
+                        component.sort_unstable();
+                        components.push(component);
+                    }
+                }
+            }
+        }
+        components.sort_by_key(|component| component[0]);
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{Edge, Node, NodeKind};
+
+    fn circuit(nodes: &[&str], edges: &[(&str, &str, EdgeType)]) -> Schematic {
+        let mut schematic = Schematic::new("c");
+        for id in nodes {
+            schematic.nodes.push(Node {
+                id: id.to_string(),
+                kind: NodeKind::Atom,
+                label: id.to_uppercase(),
+                description: None,
+                input_type: "()".into(),
+                output_type: "()".into(),
+                resource_type: "()".into(),
+                metadata: Default::default(),
+                bus_capability: None,
+                source_location: None,
+                position: None,
+                compensation_node_id: None,
+                input_schema: None,
+                output_schema: None,
+                item_type: None,
+                terminal: None,
+            });
+        }
+        for (from, to, kind) in edges {
+            schematic.edges.push(Edge {
+                from: from.to_string(),
+                to: to.to_string(),
+                kind: kind.clone(),
+                label: None,
+            });
+        }
+        schematic
+    }
+
+    #[test]
+    fn orders_nodes_and_skips_jump_edges() {
+        let schematic = circuit(
+            &["a", "c", "b", "d"],
+            &[
+                ("a", "b", EdgeType::Linear),
+                ("b", "c", EdgeType::Branch("x".into())),
+                ("c", "a", EdgeType::Jump),
+            ],
+        );
+        assert_eq!(topological_order(&schematic).unwrap(), ["a", "b", "c", "d"]);
+        assert_eq!(
+            find_cycles(&schematic),
+            [Cycle {
+                kind: CycleKind::Loop,
+                nodes: vec!["a".into(), "c".into(), "b".into()],
+            }]
+        );
+        assert_eq!(unreachable_nodes(&schematic), ["d"]);
+        let d = &degrees(&schematic)[3];
+        assert!(d.is_isolated());
+        assert_eq!(degrees(&schematic)[0].in_degree, 1);
+    }
+
+    #[test]
+    fn structural_cycles_block_ordering() {
+        let schematic = circuit(
+            &["a", "b", "c", "d"],
+            &[
+                ("a", "b", EdgeType::Linear),
+                ("b", "c", EdgeType::Linear),
+                ("c", "b", EdgeType::Fault),
+                ("c", "d", EdgeType::Linear),
+                ("d", "a", EdgeType::Jump),
+            ],
+        );
+        let err = topological_order(&schematic).unwrap_err();
+        assert_eq!(err.remaining, ["b", "c", "d"]);
+
+        let analysis = GraphAnalysis::of(&schematic);
+        assert_eq!(analysis.topological_order, None);
+        assert_eq!(
+            analysis.cycles,
+            [
+                Cycle {
+                    kind: CycleKind::Structural,
+                    nodes: vec!["b".into(), "c".into()],
+                },
+                Cycle {
+                    kind: CycleKind::Loop,
+                    nodes: vec!["a".into(), "b".into(), "c".into(), "d".into()],
+                },
+            ]
+        );
+        assert!(analysis.unreachable.is_empty());
+    }
+}
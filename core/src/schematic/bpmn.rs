@@ -0,0 +1,803 @@
+//! BPMN 2.0 export of a [`Schematic`].
+//!
+//! The document opens in standard process modeling tools (Camunda Modeler,
+//! bpmn.io, Signavio) and carries diagram interchange, so it renders without
+//! a layout step. Each circuit level becomes a `process`:
+//!
+//! | Schematic | BPMN |
+//! |---|---|
+//! | `Ingress` | start event |
+//! | `Egress` | end event |
+//! | `Atom`, `Tap`, `StreamingTransition` | service task |
+//! | `Synapse` | exclusive gateway |
+//! | `FanOut`, `FanIn` | parallel gateway |
+//! | `Subgraph` | call activity of the inner circuit's process |
+//! | `Branch` edges | an exclusive gateway after the node, one flow per branch id, the `Next` edge as default flow |
+//! | `Fault` edges | error boundary event on the node |
+//! | `compensation_node_id` | compensation boundary event associated with a compensation task |
+//!
+//! `Linear`, `Jump` and `Parallel` edges are sequence flows. A start event is
+//! added when the circuit does not begin with an `Ingress`, and an end event
+//! after every step without outgoing flow.
+//!
+//! ```rust
+//! # use ranvier_core::Schematic;
+//! let bpmn = Schematic::new("Checkout").to_bpmn();
+//! assert!(bpmn.contains("<bpmn:process id=\"Process_1\" name=\"Checkout\""));
+//! ```
+
+use super::{EdgeType, NodeKind, Schematic};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+const TARGET_NAMESPACE: &str = "https://ranvier.studio/schematic";
+
+impl Schematic {
+    /// Render the circuit as a BPMN 2.0 XML document; see the
+    /// [`bpmn`](crate::schematic::bpmn) module for the mapping.
+    pub fn to_bpmn(&self) -> String {
+        let mut exporter = Exporter::default();
+        exporter.process_id(self);
+        let mut processes = Vec::new();
+        let mut next = 0;
+        while let Some(&schematic) = exporter.queue.get(next) {
+            next += 1;
+            processes.push(exporter.process(schematic));
+        }
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            out,
+            "<bpmn:definitions xmlns:bpmn=\"http://www.omg.org/spec/BPMN/20100524/MODEL\" \
+             xmlns:bpmndi=\"http://www.omg.org/spec/BPMN/20100524/DI\" \
+             xmlns:dc=\"http://www.omg.org/spec/DD/20100524/DC\" \
+             xmlns:di=\"http://www.omg.org/spec/DD/20100524/DI\" \
+             id=\"Definitions_1\" targetNamespace=\"{TARGET_NAMESPACE}\" \
+             exporter=\"ranvier\" exporterVersion=\"{}\">",
+            crate::VERSION
+        );
+        if processes.iter().any(Process::has_faults) {
+            out.push_str("  <bpmn:error id=\"Error_fault\" name=\"Outcome::Fault\" />\n");
+        }
+        for process in &processes {
+            process.write_model(&mut out);
+        }
+        for process in &processes {
+            process.write_diagram(&mut out);
+        }
+        out.push_str("</bpmn:definitions>\n");
+        out
+    }
+}
+
+#[derive(Default)]
+struct Exporter<'s> {
+    /// Process id per schematic id; an embedded circuit is exported once.
+    process_ids: HashMap<&'s str, String>,
+    queue: Vec<&'s Schematic>,
+    used_ids: HashSet<String>,
+    /// Last number handed out per id prefix.
+    counters: HashMap<&'static str, usize>,
+}
+
+impl<'s> Exporter<'s> {
+    fn process_id(&mut self, schematic: &'s Schematic) -> String {
+        if let Some(id) = self.process_ids.get(schematic.id.as_str()) {
+            return id.clone();
+        }
+        let id = format!("Process_{}", self.process_ids.len() + 1);
+        self.process_ids.insert(&schematic.id, id.clone());
+        self.queue.push(schematic);
+        id
+    }
+
+    /// An unused element id: `prefix_<hint>`, or `prefix_<n>` without hint.
+    fn element_id(&mut self, prefix: &'static str, hint: Option<&str>) -> String {
+        let base = match hint {
+            Some(hint) => format!("{prefix}_{}", xml_id(hint)),
+            None => {
+                let counter = self.counters.entry(prefix).or_default();
+                *counter += 1;
+                format!("{prefix}_{counter}")
+            }
+        };
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while !self.used_ids.insert(id.clone()) {
+            id = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+        id
+    }
+
+    fn process(&mut self, schematic: &'s Schematic) -> Process {
+        let mut process = Process {
+            id: self.process_ids[schematic.id.as_str()].clone(),
+            name: schematic.name.clone(),
+            elements: Vec::new(),
+            flows: Vec::new(),
+            associations: Vec::new(),
+        };
+
+        let mut by_node = HashMap::new();
+        for node in &schematic.nodes {
+            let shape = match &node.kind {
+                NodeKind::Ingress => Shape::Start,
+                NodeKind::Egress => Shape::End,
+                NodeKind::Atom | NodeKind::Tap | NodeKind::StreamingTransition => Shape::Task {
+                    for_compensation: false,
+                },
+                NodeKind::Synapse => Shape::Exclusive { default: None },
+                NodeKind::FanOut | NodeKind::FanIn => Shape::Parallel,
+                NodeKind::Subgraph(inner) => Shape::Call(self.process_id(inner)),
+            };
+            let id = self.element_id("Node", Some(&node.id));
+            let index = process.push(id, &node.label, shape);
+            process.elements[index].documentation = node
+                .description
+                .clone()
+                .or_else(|| node.metadata.description.clone());
+            by_node.insert(node.id.as_str(), index);
+        }
+        let starts_with_ingress = schematic
+            .nodes
+            .first()
+            .is_some_and(|node| matches!(node.kind, NodeKind::Ingress));
+        if !starts_with_ingress {
+            let id = self.element_id("Start", None);
+            let start = process.push(id, "", Shape::Start);
+            if let Some(first) = schematic.nodes.first() {
+                let id = self.element_id("Flow", None);
+                process.flow(id, start, by_node[first.id.as_str()], None);
+            }
+        }
+
+        let mut gateways = HashMap::new();
+        let mut fault_events = HashMap::new();
+        for edge in &schematic.edges {
+            let (Some(&from), Some(&to)) = (
+                by_node.get(edge.from.as_str()),
+                by_node.get(edge.to.as_str()),
+            ) else {
+                continue;
+            };
+            let branches = schematic.edges.iter().any(|e| {
+                e.from == edge.from
+                    && matches!(e.kind, EdgeType::Branch(_))
+                    && by_node.contains_key(e.to.as_str())
+            });
+            let label = edge.label.clone();
+            match &edge.kind {
+                EdgeType::Branch(branch) => {
+                    let gateway = self.branch_gateway(&mut process, &mut gateways, from);
+                    let id = self.element_id("Flow", None);
+                    process.flow(
+                        id,
+                        gateway,
+                        to,
+                        Some(label.unwrap_or_else(|| branch.clone())),
+                    );
+                }
+                EdgeType::Linear if branches => {
+                    let gateway = self.branch_gateway(&mut process, &mut gateways, from);
+                    let id = self.element_id("Flow", None);
+                    let flow = process.flow(id, gateway, to, label);
+                    if let Shape::Exclusive { default } = &mut process.elements[gateway].shape {
+                        default.get_or_insert(flow);
+                    }
+                }
+                EdgeType::Fault => {
+                    let source = if process.elements[from].is_activity() {
+                        *fault_events.entry(from).or_insert_with(|| {
+                            let id = self.element_id("Fault", None);
+                            let event = Shape::Boundary {
+                                host: from,
+                                compensation: false,
+                            };
+                            process.push(id, "", event)
+                        })
+                    } else {
+                        from
+                    };
+                    let id = self.element_id("Flow", None);
+                    process.flow(
+                        id,
+                        source,
+                        to,
+                        Some(label.unwrap_or_else(|| "fault".into())),
+                    );
+                }
+                EdgeType::Jump => {
+                    let id = self.element_id("Flow", None);
+                    process.flow(id, from, to, Some(label.unwrap_or_else(|| "jump".into())));
+                }
+                EdgeType::Linear | EdgeType::Parallel => {
+                    let id = self.element_id("Flow", None);
+                    let label = label.filter(|label| label != "Next");
+                    process.flow(id, from, to, label);
+                }
+            }
+        }
+
+        for node in &schematic.nodes {
+            let Some(&compensation) = node
+                .compensation_node_id
+                .as_deref()
+                .and_then(|id| by_node.get(id))
+            else {
+                continue;
+            };
+            let host = by_node[node.id.as_str()];
+            if let Shape::Task { for_compensation } = &mut process.elements[compensation].shape {
+                *for_compensation = true;
+            }
+            let id = self.element_id("Compensation", None);
+            let event = process.push(
+                id,
+                "",
+                Shape::Boundary {
+                    host,
+                    compensation: true,
+                },
+            );
+            let id = self.element_id("Association", None);
+            process.associations.push(Flow {
+                id,
+                from: event,
+                to: compensation,
+                name: None,
+            });
+        }
+
+        // Every path ends in an end event.
+        for index in 0..process.elements.len() {
+            let open = match process.elements[index].shape {
+                Shape::Start | Shape::Exclusive { .. } | Shape::Parallel | Shape::Call(_) => true,
+                Shape::Task { for_compensation } => !for_compensation,
+                Shape::End | Shape::Boundary { .. } => false,
+            };
+            if open && !process.flows.iter().any(|flow| flow.from == index) {
+                let id = self.element_id("End", None);
+                let end = process.push(id, "", Shape::End);
+                let id = self.element_id("Flow", None);
+                process.flow(id, index, end, None);
+            }
+        }
+        process
+    }
+
+    /// The exclusive gateway routing `from`'s branches, added on first use.
+    fn branch_gateway(
+        &mut self,
+        process: &mut Process,
+        gateways: &mut HashMap<usize, usize>,
+        from: usize,
+    ) -> usize {
+        if let Some(&gateway) = gateways.get(&from) {
+            return gateway;
+        }
+        let id = self.element_id("Gateway", None);
+        let gateway = process.push(id, "", Shape::Exclusive { default: None });
+        let id = self.element_id("Flow", None);
+        process.flow(id, from, gateway, None);
+        gateways.insert(from, gateway);
+        gateway
+    }
+}
+
+enum Shape {
+    Start,
+    End,
+    Task {
+        for_compensation: bool,
+    },
+    /// Call activity of the process with this id.
+    Call(String),
+    /// `default` is the index of the default flow.
+    Exclusive {
+        default: Option<usize>,
+    },
+    Parallel,
+    /// Error or compensation event attached to the `host` element.
+    Boundary {
+        host: usize,
+        compensation: bool,
+    },
+}
+
+struct Element {
+    id: String,
+    name: String,
+    documentation: Option<String>,
+    shape: Shape,
+}
+
+impl Element {
+    fn is_activity(&self) -> bool {
+        matches!(self.shape, Shape::Task { .. } | Shape::Call(_))
+    }
+
+    fn size(&self) -> (f64, f64) {
+        match self.shape {
+            Shape::Task { .. } | Shape::Call(_) => (100.0, 80.0),
+            Shape::Exclusive { .. } | Shape::Parallel => (50.0, 50.0),
+            Shape::Start | Shape::End | Shape::Boundary { .. } => (36.0, 36.0),
+        }
+    }
+}
+
+struct Flow {
+    id: String,
+    from: usize,
+    to: usize,
+    name: Option<String>,
+}
+
+struct Process {
+    id: String,
+    name: String,
+    elements: Vec<Element>,
+    flows: Vec<Flow>,
+    associations: Vec<Flow>,
+}
+
+/// Top-left corner and size of a shape.
+#[derive(Clone, Copy)]
+struct Bounds {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Bounds {
+    fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+}
+
+impl Process {
+    fn push(&mut self, id: String, name: &str, shape: Shape) -> usize {
+        self.elements.push(Element {
+            id,
+            name: name.to_string(),
+            documentation: None,
+            shape,
+        });
+        self.elements.len() - 1
+    }
+
+    fn flow(&mut self, id: String, from: usize, to: usize, name: Option<String>) -> usize {
+        self.flows.push(Flow { id, from, to, name });
+        self.flows.len() - 1
+    }
+
+    fn has_faults(&self) -> bool {
+        self.elements.iter().any(|element| {
+            matches!(
+                element.shape,
+                Shape::Boundary {
+                    compensation: false,
+                    ..
+                }
+            )
+        })
+    }
+
+    fn write_model(&self, out: &mut String) {
+        let _ = writeln!(
+            out,
+            "  <bpmn:process id=\"{}\" name={} isExecutable=\"false\">",
+            self.id,
+            xml_attr(&self.name)
+        );
+        for element in &self.elements {
+            let id = &element.id;
+            let name = if element.name.is_empty() {
+                String::new()
+            } else {
+                format!(" name={}", xml_attr(&element.name))
+            };
+            let (tag, attrs) = match &element.shape {
+                Shape::Start => ("startEvent", String::new()),
+                Shape::End => ("endEvent", String::new()),
+                Shape::Task { for_compensation } => (
+                    "serviceTask",
+                    if *for_compensation {
+                        " isForCompensation=\"true\"".to_string()
+                    } else {
+                        String::new()
+                    },
+                ),
+                Shape::Call(process) => ("callActivity", format!(" calledElement=\"{process}\"")),
+                Shape::Exclusive { default } => (
+                    "exclusiveGateway",
+                    default
+                        .map(|flow| format!(" default=\"{}\"", self.flows[flow].id))
+                        .unwrap_or_default(),
+                ),
+                Shape::Parallel => ("parallelGateway", String::new()),
+                Shape::Boundary { host, compensation } => {
+                    let definition = if *compensation {
+                        "<bpmn:compensateEventDefinition />"
+                    } else {
+                        "<bpmn:errorEventDefinition errorRef=\"Error_fault\" />"
+                    };
+                    let _ = writeln!(
+                        out,
+                        "    <bpmn:boundaryEvent id=\"{id}\"{name} attachedToRef=\"{}\">",
+                        self.elements[*host].id
+                    );
+                    let _ = writeln!(out, "      {definition}");
+                    let _ = writeln!(out, "    </bpmn:boundaryEvent>");
+                    continue;
+                }
+            };
+            match &element.documentation {
+                Some(documentation) => {
+                    let _ = writeln!(out, "    <bpmn:{tag} id=\"{id}\"{name}{attrs}>");
+                    let _ = writeln!(
+                        out,
+                        "      <bpmn:documentation>{}</bpmn:documentation>",
+                        xml_text(documentation)
+                    );
+                    let _ = writeln!(out, "    </bpmn:{tag}>");
+                }
+                None => {
+                    let _ = writeln!(out, "    <bpmn:{tag} id=\"{id}\"{name}{attrs} />");
+                }
+            }
+        }
+        for flow in &self.flows {
+            let name = flow
+                .name
+                .as_deref()
+                .map(|name| format!(" name={}", xml_attr(name)))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "    <bpmn:sequenceFlow id=\"{}\" sourceRef=\"{}\" targetRef=\"{}\"{name} />",
+                flow.id, self.elements[flow.from].id, self.elements[flow.to].id
+            );
+        }
+        for association in &self.associations {
+            let _ = writeln!(
+                out,
+                "    <bpmn:association id=\"{}\" associationDirection=\"One\" sourceRef=\"{}\" targetRef=\"{}\" />",
+                association.id,
+                self.elements[association.from].id,
+                self.elements[association.to].id
+            );
+        }
+        out.push_str("  </bpmn:process>\n");
+    }
+
+    /// Lay elements out left to right by distance from a start event, one
+    /// row per element of a column. Boundary events sit on their host's
+    /// lower edge and compensation tasks below their host.
+    fn layout(&self) -> Vec<Bounds> {
+        let n = self.elements.len();
+        let host = |i: usize| match self.elements[i].shape {
+            Shape::Boundary { host, .. } => host,
+            _ => i,
+        };
+        let mut column: Vec<Option<usize>> = vec![None; n];
+        let mut queue = VecDeque::new();
+        for (i, element) in self.elements.iter().enumerate() {
+            if matches!(element.shape, Shape::Start) {
+                column[i] = Some(0);
+                queue.push_back(i);
+            }
+        }
+        // Rows are handed out in visiting order, so the first flow out of
+        // an element continues on its row.
+        let mut order = Vec::with_capacity(n);
+        while let Some(i) = queue.pop_front() {
+            order.push(i);
+            let col = column[i].unwrap_or_default();
+            for flow in self.flows.iter().filter(|flow| host(flow.from) == i) {
+                if column[flow.to].is_none() {
+                    column[flow.to] = Some(col + 1);
+                    queue.push_back(flow.to);
+                }
+            }
+        }
+        for association in &self.associations {
+            let host = host(association.from);
+            if column[association.to].is_none() {
+                column[association.to] = column[host];
+                order.push(association.to);
+            }
+        }
+        order.extend((0..n).filter(|&i| column[i].is_none()));
+        let last = column.iter().flatten().max().map_or(0, |max| max + 1);
+
+        let mut rows: HashMap<usize, usize> = HashMap::new();
+        let mut bounds = vec![
+            Bounds {
+                x: 0.0,
+                y: 0.0,
+                width: 0.0,
+                height: 0.0,
+            };
+            n
+        ];
+        let mut boundaries = Vec::new();
+        for i in order {
+            let element = &self.elements[i];
+            if matches!(element.shape, Shape::Boundary { .. }) {
+                boundaries.push(i);
+                continue;
+            }
+            let col = column[i].unwrap_or(last);
+            let row = rows.entry(col).or_default();
+            let (width, height) = element.size();
+            let (cx, cy) = (150.0 + col as f64 * 170.0, 100.0 + *row as f64 * 130.0);
+            *row += 1;
+            bounds[i] = Bounds {
+                x: cx - width / 2.0,
+                y: cy - height / 2.0,
+                width,
+                height,
+            };
+        }
+        for i in boundaries {
+            let Shape::Boundary { host, compensation } = self.elements[i].shape else {
+                continue;
+            };
+            let host = bounds[host];
+            let offset = if compensation { 25.0 } else { -25.0 };
+            bounds[i] = Bounds {
+                x: host.x + host.width / 2.0 + offset - 18.0,
+                y: host.y + host.height - 18.0,
+                width: 36.0,
+                height: 36.0,
+            };
+        }
+        bounds
+    }
+
+    fn write_diagram(&self, out: &mut String) {
+        let bounds = self.layout();
+        let _ = writeln!(out, "  <bpmndi:BPMNDiagram id=\"Diagram_{}\">", self.id);
+        let _ = writeln!(
+            out,
+            "    <bpmndi:BPMNPlane id=\"Plane_{}\" bpmnElement=\"{}\">",
+            self.id, self.id
+        );
+        for (element, b) in self.elements.iter().zip(&bounds) {
+            let _ = writeln!(
+                out,
+                "      <bpmndi:BPMNShape id=\"{}_di\" bpmnElement=\"{}\">",
+                element.id, element.id
+            );
+            let _ = writeln!(
+                out,
+                "        <dc:Bounds x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" />",
+                b.x, b.y, b.width, b.height
+            );
+            let _ = writeln!(out, "      </bpmndi:BPMNShape>");
+        }
+        for flow in self.flows.iter().chain(&self.associations) {
+            let _ = writeln!(
+                out,
+                "      <bpmndi:BPMNEdge id=\"{}_di\" bpmnElement=\"{}\">",
+                flow.id, flow.id
+            );
+            for (x, y) in waypoints(bounds[flow.from], bounds[flow.to]) {
+                let _ = writeln!(out, "        <di:waypoint x=\"{x}\" y=\"{y}\" />");
+            }
+            let _ = writeln!(out, "      </bpmndi:BPMNEdge>");
+        }
+        out.push_str("    </bpmndi:BPMNPlane>\n");
+        out.push_str("  </bpmndi:BPMNDiagram>\n");
+    }
+}
+
+/// Forward edges leave on the right and enter on the left, with an elbow
+/// between rows. Edges to a shape below in the same column go down, and
+/// edges back to an earlier column run below both shapes.
+fn waypoints(from: Bounds, to: Bounds) -> Vec<(f64, f64)> {
+    let (fx, fy) = from.center();
+    let (tx, ty) = to.center();
+    if to.x >= from.x + from.width {
+        let (start, end) = ((from.x + from.width, fy), (to.x, ty));
+        if fy == ty {
+            return vec![start, end];
+        }
+        let mid = (start.0 + end.0) / 2.0;
+        return vec![start, (mid, fy), (mid, ty), end];
+    }
+    if to.y >= from.y + from.height {
+        let (start, end) = ((fx, from.y + from.height), (tx, to.y));
+        if fx == tx {
+            return vec![start, end];
+        }
+        let mid = (start.1 + end.1) / 2.0;
+        return vec![start, (fx, mid), (tx, mid), end];
+    }
+    let below = (from.y + from.height).max(to.y + to.height) + 30.0;
+    vec![
+        (fx, from.y + from.height),
+        (fx, below),
+        (tx, below),
+        (tx, to.y + to.height),
+    ]
+}
+
+/// A valid XML id (NCName) from an arbitrary node id.
+fn xml_id(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// A quoted XML attribute value.
+fn xml_attr(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '&' => quoted.push_str("&amp;"),
+            '<' => quoted.push_str("&lt;"),
+            '>' => quoted.push_str("&gt;"),
+            '"' => quoted.push_str("&quot;"),
+            '\n' => quoted.push_str("&#10;"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Escape `value` for use as XML character data.
+fn xml_text(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{Edge, Node};
+
+    fn node(id: &str, kind: NodeKind, label: &str) -> Node {
+        Node {
+            id: id.to_string(),
+            kind,
+            label: label.to_string(),
+            description: None,
+            input_type: "Order".into(),
+            output_type: "Order".into(),
+            resource_type: "()".into(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: None,
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        }
+    }
+
+    fn edge(from: &str, to: &str, kind: EdgeType, label: Option<&str>) -> Edge {
+        Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+            label: label.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn branches_become_gateways_and_subgraphs_call_activities() {
+        let mut notify = Schematic::new("Notify");
+        notify.nodes = vec![node("n", NodeKind::Atom, "Email")];
+        let mut schematic = Schematic::new("Checkout <v2>");
+        schematic.nodes = vec![
+            node("in", NodeKind::Ingress, "Checkout"),
+            node("charge", NodeKind::Atom, "Charge & capture"),
+            node("review", NodeKind::Atom, "Review"),
+            node("notify", NodeKind::Subgraph(Box::new(notify)), "Notify"),
+            node("refund", NodeKind::Atom, "Refund"),
+            node("fail", NodeKind::Atom, "Report"),
+        ];
+        schematic.nodes[1].compensation_node_id = Some("refund".to_string());
+        schematic.nodes[2].description = Some("Flag <large> orders".to_string());
+        schematic.edges = vec![
+            edge("in", "charge", EdgeType::Linear, Some("Next")),
+            edge(
+                "charge",
+                "review",
+                EdgeType::Branch("suspicious".into()),
+                None,
+            ),
+            edge("charge", "notify", EdgeType::Linear, Some("Otherwise")),
+            edge("charge", "fail", EdgeType::Fault, None),
+        ];
+
+        let bpmn = schematic.to_bpmn();
+
+        assert!(bpmn.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<bpmn:definitions "));
+        assert!(bpmn.contains(
+            "<bpmn:process id=\"Process_1\" name=\"Checkout &lt;v2&gt;\" isExecutable=\"false\">"
+        ));
+        assert!(bpmn.contains("<bpmn:startEvent id=\"Node_in\" name=\"Checkout\" />"));
+        assert!(
+            bpmn.contains("<bpmn:serviceTask id=\"Node_charge\" name=\"Charge &amp; capture\" />")
+        );
+        assert!(bpmn.contains(
+            "<bpmn:serviceTask id=\"Node_review\" name=\"Review\">\n      \
+             <bpmn:documentation>Flag &lt;large&gt; orders</bpmn:documentation>\n    \
+             </bpmn:serviceTask>"
+        ));
+        assert!(bpmn.contains(
+            "<bpmn:callActivity id=\"Node_notify\" name=\"Notify\" calledElement=\"Process_2\" />"
+        ));
+        assert!(
+            bpmn.contains("<bpmn:process id=\"Process_2\" name=\"Notify\" isExecutable=\"false\">")
+        );
+
+        // The branch gets a gateway whose default flow is the Next edge.
+        assert!(bpmn.contains("<bpmn:exclusiveGateway id=\"Gateway_1\" default=\"Flow_4\" />"));
+        assert!(bpmn.contains(
+            "<bpmn:sequenceFlow id=\"Flow_1\" sourceRef=\"Node_in\" targetRef=\"Node_charge\" />"
+        ));
+        assert!(bpmn.contains(
+            "<bpmn:sequenceFlow id=\"Flow_2\" sourceRef=\"Node_charge\" targetRef=\"Gateway_1\" />"
+        ));
+        assert!(
+            bpmn.contains(
+                "sourceRef=\"Gateway_1\" targetRef=\"Node_review\" name=\"suspicious\" />"
+            )
+        );
+        assert!(bpmn.contains(
+            "<bpmn:sequenceFlow id=\"Flow_4\" sourceRef=\"Gateway_1\" targetRef=\"Node_notify\" name=\"Otherwise\" />"
+        ));
+
+        // Faults leave through an error boundary event, compensation through
+        // a compensation boundary event.
+        assert!(bpmn.contains("<bpmn:error id=\"Error_fault\" name=\"Outcome::Fault\" />"));
+        assert!(bpmn.contains("attachedToRef=\"Node_charge\">\n      <bpmn:errorEventDefinition errorRef=\"Error_fault\" />"));
+        assert!(
+            bpmn.contains(
+                "attachedToRef=\"Node_charge\">\n      <bpmn:compensateEventDefinition />"
+            )
+        );
+        assert!(bpmn.contains(
+            "<bpmn:serviceTask id=\"Node_refund\" name=\"Refund\" isForCompensation=\"true\" />"
+        ));
+        assert!(bpmn.contains("associationDirection=\"One\""));
+        assert!(bpmn.contains("targetRef=\"Node_fail\" name=\"fault\" />"));
+
+        // Every element and flow has diagram interchange.
+        for id in ["Node_in", "Node_charge", "Gateway_1", "Flow_4", "Node_n"] {
+            assert!(bpmn.contains(&format!("bpmnElement=\"{id}\"")), "{id}");
+        }
+        // Open paths end in end events; the compensation task stays open.
+        assert_eq!(bpmn.matches("<bpmn:endEvent").count(), 4);
+        assert!(bpmn.ends_with("</bpmn:definitions>\n"));
+    }
+
+    #[test]
+    fn circuits_without_ingress_get_a_start_event() {
+        let mut schematic = Schematic::new("Nightly");
+        schematic.nodes = vec![node("a", NodeKind::Atom, "Sweep")];
+        let bpmn = schematic.to_bpmn();
+        assert!(bpmn.contains("<bpmn:startEvent id=\"Start_1\" />"));
+        assert!(bpmn.contains("sourceRef=\"Start_1\" targetRef=\"Node_a\""));
+        assert!(bpmn.contains("sourceRef=\"Node_a\" targetRef=\"End_1\""));
+        assert!(!bpmn.contains("Error_fault"));
+    }
+}
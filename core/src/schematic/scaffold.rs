@@ -0,0 +1,925 @@
+//! Rust skeletons generated from a [`Schematic`].
+//!
+//! A circuit designed in Studio first becomes a source file with one
+//! `Transition` struct per node, each with a `todo!()` body, and a function
+//! wiring them into an Axon the way the schematic's edges do:
+//!
+//! ```rust
+//! # use ranvier_core::Schematic;
+//! let code = Schematic::new("Checkout").to_rust_scaffold();
+//! assert!(code.contains("pub fn checkout_circuit()"));
+//! ```
+//!
+//! Wiring follows `Linear` edges with `.then(..)` and turns `Branch` edges
+//! into `Axon::branch` arms, the `Linear` successor of a branching node being the
+//! `otherwise` arm. Arms end where they meet again, and a `Synapse` node at
+//! that point is taken for the builder's join rather than a step. A terminal
+//! `Egress` reached by a branch is where that branch leaves the circuit, so
+//! it gets no arm; the branch stays declared by the transition's `branches`. Subgraph
+//! nodes get a circuit function of their own, and compensation nodes are
+//! passed to `then_compensated`. What cannot be wired this way (jump and
+//! parallel edges, fan-out/fan-in and streaming nodes) is listed under
+//! `TODO` in the circuit function's doc comment.
+//!
+//! Generated transitions keep the schematic's node ids, labels and positions,
+//! so rebuilding the circuit yields a schematic Studio matches node for node.
+//! Types come from the nodes' `input_type`/`output_type`: `void` becomes
+//! `()`, names that are not valid Rust types become `serde_json::Value`, and
+//! other types not in the standard prelude get an empty struct to fill in.
+
+use super::{EdgeType, NodeKind, Schematic};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+/// Settings of [`generate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaffoldOptions {
+    /// `Transition::Error` of every generated transition. Defaults to `String`.
+    pub error_type: String,
+}
+
+impl Default for ScaffoldOptions {
+    fn default() -> Self {
+        Self {
+            error_type: "String".to_string(),
+        }
+    }
+}
+
+impl ScaffoldOptions {
+    pub fn with_error_type(mut self, error_type: impl Into<String>) -> Self {
+        self.error_type = error_type.into();
+        self
+    }
+}
+
+/// Generate a Rust source file implementing `schematic`.
+pub fn generate(schematic: &Schematic, options: &ScaffoldOptions) -> String {
+    let mut generator = Generator::new(options);
+    generator.plan(schematic);
+    generator.render(schematic)
+}
+
+/// Parse a Schematic JSON export and [`generate`] its scaffold.
+pub fn from_json(json: &str, options: &ScaffoldOptions) -> Result<String, serde_json::Error> {
+    let schematic: Schematic = serde_json::from_str(json)?;
+    Ok(generate(&schematic, options))
+}
+
+impl Schematic {
+    /// Rust skeleton of this circuit; see the [`scaffold`](crate::schematic::scaffold) module.
+    pub fn to_rust_scaffold(&self) -> String {
+        generate(self, &ScaffoldOptions::default())
+    }
+}
+
+/// Names the generated code imports or declares itself.
+const RESERVED: &[&str] = &[
+    "Axon",
+    "Bus",
+    "Outcome",
+    "Transition",
+    "ResourceRequirement",
+    "Serialize",
+    "Deserialize",
+    "Self",
+];
+
+/// Types usable without a stub, with the import they need.
+const KNOWN_TYPES: &[(&str, Option<&str>)] = &[
+    ("String", None),
+    ("Vec", None),
+    ("Option", None),
+    ("Result", None),
+    ("Box", None),
+    ("HashMap", Some("std::collections::HashMap")),
+    ("HashSet", Some("std::collections::HashSet")),
+    ("BTreeMap", Some("std::collections::BTreeMap")),
+    ("BTreeSet", Some("std::collections::BTreeSet")),
+    ("Value", Some("serde_json::Value")),
+];
+
+struct Generator<'o> {
+    options: &'o ScaffoldOptions,
+    /// Struct name of every transition node, by node id.
+    structs: HashMap<String, String>,
+    /// Function name of every circuit, by schematic id.
+    circuits: HashMap<String, String>,
+    used_names: HashSet<String>,
+    /// Types that need a stub struct.
+    stubs: BTreeSet<String>,
+    /// Stubs used as `Transition::Resources`.
+    resources: BTreeSet<String>,
+    imports: BTreeSet<&'static str>,
+}
+
+impl<'o> Generator<'o> {
+    fn new(options: &'o ScaffoldOptions) -> Self {
+        Self {
+            options,
+            structs: HashMap::new(),
+            circuits: HashMap::new(),
+            used_names: RESERVED.iter().map(|name| name.to_string()).collect(),
+            stubs: BTreeSet::new(),
+            resources: BTreeSet::new(),
+            imports: BTreeSet::new(),
+        }
+    }
+
+    /// Collect types, then name circuits and transitions at every level.
+    fn plan(&mut self, schematic: &Schematic) {
+        let mut types = Vec::new();
+        collect_types(schematic, &mut types);
+        for ty in types {
+            for ident in type_idents(&ty) {
+                match KNOWN_TYPES.iter().find(|(name, _)| *name == ident) {
+                    Some((_, Some(import))) => {
+                        self.imports.insert(import);
+                    }
+                    Some((_, None)) => {}
+                    None => {
+                        self.used_names.insert(ident.clone());
+                        self.stubs.insert(ident);
+                    }
+                }
+            }
+        }
+        self.name_level(schematic);
+    }
+
+    fn name_level(&mut self, schematic: &Schematic) {
+        let name = self.unique(&format!("{}_circuit", snake_case(&schematic.name)));
+        self.circuits.insert(schematic.id.clone(), name);
+        let resources = rust_type(resource_type(schematic));
+        if resources != "()" {
+            self.resources.insert(resources);
+        }
+        for (index, node) in schematic.nodes.iter().enumerate() {
+            match &node.kind {
+                NodeKind::Subgraph(inner) => self.name_level(inner),
+                NodeKind::Ingress if index == 0 => {}
+                NodeKind::FanOut | NodeKind::FanIn | NodeKind::StreamingTransition => {}
+                _ if is_join(schematic, index) || is_exit(schematic, index) => {}
+                _ => {
+                    let name = self.unique(&pascal_case(&node.label));
+                    self.structs.insert(node.id.clone(), name);
+                }
+            }
+        }
+    }
+
+    fn unique(&mut self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut suffix = 2;
+        while !self.used_names.insert(name.clone()) {
+            name = format!("{base}{suffix}");
+            suffix += 1;
+        }
+        name
+    }
+
+    fn render(&self, schematic: &Schematic) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "//! Scaffold of the `{}` circuit, generated from its schematic.",
+            schematic.name
+        );
+        let _ = writeln!(out, "//!");
+        let _ = writeln!(
+            out,
+            "//! Replace each `todo!()` with the step's logic and fill in the types."
+        );
+        out.push('\n');
+        out.push_str("use async_trait::async_trait;\n");
+        let mut core = vec!["Bus", "Outcome", "Transition"];
+        if !self.resources.is_empty() {
+            core.insert(2, "ResourceRequirement");
+        }
+        let _ = writeln!(out, "use ranvier_core::prelude::{{{}}};", core.join(", "));
+        out.push_str("use ranvier_runtime::Axon;\n");
+        if self.stubs.iter().any(|stub| !self.resources.contains(stub)) {
+            out.push_str("use serde::{Deserialize, Serialize};\n");
+        }
+        for import in &self.imports {
+            let _ = writeln!(out, "use {import};");
+        }
+
+        for stub in &self.stubs {
+            out.push('\n');
+            if self.resources.contains(stub) {
+                let _ = writeln!(out, "#[derive(Debug, Clone, Default)]");
+                let _ = writeln!(out, "pub struct {stub} {{}}");
+                let _ = writeln!(out, "\nimpl ResourceRequirement for {stub} {{}}");
+            } else {
+                let _ = writeln!(
+                    out,
+                    "#[derive(Debug, Clone, Default, Serialize, Deserialize)]"
+                );
+                let _ = writeln!(out, "pub struct {stub} {{}}");
+            }
+        }
+
+        self.render_transitions(schematic, &mut out);
+        self.render_circuits(schematic, &mut out);
+        out
+    }
+
+    fn render_transitions(&self, schematic: &Schematic, out: &mut String) {
+        let resources = rust_type(resource_type(schematic));
+        for node in &schematic.nodes {
+            if let NodeKind::Subgraph(inner) = &node.kind {
+                self.render_transitions(inner, out);
+                continue;
+            }
+            let Some(name) = self.structs.get(&node.id) else {
+                continue;
+            };
+            let input = rust_type(&node.input_type);
+            let output = rust_type(&node.output_type);
+            out.push('\n');
+            for line in node.description.iter().flat_map(|d| d.lines()) {
+                let _ = writeln!(out, "/// {line}");
+            }
+            let _ = writeln!(out, "#[derive(Debug, Clone)]");
+            let _ = writeln!(out, "pub struct {name};");
+            out.push('\n');
+            let _ = writeln!(out, "#[async_trait]");
+            let _ = writeln!(out, "impl Transition<{input}, {output}> for {name} {{");
+            let _ = writeln!(out, "    type Error = {};", self.options.error_type);
+            let _ = writeln!(out, "    type Resources = {resources};");
+            let _ = writeln!(out);
+            let _ = writeln!(out, "    fn label(&self) -> String {{");
+            let _ = writeln!(out, "        {:?}.to_string()", node.label);
+            let _ = writeln!(out, "    }}");
+            let _ = writeln!(out);
+            let _ = writeln!(out, "    fn node_id(&self) -> Option<String> {{");
+            let _ = writeln!(out, "        Some({:?}.to_string())", node.id);
+            let _ = writeln!(out, "    }}");
+            if let Some(position) = &node.position {
+                let _ = writeln!(out);
+                let _ = writeln!(out, "    fn position(&self) -> Option<(f32, f32)> {{");
+                let _ = writeln!(out, "        Some(({:?}, {:?}))", position.x, position.y);
+                let _ = writeln!(out, "    }}");
+            }
+            let branches = branch_edges(schematic, &node.id);
+            if !branches.is_empty() {
+                let list: Vec<String> = branches
+                    .iter()
+                    .map(|(branch, _)| format!("{branch:?}.to_string()"))
+                    .collect();
+                let _ = writeln!(out);
+                let _ = writeln!(out, "    fn branches(&self) -> Vec<String> {{");
+                let _ = writeln!(out, "        vec![{}]", list.join(", "));
+                let _ = writeln!(out, "    }}");
+            }
+            let _ = writeln!(out);
+            let _ = writeln!(out, "    async fn run(");
+            let _ = writeln!(out, "        &self,");
+            let _ = writeln!(out, "        _state: {input},");
+            let _ = writeln!(out, "        _resources: &Self::Resources,");
+            let _ = writeln!(out, "        _bus: &mut Bus,");
+            let _ = writeln!(out, "    ) -> Outcome<{output}, Self::Error> {{");
+            let _ = writeln!(out, "        todo!({:?})", node.label);
+            let _ = writeln!(out, "    }}");
+            let _ = writeln!(out, "}}");
+        }
+    }
+
+    fn render_circuits(&self, schematic: &Schematic, out: &mut String) {
+        let level = Level::new(schematic);
+        let mut wiring = Wiring {
+            generator: self,
+            level: &level,
+            visited: HashSet::new(),
+            todo: Vec::new(),
+        };
+        let (expr, input, output) = wiring.circuit();
+        for (index, node) in schematic.nodes.iter().enumerate() {
+            if self.structs.contains_key(&node.id)
+                && !wiring.visited.contains(&index)
+                && !level.compensations.contains(&index)
+            {
+                wiring
+                    .todo
+                    .push(format!("`{}` is not reached from the start", node.label));
+            }
+        }
+        for edge in &schematic.edges {
+            let kind = match edge.kind {
+                EdgeType::Jump => "jump",
+                EdgeType::Parallel => "parallel edge",
+                EdgeType::Fault => "fault edge",
+                EdgeType::Linear | EdgeType::Branch(_) => continue,
+            };
+            wiring.todo.push(format!(
+                "{kind} `{}` -> `{}`",
+                level.label(&edge.from),
+                level.label(&edge.to)
+            ));
+        }
+
+        let resources = rust_type(resource_type(schematic));
+        let name = &self.circuits[&schematic.id];
+        out.push('\n');
+        let _ = writeln!(out, "/// Wiring of the `{}` circuit.", schematic.name);
+        if !wiring.todo.is_empty() {
+            let _ = writeln!(out, "///");
+            let _ = writeln!(out, "/// TODO, not wired:");
+            for item in &wiring.todo {
+                let _ = writeln!(out, "/// - {item}");
+            }
+        }
+        let _ = writeln!(
+            out,
+            "pub fn {name}() -> Axon<{input}, {output}, {}, {resources}> {{",
+            self.options.error_type
+        );
+        let _ = writeln!(out, "    {expr}");
+        let _ = writeln!(out, "}}");
+
+        for node in &schematic.nodes {
+            if let NodeKind::Subgraph(inner) = &node.kind {
+                self.render_circuits(inner, out);
+            }
+        }
+    }
+}
+
+/// Edge lookups for one schematic level.
+struct Level<'s> {
+    schematic: &'s Schematic,
+    index: HashMap<&'s str, usize>,
+    /// `Linear` successors per node.
+    next: Vec<Vec<usize>>,
+    /// `(branch id, target)` per node.
+    branches: Vec<Vec<(&'s str, usize)>>,
+    /// Number of `Linear` and `Branch` edges entering each node.
+    incoming: Vec<usize>,
+    /// Nodes that compensate another node.
+    compensations: HashSet<usize>,
+}
+
+impl<'s> Level<'s> {
+    fn new(schematic: &'s Schematic) -> Self {
+        let index: HashMap<&str, usize> = schematic
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.as_str(), i))
+            .collect();
+        // The builder links the step after `then_compensated` from the
+        // compensation node, which is pushed last.
+        let compensated: HashMap<usize, usize> = schematic
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, node)| {
+                let comp = index.get(node.compensation_node_id.as_deref()?)?;
+                Some((*comp, i))
+            })
+            .collect();
+        let n = schematic.nodes.len();
+        let mut next = vec![Vec::new(); n];
+        let mut branches = vec![Vec::new(); n];
+        let mut incoming = vec![0; n];
+        for edge in &schematic.edges {
+            let (Some(&from), Some(&to)) =
+                (index.get(edge.from.as_str()), index.get(edge.to.as_str()))
+            else {
+                continue;
+            };
+            let from = compensated.get(&from).copied().unwrap_or(from);
+            match &edge.kind {
+                EdgeType::Linear => next[from].push(to),
+                EdgeType::Branch(_) if is_exit(schematic, to) => continue,
+                EdgeType::Branch(branch) => branches[from].push((branch.as_str(), to)),
+                _ => continue,
+            }
+            incoming[to] += 1;
+        }
+        let compensations = compensated.into_keys().collect();
+        Self {
+            schematic,
+            index,
+            next,
+            branches,
+            incoming,
+            compensations,
+        }
+    }
+
+    fn label<'a>(&'a self, id: &'a str) -> &'a str {
+        self.index
+            .get(id)
+            .map_or(id, |&i| self.schematic.nodes[i].label.as_str())
+    }
+}
+
+/// Builds the Axon expression of one level.
+struct Wiring<'g, 's> {
+    generator: &'g Generator<'g>,
+    level: &'g Level<'s>,
+    visited: HashSet<usize>,
+    todo: Vec<String>,
+}
+
+impl Wiring<'_, '_> {
+    /// The circuit's expression with its input and output types.
+    fn circuit(&mut self) -> (String, String, String) {
+        let schematic = self.level.schematic;
+        let Some(first) = schematic.nodes.first() else {
+            let ty = self.identity("()", &schematic.name);
+            return (ty, "()".to_string(), "()".to_string());
+        };
+        if matches!(first.kind, NodeKind::Ingress) {
+            let input = rust_type(&first.output_type);
+            let mut expr = self.identity(&input, &first.label);
+            self.visited.insert(0);
+            let output = self.follow(0, &mut expr, 1, input.clone());
+            (expr, input, output)
+        } else {
+            let input = rust_type(&first.input_type);
+            let mut expr = self.identity(&input, &schematic.name);
+            let output = self.walk(0, &mut expr, 1);
+            (expr, input, output)
+        }
+    }
+
+    /// `Axon::<T, T, E, Res>::new(label)`.
+    fn identity(&self, ty: &str, label: &str) -> String {
+        format!(
+            "Axon::<{ty}, {ty}, {}, {}>::new({label:?})",
+            self.generator.options.error_type,
+            rust_type(resource_type(self.level.schematic))
+        )
+    }
+
+    /// Append `node` and the steps after it; returns the output type.
+    fn walk(&mut self, node: usize, expr: &mut String, depth: usize) -> String {
+        let output = self.step(node, expr, depth);
+        self.follow(node, expr, depth, output)
+    }
+
+    /// Append the steps after `node`, whose output is `output`.
+    fn follow(&mut self, node: usize, expr: &mut String, depth: usize, output: String) -> String {
+        match self.successor(node, expr, depth, output) {
+            (Some(merge), output) => self.resume(merge, expr, depth, output),
+            (None, output) => output,
+        }
+    }
+
+    /// Continue the chain at `node`, which several edges enter.
+    fn resume(&mut self, node: usize, expr: &mut String, depth: usize, output: String) -> String {
+        if self.visited.contains(&node) {
+            return output;
+        }
+        if is_join(self.level.schematic, node) {
+            self.visited.insert(node);
+            self.follow(node, expr, depth, output)
+        } else {
+            self.walk(node, expr, depth)
+        }
+    }
+
+    /// Append the edges leaving `node`. Stops at a node several edges enter
+    /// and returns it, for the caller to resume there.
+    fn successor(
+        &mut self,
+        node: usize,
+        expr: &mut String,
+        depth: usize,
+        output: String,
+    ) -> (Option<usize>, String) {
+        let level = self.level;
+        let label = &level.schematic.nodes[node].label;
+        if level.next[node].len() > 1 {
+            self.todo
+                .push(format!("`{label}` has more than one Next edge"));
+        }
+        let next = level.next[node].first().copied();
+        if level.branches[node].is_empty() {
+            return match next {
+                Some(next) if level.incoming[next] > 1 => (Some(next), output),
+                Some(next) if self.visited.contains(&next) => {
+                    self.todo.push(format!(
+                        "`{label}` -> `{}` closes a cycle",
+                        level.schematic.nodes[next].label
+                    ));
+                    (None, output)
+                }
+                Some(next) => {
+                    let output = self.walk(next, expr, depth);
+                    (None, output)
+                }
+                None => (None, output),
+            };
+        }
+
+        let indent = "    ".repeat(depth);
+        let mut merges = Vec::new();
+        let _ = write!(expr, "\n{indent}    .branch(|b| {{\n{indent}        b");
+        for &(branch, target) in &level.branches[node] {
+            let (arm, merge, _) = self.arm(target, depth + 3);
+            let _ = write!(expr, "\n{indent}            .on({branch:?}, {arm})");
+            merges.push(merge);
+        }
+        let (otherwise, merge, output) = match next {
+            Some(next) if level.incoming[next] == 1 => self.arm(next, depth + 3),
+            _ => (self.identity(&output, "Next"), next, output),
+        };
+        let _ = write!(
+            expr,
+            "\n{indent}            .otherwise({otherwise})\n{indent}    }})"
+        );
+        merges.push(merge);
+
+        let mut merges = merges.into_iter().flatten();
+        let merge = merges.next();
+        if merges.any(|other| Some(other) != merge) {
+            self.todo
+                .push(format!("the arms of `{label}` meet at different nodes"));
+        }
+        (merge, output)
+    }
+
+    /// A branch arm starting at `node`, the node it stops at and its output.
+    fn arm(&mut self, node: usize, depth: usize) -> (String, Option<usize>, String) {
+        let schematic = self.level.schematic;
+        let target = &schematic.nodes[node];
+        let (mut expr, output) = match &target.kind {
+            NodeKind::Subgraph(inner) => {
+                self.visited.insert(node);
+                let name = &self.generator.circuits[&inner.id];
+                (format!("{name}()"), rust_type(&target.output_type))
+            }
+            _ => {
+                let input = rust_type(&target.input_type);
+                let mut expr = self.identity(&input, &target.label);
+                let output = self.step(node, &mut expr, depth);
+                (expr, output)
+            }
+        };
+        let (merge, output) = self.successor(node, &mut expr, depth, output);
+        (expr, merge, output)
+    }
+
+    /// Append `node` itself; returns its output type.
+    fn step(&mut self, node: usize, expr: &mut String, depth: usize) -> String {
+        self.visited.insert(node);
+        let schematic = self.level.schematic;
+        let target = &schematic.nodes[node];
+        let indent = "    ".repeat(depth);
+        let output = rust_type(&target.output_type);
+        if let NodeKind::Subgraph(inner) = &target.kind {
+            let name = &self.generator.circuits[&inner.id];
+            let _ = write!(expr, "\n{indent}    .then_axon({name}())");
+            return output;
+        }
+        let Some(name) = self.generator.structs.get(&target.id) else {
+            self.todo.push(format!(
+                "`{}` ({:?}) and what follows it",
+                target.label, target.kind
+            ));
+            return output;
+        };
+        let compensation = target
+            .compensation_node_id
+            .as_deref()
+            .and_then(|id| self.generator.structs.get(id));
+        match compensation {
+            Some(comp) => {
+                let _ = write!(expr, "\n{indent}    .then_compensated({name}, {comp})");
+            }
+            None => {
+                let _ = write!(expr, "\n{indent}    .then({name})");
+            }
+        }
+        output
+    }
+}
+
+/// A terminal `Egress` without outgoing edges: where an unhandled branch
+/// leaves the circuit. The branch stays declared by its source transition.
+fn is_exit(schematic: &Schematic, index: usize) -> bool {
+    let node = &schematic.nodes[index];
+    matches!(node.kind, NodeKind::Egress)
+        && node.terminal == Some(true)
+        && !schematic.edges.iter().any(|edge| edge.from == node.id)
+}
+
+/// A `Synapse` several edges enter: the join `Axon::branch` adds.
+fn is_join(schematic: &Schematic, index: usize) -> bool {
+    let node = &schematic.nodes[index];
+    matches!(node.kind, NodeKind::Synapse)
+        && schematic
+            .edges
+            .iter()
+            .filter(|edge| edge.to == node.id && matches!(edge.kind, EdgeType::Linear))
+            .count()
+            > 1
+}
+
+fn branch_edges<'s>(schematic: &'s Schematic, from: &str) -> Vec<(&'s str, &'s str)> {
+    schematic
+        .edges
+        .iter()
+        .filter(|edge| edge.from == from)
+        .filter_map(|edge| match &edge.kind {
+            EdgeType::Branch(branch) => Some((branch.as_str(), edge.to.as_str())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn resource_type(schematic: &Schematic) -> &str {
+    schematic
+        .nodes
+        .first()
+        .map_or("()", |node| node.resource_type.as_str())
+}
+
+fn collect_types(schematic: &Schematic, out: &mut Vec<String>) {
+    out.push(rust_type(resource_type(schematic)));
+    for node in &schematic.nodes {
+        out.push(rust_type(&node.input_type));
+        out.push(rust_type(&node.output_type));
+        if let NodeKind::Subgraph(inner) = &node.kind {
+            collect_types(inner, out);
+        }
+    }
+}
+
+/// The Rust spelling of a schematic type name.
+fn rust_type(name: &str) -> String {
+    let name = name.trim();
+    if name.is_empty() || name == "void" || name == "()" {
+        return "()".to_string();
+    }
+    let mut depth = 0i32;
+    let balanced = name.chars().all(|c| {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            _ => {}
+        }
+        depth >= 0 && (c.is_alphanumeric() || "_:<>, &'()[];".contains(c))
+    }) && depth == 0;
+    let starts_well = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '(' || c == '[' || c == '&');
+    if balanced && starts_well {
+        name.to_string()
+    } else {
+        "serde_json::Value".to_string()
+    }
+}
+
+/// Capitalized identifiers of a type that are not part of a path.
+fn type_idents(ty: &str) -> Vec<String> {
+    let mut idents = Vec::new();
+    let mut rest = ty;
+    while let Some(start) = rest.find(|c: char| c.is_alphanumeric() || c == '_') {
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+            .unwrap_or(rest.len());
+        let word = &rest[..end];
+        if !word.contains("::") && word.starts_with(|c: char| c.is_uppercase()) {
+            idents.push(word.to_string());
+        }
+        rest = &rest[end..];
+    }
+    idents
+}
+
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_numeric();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn pascal_case(text: &str) -> String {
+    let name: String = words(text)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| {
+                    first
+                        .to_uppercase()
+                        .chain(chars.flat_map(char::to_lowercase))
+                })
+                .into_iter()
+                .flatten()
+                .collect::<String>()
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_alphabetic()) {
+        name
+    } else {
+        format!("Step{name}")
+    }
+}
+
+fn snake_case(text: &str) -> String {
+    let name = words(text)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if name.starts_with(|c: char| c.is_alphabetic()) {
+        name
+    } else {
+        format!("circuit_{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{Edge, Node, Position};
+
+    fn node(id: &str, kind: NodeKind, label: &str, input: &str, output: &str) -> Node {
+        Node {
+            id: id.to_string(),
+            kind,
+            label: label.to_string(),
+            description: None,
+            input_type: input.to_string(),
+            output_type: output.to_string(),
+            resource_type: "()".to_string(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: None,
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        }
+    }
+
+    fn edge(from: &str, to: &str, kind: EdgeType) -> Edge {
+        Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+            label: None,
+        }
+    }
+
+    #[test]
+    fn studio_design_becomes_transitions_and_wiring() {
+        let mut schematic = Schematic::new("Checkout");
+        schematic.nodes = vec![
+            node("in", NodeKind::Ingress, "Checkout", "void", "Order"),
+            node("v", NodeKind::Atom, "validate order", "Order", "Order"),
+            node("r", NodeKind::Atom, "Manual review", "Order", "Order"),
+            node("c", NodeKind::Atom, "Charge", "Order", "Vec<Receipt>"),
+            node("x", NodeKind::Atom, "Refund", "Order", "void"),
+            node("z", NodeKind::Atom, "Orphan", "Order", "String>"),
+        ];
+        schematic.nodes[1].position = Some(Position { x: 10.0, y: 20.5 });
+        schematic.nodes[1].description = Some("Rejects empty carts.".to_string());
+        schematic.nodes[3].compensation_node_id = Some("x".to_string());
+        schematic.edges = vec![
+            edge("in", "v", EdgeType::Linear),
+            edge("v", "c", EdgeType::Linear),
+            edge("v", "r", EdgeType::Branch("suspicious".to_string())),
+            edge("r", "c", EdgeType::Linear),
+            edge("c", "v", EdgeType::Jump),
+        ];
+
+        let code = schematic.to_rust_scaffold();
+
+        assert!(code.contains("pub struct Order {}"));
+        assert!(code.contains("pub struct Receipt {}"));
+        assert!(code.contains(
+            "/// Rejects empty carts.\n#[derive(Debug, Clone)]\npub struct ValidateOrder;"
+        ));
+        assert!(code.contains("impl Transition<Order, Vec<Receipt>> for Charge {"));
+        assert!(code.contains("impl Transition<Order, ()> for Refund {"));
+        assert!(code.contains("impl Transition<Order, serde_json::Value> for Orphan {"));
+        assert!(code.contains("        Some(\"v\".to_string())"));
+        assert!(code.contains("        Some((10.0, 20.5))"));
+        assert!(code.contains("        vec![\"suspicious\".to_string()]"));
+        assert!(code.contains("todo!(\"validate order\")"));
+        assert!(
+            code.contains("pub fn checkout_circuit() -> Axon<Order, Vec<Receipt>, String, ()> {")
+        );
+        // The arm and the Next edge meet at Charge, which follows the branch.
+        assert!(code.contains(
+            "    Axon::<Order, Order, String, ()>::new(\"Checkout\")\n        .then(ValidateOrder)\n        .branch(|b| {\n            b\n                .on(\"suspicious\", Axon::<Order, Order, String, ()>::new(\"Manual review\")\n                    .then(ManualReview))\n                .otherwise(Axon::<Order, Order, String, ()>::new(\"Next\"))\n        })\n        .then_compensated(Charge, Refund)\n"
+        ));
+        assert!(code.contains("/// - `Orphan` is not reached from the start"));
+        assert!(code.contains("/// - jump `Charge` -> `validate order`"));
+        assert!(!code.contains("is not reached from the start\n/// - `Refund`"));
+    }
+
+    #[test]
+    fn builder_branches_become_circuit_functions() {
+        let mut arm = Schematic::new("Review");
+        arm.nodes = vec![
+            node("a0", NodeKind::Ingress, "Review", "void", "Order"),
+            node("a1", NodeKind::Atom, "Approve", "Order", "Order"),
+        ];
+        arm.edges = vec![edge("a0", "a1", EdgeType::Linear)];
+        let mut otherwise = Schematic::new("Next");
+        otherwise.nodes = vec![node("o0", NodeKind::Ingress, "Next", "void", "Order")];
+
+        let mut schematic = Schematic::new("order flow");
+        schematic.nodes = vec![
+            node("in", NodeKind::Ingress, "order flow", "void", "Order"),
+            node("s", NodeKind::Atom, "Score", "Order", "Order"),
+            node(
+                "arm",
+                NodeKind::Subgraph(Box::new(arm)),
+                "Review",
+                "Order",
+                "Order",
+            ),
+            node(
+                "else",
+                NodeKind::Subgraph(Box::new(otherwise)),
+                "Next",
+                "Order",
+                "Order",
+            ),
+            node("join", NodeKind::Synapse, "BranchJoin", "Order", "Order"),
+            node("ship", NodeKind::Atom, "Ship", "Order", "Order"),
+            node("exit", NodeKind::Egress, "fraud", "Option", "Option"),
+        ];
+        schematic.nodes[6].terminal = Some(true);
+        schematic.edges = vec![
+            edge("in", "s", EdgeType::Linear),
+            edge("s", "exit", EdgeType::Branch("fraud".to_string())),
+            edge("s", "arm", EdgeType::Branch("review".to_string())),
+            edge("s", "else", EdgeType::Linear),
+            edge("arm", "join", EdgeType::Linear),
+            edge("else", "join", EdgeType::Linear),
+            edge("join", "ship", EdgeType::Linear),
+        ];
+
+        let code = schematic.to_rust_scaffold();
+
+        assert!(!code.contains("BranchJoin"));
+        // The unhandled `fraud` branch stays declared but gets no arm.
+        assert!(code.contains("vec![\"fraud\".to_string(), \"review\".to_string()]"));
+        assert!(!code.contains(".on(\"fraud\""));
+        assert!(!code.contains("for Fraud"));
+        assert!(code.contains(".on(\"review\", review_circuit())"));
+        assert!(code.contains(".otherwise(next_circuit())"));
+        assert!(code.contains("        })\n        .then(Ship)\n"));
+        assert!(code.contains("pub fn review_circuit() -> Axon<Order, Order, String, ()> {"));
+        assert!(code.contains("pub fn next_circuit() -> Axon<Order, Order, String, ()> {"));
+        assert!(!code.contains("TODO"));
+    }
+
+    #[test]
+    fn names_are_valid_and_unique() {
+        assert_eq!(pascal_case("validate order"), "ValidateOrder");
+        assert_eq!(pascal_case("chargeCard"), "ChargeCard");
+        assert_eq!(pascal_case("3ds check"), "Step3dsCheck");
+        assert_eq!(snake_case("Order Flow"), "order_flow");
+        assert_eq!(snake_case("OrderFlow"), "order_flow");
+        assert_eq!(rust_type("Option<Order>"), "Option<Order>");
+        assert_eq!(rust_type("Stream<Order"), "serde_json::Value");
+
+        let options = ScaffoldOptions::default().with_error_type("AppError");
+        let mut schematic = Schematic::new("Flow");
+        schematic.nodes = vec![
+            node("a", NodeKind::Atom, "Order", "Order", "Order"),
+            node("b", NodeKind::Atom, "Order", "Order", "Order"),
+        ];
+        schematic.edges = vec![edge("a", "b", EdgeType::Linear)];
+        let code = generate(&schematic, &options);
+        assert!(code.contains("impl Transition<Order, Order> for Order2 {"));
+        assert!(code.contains("impl Transition<Order, Order> for Order3 {"));
+        assert!(code.contains("    type Error = AppError;"));
+        assert!(code.contains(
+            "Axon::<Order, Order, AppError, ()>::new(\"Flow\")\n        .then(Order2)\n        .then(Order3)"
+        ));
+    }
+}
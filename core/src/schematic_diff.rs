@@ -0,0 +1,389 @@
+//! Structural comparison of two [`Schematic`]s.
+//!
+//! Node ids are derived from the circuit name, label and position, so an
+//! unchanged node keeps its id, but one that moved (e.g. after a node was
+//! inserted ahead of it) gets a new one. Nodes are therefore matched by id
+//! when both sides share it, then by label, and finally a leftover removed
+//! node and a leftover added node with the same kind and I/O types count as a
+//! rename.
+//! Nodes inside subgraphs are addressed by their label path, e.g.
+//! `Refunds/notify`. Edges are compared by the nodes they connect after
+//! matching, so renaming a node does not also report its edges.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schematic::{EdgeType, Node, NodeKind, Schematic};
+
+/// A node as reported by [`Schematic::diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeRef {
+    pub id: String,
+    /// Label path, with enclosing subgraph labels joined by `/`.
+    pub path: String,
+    pub kind: String,
+}
+
+/// A node matched across both schematics under a different label.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeRename {
+    pub before: NodeRef,
+    pub after: NodeRef,
+}
+
+/// An edge as reported by [`Schematic::diff`], between node label paths.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct EdgeRef {
+    pub from: String,
+    pub to: String,
+    /// `next`, `branch:<id>`, `jump`, `fault` or `parallel`.
+    pub kind: String,
+    pub label: Option<String>,
+}
+
+/// Result of [`Schematic::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchematicDiff {
+    pub added_nodes: Vec<NodeRef>,
+    pub removed_nodes: Vec<NodeRef>,
+    pub renamed_nodes: Vec<NodeRename>,
+    pub added_edges: Vec<EdgeRef>,
+    pub removed_edges: Vec<EdgeRef>,
+}
+
+impl SchematicDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.renamed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+/// One line per change, `-` for removals, `+` for additions and `~` for
+/// renames.
+impl std::fmt::Display for SchematicDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for node in &self.removed_nodes {
+            writeln!(f, "- node {} ({})", node.path, node.kind)?;
+        }
+        for node in &self.added_nodes {
+            writeln!(f, "+ node {} ({})", node.path, node.kind)?;
+        }
+        for rename in &self.renamed_nodes {
+            writeln!(
+                f,
+                "~ node {} -> {} ({})",
+                rename.before.path, rename.after.path, rename.after.kind
+            )?;
+        }
+        for edge in &self.removed_edges {
+            writeln!(f, "- edge {edge}")?;
+        }
+        for edge in &self.added_edges {
+            writeln!(f, "+ edge {edge}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for EdgeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {} [{}]", self.from, self.to, self.kind)?;
+        if let Some(label) = &self.label {
+            write!(f, " \"{label}\"")?;
+        }
+        Ok(())
+    }
+}
+
+impl Schematic {
+    /// What changed structurally from this schematic to `after`.
+    ///
+    /// ```rust
+    /// # use ranvier_core::Schematic;
+    /// let before = Schematic::new("Checkout");
+    /// assert!(before.diff(&before.clone()).is_empty());
+    /// ```
+    pub fn diff(&self, after: &Schematic) -> SchematicDiff {
+        let before_nodes = flatten(self);
+        let after_nodes = flatten(after);
+        let matches = match_nodes(&before_nodes, &after_nodes);
+
+        let mut diff = SchematicDiff::default();
+        let mut matched_after = vec![false; after_nodes.len()];
+        for (i, node) in before_nodes.iter().enumerate() {
+            match matches.get(&i) {
+                Some(&j) => {
+                    matched_after[j] = true;
+                    if node.path != after_nodes[j].path {
+                        diff.renamed_nodes.push(NodeRename {
+                            before: node.to_ref(),
+                            after: after_nodes[j].to_ref(),
+                        });
+                    }
+                }
+                None => diff.removed_nodes.push(node.to_ref()),
+            }
+        }
+        diff.added_nodes = after_nodes
+            .iter()
+            .zip(&matched_after)
+            .filter(|(_, matched)| !**matched)
+            .map(|(node, _)| node.to_ref())
+            .collect();
+
+        // Name both sides' edge ends by the after-path so renames cancel out.
+        let mut before_paths: HashMap<&str, &str> = before_nodes
+            .iter()
+            .map(|node| (node.node.id.as_str(), node.path.as_str()))
+            .collect();
+        for (&i, &j) in &matches {
+            before_paths.insert(&before_nodes[i].node.id, &after_nodes[j].path);
+        }
+        let after_paths: HashMap<&str, &str> = after_nodes
+            .iter()
+            .map(|node| (node.node.id.as_str(), node.path.as_str()))
+            .collect();
+        let mut before_edges = edges(self, &before_paths);
+        let mut after_edges = edges(after, &after_paths);
+        before_edges.sort();
+        after_edges.sort();
+        let (removed, added) = multiset_difference(before_edges, after_edges);
+        diff.removed_edges = removed;
+        diff.added_edges = added;
+        diff
+    }
+}
+
+struct FlatNode<'a> {
+    node: &'a Node,
+    path: String,
+}
+
+impl FlatNode<'_> {
+    fn to_ref(&self) -> NodeRef {
+        NodeRef {
+            id: self.node.id.clone(),
+            path: self.path.clone(),
+            kind: kind_name(&self.node.kind).to_string(),
+        }
+    }
+
+    fn signature(&self) -> (&'static str, &str, &str) {
+        (
+            kind_name(&self.node.kind),
+            &self.node.input_type,
+            &self.node.output_type,
+        )
+    }
+}
+
+fn flatten(schematic: &Schematic) -> Vec<FlatNode<'_>> {
+    fn walk<'a>(schematic: &'a Schematic, prefix: &str, out: &mut Vec<FlatNode<'a>>) {
+        for node in &schematic.nodes {
+            let path = format!("{prefix}{}", node.label);
+            if let NodeKind::Subgraph(inner) = &node.kind {
+                walk(inner, &format!("{path}/"), out);
+            }
+            out.push(FlatNode { node, path });
+        }
+    }
+    let mut out = Vec::new();
+    walk(schematic, "", &mut out);
+    out
+}
+
+/// Pair before-indices with after-indices: by id, then label path, then
+/// signature for the leftovers, each in order of appearance.
+fn match_nodes(before: &[FlatNode<'_>], after: &[FlatNode<'_>]) -> HashMap<usize, usize> {
+    let mut matches = HashMap::new();
+    let mut taken = vec![false; after.len()];
+    let passes: [&dyn Fn(&FlatNode<'_>, &FlatNode<'_>) -> bool; 3] = [
+        &|a, b| a.node.id == b.node.id,
+        &|a, b| a.path == b.path && a.signature().0 == b.signature().0,
+        &|a, b| a.signature() == b.signature(),
+    ];
+    for same in passes {
+        for (i, node) in before.iter().enumerate() {
+            if matches.contains_key(&i) {
+                continue;
+            }
+            if let Some(j) = (0..after.len()).find(|&j| !taken[j] && same(node, &after[j])) {
+                taken[j] = true;
+                matches.insert(i, j);
+            }
+        }
+    }
+    matches
+}
+
+fn edges(schematic: &Schematic, paths: &HashMap<&str, &str>) -> Vec<EdgeRef> {
+    let mut out = Vec::new();
+    let mut stack = vec![schematic];
+    while let Some(schematic) = stack.pop() {
+        for edge in &schematic.edges {
+            let path = |id: &str| {
+                paths
+                    .get(id)
+                    .map_or_else(|| id.to_string(), |p| p.to_string())
+            };
+            out.push(EdgeRef {
+                from: path(&edge.from),
+                to: path(&edge.to),
+                kind: match &edge.kind {
+                    EdgeType::Linear => "next".to_string(),
+                    EdgeType::Branch(id) => format!("branch:{id}"),
+                    EdgeType::Jump => "jump".to_string(),
+                    EdgeType::Fault => "fault".to_string(),
+                    EdgeType::Parallel => "parallel".to_string(),
+                },
+                label: edge.label.clone(),
+            });
+        }
+        stack.extend(schematic.nodes.iter().filter_map(|node| match &node.kind {
+            NodeKind::Subgraph(inner) => Some(inner.as_ref()),
+            _ => None,
+        }));
+    }
+    out
+}
+
+/// Elements only in `before` and only in `after`, both inputs sorted.
+fn multiset_difference(before: Vec<EdgeRef>, after: Vec<EdgeRef>) -> (Vec<EdgeRef>, Vec<EdgeRef>) {
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let mut before = before.into_iter().peekable();
+    let mut after = after.into_iter().peekable();
+    loop {
+        match (before.peek(), after.peek()) {
+            (Some(b), Some(a)) if b == a => {
+                before.next();
+                after.next();
+            }
+            (Some(b), Some(a)) if b < a => removed.extend(before.next()),
+            (Some(_), Some(_)) | (None, Some(_)) => added.extend(after.next()),
+            (Some(_), None) => removed.extend(before.next()),
+            (None, None) => break,
+        }
+    }
+    (removed, added)
+}
+
+pub(crate) fn kind_name(kind: &NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Ingress => "Ingress",
+        NodeKind::Atom => "Atom",
+        NodeKind::Synapse => "Synapse",
+        NodeKind::Egress => "Egress",
+        NodeKind::Subgraph(_) => "Subgraph",
+        NodeKind::FanOut => "FanOut",
+        NodeKind::FanIn => "FanIn",
+        NodeKind::StreamingTransition => "StreamingTransition",
+        NodeKind::Tap => "Tap",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::StepMetadata;
+    use crate::schematic::Edge;
+    use uuid::Uuid;
+
+    fn node(label: &str, output: &str) -> Node {
+        Node {
+            id: Uuid::new_v4().to_string(),
+            kind: NodeKind::Atom,
+            label: label.to_string(),
+            description: None,
+            input_type: "Order".into(),
+            output_type: output.into(),
+            resource_type: "()".into(),
+            metadata: StepMetadata::default(),
+            bus_capability: None,
+            source_location: None,
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        }
+    }
+
+    fn chain(nodes: Vec<Node>) -> Schematic {
+        let mut schematic = Schematic::new("orders");
+        for pair in nodes.windows(2) {
+            schematic.edges.push(Edge {
+                from: pair[0].id.clone(),
+                to: pair[1].id.clone(),
+                kind: EdgeType::Linear,
+                label: None,
+            });
+        }
+        schematic.nodes = nodes;
+        schematic
+    }
+
+    #[test]
+    fn diff_reports_renames_additions_and_edge_changes() {
+        let before = chain(vec![
+            node("validate", "Order"),
+            node("charge", "Receipt"),
+            node("email", "Receipt"),
+        ]);
+        // Rebuilt: fresh ids, `charge` renamed, `email` replaced by `ship`.
+        let after = chain(vec![
+            node("validate", "Order"),
+            node("charge_card", "Receipt"),
+            node("ship", "Shipment"),
+        ]);
+        assert!(before.diff(&before.clone()).is_empty());
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.renamed_nodes.len(), 1);
+        assert_eq!(diff.renamed_nodes[0].before.path, "charge");
+        assert_eq!(diff.renamed_nodes[0].after.path, "charge_card");
+        assert_eq!(diff.removed_nodes[0].path, "email");
+        assert_eq!(diff.added_nodes[0].path, "ship");
+        // validate -> charge survives the rename; only the tail edge changed.
+        assert_eq!(
+            diff.to_string(),
+            "- node email (Atom)\n\
+             + node ship (Atom)\n\
+             ~ node charge -> charge_card (Atom)\n\
+             - edge charge_card -> email [next]\n\
+             + edge charge_card -> ship [next]\n"
+        );
+    }
+
+    #[test]
+    fn diff_addresses_subgraph_nodes_by_path() {
+        let mut before = chain(vec![node("validate", "Order")]);
+        let mut sub = node("Refunds", "Order");
+        sub.kind = NodeKind::Subgraph(Box::new(chain(vec![node("refund", "Order")])));
+        before.nodes.push(sub.clone());
+
+        let mut after = before.clone();
+        let NodeKind::Subgraph(inner) = &mut after.nodes[1].kind else {
+            unreachable!()
+        };
+        inner.nodes.push(node("notify", "Order"));
+        inner.edges.push(Edge {
+            from: inner.nodes[0].id.clone(),
+            to: inner.nodes[1].id.clone(),
+            kind: EdgeType::Branch("partial".into()),
+            label: None,
+        });
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added_nodes[0].path, "Refunds/notify");
+        assert_eq!(
+            diff.added_edges[0].to_string(),
+            "Refunds/refund -> Refunds/notify [branch:partial]"
+        );
+        assert!(diff.removed_nodes.is_empty() && diff.renamed_nodes.is_empty());
+    }
+}
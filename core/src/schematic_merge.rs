@@ -0,0 +1,416 @@
+//! Union of two [`Schematic`]s.
+//!
+//! Used to compose a circuit from pieces built in different crates, or to
+//! put every circuit of a service on one map. Nodes are matched by id: a node
+//! whose id is new is added, and a node present on both sides is kept once
+//! when label, kind and I/O types agree. Subgraphs present on both sides are
+//! merged level by level. A node whose id matches but whose label, kind or
+//! types differ is a [`NodeConflict`], resolved according to [`OnConflict`].
+//! Edges are added unless an identical edge already exists.
+//!
+//! ```rust
+//! # use ranvier_core::Schematic;
+//! let checkout = Schematic::new("Checkout");
+//! let refunds = Schematic::new("Refunds");
+//! let service = checkout.merge(&refunds).unwrap();
+//! assert_eq!(service.name, "Checkout");
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schematic::{
+    Node, NodeKind, SCHEMA_VERSION_MISMATCH, SchemaVersionMismatch, Schematic, stable_node_id,
+};
+use crate::schematic_diff::kind_name;
+
+/// Error category for schematics that cannot be merged without losing a node.
+pub const SCHEMATIC_MERGE_CONFLICT: &str = "schematic_merge_conflict";
+
+/// What [`Schematic::merge_with`] does with a [`NodeConflict`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnConflict {
+    /// Keep this schematic's node; edges of the other side attach to it.
+    #[default]
+    KeepOurs,
+    /// Replace this schematic's node with the other side's.
+    KeepTheirs,
+    /// Keep both, giving the other side's node (and its edges) a new id.
+    Rename,
+}
+
+/// Label, kind and I/O types of one side of a [`NodeConflict`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeSignature {
+    pub label: String,
+    pub kind: String,
+    pub input_type: String,
+    pub output_type: String,
+}
+
+impl NodeSignature {
+    fn of(node: &Node) -> Self {
+        Self {
+            label: node.label.clone(),
+            kind: kind_name(&node.kind).to_string(),
+            input_type: node.input_type.clone(),
+            output_type: node.output_type.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for NodeSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}' ({}, {} -> {})",
+            self.label, self.kind, self.input_type, self.output_type
+        )
+    }
+}
+
+/// Two different nodes sharing an id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeConflict {
+    pub id: String,
+    /// Label path of the enclosing subgraphs, joined by `/`; empty at the top
+    /// level.
+    pub scope: String,
+    pub ours: NodeSignature,
+    pub theirs: NodeSignature,
+    /// Id the other side's node got under [`OnConflict::Rename`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renamed_to: Option<String>,
+}
+
+impl std::fmt::Display for NodeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.scope.is_empty() {
+            write!(f, "{}/", self.scope)?;
+        }
+        write!(f, "{}: {} vs {}", self.id, self.ours, self.theirs)
+    }
+}
+
+/// Result of [`Schematic::merge_with`].
+#[derive(Debug, Clone)]
+pub struct SchematicMerge {
+    pub schematic: Schematic,
+    /// Conflicts met, each resolved by the chosen [`OnConflict`].
+    pub conflicts: Vec<NodeConflict>,
+}
+
+/// Why [`Schematic::merge`] refused to merge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchematicMergeError {
+    /// The schematics use incompatible schema versions.
+    Version(SchemaVersionMismatch),
+    /// Nodes on both sides share an id but differ.
+    Conflicts {
+        circuit: String,
+        conflicts: Vec<NodeConflict>,
+    },
+}
+
+impl SchematicMergeError {
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Version(_) => SCHEMA_VERSION_MISMATCH,
+            Self::Conflicts { .. } => SCHEMATIC_MERGE_CONFLICT,
+        }
+    }
+}
+
+impl std::fmt::Display for SchematicMergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Version(mismatch) => write!(f, "{mismatch}"),
+            Self::Conflicts { circuit, conflicts } => {
+                write!(
+                    f,
+                    "{SCHEMATIC_MERGE_CONFLICT}: '{circuit}' has {} conflicting node(s)",
+                    conflicts.len()
+                )?;
+                for (i, conflict) in conflicts.iter().enumerate() {
+                    let sep = if i == 0 { " (" } else { "; " };
+                    write!(f, "{sep}{conflict}")?;
+                }
+                if !conflicts.is_empty() {
+                    f.write_str(")")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchematicMergeError {}
+
+impl From<SchemaVersionMismatch> for SchematicMergeError {
+    fn from(mismatch: SchemaVersionMismatch) -> Self {
+        Self::Version(mismatch)
+    }
+}
+
+impl From<SchematicMergeError> for String {
+    fn from(error: SchematicMergeError) -> Self {
+        error.to_string()
+    }
+}
+
+impl From<SchematicMergeError> for crate::error::RanvierError {
+    fn from(error: SchematicMergeError) -> Self {
+        Self::validation(error.to_string())
+    }
+}
+
+impl Schematic {
+    /// Union of this schematic and `other`, failing on any [`NodeConflict`].
+    ///
+    /// The result keeps this schematic's id, name and description.
+    pub fn merge(&self, other: &Schematic) -> Result<Schematic, SchematicMergeError> {
+        let merged = self.merge_with(other, OnConflict::KeepOurs)?;
+        if merged.conflicts.is_empty() {
+            Ok(merged.schematic)
+        } else {
+            Err(SchematicMergeError::Conflicts {
+                circuit: self.name.clone(),
+                conflicts: merged.conflicts,
+            })
+        }
+    }
+
+    /// Union of this schematic and `other`, resolving conflicts with
+    /// `on_conflict` and reporting each of them.
+    pub fn merge_with(
+        &self,
+        other: &Schematic,
+        on_conflict: OnConflict,
+    ) -> Result<SchematicMerge, SchemaVersionMismatch> {
+        SchemaVersionMismatch::check(&other.name, &self.schema_version, &other.schema_version)?;
+        let mut schematic = self.clone();
+        if schematic.cache_policy.is_none() {
+            schematic.cache_policy = other.cache_policy.clone();
+        }
+        let mut conflicts = Vec::new();
+        merge_level(&mut schematic, other, on_conflict, "", &mut conflicts);
+        Ok(SchematicMerge {
+            schematic,
+            conflicts,
+        })
+    }
+}
+
+fn merge_level(
+    ours: &mut Schematic,
+    theirs: &Schematic,
+    on_conflict: OnConflict,
+    scope: &str,
+    conflicts: &mut Vec<NodeConflict>,
+) {
+    let mut renamed: HashMap<&str, String> = HashMap::new();
+    let first_added = ours.nodes.len();
+    for node in &theirs.nodes {
+        let Some(existing) = ours.nodes.iter_mut().find(|n| n.id == node.id) else {
+            ours.nodes.push(node.clone());
+            continue;
+        };
+        let signature = NodeSignature::of(node);
+        let existing_signature = NodeSignature::of(existing);
+        if signature == existing_signature {
+            if let (NodeKind::Subgraph(inner), NodeKind::Subgraph(their_inner)) =
+                (&mut existing.kind, &node.kind)
+            {
+                let scope = if scope.is_empty() {
+                    node.label.clone()
+                } else {
+                    format!("{scope}/{}", node.label)
+                };
+                merge_level(inner, their_inner, on_conflict, &scope, conflicts);
+            }
+            continue;
+        }
+        let mut conflict = NodeConflict {
+            id: node.id.clone(),
+            scope: scope.to_string(),
+            ours: existing_signature,
+            theirs: signature,
+            renamed_to: None,
+        };
+        match on_conflict {
+            OnConflict::KeepOurs => {}
+            OnConflict::KeepTheirs => *existing = node.clone(),
+            OnConflict::Rename => {
+                let id = fresh_id(ours, theirs, node);
+                renamed.insert(&node.id, id.clone());
+                conflict.renamed_to = Some(id.clone());
+                ours.nodes.push(Node { id, ..node.clone() });
+            }
+        }
+        conflicts.push(conflict);
+    }
+
+    let rename = |id: &str| renamed.get(id).cloned().unwrap_or_else(|| id.to_string());
+    for node in &mut ours.nodes[first_added..] {
+        if let Some(id) = &node.compensation_node_id {
+            node.compensation_node_id = Some(rename(id));
+        }
+    }
+    for edge in &theirs.edges {
+        let mut edge = edge.clone();
+        edge.from = rename(&edge.from);
+        edge.to = rename(&edge.to);
+        if !ours.edges.contains(&edge) {
+            ours.edges.push(edge);
+        }
+    }
+}
+
+/// An id for `node` that neither side uses.
+fn fresh_id(ours: &Schematic, theirs: &Schematic, node: &Node) -> String {
+    (1..)
+        .map(|attempt| stable_node_id(&theirs.name, &node.id, attempt))
+        .find(|id| {
+            !ours.nodes.iter().any(|n| &n.id == id) && !theirs.nodes.iter().any(|n| &n.id == id)
+        })
+        .expect("an unused id exists")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{Edge, EdgeType};
+
+    fn node(id: &str, label: &str, output: &str) -> Node {
+        Node {
+            id: id.to_string(),
+            kind: NodeKind::Atom,
+            label: label.to_string(),
+            description: None,
+            input_type: "Order".into(),
+            output_type: output.into(),
+            resource_type: "()".into(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: None,
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> Edge {
+        Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind: EdgeType::Linear,
+            label: None,
+        }
+    }
+
+    fn circuit(name: &str, nodes: Vec<Node>, edges: Vec<Edge>) -> Schematic {
+        let mut schematic = Schematic::new(name);
+        schematic.nodes = nodes;
+        schematic.edges = edges;
+        schematic
+    }
+
+    #[test]
+    fn shared_nodes_and_edges_are_kept_once() {
+        let checkout = circuit(
+            "Checkout",
+            vec![node("a", "validate", "Order"), node("b", "charge", "Order")],
+            vec![edge("a", "b")],
+        );
+        let mut nested = circuit("Notify", vec![node("n1", "email", "Order")], vec![]);
+        let mut subgraph = node("s", "Notify", "Order");
+        subgraph.kind = NodeKind::Subgraph(Box::new(nested.clone()));
+        let refunds = circuit(
+            "Refunds",
+            vec![
+                node("a", "validate", "Order"),
+                node("r", "refund", "Order"),
+                subgraph.clone(),
+            ],
+            vec![edge("a", "b"), edge("a", "r"), edge("r", "s")],
+        );
+
+        let merged = checkout.merge(&refunds).unwrap();
+        let ids: Vec<&str> = merged.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "r", "s"]);
+        assert_eq!(merged.edges.len(), 3);
+        assert_eq!(merged.name, "Checkout");
+        assert_eq!(merged.id, checkout.id);
+
+        // Subgraphs on both sides are merged level by level.
+        nested.nodes.push(node("n2", "sms", "Order"));
+        subgraph.kind = NodeKind::Subgraph(Box::new(nested));
+        let more = circuit("Refunds", vec![subgraph], vec![]);
+        let merged = merged.merge(&more).unwrap();
+        let NodeKind::Subgraph(inner) = &merged.nodes[3].kind else {
+            panic!("subgraph expected");
+        };
+        assert_eq!(inner.nodes.len(), 2);
+    }
+
+    #[test]
+    fn conflicting_nodes_fail_or_resolve_per_policy() {
+        let ours = circuit(
+            "Checkout",
+            vec![
+                node("a", "validate", "Order"),
+                node("b", "charge", "Receipt"),
+            ],
+            vec![edge("a", "b")],
+        );
+        let theirs = circuit(
+            "Refunds",
+            vec![node("b", "refund", "Order"), node("c", "notify", "Order")],
+            vec![edge("b", "c")],
+        );
+
+        let error = ours.merge(&theirs).unwrap_err();
+        assert_eq!(error.category(), SCHEMATIC_MERGE_CONFLICT);
+        assert_eq!(
+            error.to_string(),
+            "schematic_merge_conflict: 'Checkout' has 1 conflicting node(s) \
+             (b: 'charge' (Atom, Order -> Receipt) vs 'refund' (Atom, Order -> Order))"
+        );
+
+        let kept = ours.merge_with(&theirs, OnConflict::KeepOurs).unwrap();
+        assert_eq!(kept.conflicts.len(), 1);
+        assert_eq!(kept.schematic.nodes[1].label, "charge");
+        assert!(kept.schematic.edges.contains(&edge("b", "c")));
+
+        let replaced = ours.merge_with(&theirs, OnConflict::KeepTheirs).unwrap();
+        assert_eq!(replaced.schematic.nodes[1].label, "refund");
+
+        let renamed = ours.merge_with(&theirs, OnConflict::Rename).unwrap();
+        let new_id = renamed.conflicts[0].renamed_to.clone().unwrap();
+        let labels: Vec<&str> = renamed
+            .schematic
+            .nodes
+            .iter()
+            .map(|n| n.label.as_str())
+            .collect();
+        assert_eq!(labels, ["validate", "charge", "refund", "notify"]);
+        assert_eq!(renamed.schematic.nodes[2].id, new_id);
+        assert!(renamed.schematic.edges.contains(&edge("a", "b")));
+        assert!(renamed.schematic.edges.contains(&edge(&new_id, "c")));
+        assert!(!renamed.schematic.edges.contains(&edge("b", "c")));
+    }
+
+    #[test]
+    fn incompatible_schema_versions_are_rejected() {
+        let ours = Schematic::new("Checkout");
+        let mut theirs = Schematic::new("Legacy");
+        theirs.schema_version = "0.9".to_string();
+        let error = ours.merge(&theirs).unwrap_err();
+        assert_eq!(error.category(), SCHEMA_VERSION_MISMATCH);
+    }
+}
@@ -0,0 +1,174 @@
+//! Every circuit of an application, by name.
+//!
+//! Axons register their schematic in [`SchematicRegistry::global`] when they
+//! are mounted on an ingress or first executed, and can register explicitly
+//! with `Axon::register`. A name holds one schematic: registering the same
+//! name again replaces it. [`SchematicRegistry::bundle`] returns all of them
+//! as one artifact for the Inspector (`/schematics`) and code generators,
+//! which should [`verify`](SchematicBundle::verify) it before use.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::schematic::{SCHEMA_VERSION, SchemaVersionMismatch, Schematic};
+
+static GLOBAL: OnceLock<SchematicRegistry> = OnceLock::new();
+
+/// Named schematics. Clones share the same entries.
+#[derive(Debug, Clone, Default)]
+pub struct SchematicRegistry {
+    entries: Arc<RwLock<BTreeMap<String, Schematic>>>,
+}
+
+impl SchematicRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide registry Axons register into.
+    pub fn global() -> &'static SchematicRegistry {
+        GLOBAL.get_or_init(SchematicRegistry::new)
+    }
+
+    /// Store `schematic` under its name, replacing any previous one.
+    ///
+    /// A schematic whose schema version this crate does not support is
+    /// stored anyway, with a warning; [`SchematicBundle::verify`] rejects it.
+    pub fn register(&self, schematic: Schematic) {
+        if let Err(mismatch) =
+            SchemaVersionMismatch::check(&schematic.name, SCHEMA_VERSION, &schematic.schema_version)
+        {
+            tracing::warn!(circuit = %schematic.name, "{mismatch}");
+        }
+        self.write().insert(schematic.name.clone(), schematic);
+    }
+
+    /// Register `schematic` unless the same build of it is already stored.
+    ///
+    /// A build is identified by circuit id and node count, so this is cheap
+    /// enough to call on every execution.
+    pub fn observe(&self, schematic: &Schematic) {
+        let current = self.read().get(&schematic.name).is_some_and(|stored| {
+            stored.id == schematic.id && stored.nodes.len() == schematic.nodes.len()
+        });
+        if !current {
+            self.register(schematic.clone());
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Schematic> {
+        self.read().get(name).cloned()
+    }
+
+    pub fn remove(&self, name: &str) -> Option<Schematic> {
+        self.write().remove(name)
+    }
+
+    /// Registered names, sorted.
+    pub fn names(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// All registered schematics as one artifact, sorted by name.
+    pub fn bundle(&self) -> SchematicBundle {
+        SchematicBundle {
+            schema_version: SCHEMA_VERSION.to_string(),
+            circuits: self.read().values().cloned().collect(),
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, BTreeMap<String, Schematic>> {
+        self.entries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, BTreeMap<String, Schematic>> {
+        self.entries
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Every circuit of an application, returned by [`SchematicRegistry::bundle`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchematicBundle {
+    pub schema_version: String,
+    pub circuits: Vec<Schematic>,
+}
+
+impl SchematicBundle {
+    pub fn get(&self, name: &str) -> Option<&Schematic> {
+        self.circuits.iter().find(|circuit| circuit.name == name)
+    }
+
+    /// Check that this crate can read the bundle and that every circuit in
+    /// it shares the bundle's schema major version.
+    pub fn verify(&self) -> Result<(), SchemaVersionMismatch> {
+        SchemaVersionMismatch::check("bundle", SCHEMA_VERSION, &self.schema_version)?;
+        for circuit in &self.circuits {
+            SchemaVersionMismatch::check(
+                &circuit.name,
+                &self.schema_version,
+                &circuit.schema_version,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observe_replaces_only_changed_builds() {
+        let registry = SchematicRegistry::new();
+        let orders = Schematic::new("orders");
+        registry.observe(&orders);
+        registry.register(Schematic::new("billing"));
+        assert_eq!(registry.names(), ["billing", "orders"]);
+
+        // Same id and node count: the stored build is kept.
+        let mut same_build = orders.clone();
+        same_build.description = Some("ignored".into());
+        registry.observe(&same_build);
+        assert_eq!(registry.get("orders").unwrap().description, None);
+
+        let rebuilt = Schematic::new("orders");
+        registry.observe(&rebuilt);
+        assert_eq!(registry.get("orders").unwrap().id, rebuilt.id);
+
+        let bundle = registry.bundle();
+        assert_eq!(bundle.circuits.len(), 2);
+        assert!(bundle.get("billing").is_some());
+        assert!(bundle.verify().is_ok());
+    }
+
+    #[test]
+    fn verify_rejects_circuits_from_another_schema_major() {
+        let registry = SchematicRegistry::new();
+        registry.register(Schematic::new("orders"));
+        let mut legacy = Schematic::new("legacy");
+        legacy.schema_version = "2.0".into();
+        registry.register(legacy);
+
+        let mismatch = registry.bundle().verify().unwrap_err();
+        assert_eq!(mismatch.artifact, "legacy");
+        assert_eq!(mismatch.found, "2.0");
+        assert_eq!(
+            mismatch.to_string(),
+            "schema_version_mismatch: 'legacy' has schema version 2.0, expected 1.0"
+        );
+    }
+}
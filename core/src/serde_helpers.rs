@@ -0,0 +1,51 @@
+//! `#[serde(with = "...")]` helpers shared by the crate's config types.
+
+/// `Option<Duration>` as fractional seconds.
+///
+/// Negative, non-finite and out-of-range values are rejected when
+/// deserializing.
+pub(crate) mod optional_duration_secs {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(val: &Option<Duration>, ser: S) -> Result<S::Ok, S::Error> {
+        match val {
+            Some(d) => d.as_secs_f64().serialize(ser),
+            None => ser.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<Option<Duration>, D::Error> {
+        let opt: Option<f64> = Option::deserialize(de)?;
+        opt.map(|secs| {
+            Duration::try_from_secs_f64(secs).map_err(|e| {
+                serde::de::Error::custom(format_args!("invalid duration {secs}s: {e}"))
+            })
+        })
+        .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use std::time::Duration;
+
+    #[derive(Debug, Deserialize)]
+    struct Timeout {
+        #[serde(default, with = "super::optional_duration_secs")]
+        after: Option<Duration>,
+    }
+
+    #[test]
+    fn optional_duration_secs_rejects_invalid_seconds() {
+        let parse = |json: &str| serde_json::from_str::<Timeout>(json).map(|t| t.after);
+        assert_eq!(
+            parse(r#"{"after": 1.5}"#).unwrap(),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(parse(r#"{"after": null}"#).unwrap(), None);
+        assert!(parse(r#"{"after": -1.0}"#).is_err());
+        assert!(parse(r#"{"after": 1e300}"#).is_err());
+    }
+}
@@ -94,6 +94,16 @@ pub trait StaticAxon: Send + Sync {
     ///
     /// This is called at build time with an empty or pre-configured Bus.
     fn generate(&self, bus: &mut Bus) -> Result<Outcome<Self::Output, Self::Error>>;
+
+    /// Rust type name of the output, recorded in the manifest for typed clients.
+    fn output_type(&self) -> &'static str {
+        std::any::type_name::<Self::Output>()
+    }
+
+    /// Human-readable description of the state, emitted as JSDoc by typed clients.
+    fn description(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Manifest for static build output.
@@ -127,8 +137,28 @@ impl StaticManifest {
             name: name.into(),
             file: file.into(),
             content_type: "application/json".to_string(),
+            output_type: None,
+            description: None,
         });
     }
+
+    /// Add a state entry along with the Rust type it was generated from.
+    ///
+    /// Only the last path segment of a non-generic type is kept
+    /// (`app::state::LandingState` → `LandingState`).
+    pub fn add_typed_state(
+        &mut self,
+        name: impl Into<String>,
+        file: impl Into<String>,
+        output_type: &str,
+    ) {
+        self.add_state(name, file);
+        if let Some(entry) = self.states.last_mut()
+            && !output_type.contains('<')
+        {
+            entry.output_type = output_type.rsplit("::").next().map(str::to_string);
+        }
+    }
 }
 
 impl Default for StaticManifest {
@@ -148,6 +178,14 @@ pub struct StaticStateEntry {
 
     /// MIME type of the content
     pub content_type: String,
+
+    /// Short name of the `StaticAxon::Output` type, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_type: Option<String>,
+
+    /// Description of the state, usually the generating circuit's doc comment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 /// Configuration for static builds.
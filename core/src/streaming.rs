@@ -9,8 +9,12 @@
 //! * **Separate trait** — does not modify existing `Transition` or `Outcome`
 //! * **Terminal semantics** — `then_stream()` is the last step in an Axon chain
 //! * **Bus snapshot** — Bus is available in `run_stream()` but not during streaming
+//!
+//! [`StreamTransition`] is the non-terminal variant: it yields an `Outcome`
+//! per item, and later per-item steps consume the stream one item at a time.
 
 use crate::bus::Bus;
+use crate::outcome::Outcome;
 use crate::transition::ResourceRequirement;
 use async_trait::async_trait;
 use futures_core::Stream;
@@ -139,6 +143,76 @@ where
     }
 }
 
+// ---------------------------------------------------------------------------
+// StreamTransition — per-item outcomes
+// ---------------------------------------------------------------------------
+
+/// A stream of per-item outcomes produced by a [`StreamTransition`].
+pub type OutcomeStream<To, E> = Pin<Box<dyn Stream<Item = Outcome<To, E>> + Send>>;
+
+/// A transition that yields one `Outcome` per item instead of a single one.
+///
+/// Unlike [`StreamingTransition`], items are `Outcome`s, so a single item can
+/// fault without ending the whole stream, and the runtime can run further
+/// transitions on each `Next` item as it arrives, without collecting the
+/// stream in between. Failing to start the stream is reported as a single
+/// `Fault` item.
+///
+/// ```rust,ignore
+/// #[async_trait::async_trait]
+/// impl StreamTransition<ExportRequest, CustomerRow> for ScanCustomers {
+///     type Error = DbError;
+///     type Resources = AppResources;
+///
+///     async fn run_stream(
+///         &self,
+///         request: ExportRequest,
+///         resources: &AppResources,
+///         _bus: &mut Bus,
+///     ) -> OutcomeStream<CustomerRow, DbError> {
+///         let rows = resources.db.stream_customers(request.since);
+///         Box::pin(rows.map(|row| match row {
+///             Ok(row) => Outcome::Next(row),
+///             Err(error) => Outcome::Fault(error),
+///         }))
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait StreamTransition<From, To>: Send + Sync + 'static
+where
+    From: Send + 'static,
+    To: Send + 'static,
+{
+    /// Domain-specific error type of faulted items.
+    type Error: Send + Sync + Debug + 'static;
+
+    /// The type of resources required by this transition.
+    type Resources: ResourceRequirement;
+
+    /// Start the stream of per-item outcomes for `input`.
+    ///
+    /// The Bus is only available while the stream is being created.
+    async fn run_stream(
+        &self,
+        input: From,
+        resources: &Self::Resources,
+        bus: &mut Bus,
+    ) -> OutcomeStream<To, Self::Error>;
+
+    /// Returns a human-readable label for this transition.
+    /// Defaults to the type name.
+    fn label(&self) -> String {
+        let full = std::any::type_name::<Self>();
+        full.split("::").last().unwrap_or(full).to_string()
+    }
+
+    /// Returns a description of what this transition does.
+    fn description(&self) -> Option<String> {
+        None
+    }
+}
+
 // ---------------------------------------------------------------------------
 // StreamEvent — SSE framing protocol
 // ---------------------------------------------------------------------------
@@ -228,7 +302,7 @@ pub struct StreamTimeoutConfig {
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
-        with = "optional_duration_secs"
+        with = "crate::serde_helpers::optional_duration_secs"
     )]
     pub init: Option<Duration>,
 
@@ -236,7 +310,7 @@ pub struct StreamTimeoutConfig {
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
-        with = "optional_duration_secs"
+        with = "crate::serde_helpers::optional_duration_secs"
     )]
     pub idle: Option<Duration>,
 
@@ -244,7 +318,7 @@ pub struct StreamTimeoutConfig {
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
-        with = "optional_duration_secs"
+        with = "crate::serde_helpers::optional_duration_secs"
     )]
     pub total: Option<Duration>,
 }
@@ -284,24 +358,6 @@ impl Default for StreamTimeoutConfig {
     }
 }
 
-/// Serde helper for optional Duration as seconds (f64).
-mod optional_duration_secs {
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
-    use std::time::Duration;
-
-    pub fn serialize<S: Serializer>(val: &Option<Duration>, ser: S) -> Result<S::Ok, S::Error> {
-        match val {
-            Some(d) => d.as_secs_f64().serialize(ser),
-            None => ser.serialize_none(),
-        }
-    }
-
-    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<Option<Duration>, D::Error> {
-        let opt: Option<f64> = Option::deserialize(de)?;
-        Ok(opt.map(Duration::from_secs_f64))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -9,8 +9,12 @@ use async_trait::async_trait;
 use std::fmt::Debug;
 
 /// Represents the context of a Trace (e.g., Trace ID, Span ID).
-/// In a real OTLP implementation, this would hold the actual SpanContext.
-#[derive(Debug, Clone)]
+///
+/// Generated IDs use the W3C trace context format (32 and 16 lowercase hex
+/// digits), so they can be handed to an OTLP exporter as they are. An ingress
+/// that propagates an incoming trace inserts its context into the Bus; the
+/// Axon executor then runs each execution in a [`child`](Self::child) span.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TraceContext {
     pub trace_id: String,
     pub span_id: String,
@@ -19,8 +23,8 @@ pub struct TraceContext {
 impl Default for TraceContext {
     fn default() -> Self {
         Self {
-            trace_id: uuid::Uuid::new_v4().to_string(),
-            span_id: uuid::Uuid::new_v4().to_string(),
+            trace_id: uuid::Uuid::new_v4().simple().to_string(),
+            span_id: new_span_id(),
         }
     }
 }
@@ -29,6 +33,20 @@ impl TraceContext {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// A new span in the same trace.
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            span_id: new_span_id(),
+        }
+    }
+}
+
+fn new_span_id() -> String {
+    let mut id = uuid::Uuid::new_v4().simple().to_string();
+    id.truncate(16);
+    id
 }
 
 /// A wrapper Transition that adds telemetry (tracing) to any inner Transition.
@@ -92,6 +110,12 @@ where
                 Outcome::Emit(event_type, _) => {
                     tracing::info!(?event_type, ?duration, "Transition completed: Emit");
                 }
+                Outcome::Suspend(token, _) => {
+                    tracing::info!(?token, ?duration, "Transition completed: Suspend");
+                }
+                Outcome::Retry { after } => {
+                    tracing::info!(?after, ?duration, "Transition completed: Retry");
+                }
                 Outcome::Fault(e) => {
                     tracing::error!(error = ?e, ?duration, "Transition failed: Fault");
                 }
@@ -4,6 +4,7 @@
 //! consume via the Bus:
 //!
 //! * [`TenantId`] — typed tenant identifier (Bus-injectable)
+//! * [`TenantTier`] — service tier of the tenant (Bus-injectable)
 //! * [`TenantExtractor`] — trait for resolving a tenant from context
 //! * [`IsolationPolicy`] — how strict tenant enforcement is
 //! * [`TenantResolver`] — hint for where to find the tenant in HTTP requests
@@ -35,6 +36,25 @@ impl std::fmt::Display for TenantId {
     }
 }
 
+// ── Tenant tier ───────────────────────────────────────────────
+
+/// Service tier of the current tenant (e.g. `"free"`, `"premium"`).
+///
+/// Used to select tier-specific policy such as rate limits declared in
+/// `ranvier.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TenantTier(pub String);
+
+impl TenantTier {
+    pub fn new(tier: impl Into<String>) -> Self {
+        Self(tier.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 // ── Extractor trait ───────────────────────────────────────────
 
 /// Helper extension trait to retrieve [`TenantId`] from a generic
@@ -1,51 +1,419 @@
+pub mod chrome_trace;
+pub mod diff;
+pub mod folded;
+pub mod otlp;
+pub mod stats;
+pub mod tail;
+
+use crate::bus_snapshot::BusSnapshot;
+use crate::cancellation::CancellationReason;
+use crate::capture::{CaptureFormat, CapturedPayload};
+use crate::fault::FaultCause;
 use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Current Timeline serialization version.
+///
+/// - `1` (or no `version` in the header): event timestamps are epoch millis.
+/// - `2`: event timestamps are [`Timestamp`] nanoseconds since the epoch.
+pub const TIMELINE_FORMAT_VERSION: u32 = 2;
+
+const LEGACY_TIMELINE_VERSION: u32 = 1;
+
+/// Nanoseconds since the Unix epoch.
+///
+/// [`Timestamp::now`] is anchored to the wall clock once per process and then
+/// advanced with a monotonic clock, so timestamps taken in one process never
+/// go backwards and their differences are exact durations.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    pub const UNIX_EPOCH: Self = Self(0);
+
+    pub fn now() -> Self {
+        static ANCHOR: OnceLock<(Instant, Timestamp)> = OnceLock::new();
+        let (instant, wall) = ANCHOR.get_or_init(|| (Instant::now(), Self::wall_clock()));
+        wall.saturating_add(instant.elapsed())
+    }
+
+    /// Read the system clock directly, without the monotonic guarantee.
+    pub fn wall_clock() -> Self {
+        Self::from_system_time(SystemTime::now())
+    }
+
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(nanos)
+    }
+
+    pub const fn from_micros(micros: u64) -> Self {
+        Self(micros.saturating_mul(1_000))
+    }
+
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(millis.saturating_mul(1_000_000))
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(secs.saturating_mul(1_000_000_000))
+    }
+
+    /// Times before the epoch clamp to [`Timestamp::UNIX_EPOCH`].
+    pub fn from_system_time(time: SystemTime) -> Self {
+        time.duration_since(UNIX_EPOCH)
+            .map(|d| Self(u64::try_from(d.as_nanos()).unwrap_or(u64::MAX)))
+            .unwrap_or(Self::UNIX_EPOCH)
+    }
+
+    pub const fn as_nanos(self) -> u64 {
+        self.0
+    }
+
+    pub const fn as_micros(self) -> u64 {
+        self.0 / 1_000
+    }
+
+    pub const fn as_millis(self) -> u64 {
+        self.0 / 1_000_000
+    }
+
+    pub const fn as_secs(self) -> u64 {
+        self.0 / 1_000_000_000
+    }
+
+    pub fn to_system_time(self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_nanos(self.0)
+    }
+
+    pub fn to_datetime(self) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp_nanos(i64::try_from(self.0).unwrap_or(i64::MAX))
+    }
+
+    /// Time elapsed from `earlier` to `self`; zero if `earlier` is later.
+    pub fn duration_since(self, earlier: Self) -> Duration {
+        Duration::from_nanos(self.0.saturating_sub(earlier.0))
+    }
+
+    /// Time elapsed since `self`, measured with [`Timestamp::now`].
+    pub fn elapsed(self) -> Duration {
+        Self::now().duration_since(self)
+    }
+
+    pub fn saturating_add(self, duration: Duration) -> Self {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        Self(self.0.saturating_add(nanos))
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        Self::from_system_time(time)
+    }
+}
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &self
+                .to_datetime()
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+        )
+    }
+}
+
+/// Which side of a circuit, or of a single node, a captured payload belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CaptureDirection {
+    Input,
+    Output,
+    /// Input of a node, recorded under a
+    /// [`NodeCapturePolicy`](crate::capture::NodeCapturePolicy).
+    NodeInput,
+    /// Output of a node that completed with `Outcome::Next`.
+    NodeOutput,
+}
+
+/// Identifies the execution that recorded a [`TimelineEvent`].
+///
+/// The executor fills it in for every event of a run, so runs interleaved in
+/// one timeline (appended files, merged parallel branches) can be told apart
+/// and joined with the OTLP span of the execution. IDs use the W3C trace
+/// context format unless the caller provided its own
+/// [`TraceContext`](crate::telemetry::TraceContext).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EventTrace {
+    pub trace_id: String,
+    pub run_id: String,
+    /// Span of the execution the event happened in.
+    pub parent_span_id: String,
+}
 
 /// Represents a discrete event in the execution timeline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TimelineEvent {
     /// Execution started at a node
     NodeEnter {
         node_id: String,
         node_label: String,
-        timestamp: u64,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
     },
     /// Execution finished at a node
     NodeExit {
         node_id: String,
         outcome_type: String, // "Next", "Branch", "Error"
         duration_ms: u64,
-        timestamp: u64,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
     },
     /// Execution paused at a node (debugger)
-    NodePaused { node_id: String, timestamp: u64 },
+    NodePaused {
+        node_id: String,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
+    },
     /// A branch decision was made
-    Branchtaken { branch_id: String, timestamp: u64 },
+    Branchtaken {
+        branch_id: String,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
+    },
     /// A faulted node is being retried (DLQ RetryThenDlq policy)
     NodeRetry {
         node_id: String,
         attempt: u32,
         max_attempts: u32,
         backoff_ms: u64,
-        timestamp: u64,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
     },
     /// All retry attempts exhausted; event sent to Dead Letter Queue
     DlqExhausted {
         node_id: String,
         total_attempts: u32,
-        timestamp: u64,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
     },
     /// A node execution exceeded the configured timeout
     NodeTimeout {
         node_id: String,
         timeout_ms: u64,
-        timestamp: u64,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
+    },
+    /// An input or output payload recorded under the circuit's capture policy
+    PayloadCaptured {
+        node_id: String,
+        direction: CaptureDirection,
+        payload: CapturedPayload,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
+    },
+    /// A node faulted; `cause` holds the full cause tree at that point
+    FaultRecorded {
+        node_id: String,
+        cause: FaultCause,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
+    },
+    /// The execution was cancelled; `node_id` is the node that was running
+    /// or about to start, if any
+    ExecutionCancelled {
+        node_id: Option<String>,
+        reason: CancellationReason,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
+    },
+    /// A `Cached` transition looked up its key; `hit` is false on a miss
+    CacheLookup {
+        node_id: String,
+        key: String,
+        hit: bool,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
+    },
+    /// The Bus as the circuit received it, under a capture policy that
+    /// includes the Bus
+    BusCaptured {
+        node_id: String,
+        snapshot: BusSnapshot,
+        timestamp: Timestamp,
+        /// Execution that recorded the event; see [`EventTrace`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<EventTrace>,
     },
 }
 
+impl TimelineEvent {
+    pub fn timestamp(&self) -> Timestamp {
+        match self {
+            Self::NodeEnter { timestamp, .. }
+            | Self::NodeExit { timestamp, .. }
+            | Self::NodePaused { timestamp, .. }
+            | Self::Branchtaken { timestamp, .. }
+            | Self::NodeRetry { timestamp, .. }
+            | Self::DlqExhausted { timestamp, .. }
+            | Self::NodeTimeout { timestamp, .. }
+            | Self::PayloadCaptured { timestamp, .. }
+            | Self::FaultRecorded { timestamp, .. }
+            | Self::ExecutionCancelled { timestamp, .. }
+            | Self::CacheLookup { timestamp, .. }
+            | Self::BusCaptured { timestamp, .. } => *timestamp,
+        }
+    }
+
+    pub fn trace(&self) -> Option<&EventTrace> {
+        match self {
+            Self::NodeEnter { trace, .. }
+            | Self::NodeExit { trace, .. }
+            | Self::NodePaused { trace, .. }
+            | Self::Branchtaken { trace, .. }
+            | Self::NodeRetry { trace, .. }
+            | Self::DlqExhausted { trace, .. }
+            | Self::NodeTimeout { trace, .. }
+            | Self::PayloadCaptured { trace, .. }
+            | Self::FaultRecorded { trace, .. }
+            | Self::ExecutionCancelled { trace, .. }
+            | Self::CacheLookup { trace, .. }
+            | Self::BusCaptured { trace, .. } => trace.as_ref(),
+        }
+    }
+
+    fn trace_mut(&mut self) -> &mut Option<EventTrace> {
+        match self {
+            Self::NodeEnter { trace, .. }
+            | Self::NodeExit { trace, .. }
+            | Self::NodePaused { trace, .. }
+            | Self::Branchtaken { trace, .. }
+            | Self::NodeRetry { trace, .. }
+            | Self::DlqExhausted { trace, .. }
+            | Self::NodeTimeout { trace, .. }
+            | Self::PayloadCaptured { trace, .. }
+            | Self::FaultRecorded { trace, .. }
+            | Self::ExecutionCancelled { trace, .. }
+            | Self::CacheLookup { trace, .. }
+            | Self::BusCaptured { trace, .. } => trace,
+        }
+    }
+
+    fn timestamp_mut(&mut self) -> &mut Timestamp {
+        match self {
+            Self::NodeEnter { timestamp, .. }
+            | Self::NodeExit { timestamp, .. }
+            | Self::NodePaused { timestamp, .. }
+            | Self::Branchtaken { timestamp, .. }
+            | Self::NodeRetry { timestamp, .. }
+            | Self::DlqExhausted { timestamp, .. }
+            | Self::NodeTimeout { timestamp, .. }
+            | Self::PayloadCaptured { timestamp, .. }
+            | Self::FaultRecorded { timestamp, .. }
+            | Self::ExecutionCancelled { timestamp, .. }
+            | Self::CacheLookup { timestamp, .. }
+            | Self::BusCaptured { timestamp, .. } => timestamp,
+        }
+    }
+}
+
+/// Timeline-wide metadata needed to interpret its events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TimelineHeader {
+    /// Serialization version (see [`TIMELINE_FORMAT_VERSION`]).
+    #[serde(default = "legacy_timeline_version")]
+    pub version: u32,
+    /// Format of `PayloadCaptured` events, when capture is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_format: Option<CaptureFormat>,
+}
+
+impl Default for TimelineHeader {
+    fn default() -> Self {
+        Self {
+            version: TIMELINE_FORMAT_VERSION,
+            capture_format: None,
+        }
+    }
+}
+
+fn legacy_timeline_version() -> u32 {
+    LEGACY_TIMELINE_VERSION
+}
+
+fn legacy_timeline_header() -> TimelineHeader {
+    TimelineHeader {
+        version: LEGACY_TIMELINE_VERSION,
+        ..TimelineHeader::default()
+    }
+}
+
 /// A sequential record of an execution session.
+///
+/// Timelines written before [`TIMELINE_FORMAT_VERSION`] 2 are upgraded on
+/// deserialization: their millisecond timestamps are converted to nanoseconds.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(from = "SerializedTimeline")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Timeline {
+    pub header: TimelineHeader,
     pub events: Vec<TimelineEvent>,
+    /// Live feed of pushed events; see [`tail`].
+    #[serde(skip)]
+    tail: Option<tail::TailTap>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct SerializedTimeline {
+    #[serde(default = "legacy_timeline_header")]
+    header: TimelineHeader,
+    events: Vec<TimelineEvent>,
+}
+
+impl From<SerializedTimeline> for Timeline {
+    fn from(mut raw: SerializedTimeline) -> Self {
+        if raw.header.version < TIMELINE_FORMAT_VERSION {
+            for event in &mut raw.events {
+                let timestamp = event.timestamp_mut();
+                *timestamp = Timestamp::from_millis(timestamp.as_nanos());
+            }
+            raw.header.version = TIMELINE_FORMAT_VERSION;
+        }
+        Self {
+            header: raw.header,
+            events: raw.events,
+            tail: None,
+        }
+    }
 }
 
 impl Timeline {
@@ -54,40 +422,136 @@ impl Timeline {
     }
 
     pub fn push(&mut self, event: TimelineEvent) {
+        self.publish_to_tail(&event);
         self.events.push(event);
     }
 
     /// Sort events by timestamp while preserving insertion order for ties.
     ///
     /// Parallel execution uses deterministic phase/declaration ordering before
-    /// insertion, so millisecond timestamp ties remain reproducible here.
+    /// insertion, so timestamp ties remain reproducible here.
     pub fn sort(&mut self) {
-        self.events.sort_by_key(|e| match e {
-            TimelineEvent::NodeEnter { timestamp, .. } => *timestamp,
-            TimelineEvent::NodeExit { timestamp, .. } => *timestamp,
-            TimelineEvent::NodePaused { timestamp, .. } => *timestamp,
-            TimelineEvent::Branchtaken { timestamp, .. } => *timestamp,
-            TimelineEvent::NodeRetry { timestamp, .. } => *timestamp,
-            TimelineEvent::DlqExhausted { timestamp, .. } => *timestamp,
-            TimelineEvent::NodeTimeout { timestamp, .. } => *timestamp,
-        });
+        self.events.sort_by_key(TimelineEvent::timestamp);
+    }
+
+    /// Attribute every event that has no [`EventTrace`] yet to `trace`.
+    ///
+    /// Events recorded by a nested execution were stamped when it finished
+    /// and keep their own span.
+    pub fn stamp(&mut self, trace: &EventTrace) {
+        for event in &mut self.events {
+            let slot = event.trace_mut();
+            if slot.is_none() {
+                *slot = Some(trace.clone());
+            }
+        }
+    }
+
+    /// The events recorded by one run, e.g. to separate runs appended to a
+    /// shared timeline file.
+    pub fn run(&self, run_id: &str) -> Timeline {
+        Timeline {
+            header: self.header.clone(),
+            events: self
+                .events
+                .iter()
+                .filter(|event| event.trace().is_some_and(|trace| trace.run_id == run_id))
+                .cloned()
+                .collect(),
+            tail: None,
+        }
+    }
+
+    /// Cause tree of the most recent fault, if any node faulted.
+    pub fn latest_fault(&self) -> Option<&FaultCause> {
+        self.events.iter().rev().find_map(|event| match event {
+            TimelineEvent::FaultRecorded { cause, .. } => Some(cause),
+            _ => None,
+        })
+    }
+
+    /// The Bus captured when the circuit was entered.
+    pub fn captured_bus(&self) -> Option<&BusSnapshot> {
+        self.events.iter().find_map(|event| match event {
+            TimelineEvent::BusCaptured { snapshot, .. } => Some(snapshot),
+            _ => None,
+        })
+    }
+
+    /// The first captured payload in `direction` (the circuit input or output).
+    pub fn captured_payload(&self, direction: CaptureDirection) -> Option<&CapturedPayload> {
+        self.events.iter().find_map(|event| match event {
+            TimelineEvent::PayloadCaptured {
+                direction: d,
+                payload,
+                ..
+            } if *d == direction => Some(payload),
+            _ => None,
+        })
+    }
+
+    /// Payloads captured for `node_id`, in recording order (one pair per
+    /// visit of a node inside a loop).
+    pub fn node_payloads<'a>(
+        &'a self,
+        node_id: &'a str,
+    ) -> impl Iterator<Item = (CaptureDirection, &'a CapturedPayload)> + 'a {
+        self.events.iter().filter_map(move |event| match event {
+            TimelineEvent::PayloadCaptured {
+                node_id: id,
+                direction: direction @ (CaptureDirection::NodeInput | CaptureDirection::NodeOutput),
+                payload,
+                ..
+            } if id == node_id => Some((*direction, payload)),
+            _ => None,
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Timeline, TimelineEvent};
+    use super::{CaptureDirection, TIMELINE_FORMAT_VERSION, Timeline, TimelineEvent, Timestamp};
+    use crate::capture::{CaptureFormat, CapturedPayload};
+    use std::time::Duration;
+
+    #[test]
+    fn header_defaults_when_absent_and_round_trips_format() {
+        let legacy: Timeline = serde_json::from_str(r#"{"events":[]}"#).unwrap();
+        assert_eq!(legacy.header.capture_format, None);
+
+        let mut timeline = Timeline::new();
+        timeline.header.capture_format = Some(CaptureFormat::Cbor);
+        timeline.push(TimelineEvent::PayloadCaptured {
+            node_id: "ingress".into(),
+            direction: CaptureDirection::Input,
+            payload: CapturedPayload::encode(CaptureFormat::Cbor, &42u32).unwrap(),
+            timestamp: Timestamp::from_nanos(1),
+            trace: None,
+        });
+        let restored: Timeline =
+            serde_json::from_str(&serde_json::to_string(&timeline).unwrap()).unwrap();
+        assert_eq!(restored.header.capture_format, Some(CaptureFormat::Cbor));
+        let input = restored.captured_payload(CaptureDirection::Input).unwrap();
+        assert_eq!(input.decode::<u32>().unwrap(), 42);
+        assert!(
+            restored
+                .captured_payload(CaptureDirection::Output)
+                .is_none()
+        );
+    }
 
     #[test]
     fn sort_preserves_insertion_order_for_equal_timestamps() {
         let mut timeline = Timeline::new();
         timeline.push(TimelineEvent::NodePaused {
             node_id: "first".to_string(),
-            timestamp: 42,
+            timestamp: Timestamp::from_nanos(42),
+            trace: None,
         });
         timeline.push(TimelineEvent::NodePaused {
             node_id: "second".to_string(),
-            timestamp: 42,
+            timestamp: Timestamp::from_nanos(42),
+            trace: None,
         });
 
         timeline.sort();
@@ -102,4 +566,48 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(ids, vec!["first", "second"]);
     }
+
+    #[test]
+    fn timestamp_conversions_round_trip() {
+        let ts = Timestamp::from_millis(1_700_000_000_123);
+        assert_eq!(ts.as_nanos(), 1_700_000_000_123_000_000);
+        assert_eq!(ts.as_micros(), 1_700_000_000_123_000);
+        assert_eq!(ts.as_secs(), 1_700_000_000);
+        assert_eq!(Timestamp::from_system_time(ts.to_system_time()), ts);
+        assert_eq!(ts.to_string(), "2023-11-14T22:13:20.123Z");
+        assert_eq!(
+            Timestamp::from_nanos(2_500).duration_since(Timestamp::from_micros(1)),
+            Duration::from_nanos(1_500)
+        );
+        assert_eq!(
+            Timestamp::from_nanos(1).duration_since(Timestamp::from_nanos(5)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn now_is_monotonic() {
+        let first = Timestamp::now();
+        let second = Timestamp::now();
+        assert!(second >= first);
+        assert!(first.as_secs() > 1_600_000_000);
+    }
+
+    #[test]
+    fn legacy_millisecond_timelines_are_upgraded() {
+        let legacy: Timeline =
+            serde_json::from_str(r#"{"events":[{"NodePaused":{"node_id":"a","timestamp":1500}}]}"#)
+                .unwrap();
+        assert_eq!(legacy.header.version, TIMELINE_FORMAT_VERSION);
+        assert_eq!(legacy.events[0].timestamp(), Timestamp::from_millis(1500));
+
+        let json = serde_json::to_value(&legacy).unwrap();
+        assert_eq!(json["header"]["version"], TIMELINE_FORMAT_VERSION);
+        assert_eq!(
+            json["events"][0]["NodePaused"]["timestamp"],
+            1_500_000_000u64
+        );
+        let reread: Timeline = serde_json::from_value(json).unwrap();
+        assert_eq!(reread.events[0].timestamp(), Timestamp::from_millis(1500));
+    }
 }
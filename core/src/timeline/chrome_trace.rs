@@ -0,0 +1,267 @@
+//! Chrome trace event format export of a [`Timeline`].
+//!
+//! The output loads in `chrome://tracing`, Perfetto (ui.perfetto.dev) and
+//! Speedscope:
+//!
+//! | Timeline | Trace event |
+//! |---|---|
+//! | `NodeEnter` + `NodeExit` | complete event (`X`) named after the node label |
+//! | `NodeEnter` without exit | begin event (`B`), shown as unfinished |
+//! | every other event | thread-scoped instant event (`i`) |
+//!
+//! Each run gets its own track (a `tid`, named after the run id), so runs
+//! interleaved in one Timeline show up side by side. Events recorded without
+//! an [`EventTrace`](super::EventTrace) share track 0.
+//!
+//! ```rust
+//! # use ranvier_core::timeline::Timeline;
+//! let trace = Timeline::new().to_chrome_trace();
+//! assert!(trace["traceEvents"].is_array());
+//! ```
+
+use super::{Timeline, TimelineEvent, Timestamp};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+const PID: u32 = 1;
+
+impl Timeline {
+    /// Render the Timeline as a Chrome trace (JSON object format).
+    pub fn to_chrome_trace(&self) -> Value {
+        let mut tracks: Vec<Option<&str>> = Vec::new();
+        let mut open: HashMap<(usize, &str), Vec<(Timestamp, &str)>> = HashMap::new();
+        let mut trace_events = Vec::new();
+
+        for event in &self.events {
+            let run_id = event.trace().map(|trace| trace.run_id.as_str());
+            let tid = match tracks.iter().position(|track| *track == run_id) {
+                Some(tid) => tid,
+                None => {
+                    tracks.push(run_id);
+                    tracks.len() - 1
+                }
+            };
+            match event {
+                TimelineEvent::NodeEnter {
+                    node_id,
+                    node_label,
+                    timestamp,
+                    ..
+                } => open
+                    .entry((tid, node_id.as_str()))
+                    .or_default()
+                    .push((*timestamp, node_label.as_str())),
+                TimelineEvent::NodeExit {
+                    node_id,
+                    outcome_type,
+                    timestamp,
+                    ..
+                } => {
+                    let Some((entered, label)) =
+                        open.get_mut(&(tid, node_id.as_str())).and_then(Vec::pop)
+                    else {
+                        continue;
+                    };
+                    trace_events.push(json!({
+                        "name": label,
+                        "cat": "node",
+                        "ph": "X",
+                        "ts": micros(entered),
+                        "dur": timestamp.duration_since(entered).as_nanos() as f64 / 1_000.0,
+                        "pid": PID,
+                        "tid": tid,
+                        "args": { "node_id": node_id, "outcome": outcome_type },
+                    }));
+                }
+                other => {
+                    let (name, args) = instant(other);
+                    trace_events.push(json!({
+                        "name": name,
+                        "cat": "event",
+                        "ph": "i",
+                        "s": "t",
+                        "ts": micros(other.timestamp()),
+                        "pid": PID,
+                        "tid": tid,
+                        "args": args,
+                    }));
+                }
+            }
+        }
+
+        let mut unfinished: Vec<_> = open
+            .into_iter()
+            .flat_map(|((tid, node_id), entries)| {
+                entries
+                    .into_iter()
+                    .map(move |(entered, label)| (entered, tid, node_id, label))
+            })
+            .collect();
+        unfinished.sort();
+        for (entered, tid, node_id, label) in unfinished {
+            trace_events.push(json!({
+                "name": label,
+                "cat": "node",
+                "ph": "B",
+                "ts": micros(entered),
+                "pid": PID,
+                "tid": tid,
+                "args": { "node_id": node_id },
+            }));
+        }
+
+        trace_events.push(json!({
+            "name": "process_name",
+            "ph": "M",
+            "pid": PID,
+            "args": { "name": "ranvier" },
+        }));
+        for (tid, run_id) in tracks.iter().enumerate() {
+            trace_events.push(json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": PID,
+                "tid": tid,
+                "args": { "name": run_id.unwrap_or("timeline") },
+            }));
+        }
+
+        json!({ "traceEvents": trace_events, "displayTimeUnit": "ms" })
+    }
+}
+
+fn micros(timestamp: Timestamp) -> f64 {
+    timestamp.as_nanos() as f64 / 1_000.0
+}
+
+/// Name and `args` of an event shown as an instant.
+fn instant(event: &TimelineEvent) -> (String, Value) {
+    match event {
+        TimelineEvent::NodeEnter { node_id, .. } | TimelineEvent::NodeExit { node_id, .. } => {
+            ("Node".to_string(), json!({ "node_id": node_id }))
+        }
+        TimelineEvent::NodePaused { node_id, .. } => {
+            ("Paused".to_string(), json!({ "node_id": node_id }))
+        }
+        TimelineEvent::Branchtaken { branch_id, .. } => (
+            format!("Branch: {branch_id}"),
+            json!({ "branch_id": branch_id }),
+        ),
+        TimelineEvent::NodeRetry {
+            node_id,
+            attempt,
+            max_attempts,
+            backoff_ms,
+            ..
+        } => (
+            "Retry".to_string(),
+            json!({
+                "node_id": node_id,
+                "attempt": attempt,
+                "max_attempts": max_attempts,
+                "backoff_ms": backoff_ms,
+            }),
+        ),
+        TimelineEvent::DlqExhausted {
+            node_id,
+            total_attempts,
+            ..
+        } => (
+            "DLQ".to_string(),
+            json!({ "node_id": node_id, "total_attempts": total_attempts }),
+        ),
+        TimelineEvent::NodeTimeout {
+            node_id,
+            timeout_ms,
+            ..
+        } => (
+            "Timeout".to_string(),
+            json!({ "node_id": node_id, "timeout_ms": timeout_ms }),
+        ),
+        TimelineEvent::PayloadCaptured {
+            node_id, direction, ..
+        } => (
+            format!("Capture: {direction:?}"),
+            json!({ "node_id": node_id }),
+        ),
+        TimelineEvent::FaultRecorded { node_id, cause, .. } => (
+            "Fault".to_string(),
+            json!({ "node_id": node_id, "error": cause.error }),
+        ),
+        TimelineEvent::ExecutionCancelled {
+            node_id, reason, ..
+        } => (
+            "Cancelled".to_string(),
+            json!({ "node_id": node_id, "reason": format!("{reason:?}") }),
+        ),
+        TimelineEvent::CacheLookup {
+            node_id, key, hit, ..
+        } => (
+            if *hit { "Cache hit" } else { "Cache miss" }.to_string(),
+            json!({ "node_id": node_id, "key": key }),
+        ),
+        TimelineEvent::BusCaptured { node_id, .. } => {
+            ("Bus captured".to_string(), json!({ "node_id": node_id }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{EventTrace, Timeline, TimelineEvent, Timestamp};
+
+    fn traced(run_id: &str) -> Option<EventTrace> {
+        Some(EventTrace {
+            trace_id: "t".into(),
+            run_id: run_id.into(),
+            parent_span_id: "s".into(),
+        })
+    }
+
+    #[test]
+    fn nodes_become_complete_events_on_one_track_per_run() {
+        let mut timeline = Timeline::new();
+        for (run, start) in [("run-a", 1_000), ("run-b", 1_500)] {
+            timeline.push(TimelineEvent::NodeEnter {
+                node_id: "n1".into(),
+                node_label: "Charge".into(),
+                timestamp: Timestamp::from_micros(start),
+                trace: traced(run),
+            });
+        }
+        timeline.push(TimelineEvent::NodeExit {
+            node_id: "n1".into(),
+            outcome_type: "Next".into(),
+            duration_ms: 2,
+            timestamp: Timestamp::from_micros(3_000),
+            trace: traced("run-a"),
+        });
+        timeline.push(TimelineEvent::Branchtaken {
+            branch_id: "review".into(),
+            timestamp: Timestamp::from_micros(3_000),
+            trace: None,
+        });
+
+        let trace = timeline.to_chrome_trace();
+        let events = trace["traceEvents"].as_array().unwrap();
+        let phase = |ph: &'static str| events.iter().filter(move |event| event["ph"] == ph);
+
+        let complete: Vec<_> = phase("X").collect();
+        assert_eq!(complete.len(), 1);
+        assert_eq!(complete[0]["name"], "Charge");
+        assert_eq!(complete[0]["ts"], 1_000.0);
+        assert_eq!(complete[0]["dur"], 2_000.0);
+        assert_eq!(complete[0]["tid"], 0);
+
+        let unfinished: Vec<_> = phase("B").collect();
+        assert_eq!(unfinished.len(), 1);
+        assert_eq!(unfinished[0]["tid"], 1);
+
+        assert_eq!(phase("i").next().unwrap()["name"], "Branch: review");
+        let tracks: Vec<_> = phase("M")
+            .filter(|event| event["name"] == "thread_name")
+            .map(|event| event["args"]["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(tracks, ["run-a", "run-b", "timeline"]);
+    }
+}
@@ -0,0 +1,296 @@
+//! Comparison of two timelines of the same circuit.
+//!
+//! [`Timeline::diff`] lines a run up against a baseline, typically a bad run
+//! against a known-good one:
+//!
+//! - node events are aligned by node id, giving each node's latency and
+//!   outcome in both runs;
+//! - branch decisions are compared in the order they were taken;
+//! - faults recorded by nodes that did not fault in the baseline are listed
+//!   as new.
+//!
+//! ```rust
+//! # use ranvier_core::timeline::Timeline;
+//! let (good, bad) = (Timeline::new(), Timeline::new());
+//! let diff = good.diff(&bad);
+//! assert!(diff.is_empty());
+//! ```
+
+use super::{Timeline, TimelineEvent};
+use crate::fault::FaultCause;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Differences between a baseline timeline and another run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TimelineDiff {
+    /// Every node seen in either run, baseline order first.
+    pub nodes: Vec<NodeDiff>,
+    /// Branch positions where the runs decided differently.
+    pub branches: Vec<BranchDivergence>,
+    /// Faults of the other run on nodes that did not fault in the baseline.
+    pub new_faults: Vec<FaultCause>,
+}
+
+/// One node in both runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NodeDiff {
+    pub node_id: String,
+    pub node_label: String,
+    /// `None` when the node did not run in the baseline.
+    pub baseline: Option<NodeRun>,
+    /// `None` when the node did not run in the other timeline.
+    pub other: Option<NodeRun>,
+}
+
+/// What a node did in one run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NodeRun {
+    /// Number of times the node was entered.
+    pub visits: usize,
+    /// Total duration of the visits that exited.
+    pub duration_ms: u64,
+    /// Outcome of the last exit; `None` if the node never exited.
+    pub outcome: Option<String>,
+}
+
+/// A branch decision that differs between the runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BranchDivergence {
+    /// Position among the branch decisions of each run.
+    pub index: usize,
+    /// `None` when the baseline took fewer branches.
+    pub baseline: Option<String>,
+    /// `None` when the other run took fewer branches.
+    pub other: Option<String>,
+}
+
+impl NodeDiff {
+    /// Other run's duration minus the baseline's, when the node ran in both.
+    pub fn latency_delta_ms(&self) -> Option<i64> {
+        let (baseline, other) = (self.baseline.as_ref()?, self.other.as_ref()?);
+        Some(other.duration_ms as i64 - baseline.duration_ms as i64)
+    }
+
+    /// Whether the node ran in only one run or ended with another outcome.
+    pub fn diverged(&self) -> bool {
+        match (&self.baseline, &self.other) {
+            (Some(baseline), Some(other)) => baseline.outcome != other.outcome,
+            _ => true,
+        }
+    }
+}
+
+impl TimelineDiff {
+    /// True when both runs took the same nodes, outcomes and branches and the
+    /// other run has no new faults. Latencies are not compared.
+    pub fn is_empty(&self) -> bool {
+        self.branches.is_empty()
+            && self.new_faults.is_empty()
+            && !self.nodes.iter().any(NodeDiff::diverged)
+    }
+
+    /// Nodes whose latency changed by at least `threshold_ms`, either way.
+    pub fn latency_changes(&self, threshold_ms: u64) -> impl Iterator<Item = &NodeDiff> {
+        self.nodes.iter().filter(move |node| {
+            node.latency_delta_ms()
+                .is_some_and(|delta| delta.unsigned_abs() >= threshold_ms)
+        })
+    }
+}
+
+impl Timeline {
+    /// Compare `other` against this timeline as the baseline.
+    pub fn diff(&self, other: &Timeline) -> TimelineDiff {
+        let mut nodes: Vec<NodeDiff> = Vec::new();
+        let mut index_of: HashMap<&str, usize> = HashMap::new();
+        for (timeline, is_baseline) in [(self, true), (other, false)] {
+            for event in &timeline.events {
+                let (node_id, node_label) = match event {
+                    TimelineEvent::NodeEnter {
+                        node_id,
+                        node_label,
+                        ..
+                    } => (node_id, node_label.as_str()),
+                    TimelineEvent::NodeExit { node_id, .. } => (node_id, ""),
+                    _ => continue,
+                };
+                let index = *index_of.entry(node_id.as_str()).or_insert_with(|| {
+                    nodes.push(NodeDiff {
+                        node_id: node_id.clone(),
+                        node_label: node_label.to_string(),
+                        baseline: None,
+                        other: None,
+                    });
+                    nodes.len() - 1
+                });
+                let node = &mut nodes[index];
+                if node.node_label.is_empty() {
+                    node.node_label = node_label.to_string();
+                }
+                let run = if is_baseline {
+                    &mut node.baseline
+                } else {
+                    &mut node.other
+                };
+                let run = run.get_or_insert(NodeRun {
+                    visits: 0,
+                    duration_ms: 0,
+                    outcome: None,
+                });
+                match event {
+                    TimelineEvent::NodeEnter { .. } => run.visits += 1,
+                    TimelineEvent::NodeExit {
+                        outcome_type,
+                        duration_ms,
+                        ..
+                    } => {
+                        run.duration_ms += duration_ms;
+                        run.outcome = Some(outcome_type.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let baseline_branches = branches(self);
+        let other_branches = branches(other);
+        let branches = (0..baseline_branches.len().max(other_branches.len()))
+            .filter_map(|index| {
+                let baseline = baseline_branches.get(index).map(|id| id.to_string());
+                let other = other_branches.get(index).map(|id| id.to_string());
+                (baseline != other).then_some(BranchDivergence {
+                    index,
+                    baseline,
+                    other,
+                })
+            })
+            .collect();
+
+        let baseline_faults: Vec<&str> = faulted_nodes(self).collect();
+        let new_faults = other
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                TimelineEvent::FaultRecorded { node_id, cause, .. }
+                    if !baseline_faults.contains(&node_id.as_str()) =>
+                {
+                    Some(cause.clone())
+                }
+                _ => None,
+            })
+            .collect();
+
+        TimelineDiff {
+            nodes,
+            branches,
+            new_faults,
+        }
+    }
+}
+
+fn branches(timeline: &Timeline) -> Vec<&str> {
+    timeline
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            TimelineEvent::Branchtaken { branch_id, .. } => Some(branch_id.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn faulted_nodes(timeline: &Timeline) -> impl Iterator<Item = &str> {
+    timeline.events.iter().filter_map(|event| match event {
+        TimelineEvent::FaultRecorded { node_id, .. } => Some(node_id.as_str()),
+        TimelineEvent::NodeExit {
+            node_id,
+            outcome_type,
+            ..
+        } if outcome_type == "Fault" => Some(node_id.as_str()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Timeline, TimelineEvent, Timestamp};
+    use crate::fault::FaultCause;
+
+    fn run(nodes: &[(&str, u64, &str)], branch: &str) -> Timeline {
+        let mut timeline = Timeline::new();
+        for (node_id, duration_ms, outcome) in nodes {
+            timeline.push(TimelineEvent::NodeEnter {
+                node_id: node_id.to_string(),
+                node_label: node_id.to_uppercase(),
+                timestamp: Timestamp::from_millis(0),
+                trace: None,
+            });
+            if *outcome == "Fault" {
+                timeline.push(TimelineEvent::FaultRecorded {
+                    node_id: node_id.to_string(),
+                    cause: FaultCause::new(*node_id, node_id.to_uppercase(), "declined"),
+                    timestamp: Timestamp::from_millis(*duration_ms),
+                    trace: None,
+                });
+            }
+            timeline.push(TimelineEvent::NodeExit {
+                node_id: node_id.to_string(),
+                outcome_type: outcome.to_string(),
+                duration_ms: *duration_ms,
+                timestamp: Timestamp::from_millis(*duration_ms),
+                trace: None,
+            });
+        }
+        timeline.push(TimelineEvent::Branchtaken {
+            branch_id: branch.into(),
+            timestamp: Timestamp::from_millis(0),
+            trace: None,
+        });
+        timeline
+    }
+
+    #[test]
+    fn diff_reports_latency_branches_and_new_faults() {
+        let good = run(&[("load", 10, "Next"), ("charge", 20, "Next")], "ship");
+        let same = run(&[("load", 40, "Next"), ("charge", 20, "Next")], "ship");
+        let bad = run(
+            &[
+                ("load", 40, "Next"),
+                ("charge", 5, "Fault"),
+                ("refund", 3, "Next"),
+            ],
+            "cancel",
+        );
+
+        let diff = good.diff(&same);
+        assert!(diff.is_empty());
+        let slower: Vec<_> = diff.latency_changes(30).collect();
+        assert_eq!(slower.len(), 1);
+        assert_eq!(slower[0].node_id, "load");
+        assert_eq!(slower[0].latency_delta_ms(), Some(30));
+
+        let diff = good.diff(&bad);
+        assert!(!diff.is_empty());
+        let diverged: Vec<_> = diff
+            .nodes
+            .iter()
+            .filter(|node| node.diverged())
+            .map(|node| node.node_id.as_str())
+            .collect();
+        assert_eq!(diverged, ["charge", "refund"]);
+        assert_eq!(diff.nodes[2].node_label, "REFUND");
+        assert!(diff.nodes[2].baseline.is_none());
+        assert_eq!(diff.branches.len(), 1);
+        assert_eq!(diff.branches[0].baseline.as_deref(), Some("ship"));
+        assert_eq!(diff.branches[0].other.as_deref(), Some("cancel"));
+        assert_eq!(diff.new_faults.len(), 1);
+        assert_eq!(diff.new_faults[0].node_id, "charge");
+
+        assert!(bad.diff(&good).new_faults.is_empty());
+    }
+}
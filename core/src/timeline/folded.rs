@@ -0,0 +1,174 @@
+//! Folded stack export of a [`Timeline`] for flamegraphs.
+//!
+//! Each line is a `;`-separated stack and a weight, the input format of
+//! inferno (`inferno-flamegraph`) and Brendan Gregg's `flamegraph.pl`:
+//!
+//! ```text
+//! Checkout;Auth;VerifyToken 1200
+//! Checkout;ChargeCard 48000
+//! ```
+//!
+//! Stacks follow the [`Schematic`]: the circuit, the `Subgraph` nodes
+//! enclosing the node, then the node label. Weights are the summed durations
+//! of the node's visits in microseconds, measured between `NodeEnter` and
+//! `NodeExit`. Unfinished visits are left out.
+//!
+//! ```rust
+//! # use ranvier_core::schematic::Schematic;
+//! # use ranvier_core::timeline::Timeline;
+//! let folded = Timeline::new().to_folded_stacks(&Schematic::new("checkout"));
+//! assert!(folded.is_empty());
+//! ```
+
+use super::{Timeline, TimelineEvent, Timestamp};
+use crate::schematic::{NodeKind, Schematic};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+impl Timeline {
+    /// Render the Timeline as folded stacks, nested by `schematic`.
+    ///
+    /// Nodes missing from the schematic are placed directly under the circuit.
+    pub fn to_folded_stacks(&self, schematic: &Schematic) -> String {
+        let circuit = frame(&schematic.name);
+        let mut paths = HashMap::new();
+        collect_paths(schematic, &circuit, &mut paths);
+
+        let mut open: HashMap<(Option<&str>, &str), Vec<(Timestamp, &str)>> = HashMap::new();
+        let mut weights: BTreeMap<String, u64> = BTreeMap::new();
+        for event in &self.events {
+            let run_id = event.trace().map(|trace| trace.run_id.as_str());
+            match event {
+                TimelineEvent::NodeEnter {
+                    node_id,
+                    node_label,
+                    timestamp,
+                    ..
+                } => open
+                    .entry((run_id, node_id.as_str()))
+                    .or_default()
+                    .push((*timestamp, node_label.as_str())),
+                TimelineEvent::NodeExit {
+                    node_id, timestamp, ..
+                } => {
+                    let Some((entered, label)) =
+                        open.get_mut(&(run_id, node_id.as_str())).and_then(Vec::pop)
+                    else {
+                        continue;
+                    };
+                    let micros = timestamp.duration_since(entered).as_micros() as u64;
+                    let stack = paths.get(node_id.as_str()).map_or_else(
+                        || format!("{circuit};{}", frame(label)),
+                        |path| path.clone(),
+                    );
+                    *weights.entry(stack).or_default() += micros;
+                }
+                _ => {}
+            }
+        }
+
+        let mut out = String::new();
+        for (stack, micros) in weights.into_iter().filter(|(_, micros)| *micros > 0) {
+            let _ = writeln!(out, "{stack} {micros}");
+        }
+        out
+    }
+}
+
+/// Map every non-subgraph node id to its stack, recursing into subgraphs.
+fn collect_paths(schematic: &Schematic, prefix: &str, paths: &mut HashMap<String, String>) {
+    for node in &schematic.nodes {
+        let path = format!("{prefix};{}", frame(&node.label));
+        match &node.kind {
+            NodeKind::Subgraph(inner) => collect_paths(inner, &path, paths),
+            _ => {
+                paths.entry(node.id.clone()).or_insert(path);
+            }
+        }
+    }
+}
+
+/// A frame name without the separators of the folded format.
+fn frame(name: &str) -> String {
+    name.replace(';', ":").replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{EventTrace, Timeline, TimelineEvent, Timestamp};
+    use crate::metadata::StepMetadata;
+    use crate::schematic::{Node, NodeKind, Schematic};
+
+    fn visit(timeline: &mut Timeline, run_id: &str, node_id: &str, from_ms: u64, to_ms: u64) {
+        let trace = Some(EventTrace {
+            trace_id: "t".into(),
+            run_id: run_id.into(),
+            parent_span_id: "s".into(),
+        });
+        timeline.push(TimelineEvent::NodeEnter {
+            node_id: node_id.into(),
+            node_label: node_id.into(),
+            timestamp: Timestamp::from_millis(from_ms),
+            trace: trace.clone(),
+        });
+        timeline.push(TimelineEvent::NodeExit {
+            node_id: node_id.into(),
+            outcome_type: "Next".into(),
+            duration_ms: to_ms - from_ms,
+            timestamp: Timestamp::from_millis(to_ms),
+            trace,
+        });
+    }
+
+    fn node(id: &str, label: &str, kind: NodeKind) -> Node {
+        Node {
+            id: id.into(),
+            kind,
+            label: label.into(),
+            description: None,
+            input_type: "i32".into(),
+            output_type: "i32".into(),
+            resource_type: "()".into(),
+            metadata: StepMetadata::default(),
+            bus_capability: None,
+            source_location: None,
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        }
+    }
+
+    #[test]
+    fn folded_stacks_nest_subgraphs_and_sum_visits() {
+        let mut auth = Schematic::new("Auth");
+        auth.nodes
+            .push(node("verify", "Verify;Token", NodeKind::Atom));
+        let mut checkout = Schematic::new("Checkout");
+        checkout
+            .nodes
+            .push(node("auth", "Auth", NodeKind::Subgraph(Box::new(auth))));
+        checkout
+            .nodes
+            .push(node("charge", "Charge", NodeKind::Atom));
+
+        let mut timeline = Timeline::new();
+        visit(&mut timeline, "r1", "verify", 0, 2);
+        visit(&mut timeline, "r1", "charge", 2, 10);
+        visit(&mut timeline, "r2", "charge", 0, 5);
+        visit(&mut timeline, "r2", "Audit", 5, 6);
+        timeline.push(TimelineEvent::NodeEnter {
+            node_id: "charge".into(),
+            node_label: "Charge".into(),
+            timestamp: Timestamp::from_millis(20),
+            trace: None,
+        });
+
+        assert_eq!(
+            timeline.to_folded_stacks(&checkout),
+            "Checkout;Audit 1000\nCheckout;Auth;Verify:Token 2000\nCheckout;Charge 13000\n"
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! OTLP span export of a [`Timeline`].
+//!
+//! Environments that run without live tracing can still backfill traces from
+//! stored timelines: [`Timeline::to_otlp`] renders an OTLP/JSON
+//! `ExportTraceServiceRequest` that any OTLP/HTTP collector accepts on
+//! `/v1/traces`.
+//!
+//! Every run becomes one trace, keyed by its [`EventTrace`]:
+//!
+//! - a root `Circuit` span whose id is the run's `parent_span_id`, i.e. the
+//!   span the executor announced for the execution, spanning the run's first
+//!   to last event;
+//! - a child span per node, from `NodeEnter` to `NodeExit`, with status
+//!   `ERROR` when the node faulted;
+//! - every other event as a span event on the node that was running, or on
+//!   the root span.
+//!
+//! Events recorded without an `EventTrace` are grouped into one run with a
+//! fresh trace id.
+//!
+//! ```rust
+//! # use ranvier_core::timeline::Timeline;
+//! let request = Timeline::new().to_otlp("checkout-service");
+//! assert!(request["resourceSpans"].is_array());
+//! ```
+
+use super::{EventTrace, Timeline, TimelineEvent, Timestamp};
+use crate::telemetry::TraceContext;
+use serde_json::{Value, json};
+
+/// `SPAN_KIND_INTERNAL`
+const SPAN_KIND_INTERNAL: u8 = 1;
+/// `STATUS_CODE_ERROR`
+const STATUS_CODE_ERROR: u8 = 2;
+
+struct Run<'a> {
+    trace: Option<&'a EventTrace>,
+    context: TraceContext,
+    start: Timestamp,
+    end: Timestamp,
+    events: Vec<Value>,
+    /// Nodes entered and not yet exited.
+    open: Vec<OpenNode<'a>>,
+}
+
+struct OpenNode<'a> {
+    node_id: &'a str,
+    label: &'a str,
+    start: Timestamp,
+    span_id: String,
+    events: Vec<Value>,
+}
+
+impl Timeline {
+    /// Render the Timeline as an OTLP/JSON `ExportTraceServiceRequest`, with
+    /// `service_name` as the `service.name` resource attribute.
+    pub fn to_otlp(&self, service_name: &str) -> Value {
+        let mut runs: Vec<Run<'_>> = Vec::new();
+        let mut spans = Vec::new();
+
+        for event in &self.events {
+            let trace = event.trace();
+            let timestamp = event.timestamp();
+            let index = match runs.iter().position(|run| same_run(run.trace, trace)) {
+                Some(index) => index,
+                None => {
+                    let context = match trace {
+                        Some(trace) => TraceContext {
+                            trace_id: trace.trace_id.clone(),
+                            span_id: trace.parent_span_id.clone(),
+                        },
+                        None => TraceContext::new(),
+                    };
+                    runs.push(Run {
+                        trace,
+                        context,
+                        start: timestamp,
+                        end: timestamp,
+                        events: Vec::new(),
+                        open: Vec::new(),
+                    });
+                    runs.len() - 1
+                }
+            };
+            let run = &mut runs[index];
+            run.start = run.start.min(timestamp);
+            run.end = run.end.max(timestamp);
+
+            match event {
+                TimelineEvent::NodeEnter {
+                    node_id,
+                    node_label,
+                    timestamp,
+                    ..
+                } => run.open.push(OpenNode {
+                    node_id,
+                    label: node_label,
+                    start: *timestamp,
+                    span_id: run.context.child().span_id,
+                    events: Vec::new(),
+                }),
+                TimelineEvent::NodeExit {
+                    node_id,
+                    outcome_type,
+                    timestamp,
+                    ..
+                } => {
+                    let Some(position) = run.open.iter().rposition(|node| node.node_id == node_id)
+                    else {
+                        continue;
+                    };
+                    let node = run.open.remove(position);
+                    let mut span = span(
+                        &run.context.trace_id,
+                        &node.span_id,
+                        Some(&run.context.span_id),
+                        node.label,
+                        node.start,
+                        *timestamp,
+                        vec![
+                            attribute("ranvier.node_id", node_id),
+                            attribute("ranvier.outcome", outcome_type),
+                        ],
+                        node.events,
+                    );
+                    if outcome_type == "Fault" {
+                        span["status"] = json!({ "code": STATUS_CODE_ERROR });
+                    }
+                    spans.push(span);
+                }
+                other => {
+                    let span_event = span_event(other);
+                    let node_id = event_node_id(other);
+                    match run
+                        .open
+                        .iter_mut()
+                        .rev()
+                        .find(|node| Some(node.node_id) == node_id)
+                    {
+                        Some(node) => node.events.push(span_event),
+                        None => run.events.push(span_event),
+                    }
+                }
+            }
+        }
+
+        for run in runs {
+            let mut attributes = Vec::new();
+            if let Some(trace) = run.trace {
+                attributes.push(attribute("ranvier.run_id", &trace.run_id));
+            }
+            // Nodes that never exited end with the run.
+            for node in run.open {
+                let mut span = span(
+                    &run.context.trace_id,
+                    &node.span_id,
+                    Some(&run.context.span_id),
+                    node.label,
+                    node.start,
+                    run.end,
+                    vec![
+                        attribute("ranvier.node_id", node.node_id),
+                        attribute("ranvier.outcome", "Unfinished"),
+                    ],
+                    node.events,
+                );
+                span["status"] =
+                    json!({ "code": STATUS_CODE_ERROR, "message": "node did not exit" });
+                spans.push(span);
+            }
+            spans.push(span(
+                &run.context.trace_id,
+                &run.context.span_id,
+                None,
+                "Circuit",
+                run.start,
+                run.end,
+                attributes,
+                run.events,
+            ));
+        }
+
+        json!({
+            "resourceSpans": [{
+                "resource": { "attributes": [attribute("service.name", service_name)] },
+                "scopeSpans": [{
+                    "scope": { "name": "ranvier", "version": env!("CARGO_PKG_VERSION") },
+                    "spans": spans,
+                }],
+            }],
+        })
+    }
+}
+
+fn same_run(a: Option<&EventTrace>, b: Option<&EventTrace>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.run_id == b.run_id && a.parent_span_id == b.parent_span_id,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn span(
+    trace_id: &str,
+    span_id: &str,
+    parent_span_id: Option<&str>,
+    name: &str,
+    start: Timestamp,
+    end: Timestamp,
+    attributes: Vec<Value>,
+    events: Vec<Value>,
+) -> Value {
+    let mut span = json!({
+        "traceId": trace_id,
+        "spanId": span_id,
+        "name": name,
+        "kind": SPAN_KIND_INTERNAL,
+        "startTimeUnixNano": start.as_nanos().to_string(),
+        "endTimeUnixNano": end.max(start).as_nanos().to_string(),
+        "attributes": attributes,
+        "events": events,
+    });
+    if let Some(parent) = parent_span_id {
+        span["parentSpanId"] = json!(parent);
+    }
+    span
+}
+
+fn attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+fn event_node_id(event: &TimelineEvent) -> Option<&str> {
+    match event {
+        TimelineEvent::NodeEnter { node_id, .. }
+        | TimelineEvent::NodeExit { node_id, .. }
+        | TimelineEvent::NodePaused { node_id, .. }
+        | TimelineEvent::NodeRetry { node_id, .. }
+        | TimelineEvent::DlqExhausted { node_id, .. }
+        | TimelineEvent::NodeTimeout { node_id, .. }
+        | TimelineEvent::PayloadCaptured { node_id, .. }
+        | TimelineEvent::FaultRecorded { node_id, .. }
+        | TimelineEvent::CacheLookup { node_id, .. }
+        | TimelineEvent::BusCaptured { node_id, .. } => Some(node_id),
+        TimelineEvent::ExecutionCancelled { node_id, .. } => node_id.as_deref(),
+        TimelineEvent::Branchtaken { .. } => None,
+    }
+}
+
+fn span_event(event: &TimelineEvent) -> Value {
+    let (name, attributes) = match event {
+        TimelineEvent::NodeEnter { .. } | TimelineEvent::NodeExit { .. } => ("node", vec![]),
+        TimelineEvent::NodePaused { .. } => ("paused", vec![]),
+        TimelineEvent::Branchtaken { branch_id, .. } => {
+            ("branch", vec![attribute("ranvier.branch_id", branch_id)])
+        }
+        TimelineEvent::NodeRetry {
+            attempt,
+            backoff_ms,
+            ..
+        } => (
+            "retry",
+            vec![
+                attribute("ranvier.attempt", &attempt.to_string()),
+                attribute("ranvier.backoff_ms", &backoff_ms.to_string()),
+            ],
+        ),
+        TimelineEvent::DlqExhausted { total_attempts, .. } => (
+            "dlq",
+            vec![attribute(
+                "ranvier.total_attempts",
+                &total_attempts.to_string(),
+            )],
+        ),
+        TimelineEvent::NodeTimeout { timeout_ms, .. } => (
+            "timeout",
+            vec![attribute("ranvier.timeout_ms", &timeout_ms.to_string())],
+        ),
+        TimelineEvent::PayloadCaptured { direction, .. } => (
+            "payload_captured",
+            vec![attribute("ranvier.direction", &format!("{direction:?}"))],
+        ),
+        // Follows the OpenTelemetry exception semantic conventions.
+        TimelineEvent::FaultRecorded { cause, .. } => (
+            "exception",
+            vec![attribute("exception.message", &cause.error)],
+        ),
+        TimelineEvent::ExecutionCancelled { reason, .. } => (
+            "cancelled",
+            vec![attribute("ranvier.reason", &format!("{reason:?}"))],
+        ),
+        TimelineEvent::CacheLookup { key, hit, .. } => (
+            "cache_lookup",
+            vec![
+                attribute("ranvier.cache_key", key),
+                attribute("ranvier.cache_hit", &hit.to_string()),
+            ],
+        ),
+        TimelineEvent::BusCaptured { .. } => ("bus_captured", vec![]),
+    };
+    json!({
+        "name": name,
+        "timeUnixNano": event.timestamp().as_nanos().to_string(),
+        "attributes": attributes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{EventTrace, Timeline, TimelineEvent, Timestamp};
+    use crate::fault::FaultCause;
+
+    #[test]
+    fn runs_become_traces_with_node_child_spans() {
+        let trace = Some(EventTrace {
+            trace_id: "4bf92f3577b34da6a3ce929d0e0e4736".into(),
+            run_id: "run-1".into(),
+            parent_span_id: "00f067aa0ba902b7".into(),
+        });
+        let mut timeline = Timeline::new();
+        timeline.push(TimelineEvent::NodeEnter {
+            node_id: "n1".into(),
+            node_label: "Charge".into(),
+            timestamp: Timestamp::from_millis(10),
+            trace: trace.clone(),
+        });
+        timeline.push(TimelineEvent::FaultRecorded {
+            node_id: "n1".into(),
+            cause: FaultCause::new("n1", "Charge", "declined"),
+            timestamp: Timestamp::from_millis(15),
+            trace: trace.clone(),
+        });
+        timeline.push(TimelineEvent::NodeExit {
+            node_id: "n1".into(),
+            outcome_type: "Fault".into(),
+            duration_ms: 10,
+            timestamp: Timestamp::from_millis(20),
+            trace,
+        });
+
+        let request = timeline.to_otlp("checkout");
+        let resource = &request["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "checkout"
+        );
+        let spans = resource["scopeSpans"][0]["spans"].as_array().unwrap();
+        assert_eq!(spans.len(), 2);
+
+        let (node, root) = (&spans[0], &spans[1]);
+        assert_eq!(root["name"], "Circuit");
+        assert_eq!(root["spanId"], "00f067aa0ba902b7");
+        assert!(root.get("parentSpanId").is_none());
+        assert_eq!(node["name"], "Charge");
+        assert_eq!(node["traceId"], "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(node["parentSpanId"], "00f067aa0ba902b7");
+        assert_eq!(node["spanId"].as_str().unwrap().len(), 16);
+        assert_eq!(node["startTimeUnixNano"], "10000000");
+        assert_eq!(node["endTimeUnixNano"], "20000000");
+        assert_eq!(node["status"]["code"], 2);
+        assert_eq!(node["events"][0]["name"], "exception");
+    }
+}
@@ -0,0 +1,270 @@
+//! Aggregation of many timelines into per-node statistics.
+//!
+//! [`TimelineStats`] folds the timelines of one circuit into latency
+//! percentiles, error rates and branch frequencies per node, the numbers the
+//! public and internal projections report:
+//!
+//! ```rust
+//! # use ranvier_core::timeline::{Timeline, stats::TimelineStats};
+//! let runs = vec![Timeline::new(), Timeline::new()];
+//! let stats = TimelineStats::from_timelines(&runs);
+//! assert_eq!(stats.runs, 2);
+//! ```
+//!
+//! Latencies are the `duration_ms` of `NodeExit` events; a visit counts as an
+//! error when the node exited with `Fault`. Stats of separate batches can be
+//! combined with [`TimelineStats::merge`].
+
+use super::{Timeline, TimelineEvent, Timestamp};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Per-node statistics over a set of timelines.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TimelineStats {
+    /// Number of timelines folded in.
+    pub runs: u64,
+    /// Keyed by node id.
+    pub nodes: BTreeMap<String, NodeStats>,
+    /// How many times each branch was taken, keyed by branch id.
+    pub branches: BTreeMap<String, u64>,
+    /// Earliest event seen.
+    pub first_event: Option<Timestamp>,
+    /// Latest event seen.
+    pub last_event: Option<Timestamp>,
+}
+
+/// Statistics of one node.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NodeStats {
+    pub node_label: String,
+    /// Number of exits.
+    pub visits: u64,
+    /// Exits with a `Fault` outcome.
+    pub error_count: u64,
+    /// Exit count per outcome type.
+    pub outcomes: BTreeMap<String, u64>,
+    /// Durations of every exit in milliseconds, sorted ascending.
+    pub latencies_ms: Vec<u64>,
+}
+
+impl NodeStats {
+    pub fn error_rate(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.error_count as f64 / self.visits as f64
+        }
+    }
+
+    /// Latency at quantile `p` (`0.0..=1.0`), interpolated between samples.
+    pub fn percentile(&self, p: f64) -> f64 {
+        let sorted = &self.latencies_ms;
+        match sorted.len() {
+            0 => 0.0,
+            1 => sorted[0] as f64,
+            len => {
+                let rank = p.clamp(0.0, 1.0) * (len - 1) as f64;
+                let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+                let frac = rank - lower as f64;
+                sorted[lower] as f64 * (1.0 - frac) + sorted[upper] as f64 * frac
+            }
+        }
+    }
+
+    pub fn p50(&self) -> f64 {
+        self.percentile(0.50)
+    }
+
+    pub fn p95(&self) -> f64 {
+        self.percentile(0.95)
+    }
+
+    pub fn p99(&self) -> f64 {
+        self.percentile(0.99)
+    }
+
+    pub fn avg_ms(&self) -> f64 {
+        if self.latencies_ms.is_empty() {
+            0.0
+        } else {
+            self.latencies_ms.iter().sum::<u64>() as f64 / self.latencies_ms.len() as f64
+        }
+    }
+
+    fn record(&mut self, duration_ms: u64) {
+        let index = self.latencies_ms.partition_point(|ms| *ms <= duration_ms);
+        self.latencies_ms.insert(index, duration_ms);
+    }
+}
+
+impl TimelineStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_timelines<'a>(timelines: impl IntoIterator<Item = &'a Timeline>) -> Self {
+        let mut stats = Self::new();
+        for timeline in timelines {
+            stats.fold(timeline);
+        }
+        stats
+    }
+
+    /// Add one timeline.
+    pub fn fold(&mut self, timeline: &Timeline) {
+        self.runs += 1;
+        let mut labels: BTreeMap<&str, &str> = BTreeMap::new();
+        for event in &timeline.events {
+            let timestamp = event.timestamp();
+            self.first_event = Some(self.first_event.map_or(timestamp, |t| t.min(timestamp)));
+            self.last_event = Some(self.last_event.map_or(timestamp, |t| t.max(timestamp)));
+            match event {
+                TimelineEvent::NodeEnter {
+                    node_id,
+                    node_label,
+                    ..
+                } => {
+                    labels.insert(node_id, node_label);
+                }
+                TimelineEvent::NodeExit {
+                    node_id,
+                    outcome_type,
+                    duration_ms,
+                    ..
+                } => {
+                    let node = self.nodes.entry(node_id.clone()).or_default();
+                    if let Some(label) = labels.get(node_id.as_str()) {
+                        node.node_label = label.to_string();
+                    }
+                    node.visits += 1;
+                    if outcome_type == "Fault" {
+                        node.error_count += 1;
+                    }
+                    *node.outcomes.entry(outcome_type.clone()).or_default() += 1;
+                    node.record(*duration_ms);
+                }
+                TimelineEvent::Branchtaken { branch_id, .. } => {
+                    *self.branches.entry(branch_id.clone()).or_default() += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Combine with stats of another batch of timelines.
+    pub fn merge(&mut self, other: &TimelineStats) {
+        self.runs += other.runs;
+        for (node_id, theirs) in &other.nodes {
+            let node = self.nodes.entry(node_id.clone()).or_default();
+            if node.node_label.is_empty() {
+                node.node_label = theirs.node_label.clone();
+            }
+            node.visits += theirs.visits;
+            node.error_count += theirs.error_count;
+            for (outcome, count) in &theirs.outcomes {
+                *node.outcomes.entry(outcome.clone()).or_default() += count;
+            }
+            node.latencies_ms.extend_from_slice(&theirs.latencies_ms);
+            node.latencies_ms.sort_unstable();
+        }
+        for (branch_id, count) in &other.branches {
+            *self.branches.entry(branch_id.clone()).or_default() += count;
+        }
+        self.first_event = match (self.first_event, other.first_event) {
+            (Some(ours), Some(theirs)) => Some(ours.min(theirs)),
+            (ours, theirs) => ours.or(theirs),
+        };
+        self.last_event = self.last_event.max(other.last_event);
+    }
+
+    /// Share of runs that took `branch_id`; above 1.0 when runs take it
+    /// repeatedly.
+    pub fn branch_frequency(&self, branch_id: &str) -> f64 {
+        if self.runs == 0 {
+            return 0.0;
+        }
+        self.branches.get(branch_id).copied().unwrap_or(0) as f64 / self.runs as f64
+    }
+
+    /// Exits across all nodes.
+    pub fn total_visits(&self) -> u64 {
+        self.nodes.values().map(|node| node.visits).sum()
+    }
+
+    /// Errors over exits across all nodes.
+    pub fn error_rate(&self) -> f64 {
+        let visits = self.total_visits();
+        if visits == 0 {
+            return 0.0;
+        }
+        self.nodes
+            .values()
+            .map(|node| node.error_count)
+            .sum::<u64>() as f64
+            / visits as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Timeline, TimelineEvent, Timestamp};
+    use super::TimelineStats;
+
+    fn run(charge_ms: u64, outcome: &str, branch: &str) -> Timeline {
+        let mut timeline = Timeline::new();
+        timeline.push(TimelineEvent::NodeEnter {
+            node_id: "charge".into(),
+            node_label: "Charge".into(),
+            timestamp: Timestamp::from_millis(100),
+            trace: None,
+        });
+        timeline.push(TimelineEvent::NodeExit {
+            node_id: "charge".into(),
+            outcome_type: outcome.into(),
+            duration_ms: charge_ms,
+            timestamp: Timestamp::from_millis(100 + charge_ms),
+            trace: None,
+        });
+        timeline.push(TimelineEvent::Branchtaken {
+            branch_id: branch.into(),
+            timestamp: Timestamp::from_millis(100 + charge_ms),
+            trace: None,
+        });
+        timeline
+    }
+
+    #[test]
+    fn stats_fold_percentiles_error_rates_and_branches() {
+        let runs: Vec<_> = (1..=100)
+            .map(|ms| {
+                if ms % 10 == 0 {
+                    run(ms, "Fault", "refund")
+                } else {
+                    run(ms, "Next", "ship")
+                }
+            })
+            .collect();
+        let mut stats = TimelineStats::from_timelines(&runs[..50]);
+        stats.merge(&TimelineStats::from_timelines(&runs[50..]));
+        stats.merge(&TimelineStats::new());
+        assert_eq!(stats, TimelineStats::from_timelines(&runs));
+
+        let charge = &stats.nodes["charge"];
+        assert_eq!(charge.node_label, "Charge");
+        assert_eq!(charge.visits, 100);
+        assert_eq!(charge.p50(), 50.5);
+        assert!((charge.p95() - 95.05).abs() < 1e-9);
+        assert!((charge.p99() - 99.01).abs() < 1e-9);
+        assert_eq!(charge.error_rate(), 0.1);
+        assert_eq!(charge.outcomes["Next"], 90);
+
+        assert_eq!(stats.runs, 100);
+        assert_eq!(stats.branch_frequency("ship"), 0.9);
+        assert_eq!(stats.branch_frequency("unknown"), 0.0);
+        assert_eq!(stats.first_event, Some(Timestamp::from_millis(100)));
+        assert_eq!(stats.last_event, Some(Timestamp::from_millis(200)));
+    }
+}
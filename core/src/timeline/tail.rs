@@ -0,0 +1,144 @@
+//! Live feed of timeline events as they are recorded.
+//!
+//! A [`TimelineTail`] on the Bus makes the Axon executor publish every event
+//! the moment it is pushed onto the execution's [`Timeline`], attributed to
+//! the run with its [`EventTrace`]. Any number of consumers (the Inspector's
+//! `/events` socket, a terminal tail, tests) subscribe to the same tail:
+//!
+//! ```rust
+//! # use ranvier_core::timeline::{Timeline, TimelineEvent, Timestamp};
+//! # use ranvier_core::timeline::tail::TimelineTail;
+//! # use futures_util::{FutureExt, StreamExt};
+//! let tail = TimelineTail::new();
+//! let mut events = tail.subscribe();
+//!
+//! let mut timeline = Timeline::new();
+//! timeline.set_tail(Some(tail.tap(None)));
+//! timeline.push(TimelineEvent::Branchtaken {
+//!     branch_id: "ship".into(),
+//!     timestamp: Timestamp::now(),
+//!     trace: None,
+//! });
+//! let event = events.next().now_or_never().flatten();
+//! assert!(matches!(event, Some(TimelineEvent::Branchtaken { .. })));
+//! ```
+//!
+//! Delivery is best effort: a subscriber that falls more than the tail's
+//! capacity behind skips the events it missed, and events published before
+//! it subscribed are not replayed. Completed runs remain available from the
+//! timeline stores.
+
+use super::{EventTrace, Timeline, TimelineEvent};
+use futures_core::Stream;
+use std::fmt;
+use std::pin::Pin;
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber by [`TimelineTail::new`].
+pub const DEFAULT_TAIL_CAPACITY: usize = 1024;
+
+/// Stream of live events returned by [`TimelineTail::subscribe`].
+pub type TimelineEventStream = Pin<Box<dyn Stream<Item = TimelineEvent> + Send>>;
+
+/// Broadcast of timeline events, shared by cloning.
+#[derive(Clone)]
+pub struct TimelineTail {
+    sender: broadcast::Sender<TimelineEvent>,
+}
+
+impl Default for TimelineTail {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for TimelineTail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimelineTail")
+            .field("subscribers", &self.subscriber_count())
+            .finish()
+    }
+}
+
+impl TimelineTail {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_TAIL_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Events published from now on. Ends when every clone of the tail has
+    /// been dropped.
+    pub fn subscribe(&self) -> TimelineEventStream {
+        let receiver = self.sender.subscribe();
+        Box::pin(futures_util::stream::unfold(
+            receiver,
+            |mut receiver| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) => return Some((event, receiver)),
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!(skipped, "Timeline tail subscriber lagged");
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        ))
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Send `event` to the current subscribers.
+    pub fn publish(&self, event: TimelineEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    /// A tap for [`Timeline::set_tail`] that attributes unattributed events
+    /// to `trace`.
+    pub fn tap(&self, trace: Option<EventTrace>) -> TailTap {
+        TailTap {
+            tail: self.clone(),
+            trace,
+        }
+    }
+}
+
+/// Connection of one [`Timeline`] to a [`TimelineTail`].
+#[derive(Debug, Clone)]
+pub struct TailTap {
+    tail: TimelineTail,
+    trace: Option<EventTrace>,
+}
+
+impl TailTap {
+    fn publish(&self, event: &TimelineEvent) {
+        if self.tail.subscriber_count() == 0 {
+            return;
+        }
+        let mut event = event.clone();
+        if let (slot @ None, Some(trace)) = (event.trace_mut(), &self.trace) {
+            *slot = Some(trace.clone());
+        }
+        self.tail.publish(event);
+    }
+}
+
+impl Timeline {
+    /// Publish every event pushed from now on through `tap`, replacing the
+    /// previous tap, which is returned.
+    pub fn set_tail(&mut self, tap: Option<TailTap>) -> Option<TailTap> {
+        std::mem::replace(&mut self.tail, tap)
+    }
+
+    pub(crate) fn publish_to_tail(&self, event: &TimelineEvent) {
+        if let Some(tap) = &self.tail {
+            tap.publish(event);
+        }
+    }
+}
@@ -0,0 +1,545 @@
+//! Storage for the timelines of many executions.
+//!
+//! `RANVIER_TIMELINE_OUTPUT` writes the timeline of the latest execution to a
+//! single file, which is enough for replaying one run but keeps no history.
+//! A [`TimelineStore`] records every run under its own id instead, so tools
+//! such as the Inspector can list past executions of a circuit and open any of
+//! them. `ranvier-runtime` records into the store found on the Bus and ships a
+//! SQLite implementation behind `timeline-sqlite`.
+//!
+//! Runs are found with a [`TimelineRunQuery`], most conveniently built with
+//! the `query()` of a store (see [`TimelineQuery`]):
+//!
+//! ```rust
+//! # use ranvier_core::timeline::Timestamp;
+//! # use ranvier_core::timeline_store::InMemoryTimelineStore;
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let store = InMemoryTimelineStore::new();
+//! let faults = store
+//!     .query()
+//!     .circuit("orders")
+//!     .outcome("Fault")
+//!     .since(Timestamp::from_millis(0))
+//!     .list()
+//!     .await
+//!     .unwrap();
+//! assert!(faults.is_empty());
+//! # }
+//! ```
+//!
+//! A [`TimelineRetention`] bounds how much history a store keeps: the newest
+//! `max_runs`, runs younger than `max_age`, and optionally every faulted run
+//! regardless of both. [`TimelineStore::compact`] applies it on demand.
+
+use crate::timeline::{Timeline, Timestamp};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Timeline of one execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineRun {
+    pub run_id: String,
+    pub circuit: String,
+    /// Kind of the final outcome (`Next`, `Fault`, ...).
+    pub outcome: String,
+    pub recorded_at: Timestamp,
+    pub timeline: Timeline,
+}
+
+impl TimelineRun {
+    pub fn new(
+        run_id: impl Into<String>,
+        circuit: impl Into<String>,
+        outcome: impl Into<String>,
+        timeline: Timeline,
+    ) -> Self {
+        Self {
+            run_id: run_id.into(),
+            circuit: circuit.into(),
+            outcome: outcome.into(),
+            recorded_at: Timestamp::now(),
+            timeline,
+        }
+    }
+
+    pub fn summary(&self) -> TimelineRunSummary {
+        TimelineRunSummary {
+            run_id: self.run_id.clone(),
+            circuit: self.circuit.clone(),
+            outcome: self.outcome.clone(),
+            recorded_at: self.recorded_at,
+            event_count: self.timeline.events.len(),
+        }
+    }
+}
+
+/// A stored run without its events, as returned by [`TimelineStore::list_runs`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineRunSummary {
+    pub run_id: String,
+    pub circuit: String,
+    pub outcome: String,
+    pub recorded_at: Timestamp,
+    pub event_count: usize,
+}
+
+/// Filter for [`TimelineStore::list_runs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineRunQuery {
+    /// Only runs of this circuit.
+    #[serde(default)]
+    pub circuit: Option<String>,
+    /// Only runs whose final outcome is of this kind (`Next`, `Fault`, ...).
+    #[serde(default)]
+    pub outcome: Option<String>,
+    /// Only runs recorded at or after this instant.
+    #[serde(default)]
+    pub since: Option<Timestamp>,
+    /// Only runs recorded before this instant.
+    #[serde(default)]
+    pub until: Option<Timestamp>,
+    /// At most this many runs; all of them when unset.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl TimelineRunQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn circuit(mut self, circuit: impl Into<String>) -> Self {
+        self.circuit = Some(circuit.into());
+        self
+    }
+
+    pub fn outcome(mut self, outcome: impl Into<String>) -> Self {
+        self.outcome = Some(outcome.into());
+        self
+    }
+
+    pub fn since(mut self, since: Timestamp) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn until(mut self, until: Timestamp) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Whether a run passes every filter except `limit`.
+    pub fn matches(&self, run: &TimelineRunSummary) -> bool {
+        self.circuit
+            .as_deref()
+            .is_none_or(|circuit| run.circuit == circuit)
+            && self
+                .outcome
+                .as_deref()
+                .is_none_or(|outcome| run.outcome == outcome)
+            && self.since.is_none_or(|since| run.recorded_at >= since)
+            && self.until.is_none_or(|until| run.recorded_at < until)
+    }
+}
+
+/// A [`TimelineRunQuery`] bound to the store it runs against, created by
+/// `query()` on a store or an `Arc<dyn TimelineStore>`.
+#[must_use = "a query does nothing until `list` or `fetch` is awaited"]
+pub struct TimelineQuery<'a> {
+    store: &'a dyn TimelineStore,
+    query: TimelineRunQuery,
+}
+
+impl<'a> TimelineQuery<'a> {
+    pub fn new(store: &'a dyn TimelineStore) -> Self {
+        Self::with_query(store, TimelineRunQuery::new())
+    }
+
+    /// Start from an existing filter, e.g. one parsed from a request.
+    pub fn with_query(store: &'a dyn TimelineStore, query: TimelineRunQuery) -> Self {
+        Self { store, query }
+    }
+
+    pub fn circuit(mut self, circuit: impl Into<String>) -> Self {
+        self.query = self.query.circuit(circuit);
+        self
+    }
+
+    pub fn outcome(mut self, outcome: impl Into<String>) -> Self {
+        self.query = self.query.outcome(outcome);
+        self
+    }
+
+    pub fn since(mut self, since: Timestamp) -> Self {
+        self.query = self.query.since(since);
+        self
+    }
+
+    pub fn until(mut self, until: Timestamp) -> Self {
+        self.query = self.query.until(until);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.query = self.query.limit(limit);
+        self
+    }
+
+    pub fn as_query(&self) -> &TimelineRunQuery {
+        &self.query
+    }
+
+    /// Summaries of the matching runs, most recently recorded first.
+    pub async fn list(self) -> Result<Vec<TimelineRunSummary>, String> {
+        self.store.list_runs(self.query).await
+    }
+
+    /// The matching runs with their timelines, most recently recorded first.
+    pub async fn fetch(self) -> Result<Vec<TimelineRun>, String> {
+        let store = self.store;
+        let mut runs = Vec::new();
+        for summary in store.list_runs(self.query).await? {
+            // A run compacted away since listing is skipped.
+            if let Some(run) = store.fetch_run(&summary.run_id).await? {
+                runs.push(run);
+            }
+        }
+        Ok(runs)
+    }
+}
+
+/// Which stored runs survive compaction.
+///
+/// The default keeps everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineRetention {
+    /// Keep at most this many runs, dropping the oldest first.
+    #[serde(default)]
+    pub max_runs: Option<usize>,
+    /// Drop runs recorded longer ago than this.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::serde_helpers::optional_duration_secs"
+    )]
+    pub max_age: Option<Duration>,
+    /// Keep every `Fault` run; they do not count towards `max_runs`.
+    #[serde(default)]
+    pub keep_faults: bool,
+}
+
+impl TimelineRetention {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_runs(mut self, max_runs: usize) -> Self {
+        self.max_runs = Some(max_runs);
+        self
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn keep_faults(mut self, keep_faults: bool) -> Self {
+        self.keep_faults = keep_faults;
+        self
+    }
+
+    /// Whether this retention never drops a run.
+    pub fn keeps_everything(&self) -> bool {
+        self.max_runs.is_none() && self.max_age.is_none()
+    }
+
+    /// Runs recorded before this instant are too old, relative to `now`.
+    pub fn cutoff(&self, now: Timestamp) -> Option<Timestamp> {
+        self.max_age.map(|max_age| {
+            let age = u64::try_from(max_age.as_nanos()).unwrap_or(u64::MAX);
+            Timestamp::from_nanos(now.as_nanos().saturating_sub(age))
+        })
+    }
+
+    /// Ids of the runs to drop from `runs`, given most recently recorded first.
+    pub fn expired<'a>(
+        &self,
+        runs: impl IntoIterator<Item = &'a TimelineRunSummary>,
+        now: Timestamp,
+    ) -> Vec<String> {
+        let cutoff = self.cutoff(now);
+        let max_runs = self.max_runs.unwrap_or(usize::MAX);
+        let mut kept = 0;
+        runs.into_iter()
+            .filter(|run| {
+                if self.keep_faults && run.outcome == "Fault" {
+                    return false;
+                }
+                let expired = kept >= max_runs || cutoff.is_some_and(|c| run.recorded_at < c);
+                if !expired {
+                    kept += 1;
+                }
+                expired
+            })
+            .map(|run| run.run_id.clone())
+            .collect()
+    }
+}
+
+/// Backend for recorded execution timelines.
+#[async_trait]
+pub trait TimelineStore: Send + Sync {
+    /// Record a run, replacing a stored run with the same id.
+    async fn append_run(&self, run: TimelineRun) -> Result<(), String>;
+
+    /// Runs matching `query`, most recently recorded first.
+    async fn list_runs(&self, query: TimelineRunQuery) -> Result<Vec<TimelineRunSummary>, String>;
+
+    async fn fetch_run(&self, run_id: &str) -> Result<Option<TimelineRun>, String>;
+
+    /// Remove the given runs, returning how many were stored.
+    async fn delete_runs(&self, run_ids: &[String]) -> Result<usize, String>;
+
+    /// Drop the runs `retention` does not keep, returning how many were
+    /// removed.
+    async fn compact(&self, retention: &TimelineRetention) -> Result<usize, String> {
+        if retention.keeps_everything() {
+            return Ok(0);
+        }
+        let runs = self.list_runs(TimelineRunQuery::new()).await?;
+        let expired = retention.expired(&runs, Timestamp::now());
+        if expired.is_empty() {
+            return Ok(0);
+        }
+        self.delete_runs(&expired).await
+    }
+}
+
+impl dyn TimelineStore {
+    /// Start a query against this store.
+    pub fn query(&self) -> TimelineQuery<'_> {
+        TimelineQuery::new(self)
+    }
+}
+
+/// Process-local [`TimelineStore`] that keeps the most recent runs.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryTimelineStore {
+    runs: Arc<Mutex<VecDeque<TimelineRun>>>,
+    retention: TimelineRetention,
+}
+
+impl InMemoryTimelineStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep at most `max_runs` runs, dropping the oldest first.
+    pub fn with_max_runs(max_runs: usize) -> Self {
+        Self::with_retention(TimelineRetention::new().max_runs(max_runs.max(1)))
+    }
+
+    /// Apply `retention` every time a run is appended.
+    pub fn with_retention(retention: TimelineRetention) -> Self {
+        Self {
+            runs: Arc::default(),
+            retention,
+        }
+    }
+
+    /// Start a query against this store.
+    pub fn query(&self) -> TimelineQuery<'_> {
+        TimelineQuery::new(self)
+    }
+
+    fn remove(runs: &mut VecDeque<TimelineRun>, run_ids: &[String]) -> usize {
+        let before = runs.len();
+        runs.retain(|run| !run_ids.contains(&run.run_id));
+        before - runs.len()
+    }
+}
+
+#[async_trait]
+impl TimelineStore for InMemoryTimelineStore {
+    async fn append_run(&self, run: TimelineRun) -> Result<(), String> {
+        let mut runs = self.runs.lock();
+        runs.retain(|stored| stored.run_id != run.run_id);
+        runs.push_back(run);
+        if !self.retention.keeps_everything() {
+            let summaries: Vec<_> = runs.iter().rev().map(TimelineRun::summary).collect();
+            let expired = self.retention.expired(&summaries, Timestamp::now());
+            Self::remove(&mut runs, &expired);
+        }
+        Ok(())
+    }
+
+    async fn list_runs(&self, query: TimelineRunQuery) -> Result<Vec<TimelineRunSummary>, String> {
+        Ok(self
+            .runs
+            .lock()
+            .iter()
+            .rev()
+            .map(TimelineRun::summary)
+            .filter(|run| query.matches(run))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
+    async fn fetch_run(&self, run_id: &str) -> Result<Option<TimelineRun>, String> {
+        Ok(self
+            .runs
+            .lock()
+            .iter()
+            .find(|run| run.run_id == run_id)
+            .cloned())
+    }
+
+    async fn delete_runs(&self, run_ids: &[String]) -> Result<usize, String> {
+        Ok(Self::remove(&mut self.runs.lock(), run_ids))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeline::TimelineEvent;
+
+    fn run(run_id: &str, circuit: &str, events: usize) -> TimelineRun {
+        let mut timeline = Timeline::new();
+        for i in 0..events {
+            timeline.push(TimelineEvent::NodeEnter {
+                node_id: format!("n{i}"),
+                node_label: format!("Step{i}"),
+                timestamp: Timestamp::from_millis(i as u64),
+                trace: None,
+            });
+        }
+        TimelineRun::new(run_id, circuit, "Next", timeline)
+    }
+
+    #[tokio::test]
+    async fn in_memory_store_lists_newest_first_and_replaces_by_id() {
+        let store = InMemoryTimelineStore::with_max_runs(3);
+        store.append_run(run("a", "Orders", 1)).await.unwrap();
+        store.append_run(run("b", "Billing", 2)).await.unwrap();
+        store.append_run(run("c", "Orders", 3)).await.unwrap();
+        store.append_run(run("a", "Orders", 4)).await.unwrap();
+
+        let ids: Vec<String> = store
+            .list_runs(TimelineRunQuery::new())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|summary| summary.run_id)
+            .collect();
+        assert_eq!(ids, ["a", "c", "b"]);
+
+        let orders = store
+            .list_runs(TimelineRunQuery::new().circuit("Orders").limit(1))
+            .await
+            .unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].run_id, "a");
+        assert_eq!(orders[0].event_count, 4);
+
+        store.append_run(run("d", "Orders", 1)).await.unwrap();
+        assert!(store.fetch_run("b").await.unwrap().is_none());
+        let fetched = store.fetch_run("c").await.unwrap().unwrap();
+        assert_eq!(fetched.timeline.events.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn query_filters_by_outcome_and_time_range() {
+        let store = InMemoryTimelineStore::new();
+        for (i, (run_id, outcome)) in [("a", "Fault"), ("b", "Next"), ("c", "Fault")]
+            .into_iter()
+            .enumerate()
+        {
+            let mut run = run(run_id, "Orders", 1);
+            run.outcome = outcome.into();
+            run.recorded_at = Timestamp::from_millis(1_000 * (i as u64 + 1));
+            store.append_run(run).await.unwrap();
+        }
+        store.append_run(run("d", "Billing", 1)).await.unwrap();
+
+        let ids = |runs: Vec<TimelineRunSummary>| -> Vec<String> {
+            runs.into_iter().map(|run| run.run_id).collect()
+        };
+        let faults = store
+            .query()
+            .circuit("Orders")
+            .outcome("Fault")
+            .list()
+            .await
+            .unwrap();
+        assert_eq!(ids(faults), ["c", "a"]);
+        let window = store
+            .query()
+            .circuit("Orders")
+            .since(Timestamp::from_millis(2_000))
+            .until(Timestamp::from_millis(3_000))
+            .list()
+            .await
+            .unwrap();
+        assert_eq!(ids(window), ["b"]);
+
+        let shared: Arc<dyn TimelineStore> = Arc::new(store);
+        let runs = shared
+            .query()
+            .outcome("Fault")
+            .limit(1)
+            .fetch()
+            .await
+            .unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].run_id, "c");
+        assert_eq!(runs[0].timeline.events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn retention_drops_old_and_excess_runs_but_keeps_faults() {
+        let now = Timestamp::from_millis(100_000);
+        let aged = |run_id: &str, outcome: &str, age_secs: u64| {
+            let mut run = run(run_id, "Orders", 1);
+            run.outcome = outcome.into();
+            run.recorded_at = Timestamp::from_millis(100_000 - age_secs * 1_000);
+            run.summary()
+        };
+        let runs = [
+            aged("e", "Next", 1),
+            aged("d", "Fault", 2),
+            aged("c", "Next", 3),
+            aged("b", "Next", 50),
+            aged("a", "Fault", 90),
+        ];
+        let retention = TimelineRetention::new()
+            .max_runs(2)
+            .max_age(Duration::from_secs(60));
+        assert_eq!(retention.expired(&runs, now), ["c", "b", "a"]);
+        let retention = retention.keep_faults(true);
+        assert_eq!(retention.expired(&runs, now), ["b"]);
+        assert!(TimelineRetention::new().expired(&runs, now).is_empty());
+
+        let store = InMemoryTimelineStore::new();
+        for run_id in ["a", "b", "c"] {
+            store.append_run(run(run_id, "Orders", 1)).await.unwrap();
+        }
+        let removed = store
+            .compact(&TimelineRetention::new().max_runs(1))
+            .await
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert!(store.fetch_run("c").await.unwrap().is_some());
+        assert_eq!(store.delete_runs(&["c".into()]).await.unwrap(), 1);
+    }
+}
@@ -8,9 +8,12 @@
 //! * **No Hidden Effects**: All effects must go through the `Bus`
 //! * **Outcome-Based Control Flow**: Returns `Outcome` not `Result`
 
-use crate::bus::{Bus, BusAccessPolicy};
+use crate::bus::{Bus, BusAccessPolicy, BusTypeRef};
+use crate::metadata::NodeAnnotations;
 use crate::outcome::Outcome;
+use crate::schematic::SourceLocation;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 /// Resource requirement for a transition.
@@ -19,11 +22,40 @@ use std::fmt::Debug;
 /// to [`Transition::run`]. Implementations should usually be a struct containing
 /// application dependencies. Adapter-injected request context belongs in the
 /// per-execution [`Bus`], where presence and authorization are runtime checks.
-pub trait ResourceRequirement: Send + Sync + 'static {}
+///
+/// A bundle may also list the Bus entries its transitions read, so that
+/// `Axon::check_resources` can report a missing one at startup instead of
+/// mid-request.
+pub trait ResourceRequirement: Send + Sync + 'static {
+    /// Bus entries that transitions using this bundle expect to find.
+    fn bus_requirements() -> Vec<BusTypeRef>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+}
 
 /// Blanket implementation for () if no resources are needed.
 impl ResourceRequirement for () {}
 
+/// A side effect a transition declares through [`Transition::describe`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SideEffect {
+    /// Short category, e.g. `"db.update"` or `"email.send"`.
+    pub kind: String,
+    pub detail: String,
+}
+
+impl SideEffect {
+    pub fn new(kind: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            detail: detail.into(),
+        }
+    }
+}
+
 /// The contract for a Typed State Transition.
 ///
 /// `Transition` converts state `From` to `Outcome<To, Error>`.
@@ -104,6 +136,16 @@ where
         None
     }
 
+    /// Fixed ID for this transition's node.
+    ///
+    /// Defaults to `None`: the ID is derived from the circuit name, label
+    /// and position (see [`stable_node_id`](crate::schematic::stable_node_id)).
+    /// Set one to keep the ID when steps are inserted before this one.
+    /// `#[transition(id = "...")]` sets it.
+    fn node_id(&self) -> Option<String> {
+        None
+    }
+
     /// Returns the visual position of this transition in a schematic.
     /// (x, y) coordinates.
     fn position(&self) -> Option<(f32, f32)> {
@@ -117,6 +159,41 @@ where
         None
     }
 
+    /// Bus entries this transition reads, recorded on its node and checked by
+    /// `Axon::check_resources`.
+    ///
+    /// Defaults to the [`bus_requirements`](ResourceRequirement::bus_requirements)
+    /// of its resource bundle.
+    fn required_resources(&self) -> Vec<BusTypeRef> {
+        Self::Resources::bus_requirements()
+    }
+
+    /// Where this transition is defined.
+    ///
+    /// Nodes built from it point here instead of at the builder call.
+    /// `#[transition]` fills this with the function's file, line and module.
+    fn source_location(&self) -> Option<SourceLocation> {
+        None
+    }
+
+    /// Branch ids this transition may return in `Outcome::Branch`.
+    ///
+    /// Each one becomes a `Branch` edge of the Schematic, ending in an
+    /// `Egress` node until `Axon::branch` handles it with an arm.
+    /// `#[transition]` fills this from `#[branch(..)]` attributes and from
+    /// string literals passed to `Outcome::Branch`/`Outcome::branch`.
+    fn branches(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Owner, team, SLA and tags recorded on this transition's nodes.
+    ///
+    /// `#[transition(owner = "...", team = "...", sla_ms = .., tags = [..])]`
+    /// fills this.
+    fn annotations(&self) -> NodeAnnotations {
+        NodeAnnotations::default()
+    }
+
     /// Optional JSON Schema for the input type of this transition.
     ///
     /// When `#[transition(schema)]` is used, this returns the JSON Schema
@@ -125,6 +202,26 @@ where
         None
     }
 
+    /// Side effects this transition performs when it runs.
+    ///
+    /// Reported by dry runs; the default declares none.
+    fn describe(&self) -> Vec<SideEffect> {
+        Vec::new()
+    }
+
+    /// Predict the outcome of [`run`](Transition::run) without performing it.
+    ///
+    /// Dry runs call this instead of `run`. The default `None` means the
+    /// outcome cannot be predicted, which stops a dry run at this transition.
+    fn estimate(
+        &self,
+        _state: &From,
+        _resources: &Self::Resources,
+        _bus: &Bus,
+    ) -> Option<Outcome<To, Self::Error>> {
+        None
+    }
+
     /// Execute the transition.
     ///
     /// # Parameters
@@ -170,9 +267,42 @@ where
         self.as_ref().bus_access_policy()
     }
 
+    fn required_resources(&self) -> Vec<BusTypeRef> {
+        self.as_ref().required_resources()
+    }
+
+    fn node_id(&self) -> Option<String> {
+        self.as_ref().node_id()
+    }
+
+    fn source_location(&self) -> Option<SourceLocation> {
+        self.as_ref().source_location()
+    }
+
+    fn branches(&self) -> Vec<String> {
+        self.as_ref().branches()
+    }
+
+    fn annotations(&self) -> NodeAnnotations {
+        self.as_ref().annotations()
+    }
+
     fn input_schema(&self) -> Option<serde_json::Value> {
         self.as_ref().input_schema()
     }
+
+    fn describe(&self) -> Vec<SideEffect> {
+        self.as_ref().describe()
+    }
+
+    fn estimate(
+        &self,
+        state: &From,
+        resources: &Self::Resources,
+        bus: &Bus,
+    ) -> Option<Outcome<To, Self::Error>> {
+        self.as_ref().estimate(state, resources, bus)
+    }
 }
 
 #[cfg(test)]
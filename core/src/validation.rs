@@ -0,0 +1,490 @@
+//! Structural checks over a built [`Schematic`].
+//!
+//! [`validate_schematic`] looks for graphs that build but cannot run as
+//! drawn: edges to missing nodes, nodes no path reaches, `Next` edges whose
+//! output and input types disagree, cycles closed without a `Jump` edge,
+//! duplicate labels and unresolved jump targets. `Axon::validate` in `ranvier-runtime` runs it on a circuit, and
+//! tooling can run it on an exported schematic:
+//!
+//! ```rust,ignore
+//! let schematic: Schematic = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+//! let report = validate_schematic(&schematic);
+//! print!("{report}");
+//! if report.has_errors() {
+//!     std::process::exit(1);
+//! }
+//! ```
+//!
+//! Nodes inside a subgraph are checked against their own subgraph, except
+//! for jump targets, which may name any node of the circuit.
+//!
+//! [`check_resources`] compares the Bus entries the circuit's nodes declare
+//! (see `Transition::required_resources`) against a [`ResourceRegistry`],
+//! typically the Bus an application prepares at startup.
+
+use crate::bus::ResourceRegistry;
+use crate::schematic::analysis::{CycleKind, Graph};
+use crate::schematic::{EdgeType, NodeKind, Schematic};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// What a [`Diagnostic`] is about.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// An edge names a node id the schematic does not contain.
+    DanglingEdge { from: String, to: String },
+    /// No edge touches the node.
+    OrphanNode,
+    /// The node has edges, but none of them leads to it from the start node.
+    UnreachableNode,
+    /// A `Next` edge joins an output type to a different input type.
+    TypeMismatch {
+        to: String,
+        output: String,
+        input: String,
+    },
+    /// Another node carries the same label.
+    DuplicateLabel,
+    /// A declared jump target names no node.
+    UnresolvedJumpTarget { target: String },
+    /// The node starts a cycle closed without a `Jump` edge, which no
+    /// builder produces and the executor cannot follow.
+    Cycle { nodes: Vec<String> },
+}
+
+/// One finding of [`validate_schematic`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_label: Option<String>,
+    #[serde(flatten)]
+    pub kind: DiagnosticKind,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let node = self.node_label.as_deref().unwrap_or("<edge>");
+        write!(f, "{severity} {node}: ")?;
+        match &self.kind {
+            DiagnosticKind::DanglingEdge { from, to } => {
+                write!(f, "edge {from} -> {to} names a missing node")
+            }
+            DiagnosticKind::OrphanNode => write!(f, "no edge touches this node"),
+            DiagnosticKind::UnreachableNode => write!(f, "not reachable from the start node"),
+            DiagnosticKind::TypeMismatch { to, output, input } => {
+                write!(f, "outputs {output} but '{to}' expects {input}")
+            }
+            DiagnosticKind::DuplicateLabel => write!(f, "label is used by more than one node"),
+            DiagnosticKind::UnresolvedJumpTarget { target } => {
+                write!(f, "jump target '{target}' is not a node of this circuit")
+            }
+            DiagnosticKind::Cycle { nodes } => {
+                write!(
+                    f,
+                    "cycle without a Jump edge through {}",
+                    nodes.join(" -> ")
+                )
+            }
+        }
+    }
+}
+
+/// Result of [`validate_schematic`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub circuit: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}: {diagnostic}", self.circuit)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check a schematic's structure. See the [module docs](self).
+pub fn validate_schematic(schematic: &Schematic) -> ValidationReport {
+    let mut diagnostics = Vec::new();
+    check_graph(schematic, &mut diagnostics);
+    for unresolved in schematic.unresolved_jump_targets() {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            node_id: Some(unresolved.node_id),
+            node_label: Some(unresolved.node_label),
+            kind: DiagnosticKind::UnresolvedJumpTarget {
+                target: unresolved.target,
+            },
+        });
+    }
+    ValidationReport {
+        circuit: schematic.name.clone(),
+        diagnostics,
+    }
+}
+
+/// A required Bus entry the registry does not provide.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnmetResource {
+    pub resource: String,
+    /// Labels of the nodes that require it.
+    pub required_by: Vec<String>,
+}
+
+/// Result of a failed [`check_resources`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceCheckError {
+    pub circuit: String,
+    pub missing: Vec<UnmetResource>,
+}
+
+impl ResourceCheckError {
+    pub fn category(&self) -> &'static str {
+        crate::bus::MISSING_RESOURCE
+    }
+}
+
+impl fmt::Display for ResourceCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "circuit '{}' is missing Bus resources:", self.circuit)?;
+        for unmet in &self.missing {
+            write!(
+                f,
+                " `{}` (required by {});",
+                unmet.resource,
+                unmet.required_by.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ResourceCheckError {}
+
+impl From<ResourceCheckError> for String {
+    fn from(error: ResourceCheckError) -> Self {
+        error.to_string()
+    }
+}
+
+impl From<ResourceCheckError> for crate::error::RanvierError {
+    fn from(error: ResourceCheckError) -> Self {
+        Self::not_found(error.to_string())
+    }
+}
+
+/// Every Bus entry the schematic's nodes require, by type name, with the
+/// labels of the nodes requiring it. Includes nodes inside subgraphs.
+pub fn required_resources(schematic: &Schematic) -> BTreeMap<String, Vec<String>> {
+    fn collect(schematic: &Schematic, out: &mut BTreeMap<String, Vec<String>>) {
+        for node in &schematic.nodes {
+            for resource in &node.metadata.required_resources {
+                let labels = out.entry(resource.clone()).or_default();
+                if !labels.contains(&node.label) {
+                    labels.push(node.label.clone());
+                }
+            }
+            if let NodeKind::Subgraph(inner) = &node.kind {
+                collect(inner, out);
+            }
+        }
+    }
+    let mut out = BTreeMap::new();
+    collect(schematic, &mut out);
+    out
+}
+
+/// Fail with every [`required_resources`] entry `registry` does not provide.
+pub fn check_resources(
+    schematic: &Schematic,
+    registry: &dyn ResourceRegistry,
+) -> Result<(), ResourceCheckError> {
+    let missing: Vec<UnmetResource> = required_resources(schematic)
+        .into_iter()
+        .filter(|(resource, _)| !registry.provides(resource))
+        .map(|(resource, required_by)| UnmetResource {
+            resource,
+            required_by,
+        })
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(ResourceCheckError {
+            circuit: schematic.name.clone(),
+            missing,
+        })
+    }
+}
+
+fn check_graph(schematic: &Schematic, out: &mut Vec<Diagnostic>) {
+    let nodes: HashMap<&str, usize> = schematic
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id.as_str(), i))
+        .collect();
+    // Compensation node index -> index of the node it undoes.
+    let compensated: HashMap<usize, usize> = schematic
+        .nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, node)| {
+            let comp = nodes.get(node.compensation_node_id.as_deref()?)?;
+            Some((*comp, i))
+        })
+        .collect();
+    for edge in &schematic.edges {
+        let (Some(&from), Some(&to)) = (nodes.get(edge.from.as_str()), nodes.get(edge.to.as_str()))
+        else {
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                node_id: None,
+                node_label: None,
+                kind: DiagnosticKind::DanglingEdge {
+                    from: edge.from.clone(),
+                    to: edge.to.clone(),
+                },
+            });
+            continue;
+        };
+
+        // Compensation nodes sit after the node they undo, so the builder
+        // chains the next step from them; its input follows that node.
+        let source = &schematic.nodes[compensated.get(&from).copied().unwrap_or(from)];
+        let target = &schematic.nodes[to];
+        if matches!(edge.kind, EdgeType::Linear) && source.output_type != target.input_type {
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                node_id: Some(source.id.clone()),
+                node_label: Some(source.label.clone()),
+                kind: DiagnosticKind::TypeMismatch {
+                    to: target.label.clone(),
+                    output: source.output_type.clone(),
+                    input: target.input_type.clone(),
+                },
+            });
+        }
+    }
+
+    let graph = Graph::new(schematic);
+    let reached = graph.reachable();
+    let degrees = graph.degrees();
+    for cycle in graph.cycles() {
+        if cycle.kind == CycleKind::Structural {
+            let first = &schematic.nodes[nodes[cycle.nodes[0].as_str()]];
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                node_id: Some(first.id.clone()),
+                node_label: Some(first.label.clone()),
+                kind: DiagnosticKind::Cycle { nodes: cycle.nodes },
+            });
+        }
+    }
+
+    let mut seen_labels = HashSet::new();
+    let mut duplicates = HashSet::new();
+    for node in &schematic.nodes {
+        if !seen_labels.insert(node.label.as_str()) {
+            duplicates.insert(node.label.as_str());
+        }
+    }
+
+    for (i, node) in schematic.nodes.iter().enumerate() {
+        let kind = if reached[i] {
+            None
+        } else if !degrees[i].is_isolated() {
+            Some(DiagnosticKind::UnreachableNode)
+        } else {
+            Some(DiagnosticKind::OrphanNode)
+        };
+        if let Some(kind) = kind {
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                node_id: Some(node.id.clone()),
+                node_label: Some(node.label.clone()),
+                kind,
+            });
+        }
+        if duplicates.contains(node.label.as_str()) {
+            out.push(Diagnostic {
+                severity: Severity::Warning,
+                node_id: Some(node.id.clone()),
+                node_label: Some(node.label.clone()),
+                kind: DiagnosticKind::DuplicateLabel,
+            });
+        }
+        if let NodeKind::Subgraph(inner) = &node.kind {
+            check_graph(inner, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{Edge, Node};
+
+    fn node(id: &str, label: &str, input: &str, output: &str) -> Node {
+        Node {
+            id: id.into(),
+            kind: NodeKind::Atom,
+            label: label.into(),
+            description: None,
+            input_type: input.into(),
+            output_type: output.into(),
+            resource_type: "()".into(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: None,
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        }
+    }
+
+    fn next(from: &str, to: &str) -> Edge {
+        Edge {
+            from: from.into(),
+            to: to.into(),
+            kind: EdgeType::Linear,
+            label: None,
+        }
+    }
+
+    fn kinds(report: &ValidationReport) -> Vec<(Option<&str>, &DiagnosticKind)> {
+        report
+            .diagnostics
+            .iter()
+            .map(|d| (d.node_label.as_deref(), &d.kind))
+            .collect()
+    }
+
+    #[test]
+    fn a_compensated_chain_is_clean() {
+        let mut schematic = Schematic::new("Orders");
+        let mut reserve = node("b", "Reserve", "Order", "Order");
+        reserve.compensation_node_id = Some("u".into());
+        schematic.nodes = vec![
+            node("a", "Start", "void", "Order"),
+            reserve,
+            node("u", "Compensate: Release", "Order", "void"),
+            node("c", "Price", "Order", "Priced"),
+        ];
+        schematic.edges = vec![next("a", "b"), next("u", "c")];
+        assert_eq!(validate_schematic(&schematic).diagnostics, vec![]);
+    }
+
+    #[test]
+    fn structural_problems_are_reported() {
+        let mut schematic = Schematic::new("Orders");
+        schematic.nodes = vec![
+            node("a", "Start", "void", "Order"),
+            node("b", "Price", "Invoice", "Priced"),
+            node("c", "Loop", "Priced", "Priced"),
+            node("d", "Loop", "Priced", "Priced"),
+            node("e", "Lost", "Priced", "Priced"),
+        ];
+        schematic.edges = vec![next("a", "b"), next("d", "c"), next("b", "zz")];
+
+        let report = validate_schematic(&schematic);
+        assert!(report.has_errors());
+        assert_eq!(
+            kinds(&report),
+            vec![
+                (
+                    Some("Start"),
+                    &DiagnosticKind::TypeMismatch {
+                        to: "Price".into(),
+                        output: "Order".into(),
+                        input: "Invoice".into(),
+                    }
+                ),
+                (
+                    None,
+                    &DiagnosticKind::DanglingEdge {
+                        from: "b".into(),
+                        to: "zz".into(),
+                    }
+                ),
+                (Some("Loop"), &DiagnosticKind::UnreachableNode),
+                (Some("Loop"), &DiagnosticKind::DuplicateLabel),
+                (Some("Loop"), &DiagnosticKind::UnreachableNode),
+                (Some("Loop"), &DiagnosticKind::DuplicateLabel),
+                (Some("Lost"), &DiagnosticKind::OrphanNode),
+            ]
+        );
+        assert_eq!(report.warnings().count(), 2);
+    }
+
+    #[test]
+    fn only_cycles_without_a_jump_edge_are_errors() {
+        let mut schematic = Schematic::new("Orders");
+        schematic.nodes = vec![
+            node("a", "Start", "void", "Order"),
+            node("b", "Check", "Order", "Order"),
+            node("c", "Retry", "Order", "Order"),
+        ];
+        schematic.edges = vec![
+            next("a", "b"),
+            next("b", "c"),
+            Edge {
+                kind: EdgeType::Jump,
+                ..next("c", "b")
+            },
+        ];
+        assert_eq!(validate_schematic(&schematic).diagnostics, vec![]);
+
+        schematic.edges[2].kind = EdgeType::Fault;
+        let report = validate_schematic(&schematic);
+        assert_eq!(
+            kinds(&report),
+            vec![(
+                Some("Check"),
+                &DiagnosticKind::Cycle {
+                    nodes: vec!["b".into(), "c".into()],
+                }
+            )]
+        );
+        assert!(report.to_string().contains("b -> c"));
+    }
+}
@@ -1,6 +1,6 @@
 //! # Complex Schematic Graph
 //!
-//! Shows how to manually enhance Axon schematics with Branch edges, nested subgraphs, and JSON export.
+//! Shows Branch edges from the `branch` builder, a nested sub-flow, and JSON export.
 //!
 //! ## Run
 //! ```bash
@@ -8,14 +8,13 @@
 //! ```
 //!
 //! ## Key Concepts
-//! - Manual Schematic node/edge manipulation
-//! - Branch edges for conditional flows
-//! - Nested subgraphs and JSON serialization
+//! - `Axon::branch` routes `Outcome::Branch` into sub-chains and records Branch edges
+//! - `Axon::then_axon` embeds a reusable flow as a Subgraph node
+//! - JSON serialization of the nested Schematic
 
 use anyhow::Result;
 use async_trait::async_trait;
 use ranvier_core::prelude::*;
-use ranvier_core::schematic::{Edge, EdgeType, Node, NodeKind};
 use ranvier_runtime::Axon;
 use serde::{Deserialize, Serialize};
 
@@ -54,8 +53,7 @@ impl Transition<LoginInput, UserContext> for Authenticate {
                 role: "admin".to_string(),
             })
         } else {
-            // In a real app, this would be a Branch or Emit
-            // For now, let's simulate a Branch return
+            // Routed to the "LoginFailed" arm registered with `branch` below.
             Outcome::Branch(
                 "LoginFailed".to_string(),
                 Some(serde_json::json!("Invalid credentials")),
@@ -64,6 +62,43 @@ impl Transition<LoginInput, UserContext> for Authenticate {
     }
 }
 
+#[derive(Clone)]
+struct LoginFailedHandler;
+
+#[async_trait]
+impl Transition<String, UserContext> for LoginFailedHandler {
+    type Error = String;
+    type Resources = ();
+
+    async fn run(
+        &self,
+        reason: String,
+        _resources: &Self::Resources,
+        _bus: &mut Bus,
+    ) -> Outcome<UserContext, Self::Error> {
+        Outcome::Fault(format!("login failed: {reason}"))
+    }
+}
+
+#[derive(Clone)]
+struct RecordLogin;
+
+#[async_trait]
+impl Transition<UserContext, UserContext> for RecordLogin {
+    type Error = String;
+    type Resources = ();
+
+    async fn run(
+        &self,
+        user: UserContext,
+        _resources: &Self::Resources,
+        _bus: &mut Bus,
+    ) -> Outcome<UserContext, Self::Error> {
+        println!("audit: {} logged in as {}", user.user_id, user.role);
+        Outcome::Next(user)
+    }
+}
+
 // --- Main ---
 
 #[tokio::main]
@@ -74,74 +109,25 @@ async fn main() -> Result<()> {
         username: "guest".to_string(),
     };
 
-    // 1. Build the Linear Axon
-    // Axon currently builds the 'Happy Path' automatically
-    let mut axon = Axon::<LoginInput, LoginInput, String>::new("StartFlow").then(Authenticate);
-
-    // 2. Start Manual Schematic Enhancement
-    // Since Axon's Builder doesn't yet support auto-extraction of Branches,
-    // we manually inject the structural knowledge here to demonstrate the Schematic's capability.
-
-    // Find the 'Authenticate' node (it's the last one)
-    let auth_node_id = axon.schematic.nodes.last().unwrap().id.clone();
-
-    // Create a 'LoginFailed' Node (Synapse/Branch target)
-    let fail_node_id = uuid::Uuid::new_v4().to_string();
-    let fail_node = Node {
-        id: fail_node_id.clone(),
-        kind: NodeKind::Egress,
-        label: "LoginFailedHandler".to_string(),
-        description: Some("Handles failed login attempts".to_string()),
-        input_type: "String".to_string(), // Error message
-        output_type: "Void".to_string(),
-        resource_type: "()".to_string(),
-        metadata: Default::default(),
-        bus_capability: None,
-        source_location: None,
-        position: None,
-        compensation_node_id: None,
-        input_schema: None,
-        output_schema: None,
-        item_type: None,
-        terminal: None,
-    };
-
-    // Create a Branch Edge
-    let fail_edge = Edge {
-        from: auth_node_id,
-        to: fail_node_id,
-        kind: EdgeType::Branch("LoginFailed".to_string()),
-        label: Some("On Failure".to_string()),
-    };
-
-    // Create a Subgraph Node (to demonstrate nesting)
-    let subgraph_id = uuid::Uuid::new_v4().to_string();
-    let sub_schematic = ranvier_core::schematic::Schematic::new("AuditSubFlow");
-    let subgraph_node = Node {
-        id: subgraph_id.clone(),
-        kind: NodeKind::Subgraph(Box::new(sub_schematic)),
-        label: "AuditProcess".to_string(),
-        description: Some("Nested sub-flow for auditing".to_string()),
-        input_type: "UserContext".to_string(),
-        output_type: "Void".to_string(),
-        resource_type: "()".to_string(),
-        metadata: Default::default(),
-        bus_capability: None,
-        source_location: None,
-        position: None,
-        compensation_node_id: None,
-        input_schema: None,
-        output_schema: None,
-        item_type: None,
-        terminal: None,
-    };
-
-    // Add Subgraph to the main graph (conceptually unconnected for now, just to show JSON structure)
-    axon.schematic.nodes.push(fail_node);
-    axon.schematic.edges.push(fail_edge);
-    axon.schematic.nodes.push(subgraph_node);
+    // 1. Build the Axon. `branch` routes `Outcome::Branch("LoginFailed", ..)`
+    // into its own sub-chain and records the Branch edge in the Schematic.
+    // The audit sub-flow is an ordinary Axon, embedded below as a Subgraph.
+    let audit = Axon::<UserContext, UserContext, String>::new("AuditSubFlow").then(RecordLogin);
+
+    let axon = Axon::<LoginInput, LoginInput, String>::new("StartFlow")
+        .then(Authenticate)
+        .branch(|b| {
+            b.on(
+                "LoginFailed",
+                Axon::<String, String, String>::new("LoginFailedFlow").then(LoginFailedHandler),
+            )
+            .otherwise(Axon::<UserContext, UserContext, String>::new(
+                "Authenticated",
+            ))
+        })
+        .then_axon(audit);
 
-    // 3. Export JSON
+    // 2. Export JSON
     let json = serde_json::to_string_pretty(&axon.schematic)?;
     println!("{}", json);
 
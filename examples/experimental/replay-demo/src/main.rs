@@ -1,5 +1,10 @@
-use ranvier_core::timeline::{Timeline, TimelineEvent};
+use ranvier_core::timeline::{Timeline, TimelineEvent, Timestamp};
 use ranvier_runtime::replay::ReplayEngine;
+use std::time::Duration;
+
+fn offset_ms(timestamp: Timestamp, start: Timestamp) -> f64 {
+    timestamp.duration_since(start).as_secs_f64() * 1000.0
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -7,32 +12,37 @@ async fn main() -> anyhow::Result<()> {
 
     // 1. Construct a mock Timeline (simulating OTLP Trace)
     let mut timeline = Timeline::new();
-    let start_time = 1000;
+    let start_time = Timestamp::from_millis(1_700_000_000_000);
+    let at = |offset_ms: u64| start_time.saturating_add(Duration::from_millis(offset_ms));
 
     // Node A (Enter -> Exit)
     timeline.push(TimelineEvent::NodeEnter {
         node_id: "node-a".to_string(),
         node_label: "Auth".to_string(),
         timestamp: start_time,
+        trace: None,
     });
     timeline.push(TimelineEvent::NodeExit {
         node_id: "node-a".to_string(),
         outcome_type: "Next".to_string(),
         duration_ms: 15,
-        timestamp: start_time + 15,
+        timestamp: at(15),
+        trace: None,
     });
 
     // Node B (Enter -> Exit)
     timeline.push(TimelineEvent::NodeEnter {
         node_id: "node-b".to_string(),
         node_label: "FetchProfile".to_string(),
-        timestamp: start_time + 20,
+        timestamp: at(20),
+        trace: None,
     });
     timeline.push(TimelineEvent::NodeExit {
         node_id: "node-b".to_string(),
         outcome_type: "Next".to_string(),
         duration_ms: 50,
-        timestamp: start_time + 70,
+        timestamp: at(70),
+        trace: None,
     });
 
     // 2. Initialize ReplayEngine
@@ -49,8 +59,8 @@ async fn main() -> anyhow::Result<()> {
                     ..
                 } => {
                     println!(
-                        "[{:>5}] 🟢 Enter Node: {}",
-                        timestamp - start_time,
+                        "[+{:>7.3}ms] 🟢 Enter Node: {}",
+                        offset_ms(timestamp, start_time),
                         node_label
                     );
                 }
@@ -61,8 +71,8 @@ async fn main() -> anyhow::Result<()> {
                     ..
                 } => {
                     println!(
-                        "[{:>5}] 🔴 Exit Node : <unknown> ({}ms) -> Outcome: {}",
-                        timestamp - start_time,
+                        "[+{:>7.3}ms] 🔴 Exit Node : <unknown> ({}ms) -> Outcome: {}",
+                        offset_ms(timestamp, start_time),
                         duration_ms,
                         outcome_type
                     );
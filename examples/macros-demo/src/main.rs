@@ -121,7 +121,7 @@ async fn apply_discount(
     _res: &(),
     bus: &mut Bus,
 ) -> Outcome<ValidatedOrder, String> {
-    let discount = bus.get::<f64>().map(|d| *d).unwrap_or(0.0);
+    let discount = bus.get::<f64>().copied().unwrap_or(0.0);
     Outcome::next(ValidatedOrder {
         amount: input.amount * (1.0 - discount),
         ..input
@@ -298,14 +298,11 @@ async fn main() -> anyhow::Result<()> {
             .execute(tenant.to_string(), &store, &mut bus)
             .await;
 
-        match &result {
-            Outcome::Next(report) => {
-                println!(
-                    "    {}: {} item(s) {:?}",
-                    report.tenant, report.item_count, report.items
-                );
-            }
-            _ => {}
+        if let Outcome::Next(report) = &result {
+            println!(
+                "    {}: {} item(s) {:?}",
+                report.tenant, report.item_count, report.items
+            );
         }
     }
 
@@ -120,6 +120,8 @@ async fn run_order_flow(
         Outcome::Fault(e) => println!("\n\x1b[31m[FAULT] Error: {}\x1b[0m", e),
         Outcome::Jump(id, _) => println!("\n\x1b[33m[JUMP] {}\x1b[0m", id),
         Outcome::Emit(event, _) => println!("\n\x1b[34m[EMIT] {}\x1b[0m", event),
+        Outcome::Suspend(token, _) => println!("\n\x1b[33m[SUSPEND] {}\x1b[0m", token),
+        Outcome::Retry { after } => println!("\n\x1b[33m[RETRY] after {:?}\x1b[0m", after),
     };
 
     println!();
@@ -79,6 +79,7 @@ struct ActuatorCommand {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
 enum Action {
     IrrigateHigh,  // Full irrigation
     IrrigateLow,   // Light irrigation
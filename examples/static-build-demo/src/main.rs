@@ -6,6 +6,9 @@
 //! - Normal: `cargo run -p static-build-demo`
 //! - Static build: `cargo run -p static-build-demo -- --static-build --output-dir ./dist`
 //! - Via CLI: `ranvier build static --example static-build-demo`
+//!
+//! The static build also writes `ranvier-hydrate/`, a TypeScript client with
+//! typed accessors (`getLandingPage()`, ...) for the generated states.
 
 #![allow(deprecated)]
 use anyhow::Result;
@@ -13,6 +16,7 @@ use chrono::{DateTime, Utc};
 use http::Request;
 use ranvier_core::Never;
 use ranvier_core::bus::Bus;
+use ranvier_core::hydrate::HydrateGenerator;
 use ranvier_core::outcome::Outcome;
 use ranvier_core::static_gen::{StaticAxon, StaticManifest, write_json_file};
 use serde::{Deserialize, Serialize};
@@ -104,6 +108,10 @@ impl StaticAxon for LandingPageAxon {
         "landing_page"
     }
 
+    fn description(&self) -> Option<String> {
+        Some("Hero copy and feature highlights for the landing page.".to_string())
+    }
+
     fn generate(&self, _bus: &mut Bus) -> Result<Outcome<LandingState, Self::Error>> {
         let state = LandingState {
             title: "Welcome to Ranvier".to_string(),
@@ -331,6 +339,14 @@ where
         self.inner.name()
     }
 
+    fn output_type(&self) -> &'static str {
+        self.inner.output_type()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.inner.description()
+    }
+
     fn generate(&self, bus: &mut Bus) -> Result<Outcome<Self::Output, Self::Error>> {
         let outcome = self.inner.generate(bus)?;
         match outcome {
@@ -370,7 +386,10 @@ fn run_static_build(output_dir: &str) -> Result<()> {
                 write_json_file(&file_path, &value, true)?;
                 println!("     ✅ Wrote: {}", file_name);
 
-                manifest.add_state(name, file_name);
+                manifest.add_typed_state(name, file_name, axon.output_type());
+                if let Some(entry) = manifest.states.last_mut() {
+                    entry.description = axon.description();
+                }
             }
             Ok(Outcome::Fault(e)) => {
                 eprintln!("     ❌ Fault: {:?}", e);
@@ -389,6 +408,10 @@ fn run_static_build(output_dir: &str) -> Result<()> {
     write_json_file(&manifest_path, &manifest, true)?;
     println!("   📋 Wrote: manifest.json");
 
+    // Typed TypeScript client for the frontend
+    HydrateGenerator::new(manifest).write_package(&out_path.join("ranvier-hydrate"))?;
+    println!("   🧩 Wrote: ranvier-hydrate/index.ts");
+
     println!("✅ Static build complete!");
     println!("   📁 Output: {}/", out_path.display());
 
@@ -412,11 +435,9 @@ fn main() -> Result<()> {
             "--static-build" => {
                 static_build = true;
             }
-            "--output-dir" => {
-                if i + 1 < args.len() {
-                    output_dir = &args[i + 1];
-                    i += 1;
-                }
+            "--output-dir" if i + 1 < args.len() => {
+                output_dir = &args[i + 1];
+                i += 1;
             }
             _ => {}
         }
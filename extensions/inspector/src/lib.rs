@@ -5,6 +5,7 @@ pub mod lineage;
 pub mod metrics;
 pub mod payload;
 pub mod prometheus;
+pub mod public_projection;
 pub mod relay;
 pub mod routes;
 pub mod schema;
@@ -27,14 +28,23 @@ use axum::{
     routing::get,
 };
 use ranvier_core::cancellation::{CancellationReason, CancellationToken};
+use ranvier_core::capture::CapturedPayload;
+use ranvier_core::circuit_breaker::{CircuitBreakerStateReader, CircuitState};
 use ranvier_core::config::ResolvedRuntimeConfig;
 use ranvier_core::event::DlqReader;
 use ranvier_core::prelude::DebugControl;
+use ranvier_core::rate_limit::RateLimitStateReader;
+use ranvier_core::redaction::RedactionRules;
 use ranvier_core::runtime_policy::{
     PolicyComponent, PolicyField, PolicyObservation, PolicyValue, RuntimeProfile,
     StartupPolicyCode, StartupPolicyContribution, StartupPolicyError, StartupPolicyProvider,
 };
-use ranvier_core::schematic::{NodeKind, Schematic};
+use ranvier_core::schematic::{NodeKind, SchemaVersionMismatch, Schematic};
+use ranvier_core::schematic_registry::{SchematicBundle, SchematicRegistry};
+use ranvier_core::timeline::stats::TimelineStats;
+use ranvier_core::timeline::tail::TimelineTail;
+use ranvier_core::timeline::{Timeline, TimelineEvent, Timestamp};
+use ranvier_core::timeline_store::{TimelineQuery, TimelineRunQuery, TimelineStore};
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
@@ -289,7 +299,8 @@ impl RedactionMode {
 #[derive(Clone, Debug)]
 struct TelemetryRedactionPolicy {
     mode_override: Option<RedactionMode>,
-    sensitive_patterns: Vec<String>,
+    /// Sensitive field patterns, shared with timeline redaction.
+    fields: RedactionRules,
     allow_keys: HashSet<String>,
     valid: bool,
 }
@@ -298,7 +309,7 @@ impl Default for TelemetryRedactionPolicy {
     fn default() -> Self {
         Self {
             mode_override: None,
-            sensitive_patterns: default_sensitive_patterns(),
+            fields: RedactionRules::default(),
             allow_keys: HashSet::new(),
             valid: true,
         }
@@ -318,16 +329,9 @@ impl TelemetryRedactionPolicy {
             Err(std::env::VarError::NotUnicode(_)) => policy.mark_invalid_mode(),
         }
 
-        match std::env::var("RANVIER_TELEMETRY_REDACT_KEYS") {
-            Ok(extra) => {
-                for key in parse_csv_lower(&extra) {
-                    if !policy.sensitive_patterns.contains(&key) {
-                        policy.sensitive_patterns.push(key);
-                    }
-                }
-            }
-            Err(std::env::VarError::NotPresent) => {}
-            Err(std::env::VarError::NotUnicode(_)) => policy.mark_invalid_csv(),
+        match RedactionRules::try_from_env() {
+            Ok(fields) => policy.fields = fields,
+            Err(_) => policy.mark_invalid_csv(),
         }
 
         match std::env::var("RANVIER_TELEMETRY_ALLOW_KEYS") {
@@ -363,10 +367,7 @@ impl TelemetryRedactionPolicy {
     }
 
     fn is_sensitive_key(&self, key: &str) -> bool {
-        let lowered = key.to_ascii_lowercase();
-        self.sensitive_patterns
-            .iter()
-            .any(|pattern| lowered.contains(pattern))
+        self.fields.is_sensitive(key)
     }
 }
 
@@ -421,7 +422,13 @@ pub struct Inspector {
     allow_unauthenticated: bool,
     trace_registry_config: TraceRegistryConfig,
     trace_store: Option<Arc<dyn trace_store::TraceStore>>,
+    timeline_store: Option<Arc<dyn TimelineStore>>,
+    timeline_redaction: Option<RedactionRules>,
+    timeline_tail: Option<TimelineTail>,
     alert_dispatcher: Option<Arc<alert::AlertDispatcher>>,
+    rate_limit_readers: Vec<Arc<dyn RateLimitStateReader>>,
+    circuit_breaker_readers: Vec<Arc<dyn CircuitBreakerStateReader>>,
+    bus_tracking: bool,
 }
 
 impl Inspector {
@@ -455,7 +462,13 @@ impl Inspector {
             allow_unauthenticated: false,
             trace_registry_config: TraceRegistryConfig::default(),
             trace_store: None,
+            timeline_store: None,
+            timeline_redaction: None,
+            timeline_tail: None,
             alert_dispatcher: None,
+            rate_limit_readers: Vec::new(),
+            circuit_breaker_readers: Vec::new(),
+            bus_tracking: false,
         }
     }
 
@@ -497,6 +510,36 @@ impl Inspector {
         self
     }
 
+    /// Expose a rate limiter's live state at `/limits`.
+    ///
+    /// May be called repeatedly; each reader is reported as one entry.
+    pub fn with_rate_limit_reader(mut self, reader: Arc<dyn RateLimitStateReader>) -> Self {
+        self.rate_limit_readers.push(reader);
+        self
+    }
+
+    /// Expose a circuit breaker's live state at `/breakers`.
+    ///
+    /// May be called repeatedly; each reader is reported as one entry.
+    pub fn with_circuit_breaker_reader(
+        mut self,
+        reader: Arc<dyn CircuitBreakerStateReader>,
+    ) -> Self {
+        self.circuit_breaker_readers.push(reader);
+        self
+    }
+
+    /// Report the resources held by live Buses at `/resources`.
+    ///
+    /// Tracking is process-global: when the Inspector starts serving it turns
+    /// on [`bus_registry`](ranvier_core::bus_registry) tracking for every Bus
+    /// the process creates from then on, and leaves it on after shutdown.
+    /// Without it the endpoint answers with `tracking: false` and no entries.
+    pub fn with_bus_tracking(mut self) -> Self {
+        self.bus_tracking = true;
+        self
+    }
+
     /// Attach a read-only public projection artifact.
     pub fn with_public_projection(self, projection: Value) -> Self {
         if let Ok(mut slot) = self.public_projection.lock() {
@@ -505,6 +548,28 @@ impl Inspector {
         self
     }
 
+    /// Attach a public projection generated from the live metrics registry.
+    ///
+    /// See [`public_projection::public_projection_from_metrics`] for the
+    /// coarsening `options` apply.
+    pub fn with_public_projection_from_metrics(
+        self,
+        options: &public_projection::PublicProjectionOptions,
+    ) -> Self {
+        let snapshots = metrics::snapshot_all();
+        let projection = match self.schematic.lock() {
+            Ok(schematic) => {
+                public_projection::public_projection_from_metrics(&schematic, &snapshots, options)
+            }
+            Err(poisoned) => public_projection::public_projection_from_metrics(
+                &poisoned.into_inner(),
+                &snapshots,
+                options,
+            ),
+        };
+        self.with_public_projection(projection)
+    }
+
     /// Attach a read-only internal projection artifact.
     pub fn with_internal_projection(self, projection: Value) -> Self {
         if let Ok(mut slot) = self.internal_projection.lock() {
@@ -513,6 +578,44 @@ impl Inspector {
         self
     }
 
+    /// Attach an internal projection built from a recorded execution Timeline.
+    ///
+    /// See [`internal_projection_from_timeline`].
+    pub fn with_internal_timeline(self, trace_id: &str, timeline: &Timeline) -> Self {
+        let projection = match self.schematic.lock() {
+            Ok(schematic) => internal_projection_from_timeline(&schematic, trace_id, timeline),
+            Err(poisoned) => {
+                internal_projection_from_timeline(&poisoned.into_inner(), trace_id, timeline)
+            }
+        };
+        self.with_internal_projection(projection)
+    }
+
+    /// Attach public and internal projections aggregated over recorded
+    /// timelines of this circuit.
+    ///
+    /// See [`public_projection::public_projection_from_timelines`] and
+    /// [`internal_projection_from_stats`].
+    pub fn with_projections_from_timelines<'a>(
+        self,
+        timelines: impl IntoIterator<Item = &'a Timeline>,
+        options: &public_projection::PublicProjectionOptions,
+    ) -> Self {
+        let stats = TimelineStats::from_timelines(timelines);
+        let (public, internal) = {
+            let schematic = match self.schematic.lock() {
+                Ok(schematic) => schematic,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            (
+                public_projection::public_projection_from_timelines(&schematic, &stats, options),
+                internal_projection_from_stats(&schematic, &stats),
+            )
+        };
+        self.with_public_projection(public)
+            .with_internal_projection(internal)
+    }
+
     /// Load optional projection artifacts from environment variables:
     /// - `RANVIER_TRACE_PUBLIC_PATH`
     /// - `RANVIER_TRACE_INTERNAL_PATH`
@@ -524,7 +627,10 @@ impl Inspector {
         if let Ok(path) = std::env::var("RANVIER_TRACE_PUBLIC_PATH") {
             inspector.public_projection_path = Some(path.clone());
             match read_projection_file(&path) {
-                Ok(v) => inspector = inspector.with_public_projection(v),
+                Ok(v) => {
+                    inspector.warn_on_projection_skew(&path, &v);
+                    inspector = inspector.with_public_projection(v)
+                }
                 Err(e) => tracing::warn!("Failed to load public projection from {}: {}", path, e),
             }
         }
@@ -532,7 +638,10 @@ impl Inspector {
         if let Ok(path) = std::env::var("RANVIER_TRACE_INTERNAL_PATH") {
             inspector.internal_projection_path = Some(path.clone());
             match read_projection_file(&path) {
-                Ok(v) => inspector = inspector.with_internal_projection(v),
+                Ok(v) => {
+                    inspector.warn_on_projection_skew(&path, &v);
+                    inspector = inspector.with_internal_projection(v)
+                }
                 Err(e) => tracing::warn!("Failed to load internal projection from {}: {}", path, e),
             }
         }
@@ -540,6 +649,19 @@ impl Inspector {
         inspector
     }
 
+    fn warn_on_projection_skew(&self, path: &str, projection: &Value) {
+        let Ok(schematic) = self.schematic.lock() else {
+            return;
+        };
+        if let Some(warning) = projection_skew(projection, &schematic) {
+            tracing::warn!(
+                path = %path,
+                reason = %warning["reason"],
+                "Projection file was produced by a different build of this circuit (stale_projection)"
+            );
+        }
+    }
+
     /// Configure inspector route surface using `RANVIER_MODE=dev|prod`.
     ///
     /// - `dev` (default): expose `/trace/internal`, `/events`, `/quick-view`
@@ -625,6 +747,18 @@ impl Inspector {
         self
     }
 
+    /// Redact the field patterns of `rules` from projections and from the
+    /// timelines served under `/api/v1/timelines`.
+    ///
+    /// Timelines recorded with the same rules on the Bus are already redacted;
+    /// this covers runs stored before the rules were in place.
+    pub fn with_redaction_rules(mut self, rules: RedactionRules) -> Self {
+        let fields = std::mem::replace(&mut self.redaction_policy.fields, RedactionRules::empty());
+        self.redaction_policy.fields = fields.merge(&rules);
+        self.timeline_redaction = Some(rules);
+        self
+    }
+
     /// Configure Bearer token authentication for production deployments.
     pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
         self.bearer_auth = auth::BearerAuth {
@@ -658,6 +792,22 @@ impl Inspector {
         self
     }
 
+    /// Serve the execution history recorded in a timeline store under
+    /// `/api/v1/timelines`.
+    pub fn with_timeline_store(mut self, store: Arc<dyn TimelineStore>) -> Self {
+        self.timeline_store = Some(store);
+        self
+    }
+
+    /// Forward the events of a [`TimelineTail`] to `/events` subscribers as
+    /// `timeline_event` messages while executions run.
+    ///
+    /// Insert the same tail into the Bus of the executions to follow.
+    pub fn with_timeline_tail(mut self, tail: TimelineTail) -> Self {
+        self.timeline_tail = Some(tail);
+        self
+    }
+
     /// Configure the in-memory trace registry ring buffer.
     ///
     /// Controls the maximum number of active and recent traces kept in each
@@ -791,6 +941,9 @@ impl Inspector {
         let surface_policy = self.surface_policy;
         let bearer_auth = self.bearer_auth.clone();
         let bearer_auth_enabled = bearer_auth.is_enabled();
+        if self.bus_tracking {
+            ranvier_core::bus_registry::enable_tracking();
+        }
 
         // Auth policy enforcement: warn in release builds if no bearer token configured
         if !self.bearer_auth.is_enabled() && !self.allow_unauthenticated {
@@ -808,6 +961,7 @@ impl Inspector {
             );
         }
 
+        let tail_redaction = self.timeline_redaction.clone();
         let state = InspectorState {
             schematic: self.schematic.clone(),
             public_projection: self.public_projection.clone(),
@@ -824,12 +978,17 @@ impl Inspector {
             bearer_auth: self.bearer_auth,
             allow_unauthenticated: self.allow_unauthenticated,
             trace_store: self.trace_store,
+            timeline_store: self.timeline_store,
+            timeline_redaction: self.timeline_redaction,
             alert_dispatcher: self.alert_dispatcher,
+            rate_limit_readers: self.rate_limit_readers,
+            circuit_breaker_readers: self.circuit_breaker_readers,
         };
 
         let mut app = Router::new()
             .route("/healthz", get(get_healthz))
             .route("/schematic", get(get_schematic))
+            .route("/schematics", get(get_schematics))
             .route("/trace/public", get(get_public_projection))
             .route("/metrics", get(prometheus_metrics_handler));
 
@@ -868,6 +1027,9 @@ impl Inspector {
                     axum::routing::delete(api_delete_breakpoint).patch(api_patch_breakpoint),
                 )
                 .route("/api/v1/stalls", get(api_get_stalls))
+                .route("/limits", get(api_get_limits))
+                .route("/breakers", get(api_get_breakers))
+                .route("/resources", get(api_get_resources))
                 .route("/api/v1/routes", get(api_get_routes))
                 .route(
                     "/api/v1/routes/schema",
@@ -880,7 +1042,11 @@ impl Inspector {
                 .route("/api/v1/relay", axum::routing::post(api_post_relay))
                 .route("/api/v1/traces/stored", get(api_get_stored_traces))
                 .route("/api/v1/lineage/:trace_id", get(api_get_lineage))
-                .route("/api/v1/traces/diff", get(api_get_trace_diff));
+                .route("/api/v1/traces/diff", get(api_get_trace_diff))
+                .route("/api/v1/timelines", get(api_get_timeline_runs))
+                .route("/api/v1/timelines/diff", get(api_get_timeline_diff))
+                .route("/api/v1/timelines/stats", get(api_get_timeline_stats))
+                .route("/api/v1/timelines/:run_id", get(api_get_timeline_run));
         }
 
         if surface_policy.expose_events {
@@ -963,6 +1129,18 @@ impl Inspector {
             None
         };
 
+        // Live timeline events share the `/events` channel with metrics.
+        let tail_task = self
+            .timeline_tail
+            .filter(|_| surface_policy.expose_events)
+            .map(|tail| {
+                tokio::spawn(forward_timeline_tail(
+                    tail,
+                    tail_redaction,
+                    lifecycle_token.clone(),
+                ))
+            });
+
         if let Some(token) = lifecycle_token {
             let shutdown_token = token.clone();
             let result = axum::serve(listener, app)
@@ -976,11 +1154,17 @@ impl Inspector {
             {
                 tracing::warn!(error = %error, "Inspector metrics task join failed");
             }
+            if let Some(task) = tail_task
+                && let Err(error) = task.await
+            {
+                tracing::warn!(error = %error, "Inspector timeline tail task join failed");
+            }
             result
         } else {
             // Preserve the existing detached metrics-loop behavior for the
             // compatibility entrypoint.
             drop(metrics_task);
+            drop(tail_task);
             axum::serve(listener, app).await
         }
     }
@@ -1221,6 +1405,9 @@ async fn require_bearer_auth(
 fn default_public_projection(schematic: &Schematic) -> Value {
     serde_json::json!({
         "service_name": schematic.name,
+        "circuit_id": schematic.id,
+        "schematic_hash": schematic.structural_hash(),
+        "schema_version": schematic.schema_version,
         "window_start": "1970-01-01T00:00:00Z",
         "window_end": "1970-01-01T00:00:00Z",
         "overall_status": "operational",
@@ -1241,7 +1428,7 @@ fn default_internal_projection(schematic: &Schematic) -> Value {
         .nodes
         .iter()
         .map(|n| {
-            serde_json::json!({
+            let mut entry = serde_json::json!({
                 "node_id": n.id,
                 "label": n.label,
                 "kind": node_kind_name(&n.kind),
@@ -1252,16 +1439,24 @@ fn default_internal_projection(schematic: &Schematic) -> Value {
                 "branch_id": Value::Null,
                 "error_code": Value::Null,
                 "error_category": Value::Null
-            })
+            });
+            if !n.metadata.annotations.is_empty() {
+                entry["annotations"] =
+                    serde_json::to_value(&n.metadata.annotations).unwrap_or(Value::Null);
+            }
+            entry
         })
         .collect::<Vec<_>>();
 
     serde_json::json!({
         "trace_id": "bootstrap",
         "circuit_id": schematic.id,
+        "schematic_hash": schematic.structural_hash(),
+        "schema_version": schematic.schema_version,
         "started_at": "1970-01-01T00:00:00Z",
         "finished_at": "1970-01-01T00:00:00Z",
         "nodes": nodes,
+        "fault_chain": Value::Null,
         "summary": {
             "node_count": schematic.nodes.len(),
             "fault_count": 0,
@@ -1270,6 +1465,209 @@ fn default_internal_projection(schematic: &Schematic) -> Value {
     })
 }
 
+/// Build an internal projection for one execution from its recorded Timeline.
+///
+/// Besides per-node timing and outcomes, the projection carries the fault
+/// cause tree: each faulted node gets a `fault` entry and the trace root gets
+/// `fault_chain`, the cause tree of the last fault (source node, error, retry
+/// history and underlying faults).
+///
+/// Nodes with [`NodeAnnotations`](ranvier_core::metadata::NodeAnnotations)
+/// carry them as `annotations`, so alerts can be routed to the owner; nodes
+/// with an `sla_ms` also get `sla_breached`, counted in `summary.sla_breaches`.
+pub fn internal_projection_from_timeline(
+    schematic: &Schematic,
+    trace_id: &str,
+    timeline: &Timeline,
+) -> Value {
+    let mut nodes: Vec<serde_json::Map<String, Value>> = Vec::new();
+    let mut open: HashMap<&str, (usize, Timestamp)> = HashMap::new();
+    let mut fault_count = 0;
+    let mut branch_count = 0;
+    let mut sla_breaches = 0;
+
+    for event in &timeline.events {
+        match event {
+            TimelineEvent::NodeEnter {
+                node_id,
+                node_label,
+                timestamp,
+                ..
+            } => {
+                let node = schematic.nodes.iter().find(|node| node.id == *node_id);
+                let kind = node
+                    .map(|node| node_kind_name(&node.kind))
+                    .unwrap_or("Atom");
+                let mut entry = serde_json::json!({
+                    "node_id": node_id,
+                    "label": node_label,
+                    "kind": kind,
+                    "entered_at": timestamp.to_string(),
+                    "exited_at": Value::Null,
+                    "latency_ms": 0.0,
+                    "outcome_type": Value::Null,
+                    "branch_id": Value::Null,
+                    "error_code": Value::Null,
+                    "error_category": Value::Null,
+                    "fault": Value::Null
+                });
+                if let Some(node) = node.filter(|node| !node.metadata.annotations.is_empty()) {
+                    entry["annotations"] =
+                        serde_json::to_value(&node.metadata.annotations).unwrap_or(Value::Null);
+                }
+                if let Value::Object(map) = entry {
+                    open.insert(node_id, (nodes.len(), *timestamp));
+                    nodes.push(map);
+                }
+            }
+            TimelineEvent::NodeExit {
+                node_id,
+                outcome_type,
+                timestamp,
+                ..
+            } => {
+                if outcome_type == "Fault" {
+                    fault_count += 1;
+                }
+                let Some(&(idx, entered)) = open.get(node_id.as_str()) else {
+                    continue;
+                };
+                let node = &mut nodes[idx];
+                let latency_ms = timestamp.duration_since(entered).as_secs_f64() * 1000.0;
+                node.insert("exited_at".into(), Value::from(timestamp.to_string()));
+                node.insert("latency_ms".into(), Value::from(latency_ms));
+                if let Some(annotations) = schematic
+                    .nodes
+                    .iter()
+                    .find(|n| n.id == *node_id)
+                    .map(|n| &n.metadata.annotations)
+                    .filter(|annotations| annotations.sla_ms.is_some())
+                {
+                    let breached = annotations.sla_breached(latency_ms);
+                    sla_breaches += usize::from(breached);
+                    node.insert("sla_breached".into(), Value::from(breached));
+                }
+                node.insert("outcome_type".into(), Value::from(outcome_type.clone()));
+            }
+            TimelineEvent::Branchtaken { branch_id, .. } => {
+                branch_count += 1;
+                if let Some(node) = nodes.last_mut() {
+                    node.insert("branch_id".into(), Value::from(branch_id.clone()));
+                }
+            }
+            TimelineEvent::FaultRecorded { node_id, cause, .. } => {
+                if let Some(&(idx, _)) = open.get(node_id.as_str()) {
+                    if let Some(category) = &cause.category {
+                        nodes[idx].insert("error_category".into(), Value::from(category.clone()));
+                    }
+                    nodes[idx].insert(
+                        "fault".into(),
+                        serde_json::to_value(cause).unwrap_or(Value::Null),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let started_at = timeline.events.first().map(|e| e.timestamp().to_string());
+    let finished_at = timeline.events.last().map(|e| e.timestamp().to_string());
+    let fault_chain = timeline
+        .latest_fault()
+        .and_then(|cause| serde_json::to_value(cause).ok())
+        .unwrap_or(Value::Null);
+
+    serde_json::json!({
+        "trace_id": trace_id,
+        "circuit_id": schematic.id,
+        "schematic_hash": schematic.structural_hash(),
+        "schema_version": schematic.schema_version,
+        "started_at": started_at,
+        "finished_at": finished_at,
+        "nodes": nodes,
+        "fault_chain": fault_chain,
+        "summary": {
+            "node_count": nodes.len(),
+            "fault_count": fault_count,
+            "branch_count": branch_count,
+            "sla_breaches": sla_breaches
+        }
+    })
+}
+
+/// Build an internal projection aggregated over many recorded executions.
+///
+/// The projection keeps the shape of [`internal_projection_from_timeline`],
+/// under the trace id `aggregate`: each node's `latency_ms` is its p50 and
+/// `outcome_type` its most frequent outcome. Nodes add `visits`,
+/// `error_rate`, `outcomes` and `p50`/`p95`/`p99_latency_ms`; branch
+/// frequencies (share of runs taking each branch) are listed under
+/// `branches`.
+pub fn internal_projection_from_stats(schematic: &Schematic, stats: &TimelineStats) -> Value {
+    let nodes: Vec<Value> = stats
+        .nodes
+        .iter()
+        .map(|(node_id, node)| {
+            let kind = schematic
+                .nodes
+                .iter()
+                .find(|n| n.id == *node_id)
+                .map(|n| node_kind_name(&n.kind))
+                .unwrap_or("Atom");
+            let outcome_type = node
+                .outcomes
+                .iter()
+                .max_by_key(|(_, count)| **count)
+                .map(|(outcome, _)| outcome.clone());
+            serde_json::json!({
+                "node_id": node_id,
+                "label": node.node_label,
+                "kind": kind,
+                "latency_ms": node.p50(),
+                "outcome_type": outcome_type,
+                "visits": node.visits,
+                "error_count": node.error_count,
+                "error_rate": node.error_rate(),
+                "outcomes": node.outcomes,
+                "p50_latency_ms": node.p50(),
+                "p95_latency_ms": node.p95(),
+                "p99_latency_ms": node.p99(),
+                "avg_latency_ms": node.avg_ms(),
+            })
+        })
+        .collect();
+    let branches: Vec<Value> = stats
+        .branches
+        .iter()
+        .map(|(branch_id, count)| {
+            serde_json::json!({
+                "branch_id": branch_id,
+                "count": count,
+                "frequency": stats.branch_frequency(branch_id),
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "trace_id": "aggregate",
+        "circuit_id": schematic.id,
+        "schematic_hash": schematic.structural_hash(),
+        "schema_version": schematic.schema_version,
+        "started_at": stats.first_event.map(|t| t.to_string()),
+        "finished_at": stats.last_event.map(|t| t.to_string()),
+        "nodes": nodes,
+        "fault_chain": Value::Null,
+        "branches": branches,
+        "summary": {
+            "node_count": stats.nodes.len(),
+            "fault_count": stats.nodes.values().map(|node| node.error_count).sum::<u64>(),
+            "branch_count": stats.branches.values().sum::<u64>(),
+            "run_count": stats.runs,
+            "error_rate": stats.error_rate(),
+        }
+    })
+}
+
 fn node_kind_name(kind: &NodeKind) -> &'static str {
     match kind {
         NodeKind::Ingress => "Ingress",
@@ -1280,6 +1678,7 @@ fn node_kind_name(kind: &NodeKind) -> &'static str {
         NodeKind::FanOut => "FanOut",
         NodeKind::FanIn => "FanIn",
         NodeKind::StreamingTransition => "StreamingTransition",
+        NodeKind::Tap => "Tap",
     }
 }
 
@@ -1288,20 +1687,96 @@ fn read_projection_file(path: &str) -> Result<Value, String> {
     serde_json::from_str::<Value>(&content).map_err(|e| e.to_string())
 }
 
-fn default_sensitive_patterns() -> Vec<String> {
-    vec![
-        "password".to_string(),
-        "secret".to_string(),
-        "token".to_string(),
-        "authorization".to_string(),
-        "cookie".to_string(),
-        "session".to_string(),
-        "api_key".to_string(),
-        "credit_card".to_string(),
-        "ssn".to_string(),
-        "email".to_string(),
-        "phone".to_string(),
-    ]
+/// Compare the build identity embedded in a projection with the live schematic.
+///
+/// A `schema_version` of another major version is reported first, since such
+/// a projection cannot be read reliably at all. Otherwise `schematic_hash` is
+/// authoritative when present because the circuit ID is random per build
+/// unless pinned with `Schematic::with_id` (node IDs, by contrast, are stable
+/// across identical builds); `circuit_id` is the fallback for
+/// artifacts that predate the hash. Returns a `stale_projection` warning
+/// object on mismatch.
+fn projection_skew(projection: &Value, schematic: &Schematic) -> Option<Value> {
+    let identifies = |v: &Value| v.get("schematic_hash").is_some() || v.get("circuit_id").is_some();
+    let subject = if identifies(projection) {
+        projection.clone()
+    } else {
+        latest_trace_from_projection(projection).filter(identifies)?
+    };
+
+    let live_hash = schematic.structural_hash();
+    let found_hash = subject.get("schematic_hash").and_then(Value::as_str);
+    let found_id = subject.get("circuit_id").and_then(Value::as_str);
+    let found_version = subject.get("schema_version").and_then(Value::as_str);
+    let version_mismatch = found_version.is_some_and(|version| {
+        SchemaVersionMismatch::check("", &schematic.schema_version, version).is_err()
+    });
+    let reason = match (found_hash, found_id) {
+        _ if version_mismatch => "schema_version_mismatch",
+        (Some(hash), _) if hash != live_hash => "schematic_hash_mismatch",
+        (None, Some(id)) if id != schematic.id => "circuit_id_mismatch",
+        _ => return None,
+    };
+    Some(serde_json::json!({
+        "code": "stale_projection",
+        "reason": reason,
+        "message": "Projection was produced by a different build of this circuit",
+        "live": {
+            "circuit_id": schematic.id,
+            "schematic_hash": live_hash,
+            "schema_version": schematic.schema_version
+        },
+        "projection": {
+            "circuit_id": found_id,
+            "schematic_hash": found_hash,
+            "schema_version": found_version
+        }
+    }))
+}
+
+/// Attach a `warnings` entry to object projections that are out of date.
+fn annotate_projection_skew(mut projection: Value, schematic: &Schematic) -> Value {
+    if let Some(warning) = projection_skew(&projection, schematic)
+        && let Value::Object(map) = &mut projection
+    {
+        match map.get_mut("warnings") {
+            Some(Value::Array(warnings)) => warnings.push(warning),
+            _ => {
+                map.insert("warnings".to_string(), Value::Array(vec![warning]));
+            }
+        }
+    }
+    projection
+}
+
+/// Expand MessagePack/CBOR captures into a readable `decoded` field.
+///
+/// Runs before redaction so decoded fields are subject to the same policy as
+/// JSON captures.
+fn decode_captured_payloads(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let is_binary_capture = map.len() == 2
+                && map.contains_key("data")
+                && matches!(
+                    map.get("format").and_then(Value::as_str),
+                    Some("msgpack" | "cbor")
+                );
+            if is_binary_capture {
+                let decoded = serde_json::from_value::<CapturedPayload>(Value::Object(map.clone()))
+                    .map_err(|e| e.to_string())
+                    .and_then(|payload| payload.to_json().map_err(|e| e.to_string()));
+                match decoded {
+                    Ok(json) => map.insert("decoded".to_string(), json),
+                    Err(error) => map.insert("decode_error".to_string(), Value::String(error)),
+                };
+                return;
+            }
+            map.values_mut().for_each(decode_captured_payloads);
+        }
+        Value::Array(items) => items.iter_mut().for_each(decode_captured_payloads),
+        _ => {}
+    }
 }
 
 fn parse_csv_lower(raw: &str) -> Vec<String> {
@@ -1637,6 +2112,64 @@ async fn api_get_stalls(
     ))
 }
 
+async fn api_get_limits(
+    headers: HeaderMap,
+    State(state): State<InspectorState>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    ensure_internal_access(&headers, &state.auth_policy)?;
+    let mut limiters = Vec::with_capacity(state.rate_limit_readers.len());
+    for reader in &state.rate_limit_readers {
+        limiters.push(reader.rate_limit_state().await);
+    }
+    Ok(inspector_envelope(
+        "inspector.limits.v1",
+        serde_json::json!({
+            "count": limiters.len(),
+            "limiters": limiters
+        }),
+    ))
+}
+
+async fn api_get_breakers(
+    headers: HeaderMap,
+    State(state): State<InspectorState>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    ensure_internal_access(&headers, &state.auth_policy)?;
+    let mut breakers = Vec::with_capacity(state.circuit_breaker_readers.len());
+    for reader in &state.circuit_breaker_readers {
+        breakers.push(reader.circuit_breaker_state().await);
+    }
+    let open = breakers
+        .iter()
+        .filter(|breaker| breaker.state != CircuitState::Closed)
+        .count();
+    Ok(inspector_envelope(
+        "inspector.breakers.v1",
+        serde_json::json!({
+            "count": breakers.len(),
+            "open": open,
+            "breakers": breakers
+        }),
+    ))
+}
+
+async fn api_get_resources(
+    headers: HeaderMap,
+    State(state): State<InspectorState>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    ensure_internal_access(&headers, &state.auth_policy)?;
+    let report = ranvier_core::bus_registry::resource_report();
+    Ok(inspector_envelope(
+        "inspector.resources.v1",
+        serde_json::json!({
+            "tracking": report.tracking,
+            "live_buses": report.buses.len(),
+            "resources": report.resources,
+            "buses": report.buses
+        }),
+    ))
+}
+
 static DEBUG_REGISTRY: OnceLock<Arc<Mutex<HashMap<String, DebugControl>>>> = OnceLock::new();
 
 fn get_debug_registry() -> Arc<Mutex<HashMap<String, DebugControl>>> {
@@ -1690,8 +2223,12 @@ struct InspectorState {
     bearer_auth: auth::BearerAuth,
     allow_unauthenticated: bool,
     trace_store: Option<Arc<dyn trace_store::TraceStore>>,
+    timeline_store: Option<Arc<dyn TimelineStore>>,
+    timeline_redaction: Option<RedactionRules>,
     #[allow(dead_code)]
     alert_dispatcher: Option<Arc<alert::AlertDispatcher>>,
+    rate_limit_readers: Vec<Arc<dyn RateLimitStateReader>>,
+    circuit_breaker_readers: Vec<Arc<dyn CircuitBreakerStateReader>>,
 }
 
 fn schematic_snapshot(state: &InspectorState) -> Schematic {
@@ -2100,6 +2637,35 @@ async fn get_schematic(
     Ok(Json(schematic_snapshot(&state)))
 }
 
+/// Every registered circuit, plus the Inspector's own schematic if it was
+/// never registered.
+/// Every registered circuit as one bundle. Answers `409 Conflict` when the
+/// circuits do not share a schema major version, so code generators never
+/// combine them.
+async fn get_schematics(
+    headers: HeaderMap,
+    State(state): State<InspectorState>,
+) -> Result<Json<SchematicBundle>, (StatusCode, Json<Value>)> {
+    ensure_public_access(&headers, &state.auth_policy)?;
+    let mut bundle = SchematicRegistry::global().bundle();
+    let own = schematic_snapshot(&state);
+    if bundle.get(&own.name).is_none() {
+        bundle.circuits.push(own);
+        bundle.circuits.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    if let Err(mismatch) = bundle.verify() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "code": mismatch.category(),
+                "message": mismatch.to_string(),
+                "circuit": mismatch.artifact,
+            })),
+        ));
+    }
+    Ok(Json(bundle))
+}
+
 async fn get_public_projection(
     headers: HeaderMap,
     State(state): State<InspectorState>,
@@ -2108,10 +2674,9 @@ async fn get_public_projection(
     if let Some(path) = &state.public_projection_path
         && let Ok(v) = read_projection_file(path)
     {
-        return Ok(Json(apply_projection_redaction(
-            v,
-            ProjectionSurface::Public,
-            &state.redaction_policy,
+        return Ok(Json(annotate_projection_skew(
+            apply_projection_redaction(v, ProjectionSurface::Public, &state.redaction_policy),
+            &schematic_snapshot(&state),
         )));
     }
 
@@ -2121,10 +2686,13 @@ async fn get_public_projection(
         .ok()
         .and_then(|v| v.clone())
         .unwrap_or(Value::Null);
-    Ok(Json(apply_projection_redaction(
-        projection,
-        ProjectionSurface::Public,
-        &state.redaction_policy,
+    Ok(Json(annotate_projection_skew(
+        apply_projection_redaction(
+            projection,
+            ProjectionSurface::Public,
+            &state.redaction_policy,
+        ),
+        &schematic_snapshot(&state),
     )))
 }
 
@@ -2134,25 +2702,29 @@ async fn get_internal_projection(
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
     ensure_internal_access(&headers, &state.auth_policy)?;
     if let Some(path) = &state.internal_projection_path
-        && let Ok(v) = read_projection_file(path)
+        && let Ok(mut v) = read_projection_file(path)
     {
-        return Ok(Json(apply_projection_redaction(
-            v,
-            ProjectionSurface::Internal,
-            &state.redaction_policy,
+        decode_captured_payloads(&mut v);
+        return Ok(Json(annotate_projection_skew(
+            apply_projection_redaction(v, ProjectionSurface::Internal, &state.redaction_policy),
+            &schematic_snapshot(&state),
         )));
     }
 
-    let projection = state
+    let mut projection = state
         .internal_projection
         .lock()
         .ok()
         .and_then(|v| v.clone())
         .unwrap_or(Value::Null);
-    Ok(Json(apply_projection_redaction(
-        projection,
-        ProjectionSurface::Internal,
-        &state.redaction_policy,
+    decode_captured_payloads(&mut projection);
+    Ok(Json(annotate_projection_skew(
+        apply_projection_redaction(
+            projection,
+            ProjectionSurface::Internal,
+            &state.redaction_policy,
+        ),
+        &schematic_snapshot(&state),
     )))
 }
 
@@ -2366,8 +2938,9 @@ async fn get_inspector_timeline_by_request_id(
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
     ensure_internal_access(&headers, &state.auth_policy)?;
     let projection = load_internal_projection_value(&state);
-    let trace = find_trace_by_request_id(&projection, &request_id)
+    let mut trace = find_trace_by_request_id(&projection, &request_id)
         .ok_or_else(|| policy_error(StatusCode::NOT_FOUND, "timeline_request_not_found"))?;
+    decode_captured_payloads(&mut trace);
 
     Ok(inspector_envelope(
         "inspector.timeline.v1",
@@ -2435,6 +3008,42 @@ async fn handle_socket(mut socket: WebSocket) {
     }
 }
 
+/// Publish the events of `tail` as `timeline_event` messages until the tail
+/// closes or `token` is cancelled.
+async fn forward_timeline_tail(
+    tail: TimelineTail,
+    redaction: Option<RedactionRules>,
+    token: Option<CancellationToken>,
+) {
+    use futures::StreamExt as _;
+
+    let mut events = tail.subscribe();
+    drop(tail);
+    loop {
+        let next = match token.as_ref() {
+            Some(token) => tokio::select! {
+                biased;
+                _ = token.cancelled() => break,
+                next = events.next() => next,
+            },
+            None => events.next().await,
+        };
+        let Some(mut event) = next else {
+            break;
+        };
+        if let Some(rules) = &redaction {
+            rules.redact_event(&mut event);
+        }
+        let msg = serde_json::json!({
+            "type": "timeline_event",
+            "event": event,
+            "timestamp": epoch_ms()
+        })
+        .to_string();
+        let _ = get_sender().send(msg);
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 enum BroadcastReceiveEnd {
     Lagged(u64),
@@ -2704,49 +3313,259 @@ async fn api_post_relay(
     }
 }
 
+#[derive(Deserialize, Default)]
+struct StoredTracesQuery {
+    circuit: Option<String>,
+    status: Option<String>,
+    from: Option<u64>,
+    to: Option<u64>,
+    limit: Option<usize>,
+    cursor: Option<String>,
+    /// `ndjson` streams every matching trace, one JSON object per line.
+    format: Option<String>,
+}
+
+/// Page size used for NDJSON exports when `limit` is not given.
+const STORED_TRACES_STREAM_BATCH: usize = 500;
+
+/// `GET /api/v1/traces/stored` — cursor-paginated listing.
+///
+/// JSON mode returns one page plus `next_cursor`. With `?format=ndjson` the
+/// whole result set after `cursor` is streamed, fetched from the store in
+/// batches of `limit` so large exports never sit in memory at once.
 async fn api_get_stored_traces(
     headers: HeaderMap,
+    Query(params): Query<StoredTracesQuery>,
     State(state): State<InspectorState>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+) -> Result<Response, (StatusCode, Json<Value>)> {
     ensure_internal_access(&headers, &state.auth_policy)?;
     state.bearer_auth.validate(&headers)?;
 
-    let Some(store) = &state.trace_store else {
+    if let Some(cursor) = params.cursor.as_deref() {
+        trace_store::TraceCursor::parse(cursor).map_err(|message| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid_cursor", "message": message })),
+            )
+        })?;
+    }
+    let ndjson = match params.format.as_deref() {
+        None | Some("json") => false,
+        Some("ndjson") => true,
+        Some(other) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "unsupported_format", "format": other })),
+            ));
+        }
+    };
+
+    let Some(store) = state.trace_store.clone() else {
+        if ndjson {
+            return Ok(ndjson_response(axum::body::Body::empty()));
+        }
         return Ok(inspector_envelope(
             "inspector.traces_stored.v1",
             serde_json::json!({
                 "total": 0,
                 "traces": [],
+                "next_cursor": null,
                 "note": "No trace store configured"
             }),
-        ));
+        )
+        .into_response());
     };
 
-    let query = trace_store::TraceQuery::default();
-    match store.query(query).await {
-        Ok(traces) => Ok(inspector_envelope(
+    let query = trace_store::TraceQuery {
+        circuit: params.circuit,
+        status: params.status,
+        from: params.from,
+        to: params.to,
+        limit: params
+            .limit
+            .or(ndjson.then_some(STORED_TRACES_STREAM_BATCH)),
+        cursor: params.cursor,
+    };
+    let first = store.query_page(query.clone()).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": "trace_store_error", "message": e })),
+        )
+    })?;
+
+    if !ndjson {
+        return Ok(inspector_envelope(
             "inspector.traces_stored.v1",
             serde_json::json!({
-                "total": traces.len(),
-                "traces": traces
+                "total": first.traces.len(),
+                "traces": first.traces,
+                "next_cursor": first.next_cursor
             }),
-        )),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": "trace_store_error", "message": e })),
-        )),
-    }
+        )
+        .into_response());
+    }
+
+    // State: the page to emit next (prefetched for the first one) and the
+    // query positioned at the following page, or `None` once exhausted.
+    let pages = futures::stream::unfold((Some(Ok(first)), Some(query)), move |(pending, query)| {
+        let store = store.clone();
+        async move {
+            let mut query = query?;
+            let page = match pending {
+                Some(page) => page,
+                None => store.query_page(query.clone()).await,
+            };
+            let mut chunk = String::new();
+            let next_query = match page {
+                Ok(page) => {
+                    for trace in &page.traces {
+                        chunk.push_str(&serde_json::to_string(trace).unwrap_or_default());
+                        chunk.push('\n');
+                    }
+                    page.next_cursor.map(|cursor| {
+                        query.cursor = Some(cursor);
+                        query
+                    })
+                }
+                Err(e) => {
+                    chunk.push_str(
+                        &serde_json::json!({ "error": "trace_store_error", "message": e })
+                            .to_string(),
+                    );
+                    chunk.push('\n');
+                    None
+                }
+            };
+            Some((Ok::<_, std::convert::Infallible>(chunk), (None, next_query)))
+        }
+    });
+    Ok(ndjson_response(axum::body::Body::from_stream(pages)))
 }
 
-async fn api_get_lineage(
+fn ndjson_response(body: axum::body::Body) -> Response {
+    ([(header::CONTENT_TYPE, "application/x-ndjson")], body).into_response()
+}
+
+/// `GET /api/v1/timelines` — recorded runs, newest first, without events.
+///
+/// Filters: `circuit`, `outcome`, `since` and `until` (recorded-at
+/// nanoseconds since the epoch), `limit`.
+async fn api_get_timeline_runs(
     headers: HeaderMap,
-    AxPath(trace_id): AxPath<String>,
+    Query(query): Query<TimelineRunQuery>,
     State(state): State<InspectorState>,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
     ensure_internal_access(&headers, &state.auth_policy)?;
     state.bearer_auth.validate(&headers)?;
 
-    let Some(store) = &state.trace_store else {
+    let Some(store) = &state.timeline_store else {
+        return Ok(inspector_envelope(
+            "inspector.timelines.v1",
+            serde_json::json!({
+                "total": 0,
+                "runs": [],
+                "note": "No timeline store configured"
+            }),
+        ));
+    };
+    let runs = store.list_runs(query).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": "timeline_store_error", "message": e })),
+        )
+    })?;
+    Ok(inspector_envelope(
+        "inspector.timelines.v1",
+        serde_json::json!({ "total": runs.len(), "runs": runs }),
+    ))
+}
+
+/// `GET /api/v1/timelines/stats` — [`TimelineStats`] over the runs matching
+/// the same filters as `/api/v1/timelines`.
+async fn api_get_timeline_stats(
+    headers: HeaderMap,
+    Query(query): Query<TimelineRunQuery>,
+    State(state): State<InspectorState>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    ensure_internal_access(&headers, &state.auth_policy)?;
+    state.bearer_auth.validate(&headers)?;
+
+    let Some(store) = &state.timeline_store else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "no_timeline_store" })),
+        ));
+    };
+    let mut runs = TimelineQuery::with_query(store.as_ref(), query.clone())
+        .fetch()
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": "timeline_store_error", "message": e })),
+            )
+        })?;
+    if let Some(rules) = &state.timeline_redaction {
+        for run in &mut runs {
+            run.timeline.redact(rules);
+        }
+    }
+    let stats = TimelineStats::from_timelines(runs.iter().map(|run| &run.timeline));
+    Ok(inspector_envelope(
+        "inspector.timeline_stats.v1",
+        serde_json::json!({
+            "query": query,
+            "error_rate": stats.error_rate(),
+            "stats": stats,
+        }),
+    ))
+}
+
+/// `GET /api/v1/timelines/:run_id` — one recorded run with its timeline.
+async fn api_get_timeline_run(
+    headers: HeaderMap,
+    AxPath(run_id): AxPath<String>,
+    State(state): State<InspectorState>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    ensure_internal_access(&headers, &state.auth_policy)?;
+    state.bearer_auth.validate(&headers)?;
+
+    let Some(store) = &state.timeline_store else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "no_timeline_store" })),
+        ));
+    };
+    let run = store.fetch_run(&run_id).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": "timeline_store_error", "message": e })),
+        )
+    })?;
+    let Some(mut run) = run else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "timeline_run_not_found", "run_id": run_id })),
+        ));
+    };
+    if let Some(rules) = &state.timeline_redaction {
+        run.timeline.redact(rules);
+    }
+    Ok(inspector_envelope(
+        "inspector.timeline_run.v1",
+        serde_json::to_value(&run).unwrap_or_default(),
+    ))
+}
+
+async fn api_get_lineage(
+    headers: HeaderMap,
+    AxPath(trace_id): AxPath<String>,
+    State(state): State<InspectorState>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    ensure_internal_access(&headers, &state.auth_policy)?;
+    state.bearer_auth.validate(&headers)?;
+
+    let Some(store) = &state.trace_store else {
         return Err((
             StatusCode::NOT_FOUND,
             Json(serde_json::json!({ "error": "no_trace_store" })),
@@ -2783,6 +3602,53 @@ async fn api_get_lineage(
     }
 }
 
+/// `GET /api/v1/timelines/diff?a=<run_id>&b=<run_id>` — run `b` compared
+/// against run `a` as the baseline (see [`Timeline::diff`]).
+async fn api_get_timeline_diff(
+    headers: HeaderMap,
+    Query(params): Query<TraceDiffQuery>,
+    State(state): State<InspectorState>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    ensure_internal_access(&headers, &state.auth_policy)?;
+    state.bearer_auth.validate(&headers)?;
+
+    let Some(store) = &state.timeline_store else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "no_timeline_store" })),
+        ));
+    };
+    let mut runs = Vec::with_capacity(2);
+    for run_id in [&params.a, &params.b] {
+        let run = store.fetch_run(run_id).await.map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": "timeline_store_error", "message": e })),
+            )
+        })?;
+        let Some(mut run) = run else {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({ "error": "timeline_run_not_found", "run_id": run_id })),
+            ));
+        };
+        if let Some(rules) = &state.timeline_redaction {
+            run.timeline.redact(rules);
+        }
+        runs.push(run);
+    }
+    let diff = runs[0].timeline.diff(&runs[1].timeline);
+    Ok(inspector_envelope(
+        "inspector.timeline_diff.v1",
+        serde_json::json!({
+            "baseline": params.a,
+            "other": params.b,
+            "identical": diff.is_empty(),
+            "diff": diff,
+        }),
+    ))
+}
+
 #[derive(Deserialize)]
 struct TraceDiffQuery {
     a: String,
@@ -2853,7 +3719,8 @@ async fn api_get_trace_diff(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ranvier_core::schematic::Schematic;
+    use ranvier_core::json_schema::SchemaArtifact;
+    use ranvier_core::schematic::{Node, Schematic};
     use std::time::Duration;
 
     fn reserve_listener() -> (u16, tokio::net::TcpListener) {
@@ -2915,6 +3782,621 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    struct FixedLimits;
+
+    #[async_trait]
+    impl RateLimitStateReader for FixedLimits {
+        async fn rate_limit_state(&self) -> ranvier_core::rate_limit::RateLimitState {
+            ranvier_core::rate_limit::RateLimitState {
+                limiter: "api".to_string(),
+                config: ranvier_core::rate_limit::RateLimitsConfig {
+                    default: Some(ranvier_core::rate_limit::RateLimitRule::new(10, 1000)),
+                    ..Default::default()
+                },
+                buckets: Vec::new(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_endpoint_reports_registered_limiters() {
+        let (port, listener) = reserve_listener();
+        let token = CancellationToken::new();
+        let inspector = Inspector::new(Schematic::new("limits"), port)
+            .with_mode("dev")
+            .with_rate_limit_reader(Arc::new(FixedLimits));
+        let server_token = token.clone();
+        let handle = tokio::spawn(async move {
+            inspector
+                .serve_with_listener_and_cancellation(listener, server_token)
+                .await
+        });
+
+        wait_ready(port).await;
+        let body: Value = reqwest::get(format!("http://127.0.0.1:{port}/limits"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["kind"], "inspector.limits.v1");
+        assert_eq!(body["data"]["count"], 1);
+        assert_eq!(body["data"]["limiters"][0]["limiter"], "api");
+        assert_eq!(
+            body["data"]["limiters"][0]["config"]["default"]["max_requests"],
+            10
+        );
+
+        token.cancel(CancellationReason::OperatorShutdown);
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+    }
+
+    struct TrippedBreaker;
+
+    #[async_trait]
+    impl CircuitBreakerStateReader for TrippedBreaker {
+        async fn circuit_breaker_state(
+            &self,
+        ) -> ranvier_core::circuit_breaker::CircuitBreakerState {
+            ranvier_core::circuit_breaker::CircuitBreakerState {
+                node: "ChargeCard".to_string(),
+                state: CircuitState::Open,
+                calls: 10,
+                failures: 6,
+                failure_threshold: 0.5,
+                cool_down_ms: 30_000,
+                opened_at: None,
+                rejected: 3,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn breakers_endpoint_reports_registered_breakers() {
+        let (port, listener) = reserve_listener();
+        let token = CancellationToken::new();
+        let inspector = Inspector::new(Schematic::new("breakers"), port)
+            .with_mode("dev")
+            .with_circuit_breaker_reader(Arc::new(TrippedBreaker));
+        let server_token = token.clone();
+        let handle = tokio::spawn(async move {
+            inspector
+                .serve_with_listener_and_cancellation(listener, server_token)
+                .await
+        });
+
+        wait_ready(port).await;
+        let body: Value = reqwest::get(format!("http://127.0.0.1:{port}/breakers"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["kind"], "inspector.breakers.v1");
+        assert_eq!(body["data"]["open"], 1);
+        assert_eq!(body["data"]["breakers"][0]["node"], "ChargeCard");
+        assert_eq!(body["data"]["breakers"][0]["state"], "open");
+
+        token.cancel(CancellationReason::OperatorShutdown);
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+    }
+
+    #[tokio::test]
+    async fn resources_endpoint_reports_live_bus_entries() {
+        struct PaymentsClient;
+
+        let (port, listener) = reserve_listener();
+        let token = CancellationToken::new();
+        let inspector = Inspector::new(Schematic::new("resources"), port)
+            .with_mode("dev")
+            .with_bus_tracking();
+        let server_token = token.clone();
+        let handle = tokio::spawn(async move {
+            inspector
+                .serve_with_listener_and_cancellation(listener, server_token)
+                .await
+        });
+
+        wait_ready(port).await;
+        let mut bus = ranvier_core::Bus::new();
+        bus.insert(PaymentsClient);
+        let body: Value = reqwest::get(format!("http://127.0.0.1:{port}/resources"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["kind"], "inspector.resources.v1");
+        assert_eq!(body["data"]["tracking"], true);
+        let resources = body["data"]["resources"].as_array().unwrap();
+        let payments = resources
+            .iter()
+            .find(|usage| {
+                usage["type_name"]
+                    .as_str()
+                    .is_some_and(|name| name.ends_with("PaymentsClient"))
+            })
+            .unwrap();
+        assert_eq!(payments["buses"], 1);
+
+        drop(bus);
+        token.cancel(CancellationReason::OperatorShutdown);
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+    }
+
+    #[tokio::test]
+    async fn schematics_endpoint_lists_registered_circuits() {
+        let (port, listener) = reserve_listener();
+        let token = CancellationToken::new();
+        SchematicRegistry::global().register(Schematic::new("inspector-test-billing"));
+        let inspector = Inspector::new(Schematic::new("inspector-test-orders"), port);
+        let server_token = token.clone();
+        let handle = tokio::spawn(async move {
+            inspector
+                .serve_with_listener_and_cancellation(listener, server_token)
+                .await
+        });
+
+        wait_ready(port).await;
+        let body: Value = reqwest::get(format!("http://127.0.0.1:{port}/schematics"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let names: Vec<&str> = body["circuits"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|circuit| circuit["name"].as_str())
+            .collect();
+        assert!(names.contains(&"inspector-test-billing"));
+        assert!(names.contains(&"inspector-test-orders"));
+
+        token.cancel(CancellationReason::OperatorShutdown);
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+    }
+
+    #[test]
+    fn binary_captures_gain_decoded_json() {
+        use ranvier_core::capture::CaptureFormat;
+        let cbor =
+            CapturedPayload::encode(CaptureFormat::Cbor, &serde_json::json!({ "id": 9 })).unwrap();
+        let json = CapturedPayload::encode(CaptureFormat::Json, &1).unwrap();
+        let mut trace = serde_json::json!({
+            "events": [
+                { "PayloadCaptured": { "direction": "Input", "payload": cbor } },
+                { "PayloadCaptured": { "direction": "Output", "payload": json } }
+            ]
+        });
+        decode_captured_payloads(&mut trace);
+        assert_eq!(
+            trace["events"][0]["PayloadCaptured"]["payload"]["decoded"]["id"],
+            9
+        );
+        assert!(
+            trace["events"][1]["PayloadCaptured"]["payload"]
+                .get("decoded")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn timeline_projection_carries_fault_cause_tree() {
+        use ranvier_core::fault::{FaultCause, FaultRetry};
+        use ranvier_core::timeline::Timestamp;
+
+        let schematic = Schematic::new("checkout");
+        let at = Timestamp::from_millis;
+        let cause = FaultCause::new("n2", "charge_card", "declined")
+            .with_retries(vec![FaultRetry {
+                attempt: 1,
+                error: "timeout".into(),
+                backoff_ms: 5,
+                timestamp: at(12),
+            }])
+            .caused_by(FaultCause::new("n1", "reserve_stock", "warehouse offline"));
+        let mut timeline = Timeline::new();
+        for event in [
+            TimelineEvent::NodeEnter {
+                node_id: "n2".into(),
+                node_label: "charge_card".into(),
+                timestamp: at(10),
+                trace: None,
+            },
+            TimelineEvent::FaultRecorded {
+                node_id: "n2".into(),
+                cause,
+                timestamp: at(20),
+                trace: None,
+            },
+            TimelineEvent::NodeExit {
+                node_id: "n2".into(),
+                outcome_type: "Fault".into(),
+                duration_ms: 10,
+                timestamp: at(20),
+                trace: None,
+            },
+        ] {
+            timeline.push(event);
+        }
+
+        SchemaArtifact::Timeline
+            .validate(&serde_json::to_value(&timeline).unwrap())
+            .unwrap();
+        let projection = internal_projection_from_timeline(&schematic, "t-1", &timeline);
+        SchemaArtifact::InternalProjection
+            .validate(&projection)
+            .unwrap();
+        assert_eq!(projection["summary"]["fault_count"], 1);
+        assert_eq!(projection["summary"]["sla_breaches"], 0);
+        assert!(projection["nodes"][0].get("sla_breached").is_none());
+        assert_eq!(projection["nodes"][0]["latency_ms"], 10.0);
+        assert_eq!(projection["nodes"][0]["fault"]["error"], "declined");
+        let chain = &projection["fault_chain"];
+        assert_eq!(chain["node_label"], "charge_card");
+        assert_eq!(chain["retries"][0]["error"], "timeout");
+        assert_eq!(chain["causes"][0]["node_label"], "reserve_stock");
+    }
+
+    #[test]
+    fn projections_aggregate_recorded_timelines() {
+        let runs: Vec<Timeline> = (1..=30)
+            .map(|i| {
+                let mut timeline = Timeline::new();
+                timeline.push(TimelineEvent::NodeEnter {
+                    node_id: "n1".into(),
+                    node_label: "charge".into(),
+                    timestamp: Timestamp::from_millis(i * 1_000),
+                    trace: None,
+                });
+                timeline.push(TimelineEvent::NodeExit {
+                    node_id: "n1".into(),
+                    outcome_type: if i % 10 == 0 { "Fault" } else { "Next" }.into(),
+                    duration_ms: i * 10,
+                    timestamp: Timestamp::from_millis(i * 1_000 + i * 10),
+                    trace: None,
+                });
+                timeline.push(TimelineEvent::Branchtaken {
+                    branch_id: if i % 3 == 0 { "review" } else { "ship" }.into(),
+                    timestamp: Timestamp::from_millis(i * 1_000 + i * 10),
+                    trace: None,
+                });
+                timeline
+            })
+            .collect();
+        let schematic = Schematic::new("checkout");
+        let stats = TimelineStats::from_timelines(&runs);
+
+        let internal = internal_projection_from_stats(&schematic, &stats);
+        SchemaArtifact::InternalProjection
+            .validate(&internal)
+            .unwrap();
+        let node = &internal["nodes"][0];
+        assert_eq!(node["visits"], 30);
+        assert_eq!(node["outcome_type"], "Next");
+        assert_eq!(node["latency_ms"], 155.0);
+        assert_eq!(node["error_rate"], 0.1);
+        assert_eq!(internal["summary"]["fault_count"], 3);
+        assert_eq!(internal["branches"][0]["branch_id"], "review");
+        assert_eq!(internal["branches"][0]["frequency"], 10.0 / 30.0);
+
+        let public = public_projection::public_projection_from_timelines(
+            &schematic,
+            &stats,
+            &public_projection::PublicProjectionOptions::default(),
+        );
+        SchemaArtifact::PublicProjection.validate(&public).unwrap();
+        let circuit = &public["circuits"][0];
+        assert_eq!(circuit["name"], "checkout");
+        assert_eq!(circuit["error_rate"], 0.1);
+        assert_eq!(circuit["p95_latency_ms"], stats.nodes["n1"].p95());
+        assert_eq!(
+            public["window_start"],
+            Timestamp::from_millis(1_000).to_string()
+        );
+    }
+
+    #[test]
+    fn bootstrap_projections_match_published_schemas() {
+        let mut schematic = Schematic::new("checkout");
+        schematic.nodes.push(Node {
+            id: "n1".into(),
+            kind: NodeKind::Atom,
+            label: "charge_card".into(),
+            description: None,
+            input_type: "Order".into(),
+            output_type: "Receipt".into(),
+            resource_type: "()".into(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: None,
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        });
+
+        SchemaArtifact::PublicProjection
+            .validate(&default_public_projection(&schematic))
+            .unwrap();
+        SchemaArtifact::InternalProjection
+            .validate(&default_internal_projection(&schematic))
+            .unwrap();
+        let err = SchemaArtifact::PublicProjection
+            .validate(&serde_json::json!({ "service_name": "checkout", "overall_status": "fine", "circuits": [] }))
+            .unwrap_err();
+        assert_eq!(err.violations[0].path, "/overall_status");
+    }
+
+    #[test]
+    fn timeline_projection_routes_slow_nodes_to_their_owner() {
+        use ranvier_core::metadata::NodeAnnotations;
+        use ranvier_core::timeline::Timestamp;
+
+        let mut schematic = Schematic::new("checkout");
+        let mut node: Node = serde_json::from_value(serde_json::json!({
+            "id": "n1",
+            "kind": "Atom",
+            "label": "charge_card",
+            "input_type": "Order",
+            "output_type": "Receipt",
+            "resource_type": "()",
+            "metadata": serde_json::to_value(ranvier_core::metadata::StepMetadata::default()).unwrap()
+        }))
+        .unwrap();
+        node.metadata.annotations = NodeAnnotations::default()
+            .with_owner("payments-oncall")
+            .with_sla_ms(5);
+        schematic.nodes.push(node);
+
+        let mut timeline = Timeline::new();
+        timeline.push(TimelineEvent::NodeEnter {
+            node_id: "n1".into(),
+            node_label: "charge_card".into(),
+            timestamp: Timestamp::from_millis(10),
+            trace: None,
+        });
+        timeline.push(TimelineEvent::NodeExit {
+            node_id: "n1".into(),
+            outcome_type: "Next".into(),
+            duration_ms: 12,
+            timestamp: Timestamp::from_millis(22),
+            trace: None,
+        });
+
+        let projection = internal_projection_from_timeline(&schematic, "t-1", &timeline);
+        SchemaArtifact::InternalProjection
+            .validate(&projection)
+            .unwrap();
+        let node = &projection["nodes"][0];
+        assert_eq!(node["annotations"]["owner"], "payments-oncall");
+        assert_eq!(node["sla_breached"], true);
+        assert_eq!(projection["summary"]["sla_breaches"], 1);
+        assert_eq!(
+            default_internal_projection(&schematic)["nodes"][0]["annotations"]["sla_ms"],
+            5
+        );
+    }
+
+    #[test]
+    fn projection_from_other_build_is_flagged_stale() {
+        let schematic = Schematic::with_id("orders", "orders-v2");
+        let fresh = default_internal_projection(&schematic);
+        assert!(projection_skew(&fresh, &schematic).is_none());
+
+        let mut old = fresh.clone();
+        old["schematic_hash"] = Value::from("0000000000000000");
+        let annotated = annotate_projection_skew(old, &schematic);
+        assert_eq!(annotated["warnings"][0]["code"], "stale_projection");
+        assert_eq!(
+            annotated["warnings"][0]["reason"],
+            "schematic_hash_mismatch"
+        );
+
+        let mut next_major = fresh.clone();
+        next_major["schema_version"] = Value::from("2.0");
+        let warning = projection_skew(&next_major, &schematic).unwrap();
+        assert_eq!(warning["reason"], "schema_version_mismatch");
+        assert_eq!(warning["projection"]["schema_version"], "2.0");
+        let mut next_minor = fresh.clone();
+        next_minor["schema_version"] = Value::from("1.4");
+        assert!(projection_skew(&next_minor, &schematic).is_none());
+
+        let legacy =
+            serde_json::json!({ "traces": [{ "trace_id": "t", "circuit_id": "orders-v1" }] });
+        let warning = projection_skew(&legacy, &schematic).unwrap();
+        assert_eq!(warning["reason"], "circuit_id_mismatch");
+        assert!(projection_skew(&serde_json::json!({ "traces": [] }), &schematic).is_none());
+    }
+
+    #[tokio::test]
+    async fn stored_traces_paginate_and_stream_ndjson() {
+        use trace_store::TraceStore;
+        let store = Arc::new(trace_store::InMemoryTraceStore::with_ttl(100, 0));
+        for i in 0..3u64 {
+            store
+                .save(trace_store::StoredTrace {
+                    trace_id: format!("t{i}"),
+                    circuit: "orders".into(),
+                    status: "completed".into(),
+                    started_at: 1000 + i,
+                    finished_at: 1010 + i,
+                    duration_ms: 10,
+                    outcome_type: Some("Next".into()),
+                    node_count: 1,
+                    fault_count: 0,
+                    timeline_json: None,
+                })
+                .await
+                .unwrap();
+        }
+        let (port, listener) = reserve_listener();
+        let token = CancellationToken::new();
+        let inspector = Inspector::new(Schematic::new("stored"), port)
+            .with_mode("dev")
+            .with_trace_store(store);
+        let server_token = token.clone();
+        let handle = tokio::spawn(async move {
+            inspector
+                .serve_with_listener_and_cancellation(listener, server_token)
+                .await
+        });
+        wait_ready(port).await;
+        let base = format!("http://127.0.0.1:{port}/api/v1/traces/stored");
+
+        let page: Value = reqwest::get(format!("{base}?limit=2"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(page["data"]["traces"][0]["trace_id"], "t2");
+        assert_eq!(page["data"]["next_cursor"], "1001:t1");
+        let rest: Value = reqwest::get(format!("{base}?limit=2&cursor=1001:t1"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(rest["data"]["traces"][0]["trace_id"], "t0");
+        assert!(rest["data"]["next_cursor"].is_null());
+
+        let response = reqwest::get(format!("{base}?format=ndjson&limit=1"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+        let body = response.text().await.unwrap();
+        let ids: Vec<String> = body
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["trace_id"].to_string())
+            .collect();
+        assert_eq!(ids, vec!["\"t2\"", "\"t1\"", "\"t0\""]);
+
+        let bad = reqwest::get(format!("{base}?cursor=garbage"))
+            .await
+            .unwrap();
+        assert_eq!(bad.status(), reqwest::StatusCode::BAD_REQUEST);
+
+        token.cancel(CancellationReason::OperatorShutdown);
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+    }
+
+    #[tokio::test]
+    async fn timeline_runs_are_listed_and_fetched_from_the_store() {
+        use ranvier_core::timeline_store::{InMemoryTimelineStore, TimelineRun};
+        let store = InMemoryTimelineStore::new();
+        for (run_id, circuit) in [("r1", "orders"), ("r2", "billing"), ("r3", "orders")] {
+            let mut timeline = Timeline::new();
+            timeline.push(TimelineEvent::NodeEnter {
+                node_id: "n1".into(),
+                node_label: "Reserve".into(),
+                timestamp: Timestamp::from_millis(1),
+                trace: None,
+            });
+            if run_id == "r2" {
+                timeline.push(TimelineEvent::PayloadCaptured {
+                    node_id: "n1".into(),
+                    direction: ranvier_core::timeline::CaptureDirection::Input,
+                    payload: ranvier_core::capture::CapturedPayload::encode(
+                        ranvier_core::capture::CaptureFormat::Json,
+                        &serde_json::json!({ "iban": "DE89370400440532013000", "amount": 5 }),
+                    )
+                    .unwrap(),
+                    timestamp: Timestamp::from_millis(2),
+                    trace: None,
+                });
+            }
+            let outcome = if run_id == "r2" { "Fault" } else { "Next" };
+            store
+                .append_run(TimelineRun::new(run_id, circuit, outcome, timeline))
+                .await
+                .unwrap();
+        }
+        let (port, listener) = reserve_listener();
+        let token = CancellationToken::new();
+        let inspector = Inspector::new(Schematic::new("timelines"), port)
+            .with_mode("dev")
+            .with_timeline_store(Arc::new(store))
+            .with_redaction_rules(RedactionRules::empty().field("iban"));
+        let server_token = token.clone();
+        let handle = tokio::spawn(async move {
+            inspector
+                .serve_with_listener_and_cancellation(listener, server_token)
+                .await
+        });
+        wait_ready(port).await;
+        let base = format!("http://127.0.0.1:{port}/api/v1/timelines");
+
+        let listed: Value = reqwest::get(format!("{base}?circuit=orders&limit=5"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(listed["data"]["total"], 2);
+        assert_eq!(listed["data"]["runs"][0]["run_id"], "r3");
+        assert_eq!(listed["data"]["runs"][1]["event_count"], 1);
+
+        let faults: Value = reqwest::get(format!("{base}?outcome=Fault"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(faults["data"]["runs"][0]["run_id"], "r2");
+        assert_eq!(faults["data"]["total"], 1);
+        let future: Value = reqwest::get(format!("{base}?since={}", u64::MAX))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(future["data"]["total"], 0);
+
+        let stats: Value = reqwest::get(format!("{base}/stats?circuit=orders"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(stats["kind"], "inspector.timeline_stats.v1");
+        assert_eq!(stats["data"]["stats"]["runs"], 2);
+        assert_eq!(stats["data"]["query"]["circuit"], "orders");
+
+        let run: Value = reqwest::get(format!("{base}/r2"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(run["data"]["circuit"], "billing");
+        assert_eq!(
+            run["data"]["timeline"]["events"][0]["NodeEnter"]["node_label"],
+            "Reserve"
+        );
+        let captured = &run["data"]["timeline"]["events"][1]["PayloadCaptured"]["payload"]["data"];
+        assert_eq!(captured["iban"], ranvier_core::redaction::REDACTED);
+        assert_eq!(captured["amount"], 5);
+
+        let missing = reqwest::get(format!("{base}/nope")).await.unwrap();
+        assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+
+        let diff: Value = reqwest::get(format!("{base}/diff?a=r1&b=r3"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(diff["data"]["identical"], true);
+        assert_eq!(diff["data"]["diff"]["nodes"][0]["node_id"], "n1");
+
+        token.cancel(CancellationReason::OperatorShutdown);
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+    }
+
     #[test]
     fn production_policy_aggregates_profile_bind_auth_and_cors_violations() {
         let runtime = resolved(RuntimeProfile::Production, "");
@@ -3138,7 +4620,7 @@ expires_on = "2099-01-02"
     #[test]
     fn custom_sensitive_patterns_are_applied() {
         let mut policy = TelemetryRedactionPolicy::default();
-        policy.sensitive_patterns.push("tenant_id".to_string());
+        policy.fields = policy.fields.field("tenant_id");
 
         let src = serde_json::json!({
             "tenant_id": "team-a",
@@ -3617,6 +5099,50 @@ expires_on = "2099-01-02"
         handle.abort();
     }
 
+    #[tokio::test]
+    async fn timeline_tail_events_are_forwarded_redacted_to_event_subscribers() {
+        let tail = TimelineTail::new();
+        let token = CancellationToken::new();
+        let mut receiver = get_sender().subscribe();
+        let forward = tokio::spawn(forward_timeline_tail(
+            tail.clone(),
+            Some(RedactionRules::empty().field("iban")),
+            Some(token.clone()),
+        ));
+        while tail.subscriber_count() == 0 {
+            tokio::task::yield_now().await;
+        }
+        tail.publish(TimelineEvent::PayloadCaptured {
+            node_id: "n1".into(),
+            direction: ranvier_core::timeline::CaptureDirection::Input,
+            payload: ranvier_core::capture::CapturedPayload::encode(
+                ranvier_core::capture::CaptureFormat::Json,
+                &serde_json::json!({ "iban": "DE89370400440532013000" }),
+            )
+            .unwrap(),
+            timestamp: Timestamp::from_millis(1),
+            trace: None,
+        });
+
+        let message = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                let message: Value = serde_json::from_str(&receiver.recv().await.unwrap()).unwrap();
+                if message["type"] == "timeline_event" {
+                    return message;
+                }
+            }
+        })
+        .await
+        .expect("timeline event forwarded");
+        assert_eq!(
+            message["event"]["PayloadCaptured"]["payload"]["data"]["iban"],
+            ranvier_core::redaction::REDACTED
+        );
+
+        token.cancel(CancellationReason::OperatorShutdown);
+        forward.await.unwrap();
+    }
+
     #[tokio::test]
     async fn lagging_event_subscriber_isolated_from_healthy_subscriber() {
         let (sender, _receiver) = broadcast::channel(2);
@@ -1,3 +1,4 @@
+use ranvier_core::timeline::stats::TimelineStats;
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -155,6 +156,40 @@ pub struct CircuitMetricsSnapshot {
     pub nodes: HashMap<String, NodeMetricsSnapshot>,
 }
 
+impl CircuitMetricsSnapshot {
+    /// Snapshot of recorded timelines instead of live samples; the window
+    /// spans their first to last event.
+    pub fn from_timeline_stats(circuit: impl Into<String>, stats: &TimelineStats) -> Self {
+        let window_ms = match (stats.first_event, stats.last_event) {
+            (Some(first), Some(last)) => last.duration_since(first).as_millis() as u64,
+            _ => 0,
+        };
+        let window_secs = (window_ms as f64 / 1000.0).max(1.0);
+        let nodes = stats
+            .nodes
+            .iter()
+            .map(|(node_id, node)| {
+                let snapshot = NodeMetricsSnapshot {
+                    throughput: (node.visits as f64 / window_secs).round() as u64,
+                    error_count: node.error_count,
+                    error_rate: node.error_rate(),
+                    latency_p50: node.p50(),
+                    latency_p95: node.p95(),
+                    latency_p99: node.p99(),
+                    latency_avg: node.avg_ms(),
+                    sample_count: node.visits,
+                };
+                (node_id.clone(), snapshot)
+            })
+            .collect();
+        Self {
+            circuit: circuit.into(),
+            window_ms,
+            nodes,
+        }
+    }
+}
+
 /// Bounded-retention counters for one metrics node.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct NodeMetricsRetentionStats {
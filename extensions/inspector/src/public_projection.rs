@@ -0,0 +1,501 @@
+//! Public projection generation from live metrics.
+//!
+//! The public projection backs `/trace/public` and status pages, so it must
+//! not reveal fine-grained traffic patterns. [`PublicProjectionOptions`]
+//! coarsens the numbers before the projection is written:
+//!
+//! - latency percentiles are reported as the upper edge of a bucket,
+//! - error and success rates are rounded to a fixed step,
+//! - circuits with too few samples in the window are left out entirely.
+//!
+//! ```rust,ignore
+//! let projection = public_projection_from_metrics(
+//!     &schematic,
+//!     &metrics::snapshot_all(),
+//!     &PublicProjectionOptions::public_default(),
+//! );
+//! std::fs::write("public.json", serde_json::to_vec_pretty(&projection)?)?;
+//! ```
+//!
+//! Services deployed in several regions publish one projection per region;
+//! [`aggregate_regional_projections`] merges them into the service-level
+//! projection a global status page reads, keeping a per-region breakdown.
+
+use crate::metrics::CircuitMetricsSnapshot;
+use ranvier_core::schematic::{SCHEMA_VERSION, SchemaVersionMismatch, Schematic};
+use ranvier_core::timeline::Timestamp;
+use ranvier_core::timeline::stats::TimelineStats;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Error rate at or above which a circuit is reported as `degraded`.
+const DEGRADED_ERROR_RATE: f64 = 0.05;
+/// Error rate at or above which a circuit is reported as `outage`.
+const OUTAGE_ERROR_RATE: f64 = 0.5;
+
+/// Coarsening applied to metrics before they enter a public projection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicProjectionOptions {
+    /// Ascending bucket edges in milliseconds; empty reports exact latencies.
+    pub latency_buckets_ms: Vec<f64>,
+    /// Rates are rounded to the nearest multiple of this step; `0.0` keeps them exact.
+    pub rate_step: f64,
+    /// Circuits with fewer samples in the window are omitted.
+    pub min_sample_count: u64,
+}
+
+impl Default for PublicProjectionOptions {
+    /// Exact values, nothing suppressed.
+    fn default() -> Self {
+        Self {
+            latency_buckets_ms: Vec::new(),
+            rate_step: 0.0,
+            min_sample_count: 0,
+        }
+    }
+}
+
+impl PublicProjectionOptions {
+    /// Coarse settings suitable for an unauthenticated status endpoint.
+    pub fn public_default() -> Self {
+        Self {
+            latency_buckets_ms: vec![50.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 5_000.0],
+            rate_step: 0.01,
+            min_sample_count: 20,
+        }
+    }
+
+    pub fn with_latency_buckets(mut self, mut edges_ms: Vec<f64>) -> Self {
+        edges_ms.retain(|edge| edge.is_finite() && *edge > 0.0);
+        edges_ms.sort_by(f64::total_cmp);
+        edges_ms.dedup();
+        self.latency_buckets_ms = edges_ms;
+        self
+    }
+
+    pub fn with_rate_step(mut self, step: f64) -> Self {
+        self.rate_step = if step.is_finite() {
+            step.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self
+    }
+
+    pub fn suppress_below(mut self, min_sample_count: u64) -> Self {
+        self.min_sample_count = min_sample_count;
+        self
+    }
+
+    /// Report `latency_ms` as its bucket's upper edge, plus a bucket label.
+    ///
+    /// Values beyond the last edge are reported as that edge with a `+` label.
+    fn bucket_latency(&self, latency_ms: f64) -> (f64, Option<String>) {
+        let Some(&last) = self.latency_buckets_ms.last() else {
+            return (latency_ms, None);
+        };
+        let mut lower = 0.0;
+        for &edge in &self.latency_buckets_ms {
+            if latency_ms <= edge {
+                return (edge, Some(format!("{lower}-{edge}")));
+            }
+            lower = edge;
+        }
+        (last, Some(format!("{last}+")))
+    }
+
+    fn round_rate(&self, rate: f64) -> f64 {
+        let rate = rate.clamp(0.0, 1.0);
+        if self.rate_step <= 0.0 {
+            return rate;
+        }
+        let rounded = (rate / self.rate_step).round() * self.rate_step;
+        // Strip float noise such as 0.30000000000000004.
+        (rounded.clamp(0.0, 1.0) * 1e6).round() / 1e6
+    }
+}
+
+/// Build a public projection from per-circuit metrics snapshots.
+///
+/// Each circuit reports its overall error rate across node executions and
+/// the slowest node's p95 latency. The projection keeps the shape of the
+/// bootstrap projection and adds `suppressed_circuits`, the number of
+/// circuits omitted for low volume.
+pub fn public_projection_from_metrics(
+    schematic: &Schematic,
+    snapshots: &[CircuitMetricsSnapshot],
+    options: &PublicProjectionOptions,
+) -> Value {
+    let now = Timestamp::now();
+    let window_ms = snapshots.iter().map(|s| s.window_ms).max().unwrap_or(0);
+    let window_start = Timestamp::from_millis(now.as_millis().saturating_sub(window_ms));
+
+    let mut suppressed = 0usize;
+    let mut circuits = Vec::new();
+    let mut overall = "operational";
+    for snapshot in snapshots {
+        let samples: u64 = snapshot.nodes.values().map(|n| n.sample_count).sum();
+        if samples == 0 || samples < options.min_sample_count {
+            suppressed += 1;
+            continue;
+        }
+        let errors: u64 = snapshot.nodes.values().map(|n| n.error_count).sum();
+        let error_rate = options.round_rate(errors as f64 / samples as f64);
+        let p95 = snapshot
+            .nodes
+            .values()
+            .map(|n| n.latency_p95)
+            .fold(0.0, f64::max);
+        let (p95_latency_ms, p95_bucket) = options.bucket_latency(p95);
+        let status = status_for(error_rate);
+        if severity(status) > severity(overall) {
+            overall = status;
+        }
+
+        let mut circuit = serde_json::json!({
+            "name": snapshot.circuit,
+            "status": status,
+            "success_rate": options.round_rate(1.0 - error_rate),
+            "error_rate": error_rate,
+            "p95_latency_ms": p95_latency_ms,
+        });
+        if let Some(bucket) = p95_bucket {
+            circuit["p95_latency_bucket"] = Value::from(bucket);
+        }
+        circuits.push(circuit);
+    }
+    circuits.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    serde_json::json!({
+        "service_name": schematic.name,
+        "circuit_id": schematic.id,
+        "schematic_hash": schematic.structural_hash(),
+        "schema_version": schematic.schema_version,
+        "window_start": window_start.to_string(),
+        "window_end": now.to_string(),
+        "overall_status": overall,
+        "circuits": circuits,
+        "suppressed_circuits": suppressed,
+    })
+}
+
+/// Build a public projection from recorded timelines of the schematic's
+/// circuit, aggregated with [`TimelineStats`].
+///
+/// Same coarsening as [`public_projection_from_metrics`]; the window is the
+/// span of the recorded events rather than the live metrics window.
+pub fn public_projection_from_timelines(
+    schematic: &Schematic,
+    stats: &TimelineStats,
+    options: &PublicProjectionOptions,
+) -> Value {
+    let snapshot = CircuitMetricsSnapshot::from_timeline_stats(schematic.name.clone(), stats);
+    let mut projection = public_projection_from_metrics(schematic, &[snapshot], options);
+    if let (Some(first), Some(last)) = (stats.first_event, stats.last_event) {
+        projection["window_start"] = Value::from(first.to_string());
+        projection["window_end"] = Value::from(last.to_string());
+    }
+    projection
+}
+
+/// A public projection published by one regional deployment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionalProjection {
+    pub region: String,
+    pub projection: Value,
+    /// Relative traffic served by the region, e.g. requests in the window.
+    pub traffic_weight: f64,
+}
+
+impl RegionalProjection {
+    pub fn new(region: impl Into<String>, projection: Value, traffic_weight: f64) -> Self {
+        Self {
+            region: region.into(),
+            projection,
+            traffic_weight: if traffic_weight.is_finite() {
+                traffic_weight.max(0.0)
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Merge regional public projections into one service-level projection.
+///
+/// For each circuit, rates are averaged over the regions reporting it,
+/// weighted by traffic; when no region carries weight every region counts
+/// equally. Percentiles cannot be averaged, so `p95_latency_ms` is the
+/// slowest region's. The window spans all regional windows, and each
+/// circuit keeps a `regions` breakdown next to a top-level `regions` list
+/// with each region's status and traffic share.
+///
+/// A region whose projection carries a `schema_version` of another major
+/// version is left out and reported under `warnings` instead.
+pub fn aggregate_regional_projections(service_name: &str, regions: &[RegionalProjection]) -> Value {
+    let mut warnings = Vec::new();
+    let regions: Vec<&RegionalProjection> = regions
+        .iter()
+        .filter(|region| {
+            let Some(version) = region.projection["schema_version"].as_str() else {
+                return true;
+            };
+            match SchemaVersionMismatch::check(&region.region, SCHEMA_VERSION, version) {
+                Ok(()) => true,
+                Err(mismatch) => {
+                    warnings.push(serde_json::json!({
+                        "code": mismatch.category(),
+                        "region": region.region,
+                        "message": mismatch.to_string(),
+                    }));
+                    false
+                }
+            }
+        })
+        .collect();
+
+    let total_weight: f64 = regions.iter().map(|r| r.traffic_weight).sum();
+    let weight_of = |region: &RegionalProjection| {
+        if total_weight > 0.0 {
+            region.traffic_weight
+        } else {
+            1.0
+        }
+    };
+    let share_total = if total_weight > 0.0 {
+        total_weight
+    } else {
+        regions.len() as f64
+    };
+
+    struct Merged {
+        weight: f64,
+        weighted_error_rate: f64,
+        p95_latency_ms: f64,
+        breakdown: Vec<Value>,
+    }
+
+    let mut merged: BTreeMap<String, Merged> = BTreeMap::new();
+    let mut region_summaries = Vec::with_capacity(regions.len());
+    let mut window_start: Option<&str> = None;
+    let mut window_end: Option<&str> = None;
+    for region in regions {
+        let projection = &region.projection;
+        if let Some(start) = projection["window_start"].as_str() {
+            window_start = Some(window_start.map_or(start, |current| current.min(start)));
+        }
+        if let Some(end) = projection["window_end"].as_str() {
+            window_end = Some(window_end.map_or(end, |current| current.max(end)));
+        }
+        region_summaries.push(serde_json::json!({
+            "region": region.region,
+            "overall_status": projection["overall_status"].as_str().unwrap_or("operational"),
+            "traffic_share": round_share(weight_of(region) / share_total),
+            "window_end": projection["window_end"],
+        }));
+
+        let weight = weight_of(region);
+        for circuit in projection["circuits"].as_array().into_iter().flatten() {
+            let Some(name) = circuit["name"].as_str() else {
+                continue;
+            };
+            let error_rate = circuit["error_rate"]
+                .as_f64()
+                .unwrap_or(0.0)
+                .clamp(0.0, 1.0);
+            let p95 = circuit["p95_latency_ms"].as_f64().unwrap_or(0.0);
+            let entry = merged.entry(name.to_string()).or_insert(Merged {
+                weight: 0.0,
+                weighted_error_rate: 0.0,
+                p95_latency_ms: 0.0,
+                breakdown: Vec::new(),
+            });
+            entry.weight += weight;
+            entry.weighted_error_rate += error_rate * weight;
+            entry.p95_latency_ms = entry.p95_latency_ms.max(p95);
+            entry.breakdown.push(serde_json::json!({
+                "region": region.region,
+                "status": circuit["status"].as_str().unwrap_or_else(|| status_for(error_rate)),
+                "error_rate": error_rate,
+                "p95_latency_ms": p95,
+            }));
+        }
+    }
+
+    let mut overall = "operational";
+    let circuits: Vec<Value> = merged
+        .into_iter()
+        .map(|(name, entry)| {
+            let error_rate = if entry.weight > 0.0 {
+                round_share(entry.weighted_error_rate / entry.weight)
+            } else {
+                0.0
+            };
+            let status = status_for(error_rate);
+            if severity(status) > severity(overall) {
+                overall = status;
+            }
+            serde_json::json!({
+                "name": name,
+                "status": status,
+                "success_rate": round_share(1.0 - error_rate),
+                "error_rate": error_rate,
+                "p95_latency_ms": entry.p95_latency_ms,
+                "regions": entry.breakdown,
+            })
+        })
+        .collect();
+
+    let mut aggregate = serde_json::json!({
+        "service_name": service_name,
+        "window_start": window_start,
+        "window_end": window_end,
+        "overall_status": overall,
+        "circuits": circuits,
+        "regions": region_summaries,
+    });
+    if !warnings.is_empty() {
+        aggregate["warnings"] = Value::Array(warnings);
+    }
+    aggregate
+}
+
+/// Strip float noise from a ratio.
+fn round_share(value: f64) -> f64 {
+    (value * 1e6).round() / 1e6
+}
+
+fn status_for(error_rate: f64) -> &'static str {
+    if error_rate >= OUTAGE_ERROR_RATE {
+        "outage"
+    } else if error_rate >= DEGRADED_ERROR_RATE {
+        "degraded"
+    } else {
+        "operational"
+    }
+}
+
+fn severity(status: &str) -> u8 {
+    match status {
+        "outage" => 2,
+        "degraded" => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::MetricsCollector;
+    use ranvier_core::json_schema::SchemaArtifact;
+
+    #[test]
+    fn coarsens_rates_and_latency_and_suppresses_low_volume() {
+        let mut busy = MetricsCollector::new("checkout", 60_000);
+        for i in 0..40 {
+            busy.record_node_exit("charge", 120 + i, i % 13 == 0);
+        }
+        let mut quiet = MetricsCollector::new("admin_export", 60_000);
+        for _ in 0..3 {
+            quiet.record_node_exit("export", 900, false);
+        }
+
+        let projection = public_projection_from_metrics(
+            &Schematic::new("shop"),
+            &[busy.snapshot(), quiet.snapshot()],
+            &PublicProjectionOptions::public_default().with_rate_step(0.05),
+        );
+
+        assert_eq!(projection["suppressed_circuits"], 1);
+        let circuits = projection["circuits"].as_array().unwrap();
+        assert_eq!(circuits.len(), 1);
+        let checkout = &circuits[0];
+        assert_eq!(checkout["name"], "checkout");
+        // 4 errors in 40 samples = 0.1, already a multiple of 0.05.
+        assert_eq!(checkout["error_rate"], 0.1);
+        assert_eq!(checkout["success_rate"], 0.9);
+        assert_eq!(checkout["status"], "degraded");
+        assert_eq!(checkout["p95_latency_ms"], 250.0);
+        assert_eq!(checkout["p95_latency_bucket"], "100-250");
+        assert_eq!(projection["overall_status"], "degraded");
+    }
+
+    #[test]
+    fn regional_projections_merge_weighted_by_traffic() {
+        let region = |error_rate: f64, p95: f64, end: &str| {
+            serde_json::json!({
+                "service_name": "shop",
+                "window_start": "2026-10-17T10:00:00Z",
+                "window_end": end,
+                "overall_status": status_for(error_rate),
+                "circuits": [{
+                    "name": "checkout",
+                    "status": status_for(error_rate),
+                    "success_rate": 1.0 - error_rate,
+                    "error_rate": error_rate,
+                    "p95_latency_ms": p95,
+                }],
+            })
+        };
+
+        let global = aggregate_regional_projections(
+            "shop",
+            &[
+                RegionalProjection::new(
+                    "us-east",
+                    region(0.0, 100.0, "2026-10-17T10:05:00Z"),
+                    900.0,
+                ),
+                RegionalProjection::new(
+                    "eu-west",
+                    region(0.6, 250.0, "2026-10-17T10:06:00Z"),
+                    100.0,
+                ),
+            ],
+        );
+
+        SchemaArtifact::PublicProjection.validate(&global).unwrap();
+        let checkout = &global["circuits"][0];
+        assert_eq!(checkout["error_rate"], 0.06);
+        assert_eq!(checkout["success_rate"], 0.94);
+        assert_eq!(checkout["status"], "degraded");
+        assert_eq!(checkout["p95_latency_ms"], 250.0);
+        assert_eq!(checkout["regions"][1]["region"], "eu-west");
+        assert_eq!(checkout["regions"][1]["status"], "outage");
+        assert_eq!(global["overall_status"], "degraded");
+        assert_eq!(global["window_end"], "2026-10-17T10:06:00Z");
+        assert_eq!(global["regions"][0]["traffic_share"], 0.9);
+        assert_eq!(global["regions"][1]["overall_status"], "outage");
+        assert!(global.get("warnings").is_none());
+
+        let mut next_major = region(0.9, 900.0, "2026-10-17T10:07:00Z");
+        next_major["schema_version"] = Value::from("2.0");
+        let global = aggregate_regional_projections(
+            "shop",
+            &[
+                RegionalProjection::new("us-east", region(0.0, 100.0, "2026-10-17T10:05:00Z"), 1.0),
+                RegionalProjection::new("ap-south", next_major, 1.0),
+            ],
+        );
+        assert_eq!(global["circuits"][0]["error_rate"], 0.0);
+        assert_eq!(global["regions"].as_array().unwrap().len(), 1);
+        assert_eq!(global["warnings"][0]["code"], "schema_version_mismatch");
+        SchemaArtifact::PublicProjection.validate(&global).unwrap();
+        assert_eq!(global["warnings"][0]["region"], "ap-south");
+    }
+
+    #[test]
+    fn latency_beyond_last_bucket_is_capped() {
+        let options = PublicProjectionOptions::default().with_latency_buckets(vec![500.0, 100.0]);
+        assert_eq!(options.bucket_latency(80.0), (100.0, Some("0-100".into())));
+        assert_eq!(
+            options.bucket_latency(7_000.0),
+            (500.0, Some("500+".into()))
+        );
+        assert_eq!(options.round_rate(0.123), 0.123);
+        assert_eq!(
+            PublicProjectionOptions::default().bucket_latency(42.5),
+            (42.5, None)
+        );
+    }
+}
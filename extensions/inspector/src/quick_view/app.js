@@ -73,7 +73,15 @@ function drawGraph(svg, schematic, internalTrace) {
       opacity: "0.7",
     });
     kind.textContent = n.kind || "";
-    svg.append(rect, label, kind);
+    const group = el("g", {});
+    const description = n.description || n.metadata?.description;
+    if (description) {
+      const tooltip = el("title", {});
+      tooltip.textContent = description;
+      group.appendChild(tooltip);
+    }
+    group.append(rect, label, kind);
+    svg.appendChild(group);
   }
 }
 
@@ -100,6 +108,21 @@ function renderMeta(schematic) {
   meta.textContent = `${schematic?.name ?? "unknown"} | nodes=${n}, edges=${e}`;
 }
 
+function renderWarnings(...projections) {
+  const banner = document.getElementById("stale-banner");
+  const stale = projections
+    .flatMap((p) => p?.warnings ?? [])
+    .filter((w) => w.code === "stale_projection");
+  banner.hidden = stale.length === 0;
+  banner.textContent = stale
+    .map(
+      (w) =>
+        `Stale projection (${w.reason}): loaded artifact ${w.projection?.schematic_hash ?? w.projection?.circuit_id} ` +
+        `does not match live circuit ${w.live?.schematic_hash}. Regenerate projections for this build.`,
+    )
+    .join(" ");
+}
+
 async function reload() {
   try {
     const [schematic, traceInternal, tracePublic] = await Promise.all([
@@ -108,6 +131,7 @@ async function reload() {
       getJson("/trace/public"),
     ]);
     renderMeta(schematic);
+    renderWarnings(traceInternal, tracePublic);
     renderTrace(traceInternal);
     renderPublic(tracePublic);
     drawGraph(document.getElementById("graph"), schematic, traceInternal);
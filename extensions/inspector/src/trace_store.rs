@@ -3,6 +3,11 @@
 //! The `TraceStore` trait defines the interface for saving and querying traces.
 //! `InMemoryTraceStore` provides a bounded in-memory implementation.
 //! For production, use `SqliteTraceStore` (requires `trace-sqlite` feature).
+//!
+//! Listing is paginated newest-first by `(started_at, trace_id)`. Each
+//! [`TracePage`] carries an opaque `next_cursor`; passing it back in
+//! [`TraceQuery::cursor`] resumes strictly after the last trace returned, so
+//! exports stay stable while new traces are being saved.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -37,6 +42,56 @@ pub struct TraceQuery {
     pub to: Option<u64>,
     /// Maximum number of results (default: 100).
     pub limit: Option<usize>,
+    /// Resume after this cursor (from [`TracePage::next_cursor`]).
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Position in the newest-first trace ordering.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TraceCursor {
+    pub started_at: u64,
+    pub trace_id: String,
+}
+
+impl TraceCursor {
+    pub fn of(trace: &StoredTrace) -> Self {
+        Self {
+            started_at: trace.started_at,
+            trace_id: trace.trace_id.clone(),
+        }
+    }
+
+    /// Opaque wire form used in `next_cursor` / `?cursor=`.
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.started_at, self.trace_id)
+    }
+
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let (started_at, trace_id) = raw
+            .split_once(':')
+            .ok_or_else(|| format!("invalid trace cursor: {raw}"))?;
+        let started_at = started_at
+            .parse()
+            .map_err(|_| format!("invalid trace cursor: {raw}"))?;
+        Ok(Self {
+            started_at,
+            trace_id: trace_id.to_string(),
+        })
+    }
+
+    /// `true` when `trace` sorts after this cursor (i.e. is older).
+    pub fn precedes(&self, trace: &StoredTrace) -> bool {
+        (trace.started_at, trace.trace_id.as_str()) < (self.started_at, self.trace_id.as_str())
+    }
+}
+
+/// One page of query results.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TracePage {
+    pub traces: Vec<StoredTrace>,
+    /// Cursor for the following page; `None` when this is the last page.
+    pub next_cursor: Option<String>,
 }
 
 /// Retention policy for automatic trace cleanup.
@@ -63,9 +118,29 @@ pub trait TraceStore: Send + Sync {
     /// Save a completed trace.
     async fn save(&self, trace: StoredTrace) -> Result<(), String>;
 
-    /// Query stored traces with optional filters.
+    /// Query stored traces with optional filters, newest first.
+    ///
+    /// Implementations must honour [`TraceQuery::cursor`] by returning only
+    /// traces ordered after it.
     async fn query(&self, filter: TraceQuery) -> Result<Vec<StoredTrace>, String>;
 
+    /// Query one page and compute the cursor for the next.
+    async fn query_page(&self, mut filter: TraceQuery) -> Result<TracePage, String> {
+        let limit = filter.limit.unwrap_or(100);
+        filter.limit = Some(limit.saturating_add(1));
+        let mut traces = self.query(filter).await?;
+        let next_cursor = if traces.len() > limit {
+            traces.truncate(limit);
+            traces.last().map(|t| TraceCursor::of(t).encode())
+        } else {
+            None
+        };
+        Ok(TracePage {
+            traces,
+            next_cursor,
+        })
+    }
+
     /// Get a single trace by ID.
     async fn get(&self, trace_id: &str) -> Result<Option<StoredTrace>, String>;
 
@@ -187,11 +262,20 @@ impl TraceStore for InMemoryTraceStore {
     async fn query(&self, filter: TraceQuery) -> Result<Vec<StoredTrace>, String> {
         let traces = self.traces.lock().map_err(|e| e.to_string())?;
         let limit = filter.limit.unwrap_or(100);
+        let cursor = filter
+            .cursor
+            .as_deref()
+            .map(TraceCursor::parse)
+            .transpose()?;
 
-        let result: Vec<StoredTrace> = traces
+        let mut result: Vec<&StoredTrace> = traces
             .iter()
-            .rev()
             .filter(|t| {
+                if let Some(ref cursor) = cursor {
+                    if !cursor.precedes(t) {
+                        return false;
+                    }
+                }
                 if let Some(ref circuit) = filter.circuit {
                     if &t.circuit != circuit {
                         return false;
@@ -214,11 +298,12 @@ impl TraceStore for InMemoryTraceStore {
                 }
                 true
             })
-            .take(limit)
-            .cloned()
             .collect();
+        result.sort_by(|a, b| {
+            (b.started_at, b.trace_id.as_str()).cmp(&(a.started_at, a.trace_id.as_str()))
+        });
 
-        Ok(result)
+        Ok(result.into_iter().take(limit).cloned().collect())
     }
 
     async fn get(&self, trace_id: &str) -> Result<Option<StoredTrace>, String> {
@@ -407,4 +492,47 @@ mod tests {
         assert_eq!(faulted.len(), 1);
         assert_eq!(faulted[0].trace_id, "t2");
     }
+
+    #[tokio::test]
+    async fn cursor_pagination_walks_all_traces_once() {
+        let store = InMemoryTraceStore::with_ttl(100, 0);
+        for i in 0..5 {
+            store
+                .save(make_trace(&format!("t{i}"), "C", 1000 + i))
+                .await
+                .unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = store
+                .query_page(TraceQuery {
+                    limit: Some(2),
+                    cursor: cursor.take(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            seen.extend(page.traces.into_iter().map(|t| t.trace_id));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(seen, vec!["t4", "t3", "t2", "t1", "t0"]);
+    }
+
+    #[tokio::test]
+    async fn malformed_cursor_is_rejected() {
+        let store = InMemoryTraceStore::default();
+        let err = store
+            .query(TraceQuery {
+                cursor: Some("nope".into()),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(err.contains("invalid trace cursor"));
+    }
 }
@@ -87,6 +87,8 @@ impl OpenApiPathItem {
 pub struct OpenApiOperation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     #[serde(rename = "operationId", skip_serializing_if = "Option::is_none")]
     pub operation_id: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
@@ -130,6 +132,7 @@ pub struct OpenApiMediaType {
 #[derive(Clone, Debug)]
 struct OperationPatch {
     summary: Option<String>,
+    description: Option<String>,
     request_schema: Option<Value>,
     response_schema: Option<Value>,
 }
@@ -139,6 +142,9 @@ impl OperationPatch {
         if let Some(summary) = self.summary {
             operation.summary = Some(summary);
         }
+        if let Some(description) = self.description {
+            operation.description = Some(description);
+        }
         if let Some(schema) = self.request_schema {
             let mut content = BTreeMap::new();
             content.insert("application/json".to_string(), OpenApiMediaType { schema });
@@ -246,6 +252,7 @@ impl OpenApiGenerator {
         let key = operation_key(&method, path_pattern.as_ref());
         let patch = self.patches.entry(key).or_insert(OperationPatch {
             summary: None,
+            description: None,
             request_schema: None,
             response_schema: None,
         });
@@ -253,6 +260,55 @@ impl OpenApiGenerator {
         self
     }
 
+    pub fn operation_description(
+        mut self,
+        method: Method,
+        path_pattern: impl AsRef<str>,
+        description: impl Into<String>,
+    ) -> Self {
+        let key = operation_key(&method, path_pattern.as_ref());
+        let patch = self.patches.entry(key).or_insert(OperationPatch {
+            summary: None,
+            description: None,
+            request_schema: None,
+            response_schema: None,
+        });
+        patch.description = Some(description.into());
+        self
+    }
+
+    /// Describe an operation with the doc comments of the circuit serving it.
+    ///
+    /// Each documented node becomes a `- **label**: description` line, in
+    /// schematic order. Routes whose circuit has no documented nodes are left
+    /// unchanged.
+    pub fn describe_from_schematic(
+        self,
+        method: Method,
+        path_pattern: impl AsRef<str>,
+        schematic: &Schematic,
+    ) -> Self {
+        let lines: Vec<String> = schematic
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                let description = node
+                    .description
+                    .as_deref()
+                    .or(node.metadata.description.as_deref())?;
+                Some(format!(
+                    "- **{}**: {}",
+                    node.label,
+                    description.replace('\n', " ").trim()
+                ))
+            })
+            .collect();
+        if lines.is_empty() {
+            return self;
+        }
+        self.operation_description(method, path_pattern, lines.join("\n"))
+    }
+
     pub fn json_request_schema<T>(mut self, method: Method, path_pattern: impl AsRef<str>) -> Self
     where
         T: JsonSchema,
@@ -260,6 +316,7 @@ impl OpenApiGenerator {
         let key = operation_key(&method, path_pattern.as_ref());
         let patch = self.patches.entry(key).or_insert(OperationPatch {
             summary: None,
+            description: None,
             request_schema: None,
             response_schema: None,
         });
@@ -286,6 +343,7 @@ impl OpenApiGenerator {
         let key = operation_key(&method, path_pattern.as_ref());
         let patch = self.patches.entry(key).or_insert(OperationPatch {
             summary: None,
+            description: None,
             request_schema: None,
             response_schema: None,
         });
@@ -372,6 +430,7 @@ impl OpenApiGenerator {
 
             let mut operation = OpenApiOperation {
                 summary: Some(default_summary),
+                description: None,
                 operation_id: if operation_id.is_empty() {
                     None
                 } else {
@@ -673,6 +732,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn describe_from_schematic_uses_node_doc_comments() {
+        let mut schematic = Schematic::new("checkout");
+        for (label, description) in [
+            ("Ingress", None),
+            (
+                "PriceCart",
+                Some("Price every line\nusing the current catalog."),
+            ),
+            ("ChargeCard", Some("Charge the customer's card.")),
+        ] {
+            let node = serde_json::from_value(json!({
+                "id": label,
+                "kind": "Atom",
+                "label": label,
+                "description": description,
+                "input_type": "Cart",
+                "output_type": "Cart",
+                "resource_type": "()",
+                "metadata": ranvier_core::metadata::StepMetadata::default(),
+            }))
+            .expect("node json");
+            schematic.nodes.push(node);
+        }
+
+        let doc = OpenApiGenerator::from_descriptors(vec![
+            HttpRouteDescriptor::new(Method::POST, "/checkout"),
+            HttpRouteDescriptor::new(Method::GET, "/health"),
+        ])
+        .describe_from_schematic(Method::POST, "/checkout", &schematic)
+        .build();
+
+        let operation = doc.paths["/checkout"]
+            .post
+            .as_ref()
+            .expect("post operation");
+        assert_eq!(
+            operation.description.as_deref(),
+            Some(
+                "- **PriceCart**: Price every line using the current catalog.\n\
+                 - **ChargeCard**: Charge the customer's card."
+            )
+        );
+        assert!(
+            doc.paths["/health"]
+                .get
+                .as_ref()
+                .unwrap()
+                .description
+                .is_none()
+        );
+    }
+
     #[test]
     fn swagger_html_contains_spec_url() {
         let html = swagger_ui_html("/openapi.json", "API Docs");
@@ -13,6 +13,7 @@
 //! |-------|---------|----------|-----------|
 //! | [`CorsGuard`] | Origin validation + CORS headers | `RequestOrigin` | `CorsHeaders` |
 //! | [`RateLimitGuard`] | Per-client token-bucket rate limiting | `ClientIdentity` | — |
+//! | [`ConfiguredRateLimitGuard`] | Route/tier rate limits from `ranvier.toml` | `ClientIdentity`, `RateLimitRoute`, `TenantTier` | — |
 //! | [`SecurityHeadersGuard`] | Standard security response headers | — | `SecurityHeaders` |
 //! | [`IpFilterGuard`] | Allow/deny-list IP filtering | `ClientIp` | — |
 //! | [`AccessLogGuard`] | Structured access logging | `AccessLogRequest` | `AccessLogEntry` |
@@ -30,11 +31,16 @@
 //! ```
 
 use async_trait::async_trait;
+use ranvier_core::config::RanvierConfig;
 use ranvier_core::iam::{IamIdentity, IamPolicy, enforce_policy};
+use ranvier_core::rate_limit::{
+    RateLimitBucketState, RateLimitState, RateLimitStateReader, RateLimitsConfig,
+};
 use ranvier_core::runtime_policy::{
     PolicyComponent, PolicyField, PolicyObservation, PolicyValue, RuntimeProfile,
     StartupPolicyCode, StartupPolicyContribution, StartupPolicyProvider,
 };
+use ranvier_core::tenant::TenantTier;
 use ranvier_core::{bus::Bus, outcome::Outcome, transition::Transition};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -447,6 +453,342 @@ where
     }
 }
 
+// ---------------------------------------------------------------------------
+// ConfiguredRateLimitGuard
+// ---------------------------------------------------------------------------
+
+/// Bus-injectable type carrying the route key used for configured rate limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitRoute(pub String);
+
+/// Handle for hot-reloading the limits used by a [`ConfiguredRateLimitGuard`].
+///
+/// Every clone of the guard observes updates immediately; existing buckets
+/// keep their balance, clamped to the new budget.
+#[derive(Clone)]
+pub struct RateLimitConfigHandle {
+    sender: tokio::sync::watch::Sender<RateLimitsConfig>,
+}
+
+impl RateLimitConfigHandle {
+    /// Returns the configuration currently in force.
+    pub fn current(&self) -> RateLimitsConfig {
+        self.sender.borrow().clone()
+    }
+
+    /// Replace the configuration. Rejected when any rule has a zero budget or window.
+    pub fn update(&self, config: RateLimitsConfig) -> Result<(), String> {
+        let invalid = config.invalid_rules();
+        if !invalid.is_empty() {
+            return Err(format!("invalid rate limit rules: {}", invalid.join(", ")));
+        }
+        self.sender.send_replace(config);
+        Ok(())
+    }
+
+    /// Re-read the `[rate_limits]` section of a `ranvier.toml` file.
+    pub fn reload_from_file(&self, path: impl AsRef<std::path::Path>) -> Result<(), String> {
+        let config = RanvierConfig::from_file(path).map_err(|e| e.to_string())?;
+        self.update(config.rate_limits)
+    }
+
+    /// Poll `path` and reload whenever its modification time changes.
+    ///
+    /// Reload failures are logged and the previous configuration stays active.
+    pub fn watch_file(
+        &self,
+        path: impl Into<std::path::PathBuf>,
+        poll_interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let handle = self.clone();
+        let path = path.into();
+        tokio::spawn(async move {
+            let modified = |path: &std::path::Path| {
+                std::fs::metadata(path)
+                    .and_then(|meta| meta.modified())
+                    .ok()
+            };
+            let mut last_seen = modified(&path);
+            let mut interval = tokio::time::interval(poll_interval);
+            loop {
+                interval.tick().await;
+                if handle.sender.is_closed() {
+                    break;
+                }
+                let current = modified(&path);
+                if current == last_seen {
+                    continue;
+                }
+                last_seen = current;
+                match handle.reload_from_file(&path) {
+                    Ok(()) => tracing::info!(path = %path.display(), "Reloaded rate limits"),
+                    Err(error) => tracing::warn!(
+                        path = %path.display(),
+                        %error,
+                        "Rate limit reload failed; keeping previous limits"
+                    ),
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ScopedBucketKey {
+    route: Option<String>,
+    tier: Option<String>,
+    client: String,
+}
+
+/// Rate limit guard driven by the `[rate_limits]` section of `ranvier.toml`.
+///
+/// Reads `ClientIdentity`, [`RateLimitRoute`] and [`TenantTier`] from the Bus,
+/// resolves the effective limit (route + tier → route → tier → default) and
+/// enforces it with one token bucket per matched scope and client. Requests
+/// with no matching rule pass through.
+///
+/// Limits can be changed at runtime through the [`RateLimitConfigHandle`]
+/// returned by [`ConfiguredRateLimitGuard::new`]. The guard implements
+/// [`RateLimitStateReader`] so its live state can be served by the Inspector.
+pub struct ConfiguredRateLimitGuard<T> {
+    name: String,
+    config: tokio::sync::watch::Receiver<RateLimitsConfig>,
+    buckets: Arc<Mutex<std::collections::HashMap<ScopedBucketKey, RateBucket>>>,
+    bucket_ttl_ms: u64,
+    pruned_buckets: Arc<AtomicU64>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> ConfiguredRateLimitGuard<T> {
+    pub fn new(config: RateLimitsConfig) -> (Self, RateLimitConfigHandle) {
+        let (sender, receiver) = tokio::sync::watch::channel(config);
+        let guard = Self {
+            name: "rate_limits".to_string(),
+            config: receiver,
+            buckets: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            bucket_ttl_ms: 0,
+            pruned_buckets: Arc::new(AtomicU64::new(0)),
+            _marker: PhantomData,
+        };
+        (guard, RateLimitConfigHandle { sender })
+    }
+
+    /// Load limits from a resolved `ranvier.toml`.
+    pub fn from_ranvier_config(config: &RanvierConfig) -> (Self, RateLimitConfigHandle) {
+        Self::new(config.rate_limits.clone())
+    }
+
+    /// Name reported in the Inspector `/limits` payload.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set a TTL for idle buckets. See [`RateLimitGuard::with_bucket_ttl`].
+    pub fn with_bucket_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.bucket_ttl_ms = duration_millis_saturating(ttl);
+        self
+    }
+
+    /// Re-type the guard for another payload while sharing limits and buckets.
+    pub fn share<U>(&self) -> ConfiguredRateLimitGuard<U> {
+        ConfiguredRateLimitGuard {
+            name: self.name.clone(),
+            config: self.config.clone(),
+            buckets: self.buckets.clone(),
+            bucket_ttl_ms: self.bucket_ttl_ms,
+            pruned_buckets: self.pruned_buckets.clone(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the configuration currently in force.
+    pub fn current_config(&self) -> RateLimitsConfig {
+        self.config.borrow().clone()
+    }
+
+    /// Returns active-bucket and cumulative lazy-pruning metrics.
+    pub async fn stats(&self) -> RateLimitStats {
+        let active_buckets = u64::try_from(self.buckets.lock().await.len()).unwrap_or(u64::MAX);
+        RateLimitStats {
+            active_buckets,
+            pruned_buckets: self.pruned_buckets.load(Ordering::Relaxed),
+            bucket_ttl_ms: self.bucket_ttl_ms,
+        }
+    }
+}
+
+impl<T> Clone for ConfiguredRateLimitGuard<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            config: self.config.clone(),
+            buckets: self.buckets.clone(),
+            bucket_ttl_ms: self.bucket_ttl_ms,
+            pruned_buckets: self.pruned_buckets.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ConfiguredRateLimitGuard<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfiguredRateLimitGuard")
+            .field("name", &self.name)
+            .field("bucket_ttl_ms", &self.bucket_ttl_ms)
+            .finish()
+    }
+}
+
+impl<T> StartupPolicyProvider for ConfiguredRateLimitGuard<T> {
+    fn startup_policy(&self, profile: RuntimeProfile) -> StartupPolicyContribution {
+        let component = PolicyComponent::new("guard.configured_rate_limit");
+        let rules = PolicyField::new("rules");
+        let bucket_ttl_ms = PolicyField::new("bucket_ttl_ms");
+        let retention_bounded = PolicyField::new("retention_bounded");
+        let config = self.config.borrow().clone();
+        let rule_count = u64::from(config.default.is_some())
+            + config.tiers.len() as u64
+            + config
+                .routes
+                .values()
+                .map(|route| u64::from(route.default.is_some()) + route.tiers.len() as u64)
+                .sum::<u64>();
+        let mut violations = Vec::new();
+        if !config.invalid_rules().is_empty() {
+            violations.push((StartupPolicyCode::ConfigValueInvalid, rules));
+        }
+        if profile == RuntimeProfile::Production && self.bucket_ttl_ms == 0 {
+            violations.push((
+                StartupPolicyCode::LocalRateLimitUnbounded,
+                retention_bounded,
+            ));
+        }
+
+        StartupPolicyContribution::new(
+            component,
+            vec![
+                PolicyObservation::new(rules, PolicyValue::Count(rule_count)),
+                PolicyObservation::new(bucket_ttl_ms, PolicyValue::DurationMs(self.bucket_ttl_ms)),
+                PolicyObservation::new(
+                    retention_bounded,
+                    PolicyValue::Bool(self.bucket_ttl_ms > 0),
+                ),
+            ],
+            violations,
+        )
+    }
+}
+
+#[async_trait]
+impl<T> RateLimitStateReader for ConfiguredRateLimitGuard<T>
+where
+    T: Send + Sync + 'static,
+{
+    async fn rate_limit_state(&self) -> RateLimitState {
+        let config = self.current_config();
+        let now = Instant::now();
+        let mut buckets: Vec<RateLimitBucketState> = self
+            .buckets
+            .lock()
+            .await
+            .iter()
+            .filter_map(|(key, bucket)| {
+                let hit = config.resolve(key.route.as_deref(), key.tier.as_deref())?;
+                let rate = hit.rule.max_requests as f64 / hit.rule.window_ms as f64;
+                let elapsed_ms = now.duration_since(bucket.last_refill).as_millis() as f64;
+                Some(RateLimitBucketState {
+                    route: key.route.clone(),
+                    tier: key.tier.clone(),
+                    client: key.client.clone(),
+                    tokens_remaining: (bucket.tokens + elapsed_ms * rate)
+                        .min(hit.rule.max_requests as f64),
+                    max_requests: hit.rule.max_requests,
+                    window_ms: hit.rule.window_ms,
+                })
+            })
+            .collect();
+        buckets.sort_by(|a, b| (&a.route, &a.tier, &a.client).cmp(&(&b.route, &b.tier, &b.client)));
+        RateLimitState {
+            limiter: self.name.clone(),
+            config,
+            buckets,
+        }
+    }
+}
+
+#[async_trait]
+impl<T> Transition<T, T> for ConfiguredRateLimitGuard<T>
+where
+    T: Send + Sync + 'static,
+{
+    type Error = String;
+    type Resources = ();
+
+    async fn run(
+        &self,
+        input: T,
+        _resources: &Self::Resources,
+        bus: &mut Bus,
+    ) -> Outcome<T, Self::Error> {
+        let route = bus.read::<RateLimitRoute>().map(|r| r.0.clone());
+        let tier = bus.read::<TenantTier>().map(|t| t.0.clone());
+        let Some(hit) = self
+            .config
+            .borrow()
+            .resolve(route.as_deref(), tier.as_deref())
+        else {
+            return Outcome::next(input);
+        };
+        let client = bus
+            .read::<ClientIdentity>()
+            .map(|c| c.0.clone())
+            .unwrap_or_else(|| "anonymous".to_string());
+
+        let mut buckets = self.buckets.lock().await;
+        let now = Instant::now();
+
+        if self.bucket_ttl_ms > 0 {
+            let ttl = std::time::Duration::from_millis(self.bucket_ttl_ms);
+            let previous_len = buckets.len();
+            buckets.retain(|_, b| now.duration_since(b.last_refill) < ttl);
+            let pruned = previous_len.saturating_sub(buckets.len());
+            if pruned > 0 {
+                let pruned = u64::try_from(pruned).unwrap_or(u64::MAX);
+                self.pruned_buckets.fetch_add(pruned, Ordering::Relaxed);
+                tracing::debug!(pruned_buckets = pruned, "Pruned idle rate-limit buckets");
+            }
+        }
+
+        let max = hit.rule.max_requests as f64;
+        let rate = max / hit.rule.window_ms as f64 * 1000.0;
+        let key = ScopedBucketKey {
+            route: hit.route,
+            tier: hit.tier,
+            client,
+        };
+        let bucket = buckets.entry(key).or_insert(RateBucket {
+            tokens: max,
+            last_refill: now,
+        });
+
+        let elapsed_ms = now.duration_since(bucket.last_refill).as_millis() as f64;
+        bucket.tokens = (bucket.tokens + elapsed_ms * rate / 1000.0).min(max);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Outcome::next(input)
+        } else {
+            let retry_after = ((1.0 - bucket.tokens) / rate * 1000.0) as u64;
+            Outcome::fault(format!(
+                "Rate limit exceeded. Retry after {}ms",
+                retry_after
+            ))
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // SecurityHeadersGuard
 // ---------------------------------------------------------------------------
@@ -1567,8 +1909,7 @@ where
 // ---------------------------------------------------------------------------
 
 /// Bus-injectable type representing the `Idempotency-Key` header value.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct IdempotencyKey(pub String);
+pub use ranvier_core::idempotency::IdempotencyKey;
 
 /// Cached response from a previous idempotent request.
 ///
@@ -1859,11 +2200,12 @@ pub mod prelude {
     pub use crate::{
         AcceptEncoding, AccessLogEntry, AccessLogGuard, AccessLogRequest, AuthGuard, AuthStrategy,
         AuthorizationHeader, ClientIdentity, ClientIp, CompressionConfig, CompressionEncoding,
-        CompressionGuard, ContentLength, ContentTypeGuard, CorsConfig, CorsGuard, CorsHeaders,
-        IdempotencyCache, IdempotencyCacheStats, IdempotencyCachedResponse, IdempotencyGuard,
-        IdempotencyKey, IpFilterGuard, RateLimitGuard, RequestContentType, RequestId,
-        RequestIdGuard, RequestOrigin, RequestSizeLimitGuard, SecurityHeaders,
-        SecurityHeadersGuard, SecurityPolicy, TimeoutDeadline, TimeoutGuard,
+        CompressionGuard, ConfiguredRateLimitGuard, ContentLength, ContentTypeGuard, CorsConfig,
+        CorsGuard, CorsHeaders, IdempotencyCache, IdempotencyCacheStats, IdempotencyCachedResponse,
+        IdempotencyGuard, IdempotencyKey, IpFilterGuard, RateLimitConfigHandle, RateLimitGuard,
+        RateLimitRoute, RequestContentType, RequestId, RequestIdGuard, RequestOrigin,
+        RequestSizeLimitGuard, SecurityHeaders, SecurityHeadersGuard, SecurityPolicy,
+        TimeoutDeadline, TimeoutGuard,
     };
 
     #[cfg(feature = "advanced")]
@@ -1943,6 +2285,121 @@ mod tests {
         assert!(matches!(result, Outcome::Fault(_)));
     }
 
+    fn configured_limits() -> RateLimitsConfig {
+        let mut config = RateLimitsConfig {
+            default: Some(ranvier_core::rate_limit::RateLimitRule::new(5, 60000)),
+            ..Default::default()
+        };
+        config.routes.insert(
+            "/api/orders".into(),
+            ranvier_core::rate_limit::RouteRateLimits {
+                default: Some(ranvier_core::rate_limit::RateLimitRule::new(1, 60000)),
+                tiers: [(
+                    "premium".to_string(),
+                    ranvier_core::rate_limit::RateLimitRule::new(3, 60000),
+                )]
+                .into(),
+            },
+        );
+        config
+    }
+
+    #[tokio::test]
+    async fn configured_rate_limit_applies_route_and_tier() {
+        let (guard, _handle) = ConfiguredRateLimitGuard::<String>::new(configured_limits());
+        let mut bus = Bus::new();
+        bus.insert(ClientIdentity("user1".into()));
+        bus.insert(RateLimitRoute("/api/orders".into()));
+        assert!(matches!(
+            guard.run("1".into(), &(), &mut bus).await,
+            Outcome::Next(_)
+        ));
+        assert!(matches!(
+            guard.run("2".into(), &(), &mut bus).await,
+            Outcome::Fault(_)
+        ));
+
+        bus.insert(TenantTier::new("premium"));
+        for _ in 0..3 {
+            assert!(matches!(
+                guard.run("p".into(), &(), &mut bus).await,
+                Outcome::Next(_)
+            ));
+        }
+        assert!(matches!(
+            guard.run("p".into(), &(), &mut bus).await,
+            Outcome::Fault(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn configured_rate_limit_hot_reload_takes_effect() {
+        let (guard, handle) = ConfiguredRateLimitGuard::<String>::new(RateLimitsConfig::default());
+        let mut bus = Bus::new();
+        bus.insert(ClientIdentity("user1".into()));
+        bus.insert(RateLimitRoute("/api/orders".into()));
+        // No rules → unlimited.
+        for _ in 0..10 {
+            assert!(matches!(
+                guard.run("x".into(), &(), &mut bus).await,
+                Outcome::Next(_)
+            ));
+        }
+
+        handle.update(configured_limits()).unwrap();
+        assert!(matches!(
+            guard.run("x".into(), &(), &mut bus).await,
+            Outcome::Next(_)
+        ));
+        assert!(matches!(
+            guard.run("x".into(), &(), &mut bus).await,
+            Outcome::Fault(_)
+        ));
+
+        let mut invalid = configured_limits();
+        invalid.default = Some(ranvier_core::rate_limit::RateLimitRule::new(0, 0));
+        assert!(handle.update(invalid).is_err());
+        assert_eq!(guard.current_config(), configured_limits());
+    }
+
+    #[tokio::test]
+    async fn configured_rate_limit_reloads_from_file() {
+        let path = std::env::temp_dir().join(format!(
+            "ranvier-guard-limits-{}.toml",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::write(
+            &path,
+            "[rate_limits]\ndefault = { max_requests = 7, window_ms = 1000 }\n",
+        )
+        .unwrap();
+        let (guard, handle) = ConfiguredRateLimitGuard::<String>::new(RateLimitsConfig::default());
+        handle.reload_from_file(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            guard.current_config().default.map(|rule| rule.max_requests),
+            Some(7)
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_rate_limit_reports_bucket_state() {
+        let (guard, _handle) = ConfiguredRateLimitGuard::<String>::new(configured_limits());
+        let guard = guard.with_name("api");
+        let mut bus = Bus::new();
+        bus.insert(ClientIdentity("user1".into()));
+        let _ = guard.run("x".into(), &(), &mut bus).await;
+
+        let state = guard.rate_limit_state().await;
+        assert_eq!(state.limiter, "api");
+        assert_eq!(state.buckets.len(), 1);
+        let bucket = &state.buckets[0];
+        assert_eq!(bucket.client, "user1");
+        assert_eq!(bucket.route, None);
+        assert_eq!(bucket.max_requests, 5);
+        assert!(bucket.tokens_remaining < 5.0);
+    }
+
     #[tokio::test]
     async fn security_headers_injects_policy() {
         let guard = SecurityHeadersGuard::<String>::new(SecurityPolicy::default());
@@ -0,0 +1,244 @@
+//! Registry of active WebSocket connections.
+//!
+//! [`ConnectionRegistry`] is a Bus resource shared by every request and
+//! WebSocket session of an ingress. Sessions opened through
+//! [`HttpIngress::ws`](crate::HttpIngress::ws) register themselves (with the
+//! tenant resolved by Bus injectors and guards, when present) and are removed
+//! when the session handler returns. Any Transition can then push to clients
+//! without holding the connection:
+//!
+//! ```rust,ignore
+//! let registry = ConnectionRegistry::new();
+//!
+//! Ranvier::http()
+//!     .connection_registry(registry.clone())
+//!     .ws("/chat", chat_session)
+//!     .post("/rooms/:room/messages", post_message_circuit);
+//!
+//! // inside a Transition
+//! let registry = bus.read::<ConnectionRegistry>().expect("registry installed");
+//! registry.broadcast_to_topic("room:42", WebSocketEvent::text("new message"));
+//! ```
+//!
+//! Sends are queued per connection and never block the caller; a background
+//! task writes them to the socket in order.
+
+use crate::ingress::{WebSocketEvent, WebSocketSessionContext};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Snapshot of a registered connection.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConnectionInfo {
+    pub connection_id: Uuid,
+    pub path: String,
+    pub tenant: Option<String>,
+    pub subscriptions: BTreeSet<String>,
+    pub connected_at: SystemTime,
+}
+
+/// Error returned by targeted sends.
+#[derive(Debug, thiserror::Error)]
+pub enum ConnectionSendError {
+    #[error("connection {0} is not registered")]
+    NotFound(Uuid),
+    #[error("connection {0} is closed")]
+    Closed(Uuid),
+    #[error("json serialization failed: {0}")]
+    JsonSerialize(#[source] serde_json::Error),
+}
+
+struct ConnectionEntry {
+    info: ConnectionInfo,
+    outbound: mpsc::UnboundedSender<WebSocketEvent>,
+}
+
+/// Shared map of active WebSocket connections with send helpers.
+#[derive(Clone, Default)]
+pub struct ConnectionRegistry {
+    connections: Arc<RwLock<HashMap<Uuid, ConnectionEntry>>>,
+}
+
+impl std::fmt::Debug for ConnectionRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionRegistry")
+            .field("connections", &self.len())
+            .finish()
+    }
+}
+
+impl ConnectionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a connection; events sent to it are delivered on the returned receiver.
+    ///
+    /// Registering an ID again replaces the previous entry.
+    pub fn register(
+        &self,
+        session: &WebSocketSessionContext,
+        tenant: Option<String>,
+    ) -> mpsc::UnboundedReceiver<WebSocketEvent> {
+        let (outbound, receiver) = mpsc::unbounded_channel();
+        let entry = ConnectionEntry {
+            info: ConnectionInfo {
+                connection_id: session.connection_id(),
+                path: session.path().to_string(),
+                tenant,
+                subscriptions: BTreeSet::new(),
+                connected_at: SystemTime::now(),
+            },
+            outbound,
+        };
+        self.write().insert(session.connection_id(), entry);
+        receiver
+    }
+
+    /// Remove a connection; returns `false` when it was not registered.
+    pub fn unregister(&self, connection_id: Uuid) -> bool {
+        self.write().remove(&connection_id).is_some()
+    }
+
+    pub fn get(&self, connection_id: Uuid) -> Option<ConnectionInfo> {
+        self.read()
+            .get(&connection_id)
+            .map(|entry| entry.info.clone())
+    }
+
+    /// All registered connections, oldest first.
+    pub fn connections(&self) -> Vec<ConnectionInfo> {
+        let mut infos: Vec<_> = self
+            .read()
+            .values()
+            .map(|entry| entry.info.clone())
+            .collect();
+        infos.sort_by_key(|info| info.connected_at);
+        infos
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    pub fn set_tenant(&self, connection_id: Uuid, tenant: Option<String>) -> bool {
+        self.update(connection_id, |info| info.tenant = tenant)
+    }
+
+    /// Subscribe a connection to a topic; returns `false` for unknown connections.
+    pub fn subscribe(&self, connection_id: Uuid, topic: impl Into<String>) -> bool {
+        let topic = topic.into();
+        self.update(connection_id, |info| {
+            info.subscriptions.insert(topic);
+        })
+    }
+
+    pub fn unsubscribe(&self, connection_id: Uuid, topic: &str) -> bool {
+        self.update(connection_id, |info| {
+            info.subscriptions.remove(topic);
+        })
+    }
+
+    /// Connections subscribed to `topic`.
+    pub fn subscribers(&self, topic: &str) -> Vec<Uuid> {
+        self.read()
+            .values()
+            .filter(|entry| entry.info.subscriptions.contains(topic))
+            .map(|entry| entry.info.connection_id)
+            .collect()
+    }
+
+    /// Queue an event for one connection.
+    pub fn send_to(
+        &self,
+        connection_id: Uuid,
+        event: WebSocketEvent,
+    ) -> Result<(), ConnectionSendError> {
+        let sent = {
+            let connections = self.read();
+            let entry = connections
+                .get(&connection_id)
+                .ok_or(ConnectionSendError::NotFound(connection_id))?;
+            entry.outbound.send(event).is_ok()
+        };
+        if sent {
+            Ok(())
+        } else {
+            self.unregister(connection_id);
+            Err(ConnectionSendError::Closed(connection_id))
+        }
+    }
+
+    pub fn send_json_to<T: Serialize>(
+        &self,
+        connection_id: Uuid,
+        value: &T,
+    ) -> Result<(), ConnectionSendError> {
+        let event = WebSocketEvent::json(value).map_err(ConnectionSendError::JsonSerialize)?;
+        self.send_to(connection_id, event)
+    }
+
+    /// Queue an event for every connection; returns the number of recipients.
+    pub fn broadcast(&self, event: WebSocketEvent) -> usize {
+        self.send_where(event, |_| true)
+    }
+
+    /// Queue an event for the subscribers of `topic`.
+    pub fn broadcast_to_topic(&self, topic: &str, event: WebSocketEvent) -> usize {
+        self.send_where(event, |info| info.subscriptions.contains(topic))
+    }
+
+    /// Queue an event for every connection of `tenant`.
+    pub fn broadcast_to_tenant(&self, tenant: &str, event: WebSocketEvent) -> usize {
+        self.send_where(event, |info| info.tenant.as_deref() == Some(tenant))
+    }
+
+    fn send_where(&self, event: WebSocketEvent, filter: impl Fn(&ConnectionInfo) -> bool) -> usize {
+        let mut delivered = 0;
+        let mut closed = Vec::new();
+        for entry in self.read().values().filter(|entry| filter(&entry.info)) {
+            if entry.outbound.send(event.clone()).is_ok() {
+                delivered += 1;
+            } else {
+                closed.push(entry.info.connection_id);
+            }
+        }
+        if !closed.is_empty() {
+            let mut connections = self.write();
+            for id in closed {
+                connections.remove(&id);
+            }
+        }
+        delivered
+    }
+
+    fn update(&self, connection_id: Uuid, apply: impl FnOnce(&mut ConnectionInfo)) -> bool {
+        match self.write().get_mut(&connection_id) {
+            Some(entry) => {
+                apply(&mut entry.info);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<Uuid, ConnectionEntry>> {
+        self.connections
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<Uuid, ConnectionEntry>> {
+        self.connections
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
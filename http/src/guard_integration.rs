@@ -354,6 +354,37 @@ where
     }
 }
 
+impl<T> GuardIntegration for ranvier_guard::ConfiguredRateLimitGuard<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn register(self) -> RegisteredGuard {
+        // Share limits and buckets so config reloads and Inspector state apply.
+        let exec_guard = self.share::<()>();
+
+        RegisteredGuard {
+            bus_injectors: vec![Arc::new(|parts: &http::request::Parts, bus: &mut Bus| {
+                let identity = parts
+                    .headers
+                    .get("x-forwarded-for")
+                    .and_then(|v| v.to_str().ok())
+                    .map(|s| s.split(',').next().unwrap_or("").trim().to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                bus.insert(ranvier_guard::ClientIdentity(identity));
+                bus.insert(ranvier_guard::RateLimitRoute(parts.uri.path().to_string()));
+            })],
+            response_extractor: None,
+            response_body_transform: None,
+            exec: Arc::new(TransitionGuardExec {
+                guard: exec_guard,
+                default_status: http::StatusCode::TOO_MANY_REQUESTS,
+            }),
+            handles_preflight: false,
+            preflight_config: None,
+        }
+    }
+}
+
 impl<T> GuardIntegration for ranvier_guard::SecurityHeadersGuard<T>
 where
     T: Send + Sync + 'static,
@@ -43,6 +43,7 @@ use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::tungstenite::{Error as WsWireError, Message as WsWireMessage};
 use tracing::Instrument;
 
+use crate::connections::ConnectionRegistry;
 use crate::guard_integration::{
     GuardExec, GuardIntegration, PreflightConfig, RegisteredGuard, ResponseBodyTransformFn,
     ResponseExtractorFn, register_guard, registered_guard_label,
@@ -52,6 +53,10 @@ use crate::response::{
     HttpResponse, IntoResponse, boxed_body, build_response, json_error_response,
     outcome_to_json_response, outcome_to_response_with_error,
 };
+use crate::response_cache::{ResponseCache, apply_cache_headers};
+use crate::shutdown::{
+    ExecutionTracker, ShutdownHook, ShutdownReport, ShutdownReportBuilder, ShutdownReportCallback,
+};
 
 /// The Ranvier Framework entry point.
 ///
@@ -70,7 +75,7 @@ impl Ranvier {
 }
 
 /// Route handler type: boxed async function returning Response
-type RouteHandler<R> = Arc<
+pub(crate) type RouteHandler<R> = Arc<
     dyn Fn(http::request::Parts, &R) -> Pin<Box<dyn Future<Output = HttpResponse> + Send>>
         + Send
         + Sync,
@@ -133,6 +138,15 @@ impl ManagedTaskRegistry {
 
 fn install_request_cancellation(parts: &http::request::Parts, bus: &mut Bus) {
     if let Some(token) = parts.extensions.get::<RequestCancellationToken>() {
+        // A request timeout becomes the execution's Deadline, unless the
+        // Bus already carries a tighter one.
+        if let Some(at) = token.0.deadline() {
+            let deadline = match bus.read::<Deadline>().copied() {
+                Some(existing) => existing.min(Deadline::at(at)),
+                None => Deadline::at(at),
+            };
+            bus.insert(deadline);
+        }
         bus.set_cancellation_token(token.0.clone());
     }
 }
@@ -679,6 +693,14 @@ pub struct WebSocketSessionContext {
 }
 
 impl WebSocketSessionContext {
+    pub fn new(connection_id: uuid::Uuid, path: impl Into<String>, query: Option<String>) -> Self {
+        Self {
+            connection_id,
+            path: path.into(),
+            query,
+        }
+    }
+
     pub fn connection_id(&self) -> uuid::Uuid {
         self.connection_id
     }
@@ -738,7 +760,7 @@ type WsServerSource = futures_util::stream::SplitStream<WsServerStream>;
 
 /// WebSocket connection adapter bridging wire frames and EventSource/EventSink traits.
 pub struct WebSocketConnection {
-    sink: Mutex<WsServerSink>,
+    sink: Arc<Mutex<WsServerSink>>,
     source: Mutex<WsServerSource>,
     session: WebSocketSessionContext,
 }
@@ -747,7 +769,7 @@ impl WebSocketConnection {
     fn new(stream: WsServerStream, session: WebSocketSessionContext) -> Self {
         let (sink, source) = stream.split();
         Self {
-            sink: Mutex::new(sink),
+            sink: Arc::new(Mutex::new(sink)),
             source: Mutex::new(source),
             session,
         }
@@ -789,6 +811,28 @@ impl WebSocketConnection {
         }
     }
 
+    /// Write events queued through a `ConnectionRegistry` until the queue closes.
+    fn forward_outbound(
+        &self,
+        mut outbound: tokio::sync::mpsc::UnboundedReceiver<WebSocketEvent>,
+    ) -> tokio::task::JoinHandle<()> {
+        let sink = self.sink.clone();
+        let connection_id = self.session.connection_id;
+        tokio::spawn(async move {
+            while let Some(event) = outbound.recv().await {
+                let mut sink = sink.lock().await;
+                if let Err(error) = sink.send(event.into_wire_message()).await {
+                    tracing::warn!(
+                        ranvier.ws.connection_id = %connection_id,
+                        ranvier.ws.error = %error,
+                        "websocket registry send failed"
+                    );
+                    break;
+                }
+            }
+        })
+    }
+
     async fn recv_event(&mut self) -> Result<Option<WebSocketEvent>, WsWireError> {
         let mut source = self.source.lock().await;
         while let Some(item) = source.next().await {
@@ -1233,6 +1277,14 @@ pub struct HttpIngress<R = ()> {
     on_shutdown: Option<LifecycleHook>,
     /// Maximum time to wait for in-flight requests to drain.
     graceful_shutdown_timeout: Duration,
+    /// Named async hooks run after draining (telemetry flush, pool close, ...).
+    shutdown_hooks: Vec<ShutdownHook>,
+    /// Upper bound for each shutdown hook.
+    shutdown_hook_timeout: Duration,
+    /// Destination for the JSON shutdown report.
+    shutdown_report_path: Option<std::path::PathBuf>,
+    /// Callback receiving the final shutdown report.
+    on_shutdown_report: Option<ShutdownReportCallback>,
     /// Request-context to Bus injection hooks executed before each circuit run.
     bus_injectors: Vec<BusInjector>,
     /// Static asset serving configuration (serve_dir + SPA fallback).
@@ -1260,6 +1312,8 @@ pub struct HttpIngress<R = ()> {
     guard_body_transforms: Vec<ResponseBodyTransformFn>,
     /// CORS preflight configuration from guards that handle preflight.
     preflight_config: Option<PreflightConfig>,
+    /// Cache for circuits with a public `CachePolicy`.
+    response_cache: Option<ResponseCache>,
     _phantom: std::marker::PhantomData<R>,
 }
 
@@ -1277,6 +1331,10 @@ where
             on_start: None,
             on_shutdown: None,
             graceful_shutdown_timeout: Duration::from_secs(30),
+            shutdown_hooks: Vec::new(),
+            shutdown_hook_timeout: Duration::from_secs(10),
+            shutdown_report_path: None,
+            on_shutdown_report: None,
             bus_injectors: Vec::new(),
             static_assets: StaticAssetsConfig::default(),
             health: HealthConfig::default(),
@@ -1293,6 +1351,7 @@ where
             guard_response_extractors: Vec::new(),
             guard_body_transforms: Vec::new(),
             preflight_config: None,
+            response_cache: None,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -1347,6 +1406,62 @@ where
         self
     }
 
+    /// Register a named async hook run once after in-flight work drains.
+    ///
+    /// Hooks run sequentially in registration order, before the `on_shutdown`
+    /// callback. Typical uses are flushing telemetry, closing connection pools
+    /// and a final Timeline flush. Each result is recorded in the
+    /// [`ShutdownReport`]; a failing hook does not stop later ones.
+    pub fn shutdown_hook<F, Fut>(mut self, name: impl Into<String>, hook: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.shutdown_hooks.push(ShutdownHook {
+            name: name.into(),
+            run: Arc::new(move || Box::pin(hook())),
+        });
+        self
+    }
+
+    /// Register an application-wide resource for lifecycle management.
+    ///
+    /// Calls [`ResourceLifecycle::on_attach`] now and adds a shutdown hook,
+    /// named after the resource type, that calls
+    /// [`ResourceLifecycle::on_shutdown`]. Hand clones of the same `Arc` to
+    /// requests with [`bus_injector`](Self::bus_injector).
+    pub fn attach_resource<T>(self, resource: Arc<T>) -> Self
+    where
+        T: ResourceLifecycle,
+    {
+        resource.on_attach();
+        self.shutdown_hook(std::any::type_name::<T>(), move || {
+            let resource = resource.clone();
+            async move { resource.on_shutdown().await }
+        })
+    }
+
+    /// Bound each shutdown hook's runtime (default: 10 seconds).
+    pub fn shutdown_hook_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_hook_timeout = timeout;
+        self
+    }
+
+    /// Write the [`ShutdownReport`] as JSON to `path` when the server stops.
+    pub fn shutdown_report_path(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.shutdown_report_path = Some(path.into());
+        self
+    }
+
+    /// Register a callback receiving the [`ShutdownReport`] when the server stops.
+    pub fn on_shutdown_report<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ShutdownReport) + Send + Sync + 'static,
+    {
+        self.on_shutdown_report = Some(Arc::new(callback));
+        self
+    }
+
     /// Apply a `RanvierConfig` to this builder.
     ///
     /// Reads server settings (bind address, shutdown timeout) from the config
@@ -1386,6 +1501,31 @@ where
         self
     }
 
+    /// Share a [`ConnectionRegistry`] with every request and WebSocket session.
+    ///
+    /// The registry is inserted into each Bus, and sessions of `ws()` routes
+    /// registered after this call are tracked in it for their lifetime.
+    pub fn connection_registry(self, registry: ConnectionRegistry) -> Self {
+        self.bus_injector(move |_, bus| bus.insert(registry.clone()))
+    }
+
+    /// Layer application-level resources under every request Bus.
+    ///
+    /// The set is shared with each request rather than copied into it, see
+    /// [`Bus::use_app`]. Transitions read the entries but cannot mutate them.
+    pub fn app_resources(self, resources: ResourceSet) -> Self {
+        self.bus_injector(move |_, bus| bus.use_app(&resources))
+    }
+
+    /// Serve repeat requests of circuits with a public `CachePolicy` from `cache`.
+    ///
+    /// Applies to routes registered after this call. Without a cache, such
+    /// routes still emit their `Cache-Control`/`Vary` headers.
+    pub fn response_cache(mut self, cache: ResponseCache) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
     /// Register a request-context injector executed before each circuit run.
     ///
     /// Use this to bridge adapter-layer context (request extensions/headers)
@@ -1661,6 +1801,9 @@ where
     /// 1) a `WebSocketConnection` implementing `EventSource`/`EventSink`,
     /// 2) shared resources (`Arc<R>`),
     /// 3) a connection-scoped `Bus` with request injectors + `WebSocketSessionContext`.
+    ///
+    /// When a [`ConnectionRegistry`] is installed, the session is registered
+    /// under its tenant (`TenantId` from the Bus) until the handler returns.
     pub fn ws<H, Fut>(mut self, path: impl Into<String>, handler: H) -> Self
     where
         H: Fn(WebSocketConnection, Arc<R>, Bus) -> Fut + Send + Sync + 'static,
@@ -1709,6 +1852,8 @@ where
                         let mut req = Request::from_parts(parts, ());
                         let session = websocket_session_from_request(&req);
                         bus.insert(session.clone());
+                        let registry = bus.read::<ConnectionRegistry>().cloned();
+                        let tenant = ranvier_core::tenant::tenant_id(&bus).map(|id| id.to_string());
 
                         let (response, on_upgrade) = match websocket_upgrade_response(&mut req) {
                             Ok(result) => result,
@@ -1724,8 +1869,20 @@ where
                                         None,
                                     )
                                     .await;
+                                    let connection_id = session.connection_id();
                                     let connection = WebSocketConnection::new(stream, session);
+                                    let forwarder = registry.as_ref().map(|registry| {
+                                        connection.forward_outbound(
+                                            registry.register(connection.session(), tenant),
+                                        )
+                                    });
                                     ws_handler(connection, resources, bus).await;
+                                    if let Some(registry) = &registry {
+                                        registry.unregister(connection_id);
+                                    }
+                                    if let Some(forwarder) = forwarder {
+                                        forwarder.abort();
+                                    }
                                 }
                                 Err(error) => {
                                     tracing::warn!(
@@ -1874,6 +2031,36 @@ where
         )
     }
 
+    /// Emit the circuit's cache headers and, with a [`ResponseCache`]
+    /// installed, serve cacheable requests from it.
+    fn cache_aware_handler(
+        &self,
+        handler: RouteHandler<R>,
+        policy: Option<CachePolicy>,
+    ) -> RouteHandler<R> {
+        let Some(policy) = policy else {
+            return handler;
+        };
+        let policy = Arc::new(policy);
+        let cache = self.response_cache.clone();
+        Arc::new(move |parts: http::request::Parts, res: &R| {
+            let handler = handler.clone();
+            let policy = policy.clone();
+            let cache = cache.clone();
+            let res = res.clone();
+            Box::pin(async move {
+                match cache {
+                    Some(cache) => cache.serve(parts, res, handler, policy).await,
+                    None => {
+                        let mut response = handler(parts, &res).await;
+                        apply_cache_headers(&mut response, &policy);
+                        response
+                    }
+                }
+            }) as Pin<Box<dyn Future<Output = HttpResponse> + Send>>
+        })
+    }
+
     fn route_method_with_error_and_layers<Out, E, H>(
         mut self,
         method: Method,
@@ -1889,6 +2076,8 @@ where
         H: Fn(&E) -> HttpResponse + Send + Sync + 'static,
     {
         let path_str: String = path.into();
+        SchematicRegistry::global().observe(&circuit.schematic);
+        let cache_policy = circuit.schematic.cache_policy.clone();
         let circuit = Arc::new(circuit);
         let error_handler = Arc::new(error_handler);
         let route_bus_injectors = Arc::new(self.bus_injectors.clone());
@@ -1975,6 +2164,7 @@ where
             }) as Pin<Box<dyn Future<Output = HttpResponse> + Send>>
         });
 
+        let handler = self.cache_aware_handler(handler, cache_policy);
         self.routes.push(RouteEntry {
             method: method_for_pattern,
             pattern: RoutePattern::parse(&path_for_pattern),
@@ -2011,6 +2201,8 @@ where
     {
         let body_schema = serde_json::to_value(schemars::schema_for!(T)).ok();
         let path_str: String = path.into();
+        SchematicRegistry::global().observe(&circuit.schematic);
+        let cache_policy = circuit.schematic.cache_policy.clone();
         let circuit = Arc::new(circuit);
         let route_bus_injectors = Arc::new(self.bus_injectors.clone());
         let route_guard_execs = Arc::new(self.guard_execs.clone());
@@ -2125,6 +2317,7 @@ where
             }) as Pin<Box<dyn Future<Output = HttpResponse> + Send>>
         });
 
+        let handler = self.cache_aware_handler(handler, cache_policy);
         self.routes.push(RouteEntry {
             method: method_for_pattern,
             pattern: RoutePattern::parse(&path_for_pattern),
@@ -2252,6 +2445,8 @@ where
         E: Send + Sync + serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug + 'static,
     {
         let path_str: String = path.into();
+        SchematicRegistry::global().observe(&circuit.schematic);
+        let cache_policy = circuit.schematic.cache_policy.clone();
         let circuit = Arc::new(circuit);
         let route_bus_injectors = Arc::new(self.bus_injectors.clone());
         let route_guard_execs = Arc::new(self.guard_execs.clone());
@@ -2363,6 +2558,7 @@ where
             }) as Pin<Box<dyn Future<Output = HttpResponse> + Send>>
         });
 
+        let handler = self.cache_aware_handler(handler, cache_policy);
         self.routes.push(RouteEntry {
             method: method_for_pattern,
             pattern: RoutePattern::parse(&path_for_pattern),
@@ -2466,6 +2662,8 @@ where
         E: Send + Sync + Serialize + serde::de::DeserializeOwned + std::fmt::Debug + 'static,
     {
         let path_str: String = path.into();
+        SchematicRegistry::global().observe(&circuit.schematic);
+        let cache_policy = circuit.schematic.cache_policy.clone();
         let circuit = Arc::new(circuit);
         let route_bus_injectors = Arc::new(self.bus_injectors.clone());
         let route_guard_execs = Arc::new(self.guard_execs.clone());
@@ -2548,6 +2746,7 @@ where
             }) as Pin<Box<dyn Future<Output = HttpResponse> + Send>>
         });
 
+        let handler = self.cache_aware_handler(handler, cache_policy);
         self.routes.push(RouteEntry {
             method: method_for_pattern,
             pattern: RoutePattern::parse(&path_for_pattern),
@@ -2580,6 +2779,8 @@ where
     {
         let body_schema = serde_json::to_value(schemars::schema_for!(T)).ok();
         let path_str: String = path.into();
+        SchematicRegistry::global().observe(&circuit.schematic);
+        let cache_policy = circuit.schematic.cache_policy.clone();
         let circuit = Arc::new(circuit);
         let validate = Arc::new(validate);
         let route_bus_injectors = Arc::new(self.bus_injectors.clone());
@@ -2682,6 +2883,7 @@ where
             }) as Pin<Box<dyn Future<Output = HttpResponse> + Send>>
         });
 
+        let handler = self.cache_aware_handler(handler, cache_policy);
         self.routes.push(RouteEntry {
             method: method_for_pattern,
             pattern: RoutePattern::parse(&path_for_pattern),
@@ -3699,6 +3901,11 @@ where
         let on_start = self.on_start;
         let on_shutdown = self.on_shutdown;
         let graceful_shutdown_timeout = self.graceful_shutdown_timeout;
+        let shutdown_hooks = self.shutdown_hooks;
+        let shutdown_hook_timeout = self.shutdown_hook_timeout;
+        let shutdown_report_path = self.shutdown_report_path;
+        let on_shutdown_report = self.on_shutdown_report;
+        let execution_tracker = ExecutionTracker::default();
         let resources = Arc::new(resources);
 
         let listener = TcpListener::bind(addr).await?;
@@ -3745,6 +3952,7 @@ where
                     let preflight_config = preflight_config.clone();
                     let connection_cancellation = root_cancellation.clone();
                     let connection_tasks = task_registry.clone();
+                    let connection_tracker = execution_tracker.clone();
                     #[cfg(feature = "http3")]
                     let alt_svc_h3_port = self.alt_svc_h3_port;
 
@@ -3765,6 +3973,7 @@ where
                             connection_tasks,
                             #[cfg(feature = "http3")] alt_svc_h3_port,
                         );
+                        let service = track_executions(service, connection_tracker);
 
                         #[cfg(feature = "tls")]
                         if let Some(acceptor) = tls_acceptor {
@@ -3832,11 +4041,12 @@ where
             }
         }
 
+        let mut report_builder =
+            ShutdownReportBuilder::at_signal(&execution_tracker, graceful_shutdown_timeout);
         let drain_deadline = tokio::time::Instant::now()
             .checked_add(graceful_shutdown_timeout)
             .unwrap_or_else(tokio::time::Instant::now);
-        let _connections_timed_out =
-            drain_connections_until(&mut connections, drain_deadline).await;
+        let aborted_connections = drain_connections_until(&mut connections, drain_deadline).await;
         let task_report = task_registry.drain_until(drain_deadline).await;
         if task_report.forced_aborts > 0 {
             tracing::warn!(
@@ -3844,8 +4054,39 @@ where
                 "HTTP adapter child tasks exceeded the graceful shutdown budget"
             );
         }
+        report_builder.drained(
+            &execution_tracker,
+            aborted_connections,
+            task_report.forced_aborts,
+        );
 
         drop(resources);
+        let report = report_builder
+            .run_hooks(&shutdown_hooks, shutdown_hook_timeout)
+            .await;
+        tracing::info!(
+            clean = report.clean,
+            drained_executions = report.drained_executions,
+            aborted_executions = report.aborted_executions,
+            "Shutdown complete"
+        );
+        if let Some(path) = shutdown_report_path.as_ref() {
+            match serde_json::to_vec_pretty(&report) {
+                Ok(json) => {
+                    if let Err(error) = std::fs::write(path, json) {
+                        tracing::warn!(
+                            path = %path.display(),
+                            error = %error,
+                            "Failed to write shutdown report"
+                        );
+                    }
+                }
+                Err(error) => tracing::warn!(error = %error, "Failed to encode shutdown report"),
+            }
+        }
+        if let Some(callback) = on_shutdown_report.as_ref() {
+            callback(&report);
+        }
         if let Some(callback) = on_shutdown.as_ref() {
             callback();
         }
@@ -3943,6 +4184,11 @@ async fn apply_body_transforms(
     )
 }
 
+/// Count executions passing through `inner` for the shutdown report.
+fn track_executions(inner: BoxHttpService, tracker: ExecutionTracker) -> BoxHttpService {
+    BoxService::new(move |req: Request<Incoming>| tracker.track(inner.call(req)))
+}
+
 fn build_http_service<R>(
     routes: Arc<Vec<RouteEntry<R>>>,
     fallback: Option<RouteHandler<R>>,
@@ -4887,15 +5133,15 @@ async fn drain_connections(
     let deadline = tokio::time::Instant::now()
         .checked_add(graceful_shutdown_timeout)
         .unwrap_or_else(tokio::time::Instant::now);
-    drain_connections_until(connections, deadline).await
+    drain_connections_until(connections, deadline).await > 0
 }
 
 async fn drain_connections_until(
     connections: &mut tokio::task::JoinSet<()>,
     deadline: tokio::time::Instant,
-) -> bool {
+) -> usize {
     if connections.is_empty() {
-        return false;
+        return 0;
     }
 
     let drain_result = tokio::time::timeout_at(deadline, async {
@@ -4919,9 +5165,9 @@ async fn drain_connections_until(
                 tracing::warn!("Connection task abort join error: {:?}", err);
             }
         }
-        true
+        forced_aborts
     } else {
-        false
+        0
     }
 }
 
@@ -5716,6 +5962,99 @@ mod tests {
             .expect("server shutdown should succeed");
     }
 
+    #[tokio::test]
+    async fn connection_registry_pushes_to_subscribed_websocket_clients() {
+        let probe = std::net::TcpListener::bind("127.0.0.1:0").expect("bind probe");
+        let addr = probe.local_addr().expect("local addr");
+        drop(probe);
+
+        let registry = ConnectionRegistry::new();
+        let ingress = HttpIngress::<()>::new()
+            .bind(addr.to_string())
+            .bus_injector(|req, bus| {
+                if let Some(value) = req.headers.get("x-tenant-id").and_then(|v| v.to_str().ok()) {
+                    ranvier_core::tenant::inject_tenant_id(
+                        bus,
+                        ranvier_core::tenant::TenantId::new(value),
+                    );
+                }
+            })
+            .connection_registry(registry.clone())
+            .ws("/ws/rooms", |mut socket, _resources, bus| async move {
+                let registry = bus.read::<ConnectionRegistry>().expect("registry in bus");
+                registry.subscribe(socket.session().connection_id(), "room:alpha");
+                let _ = socket.send(WebSocketEvent::text("ready")).await;
+                while let Some(event) = socket.next_event().await {
+                    if event == WebSocketEvent::Close {
+                        break;
+                    }
+                }
+            });
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            ingress
+                .run_with_shutdown_signal((), async move {
+                    let _ = shutdown_rx.await;
+                })
+                .await
+        });
+
+        let ws_uri = format!("ws://{addr}/ws/rooms");
+        let (mut client, _response) = ws_connect_with_retry(&ws_uri, "acme").await;
+        let ready = client.next().await.expect("ready frame").expect("ready ok");
+        assert_eq!(ready, WsClientMessage::Text("ready".into()));
+
+        let connections = registry.connections();
+        assert_eq!(connections.len(), 1);
+        assert_eq!(connections[0].tenant.as_deref(), Some("acme"));
+        assert_eq!(connections[0].path, "/ws/rooms");
+
+        assert_eq!(
+            registry.broadcast_to_topic("room:beta", WebSocketEvent::text("x")),
+            0
+        );
+        assert_eq!(
+            registry.broadcast_to_topic("room:alpha", WebSocketEvent::text("to room")),
+            1
+        );
+        assert_eq!(
+            registry.broadcast_to_tenant("acme", WebSocketEvent::text("to tenant")),
+            1
+        );
+        registry
+            .send_json_to(
+                connections[0].connection_id,
+                &serde_json::json!({"direct": true}),
+            )
+            .expect("targeted send");
+
+        for expected in ["to room", "to tenant", r#"{"direct":true}"#] {
+            let frame = client
+                .next()
+                .await
+                .expect("pushed frame")
+                .expect("frame ok");
+            assert_eq!(frame, WsClientMessage::Text(expected.into()));
+        }
+
+        client.close(None).await.expect("close websocket");
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+        while !registry.is_empty() {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "closed session should be unregistered"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let _ = shutdown_tx.send(());
+        server
+            .await
+            .expect("server join")
+            .expect("server shutdown should succeed");
+    }
+
     #[tokio::test]
     async fn managed_shutdown_aborts_stuck_websocket_child_without_leak() {
         struct DropProbe(Arc<AtomicBool>);
@@ -5830,6 +6169,65 @@ mod tests {
         assert!(shutdown.load(Ordering::SeqCst));
     }
 
+    #[tokio::test]
+    async fn shutdown_hooks_run_and_report_is_written() {
+        let temp = tempdir().expect("tempdir");
+        let report_path = temp.path().join("shutdown.json");
+        let flushed = Arc::new(AtomicBool::new(false));
+        let reported = Arc::new(std::sync::Mutex::new(None::<ShutdownReport>));
+
+        struct Exporter(AtomicBool);
+
+        #[async_trait]
+        impl ResourceLifecycle for Exporter {
+            async fn on_shutdown(&self) -> Result<(), String> {
+                self.0.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+        }
+
+        let exporter = Arc::new(Exporter(AtomicBool::new(false)));
+        let flushed_flag = flushed.clone();
+        let reported_slot = reported.clone();
+        let ingress = HttpIngress::<()>::new()
+            .bind("127.0.0.1:0")
+            .graceful_shutdown(Duration::from_millis(50))
+            .shutdown_hook("flush-telemetry", move || {
+                let flushed_flag = flushed_flag.clone();
+                async move {
+                    flushed_flag.store(true, Ordering::SeqCst);
+                    Ok(())
+                }
+            })
+            .shutdown_hook("close-pool", || async { Err("pool busy".to_string()) })
+            .attach_resource(exporter.clone())
+            .shutdown_report_path(&report_path)
+            .on_shutdown_report(move |report| {
+                *reported_slot.lock().unwrap() = Some(report.clone());
+            });
+
+        ingress
+            .run_with_shutdown_signal((), async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            })
+            .await
+            .expect("server should exit gracefully");
+
+        assert!(flushed.load(Ordering::SeqCst));
+        assert!(exporter.0.load(Ordering::SeqCst));
+        let report = reported.lock().unwrap().clone().expect("report delivered");
+        assert_eq!(report.hooks.len(), 3);
+        assert!(report.hooks[0].ok);
+        assert_eq!(report.hooks[1].error.as_deref(), Some("pool busy"));
+        assert!(report.hooks[2].name.ends_with("Exporter"));
+        assert_eq!(report.aborted_executions, 0);
+        assert!(!report.clean);
+
+        let written: ShutdownReport =
+            serde_json::from_slice(&fs::read(&report_path).expect("report file")).unwrap();
+        assert_eq!(written, report);
+    }
+
     #[tokio::test]
     async fn graceful_shutdown_drains_in_flight_requests_before_exit() {
         #[derive(Clone)]
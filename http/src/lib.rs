@@ -26,12 +26,16 @@
 //! ```
 
 pub mod bus_ext;
+pub mod connections;
 pub mod extract;
 pub mod guard_integration;
 pub mod ingress;
+pub mod load_test;
 pub mod pagination;
 pub mod response;
+pub mod response_cache;
 pub mod service;
+pub mod shutdown;
 pub mod sse;
 
 #[cfg(feature = "htmx")]
@@ -41,6 +45,7 @@ pub mod http3;
 pub mod test_harness;
 
 pub use bus_ext::{BusHttpExt, json_outcome};
+pub use connections::{ConnectionInfo, ConnectionRegistry, ConnectionSendError};
 pub use extract::{
     CookieJar, DEFAULT_BODY_LIMIT, ExtractError, FromRequest, Header, Json, Path, Query,
 };
@@ -56,13 +61,19 @@ pub use ingress::{
     StaticAssetSource, StaticShell, WebSocketConnection, WebSocketError, WebSocketEvent,
     WebSocketSessionContext,
 };
+pub use load_test::{
+    LatencySummary, LoadReport, LoadTarget, LoadTest, OutcomeClassifier, OutcomeSummary,
+    RemoteTarget,
+};
 pub use pagination::{PageParams, Paginated};
 pub use response::{
     Html, HttpResponse, IntoProblemDetail, IntoResponse, ProblemDetail, json_error_response,
     outcome_to_json_problem_response, outcome_to_json_response, outcome_to_problem_response,
     outcome_to_response, outcome_to_response_with_error,
 };
+pub use response_cache::ResponseCache;
 pub use service::RanvierService;
+pub use shutdown::{ShutdownHookReport, ShutdownReport};
 pub use sse::{Sse, SseEvent, from_event_source, from_event_source_cancellable};
 pub use test_harness::{TestApp, TestHarnessError, TestRequest, TestResponse};
 
@@ -95,6 +106,7 @@ macro_rules! guards {
 /// Prelude module for convenient imports
 pub mod prelude {
     pub use crate::bus_ext::{BusHttpExt, json_outcome};
+    pub use crate::connections::{ConnectionInfo, ConnectionRegistry, ConnectionSendError};
     pub use crate::extract::{
         CookieJar, DEFAULT_BODY_LIMIT, ExtractError, FromRequest, Header, Json, Path, Query,
     };
@@ -110,13 +122,19 @@ pub mod prelude {
         StaticAssetSource, StaticShell, WebSocketConnection, WebSocketError, WebSocketEvent,
         WebSocketSessionContext,
     };
+    pub use crate::load_test::{
+        LatencySummary, LoadReport, LoadTarget, LoadTest, OutcomeClassifier, OutcomeSummary,
+        RemoteTarget,
+    };
     pub use crate::pagination::{PageParams, Paginated};
     pub use crate::response::{
         Html, HttpResponse, IntoProblemDetail, IntoResponse, ProblemDetail, json_error_response,
         outcome_to_json_problem_response, outcome_to_json_response, outcome_to_problem_response,
         outcome_to_response, outcome_to_response_with_error,
     };
+    pub use crate::response_cache::ResponseCache;
     pub use crate::service::RanvierService;
+    pub use crate::shutdown::{ShutdownHookReport, ShutdownReport};
     pub use crate::sse::{Sse, SseEvent, from_event_source, from_event_source_cancellable};
     pub use crate::test_harness::{TestApp, TestHarnessError, TestRequest, TestResponse};
 }
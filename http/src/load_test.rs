@@ -0,0 +1,377 @@
+//! Open-loop load generation from test fixtures.
+//!
+//! [`LoadTest`] replays the same [`TestRequest`] fixtures used by unit tests at
+//! a fixed request rate against any [`LoadTarget`]: the in-process
+//! [`TestApp`] or a running server through [`RemoteTarget`].
+//!
+//! ```rust,ignore
+//! let inputs = vec![MathInput { a: 1, b: 2 }, MathInput { a: 40, b: 2 }];
+//! let report = LoadTest::from_inputs(Method::POST, "/math", &inputs)?
+//!     .rps(500)
+//!     .duration(Duration::from_secs(60))
+//!     .run(Arc::new(TestApp::new(ingress, ())))
+//!     .await;
+//! println!("{report}");
+//! assert!(report.latency.p99_ms < 50.0);
+//! ```
+//!
+//! Requests are scheduled on a fixed clock and latency is measured from the
+//! scheduled send time, so time spent waiting for a free in-flight slot is
+//! included rather than hidden (no coordinated omission). Responses are
+//! grouped by status code unless a classifier maps them to circuit-level
+//! outcomes such as branch names.
+
+use crate::test_harness::{TestApp, TestHarnessError, TestRequest, TestResponse};
+use async_trait::async_trait;
+use http::Method;
+use ranvier_core::transition::ResourceRequirement;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Outcome label recorded for requests that never produced a response.
+pub const TRANSPORT_ERROR: &str = "transport_error";
+
+/// Something a [`LoadTest`] can send fixtures to.
+#[async_trait]
+pub trait LoadTarget: Send + Sync {
+    async fn send(&self, request: TestRequest) -> Result<TestResponse, TestHarnessError>;
+}
+
+#[async_trait]
+impl<R> LoadTarget for TestApp<R>
+where
+    R: ResourceRequirement + Clone + Send + Sync + 'static,
+{
+    async fn send(&self, request: TestRequest) -> Result<TestResponse, TestHarnessError> {
+        TestApp::send(self, request).await
+    }
+}
+
+/// Plain-HTTP/1.1 target at a remote `http://host:port` URL.
+///
+/// Each request uses its own connection (`Connection: close`); response
+/// bodies are read as-is, so chunked bodies are not decoded.
+#[derive(Clone, Debug)]
+pub struct RemoteTarget {
+    authority: String,
+    host: String,
+}
+
+impl RemoteTarget {
+    pub fn new(base_url: &str) -> Result<Self, TestHarnessError> {
+        let uri: http::Uri = base_url
+            .parse()
+            .map_err(|_| TestHarnessError::InvalidUrl(base_url.to_string()))?;
+        if uri.scheme_str() != Some("http") {
+            return Err(TestHarnessError::InvalidUrl(format!(
+                "{base_url} (only http:// is supported)"
+            )));
+        }
+        let authority = uri
+            .authority()
+            .ok_or_else(|| TestHarnessError::InvalidUrl(base_url.to_string()))?;
+        Ok(Self {
+            authority: format!(
+                "{}:{}",
+                authority.host(),
+                authority.port_u16().unwrap_or(80)
+            ),
+            host: authority.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl LoadTarget for RemoteTarget {
+    async fn send(&self, request: TestRequest) -> Result<TestResponse, TestHarnessError> {
+        let mut stream = tokio::net::TcpStream::connect(&self.authority).await?;
+        stream
+            .write_all(&request.to_http1_bytes(&self.host))
+            .await?;
+        let mut raw_response = Vec::new();
+        stream.read_to_end(&mut raw_response).await?;
+        TestResponse::from_http1_bytes(&raw_response)
+    }
+}
+
+/// Maps a response to the outcome label it is reported under.
+pub type OutcomeClassifier = Arc<dyn Fn(&TestResponse) -> String + Send + Sync>;
+
+/// Fixed-rate load run over a set of request fixtures.
+#[derive(Clone)]
+pub struct LoadTest {
+    fixtures: Vec<TestRequest>,
+    rps: u32,
+    duration: Duration,
+    max_in_flight: usize,
+    classifier: OutcomeClassifier,
+}
+
+impl std::fmt::Debug for LoadTest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadTest")
+            .field("fixtures", &self.fixtures.len())
+            .field("rps", &self.rps)
+            .field("duration", &self.duration)
+            .field("max_in_flight", &self.max_in_flight)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LoadTest {
+    /// Fixtures are sent round-robin. Defaults: 100 rps for 10 s, at most
+    /// 256 requests in flight, outcomes grouped by status code.
+    pub fn new(fixtures: impl IntoIterator<Item = TestRequest>) -> Self {
+        Self {
+            fixtures: fixtures.into_iter().collect(),
+            rps: 100,
+            duration: Duration::from_secs(10),
+            max_in_flight: 256,
+            classifier: Arc::new(|response| response.status().as_u16().to_string()),
+        }
+    }
+
+    /// One JSON request to `path` per typed input.
+    pub fn from_inputs<T: Serialize>(
+        method: Method,
+        path: &str,
+        inputs: &[T],
+    ) -> Result<Self, TestHarnessError> {
+        let fixtures = inputs
+            .iter()
+            .map(|input| TestRequest::new(method.clone(), path).json(input))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(fixtures))
+    }
+
+    pub fn rps(mut self, rps: u32) -> Self {
+        self.rps = rps.max(1);
+        self
+    }
+
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Report responses under custom labels, e.g. the branch a circuit took.
+    pub fn classify<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&TestResponse) -> String + Send + Sync + 'static,
+    {
+        self.classifier = Arc::new(classifier);
+        self
+    }
+
+    /// Number of requests a full run schedules.
+    pub fn planned_requests(&self) -> u64 {
+        if self.fixtures.is_empty() {
+            return 0;
+        }
+        (f64::from(self.rps) * self.duration.as_secs_f64()).round() as u64
+    }
+
+    pub async fn run<T>(&self, target: Arc<T>) -> LoadReport
+    where
+        T: LoadTarget + ?Sized + 'static,
+    {
+        let planned = self.planned_requests();
+        let slots = Arc::new(Semaphore::new(self.max_in_flight));
+        let mut ticker = tokio::time::interval(Duration::from_secs(1) / self.rps);
+        let mut tasks = JoinSet::new();
+        let mut samples = Vec::with_capacity(planned as usize);
+        let started = Instant::now();
+
+        for index in 0..planned {
+            ticker.tick().await;
+            let scheduled = Instant::now();
+            while let Some(sample) = tasks.try_join_next() {
+                samples.extend(sample.ok());
+            }
+            let Ok(slot) = slots.clone().acquire_owned().await else {
+                break;
+            };
+            let request = self.fixtures[index as usize % self.fixtures.len()].clone();
+            let target = target.clone();
+            let classifier = self.classifier.clone();
+            tasks.spawn(async move {
+                let result = target.send(request).await;
+                drop(slot);
+                let latency = scheduled.elapsed();
+                match result {
+                    Ok(response) => Sample {
+                        outcome: classifier(&response),
+                        latency,
+                        failed: response.status().is_server_error(),
+                    },
+                    Err(_) => Sample {
+                        outcome: TRANSPORT_ERROR.to_string(),
+                        latency,
+                        failed: true,
+                    },
+                }
+            });
+        }
+        while let Some(sample) = tasks.join_next().await {
+            samples.extend(sample.ok());
+        }
+
+        LoadReport::from_samples(samples, started.elapsed())
+    }
+}
+
+struct Sample {
+    outcome: String,
+    latency: Duration,
+    failed: bool,
+}
+
+/// Latency distribution in milliseconds.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    pub mean_ms: f64,
+}
+
+impl LatencySummary {
+    fn from_sorted(latencies: &[Duration]) -> Self {
+        if latencies.is_empty() {
+            return Self::default();
+        }
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * latencies.len() as f64).ceil() as usize;
+            millis(latencies[rank.clamp(1, latencies.len()) - 1])
+        };
+        let total: Duration = latencies.iter().sum();
+        Self {
+            p50_ms: percentile(50.0),
+            p90_ms: percentile(90.0),
+            p99_ms: percentile(99.0),
+            max_ms: millis(latencies[latencies.len() - 1]),
+            mean_ms: millis(total / latencies.len() as u32),
+        }
+    }
+}
+
+/// Count and latency of one outcome label.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OutcomeSummary {
+    pub count: u64,
+    /// Fraction of all requests.
+    pub share: f64,
+    pub latency: LatencySummary,
+}
+
+/// Result of a [`LoadTest`] run.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LoadReport {
+    pub requests: u64,
+    /// Transport failures plus 5xx responses.
+    pub errors: u64,
+    pub error_rate: f64,
+    pub elapsed_ms: f64,
+    pub achieved_rps: f64,
+    pub latency: LatencySummary,
+    pub outcomes: BTreeMap<String, OutcomeSummary>,
+}
+
+impl LoadReport {
+    fn from_samples(samples: Vec<Sample>, elapsed: Duration) -> Self {
+        let requests = samples.len() as u64;
+        let errors = samples.iter().filter(|sample| sample.failed).count() as u64;
+
+        let mut by_outcome: BTreeMap<String, Vec<Duration>> = BTreeMap::new();
+        let mut all = Vec::with_capacity(samples.len());
+        for sample in samples {
+            all.push(sample.latency);
+            by_outcome
+                .entry(sample.outcome)
+                .or_default()
+                .push(sample.latency);
+        }
+        all.sort();
+
+        let outcomes = by_outcome
+            .into_iter()
+            .map(|(outcome, mut latencies)| {
+                latencies.sort();
+                let summary = OutcomeSummary {
+                    count: latencies.len() as u64,
+                    share: ratio(latencies.len() as u64, requests),
+                    latency: LatencySummary::from_sorted(&latencies),
+                };
+                (outcome, summary)
+            })
+            .collect();
+
+        let elapsed_secs = elapsed.as_secs_f64();
+        Self {
+            requests,
+            errors,
+            error_rate: ratio(errors, requests),
+            elapsed_ms: millis(elapsed),
+            achieved_rps: if elapsed_secs > 0.0 {
+                requests as f64 / elapsed_secs
+            } else {
+                0.0
+            },
+            latency: LatencySummary::from_sorted(&all),
+            outcomes,
+        }
+    }
+}
+
+impl std::fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "requests: {} in {:.1}s ({:.1} rps), errors: {} ({:.2}%)",
+            self.requests,
+            self.elapsed_ms / 1000.0,
+            self.achieved_rps,
+            self.errors,
+            self.error_rate * 100.0
+        )?;
+        writeln!(
+            f,
+            "latency: p50 {:.2}ms  p90 {:.2}ms  p99 {:.2}ms  max {:.2}ms",
+            self.latency.p50_ms, self.latency.p90_ms, self.latency.p99_ms, self.latency.max_ms
+        )?;
+        for (outcome, summary) in &self.outcomes {
+            writeln!(
+                f,
+                "  {outcome:<20} {:>8} ({:>5.1}%)  p50 {:.2}ms  p99 {:.2}ms",
+                summary.count,
+                summary.share * 100.0,
+                summary.latency.p50_ms,
+                summary.latency.p99_ms
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
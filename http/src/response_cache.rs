@@ -0,0 +1,386 @@
+//! In-process cache for circuits that declare a [`CachePolicy`].
+//!
+//! Circuits opt in with `Axon::with_cache_policy`; every successful response
+//! of such a route carries the matching `Cache-Control` and `Vary` headers.
+//! Installing a [`ResponseCache`] on the ingress additionally serves repeat
+//! `GET`/`HEAD` requests of **public** policies from memory:
+//!
+//! ```rust,ignore
+//! Ranvier::http()
+//!     .response_cache(ResponseCache::new().with_max_entries(10_000))
+//!     .get("/catalog", catalog_circuit)
+//!     .run(())
+//!     .await?;
+//! ```
+//!
+//! Entries are keyed by method, path, query and the request values of the
+//! policy's `Vary` headers. Like a CDN in front of the service, a cache hit
+//! does not run route guards; requests carrying `Authorization` always bypass
+//! the cache, and responses that set cookies or whose body exceeds
+//! [`ResponseCache::with_max_body_bytes`] are never stored.
+//!
+//! Once an entry is older than its `s-maxage`/`max-age` but still inside the
+//! `stale-while-revalidate` window, it is served stale (`x-cache: STALE`)
+//! while a single background run of the circuit refreshes it.
+
+use crate::ingress::RouteHandler;
+use crate::response::{HttpResponse, boxed_body};
+use bytes::{Bytes, BytesMut};
+use futures_util::StreamExt;
+use http::header::{AUTHORIZATION, CACHE_CONTROL, HeaderName, HeaderValue, SET_COOKIE, VARY};
+use http::{HeaderMap, Method, StatusCode};
+use http_body_util::combinators::BoxBody;
+use http_body_util::{BodyExt, BodyStream, StreamBody};
+use ranvier_core::cache_policy::{CachePolicy, CacheVisibility};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const DEFAULT_MAX_ENTRIES: usize = 1024;
+const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+const X_CACHE: HeaderName = HeaderName::from_static("x-cache");
+
+struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    stored_at: Instant,
+    ttl: Duration,
+    stale_window: Duration,
+    revalidating: bool,
+}
+
+impl CachedResponse {
+    fn expired(&self, now: Instant) -> bool {
+        now.duration_since(self.stored_at) >= self.ttl + self.stale_window
+    }
+
+    fn to_response(&self, now: Instant, state: &'static str) -> HttpResponse {
+        let mut response = http::Response::new(boxed_body(self.body.clone()));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+        let age = now.duration_since(self.stored_at).as_secs();
+        response
+            .headers_mut()
+            .insert(http::header::AGE, HeaderValue::from(age));
+        response
+            .headers_mut()
+            .insert(X_CACHE, HeaderValue::from_static(state));
+        response
+    }
+}
+
+enum Lookup {
+    Fresh(HttpResponse),
+    Stale {
+        response: HttpResponse,
+        revalidate: bool,
+    },
+    Miss,
+}
+
+/// Shared in-memory store for cacheable circuit responses.
+#[derive(Clone)]
+pub struct ResponseCache {
+    entries: Arc<Mutex<HashMap<String, CachedResponse>>>,
+    max_entries: usize,
+    max_body_bytes: usize,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self {
+            entries: Arc::default(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+        }
+    }
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("entries", &self.len())
+            .field("max_entries", &self.max_entries)
+            .field("max_body_bytes", &self.max_body_bytes)
+            .finish()
+    }
+}
+
+impl ResponseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Upper bound on stored responses (default 1024). When full, expired
+    /// entries are dropped first, then the oldest one.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Largest response body that is stored (default 1 MiB). Longer
+    /// responses are streamed to the client without being cached.
+    pub fn with_max_body_bytes(mut self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes = max_body_bytes;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Run `handler` through the cache according to `policy`.
+    pub(crate) async fn serve<R>(
+        &self,
+        parts: http::request::Parts,
+        res: R,
+        handler: RouteHandler<R>,
+        policy: Arc<CachePolicy>,
+    ) -> HttpResponse
+    where
+        R: Clone + Send + Sync + 'static,
+    {
+        if !is_cacheable_request(&parts, &policy) {
+            let mut response = handler(parts, &res).await;
+            apply_cache_headers(&mut response, &policy);
+            return response;
+        }
+
+        let key = cache_key(&parts, &policy);
+        match self.lookup(&key) {
+            Lookup::Fresh(response) => response,
+            Lookup::Stale {
+                response,
+                revalidate,
+            } => {
+                if revalidate {
+                    let cache = self.clone();
+                    tokio::spawn(async move {
+                        let response = handler(parts, &res).await;
+                        let (_, stored) = cache.store(key.clone(), response, &policy).await;
+                        if !stored {
+                            cache.lock().remove(&key);
+                        }
+                    });
+                }
+                response
+            }
+            Lookup::Miss => {
+                let response = handler(parts, &res).await;
+                self.store(key, response, &policy).await.0
+            }
+        }
+    }
+
+    fn lookup(&self, key: &str) -> Lookup {
+        let now = Instant::now();
+        let mut entries = self.lock();
+        let Some(entry) = entries.get_mut(key) else {
+            return Lookup::Miss;
+        };
+        let age = now.duration_since(entry.stored_at);
+        if age < entry.ttl {
+            return Lookup::Fresh(entry.to_response(now, "HIT"));
+        }
+        if entry.expired(now) {
+            entries.remove(key);
+            return Lookup::Miss;
+        }
+        let revalidate = !entry.revalidating;
+        entry.revalidating = true;
+        Lookup::Stale {
+            response: entry.to_response(now, "STALE"),
+            revalidate,
+        }
+    }
+
+    /// Store a fresh response when it is cacheable; returns the response for
+    /// the client and whether it was stored.
+    async fn store(
+        &self,
+        key: String,
+        mut response: HttpResponse,
+        policy: &CachePolicy,
+    ) -> (HttpResponse, bool) {
+        apply_cache_headers(&mut response, policy);
+        if !is_cacheable_response(&response) {
+            return (response, false);
+        }
+
+        let (mut head, body) = response.into_parts();
+        let body = match buffer_body(body, self.max_body_bytes).await {
+            Ok(body) => body,
+            Err(body) => return (http::Response::from_parts(head, body), false),
+        };
+        head.headers
+            .insert(X_CACHE, HeaderValue::from_static("MISS"));
+
+        let entry = CachedResponse {
+            status: head.status,
+            headers: head.headers.clone(),
+            body: body.clone(),
+            stored_at: Instant::now(),
+            ttl: policy.shared_ttl(),
+            stale_window: Duration::from_secs(policy.stale_while_revalidate_secs.unwrap_or(0)),
+            revalidating: false,
+        };
+        self.insert(key, entry);
+        (http::Response::from_parts(head, boxed_body(body)), true)
+    }
+
+    fn insert(&self, key: String, entry: CachedResponse) {
+        let now = Instant::now();
+        let mut entries = self.lock();
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            entries.retain(|_, entry| !entry.expired(now));
+            if entries.len() >= self.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.stored_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(key, entry);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CachedResponse>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Read a body of at most `limit` bytes without trailers into memory.
+///
+/// Any other body is handed back whole, with the frames read so far put
+/// back in front of the rest.
+async fn buffer_body(
+    mut body: BoxBody<Bytes, Infallible>,
+    limit: usize,
+) -> Result<Bytes, BoxBody<Bytes, Infallible>> {
+    if http_body::Body::size_hint(&body).lower() > limit as u64 {
+        return Err(body);
+    }
+    let mut frames = Vec::new();
+    let mut len = 0;
+    while let Some(frame) = body.frame().await {
+        let Ok(frame) = frame;
+        len += frame.data_ref().map_or(0, Bytes::len);
+        let complete = frame.is_data();
+        frames.push(Ok(frame));
+        if len > limit || !complete {
+            let read = futures_util::stream::iter(frames);
+            return Err(BodyExt::boxed(StreamBody::new(
+                read.chain(BodyStream::new(body)),
+            )));
+        }
+    }
+    let mut buffered = BytesMut::with_capacity(len);
+    for frame in frames.into_iter().flatten() {
+        if let Ok(data) = frame.into_data() {
+            buffered.extend_from_slice(&data);
+        }
+    }
+    Ok(buffered.freeze())
+}
+
+/// Add `Cache-Control`/`Vary` for a successful response unless the handler
+/// already chose its own caching headers.
+pub(crate) fn apply_cache_headers<B>(response: &mut http::Response<B>, policy: &CachePolicy) {
+    if !response.status().is_success() || response.headers().contains_key(CACHE_CONTROL) {
+        return;
+    }
+    if let Ok(value) = HeaderValue::from_str(&policy.cache_control()) {
+        response.headers_mut().insert(CACHE_CONTROL, value);
+    }
+    if let Some(vary) = policy.vary_header()
+        && let Ok(value) = HeaderValue::from_str(&vary)
+    {
+        response.headers_mut().append(VARY, value);
+    }
+}
+
+fn is_cacheable_request(parts: &http::request::Parts, policy: &CachePolicy) -> bool {
+    policy.visibility == CacheVisibility::Public
+        && (parts.method == Method::GET || parts.method == Method::HEAD)
+        && !parts.headers.contains_key(AUTHORIZATION)
+}
+
+fn is_cacheable_response(response: &HttpResponse) -> bool {
+    let headers = response.headers();
+    let cache_control = headers
+        .get(CACHE_CONTROL)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    response.status() == StatusCode::OK
+        && !headers.contains_key(SET_COOKIE)
+        && cache_control.contains("public")
+        && !cache_control.contains("no-store")
+}
+
+fn cache_key(parts: &http::request::Parts, policy: &CachePolicy) -> String {
+    let mut key = format!(
+        "{} {}",
+        parts.method,
+        parts
+            .uri
+            .path_and_query()
+            .map(|pq| pq.as_str())
+            .unwrap_or("/")
+    );
+    for header in &policy.vary {
+        let value = parts
+            .headers
+            .get_all(header.as_str())
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+        key.push('\n');
+        key.push_str(&header.to_ascii_lowercase());
+        key.push(':');
+        key.push_str(&value);
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body::Frame;
+
+    fn chunked(chunks: &[&'static str]) -> BoxBody<Bytes, Infallible> {
+        let frames: Vec<Result<_, Infallible>> = chunks
+            .iter()
+            .map(|chunk| Ok(Frame::data(Bytes::from_static(chunk.as_bytes()))))
+            .collect();
+        BodyExt::boxed(StreamBody::new(futures_util::stream::iter(frames)))
+    }
+
+    #[tokio::test]
+    async fn streamed_body_over_the_limit_is_handed_back_whole() {
+        let body = chunked(&["abc", "def", "ghi"]);
+        let Err(body) = buffer_body(body, 4).await else {
+            panic!("body is over the limit");
+        };
+        let collected = body.collect().await.unwrap().to_bytes();
+        assert_eq!(collected, Bytes::from_static(b"abcdefghi"));
+
+        let buffered = buffer_body(chunked(&["ab", "cd"]), 4).await;
+        assert_eq!(buffered.ok(), Some(Bytes::from_static(b"abcd")));
+    }
+}
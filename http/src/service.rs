@@ -93,7 +93,10 @@ where
             let result = axon.execute(input, &resources, &mut bus).await;
 
             // 3. Egress Adapter: Outcome -> Response
-            let response = response_mapper(result, &bus);
+            let mut response = response_mapper(result, &bus);
+            if let Some(policy) = &axon.schematic.cache_policy {
+                crate::response_cache::apply_cache_headers(&mut response, policy);
+            }
             Ok(response)
         })
     }
@@ -129,6 +132,13 @@ where
                 "payload": payload
             }),
         ),
+        Outcome::Suspend(token, _) => json_value_response(
+            StatusCode::ACCEPTED,
+            serde_json::json!({
+                "kind": "suspend",
+                "token": token
+            }),
+        ),
         Outcome::Branch(branch_id, payload) => json_value_response(
             StatusCode::CONFLICT,
             serde_json::json!({
@@ -145,6 +155,24 @@ where
                 "payload": payload
             }),
         ),
+        Outcome::Retry { after } => {
+            let mut response = json_value_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                serde_json::json!({
+                    "kind": "retry",
+                    "after_ms": after.as_millis() as u64
+                }),
+            );
+            // Retry-After is in whole seconds; round up so clients never
+            // come back early.
+            let seconds = after.as_secs() + u64::from(after.subsec_nanos() > 0);
+            if let Ok(value) = http::HeaderValue::from_str(&seconds.to_string()) {
+                response
+                    .headers_mut()
+                    .insert(http::header::RETRY_AFTER, value);
+            }
+            response
+        }
     }
 }
 
@@ -263,6 +291,24 @@ mod tests {
         }
     }
 
+    #[derive(Clone)]
+    struct RetryTransition;
+
+    #[async_trait::async_trait]
+    impl Transition<(), serde_json::Value> for RetryTransition {
+        type Error = TestError;
+        type Resources = ();
+
+        async fn run(
+            &self,
+            _input: (),
+            _resources: &Self::Resources,
+            _bus: &mut Bus,
+        ) -> Outcome<serde_json::Value, Self::Error> {
+            Outcome::retry_after(std::time::Duration::from_millis(1500))
+        }
+    }
+
     async fn response_body_json(response: Response<Full<Bytes>>) -> serde_json::Value {
         let body = response.into_body().collect().await.unwrap().to_bytes();
         serde_json::from_slice(&body).unwrap()
@@ -286,6 +332,20 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn service_maps_unhandled_retry_to_503_with_retry_after() {
+        let axon = Axon::<(), (), TestError>::new("retry").then(RetryTransition);
+        let service =
+            RanvierService::new(axon, |_req: Request<Full<Bytes>>, _bus: &mut Bus| (), ());
+
+        let response = service.call(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[http::header::RETRY_AFTER], "2");
+        let body = response_body_json(response).await;
+        assert_eq!(body["kind"], "retry");
+        assert_eq!(body["after_ms"], 1500);
+    }
+
     #[tokio::test]
     async fn service_maps_fault_to_json_500() {
         let axon = Axon::<(), (), TestError>::new("fault").then(FaultTransition);
@@ -0,0 +1,305 @@
+//! # Shutdown Reporting
+//!
+//! Structured record of a graceful shutdown, produced by
+//! [`HttpIngress`](crate::ingress::HttpIngress) once the server stops.
+//!
+//! The report captures how many in-flight executions drained before the
+//! deadline, how many were aborted, and the result of every named shutdown
+//! hook (telemetry flush, pool close, final Timeline flush, ...). Operators can
+//! persist it with
+//! [`shutdown_report_path`](crate::ingress::HttpIngress::shutdown_report_path)
+//! and check [`ShutdownReport::clean`] to verify a restart during deploys.
+//!
+//! ```rust,ignore
+//! Ranvier::http()
+//!     .shutdown_hook("flush-telemetry", || async { flush().await.map_err(|e| e.to_string()) })
+//!     .shutdown_hook("close-db", move || {
+//!         let pool = pool.clone();
+//!         async move { pool.close().await; Ok(()) }
+//!     })
+//!     .shutdown_report_path("/var/run/ranvier/shutdown.json")
+//!     .run(())
+//!     .await?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+pub(crate) type ShutdownHookFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+pub(crate) type ShutdownHookFn = Arc<dyn Fn() -> ShutdownHookFuture + Send + Sync>;
+pub(crate) type ShutdownReportCallback = Arc<dyn Fn(&ShutdownReport) + Send + Sync>;
+
+/// A named async hook run once, in registration order, after draining.
+#[derive(Clone)]
+pub(crate) struct ShutdownHook {
+    pub(crate) name: String,
+    pub(crate) run: ShutdownHookFn,
+}
+
+/// Outcome of a single shutdown hook.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShutdownHookReport {
+    pub name: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Structured summary of a graceful shutdown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShutdownReport {
+    /// Unix epoch millis when the shutdown signal was received.
+    pub signal_at_ms: u64,
+    /// Unix epoch millis when hooks finished and the report was produced.
+    pub completed_at_ms: u64,
+    /// Configured graceful drain budget.
+    pub drain_timeout_ms: u64,
+    /// Executions still running when the signal arrived.
+    pub in_flight_at_signal: u64,
+    /// Executions that completed within the drain budget.
+    pub drained_executions: u64,
+    /// Executions that were cut off when the drain budget expired.
+    pub aborted_executions: u64,
+    /// Connections forcibly closed at the drain deadline.
+    pub aborted_connections: u64,
+    /// Adapter-owned SSE/WebSocket tasks aborted at the drain deadline.
+    pub aborted_tasks: u64,
+    /// Results of named shutdown hooks, in execution order.
+    pub hooks: Vec<ShutdownHookReport>,
+    /// `true` when nothing was aborted and every hook succeeded.
+    pub clean: bool,
+}
+
+/// Counts executions entering and leaving the HTTP service.
+#[derive(Clone, Default)]
+pub(crate) struct ExecutionTracker {
+    started: Arc<AtomicU64>,
+    finished: Arc<AtomicU64>,
+}
+
+impl ExecutionTracker {
+    pub(crate) fn start(&self) {
+        self.started.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn finish(&self) {
+        self.finished.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Count `execution` from its first poll until it completes or is
+    /// dropped, so a cancelled request still leaves the in-flight count.
+    pub(crate) fn track<F: Future>(&self, execution: F) -> impl Future<Output = F::Output> + use<F> {
+        let tracker = self.clone();
+        async move {
+            let _tracked = TrackedExecution::start(tracker);
+            execution.await
+        }
+    }
+
+    /// Executions that started but have not produced a response.
+    pub(crate) fn in_flight(&self) -> u64 {
+        self.started
+            .load(Ordering::SeqCst)
+            .saturating_sub(self.finished.load(Ordering::SeqCst))
+    }
+}
+
+/// One counted execution; finishes it when dropped.
+struct TrackedExecution(ExecutionTracker);
+
+impl TrackedExecution {
+    fn start(tracker: ExecutionTracker) -> Self {
+        tracker.start();
+        Self(tracker)
+    }
+}
+
+impl Drop for TrackedExecution {
+    fn drop(&mut self) {
+        self.0.finish();
+    }
+}
+
+/// Accumulates drain counters between the shutdown signal and hook execution.
+pub(crate) struct ShutdownReportBuilder {
+    signal_at_ms: u64,
+    drain_timeout: Duration,
+    in_flight_at_signal: u64,
+    aborted_executions: u64,
+    aborted_connections: u64,
+    aborted_tasks: u64,
+}
+
+impl ShutdownReportBuilder {
+    pub(crate) fn at_signal(tracker: &ExecutionTracker, drain_timeout: Duration) -> Self {
+        Self {
+            signal_at_ms: now_ms(),
+            drain_timeout,
+            in_flight_at_signal: tracker.in_flight(),
+            aborted_executions: 0,
+            aborted_connections: 0,
+            aborted_tasks: 0,
+        }
+    }
+
+    /// Record drain results; executions still unfinished here were aborted.
+    pub(crate) fn drained(
+        &mut self,
+        tracker: &ExecutionTracker,
+        aborted_connections: usize,
+        aborted_tasks: usize,
+    ) {
+        self.aborted_executions = tracker.in_flight();
+        self.aborted_connections = u64::try_from(aborted_connections).unwrap_or(u64::MAX);
+        self.aborted_tasks = u64::try_from(aborted_tasks).unwrap_or(u64::MAX);
+    }
+
+    pub(crate) async fn run_hooks(
+        self,
+        hooks: &[ShutdownHook],
+        timeout: Duration,
+    ) -> ShutdownReport {
+        let mut reports = Vec::with_capacity(hooks.len());
+        for hook in hooks {
+            let started = Instant::now();
+            let result = match tokio::time::timeout(timeout, (hook.run)()).await {
+                Ok(result) => result,
+                Err(_) => Err(format!("timed out after {}ms", timeout.as_millis())),
+            };
+            if let Err(error) = &result {
+                tracing::warn!(hook = %hook.name, error = %error, "Shutdown hook failed");
+            }
+            reports.push(ShutdownHookReport {
+                name: hook.name.clone(),
+                ok: result.is_ok(),
+                error: result.err(),
+                duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            });
+        }
+
+        let clean = self.aborted_executions == 0
+            && self.aborted_connections == 0
+            && self.aborted_tasks == 0
+            && reports.iter().all(|hook| hook.ok);
+        ShutdownReport {
+            signal_at_ms: self.signal_at_ms,
+            completed_at_ms: now_ms(),
+            drain_timeout_ms: u64::try_from(self.drain_timeout.as_millis()).unwrap_or(u64::MAX),
+            in_flight_at_signal: self.in_flight_at_signal,
+            drained_executions: self
+                .in_flight_at_signal
+                .saturating_sub(self.aborted_executions),
+            aborted_executions: self.aborted_executions,
+            aborted_connections: self.aborted_connections,
+            aborted_tasks: self.aborted_tasks,
+            hooks: reports,
+            clean,
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(name: &str, result: Result<(), String>) -> ShutdownHook {
+        ShutdownHook {
+            name: name.to_string(),
+            run: Arc::new(move || {
+                let result = result.clone();
+                Box::pin(async move { result })
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn report_is_clean_when_everything_drains() {
+        let tracker = ExecutionTracker::default();
+        tracker.start();
+        let mut builder = ShutdownReportBuilder::at_signal(&tracker, Duration::from_secs(1));
+        tracker.finish();
+        builder.drained(&tracker, 0, 0);
+        let report = builder
+            .run_hooks(&[hook("flush", Ok(()))], Duration::from_secs(1))
+            .await;
+
+        assert_eq!(report.in_flight_at_signal, 1);
+        assert_eq!(report.drained_executions, 1);
+        assert_eq!(report.aborted_executions, 0);
+        assert!(report.clean);
+        assert_eq!(report.hooks[0].name, "flush");
+    }
+
+    #[tokio::test]
+    async fn dropped_in_flight_execution_is_finished() {
+        let tracker = ExecutionTracker::default();
+        let mut execution = Box::pin(tracker.track(std::future::pending::<()>()));
+        assert!(futures_util::poll!(execution.as_mut()).is_pending());
+        assert_eq!(tracker.in_flight(), 1);
+
+        drop(execution);
+        assert_eq!(tracker.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn aborted_execution_and_failed_hook_mark_report_unclean() {
+        let tracker = ExecutionTracker::default();
+        tracker.start();
+        tracker.start();
+        let mut builder = ShutdownReportBuilder::at_signal(&tracker, Duration::from_millis(10));
+        tracker.finish();
+        builder.drained(&tracker, 1, 0);
+        let report = builder
+            .run_hooks(
+                &[
+                    hook("close-pool", Err("pool busy".into())),
+                    hook("ok", Ok(())),
+                ],
+                Duration::from_secs(1),
+            )
+            .await;
+
+        assert_eq!(report.drained_executions, 1);
+        assert_eq!(report.aborted_executions, 1);
+        assert_eq!(report.aborted_connections, 1);
+        assert!(!report.clean);
+        assert_eq!(report.hooks[0].error.as_deref(), Some("pool busy"));
+        assert!(report.hooks[1].ok);
+    }
+
+    #[tokio::test]
+    async fn hook_exceeding_timeout_is_reported() {
+        let slow = ShutdownHook {
+            name: "slow".to_string(),
+            run: Arc::new(|| {
+                Box::pin(async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    Ok(())
+                })
+            }),
+        };
+        let builder =
+            ShutdownReportBuilder::at_signal(&ExecutionTracker::default(), Duration::ZERO);
+        let report = builder.run_hooks(&[slow], Duration::from_millis(10)).await;
+        assert!(!report.hooks[0].ok);
+        assert!(
+            report.hooks[0]
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("timed out")
+        );
+    }
+}
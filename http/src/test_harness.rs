@@ -31,6 +31,8 @@ pub enum TestHarnessError {
     InvalidHeaderValue(#[from] http::header::InvalidHeaderValue),
     #[error("json serialization error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("invalid target url: {0}")]
+    InvalidUrl(String),
 }
 
 /// In-process HTTP test harness for `HttpIngress`.
@@ -160,7 +162,7 @@ impl TestRequest {
         Ok(self)
     }
 
-    fn to_http1_bytes(&self, host: &str) -> Vec<u8> {
+    pub(crate) fn to_http1_bytes(&self, host: &str) -> Vec<u8> {
         let path = if self.path.is_empty() {
             "/"
         } else {
@@ -217,7 +219,7 @@ pub struct TestResponse {
 }
 
 impl TestResponse {
-    fn from_http1_bytes(raw: &[u8]) -> Result<Self, TestHarnessError> {
+    pub(crate) fn from_http1_bytes(raw: &[u8]) -> Result<Self, TestHarnessError> {
         let delimiter = b"\r\n\r\n";
         let header_end = raw
             .windows(delimiter.len())
@@ -1,5 +1,5 @@
 use http::StatusCode;
-use ranvier_core::{Bus, Outcome, Transition};
+use ranvier_core::{Bus, Outcome, ResourceSet, Transition};
 use ranvier_http::prelude::*;
 use ranvier_runtime::Axon;
 
@@ -49,3 +49,31 @@ async fn bus_injector_moves_request_context_into_bus() {
     assert_eq!(response.status(), StatusCode::OK);
     assert_eq!(response.text().expect("utf8 body"), "alice");
 }
+
+#[tokio::test]
+async fn app_resources_are_visible_to_every_request() {
+    let ingress = Ranvier::http::<()>()
+        .app_resources(ResourceSet::new().with("service-account".to_string()))
+        .bus_injector(|req, bus| {
+            if let Some(user) = req.headers.get("x-user").and_then(|v| v.to_str().ok()) {
+                bus.insert(user.to_string());
+            }
+        })
+        .get(
+            "/whoami",
+            Axon::<(), (), String, ()>::new("WhoAmI").then(WhoAmI),
+        );
+
+    let app = TestApp::new(ingress, ());
+    let fallback = app
+        .send(TestRequest::get("/whoami"))
+        .await
+        .expect("request should succeed");
+    assert_eq!(fallback.text().expect("utf8 body"), "service-account");
+
+    let overridden = app
+        .send(TestRequest::get("/whoami").header("x-user", "alice"))
+        .await
+        .expect("request should succeed");
+    assert_eq!(overridden.text().expect("utf8 body"), "alice");
+}
@@ -0,0 +1,57 @@
+use ranvier_http::{
+    ConnectionRegistry, ConnectionSendError, WebSocketEvent, WebSocketSessionContext,
+};
+
+fn session(path: &str) -> WebSocketSessionContext {
+    WebSocketSessionContext::new(uuid::Uuid::new_v4(), path, None)
+}
+
+#[tokio::test]
+async fn broadcasts_reach_matching_connections_only() {
+    let registry = ConnectionRegistry::new();
+    let alice = session("/ws");
+    let bob = session("/ws");
+    let mut alice_rx = registry.register(&alice, Some("acme".into()));
+    let mut bob_rx = registry.register(&bob, Some("globex".into()));
+
+    assert!(registry.subscribe(alice.connection_id(), "room:1"));
+    assert!(!registry.subscribe(uuid::Uuid::new_v4(), "room:1"));
+    assert_eq!(registry.subscribers("room:1"), vec![alice.connection_id()]);
+
+    assert_eq!(
+        registry.broadcast_to_topic("room:1", WebSocketEvent::text("room")),
+        1
+    );
+    assert_eq!(
+        registry.broadcast_to_tenant("globex", WebSocketEvent::text("tenant")),
+        1
+    );
+    assert_eq!(registry.broadcast(WebSocketEvent::text("all")), 2);
+
+    assert_eq!(alice_rx.recv().await, Some(WebSocketEvent::text("room")));
+    assert_eq!(alice_rx.recv().await, Some(WebSocketEvent::text("all")));
+    assert_eq!(bob_rx.recv().await, Some(WebSocketEvent::text("tenant")));
+    assert_eq!(bob_rx.recv().await, Some(WebSocketEvent::text("all")));
+}
+
+#[tokio::test]
+async fn closed_connections_are_pruned() {
+    let registry = ConnectionRegistry::new();
+    let gone = session("/ws");
+    drop(registry.register(&gone, None));
+
+    assert!(matches!(
+        registry.send_to(gone.connection_id(), WebSocketEvent::text("hi")),
+        Err(ConnectionSendError::Closed(_))
+    ));
+    assert!(registry.is_empty());
+    assert!(matches!(
+        registry.send_to(gone.connection_id(), WebSocketEvent::text("hi")),
+        Err(ConnectionSendError::NotFound(_))
+    ));
+
+    let other = session("/ws");
+    drop(registry.register(&other, None));
+    assert_eq!(registry.broadcast(WebSocketEvent::text("hi")), 0);
+    assert!(registry.is_empty());
+}
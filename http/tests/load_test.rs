@@ -0,0 +1,82 @@
+use http::{Method, StatusCode};
+use ranvier_core::{Bus, Outcome, Transition};
+use ranvier_http::prelude::*;
+use ranvier_runtime::Axon;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+struct Divide {
+    a: i64,
+    b: i64,
+}
+
+#[derive(Clone)]
+struct SafeDivide;
+
+#[async_trait::async_trait]
+impl Transition<Divide, String> for SafeDivide {
+    type Error = String;
+    type Resources = ();
+
+    async fn run(
+        &self,
+        input: Divide,
+        _resources: &Self::Resources,
+        _bus: &mut Bus,
+    ) -> Outcome<String, Self::Error> {
+        if input.b == 0 {
+            Outcome::fault("division by zero".to_string())
+        } else {
+            Outcome::next((input.a / input.b).to_string())
+        }
+    }
+}
+
+#[tokio::test]
+async fn load_test_reports_latency_and_outcome_breakdown() {
+    let ingress = Ranvier::http::<()>().post_typed(
+        "/math",
+        Axon::<Divide, Divide, String, ()>::new("Divide").then(SafeDivide),
+    );
+    let app = Arc::new(TestApp::new(ingress, ()));
+
+    let inputs = [
+        Divide { a: 6, b: 3 },
+        Divide { a: 8, b: 2 },
+        Divide { a: 1, b: 0 },
+        Divide { a: 9, b: 3 },
+    ];
+    let report = LoadTest::from_inputs(Method::POST, "/math", &inputs)
+        .expect("fixtures serialize")
+        .rps(400)
+        .duration(Duration::from_millis(100))
+        .classify(|response| {
+            if response.status() == StatusCode::OK {
+                "quotient".to_string()
+            } else {
+                "fault".to_string()
+            }
+        })
+        .run(app)
+        .await;
+
+    assert_eq!(report.requests, 40);
+    assert_eq!(report.outcomes["quotient"].count, 30);
+    assert_eq!(report.outcomes["fault"].count, 10);
+    assert_eq!(report.errors, 10);
+    assert!((report.error_rate - 0.25).abs() < f64::EPSILON);
+    assert!(report.latency.p50_ms <= report.latency.p99_ms);
+    assert!(report.latency.p99_ms <= report.latency.max_ms);
+    assert!(report.to_string().contains("quotient"));
+}
+
+#[test]
+fn remote_target_requires_plain_http_url() {
+    assert!(RemoteTarget::new("http://127.0.0.1:3000").is_ok());
+    assert!(matches!(
+        RemoteTarget::new("https://example.com"),
+        Err(TestHarnessError::InvalidUrl(_))
+    ));
+}
@@ -0,0 +1,179 @@
+use http::StatusCode;
+use ranvier_core::cache_policy::CachePolicy;
+use ranvier_core::{Bus, Outcome, Transition};
+use ranvier_http::prelude::*;
+use ranvier_runtime::Axon;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[derive(Clone)]
+struct CountedGreeting {
+    runs: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl Transition<(), String> for CountedGreeting {
+    type Error = String;
+    type Resources = ();
+
+    async fn run(
+        &self,
+        _state: (),
+        _resources: &Self::Resources,
+        _bus: &mut Bus,
+    ) -> Outcome<String, Self::Error> {
+        let run = self.runs.fetch_add(1, Ordering::SeqCst) + 1;
+        Outcome::next(format!("run {run}"))
+    }
+}
+
+fn header<'a>(response: &'a TestResponse, name: &str) -> Option<&'a str> {
+    response.header(name).and_then(|value| value.to_str().ok())
+}
+
+fn greeting(runs: &Arc<AtomicUsize>, policy: CachePolicy) -> Axon<(), String, String, ()> {
+    Axon::<(), (), String, ()>::new("Greeting")
+        .then(CountedGreeting { runs: runs.clone() })
+        .with_cache_policy(policy)
+}
+
+#[tokio::test]
+async fn cache_policy_headers_are_emitted_without_a_cache() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let policy = CachePolicy::private(Duration::from_secs(30)).vary("Accept-Language");
+    let app = TestApp::new(
+        Ranvier::http::<()>().get("/greeting", greeting(&runs, policy)),
+        (),
+    );
+
+    for _ in 0..2 {
+        let response = app
+            .send(TestRequest::get("/greeting"))
+            .await
+            .expect("request should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            header(&response, "cache-control"),
+            Some("private, max-age=30")
+        );
+        assert_eq!(header(&response, "vary"), Some("Accept-Language"));
+        assert_eq!(header(&response, "x-cache"), None);
+    }
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn public_responses_are_served_from_the_response_cache() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let policy = CachePolicy::public(Duration::from_secs(60)).vary("Accept-Language");
+    let cache = ResponseCache::new();
+    let app = TestApp::new(
+        Ranvier::http::<()>()
+            .response_cache(cache.clone())
+            .get("/greeting", greeting(&runs, policy)),
+        (),
+    );
+
+    let first = app
+        .send(TestRequest::get("/greeting").header("accept-language", "en"))
+        .await
+        .expect("first request");
+    assert_eq!(header(&first, "x-cache"), Some("MISS"));
+    assert_eq!(header(&first, "cache-control"), Some("public, max-age=60"));
+    assert_eq!(first.text().expect("utf8 body"), "run 1");
+
+    let second = app
+        .send(TestRequest::get("/greeting").header("accept-language", "en"))
+        .await
+        .expect("second request");
+    assert_eq!(header(&second, "x-cache"), Some("HIT"));
+    assert!(header(&second, "age").is_some());
+    assert_eq!(second.text().expect("utf8 body"), "run 1");
+
+    let other_language = app
+        .send(TestRequest::get("/greeting").header("accept-language", "ko"))
+        .await
+        .expect("vary request");
+    assert_eq!(header(&other_language, "x-cache"), Some("MISS"));
+    assert_eq!(other_language.text().expect("utf8 body"), "run 2");
+
+    let authorized = app
+        .send(
+            TestRequest::get("/greeting")
+                .header("accept-language", "en")
+                .header("authorization", "Bearer token"),
+        )
+        .await
+        .expect("authorized request");
+    assert_eq!(header(&authorized, "x-cache"), None);
+    assert_eq!(authorized.text().expect("utf8 body"), "run 3");
+
+    assert_eq!(runs.load(Ordering::SeqCst), 3);
+    assert_eq!(cache.len(), 2);
+}
+
+#[tokio::test]
+async fn stale_entries_are_served_while_revalidating() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let policy =
+        CachePolicy::public(Duration::ZERO).stale_while_revalidate(Duration::from_secs(60));
+    let app = TestApp::new(
+        Ranvier::http::<()>()
+            .response_cache(ResponseCache::new())
+            .get("/greeting", greeting(&runs, policy)),
+        (),
+    );
+
+    let first = app
+        .send(TestRequest::get("/greeting"))
+        .await
+        .expect("first");
+    assert_eq!(first.text().expect("utf8 body"), "run 1");
+
+    let stale = app
+        .send(TestRequest::get("/greeting"))
+        .await
+        .expect("stale");
+    assert_eq!(header(&stale, "x-cache"), Some("STALE"));
+    assert_eq!(stale.text().expect("utf8 body"), "run 1");
+
+    for _ in 0..50 {
+        if runs.load(Ordering::SeqCst) == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    let refreshed = app
+        .send(TestRequest::get("/greeting"))
+        .await
+        .expect("refreshed");
+    assert_eq!(refreshed.text().expect("utf8 body"), "run 2");
+}
+
+#[tokio::test]
+async fn responses_over_the_body_limit_are_passed_through_uncached() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let policy = CachePolicy::public(Duration::from_secs(60));
+    let cache = ResponseCache::new().with_max_body_bytes(4);
+    let app = TestApp::new(
+        Ranvier::http::<()>()
+            .response_cache(cache.clone())
+            .get("/greeting", greeting(&runs, policy)),
+        (),
+    );
+
+    for run in 1..=2 {
+        let response = app
+            .send(TestRequest::get("/greeting"))
+            .await
+            .expect("request should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(header(&response, "x-cache"), None);
+        assert_eq!(response.text().expect("utf8 body"), format!("run {run}"));
+    }
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    assert!(cache.is_empty());
+}
@@ -23,7 +23,7 @@ pub use ranvier_std as std;
 // Candidate macros re-exported for facade-only consumers.
 #[cfg(feature = "streaming")]
 pub use ranvier_macros::streaming_transition;
-pub use ranvier_macros::{ResourceRequirement, transition};
+pub use ranvier_macros::{BranchKey, FromBus, ResourceRequirement, transition};
 
 // AuthContext and AuthScheme live in ranvier-core::iam (always available, no feature gate).
 pub use ranvier_core::iam::{AuthContext, AuthScheme};
@@ -34,7 +34,7 @@ pub use ranvier_core::{
 };
 #[cfg(feature = "http")]
 pub use ranvier_http::{
-    HttpIngress, HttpTaskDrainReport, Ranvier, RanvierService, RawIngressService,
+    HttpIngress, HttpTaskDrainReport, Ranvier, RanvierService, RawIngressService, ShutdownReport,
 };
 #[cfg(feature = "inspector")]
 pub use ranvier_inspector::{Inspector, StateInspector};
@@ -52,7 +52,7 @@ pub mod prelude {
     pub use ranvier_http::prelude::*;
     #[cfg(feature = "inspector")]
     pub use ranvier_inspector::{Inspector, StateInspector};
-    pub use ranvier_macros::{ResourceRequirement, transition};
+    pub use ranvier_macros::{BranchKey, FromBus, ResourceRequirement, transition};
     #[cfg(feature = "openapi")]
     pub use ranvier_openapi::prelude::*;
     pub use ranvier_runtime::prelude::*;
@@ -400,3 +400,193 @@ async fn test_audit_query_across_executions() {
     assert_eq!(read_events.len(), 1);
     assert_eq!(read_events[0].actor, "viewer");
 }
+
+// ── Typed branch keys (macros → core → runtime) ───────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, ranvier_macros::BranchKey)]
+enum Tier {
+    Free,
+    PremiumPlus,
+}
+
+#[tokio::test]
+async fn test_derived_branch_key_routes_branches() {
+    use ranvier_core::outcome::BranchKey;
+
+    assert_eq!(Tier::branch_ids(), ["free", "premium_plus"]);
+    assert_eq!(
+        Tier::from_branch_id("premium_plus"),
+        Some(Tier::PremiumPlus)
+    );
+
+    let axon = Axon::<u32, u32, String>::new("Pricing")
+        .then_fn("Classify", |amount: u32, _bus| {
+            if amount > 100 {
+                Outcome::branch(Tier::PremiumPlus, Some(serde_json::json!(amount)))
+            } else {
+                Outcome::next(amount)
+            }
+        })
+        .branch(|b| {
+            b.on(
+                Tier::PremiumPlus,
+                Axon::<u32, u32, String>::new("Premium").then_fn("Discount", |_: u32, _bus| {
+                    Outcome::next("premium".to_string())
+                }),
+            )
+            .otherwise(
+                Axon::<u32, u32, String>::new("Free").then_fn("FullPrice", |_: u32, _bus| {
+                    Outcome::next("free".to_string())
+                }),
+            )
+        });
+
+    let outcome = axon.execute(500, &(), &mut Bus::new()).await;
+    assert!(matches!(outcome, Outcome::Next(ref tier) if tier == "premium"));
+    let labels: Vec<_> = axon
+        .schematic
+        .edges
+        .iter()
+        .filter_map(|edge| edge.label.clone())
+        .collect();
+    assert!(labels.iter().any(|label| label.contains("premium_plus")));
+}
+
+#[ranvier_macros::transition]
+#[branch(Tier::PremiumPlus)]
+async fn classify_tier(amount: u32) -> Outcome<u32, String> {
+    if amount > 1000 {
+        return Outcome::branch(Tier::PremiumPlus, None);
+    }
+    if amount == 0 {
+        return Outcome::Branch("empty".to_string(), None);
+    }
+    Outcome::next(amount)
+}
+
+#[test]
+fn test_transition_macro_reports_branches_and_definition_site() {
+    use ranvier_core::schematic::EdgeType;
+
+    let axon = Axon::<u32, u32, String>::new("Tiers").then(classify_tier);
+    let branches: Vec<_> = axon
+        .schematic
+        .edges
+        .iter()
+        .filter_map(|edge| match &edge.kind {
+            EdgeType::Branch(id) => Some(id.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(branches, ["premium_plus", "empty"]);
+
+    // The node points at `classify_tier` itself, not at the `then` call.
+    let node = axon.schematic.nodes.last().unwrap();
+    let location = node.source_location.as_ref().unwrap();
+    assert!(location.file.ends_with("cross_crate_integration.rs"));
+    assert_eq!(
+        location.module_path.as_deref(),
+        Some("cross_crate_integration")
+    );
+    let source = include_str!("cross_crate_integration.rs");
+    let line = source.lines().nth(location.line as usize - 1).unwrap();
+    assert!(line.contains("async fn classify_tier"));
+}
+
+#[ranvier_macros::transition(owner = "payments-oncall", team = "payments", sla_ms = 250, tags = ["pii", "billing"])]
+async fn settle_invoice(amount: u32) -> Outcome<u32, String> {
+    Outcome::next(amount)
+}
+
+#[test]
+fn test_transition_macro_annotations_reach_the_schematic() {
+    let axon = Axon::<u32, u32, String>::new("Billing")
+        .then(settle_invoice)
+        .owner("billing-oncall")
+        .tag("pii");
+    let annotations = &axon.schematic.nodes.last().unwrap().metadata.annotations;
+    assert_eq!(annotations.owner.as_deref(), Some("billing-oncall"));
+    assert_eq!(annotations.team.as_deref(), Some("payments"));
+    assert_eq!(annotations.sla_ms, Some(250));
+    assert_eq!(annotations.tags, ["pii", "billing"]);
+
+    let json = serde_json::to_value(&axon.schematic).unwrap();
+    assert_eq!(
+        json["nodes"].as_array().unwrap().last().unwrap()["metadata"]["annotations"]["team"],
+        "payments"
+    );
+}
+
+/// Reserve stock for every line item before payment is taken.
+#[ranvier_macros::transition]
+async fn reserve_stock(amount: u32) -> Outcome<u32, String> {
+    Outcome::next(amount)
+}
+
+#[test]
+fn test_transition_doc_comment_reaches_diagrams() {
+    let axon = Axon::<u32, u32, String>::new("Fulfilment").then(reserve_stock);
+    let node = axon.schematic.nodes.last().unwrap();
+    let doc = "Reserve stock for every line item before payment is taken.";
+    assert_eq!(node.description.as_deref(), Some(doc));
+    assert_eq!(node.metadata.description.as_deref(), Some(doc));
+
+    assert!(
+        axon.schematic
+            .to_dot()
+            .contains(&format!("tooltip=\"{doc}\""))
+    );
+    assert!(
+        axon.schematic
+            .to_bpmn()
+            .contains(&format!("<bpmn:documentation>{doc}</bpmn:documentation>"))
+    );
+}
+
+// ── Aggregated Bus resources (macros → core → runtime) ────────────────────
+
+#[derive(Clone)]
+struct PricingConfig {
+    discount: u32,
+}
+
+#[derive(ranvier_macros::FromBus)]
+struct PricingDeps {
+    config: PricingConfig,
+    currency: String,
+    promo: ranvier_core::OptionalResource<u32>,
+}
+
+#[tokio::test]
+async fn test_derived_from_bus_extracts_all_members() {
+    use ranvier_core::from_bus::FromBus;
+
+    let axon = Axon::<u32, u32, String>::new("Checkout").then_fn("Price", |amount: u32, bus| {
+        match bus.extract::<PricingDeps>() {
+            Ok(deps) => Outcome::next(format!(
+                "{} {}",
+                amount - deps.config.discount - deps.promo.unwrap_or(0),
+                deps.currency
+            )),
+            Err(missing) => Outcome::fault(missing.to_string()),
+        }
+    });
+
+    let outcome = axon.execute(100, &(), &mut Bus::new()).await;
+    let Outcome::Fault(message) = outcome else {
+        panic!("expected a fault, got {outcome:?}");
+    };
+    assert!(message.contains("PricingConfig"), "{message}");
+    assert!(message.contains("alloc::string::String"), "{message}");
+
+    let mut bus = Bus::new();
+    bus.insert(PricingConfig { discount: 15 });
+    bus.insert(String::from("EUR"));
+    let outcome = axon.execute(100, &(), &mut bus).await;
+    assert!(matches!(outcome, Outcome::Next(ref price) if price == "85 EUR"));
+    bus.insert(5u32);
+    let outcome = axon.execute(100, &(), &mut bus).await;
+    assert!(matches!(outcome, Outcome::Next(ref price) if price == "80 EUR"));
+    // The optional promo is not a requirement.
+    assert_eq!(PricingDeps::requirements().len(), 2);
+}
@@ -31,6 +31,31 @@ fn core_crate_path() -> syn::Result<TokenStream2> {
 }
 
 /// Attribute macro to transform an async function into a `Transition` implementation.
+///
+/// `owner`, `team`, `sla_ms` and `tags` arguments annotate the transition's
+/// nodes for on-call routing:
+///
+/// ```rust,ignore
+/// #[transition(owner = "payments-oncall", team = "payments", sla_ms = 250, tags = ["pii"])]
+/// async fn charge(order: Order) -> Outcome<Receipt, String> { /* ... */ }
+/// ```
+///
+/// Branch ids returned by the function are reported through
+/// `Transition::branches`, so the Schematic gets a Branch edge per id. Ids
+/// written as string literals in `Outcome::Branch(..)` / `Outcome::branch(..)`
+/// are found in the body; others are declared with `#[branch(..)]` below
+/// `#[transition]`:
+///
+/// ```rust,ignore
+/// #[transition]
+/// #[branch(Route::Admin)]
+/// async fn route(req: Request) -> Outcome<Request, String> {
+///     if req.is_admin() {
+///         return Outcome::branch(Route::Admin, None);
+///     }
+///     Outcome::branch("public", None)
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn transition(attr: TokenStream, item: TokenStream) -> TokenStream {
     let core_path = match core_crate_path() {
@@ -38,10 +63,16 @@ pub fn transition(attr: TokenStream, item: TokenStream) -> TokenStream {
         Err(error) => return error.to_compile_error().into(),
     };
     let mut input_fn = parse_macro_input!(item as ItemFn);
+    let branches_method = match branches_method(&mut input_fn) {
+        Ok(method) => method,
+        Err(err) => return err.to_compile_error().into(),
+    };
     let original_ident = input_fn.sig.ident.clone();
     let vis = &input_fn.vis;
     let block = &input_fn.block;
     let inputs = &input_fn.sig.inputs;
+    let doc_attrs = doc_attributes(&input_fn.attrs);
+    let description_method = description_method(&input_fn.attrs);
 
     // We don't rename the function here, instead we use a prefix for the struct.
     // However, to make .then(multiply_by_res) work, multiply_by_res MUST be the struct name.
@@ -57,6 +88,8 @@ pub fn transition(attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut bus_deny_specified = false;
     let mut x_pos = None;
     let mut y_pos = None;
+    let mut node_id = None;
+    let mut annotations = Vec::new();
     let mut schema_flag = false;
     if !attr.is_empty() {
         let parser = syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated;
@@ -85,6 +118,28 @@ pub fn transition(attr: TokenStream, item: TokenStream) -> TokenStream {
                             x_pos = Some(nv.value);
                         } else if nv.path.is_ident("y") {
                             y_pos = Some(nv.value);
+                        } else if nv.path.is_ident("id") {
+                            node_id = Some(nv.value);
+                        } else if nv.path.is_ident("owner") {
+                            let owner = nv.value;
+                            annotations.push(quote! { .with_owner(#owner) });
+                        } else if nv.path.is_ident("team") {
+                            let team = nv.value;
+                            annotations.push(quote! { .with_team(#team) });
+                        } else if nv.path.is_ident("sla_ms") {
+                            let sla_ms = nv.value;
+                            annotations.push(quote! { .with_sla_ms(#sla_ms) });
+                        } else if nv.path.is_ident("tags") {
+                            let syn::Expr::Array(tags) = nv.value else {
+                                return syn::Error::new_spanned(
+                                    nv.value,
+                                    "expected `tags = [\"...\", ...]`",
+                                )
+                                .to_compile_error()
+                                .into();
+                            };
+                            let tags = tags.elems.into_iter();
+                            annotations.extend(tags.map(|tag| quote! { .with_tag(#tag) }));
                         }
                     }
                     _ => {}
@@ -214,6 +269,40 @@ pub fn transition(attr: TokenStream, item: TokenStream) -> TokenStream {
         quote! {}
     };
 
+    let node_id_method = match node_id {
+        Some(id) => quote! {
+            fn node_id(&self) -> Option<String> {
+                Some(::std::string::String::from(#id))
+            }
+        },
+        None => quote! {},
+    };
+
+    let annotations_method = if annotations.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn annotations(&self) -> #core_path::metadata::NodeAnnotations {
+                #core_path::metadata::NodeAnnotations::default() #(#annotations)*
+            }
+        }
+    };
+
+    // Spanned at the function name so the location is the definition site.
+    let definition_site = quote::quote_spanned! {original_ident.span()=>
+        #core_path::schematic::SourceLocation::with_column(
+            ::core::file!(),
+            ::core::line!(),
+            ::core::column!(),
+        )
+        .with_module_path(::core::module_path!())
+    };
+    let source_location_method = quote! {
+        fn source_location(&self) -> Option<#core_path::schematic::SourceLocation> {
+            Some(#definition_site)
+        }
+    };
+
     let schema_method = if schema_flag {
         quote! {
             fn input_schema(&self) -> Option<serde_json::Value> {
@@ -226,6 +315,7 @@ pub fn transition(attr: TokenStream, item: TokenStream) -> TokenStream {
     };
 
     let expanded = quote! {
+        #(#doc_attrs)*
         #[derive(Clone, Default)]
         #[allow(non_camel_case_types)]
         #vis struct #original_ident;
@@ -238,6 +328,11 @@ pub fn transition(attr: TokenStream, item: TokenStream) -> TokenStream {
             #bus_policy_method
             #position_method
             #schema_method
+            #description_method
+            #node_id_method
+            #annotations_method
+            #source_location_method
+            #branches_method
 
             async fn run(
                 &self,
@@ -363,6 +458,8 @@ pub fn streaming_transition(attr: TokenStream, item: TokenStream) -> TokenStream
     let vis = &input_fn.vis;
     let block = &input_fn.block;
     let inputs = &input_fn.sig.inputs;
+    let doc_attrs = doc_attributes(&input_fn.attrs);
+    let description_method = description_method(&input_fn.attrs);
 
     let internal_fn_ident = quote::format_ident!("__ranvier_fn_{}", original_ident);
     input_fn.sig.ident = internal_fn_ident.clone();
@@ -463,6 +560,7 @@ pub fn streaming_transition(attr: TokenStream, item: TokenStream) -> TokenStream
     };
 
     let expanded = quote! {
+        #(#doc_attrs)*
         #[derive(Clone, Default)]
         #[allow(non_camel_case_types)]
         #vis struct #original_ident;
@@ -473,6 +571,8 @@ pub fn streaming_transition(attr: TokenStream, item: TokenStream) -> TokenStream
             type Error = #error_type;
             type Resources = #res_type;
 
+            #description_method
+
             async fn run_stream(
                 &self,
                 input: #input_type,
@@ -621,6 +721,138 @@ fn extract_outcome_types(
     None
 }
 
+fn doc_attributes(attrs: &[syn::Attribute]) -> Vec<&syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .collect()
+}
+
+/// Join `///` lines into one description, dropping the single leading space
+/// rustdoc inserts and surrounding blank lines.
+fn doc_description(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = doc_attributes(attrs)
+        .into_iter()
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(text),
+                        ..
+                    }),
+                ..
+            }) => Some(text.value()),
+            _ => None,
+        })
+        .flat_map(|text| {
+            text.split('\n')
+                .map(|line| {
+                    line.strip_prefix(' ')
+                        .unwrap_or(line)
+                        .trim_end()
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let description = lines.join("\n").trim_matches('\n').to_string();
+    (!description.is_empty()).then_some(description)
+}
+
+fn description_method(attrs: &[syn::Attribute]) -> TokenStream2 {
+    match doc_description(attrs) {
+        Some(description) => quote! {
+            fn description(&self) -> Option<String> {
+                Some(#description.to_string())
+            }
+        },
+        None => quote! {},
+    }
+}
+
+/// `fn branches` for a `#[transition]`, from its `#[branch(..)]` attributes
+/// (removed from the function) and the string literals passed to
+/// `Outcome::Branch` / `Outcome::branch` in its body.
+fn branches_method(input_fn: &mut ItemFn) -> syn::Result<TokenStream2> {
+    let mut declared: Vec<syn::Expr> = Vec::new();
+    let mut attrs = Vec::with_capacity(input_fn.attrs.len());
+    for attr in std::mem::take(&mut input_fn.attrs) {
+        if attr.path().is_ident("branch") {
+            let ids = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+            )?;
+            declared.extend(ids);
+        } else {
+            attrs.push(attr);
+        }
+    }
+    input_fn.attrs = attrs;
+
+    let mut seen: HashSet<String> = declared
+        .iter()
+        .filter_map(|expr| match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => Some(lit.value()),
+            _ => None,
+        })
+        .collect();
+    let mut scanned = Vec::new();
+    scan_branch_literals(input_fn.block.to_token_stream(), &mut scanned);
+    scanned.retain(|id| seen.insert(id.clone()));
+
+    if declared.is_empty() && scanned.is_empty() {
+        return Ok(quote! {});
+    }
+    Ok(quote! {
+        fn branches(&self) -> ::std::vec::Vec<::std::string::String> {
+            ::std::vec![
+                #(::std::string::String::from(#declared),)*
+                #(::std::string::String::from(#scanned),)*
+            ]
+        }
+    })
+}
+
+/// Collect the ids of `Outcome::Branch(..)` / `Outcome::branch(..)` calls
+/// whose id argument holds exactly one string literal, e.g.
+/// `"admin".to_string()`. Computed ids are invisible here and need `#[branch]`.
+fn scan_branch_literals(tokens: TokenStream2, out: &mut Vec<String>) {
+    use proc_macro2::{Delimiter, TokenTree};
+
+    let trees: Vec<TokenTree> = tokens.into_iter().collect();
+    for (i, tree) in trees.iter().enumerate() {
+        let TokenTree::Group(group) = tree else {
+            continue;
+        };
+        let is_branch_call = group.delimiter() == Delimiter::Parenthesis
+            && i >= 4
+            && matches!(&trees[i - 1], TokenTree::Ident(name) if name == "Branch" || name == "branch")
+            && matches!(&trees[i - 2], TokenTree::Punct(p) if p.as_char() == ':')
+            && matches!(&trees[i - 3], TokenTree::Punct(p) if p.as_char() == ':')
+            && matches!(&trees[i - 4], TokenTree::Ident(name) if name == "Outcome");
+        if is_branch_call {
+            let literals: Vec<_> = group
+                .stream()
+                .into_iter()
+                .take_while(|tree| !matches!(tree, TokenTree::Punct(p) if p.as_char() == ','))
+                .filter_map(|tree| match tree {
+                    TokenTree::Literal(lit) => Some(lit),
+                    _ => None,
+                })
+                .collect();
+            if let [lit] = literals.as_slice()
+                && let Ok(syn::Lit::Str(id)) = syn::parse2::<syn::Lit>(lit.to_token_stream())
+                && !out.contains(&id.value())
+            {
+                out.push(id.value());
+            }
+        }
+        scan_branch_literals(group.stream(), out);
+    }
+}
+
 fn is_bus_argument(arg: &FnArg) -> bool {
     let FnArg::Typed(pat_type) = arg else {
         return false;
@@ -687,6 +919,263 @@ pub fn derive_resource_requirement(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Derive macro for the `BranchKey` trait on fieldless enums.
+///
+/// Each variant becomes a branch ID: its name in snake_case, or the value of
+/// `#[branch_key(rename = "...")]`. Also generates `From<YourEnum> for String`
+/// so the enum can be passed to `Outcome::branch` and `BranchBuilder::on`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use ranvier::prelude::*;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, BranchKey)]
+/// enum Route {
+///     Admin,      // "admin"
+///     PublicApi,  // "public_api"
+///     #[branch_key(rename = "legacy-v1")]
+///     Legacy,
+/// }
+/// ```
+#[proc_macro_derive(BranchKey, attributes(branch_key))]
+pub fn derive_branch_key(input: TokenStream) -> TokenStream {
+    let core_path = match core_crate_path() {
+        Ok(path) => path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_branch_key(&input, &core_path) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_branch_key(input: &DeriveInput, core_path: &TokenStream2) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let syn::Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "BranchKey can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "BranchKey cannot be derived for generic enums",
+        ));
+    }
+
+    let mut idents = Vec::new();
+    let mut ids = Vec::new();
+    let mut seen = HashSet::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "BranchKey variants cannot have fields",
+            ));
+        }
+        let id = branch_key_rename(&variant.attrs)?
+            .unwrap_or_else(|| to_snake_case(&variant.ident.to_string()));
+        if !seen.insert(id.clone()) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!("duplicate branch id `{id}`"),
+            ));
+        }
+        idents.push(&variant.ident);
+        ids.push(id);
+    }
+
+    Ok(quote! {
+        impl #core_path::outcome::BranchKey for #name {
+            fn branch_id(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #ids,)*
+                }
+            }
+
+            fn from_branch_id(id: &str) -> ::std::option::Option<Self> {
+                match id {
+                    #(#ids => ::std::option::Option::Some(Self::#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn branch_ids() -> &'static [&'static str] {
+                &[#(#ids),*]
+            }
+        }
+
+        impl ::std::convert::From<#name> for ::std::string::String {
+            fn from(key: #name) -> Self {
+                ::std::string::String::from(#core_path::outcome::BranchKey::branch_id(&key))
+            }
+        }
+    })
+}
+
+/// Derive macro for the `FromBus` trait on structs with named fields.
+///
+/// Each field is cloned out of the Bus by its type. Every field is looked up
+/// before failing, so the `MissingResources` error names all absent members.
+/// Fields typed `OptionalResource<T>` are filled when `T` is present and are
+/// not listed in `FromBus::requirements`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use ranvier::prelude::*;
+///
+/// #[derive(FromBus)]
+/// struct Deps {
+///     db: sqlx::PgPool,
+///     cfg: AppConfig,
+///     cache: OptionalResource<RedisClient>,
+/// }
+///
+/// let deps: Deps = bus.extract()?;
+/// ```
+#[proc_macro_derive(FromBus)]
+pub fn derive_from_bus(input: TokenStream) -> TokenStream {
+    let core_path = match core_crate_path() {
+        Ok(path) => path,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_from_bus(&input, &core_path) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_from_bus(input: &DeriveInput, core_path: &TokenStream2) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Named(fields) if !fields.named.is_empty() => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "FromBus requires a struct with at least one named field",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "FromBus can only be derived for structs",
+            ));
+        }
+    };
+    let (optional, required): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .partition(|field| is_optional_resource(&field.ty));
+    let optional_idents: Vec<_> = optional.iter().map(|field| &field.ident).collect();
+    let optional_types: Vec<_> = optional.iter().map(|field| &field.ty).collect();
+    let idents: Vec<_> = required.iter().map(|field| &field.ident).collect();
+    let types: Vec<_> = required.iter().map(|field| &field.ty).collect();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let read_optional = quote! {
+        #(let #optional_idents = <#optional_types>::read(bus);)*
+    };
+    let body = if idents.is_empty() {
+        quote! {
+            #read_optional
+            ::std::result::Result::Ok(Self { #(#optional_idents),* })
+        }
+    } else {
+        quote! {
+            let mut missing = ::std::vec::Vec::new();
+            #(
+                let #idents = bus
+                    .require::<#types>()
+                    .map_err(|err| missing.push(err.resource))
+                    .ok()
+                    .cloned();
+            )*
+            #read_optional
+            match (#(#idents,)*) {
+                (#(::std::option::Option::Some(#idents),)*) => ::std::result::Result::Ok(Self {
+                    #(#idents,)*
+                    #(#optional_idents,)*
+                }),
+                _ => ::std::result::Result::Err(#core_path::from_bus::MissingResources {
+                    target: ::std::any::type_name::<Self>(),
+                    resources: missing,
+                    node_id: bus.current_node().map(::std::string::ToString::to_string),
+                }),
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #core_path::from_bus::FromBus for #name #ty_generics #where_clause {
+            fn from_bus(
+                bus: &#core_path::bus::Bus,
+            ) -> ::std::result::Result<Self, #core_path::from_bus::MissingResources> {
+                #body
+            }
+
+            fn requirements() -> ::std::vec::Vec<#core_path::bus::BusTypeRef> {
+                ::std::vec![#(#core_path::bus::BusTypeRef::of::<#types>()),*]
+            }
+        }
+    })
+}
+
+fn is_optional_resource(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Path(path) if path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "OptionalResource")
+    )
+}
+
+fn branch_key_rename(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    let mut rename = None;
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("branch_key"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                rename = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(rename)
+}
+
+fn to_snake_case(ident: &str) -> String {
+    let mut out = String::with_capacity(ident.len() + 4);
+    let chars: Vec<char> = ident.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev_lower =
+                i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let prev_upper = i > 0 && chars[i - 1].is_uppercase();
+            if prev_lower || (prev_upper && next_lower) {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
 fn validate_bus_policy_types(allow: &[Type], deny: &[Type]) -> syn::Result<()> {
     let mut allow_keys = HashSet::new();
     for ty in allow {
@@ -725,8 +1214,71 @@ fn validate_bus_policy_types(allow: &[Type], deny: &[Type]) -> syn::Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_bus_argument, parse_type_array_expr, validate_bus_policy_types};
-    use syn::{Expr, FnArg, parse_quote};
+    use super::{
+        doc_description, expand_branch_key, is_bus_argument, parse_type_array_expr, to_snake_case,
+        validate_bus_policy_types,
+    };
+    use quote::quote;
+    use syn::{DeriveInput, Expr, FnArg, ItemFn, parse_quote};
+
+    #[test]
+    fn branch_key_ids_are_snake_case_unless_renamed() {
+        assert_eq!(to_snake_case("Admin"), "admin");
+        assert_eq!(to_snake_case("PublicApi"), "public_api");
+        assert_eq!(to_snake_case("HTTPRedirect"), "http_redirect");
+        assert_eq!(to_snake_case("Retry2Fast"), "retry2_fast");
+
+        let input: DeriveInput = parse_quote! {
+            enum Route {
+                Admin,
+                #[branch_key(rename = "legacy-v1")]
+                Legacy,
+            }
+        };
+        let expanded = expand_branch_key(&input, &quote!(ranvier_core))
+            .unwrap()
+            .to_string();
+        assert!(expanded.contains("\"admin\""));
+        assert!(expanded.contains("\"legacy-v1\""));
+    }
+
+    #[test]
+    fn branch_key_rejects_data_variants_and_duplicate_ids() {
+        let with_data: DeriveInput = parse_quote! {
+            enum Route { Admin(u32) }
+        };
+        assert!(expand_branch_key(&with_data, &quote!(ranvier_core)).is_err());
+
+        let duplicate: DeriveInput = parse_quote! {
+            enum Route {
+                Admin,
+                #[branch_key(rename = "admin")]
+                Root,
+            }
+        };
+        let error = expand_branch_key(&duplicate, &quote!(ranvier_core)).unwrap_err();
+        assert!(error.to_string().contains("duplicate branch id"));
+    }
+
+    #[test]
+    fn doc_comment_becomes_description() {
+        let input: ItemFn = parse_quote! {
+            /// Charge the customer's card.
+            ///
+            /// Retries are handled by the circuit.
+            #[allow(unused)]
+            async fn charge(input: u32) -> Outcome<u32, String> { Outcome::Next(input) }
+        };
+        assert_eq!(
+            doc_description(&input.attrs).as_deref(),
+            Some("Charge the customer's card.\n\nRetries are handled by the circuit.")
+        );
+
+        let undocumented: ItemFn = parse_quote! {
+            async fn noop(input: u32) -> Outcome<u32, String> { Outcome::Next(input) }
+        };
+        assert_eq!(doc_description(&undocumented.attrs), None);
+    }
 
     #[test]
     fn detects_mut_bus_reference_argument() {
@@ -0,0 +1,329 @@
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::schematic::{
+    Edge, EdgeType, Node, NodeKind, Schematic, SourceLocation, stable_node_id,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::marker::PhantomData;
+use std::panic::Location;
+use std::sync::Arc;
+
+use super::type_name_of;
+use super::*;
+
+/// Event emitted when a branch payload cannot be decoded into the arm's input.
+pub const BRANCH_PAYLOAD_INVALID: &str = "execution.branch.invalid_payload";
+
+type ArmExecutor<Next, E, Res> = Arc<
+    dyn for<'a> Fn(Option<Value>, &'a Res, &'a mut Bus) -> BoxFuture<'a, Outcome<Next, E>>
+        + Send
+        + Sync,
+>;
+
+struct BranchArm<Next, E, Res> {
+    branch_id: String,
+    input_type: String,
+    schematic: Schematic,
+    run: ArmExecutor<Next, E, Res>,
+}
+
+/// Collects the sub-chains of [`Axon::branch`].
+pub struct BranchBuilder<Out, Next, E, Res = ()> {
+    arms: Vec<BranchArm<Next, E, Res>>,
+    _out: PhantomData<fn(Out)>,
+}
+
+/// Finished branch table returned by [`BranchBuilder::otherwise`].
+pub struct Branches<Out, Next, E, Res = ()> {
+    arms: Vec<BranchArm<Next, E, Res>>,
+    otherwise: Axon<Out, Next, E, Res>,
+}
+
+impl<Out, Next, E, Res> BranchBuilder<Out, Next, E, Res>
+where
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Next: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Run `axon` when the previous step returns `Outcome::Branch(branch_id, payload)`.
+    ///
+    /// The payload is decoded into the sub-chain's input (`null` when absent).
+    /// Registering the same `branch_id` again replaces the earlier arm.
+    /// Enums deriving `BranchKey` can be passed directly as `branch_id`.
+    pub fn on<P>(mut self, branch_id: impl Into<String>, axon: Axon<P, Next, E, Res>) -> Self
+    where
+        P: Send + Sync + Serialize + DeserializeOwned + 'static,
+    {
+        let branch_id = branch_id.into();
+        let executor = axon.executor;
+        let arm_id = branch_id.clone();
+        let run: ArmExecutor<Next, E, Res> = Arc::new(
+            move |payload: Option<Value>,
+                  res: &Res,
+                  bus: &mut Bus|
+                  -> BoxFuture<'_, Outcome<Next, E>> {
+                let executor = executor.clone();
+                let arm_id = arm_id.clone();
+                Box::pin(async move {
+                    match serde_json::from_value::<P>(payload.unwrap_or(Value::Null)) {
+                        Ok(input) => executor(input, res, bus).await,
+                        Err(error) => Outcome::emit(
+                            BRANCH_PAYLOAD_INVALID,
+                            Some(serde_json::json!({
+                                "branch": arm_id,
+                                "error": error.to_string(),
+                            })),
+                        ),
+                    }
+                })
+            },
+        );
+
+        self.arms.retain(|arm| arm.branch_id != branch_id);
+        self.arms.push(BranchArm {
+            branch_id,
+            input_type: type_name_of::<P>(),
+            schematic: axon.schematic,
+            run,
+        });
+        self
+    }
+
+    /// Sub-chain for the `Next` value of the previous step.
+    pub fn otherwise(self, axon: Axon<Out, Next, E, Res>) -> Branches<Out, Next, E, Res> {
+        Branches {
+            arms: self.arms,
+            otherwise: axon,
+        }
+    }
+}
+
+pub(super) fn subgraph_node<Res>(
+    id: String,
+    schematic: Schematic,
+    input_type: String,
+    output_type: String,
+    caller: &Location<'_>,
+) -> Node {
+    Node {
+        id,
+        label: schematic.name.clone(),
+        description: schematic.description.clone(),
+        kind: NodeKind::Subgraph(Box::new(schematic)),
+        input_type,
+        output_type,
+        resource_type: type_name_of::<Res>(),
+        metadata: Default::default(),
+        bus_capability: None,
+        source_location: Some(SourceLocation::from(caller)),
+        position: None,
+        compensation_node_id: None,
+        input_schema: None,
+        output_schema: None,
+        item_type: None,
+        terminal: None,
+    }
+}
+
+/// Edge and node index of the `Egress` exit `from` got for a declared
+/// branch id.
+fn branch_exit(schematic: &Schematic, from: &str, branch_id: &str) -> Option<(usize, usize)> {
+    schematic.edges.iter().enumerate().find_map(|(edge, e)| {
+        if e.from != from || !matches!(&e.kind, EdgeType::Branch(id) if id == branch_id) {
+            return None;
+        }
+        let index = schematic
+            .nodes
+            .iter()
+            .position(|node| node.id == e.to && matches!(node.kind, NodeKind::Egress))?;
+        Some((edge, index))
+    })
+}
+
+impl<In, Out, E, Res> Axon<In, Out, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Route `Outcome::Branch` results of the last step into sub-chains.
+    ///
+    /// Each `on` arm handles one branch ID; `otherwise` continues the `Next`
+    /// path. Branch IDs without an arm still end the execution with their
+    /// `Outcome::Branch`, and `Fault`/`Emit`/`Jump` pass through unchanged.
+    ///
+    /// The Schematic gets one `Subgraph` node per arm (reached through
+    /// `Branch(id)` edges), a `Subgraph` for the `otherwise` chain, and a
+    /// `Synapse` join node that later steps attach to.
+    ///
+    /// ```rust,ignore
+    /// let axon = Axon::new("Router")
+    ///     .then(ClassifyRequest)
+    ///     .branch(|b| {
+    ///         b.on("admin_route", admin_axon)
+    ///             .on("api_route", api_axon)
+    ///             .otherwise(default_axon)
+    ///     })
+    ///     .then(RenderResponse);
+    /// ```
+    #[track_caller]
+    pub fn branch<Next, F>(self, build: F) -> Axon<In, Next, E, Res>
+    where
+        Next: Send + Sync + Serialize + DeserializeOwned + 'static,
+        F: FnOnce(BranchBuilder<Out, Next, E, Res>) -> Branches<Out, Next, E, Res>,
+    {
+        let caller = Location::caller();
+        let Branches { arms, otherwise } = build(BranchBuilder {
+            arms: Vec::new(),
+            _out: PhantomData,
+        });
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = self;
+
+        let last_node_id = schematic
+            .nodes
+            .last()
+            .map(|n| n.id.clone())
+            .unwrap_or_default();
+        let mut arm_node_ids = Vec::with_capacity(arms.len() + 1);
+
+        let mut arm_executors = Vec::with_capacity(arms.len());
+        for arm in arms {
+            // Same ID as the exit a declared branch got, which the arm replaces.
+            let node = subgraph_node::<Res>(
+                stable_node_id(&last_node_id, &arm.branch_id, 0),
+                arm.schematic,
+                arm.input_type,
+                type_name_of::<Next>(),
+                caller,
+            );
+            arm_node_ids.push(node.id.clone());
+            match branch_exit(&schematic, &last_node_id, &arm.branch_id) {
+                // A declared branch: the arm takes the place of its exit.
+                Some((edge, index)) => {
+                    schematic.edges[edge].to = node.id.clone();
+                    schematic.nodes[index] = node;
+                }
+                None => {
+                    schematic.edges.push(Edge {
+                        from: last_node_id.clone(),
+                        to: node.id.clone(),
+                        kind: EdgeType::Branch(arm.branch_id.clone()),
+                        label: Some(arm.branch_id.clone()),
+                    });
+                    schematic.nodes.push(node);
+                }
+            }
+            arm_executors.push((arm.branch_id, arm.run));
+        }
+
+        let otherwise_node = subgraph_node::<Res>(
+            schematic.next_node_id(&otherwise.schematic.name),
+            otherwise.schematic,
+            type_name_of::<Out>(),
+            type_name_of::<Next>(),
+            caller,
+        );
+        schematic.edges.push(Edge {
+            from: last_node_id,
+            to: otherwise_node.id.clone(),
+            kind: EdgeType::Linear,
+            label: Some("Otherwise".to_string()),
+        });
+        arm_node_ids.push(otherwise_node.id.clone());
+        schematic.nodes.push(otherwise_node);
+
+        let join_id = schematic.next_node_id("BranchJoin");
+        schematic.nodes.push(Node {
+            id: join_id.clone(),
+            kind: NodeKind::Synapse,
+            label: "BranchJoin".to_string(),
+            description: Some(format!("Join of {} branch arms", arm_node_ids.len())),
+            input_type: type_name_of::<Next>(),
+            output_type: type_name_of::<Next>(),
+            resource_type: type_name_of::<Res>(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: Some(SourceLocation::from(caller)),
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        });
+        for arm_node_id in arm_node_ids {
+            schematic.edges.push(Edge {
+                from: arm_node_id,
+                to: join_id.clone(),
+                kind: EdgeType::Linear,
+                label: Some("Next".to_string()),
+            });
+        }
+
+        let arms = Arc::new(arm_executors);
+        let otherwise = otherwise.executor;
+        let next_executor: Executor<In, Next, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Next, E>> {
+                let prev = prev_executor.clone();
+                let arms = arms.clone();
+                let otherwise = otherwise.clone();
+
+                Box::pin(async move {
+                    match prev(input, res, bus).await {
+                        Outcome::Next(value) => otherwise(value, res, bus).await,
+                        Outcome::Branch(branch_id, payload) => {
+                            match arms.iter().find(|(id, _)| *id == branch_id) {
+                                Some((_, run)) => run(payload, res, bus).await,
+                                None => Outcome::Branch(branch_id, payload),
+                            }
+                        }
+                        other => other.map(|_| unreachable!()),
+                    }
+                })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+}
@@ -1,25 +1,30 @@
-use ranvier_core::bus::Bus;
+use ranvier_core::bus::{Bus, ResourceRegistry};
 use ranvier_core::event::DlqPolicy;
 use ranvier_core::outcome::Outcome;
 use ranvier_core::policy::DynamicPolicy;
 use ranvier_core::saga::SagaPolicy;
+use ranvier_core::schematic::analysis::GraphAnalysis;
 use ranvier_core::schematic::{Edge, EdgeType, Node, NodeKind, Schematic, SourceLocation};
 #[cfg(feature = "streaming")]
 use ranvier_core::streaming::{StreamTimeoutConfig, StreamingTransition};
-use ranvier_core::timeline::{Timeline, TimelineEvent};
+use ranvier_core::timeline::{Timeline, TimelineEvent, Timestamp};
 use ranvier_core::transition::Transition;
+use ranvier_core::validation::{ResourceCheckError, ValidationReport, validate_schematic};
 use serde::{Serialize, de::DeserializeOwned};
 use std::fs;
 use std::panic::Location;
 use std::sync::Arc;
 
+use super::branch::subgraph_node;
 use super::*;
 use super::{
-    bus_capability_schema_from_policy, now_ms, run_this_compensated_step, run_this_step,
-    schematic_export_request_from_process, type_name_of,
+    attach_fault_retries, bus_capability_schema_from_policy, contract_enforced, record_fault_cause,
+    run_this_compensated_step, run_this_step, schematic_export_request_from_process, type_name_of,
 };
 #[cfg(feature = "inspector")]
 use super::{inspector_dev_mode_from_env, inspector_enabled_from_env};
+use crate::contract::{CONTRACT_VIOLATION, ContractViolation, OutputContract};
+use crate::timeout::{NODE_TIMEOUT, TimeoutError};
 
 // ---------------------------------------------------------------------------
 // Block 1: Constructors (identity Axon: In -> In)
@@ -48,9 +53,9 @@ where
     }
 
     fn start_with_source(label: &str, caller: &'static Location<'static>) -> Self {
-        let node_id = uuid::Uuid::new_v4().to_string();
+        let mut schematic = Schematic::new(label);
         let node = Node {
-            id: node_id,
+            id: schematic.next_node_id(label),
             kind: NodeKind::Ingress,
             label: label.to_string(),
             description: None,
@@ -59,7 +64,7 @@ where
             resource_type: type_name_of::<Res>(),
             metadata: Default::default(),
             bus_capability: None,
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+            source_location: Some(SourceLocation::from(caller)),
             position: None,
             compensation_node_id: None,
             input_schema: None,
@@ -68,7 +73,6 @@ where
             terminal: None,
         };
 
-        let mut schematic = Schematic::new(label);
         schematic.nodes.push(node);
 
         let executor: Executor<In, In, E, Res> =
@@ -89,6 +93,12 @@ where
                 ranvier_core::saga::SagaCompensationRegistry::new(),
             )),
             iam_handle: None,
+            capture_policy: None,
+            concurrency: None,
+            node_policies: None,
+            execution_hooks: Vec::new(),
+            interceptors: Vec::new(),
+            schematic_observed: std::sync::OnceLock::new(),
         }
     }
 }
@@ -177,6 +187,81 @@ where
         self
     }
 
+    /// Record circuit input/output payloads into the Timeline.
+    ///
+    /// The format is written to the Timeline header so `ReplayEngine` and the
+    /// Inspector can decode captures. Capture only happens when a Timeline is
+    /// collected (one is on the Bus, or `RANVIER_TIMELINE_OUTPUT` is set).
+    pub fn with_capture_policy(mut self, policy: CapturePolicy) -> Self {
+        self.capture_policy = Some(policy);
+        self
+    }
+
+    /// Declare how HTTP responses of this circuit may be cached.
+    ///
+    /// The policy is stored on the Schematic; `ranvier-http` turns it into
+    /// `Cache-Control`/`Vary` headers on successful responses and, when the
+    /// ingress has a `ResponseCache`, serves repeat requests from it.
+    pub fn with_cache_policy(mut self, policy: ranvier_core::cache_policy::CachePolicy) -> Self {
+        self.schematic.cache_policy = Some(policy);
+        self
+    }
+
+    /// Admit executions through a shared [`ConcurrencyScheduler`](crate::concurrency::ConcurrencyScheduler).
+    ///
+    /// The circuit's quota is looked up by Schematic name. Executions wait for
+    /// a slot after the IAM boundary check and hold it until they return.
+    pub fn with_concurrency(mut self, scheduler: crate::concurrency::ConcurrencyScheduler) -> Self {
+        self.concurrency = Some(scheduler);
+        self
+    }
+
+    /// Override retry/backoff/timeout policies of named nodes from config.
+    ///
+    /// Overrides are keyed by transition label and apply to every node of
+    /// the circuit, whether it is chained before or after this call:
+    ///
+    /// ```rust,ignore
+    /// let config = RanvierConfig::load()?;
+    /// let axon = Axon::new("checkout")
+    ///     .then_with_retry(ChargeCard, RetryPolicy::fixed(3, Duration::from_millis(100)))
+    ///     .with_node_policies(config.nodes.clone());
+    /// ```
+    ///
+    /// `max_retries`/`backoff` apply to `then_with_retry` nodes and
+    /// `timeout_ms` to `then_with_timeout` nodes; the effective policy is
+    /// recorded in each node's `metadata.policy`. When an Axon is chained
+    /// into another, the policies of the executed (outer) Axon win.
+    pub fn with_node_policies(mut self, policies: NodePoliciesConfig) -> Self {
+        record_node_policies(&mut self.schematic, &policies);
+        self.node_policies = Some(Arc::new(policies));
+        self
+    }
+
+    /// Call `hook` around every node of this Axon.
+    ///
+    /// Hooks registered with [`register_global_hook`](crate::hooks::register_global_hook)
+    /// run before the ones attached here.
+    pub fn with_hook<H>(mut self, hook: H) -> Self
+    where
+        H: crate::hooks::ExecutionHook + 'static,
+    {
+        self.execution_hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Wrap every node in `interceptor`.
+    ///
+    /// Interceptors registered first are outermost: their `before` runs
+    /// first and their `after` last. See [`Interceptor`](crate::interceptor::Interceptor).
+    pub fn intercept<I>(mut self, interceptor: I) -> Self
+    where
+        I: crate::interceptor::Interceptor + 'static,
+    {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
     /// Attach a persistence store to enable state inspection via the Inspector.
     pub fn with_persistence_store<S>(mut self, store: S) -> Self
     where
@@ -317,6 +402,195 @@ where
         self
     }
 
+    /// Declare that the **last node** emits `event_type` with payload `T`.
+    ///
+    /// The schema is recorded in the node's metadata so payload changes can be
+    /// checked with `ranvier_core::event_schema::check_event_compatibility`.
+    #[cfg(feature = "schema")]
+    pub fn with_emitted_event_schema<T>(self, event_type: impl Into<String>) -> Self
+    where
+        T: schemars::JsonSchema,
+    {
+        let schema = serde_json::to_value(schemars::schema_for!(T)).unwrap_or_default();
+        self.with_emitted_event_schema_value(event_type, schema)
+    }
+
+    /// Attach a raw JSON Schema for an event payload emitted by the **last node**.
+    pub fn with_emitted_event_schema_value(
+        mut self,
+        event_type: impl Into<String>,
+        schema: serde_json::Value,
+    ) -> Self {
+        if let Some(last_node) = self.schematic.nodes.last_mut() {
+            last_node.metadata.emits.insert(event_type.into(), schema);
+        }
+        self
+    }
+
+    /// Declare that the **last node** may return `Outcome::Jump` to `target`,
+    /// a node label or id.
+    ///
+    /// Targets may point forward to nodes chained later; [`validate`](Self::validate)
+    /// checks them once the circuit is complete. At run time the node finds
+    /// the id to jump to with [`Schematic::jump_target`] on the Bus's Schematic.
+    pub fn jump_target(mut self, target: impl Into<String>) -> Self {
+        if let Some(last_node) = self.schematic.nodes.last_mut() {
+            last_node.metadata.jump_targets.push(target.into());
+        }
+        self
+    }
+
+    /// Record who owns the **last node**, e.g. the on-call rotation paged for
+    /// it. Overrides `#[transition(owner = "...")]`.
+    pub fn owner(mut self, owner: impl Into<String>) -> Self {
+        if let Some(last_node) = self.schematic.nodes.last_mut() {
+            last_node.metadata.annotations.owner = Some(owner.into());
+        }
+        self
+    }
+
+    /// Record the team responsible for the **last node**.
+    pub fn team(mut self, team: impl Into<String>) -> Self {
+        if let Some(last_node) = self.schematic.nodes.last_mut() {
+            last_node.metadata.annotations.team = Some(team.into());
+        }
+        self
+    }
+
+    /// Record the latency the **last node** is expected to stay under.
+    ///
+    /// Informational: the inspector flags slower runs, nothing is enforced.
+    /// Use [`then_with_timeout`](Self::then_with_timeout) to cut a node off.
+    pub fn sla_ms(mut self, sla_ms: u64) -> Self {
+        if let Some(last_node) = self.schematic.nodes.last_mut() {
+            last_node.metadata.annotations.sla_ms = Some(sla_ms);
+        }
+        self
+    }
+
+    /// Add a free-form tag to the **last node**.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        if let Some(last_node) = self.schematic.nodes.last_mut() {
+            let annotations = std::mem::take(&mut last_node.metadata.annotations);
+            last_node.metadata.annotations = annotations.with_tag(tag);
+        }
+        self
+    }
+
+    /// Check the built circuit's structure: dangling edges, orphan and
+    /// unreachable nodes, type mismatches across `Next` edges, cycles closed
+    /// without a `Jump` edge, duplicate labels and [`jump_target`](Self::jump_target)s that name no node.
+    ///
+    /// Fails when the report holds at least one error; warnings alone pass.
+    /// Use [`diagnostics`](Self::diagnostics) for the full report either way.
+    pub fn validate(&self) -> Result<(), ValidationReport> {
+        let report = self.diagnostics();
+        if report.has_errors() {
+            Err(report)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Bus entries this circuit's transitions require, by type name, with the
+    /// labels of the nodes requiring each. See
+    /// [`Transition::required_resources`].
+    pub fn required_resources(&self) -> std::collections::BTreeMap<String, Vec<String>> {
+        ranvier_core::validation::required_resources(&self.schematic)
+    }
+
+    /// Check at startup that `registry` provides every
+    /// [`required_resources`](Self::required_resources) entry.
+    ///
+    /// Pass the Bus the application prepares for its executions (or a
+    /// [`BusSnapshot`](ranvier_core::bus_snapshot::BusSnapshot) of it) to fail
+    /// fast, rather than faulting with a missing resource mid-request:
+    ///
+    /// ```rust,ignore
+    /// let mut bus = Bus::new();
+    /// bus.provide(pool);
+    /// checkout.check_resources(&bus)?;
+    /// ```
+    pub fn check_resources(
+        &self,
+        registry: &impl ResourceRegistry,
+    ) -> Result<(), ResourceCheckError> {
+        ranvier_core::validation::check_resources(&self.schematic, registry)
+    }
+
+    /// Every finding of [`validate_schematic`] for this circuit.
+    pub fn diagnostics(&self) -> ValidationReport {
+        validate_schematic(&self.schematic)
+    }
+
+    /// Topological order, cycles, unreachable nodes and node degrees of the
+    /// circuit's top level. See [`ranvier_core::schematic::analysis`].
+    pub fn graph_analysis(&self) -> GraphAnalysis {
+        GraphAnalysis::of(&self.schematic)
+    }
+
+    /// Check every output of the **last node** against `contract`.
+    ///
+    /// A violation replaces `Outcome::Next` with `Outcome::Fault` built from
+    /// [`ContractViolation`] and records a fault cause categorised as
+    /// [`CONTRACT_VIOLATION`]. Enforcement follows [`ContractEnforcement`]:
+    /// every output in development and tests, a sample in production.
+    pub fn ensure_output(mut self, contract: OutputContract<Out>) -> Self
+    where
+        E: From<ContractViolation>,
+    {
+        let (node_id, node_label) = match self.schematic.nodes.last_mut() {
+            Some(node) => {
+                node.metadata.contracts.push(contract.name().to_string());
+                (node.id.clone(), node.label.clone())
+            }
+            None => (String::new(), self.schematic.name.clone()),
+        };
+        let step_idx = self.schematic.nodes.len().saturating_sub(1) as u64;
+        let prev_executor = self.executor;
+        self.executor = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Out, E>> {
+                let prev = prev_executor.clone();
+                let contract = contract.clone();
+                let node_id = node_id.clone();
+                let node_label = node_label.clone();
+
+                Box::pin(async move {
+                    let outcome = prev(input, res, bus).await;
+                    let Outcome::Next(output) = &outcome else {
+                        return outcome;
+                    };
+                    if !contract_enforced(bus, contract.production_sample_rate()) {
+                        return outcome;
+                    }
+                    let Err(reason) = contract.evaluate(output) else {
+                        return outcome;
+                    };
+
+                    let violation = ContractViolation {
+                        contract: contract.name().to_string(),
+                        node_label: node_label.clone(),
+                        reason,
+                    };
+                    tracing::warn!(
+                        node_id = %node_id,
+                        contract = %violation.contract,
+                        reason = %violation.reason,
+                        "Output contract violated"
+                    );
+                    record_fault_cause(
+                        bus,
+                        FaultCause::new(node_id, node_label, violation.to_string())
+                            .with_step_index(step_idx)
+                            .with_category(CONTRACT_VIOLATION),
+                    );
+                    Outcome::Fault(E::from(violation))
+                })
+            },
+        );
+        self
+    }
+
     // -----------------------------------------------------------------------
     // Chain methods
     // -----------------------------------------------------------------------
@@ -345,11 +619,29 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
         } = self;
 
+        if let Some(overrides) = node_policy_override(node_policies.as_deref(), &transition.label())
+        {
+            warn_unapplied_node_policy(
+                &transition.label(),
+                &[
+                    ("max_retries", overrides.max_retries.is_some()),
+                    ("backoff", overrides.backoff.is_some()),
+                    ("timeout_ms", overrides.timeout_ms.is_some()),
+                ],
+            );
+        }
+
         // Update Schematic
-        let next_node_id = uuid::Uuid::new_v4().to_string();
-        let next_node = Node {
+        let next_node_id = transition_node_id(&transition, &schematic, schematic.nodes.len());
+        let mut next_node = Node {
             id: next_node_id.clone(),
             kind: NodeKind::Atom,
             label: transition.label(),
@@ -357,9 +649,15 @@ where
             input_type: type_name_of::<Out>(),
             output_type: type_name_of::<Next>(),
             resource_type: type_name_of::<Res>(),
-            metadata: Default::default(),
+            metadata: StepMetadata {
+                annotations: transition.annotations(),
+                ..with_required_resources(
+                    node_metadata(transition.description(), None),
+                    transition.required_resources(),
+                )
+            },
             bus_capability: bus_capability_schema_from_policy(transition.bus_access_policy()),
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+            source_location: Some(transition_source(&transition, caller)),
             position: transition
                 .position()
                 .map(|(x, y)| ranvier_core::schematic::Position { x, y }),
@@ -376,6 +674,7 @@ where
             .map(|n| n.id.clone())
             .unwrap_or_default();
 
+        push_branch_exits(&mut schematic, &mut next_node, transition.branches());
         schematic.nodes.push(next_node);
         schematic.edges.push(Edge {
             from: last_node_id,
@@ -524,6 +823,12 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
         }
     }
 
@@ -561,6 +866,102 @@ where
         self.then(crate::closure_transition::ClosureTransition::new(label, f))
     }
 
+    /// Embed another Axon as the next step.
+    ///
+    /// The child's Schematic is kept intact as a single `Subgraph` node and
+    /// its steps run in place, on the same Bus and resources. Only the
+    /// child's chain is spliced in: execution settings such as persistence,
+    /// audit, DLQ and saga policies come from the parent.
+    ///
+    /// ```rust,ignore
+    /// let auth = Axon::<Request, Request, AppError, AppResources>::new("Auth")
+    ///     .then(VerifyToken)
+    ///     .then(LoadSession);
+    /// let axon = Axon::new("Checkout")
+    ///     .then_axon(auth)
+    ///     .then(ChargeCard);
+    /// ```
+    #[track_caller]
+    pub fn then_axon<Next>(self, child: Axon<Out, Next, E, Res>) -> Axon<In, Next, E, Res>
+    where
+        Next: Send + Sync + Serialize + DeserializeOwned + 'static,
+    {
+        let caller = Location::caller();
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = self;
+
+        let node = subgraph_node::<Res>(
+            schematic.next_node_id(&child.schematic.name),
+            child.schematic,
+            type_name_of::<Out>(),
+            type_name_of::<Next>(),
+            caller,
+        );
+        if let Some(last) = schematic.nodes.last() {
+            schematic.edges.push(Edge {
+                from: last.id.clone(),
+                to: node.id.clone(),
+                kind: EdgeType::Linear,
+                label: Some("Next".to_string()),
+            });
+        }
+        schematic.nodes.push(node);
+
+        let child_executor = child.executor;
+        let next_executor: Executor<In, Next, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Next, E>> {
+                let prev = prev_executor.clone();
+                let child = child_executor.clone();
+
+                Box::pin(async move {
+                    match prev(input, res, bus).await {
+                        Outcome::Next(state) => child(state, res, bus).await,
+                        other => other.map(|_| unreachable!()),
+                    }
+                })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+
     /// Chain a transition with a retry policy.
     ///
     /// If the transition returns `Outcome::Fault`, it will be retried up to
@@ -601,10 +1002,38 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
         } = self;
 
-        let next_node_id = uuid::Uuid::new_v4().to_string();
-        let next_node = Node {
+        let overrides = node_policy_override(node_policies.as_deref(), &transition.label());
+        let policy = match overrides {
+            Some(overrides) => {
+                warn_unapplied_node_policy(
+                    &transition.label(),
+                    &[("timeout_ms", overrides.timeout_ms.is_some())],
+                );
+                policy.with_overrides(overrides)
+            }
+            None => policy,
+        };
+        let applied_policy = AppliedNodePolicy {
+            max_retries: Some(policy.max_retries),
+            backoff: Some((&policy.backoff).into()),
+            timeout_ms: None,
+            source: if overrides.is_some_and(|o| o.max_retries.is_some() || o.backoff.is_some()) {
+                NodePolicySource::Config
+            } else {
+                NodePolicySource::Code
+            },
+        };
+
+        let next_node_id = transition_node_id(&transition, &schematic, schematic.nodes.len());
+        let mut next_node = Node {
             id: next_node_id.clone(),
             kind: NodeKind::Atom,
             label: transition.label(),
@@ -612,9 +1041,15 @@ where
             input_type: type_name_of::<Out>(),
             output_type: type_name_of::<Next>(),
             resource_type: type_name_of::<Res>(),
-            metadata: Default::default(),
+            metadata: StepMetadata {
+                annotations: transition.annotations(),
+                ..with_required_resources(
+                    node_metadata(transition.description(), Some(applied_policy)),
+                    transition.required_resources(),
+                )
+            },
             bus_capability: bus_capability_schema_from_policy(transition.bus_access_policy()),
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+            source_location: Some(transition_source(&transition, caller)),
             position: transition
                 .position()
                 .map(|(x, y)| ranvier_core::schematic::Position { x, y }),
@@ -631,6 +1066,7 @@ where
             .map(|n| n.id.clone())
             .unwrap_or_default();
 
+        push_branch_exits(&mut schematic, &mut next_node, transition.branches());
         schematic.nodes.push(next_node);
         schematic.edges.push(Edge {
             from: last_node_id,
@@ -661,9 +1097,17 @@ where
                         Outcome::Next(t) => t,
                         other => return other.map(|_| unreachable!()),
                     };
+                    let retry_policy = match running_node_policy(bus, &timeline_node_label) {
+                        Some(overrides) => retry_policy.with_overrides(overrides),
+                        None => retry_policy,
+                    };
 
-                    // Attempt with retries
+                    // Attempt with retries. Faults of retried attempts are
+                    // folded into the final fault's retry history instead of
+                    // nesting as causes of one another.
                     let mut last_result = None;
+                    let mut fault_retries = Vec::new();
+                    let chain_before = bus.read::<FaultChain>().cloned();
                     for attempt in 0..=retry_policy.max_retries {
                         let attempt_state = state.clone();
 
@@ -683,6 +1127,23 @@ where
                             Outcome::Next(_) => return result,
                             Outcome::Fault(_) if attempt < retry_policy.max_retries => {
                                 let delay = retry_policy.delay_for_attempt(attempt);
+                                if let Some(failed) =
+                                    bus.read_mut::<FaultChain>().and_then(FaultChain::take)
+                                {
+                                    fault_retries.extend(failed.retries);
+                                    fault_retries.push(FaultRetry {
+                                        attempt: attempt + 1,
+                                        error: failed.error,
+                                        backoff_ms: delay.as_millis() as u64,
+                                        timestamp: failed.timestamp,
+                                    });
+                                }
+                                match chain_before.clone() {
+                                    Some(chain) => bus.insert(chain),
+                                    None => {
+                                        bus.remove::<FaultChain>();
+                                    }
+                                }
                                 tracing::warn!(
                                     node_id = %timeline_node_id,
                                     attempt = attempt + 1,
@@ -696,12 +1157,16 @@ where
                                         attempt: attempt + 1,
                                         max_attempts: retry_policy.max_retries,
                                         backoff_ms: delay.as_millis() as u64,
-                                        timestamp: now_ms(),
+                                        timestamp: Timestamp::now(),
+                                        trace: None,
                                     });
                                 }
                                 tokio::time::sleep(delay).await;
                             }
                             _ => {
+                                if result.is_fault() {
+                                    attach_fault_retries(bus, fault_retries);
+                                }
                                 last_result = Some(result);
                                 break;
                             }
@@ -725,6 +1190,12 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
         }
     }
 
@@ -758,7 +1229,53 @@ where
         Trans: Transition<Out, Next, Resources = Res, Error = E> + Clone + Send + Sync + 'static,
         F: Fn() -> E + Clone + Send + Sync + 'static,
     {
-        let caller = Location::caller();
+        self.then_timeout_guarded(transition, duration, Location::caller(), move |_| {
+            make_timeout_error()
+        })
+    }
+
+    /// Chain a transition to this Axon with a timeout guard that faults with
+    /// a typed [`TimeoutError`].
+    ///
+    /// Like [`then_with_timeout`](Self::then_with_timeout), the budget can be
+    /// overridden by `timeout_ms` in the node's `[nodes.<label>]` policy and
+    /// is shown in the node metadata. A timeout also records a fault cause
+    /// categorised as [`NODE_TIMEOUT`].
+    ///
+    /// ```rust,ignore
+    /// let pipeline = Axon::<Query, Query, AppError>::new("Search")
+    ///     .then_timeout(QueryIndex, Duration::from_millis(250))
+    ///     .catch(|err, _bus| match err {
+    ///         AppError::Timeout(_) => Outcome::next(Results::empty()),
+    ///         other => Outcome::fault(other),
+    ///     });
+    /// ```
+    #[track_caller]
+    pub fn then_timeout<Next, Trans>(
+        self,
+        transition: Trans,
+        duration: std::time::Duration,
+    ) -> Axon<In, Next, E, Res>
+    where
+        Next: Send + Sync + Serialize + DeserializeOwned + 'static,
+        Trans: Transition<Out, Next, Resources = Res, Error = E> + Clone + Send + Sync + 'static,
+        E: From<TimeoutError>,
+    {
+        self.then_timeout_guarded(transition, duration, Location::caller(), E::from)
+    }
+
+    fn then_timeout_guarded<Next, Trans, F>(
+        self,
+        transition: Trans,
+        duration: std::time::Duration,
+        caller: &'static Location<'static>,
+        make_timeout_error: F,
+    ) -> Axon<In, Next, E, Res>
+    where
+        Next: Send + Sync + Serialize + DeserializeOwned + 'static,
+        Trans: Transition<Out, Next, Resources = Res, Error = E> + Clone + Send + Sync + 'static,
+        F: Fn(TimeoutError) -> E + Clone + Send + Sync + 'static,
+    {
         let Axon {
             mut schematic,
             executor: prev_executor,
@@ -772,10 +1289,41 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
         } = self;
 
-        let next_node_id = uuid::Uuid::new_v4().to_string();
-        let next_node = Node {
+        let overrides = node_policy_override(node_policies.as_deref(), &transition.label());
+        if let Some(overrides) = overrides {
+            warn_unapplied_node_policy(
+                &transition.label(),
+                &[
+                    ("max_retries", overrides.max_retries.is_some()),
+                    ("backoff", overrides.backoff.is_some()),
+                ],
+            );
+        }
+        let configured_timeout = overrides.and_then(|o| o.timeout_ms);
+        let duration = configured_timeout
+            .map(std::time::Duration::from_millis)
+            .unwrap_or(duration);
+        let applied_policy = AppliedNodePolicy {
+            max_retries: None,
+            backoff: None,
+            timeout_ms: Some(duration.as_millis() as u64),
+            source: if configured_timeout.is_some() {
+                NodePolicySource::Config
+            } else {
+                NodePolicySource::Code
+            },
+        };
+
+        let next_node_id = transition_node_id(&transition, &schematic, schematic.nodes.len());
+        let mut next_node = Node {
             id: next_node_id.clone(),
             kind: NodeKind::Atom,
             label: transition.label(),
@@ -783,9 +1331,15 @@ where
             input_type: type_name_of::<Out>(),
             output_type: type_name_of::<Next>(),
             resource_type: type_name_of::<Res>(),
-            metadata: Default::default(),
+            metadata: StepMetadata {
+                annotations: transition.annotations(),
+                ..with_required_resources(
+                    node_metadata(transition.description(), Some(applied_policy)),
+                    transition.required_resources(),
+                )
+            },
             bus_capability: bus_capability_schema_from_policy(transition.bus_access_policy()),
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+            source_location: Some(transition_source(&transition, caller)),
             position: transition
                 .position()
                 .map(|(x, y)| ranvier_core::schematic::Position { x, y }),
@@ -802,6 +1356,7 @@ where
             .map(|n| n.id.clone())
             .unwrap_or_default();
 
+        push_branch_exits(&mut schematic, &mut next_node, transition.branches());
         schematic.nodes.push(next_node);
         schematic.edges.push(Edge {
             from: last_node_id,
@@ -834,6 +1389,11 @@ where
                         other => return other.map(|_| unreachable!()),
                     };
 
+                    let timeout_duration = running_node_policy(bus, &timeline_node_label)
+                        .and_then(|overrides| overrides.timeout_ms)
+                        .map(std::time::Duration::from_millis)
+                        .unwrap_or(timeout_duration);
+
                     // Execute with timeout
                     match tokio::time::timeout(
                         timeout_duration,
@@ -861,10 +1421,25 @@ where
                                 timeline.push(TimelineEvent::NodeTimeout {
                                     node_id: timeline_node_id.clone(),
                                     timeout_ms: timeout_duration.as_millis() as u64,
-                                    timestamp: now_ms(),
+                                    timestamp: Timestamp::now(),
+                                    trace: None,
                                 });
                             }
-                            Outcome::Fault(error_factory())
+                            let timeout = TimeoutError {
+                                node_label: timeline_node_label.clone(),
+                                timeout_ms: timeout_duration.as_millis() as u64,
+                            };
+                            record_fault_cause(
+                                bus,
+                                FaultCause::new(
+                                    timeline_node_id,
+                                    timeline_node_label,
+                                    timeout.to_string(),
+                                )
+                                .with_step_index(step_idx)
+                                .with_category(NODE_TIMEOUT),
+                            );
+                            Outcome::Fault(error_factory(timeout))
                         }
                     }
                 })
@@ -883,6 +1458,12 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
         }
     }
 
@@ -916,13 +1497,19 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
         } = self;
 
         // 1. Add Primary Node
-        let next_node_id = uuid::Uuid::new_v4().to_string();
-        let comp_node_id = uuid::Uuid::new_v4().to_string();
+        let next_node_id = transition_node_id(&transition, &schematic, schematic.nodes.len());
+        let comp_node_id = transition_node_id(&compensation, &schematic, schematic.nodes.len() + 1);
 
-        let next_node = Node {
+        let mut next_node = Node {
             id: next_node_id.clone(),
             kind: NodeKind::Atom,
             label: transition.label(),
@@ -930,9 +1517,15 @@ where
             input_type: type_name_of::<Out>(),
             output_type: type_name_of::<Next>(),
             resource_type: type_name_of::<Res>(),
-            metadata: Default::default(),
+            metadata: StepMetadata {
+                annotations: transition.annotations(),
+                ..with_required_resources(
+                    node_metadata(transition.description(), None),
+                    transition.required_resources(),
+                )
+            },
             bus_capability: bus_capability_schema_from_policy(transition.bus_access_policy()),
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+            source_location: Some(transition_source(&transition, caller)),
             position: transition
                 .position()
                 .map(|(x, y)| ranvier_core::schematic::Position { x, y }),
@@ -952,7 +1545,13 @@ where
             input_type: type_name_of::<Out>(),
             output_type: "void".to_string(),
             resource_type: type_name_of::<Res>(),
-            metadata: Default::default(),
+            metadata: StepMetadata {
+                annotations: compensation.annotations(),
+                ..with_required_resources(
+                    node_metadata(compensation.description(), None),
+                    compensation.required_resources(),
+                )
+            },
             bus_capability: None,
             source_location: None,
             position: compensation
@@ -971,6 +1570,7 @@ where
             .map(|n| n.id.clone())
             .unwrap_or_default();
 
+        push_branch_exits(&mut schematic, &mut next_node, transition.branches());
         schematic.nodes.push(next_node);
         schematic.nodes.push(comp_node);
         schematic.edges.push(Edge {
@@ -1175,20 +1775,38 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
         }
     }
 
     /// Attach a compensation transition to the previously added node.
-    /// This establishes a Schematic-level Saga compensation mapping.
+    ///
+    /// When a later node faults, the saga rollback walks back through the
+    /// completed nodes in reverse order and runs this transition with the
+    /// node's output; on a node added with `then_compensated`, it replaces
+    /// that node's compensation in the rollback. Attaching a compensation
+    /// enables the saga policy; the rollback is summarized in a
+    /// [`SagaRollbackReport`](ranvier_core::saga::SagaRollbackReport) on the Bus.
+    ///
+    /// ```rust,ignore
+    /// let order = Axon::new("Order")
+    ///     .then(ReserveInventory).compensate_with(ReleaseInventory)
+    ///     .then(ChargeCard).compensate_with(RefundCard)
+    ///     .then(Ship);
+    /// ```
     #[track_caller]
     pub fn compensate_with<Comp>(mut self, transition: Comp) -> Self
     where
         Comp: Transition<Out, (), Resources = Res, Error = E> + Clone + Send + Sync + 'static,
     {
-        // NOTE: This currently only updates the Schematic.
-        // For runtime compensation behavior, use `then_compensated`.
         let caller = Location::caller();
-        let comp_node_id = uuid::Uuid::new_v4().to_string();
+        let comp_node_id =
+            transition_node_id(&transition, &self.schematic, self.schematic.nodes.len());
 
         let comp_node = Node {
             id: comp_node_id.clone(),
@@ -1198,9 +1816,15 @@ where
             input_type: type_name_of::<Out>(),
             output_type: "void".to_string(),
             resource_type: type_name_of::<Res>(),
-            metadata: Default::default(),
+            metadata: StepMetadata {
+                annotations: transition.annotations(),
+                ..with_required_resources(
+                    node_metadata(transition.description(), None),
+                    transition.required_resources(),
+                )
+            },
             bus_capability: None,
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+            source_location: Some(transition_source(&transition, caller)),
             position: transition
                 .position()
                 .map(|(x, y)| ranvier_core::schematic::Position { x, y }),
@@ -1211,53 +1835,57 @@ where
             terminal: None,
         };
 
-        if let Some(last_node) = self.schematic.nodes.last_mut() {
-            last_node.compensation_node_id = Some(comp_node_id.clone());
-        }
-
-        self.schematic.nodes.push(comp_node);
-        self
-    }
-
-    /// Add a branch point
-    #[track_caller]
-    pub fn branch(mut self, branch_id: impl Into<String>, label: &str) -> Self {
-        let caller = Location::caller();
-        let branch_id_str = branch_id.into();
-        let last_node_id = self
+        // Compensation nodes are appended after the node they undo, so skip
+        // them when looking for the node to attach to.
+        let compensation_ids: Vec<String> = self
             .schematic
             .nodes
-            .last()
-            .map(|n| n.id.clone())
-            .unwrap_or_default();
-
-        let branch_node = Node {
-            id: uuid::Uuid::new_v4().to_string(),
-            kind: NodeKind::Synapse,
-            label: label.to_string(),
-            description: None,
-            input_type: type_name_of::<Out>(),
-            output_type: type_name_of::<Out>(),
-            resource_type: type_name_of::<Res>(),
-            metadata: Default::default(),
-            bus_capability: None,
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
-            position: None,
-            compensation_node_id: None,
-            input_schema: None,
-            output_schema: None,
-            item_type: None,
-            terminal: None,
+            .iter()
+            .filter_map(|node| node.compensation_node_id.clone())
+            .collect();
+        let Some(target) = self
+            .schematic
+            .nodes
+            .iter_mut()
+            .rev()
+            .find(|node| !compensation_ids.contains(&node.id))
+        else {
+            return self;
         };
+        target.compensation_node_id = Some(comp_node_id);
+        let target_id = target.id.clone();
+        self.schematic.nodes.push(comp_node);
 
-        self.schematic.nodes.push(branch_node);
-        self.schematic.edges.push(Edge {
-            from: last_node_id,
-            to: branch_id_str.clone(),
-            kind: EdgeType::Branch(branch_id_str),
-            label: Some("Branch".to_string()),
-        });
-
+        let bus_policy = transition.bus_access_policy();
+        let handler: ranvier_core::saga::SagaCompensationFn<E, Res> =
+            Arc::new(move |output_data, res, bus| {
+                let comp = transition.clone();
+                let bus_policy = bus_policy.clone();
+                Box::pin(async move {
+                    let output: Out = match serde_json::from_slice(&output_data) {
+                        Ok(output) => output,
+                        Err(error) => {
+                            return Outcome::emit(
+                                "saga.compensation.input_deserialization_failed",
+                                Some(serde_json::json!({
+                                    "error": error.to_string(),
+                                    "input_type": type_name_of::<Out>(),
+                                    "snapshot_bytes": output_data.len()
+                                })),
+                            );
+                        }
+                    };
+                    bus.set_access_policy(comp.label(), bus_policy);
+                    let res = comp.run(output, res, bus).await;
+                    bus.clear_access_policy();
+                    res
+                })
+            });
+        match self.saga_compensation_registry.write() {
+            Ok(mut registry) => registry.register_output(target_id, handler),
+            Err(poisoned) => poisoned.into_inner().register_output(target_id, handler),
+        }
+        self.saga_policy = SagaPolicy::Enabled;
         self
     }
 
@@ -1317,6 +1945,44 @@ where
         self.then_stream_internal(streaming, Some(timeout_config))
     }
 
+    /// Chain a [`StreamTransition`](ranvier_core::streaming::StreamTransition)
+    /// whose items are per-item outcomes.
+    ///
+    /// The returned `StreamingAxon` yields `Outcome<Next, E>` items. Add
+    /// per-item steps with
+    /// [`then_each`](crate::streaming_axon::StreamingAxon::then_each) and
+    /// finish with
+    /// [`collect_outcomes`](crate::streaming_axon::StreamingAxon::collect_outcomes)
+    /// or consume the stream directly.
+    ///
+    /// ```rust,ignore
+    /// let export = Axon::new("Export")
+    ///     .then(ParseRequest)
+    ///     .then_stream_outcomes(ScanCustomers)
+    ///     .then_each(MaskPii)
+    ///     .then_each(ToCsvLine);
+    /// let mut lines = export.execute(request, &resources, &mut bus).await?;
+    /// ```
+    #[cfg(feature = "streaming")]
+    #[track_caller]
+    pub fn then_stream_outcomes<Next, S>(
+        self,
+        streaming: S,
+    ) -> crate::streaming_axon::StreamingAxon<In, Outcome<Next, E>, E, Res>
+    where
+        Next: Send + 'static,
+        S: ranvier_core::streaming::StreamTransition<Out, Next, Error = E, Resources = Res>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.then_stream_internal(
+            crate::streaming_axon::OutcomeStreamAdapter::new(streaming),
+            None,
+        )
+    }
+
     #[cfg(feature = "streaming")]
     fn then_stream_internal<Item, SErr, S>(
         self,
@@ -1344,7 +2010,7 @@ where
         } = self;
 
         // Add streaming node to schematic
-        let stream_node_id = uuid::Uuid::new_v4().to_string();
+        let stream_node_id = schematic.next_node_id(&streaming.label());
         let stream_node = Node {
             id: stream_node_id.clone(),
             kind: NodeKind::StreamingTransition,
@@ -1353,9 +2019,9 @@ where
             input_type: type_name_of::<Out>(),
             output_type: format!("Stream<{}>", type_name_of::<Item>()),
             resource_type: type_name_of::<Res>(),
-            metadata: Default::default(),
+            metadata: node_metadata(streaming.description(), None),
             bus_capability: None,
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+            source_location: Some(SourceLocation::from(caller)),
             position: None,
             compensation_node_id: None,
             input_schema: None,
@@ -1594,6 +2260,19 @@ where
         Ok(true)
     }
 
+    /// Register this circuit's schematic in
+    /// [`SchematicRegistry::global`](ranvier_core::schematic_registry::SchematicRegistry::global)
+    /// under its name.
+    ///
+    /// Circuits mounted on an HTTP ingress register on their own, executed ones
+    /// on their first execution; call this for circuits that should be listed
+    /// before they first run.
+    pub fn register(self) -> Self {
+        ranvier_core::schematic_registry::SchematicRegistry::global()
+            .register(self.schematic.clone());
+        self
+    }
+
     /// Export schematic according to the provided request.
     pub fn export_schematic(&self, request: &SchematicExportRequest) -> anyhow::Result<()> {
         let json = serde_json::to_string_pretty(self.schematic())?;
@@ -1,9 +1,15 @@
 use ranvier_core::bus::Bus;
 use ranvier_core::cancellation::{CancellationContext, CancellationToken};
 use ranvier_core::outcome::Outcome;
-use ranvier_core::saga::{SagaPolicy, SagaStack};
+use ranvier_core::redaction::RedactionRules;
+use ranvier_core::saga::{
+    SagaPolicy, SagaRollbackReport, SagaRollbackStep, SagaStack, SagaStepStatus,
+};
+use ranvier_core::schematic_registry::SchematicRegistry;
 use ranvier_core::telemetry::InterventionEvent;
-use ranvier_core::timeline::{Timeline, TimelineEvent};
+use ranvier_core::telemetry::TraceContext;
+use ranvier_core::timeline::tail::TimelineTail;
+use ranvier_core::timeline::{CaptureDirection, EventTrace, Timeline, TimelineEvent, Timestamp};
 use serde::{Serialize, de::DeserializeOwned};
 use std::panic::AssertUnwindSafe;
 use tracing::Instrument;
@@ -12,15 +18,24 @@ use super::*;
 use super::{
     ExecutionMode, ManualJump, ResumptionState, StartStep, compensation_auto_trigger,
     compensation_retry_policy, completion_from_outcome, ensure_timeline, extract_panic_message,
-    load_persistence_version, maybe_export_timeline, now_ms, outcome_kind_name, outcome_target,
-    outcome_type_name, persist_completion, persist_execution_event, persistence_auto_complete,
-    persistence_trace_id, run_compensation, should_attach_timeline,
+    in_flight_node, load_persistence_version, maybe_export_timeline, now_ms, outcome_kind_name,
+    outcome_target, outcome_type_name, persist_completion, persist_execution_event,
+    persistence_auto_complete, persistence_trace_id, push_capture, record_cancellation,
+    record_fault_cause, record_timeline_run, restore_trace_context, run_compensation,
+    should_attach_timeline, timeline_emitted,
 };
 
+use crate::dry_run::{DryRunPlan, DryRunReport};
+use crate::hooks::ExecutionHooks;
+use crate::idempotency::IdempotentExecution;
+use crate::interceptor::Interceptors;
+use crate::journal::{JournalEntry, JournalHandle, input_digest};
 use crate::persistence::{
     CompensationContext, CompensationHandle, CompensationIdempotencyHandle, CompletionState,
     PersistenceHandle,
 };
+use crate::timeline_store::TimelineStoreHandle;
+use crate::timeline_writer::TimelineWriter;
 
 impl<In, Out, E, Res> Axon<In, Out, E, Res>
 where
@@ -51,6 +66,7 @@ where
             bus.insert(crate::persistence::PersistenceTraceId(trace_id));
         }
         let caller_owned_timeline = bus.read::<Timeline>().is_some();
+        let caller_trace = bus.read::<TraceContext>().cloned();
 
         let race = {
             let execution = self.execute(input, resources, bus);
@@ -69,6 +85,22 @@ where
                 // installing and clearing its access policy, so cleanup
                 // explicitly restores framework access first.
                 bus.clear_access_policy();
+                let node_id = bus.read::<Timeline>().and_then(in_flight_node);
+                record_cancellation(bus, node_id, &context);
+                // The dropped execution left its own span on the Bus.
+                if let (Some(span), Some(run_id)) = (
+                    bus.read::<TraceContext>().cloned(),
+                    bus.read::<crate::persistence::PersistenceTraceId>()
+                        .map(|id| id.0.clone()),
+                ) && let Some(timeline) = bus.read_mut::<Timeline>()
+                {
+                    timeline.stamp(&EventTrace {
+                        trace_id: span.trace_id,
+                        run_id,
+                        parent_span_id: span.span_id,
+                    });
+                }
+                restore_trace_context(bus, caller_trace);
                 // Cleanup is shielded from the workflow token that just won.
                 // The caller still owns that original token and terminal
                 // context; compensation code receives a fresh control plane
@@ -86,7 +118,40 @@ where
     }
 
     /// Execute the Axon with the given input and resources.
+    ///
+    /// With an [`IdempotencyKey`](crate::idempotency::IdempotencyKey) and an
+    /// [`IdempotencyHandle`](crate::idempotency::IdempotencyHandle) in the
+    /// Bus, an execution that already completed under the same key returns
+    /// its recorded outcome instead of running again, and one still running
+    /// under it makes this call return an in-progress emit. The key is looked
+    /// up only once the singleton lock and the IAM check have let the
+    /// execution through.
     pub async fn execute(&self, input: In, resources: &Res, bus: &mut Bus) -> Outcome<Out, E> {
+        self.schematic_observed
+            .get_or_init(|| SchematicRegistry::global().observe(&self.schematic));
+        self.execute_once(input, resources, bus).await
+    }
+
+    /// Walk the circuit on transition estimates instead of running it.
+    ///
+    /// Each node's [`Transition::estimate`](ranvier_core::transition::Transition::estimate)
+    /// stands in for `run`; the report lists the visited nodes and the side
+    /// effects they declare. See [`dry_run`](crate::dry_run).
+    pub async fn dry_run(&self, input: In, resources: &Res, bus: &mut Bus) -> DryRunReport<Out, E> {
+        let plan = DryRunPlan::default();
+        // Shared so that forked branch buses see it too.
+        bus.insert_shared(plan.clone());
+        bus.insert(self.schematic.clone());
+        let outcome = (self.executor)(input, resources, bus).await;
+        let _ = bus.remove::<DryRunPlan>();
+        DryRunReport {
+            circuit: self.schematic.name.clone(),
+            path: plan.take(),
+            outcome,
+        }
+    }
+
+    async fn execute_once(&self, input: In, resources: &Res, bus: &mut Bus) -> Outcome<Out, E> {
         if let ExecutionMode::Singleton {
             lock_key,
             ttl_ms,
@@ -174,6 +239,26 @@ where
             }
         }
 
+        let Some(idempotent) = IdempotentExecution::from_bus(bus, &self.schematic.name, &input)
+        else {
+            return self.execute_admitted(input, resources, bus).await;
+        };
+        let claimed = match idempotent.claim().await {
+            Ok(claimed) => claimed,
+            Err(outcome) => return outcome,
+        };
+        let outcome = self.execute_admitted(input, resources, bus).await;
+        claimed.record(&outcome).await;
+        outcome
+    }
+
+    /// Run an execution the singleton lock and IAM check have admitted.
+    async fn execute_admitted(&self, input: In, resources: &Res, bus: &mut Bus) -> Outcome<Out, E> {
+        let _concurrency_permit = match &self.concurrency {
+            Some(scheduler) => Some(scheduler.acquire(&self.schematic.name).await),
+            None => None,
+        };
+
         let trace_id = persistence_trace_id(bus);
         let label = self.schematic.name.clone();
 
@@ -189,6 +274,26 @@ where
             .unwrap_or_else(|| self.dlq_policy.clone());
         bus.insert(effective_dlq_policy);
         bus.insert(self.schematic.clone());
+        // Shared so that nested executions on forked buses call them too.
+        let hooks = ExecutionHooks::collect(&self.execution_hooks);
+        match &hooks {
+            Some(hooks) => bus.insert_shared(hooks.clone()),
+            None => {
+                let _ = bus.remove::<ExecutionHooks>();
+            }
+        }
+        match Interceptors::new(&self.interceptors) {
+            Some(chain) => bus.insert_shared(chain),
+            None => {
+                let _ = bus.remove::<Interceptors>();
+            }
+        }
+        match &self.node_policies {
+            Some(policies) => bus.insert_shared(NodePolicies(policies.clone())),
+            None => {
+                let _ = bus.remove::<NodePolicies>();
+            }
+        }
         let effective_saga_policy = self
             .dynamic_saga_policy
             .as_ref()
@@ -436,14 +541,32 @@ where
             None
         };
 
-        let should_capture = should_attach_timeline(bus);
+        let caller_trace = bus.read::<TraceContext>().cloned();
+        let span = caller_trace
+            .as_ref()
+            .map(TraceContext::child)
+            .unwrap_or_default();
+        let event_trace = EventTrace {
+            trace_id: span.trace_id.clone(),
+            run_id: trace_id.clone(),
+            parent_span_id: span.span_id.clone(),
+        };
+
+        let should_capture = should_attach_timeline(bus, &label);
         let inserted_timeline = if should_capture {
             ensure_timeline(bus)
         } else {
             false
         };
+        let tail = bus.read::<TimelineTail>().cloned();
+        let caller_tail = match (&tail, bus.read_mut::<Timeline>()) {
+            (Some(tail), Some(timeline)) if should_capture => {
+                Some(timeline.set_tail(Some(tail.tap(Some(event_trace.clone())))))
+            }
+            _ => None,
+        };
         let ingress_started = std::time::Instant::now();
-        let ingress_enter_ts = now_ms();
+        let ingress_enter_ts = Timestamp::now();
         if should_capture
             && let (Some(timeline), Some(ingress)) =
                 (bus.read_mut::<Timeline>(), self.schematic.nodes.first())
@@ -452,12 +575,72 @@ where
                 node_id: ingress.id.clone(),
                 node_label: ingress.label.clone(),
                 timestamp: ingress_enter_ts,
+                trace: None,
             });
         }
+        let capture_policy = self.capture_policy.filter(|_| should_capture);
+        let redaction = bus.read::<RedactionRules>().cloned();
+        let bus_snapshot = capture_policy
+            .filter(|policy| policy.bus)
+            .map(|_| bus.snapshot())
+            .map(|mut snapshot| {
+                if let Some(rules) = &redaction {
+                    rules.redact_snapshot(&mut snapshot);
+                }
+                snapshot
+            });
+        if let Some(policy) = capture_policy
+            && let Some(timeline) = bus.read_mut::<Timeline>()
+        {
+            timeline.header.capture_format = Some(policy.format);
+            if policy.input {
+                push_capture(
+                    timeline,
+                    &self.schematic,
+                    CaptureDirection::Input,
+                    policy,
+                    redaction.as_ref(),
+                    &input,
+                );
+            }
+            if let Some(snapshot) = bus_snapshot {
+                timeline.push(TimelineEvent::BusCaptured {
+                    node_id: self
+                        .schematic
+                        .nodes
+                        .first()
+                        .map(|node| node.id.clone())
+                        .unwrap_or_default(),
+                    snapshot,
+                    timestamp: Timestamp::now(),
+                    trace: None,
+                });
+            }
+        }
+
+        let journal = bus.read::<JournalHandle>().map(JournalHandle::journal);
+        if let Some(journal) = journal.as_ref() {
+            let input_value = serde_json::to_value(&input).unwrap_or(serde_json::Value::Null);
+            let entry = JournalEntry {
+                trace_id: trace_id.clone(),
+                circuit: label.clone(),
+                input_digest: input_digest(&input_value),
+                started_at_ms: now_ms(),
+                input: journal.records_inputs().then_some(input_value),
+            };
+            if let Err(e) = journal.record_start(entry).await {
+                tracing::warn!(trace_id = %trace_id, "Failed to journal execution start: {}", e);
+            }
+        }
+
+        // Nested executions on this Bus become child spans of this one.
+        bus.insert(span.clone());
 
         let circuit_span = tracing::info_span!(
             "Circuit",
             ranvier.circuit = %label,
+            ranvier.trace_id = %span.trace_id,
+            ranvier.span_id = %span.span_id,
             ranvier.outcome_kind = tracing::field::Empty,
             ranvier.outcome_target = tracing::field::Empty
         );
@@ -502,7 +685,21 @@ where
             self.rollback_saga(resources, bus, &trace_id).await;
         }
 
-        let ingress_exit_ts = now_ms();
+        if let Some(policy) = capture_policy.filter(|policy| policy.output)
+            && let Outcome::Next(output) = &outcome
+            && let Some(timeline) = bus.read_mut::<Timeline>()
+        {
+            push_capture(
+                timeline,
+                &self.schematic,
+                CaptureDirection::Output,
+                policy,
+                redaction.as_ref(),
+                output,
+            );
+        }
+
+        let ingress_exit_ts = Timestamp::now();
         if should_capture
             && let (Some(timeline), Some(ingress)) =
                 (bus.read_mut::<Timeline>(), self.schematic.nodes.first())
@@ -512,6 +709,7 @@ where
                 outcome_type: outcome_type_name(&outcome),
                 duration_ms: ingress_started.elapsed().as_millis() as u64,
                 timestamp: ingress_exit_ts,
+                trace: None,
             });
         }
 
@@ -565,44 +763,100 @@ where
                 }
             }
 
-            if persistence_auto_complete(bus) {
+            // A suspended trace stays open until it is resumed.
+            if persistence_auto_complete(bus) && !outcome.is_suspend() {
                 persist_completion(handle, &trace_id, completion).await;
             }
         }
 
+        if let Some(hooks) = hooks.as_ref() {
+            hooks.outcome(&label, &outcome.to_json_value(), bus).await;
+        }
+
+        if let Some(journal) = journal.as_ref()
+            && let Err(e) = journal
+                .record_finish(&trace_id, outcome_kind_name(&outcome))
+                .await
+        {
+            tracing::warn!(trace_id = %trace_id, "Failed to journal execution finish: {}", e);
+        }
+
         if should_capture {
+            if let Some(timeline) = bus.read_mut::<Timeline>() {
+                timeline.stamp(&event_trace);
+                if let Some(caller_tail) = caller_tail {
+                    timeline.set_tail(caller_tail);
+                }
+            }
+        }
+        if should_capture && timeline_emitted(bus, &label, &outcome) {
             maybe_export_timeline(bus, &outcome);
+            if let Some(handle) = bus.read::<TimelineStoreHandle>().cloned() {
+                record_timeline_run(bus, &handle, &trace_id, &label, &outcome).await;
+            }
+            if let Some(writer) = bus.read::<TimelineWriter>() {
+                let mut timeline = bus.read::<Timeline>().cloned().unwrap_or_default();
+                timeline.sort();
+                if !writer.submit(timeline) {
+                    tracing::warn!(trace_id = %trace_id, "Timeline writer is full; dropping timeline");
+                }
+            }
         }
         if inserted_timeline {
             let _ = bus.remove::<Timeline>();
         }
+        restore_trace_context(bus, caller_trace);
 
         outcome
     }
 
     async fn rollback_saga(&self, resources: &Res, bus: &mut Bus, trace_id: &str) {
+        let mut report = SagaRollbackReport::default();
         while let Some(task) = {
             let mut stack = bus.read_mut::<SagaStack>();
             stack.as_mut().and_then(|stack| stack.pop())
         } {
             tracing::info!(trace_id = %trace_id, node_id = %task.node_id, "Compensating step: {}", task.node_label);
 
-            let handler = match self.saga_compensation_registry.read() {
-                Ok(registry) => registry.get(&task.node_id),
+            let (input_handler, output_handler) = match self.saga_compensation_registry.read() {
+                Ok(registry) => (
+                    registry.get(&task.node_id),
+                    registry.get_output(&task.node_id),
+                ),
                 Err(poisoned) => {
                     tracing::warn!(
                         trace_id = %trace_id,
                         node_id = %task.node_id,
                         "Saga compensation registry lock was poisoned; recovering registry for rollback lookup"
                     );
-                    poisoned.into_inner().get(&task.node_id)
+                    let registry = poisoned.into_inner();
+                    (
+                        registry.get(&task.node_id),
+                        registry.get_output(&task.node_id),
+                    )
                 }
             };
-            if let Some(handler) = handler {
-                let result = handler(task.input_snapshot, resources, bus).await;
-                match result {
+            // A `compensate_with` handler replaces the node's own compensation.
+            let compensation = match (input_handler, output_handler, task.output_snapshot) {
+                (_, Some(handler), Some(output)) => Some((handler, output)),
+                (Some(handler), _, _) => Some((handler, task.input_snapshot)),
+                _ => None,
+            };
+            let status = if let Some((handler, snapshot)) = compensation {
+                match handler(snapshot, resources, bus).await {
                     Outcome::Fault(error) => {
                         tracing::error!(trace_id = %trace_id, node_id = %task.node_id, "Saga compensation FAILED: {:?}", error);
+                        record_fault_cause(
+                            bus,
+                            FaultCause::new(
+                                task.node_id.clone(),
+                                format!("Compensate: {}", task.node_label),
+                                format!("{error:?}"),
+                            ),
+                        );
+                        SagaStepStatus::Failed {
+                            error: format!("{error:?}"),
+                        }
                     }
                     Outcome::Emit(event_type, payload) => {
                         tracing::warn!(
@@ -612,14 +866,32 @@ where
                             payload = ?payload,
                             "Saga compensation emitted a non-fatal event"
                         );
+                        if event_type == "saga.compensation.input_deserialization_failed" {
+                            SagaStepStatus::Failed { error: event_type }
+                        } else {
+                            SagaStepStatus::Compensated
+                        }
                     }
-                    _ => {}
+                    _ => SagaStepStatus::Compensated,
                 }
             } else {
-                tracing::warn!(trace_id = %trace_id, node_id = %task.node_id, "No compensation handler found in registry for saga rollback");
-            }
+                tracing::debug!(trace_id = %trace_id, node_id = %task.node_id, "No compensation handler found in registry for saga rollback");
+                SagaStepStatus::NoCompensation
+            };
+            report.steps.push(SagaRollbackStep {
+                node_id: task.node_id,
+                node_label: task.node_label,
+                status,
+            });
         }
-        tracing::info!(trace_id = %trace_id, "Saga automated rollback completed");
+        tracing::info!(
+            trace_id = %trace_id,
+            compensated = report.compensated(),
+            failed = report.failed(),
+            steps = report.steps.len(),
+            "Saga automated rollback completed"
+        );
+        bus.insert(report);
     }
 
     async fn finish_cancellation(
@@ -0,0 +1,240 @@
+use futures_util::stream::{self, StreamExt};
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::schematic::{Edge, EdgeType};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::panic::Location;
+use std::sync::Arc;
+
+use super::branch::subgraph_node;
+use super::type_name_of;
+use super::*;
+
+/// Per-item results of [`ForEach::collect_settled`], by input position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForEachReport<U, E> {
+    pub succeeded: Vec<(usize, U)>,
+    pub failed: Vec<(usize, E)>,
+}
+
+impl<U, E> ForEachReport<U, E> {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Per-item Axon over a `Vec` state; see [`Axon::for_each`].
+pub struct ForEach<In, T, U, E, Res = ()> {
+    axon: Axon<In, Vec<T>, E, Res>,
+    item: Axon<T, U, E, Res>,
+    concurrency: usize,
+    caller: &'static Location<'static>,
+}
+
+impl<In, T, E, Res> Axon<In, Vec<T>, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    T: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Run `item` once per element of the current `Vec` state.
+    ///
+    /// Items run one at a time unless [`ForEach::concurrency`] is raised.
+    /// Each item gets a Bus forked with [`Bus::fork_for_parallel`]. Finish
+    /// with [`ForEach::collect`] to stop at the first fault, or
+    /// [`ForEach::collect_settled`] to run every item and report failures.
+    ///
+    /// In the Schematic the item Axon becomes a single `ForEach(<name>)`
+    /// `Subgraph` node.
+    ///
+    /// ```rust,ignore
+    /// let axon = Axon::new("Fulfil")
+    ///     .then(LoadOrderLines)                      // -> Vec<OrderLine>
+    ///     .for_each(Axon::new("Line").then(ReserveStock).then(PackLine))
+    ///     .concurrency(8)
+    ///     .collect()                                 // -> Vec<PackedLine>
+    ///     .then(Ship);
+    /// ```
+    #[track_caller]
+    pub fn for_each<U>(self, item: Axon<T, U, E, Res>) -> ForEach<In, T, U, E, Res>
+    where
+        U: Send + Sync + Serialize + DeserializeOwned + 'static,
+    {
+        ForEach {
+            axon: self,
+            item,
+            concurrency: 1,
+            caller: Location::caller(),
+        }
+    }
+}
+
+impl<In, T, U, E, Res> ForEach<In, T, U, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    T: Send + Sync + Serialize + DeserializeOwned + 'static,
+    U: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Maximum number of items in flight at once (at least 1).
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = limit.max(1);
+        self
+    }
+
+    /// Collect every item's `Next` value in input order.
+    ///
+    /// The first item that returns anything other than `Next` ends the
+    /// step with that outcome and cancels the items still in flight.
+    pub fn collect(self) -> Axon<In, Vec<U>, E, Res> {
+        self.build(true, |results| {
+            let mut values = Vec::with_capacity(results.len());
+            for (_, outcome) in results {
+                match outcome {
+                    Outcome::Next(value) => values.push(value),
+                    other => return other.map(|_| unreachable!()),
+                }
+            }
+            Outcome::Next(values)
+        })
+    }
+
+    /// Run every item and report successes and faults side by side.
+    ///
+    /// Item faults do not fail the step. Any other non-`Next` outcome still
+    /// ends the step with that outcome once all items have finished.
+    pub fn collect_settled(self) -> Axon<In, ForEachReport<U, E>, E, Res> {
+        self.build(false, |results| {
+            let mut report = ForEachReport {
+                succeeded: Vec::new(),
+                failed: Vec::new(),
+            };
+            for (index, outcome) in results {
+                match outcome {
+                    Outcome::Next(value) => report.succeeded.push((index, value)),
+                    Outcome::Fault(error) => report.failed.push((index, error)),
+                    other => return other.map(|_| unreachable!()),
+                }
+            }
+            Outcome::Next(report)
+        })
+    }
+
+    fn build<Collected, F>(self, fail_fast: bool, finish: F) -> Axon<In, Collected, E, Res>
+    where
+        Collected: Send + Sync + Serialize + DeserializeOwned + 'static,
+        F: Fn(Vec<(usize, Outcome<U, E>)>) -> Outcome<Collected, E> + Send + Sync + 'static,
+    {
+        let ForEach {
+            axon,
+            item,
+            concurrency,
+            caller,
+        } = self;
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency: scheduler,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = axon;
+
+        let label = format!("ForEach({})", item.schematic.name);
+        let mut node = subgraph_node::<Res>(
+            schematic.next_node_id(&label),
+            item.schematic,
+            type_name_of::<Vec<T>>(),
+            type_name_of::<Collected>(),
+            caller,
+        );
+        node.label = label;
+        node.description = Some(format!("Runs per item, at most {concurrency} at once"));
+        if let Some(last) = schematic.nodes.last() {
+            schematic.edges.push(Edge {
+                from: last.id.clone(),
+                to: node.id.clone(),
+                kind: EdgeType::Linear,
+                label: Some("Next".to_string()),
+            });
+        }
+        schematic.nodes.push(node);
+
+        let item = item.executor;
+        let finish = Arc::new(finish);
+        let next_executor: Executor<In, Collected, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Collected, E>> {
+                let prev = prev_executor.clone();
+                let item = item.clone();
+                let finish = finish.clone();
+
+                Box::pin(async move {
+                    let items = match prev(input, res, bus).await {
+                        Outcome::Next(items) => items,
+                        other => return other.map(|_| unreachable!()),
+                    };
+                    let total = items.len();
+                    let runs = items.into_iter().enumerate().map(|(index, value)| {
+                        let item = item.clone();
+                        let mut item_bus = bus.fork_for_parallel();
+                        if let Some(token) = bus.cancellation_token().cloned() {
+                            item_bus.set_cancellation_token(token);
+                        }
+                        async move { (index, item(value, res, &mut item_bus).await) }
+                    });
+                    let mut in_flight = stream::iter(runs).buffer_unordered(concurrency);
+
+                    let mut results = Vec::with_capacity(total);
+                    while let Some((index, outcome)) = in_flight.next().await {
+                        let stop = fail_fast && !matches!(outcome, Outcome::Next(_));
+                        results.push((index, outcome));
+                        if stop {
+                            tracing::debug!(
+                                item = index,
+                                "ForEach item did not complete; cancelling remaining items"
+                            );
+                            return finish(results);
+                        }
+                    }
+                    results.sort_by_key(|(index, _)| *index);
+                    finish(results)
+                })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency: scheduler,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+}
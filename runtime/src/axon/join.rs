@@ -0,0 +1,523 @@
+use ranvier_core::bus::{Bus, BusAccessPolicy, BusTypeRef};
+use ranvier_core::metadata::NodeAnnotations;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::schematic::{Edge, EdgeType, Node, NodeKind, SourceLocation, stable_node_id};
+use ranvier_core::timeline::{Timeline, TimelineEvent, Timestamp};
+use ranvier_core::transition::{ResourceRequirement, Transition};
+use serde::{Serialize, de::DeserializeOwned};
+use std::panic::Location;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::persistence::PersistenceHandle;
+
+use super::parallel::{KeyedTimelineEvent, sort_parallel_branch_events};
+use super::*;
+use super::{
+    bus_capability_schema_from_policy, outcome_kind_name, outcome_type_name,
+    persist_execution_event, persistence_trace_id, type_name_of,
+};
+
+/// Schematic facts about one branch of a [`join`](Axon::join).
+#[derive(Debug, Clone)]
+pub struct JoinBranchInfo {
+    pub node_id: Option<String>,
+    pub label: String,
+    pub description: Option<String>,
+    pub output_type: String,
+    pub bus_access_policy: Option<BusAccessPolicy>,
+    pub input_schema: Option<serde_json::Value>,
+    pub required_resources: Vec<BusTypeRef>,
+    pub annotations: NodeAnnotations,
+}
+
+impl JoinBranchInfo {
+    fn of<In, Out, T>(transition: &T) -> Self
+    where
+        In: Send + 'static,
+        Out: Send + 'static,
+        T: Transition<In, Out>,
+    {
+        Self {
+            node_id: transition.node_id(),
+            label: transition.label(),
+            description: transition.description(),
+            output_type: type_name_of::<Out>(),
+            bus_access_policy: transition.bus_access_policy(),
+            input_schema: transition.input_schema(),
+            required_resources: transition.required_resources(),
+            annotations: transition.annotations(),
+        }
+    }
+}
+
+/// Timing of one executed join branch, in declaration order.
+#[derive(Debug, Clone)]
+pub struct JoinBranchTrace {
+    pub label: String,
+    pub outcome_type: String,
+    pub entered_at: Timestamp,
+    pub exited_at: Timestamp,
+    pub duration_ms: u64,
+}
+
+/// A tuple of transitions that run concurrently from the same input.
+///
+/// Implemented for tuples of 2 to 6 transitions sharing input, error and
+/// resource types; `Output` is the tuple of their outputs.
+pub trait JoinBranches<In, E, Res, Output>: Send + Sync + 'static {
+    fn branches(&self) -> Vec<JoinBranchInfo>;
+
+    /// Run every branch on its own Bus (one per branch, in order) and combine
+    /// the results. The first non-`Next` outcome in declaration order wins.
+    fn run_joined<'a>(
+        &'a self,
+        input: In,
+        resources: &'a Res,
+        buses: Vec<Bus>,
+    ) -> BoxFuture<'a, (Vec<JoinBranchTrace>, Outcome<Output, E>)>;
+}
+
+struct BranchRun<Out, E> {
+    trace: JoinBranchTrace,
+    outcome: Outcome<Out, E>,
+}
+
+async fn run_branch<In, Out, E, Res, T>(
+    transition: &T,
+    input: In,
+    resources: &Res,
+    mut bus: Bus,
+) -> BranchRun<Out, E>
+where
+    T: Transition<In, Out, Error = E, Resources = Res>,
+    In: Send + 'static,
+    Out: Send + 'static,
+    E: Send + 'static,
+    Res: ResourceRequirement,
+{
+    let label = transition.label();
+    let dry_run = DryRunPlan::from_bus(&bus);
+    bus.set_access_policy(label.clone(), transition.bus_access_policy());
+    let entered_at = Timestamp::now();
+    let started = Instant::now();
+    let outcome = match dry_run {
+        Some(plan) => plan.step(transition, &input, resources, &bus, None),
+        None => transition.run(input, resources, &mut bus).await,
+    };
+    let duration_ms = started.elapsed().as_millis() as u64;
+    BranchRun {
+        trace: JoinBranchTrace {
+            label,
+            outcome_type: outcome_type_name(&outcome),
+            entered_at,
+            exited_at: Timestamp::now(),
+            duration_ms,
+        },
+        outcome,
+    }
+}
+
+macro_rules! impl_join_branches {
+    ($(($T:ident, $O:ident, $run:ident, $idx:tt)),+) => {
+        impl<In, E, Res, $($T, $O),+> JoinBranches<In, E, Res, ($($O,)+)> for ($($T,)+)
+        where
+            In: Clone + Send + Sync + 'static,
+            E: Send + Sync + 'static,
+            Res: ResourceRequirement,
+            $(
+                $T: Transition<In, $O, Error = E, Resources = Res> + Send + Sync + 'static,
+                $O: Send + Sync + Serialize + DeserializeOwned + 'static,
+            )+
+        {
+            fn branches(&self) -> Vec<JoinBranchInfo> {
+                vec![$(JoinBranchInfo::of::<In, $O, _>(&self.$idx)),+]
+            }
+
+            fn run_joined<'a>(
+                &'a self,
+                input: In,
+                resources: &'a Res,
+                buses: Vec<Bus>,
+            ) -> BoxFuture<'a, (Vec<JoinBranchTrace>, Outcome<($($O,)+), E>)> {
+                Box::pin(async move {
+                    let mut buses = buses.into_iter();
+                    $(let $run = run_branch(
+                        &self.$idx,
+                        input.clone(),
+                        resources,
+                        buses.next().unwrap_or_else(Bus::new),
+                    );)+
+                    let ($($run,)+) = futures_util::join!($($run),+);
+                    let traces = vec![$($run.trace.clone()),+];
+                    $(
+                        let $run = match $run.outcome {
+                            Outcome::Next(value) => value,
+                            other => return (traces, other.map(|_| unreachable!())),
+                        };
+                    )+
+                    (traces, Outcome::Next(($($run,)+)))
+                })
+            }
+        }
+    };
+}
+
+impl_join_branches!((A, OA, a, 0), (B, OB, b, 1));
+impl_join_branches!((A, OA, a, 0), (B, OB, b, 1), (C, OC, c, 2));
+impl_join_branches!((A, OA, a, 0), (B, OB, b, 1), (C, OC, c, 2), (D, OD, d, 3));
+impl_join_branches!(
+    (A, OA, a, 0),
+    (B, OB, b, 1),
+    (C, OC, c, 2),
+    (D, OD, d, 3),
+    (F, OF, f, 4)
+);
+impl_join_branches!(
+    (A, OA, a, 0),
+    (B, OB, b, 1),
+    (C, OC, c, 2),
+    (D, OD, d, 3),
+    (F, OF, f, 4),
+    (G, OG, g, 5)
+);
+
+fn structural_node<Res>(
+    id: String,
+    kind: NodeKind,
+    label: &str,
+    description: String,
+    input_type: String,
+    output_type: String,
+    caller: &Location<'_>,
+) -> Node {
+    Node {
+        id,
+        kind,
+        label: label.to_string(),
+        description: Some(description),
+        input_type,
+        output_type,
+        resource_type: type_name_of::<Res>(),
+        metadata: Default::default(),
+        bus_capability: None,
+        source_location: Some(SourceLocation::from(caller)),
+        position: None,
+        compensation_node_id: None,
+        input_schema: None,
+        output_schema: None,
+        item_type: None,
+        terminal: None,
+    }
+}
+
+impl<In, Out, E, Res> Axon<In, Out, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Run a tuple of transitions concurrently on a clone of the current
+    /// output and continue with the tuple of their outputs.
+    ///
+    /// Unlike [`parallel`](Self::parallel), branches may produce different
+    /// types. All branches must return `Next`; otherwise the first non-`Next`
+    /// outcome in declaration order is returned. Each branch gets a fresh
+    /// [`Bus`], as with [`ParallelBusPolicy::Isolated`].
+    ///
+    /// The Schematic records a `FanOut` node, one `Atom` per branch joined by
+    /// `Parallel` edges, and a `FanIn` node whose output type is the tuple.
+    ///
+    /// ```rust,ignore
+    /// let axon = Axon::new("Dashboard")
+    ///     .then(LoadAccount)
+    ///     .join((FetchOrders, FetchInvoices, FetchTickets))
+    ///     .then(RenderDashboard); // receives (Orders, Invoices, Tickets)
+    /// ```
+    #[track_caller]
+    pub fn join<J, Joined>(self, branches: J) -> Axon<In, Joined, E, Res>
+    where
+        Out: Clone,
+        J: JoinBranches<Out, E, Res, Joined>,
+        Joined: Send + Sync + Serialize + DeserializeOwned + 'static,
+    {
+        self.join_with_bus_policy(branches, ParallelBusPolicy::Isolated)
+    }
+
+    /// [`join`](Self::join) with an explicit Bus inheritance policy; see
+    /// [`parallel_with_bus_policy`](Self::parallel_with_bus_policy).
+    #[track_caller]
+    pub fn join_with_bus_policy<J, Joined>(
+        self,
+        branches: J,
+        bus_policy: ParallelBusPolicy,
+    ) -> Axon<In, Joined, E, Res>
+    where
+        Out: Clone,
+        J: JoinBranches<Out, E, Res, Joined>,
+        Joined: Send + Sync + Serialize + DeserializeOwned + 'static,
+    {
+        let caller = Location::caller();
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = self;
+
+        let infos = branches.branches();
+        let fanout_id = schematic.next_node_id("FanOut");
+        let fanin_id = stable_node_id(
+            &schematic.name,
+            "FanIn",
+            schematic.nodes.len() + 1 + infos.len(),
+        );
+        let last_node_id = schematic
+            .nodes
+            .last()
+            .map(|n| n.id.clone())
+            .unwrap_or_default();
+
+        schematic.nodes.push(structural_node::<Res>(
+            fanout_id.clone(),
+            NodeKind::FanOut,
+            "FanOut",
+            format!(
+                "Join split ({} branches, bus={:?})",
+                infos.len(),
+                bus_policy
+            ),
+            type_name_of::<Out>(),
+            type_name_of::<Out>(),
+            caller,
+        ));
+        schematic.edges.push(Edge {
+            from: last_node_id,
+            to: fanout_id.clone(),
+            kind: EdgeType::Linear,
+            label: Some("Next".to_string()),
+        });
+
+        let mut branch_node_ids = Vec::with_capacity(infos.len());
+        for (i, info) in infos.iter().enumerate() {
+            let branch_id = info
+                .node_id
+                .clone()
+                .unwrap_or_else(|| schematic.next_node_id(&info.label));
+            schematic.nodes.push(Node {
+                id: branch_id.clone(),
+                kind: NodeKind::Atom,
+                label: info.label.clone(),
+                description: info.description.clone(),
+                input_type: type_name_of::<Out>(),
+                output_type: info.output_type.clone(),
+                resource_type: type_name_of::<Res>(),
+                metadata: StepMetadata {
+                    annotations: info.annotations.clone(),
+                    ..with_required_resources(
+                        node_metadata(info.description.clone(), None),
+                        info.required_resources.clone(),
+                    )
+                },
+                bus_capability: bus_capability_schema_from_policy(info.bus_access_policy.clone()),
+                source_location: Some(SourceLocation::from(caller)),
+                position: None,
+                compensation_node_id: None,
+                input_schema: info.input_schema.clone(),
+                output_schema: None,
+                item_type: None,
+                terminal: None,
+            });
+            schematic.edges.push(Edge {
+                from: fanout_id.clone(),
+                to: branch_id.clone(),
+                kind: EdgeType::Parallel,
+                label: Some(format!("Branch {}", i)),
+            });
+            branch_node_ids.push(branch_id);
+        }
+
+        schematic.nodes.push(structural_node::<Res>(
+            fanin_id.clone(),
+            NodeKind::FanIn,
+            "FanIn",
+            format!("Join into tuple (bus={:?})", bus_policy),
+            type_name_of::<Out>(),
+            type_name_of::<Joined>(),
+            caller,
+        ));
+        for branch_id in &branch_node_ids {
+            schematic.edges.push(Edge {
+                from: branch_id.clone(),
+                to: fanin_id.clone(),
+                kind: EdgeType::Parallel,
+                label: Some("Join".to_string()),
+            });
+        }
+
+        let branches = Arc::new(branches);
+        let branch_node_ids = Arc::new(branch_node_ids);
+        let step_idx = schematic.nodes.len() as u64 - 1;
+
+        let next_executor: Executor<In, Joined, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Joined, E>> {
+                let prev = prev_executor.clone();
+                let branches = branches.clone();
+                let branch_node_ids = branch_node_ids.clone();
+                let fanout_id = fanout_id.clone();
+                let fanin_id = fanin_id.clone();
+
+                Box::pin(async move {
+                    let state = match prev(input, res, bus).await {
+                        Outcome::Next(t) => t,
+                        other => return other.map(|_| unreachable!()),
+                    };
+
+                    let fanout_started = Instant::now();
+                    if let Some(timeline) = bus.read_mut::<Timeline>() {
+                        timeline.push(TimelineEvent::NodeEnter {
+                            node_id: fanout_id.clone(),
+                            node_label: "FanOut".to_string(),
+                            timestamp: Timestamp::now(),
+                            trace: None,
+                        });
+                    }
+
+                    let cancellation_token = bus.cancellation_token().cloned();
+                    let dry_run = DryRunPlan::from_bus(bus);
+                    let buses = (0..branch_node_ids.len())
+                        .map(|_| {
+                            let mut branch_bus = match bus_policy {
+                                ParallelBusPolicy::Isolated => Bus::new(),
+                                ParallelBusPolicy::InheritShared => bus.fork_for_parallel(),
+                            };
+                            if let Some(token) = cancellation_token.clone() {
+                                branch_bus.set_cancellation_token(token);
+                            }
+                            if let Some(plan) = dry_run.clone() {
+                                branch_bus.insert_shared(plan);
+                            }
+                            branch_bus
+                        })
+                        .collect();
+
+                    let (traces, combined) = branches.run_joined(state, res, buses).await;
+
+                    if let Some(timeline) = bus.read_mut::<Timeline>() {
+                        let mut branch_events: Vec<KeyedTimelineEvent> =
+                            Vec::with_capacity(traces.len() * 2);
+                        for (index, (trace, node_id)) in
+                            traces.iter().zip(branch_node_ids.iter()).enumerate()
+                        {
+                            branch_events.push((
+                                trace.entered_at,
+                                0,
+                                index,
+                                TimelineEvent::NodeEnter {
+                                    node_id: node_id.clone(),
+                                    node_label: trace.label.clone(),
+                                    timestamp: trace.entered_at,
+                                    trace: None,
+                                },
+                            ));
+                            branch_events.push((
+                                trace.exited_at,
+                                1,
+                                index,
+                                TimelineEvent::NodeExit {
+                                    node_id: node_id.clone(),
+                                    outcome_type: trace.outcome_type.clone(),
+                                    duration_ms: trace.duration_ms,
+                                    timestamp: trace.exited_at,
+                                    trace: None,
+                                },
+                            ));
+                        }
+                        sort_parallel_branch_events(&mut branch_events);
+                        for (_, _, _, event) in branch_events {
+                            timeline.push(event);
+                        }
+                        timeline.push(TimelineEvent::NodeExit {
+                            node_id: fanout_id.clone(),
+                            outcome_type: "Next".to_string(),
+                            duration_ms: fanout_started.elapsed().as_millis() as u64,
+                            timestamp: Timestamp::now(),
+                            trace: None,
+                        });
+                        let fanin_ts = Timestamp::now();
+                        timeline.push(TimelineEvent::NodeEnter {
+                            node_id: fanin_id.clone(),
+                            node_label: "FanIn".to_string(),
+                            timestamp: fanin_ts,
+                            trace: None,
+                        });
+                        timeline.push(TimelineEvent::NodeExit {
+                            node_id: fanin_id.clone(),
+                            outcome_type: outcome_type_name(&combined),
+                            duration_ms: 0,
+                            timestamp: fanin_ts,
+                            trace: None,
+                        });
+                    }
+
+                    if let Some(handle) = bus.read::<PersistenceHandle>() {
+                        let trace_id = persistence_trace_id(bus);
+                        let (circuit, version) = bus
+                            .read::<ranvier_core::schematic::Schematic>()
+                            .map(|s| (s.name.clone(), s.schema_version.clone()))
+                            .unwrap_or_default();
+                        persist_execution_event(
+                            handle,
+                            &trace_id,
+                            &circuit,
+                            &version,
+                            step_idx,
+                            Some(fanin_id.clone()),
+                            outcome_kind_name(&combined),
+                            Some(combined.to_json_value()),
+                        )
+                        .await;
+                    }
+
+                    combined
+                })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+}
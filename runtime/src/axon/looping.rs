@@ -0,0 +1,268 @@
+use ranvier_core::bus::Bus;
+use ranvier_core::fault::FaultCause;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::schematic::{Edge, EdgeType, Node, NodeKind, SourceLocation, stable_node_id};
+use serde::{Serialize, de::DeserializeOwned};
+use std::panic::Location;
+use std::sync::Arc;
+
+use super::branch::subgraph_node;
+use super::*;
+use super::{record_fault_cause, type_name_of};
+
+/// Fault category recorded when a loop hits its iteration limit.
+pub const LOOP_LIMIT_EXCEEDED: &str = "loop_limit_exceeded";
+
+/// A [`loop_while`](Axon::loop_while) that still wanted to iterate after
+/// `max_iterations` runs of its body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LoopLimitExceeded {
+    pub loop_label: String,
+    pub max_iterations: u32,
+}
+
+impl LoopLimitExceeded {
+    pub fn category(&self) -> &'static str {
+        LOOP_LIMIT_EXCEEDED
+    }
+}
+
+impl std::fmt::Display for LoopLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{LOOP_LIMIT_EXCEEDED}: '{}' still looping after {} iterations",
+            self.loop_label, self.max_iterations
+        )
+    }
+}
+
+impl std::error::Error for LoopLimitExceeded {}
+
+impl From<LoopLimitExceeded> for String {
+    fn from(exceeded: LoopLimitExceeded) -> Self {
+        exceeded.to_string()
+    }
+}
+
+impl From<LoopLimitExceeded> for ranvier_core::error::RanvierError {
+    fn from(exceeded: LoopLimitExceeded) -> Self {
+        Self::internal(exceeded.to_string())
+    }
+}
+
+type LoopPredicate<Out> = Arc<dyn Fn(&Out) -> bool + Send + Sync>;
+
+/// A loop waiting for its iteration limit; see [`Axon::loop_while`].
+pub struct LoopWhile<In, Out, E, Res = ()> {
+    axon: Axon<In, Out, E, Res>,
+    predicate: LoopPredicate<Out>,
+    body: Axon<Out, Out, E, Res>,
+    caller: &'static Location<'static>,
+}
+
+impl<In, Out, E, Res> Axon<In, Out, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Run `body` repeatedly while `predicate` holds for the current value.
+    ///
+    /// The predicate is checked before every iteration, so a value that
+    /// already fails it skips the body entirely. A body `Fault`, `Branch`,
+    /// `Jump` or `Emit` ends the loop and is returned as-is.
+    ///
+    /// The loop is only usable once [`LoopWhile::max_iterations`] is set.
+    /// In the Schematic it becomes a `LoopWhile` node with the body as a
+    /// `Subgraph` and a `Jump` edge from the body back to the condition.
+    ///
+    /// ```rust,ignore
+    /// let axon = Axon::new("Poll")
+    ///     .then(SubmitJob)
+    ///     .loop_while(|job: &Job| !job.done, Axon::new("Refresh").then(RefreshJob))
+    ///     .max_iterations(20)
+    ///     .then(CollectResult);
+    /// ```
+    #[track_caller]
+    pub fn loop_while<P>(
+        self,
+        predicate: P,
+        body: Axon<Out, Out, E, Res>,
+    ) -> LoopWhile<In, Out, E, Res>
+    where
+        P: Fn(&Out) -> bool + Send + Sync + 'static,
+    {
+        LoopWhile {
+            axon: self,
+            predicate: Arc::new(predicate),
+            body,
+            caller: Location::caller(),
+        }
+    }
+}
+
+impl<In, Out, E, Res> LoopWhile<In, Out, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Cap the number of body runs.
+    ///
+    /// When the predicate still holds after `max_iterations` runs, the loop
+    /// faults with [`LoopLimitExceeded`] and records a fault cause
+    /// categorised as [`LOOP_LIMIT_EXCEEDED`].
+    pub fn max_iterations(self, max_iterations: u32) -> Axon<In, Out, E, Res>
+    where
+        E: From<LoopLimitExceeded>,
+    {
+        let LoopWhile {
+            axon,
+            predicate,
+            body,
+            caller,
+        } = self;
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = axon;
+
+        let loop_label = format!("LoopWhile({})", body.schematic.name);
+        let last_node_id = schematic.nodes.last().map(|n| n.id.clone());
+        let condition_id = stable_node_id(&schematic.name, &loop_label, schematic.nodes.len() + 1);
+        let body_node = subgraph_node::<Res>(
+            schematic.next_node_id(&body.schematic.name),
+            body.schematic,
+            type_name_of::<Out>(),
+            type_name_of::<Out>(),
+            caller,
+        );
+        let body_node_id = body_node.id.clone();
+        schematic.nodes.push(body_node);
+        schematic.nodes.push(Node {
+            id: condition_id.clone(),
+            kind: NodeKind::Synapse,
+            label: loop_label.clone(),
+            description: Some(format!("Repeats the body at most {max_iterations} times")),
+            input_type: type_name_of::<Out>(),
+            output_type: type_name_of::<Out>(),
+            resource_type: type_name_of::<Res>(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: Some(SourceLocation::from(caller)),
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        });
+        if let Some(from) = last_node_id {
+            schematic.edges.push(Edge {
+                from,
+                to: condition_id.clone(),
+                kind: EdgeType::Linear,
+                label: Some("Next".to_string()),
+            });
+        }
+        schematic.edges.push(Edge {
+            from: condition_id.clone(),
+            to: body_node_id.clone(),
+            kind: EdgeType::Linear,
+            label: Some("While".to_string()),
+        });
+        schematic.edges.push(Edge {
+            from: body_node_id,
+            to: condition_id.clone(),
+            kind: EdgeType::Jump,
+            label: Some("Repeat".to_string()),
+        });
+        let step_idx = schematic.nodes.len().saturating_sub(1) as u64;
+
+        let body = body.executor;
+        let next_executor: Executor<In, Out, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Out, E>> {
+                let prev = prev_executor.clone();
+                let predicate = predicate.clone();
+                let body = body.clone();
+                let condition_id = condition_id.clone();
+                let loop_label = loop_label.clone();
+
+                Box::pin(async move {
+                    let mut value = match prev(input, res, bus).await {
+                        Outcome::Next(value) => value,
+                        other => return other,
+                    };
+                    let mut iterations = 0;
+                    while predicate(&value) {
+                        if iterations == max_iterations {
+                            let exceeded = LoopLimitExceeded {
+                                loop_label: loop_label.clone(),
+                                max_iterations,
+                            };
+                            tracing::warn!(
+                                node_id = %condition_id,
+                                loop_label = %loop_label,
+                                max_iterations,
+                                "Loop iteration limit exceeded"
+                            );
+                            record_fault_cause(
+                                bus,
+                                FaultCause::new(condition_id, loop_label, exceeded.to_string())
+                                    .with_step_index(step_idx)
+                                    .with_category(LOOP_LIMIT_EXCEEDED),
+                            );
+                            return Outcome::Fault(E::from(exceeded));
+                        }
+                        iterations += 1;
+                        value = match body(value, res, bus).await {
+                            Outcome::Next(value) => value,
+                            other => return other,
+                        };
+                    }
+                    Outcome::Next(value)
+                })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+}
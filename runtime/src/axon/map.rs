@@ -0,0 +1,313 @@
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::saga::{SagaCompensationFn, SagaCompensationRegistry};
+use ranvier_core::schematic::{Edge, EdgeType, NodeKind, Schematic};
+use serde::{Serialize, de::DeserializeOwned};
+use std::panic::Location;
+use std::sync::Arc;
+
+use super::recover::plain_node;
+use super::type_name_of;
+use super::*;
+
+fn push_adapter_node<Res>(
+    schematic: &mut Schematic,
+    kind: NodeKind,
+    label: &str,
+    description: String,
+    input_type: String,
+    output_type: String,
+    caller: &Location<'_>,
+) {
+    let id = schematic.next_node_id(label);
+    let mut node = plain_node::<Res>(id, kind, label, input_type, output_type, caller);
+    node.description = Some(description);
+    if let Some(last) = schematic.nodes.last() {
+        schematic.edges.push(Edge {
+            from: last.id.clone(),
+            to: node.id.clone(),
+            kind: EdgeType::Linear,
+            label: Some("Next".to_string()),
+        });
+    }
+    schematic.nodes.push(node);
+}
+
+impl<In, Out, E, Res> Axon<In, Out, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Convert the `Next` value of the chain so far with a plain function.
+    ///
+    /// Unlike [`then_fn`](Self::then_fn) the conversion is not a full step:
+    /// it records no timeline events, checkpoints or saga entries. It shows
+    /// up in the Schematic as a `Map` node.
+    ///
+    /// ```rust,ignore
+    /// let axon = Axon::new("Orders")
+    ///     .then(LoadOrder)
+    ///     .map(|order: Order| order.id)
+    ///     .then(ShipById);
+    /// ```
+    #[track_caller]
+    pub fn map<Next, F>(self, f: F) -> Axon<In, Next, E, Res>
+    where
+        Next: Send + Sync + Serialize + DeserializeOwned + 'static,
+        F: Fn(Out) -> Next + Send + Sync + 'static,
+    {
+        let caller = Location::caller();
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = self;
+
+        push_adapter_node::<Res>(
+            &mut schematic,
+            NodeKind::Atom,
+            "Map",
+            format!("{} -> {}", type_name_of::<Out>(), type_name_of::<Next>()),
+            type_name_of::<Out>(),
+            type_name_of::<Next>(),
+            caller,
+        );
+
+        let f = Arc::new(f);
+        let next_executor: Executor<In, Next, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Next, E>> {
+                let prev = prev_executor.clone();
+                let f = f.clone();
+                Box::pin(async move { prev(input, res, bus).await.map(|value| f(value)) })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Convert the error type of the chain so far with a plain function.
+    ///
+    /// Faults from every earlier step, including the saga compensations
+    /// registered so far, are converted; `Next` passes through unchanged. In
+    /// the Schematic this is a `MapErr` node.
+    ///
+    /// ```rust,ignore
+    /// let axon = Axon::<Request, Request, DbError>::new("Lookup")
+    ///     .then(QueryUser)
+    ///     .map_err(AppError::Database)
+    ///     .then(Authorize); // Transition<User, User, Error = AppError>
+    /// ```
+    #[track_caller]
+    pub fn map_err<E2, F>(self, f: F) -> Axon<In, Out, E2, Res>
+    where
+        E2: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+        F: Fn(E) -> E2 + Send + Sync + 'static,
+    {
+        let caller = Location::caller();
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = self;
+
+        push_adapter_node::<Res>(
+            &mut schematic,
+            NodeKind::Atom,
+            "MapErr",
+            format!(
+                "Fault({}) -> Fault({})",
+                type_name_of::<E>(),
+                type_name_of::<E2>()
+            ),
+            type_name_of::<Out>(),
+            type_name_of::<Out>(),
+            caller,
+        );
+
+        let f = Arc::new(f);
+        // Compensations registered so far still fault with `E`.
+        let mut registry = SagaCompensationRegistry::<E2, Res>::new();
+        if let Ok(previous) = saga_compensation_registry.read() {
+            for (node_id, handler) in &previous.handlers {
+                let handler = handler.clone();
+                let f = f.clone();
+                let converted: SagaCompensationFn<E2, Res> =
+                    Arc::new(move |snapshot: Vec<u8>, res: &Res, bus: &mut Bus| {
+                        let handler = handler.clone();
+                        let f = f.clone();
+                        Box::pin(async move {
+                            handler(snapshot, res, bus).await.map_err(|error| f(error))
+                        })
+                    });
+                registry.register(node_id.clone(), converted);
+            }
+        }
+
+        let next_executor: Executor<In, Out, E2, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Out, E2>> {
+                let prev = prev_executor.clone();
+                let f = f.clone();
+                Box::pin(async move { prev(input, res, bus).await.map_err(|error| f(error)) })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry: Arc::new(std::sync::RwLock::new(registry)),
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Observe the `Next` value without changing it, e.g. for metrics or
+    /// debug output.
+    ///
+    /// `f` runs only when the chain so far returned `Next`. Like
+    /// [`map`](Self::map) it is not a full step; the Schematic shows it as a
+    /// `Tap` node.
+    ///
+    /// ```rust,ignore
+    /// let axon = Axon::new("Checkout")
+    ///     .then(PriceCart)
+    ///     .tap(|cart: &Cart, _bus| metrics::histogram!("cart.total").record(cart.total))
+    ///     .then(ChargeCard);
+    /// ```
+    #[track_caller]
+    pub fn tap<F>(self, f: F) -> Self
+    where
+        F: Fn(&Out, &mut Bus) + Send + Sync + 'static,
+    {
+        let caller = Location::caller();
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = self;
+
+        push_adapter_node::<Res>(
+            &mut schematic,
+            NodeKind::Tap,
+            "Tap",
+            format!("Observes {}", type_name_of::<Out>()),
+            type_name_of::<Out>(),
+            type_name_of::<Out>(),
+            caller,
+        );
+
+        let f = Arc::new(f);
+        let next_executor: Executor<In, Out, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Out, E>> {
+                let prev = prev_executor.clone();
+                let f = f.clone();
+                Box::pin(async move {
+                    let outcome = prev(input, res, bus).await;
+                    if let Outcome::Next(value) = &outcome {
+                        f(value, bus);
+                    }
+                    outcome
+                })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+}
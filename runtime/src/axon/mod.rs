@@ -11,25 +11,47 @@
 //!
 //! "Axon is the flowing thing, Schematic is the visible thing."
 
+use crate::checkpoint::{Checkpoint, CheckpointHandle};
+use crate::contract::ContractEnforcement;
+use crate::dry_run::DryRunPlan;
+use crate::hooks::{EXECUTION_HOOK_REJECTED, NodeHooks};
+use crate::interceptor::{INTERCEPTOR_REJECTED, Intercepted, InterceptorRejected};
 use crate::persistence::{
     CompensationAutoTrigger, CompensationContext, CompensationHandle,
     CompensationIdempotencyHandle, CompensationRetryPolicy, CompletionState,
     PersistenceAutoComplete, PersistenceEnvelope, PersistenceHandle, PersistenceTraceId,
 };
-#[cfg(feature = "inspector")]
+use crate::retry::OutcomeRetryPolicy;
+use crate::timeline_sampling::TimelineSampling;
+use crate::timeline_store::TimelineStoreHandle;
+use crate::timeline_writer::TimelineWriter;
 use async_trait::async_trait;
 use ranvier_core::bus::Bus;
 use ranvier_core::cancellation::CancellationContext;
+use ranvier_core::capture::{
+    CaptureError, CaptureFormat, CapturePolicy, CapturedPayload, NodeCapturePolicy,
+};
 use ranvier_core::cluster::DistributedLock;
+use ranvier_core::deadline::{DEADLINE_EXCEEDED, Deadline, DeadlineExceeded};
 use ranvier_core::event::{DlqPolicy, DlqSink};
+use ranvier_core::fault::{FaultCause, FaultChain, FaultRetry};
+use ranvier_core::metadata::StepMetadata;
+use ranvier_core::node_policy::{
+    AppliedNodePolicy, NodePoliciesConfig, NodePolicyConfig, NodePolicySource,
+};
 use ranvier_core::outcome::Outcome;
 use ranvier_core::policy::DynamicPolicy;
+use ranvier_core::redaction::RedactionRules;
 use ranvier_core::saga::{SagaPolicy, SagaStack};
-use ranvier_core::schematic::{BusCapabilitySchema, Schematic};
+use ranvier_core::schematic::{
+    BusCapabilitySchema, Edge, EdgeType, Node, NodeKind, Schematic, SourceLocation, stable_node_id,
+};
 use ranvier_core::telemetry::AuditLogger;
 #[cfg(feature = "inspector")]
 use ranvier_core::telemetry::InterventionEvent;
-use ranvier_core::timeline::{Timeline, TimelineEvent};
+use ranvier_core::telemetry::TraceContext;
+use ranvier_core::timeline::{CaptureDirection, Timeline, TimelineEvent, Timestamp};
+use ranvier_core::timeline_store::TimelineRun;
 use ranvier_core::transition::Transition;
 
 #[cfg(feature = "inspector")]
@@ -172,6 +194,18 @@ pub struct Axon<In, Out, E, Res = ()> {
         Arc<std::sync::RwLock<ranvier_core::saga::SagaCompensationRegistry<E, Res>>>,
     /// Optional IAM handle for identity verification at the Schematic boundary
     pub iam_handle: Option<ranvier_core::iam::IamHandle>,
+    /// Optional input/output payload capture into the Timeline
+    pub capture_policy: Option<CapturePolicy>,
+    /// Optional shared admission control across circuits
+    pub concurrency: Option<crate::concurrency::ConcurrencyScheduler>,
+    /// Optional per-node retry/backoff/timeout overrides from `ranvier.toml`
+    pub node_policies: Option<Arc<NodePoliciesConfig>>,
+    /// Hooks called around each node, after the global ones
+    pub execution_hooks: Vec<Arc<dyn crate::hooks::ExecutionHook>>,
+    /// Interceptors wrapped around each node, outermost first
+    pub interceptors: Vec<Arc<dyn crate::interceptor::Interceptor>>,
+    /// Set once the schematic has been handed to the `SchematicRegistry`
+    pub(crate) schematic_observed: std::sync::OnceLock<()>,
 }
 
 /// Schematic export request derived from command-line args/env.
@@ -196,13 +230,31 @@ impl<In, Out, E, Res> Clone for Axon<In, Out, E, Res> {
             dynamic_saga_policy: self.dynamic_saga_policy.clone(),
             saga_compensation_registry: self.saga_compensation_registry.clone(),
             iam_handle: self.iam_handle.clone(),
+            capture_policy: self.capture_policy,
+            concurrency: self.concurrency.clone(),
+            node_policies: self.node_policies.clone(),
+            execution_hooks: self.execution_hooks.clone(),
+            interceptors: self.interceptors.clone(),
+            schematic_observed: std::sync::OnceLock::new(),
         }
     }
 }
 
+mod branch;
 mod builder;
 mod executor;
+mod for_each;
+mod join;
+mod looping;
+mod map;
 mod parallel;
+mod race;
+mod recover;
+
+pub use branch::{BRANCH_PAYLOAD_INVALID, BranchBuilder, Branches};
+pub use for_each::{ForEach, ForEachReport};
+pub use join::{JoinBranchInfo, JoinBranchTrace, JoinBranches};
+pub use looping::{LOOP_LIMIT_EXCEEDED, LoopLimitExceeded, LoopWhile};
 
 #[cfg(feature = "inspector")]
 #[async_trait]
@@ -336,7 +388,9 @@ fn maybe_export_timeline<Out, E>(bus: &mut Bus, outcome: &Outcome<Out, E>) {
         _ => return,
     };
 
-    let sampled = sampled_by_bus_id(bus.id, timeline_sample_rate());
+    // A sampling policy on the Bus has already decided to emit.
+    let sampled =
+        bus.has::<TimelineSampling>() || sampled_by_bus_id(bus.id, timeline_sample_rate());
     let policy = timeline_adaptive_policy();
     let forced = should_force_export(outcome, &policy);
     let should_export = sampled || forced;
@@ -365,6 +419,31 @@ fn maybe_export_timeline<Out, E>(bus: &mut Bus, outcome: &Outcome<Out, E>) {
     }
 }
 
+async fn record_timeline_run<Out, E>(
+    bus: &Bus,
+    handle: &TimelineStoreHandle,
+    run_id: &str,
+    circuit: &str,
+    outcome: &Outcome<Out, E>,
+) {
+    let mut timeline = bus.read::<Timeline>().cloned().unwrap_or_default();
+    timeline.sort();
+    let run = TimelineRun::new(run_id, circuit, outcome_kind_name(outcome), timeline);
+    if let Err(e) = handle.store().append_run(run).await {
+        tracing::warn!(run_id = %run_id, "Failed to record timeline run: {}", e);
+    }
+}
+
+/// Put the caller's trace context back after an execution installed its own.
+fn restore_trace_context(bus: &mut Bus, caller: Option<TraceContext>) {
+    match caller {
+        Some(context) => bus.insert(context),
+        None => {
+            let _ = bus.remove::<TraceContext>();
+        }
+    }
+}
+
 fn extract_panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
     if let Some(s) = payload.downcast_ref::<&str>() {
         (*s).to_string()
@@ -381,6 +460,8 @@ fn outcome_type_name<Out, E>(outcome: &Outcome<Out, E>) -> String {
         Outcome::Branch(id, _) => format!("Branch:{}", id),
         Outcome::Jump(id, _) => format!("Jump:{}", id),
         Outcome::Emit(event_type, _) => format!("Emit:{}", event_type),
+        Outcome::Suspend(token, _) => format!("Suspend:{}", token),
+        Outcome::Retry { .. } => "Retry".to_string(),
         Outcome::Fault(_) => "Fault".to_string(),
     }
 }
@@ -391,6 +472,8 @@ fn outcome_kind_name<Out, E>(outcome: &Outcome<Out, E>) -> &'static str {
         Outcome::Branch(_, _) => "Branch",
         Outcome::Jump(_, _) => "Jump",
         Outcome::Emit(_, _) => "Emit",
+        Outcome::Suspend(_, _) => "Suspend",
+        Outcome::Retry { .. } => "Retry",
         Outcome::Fault(_) => "Fault",
     }
 }
@@ -400,7 +483,8 @@ fn outcome_target<Out, E>(outcome: &Outcome<Out, E>) -> Option<String> {
         Outcome::Branch(branch_id, _) => Some(branch_id.clone()),
         Outcome::Jump(node_id, _) => Some(node_id.to_string()),
         Outcome::Emit(event_type, _) => Some(event_type.clone()),
-        Outcome::Next(_) | Outcome::Fault(_) => None,
+        Outcome::Suspend(token, _) => Some(token.clone()),
+        Outcome::Next(_) | Outcome::Retry { .. } | Outcome::Fault(_) => None,
     }
 }
 
@@ -419,6 +503,42 @@ fn persistence_trace_id(bus: &Bus) -> String {
     }
 }
 
+async fn save_checkpoint<T: serde::Serialize>(
+    bus: &Bus,
+    handle: &CheckpointHandle,
+    node_id: &str,
+    node_label: &str,
+    step: u64,
+    state: &T,
+) {
+    let state = match serde_json::to_value(state) {
+        Ok(state) => state,
+        Err(e) => {
+            tracing::warn!(node_id = %node_id, "Node output is not checkpointable: {}", e);
+            return;
+        }
+    };
+    let trace_id = bus
+        .read::<PersistenceTraceId>()
+        .map(|id| id.0.clone())
+        .unwrap_or_else(|| bus.id.to_string());
+    let checkpoint = Checkpoint {
+        trace_id,
+        circuit: bus
+            .read::<ranvier_core::schematic::Schematic>()
+            .map(|s| s.name.clone())
+            .unwrap_or_default(),
+        node_id: node_id.to_string(),
+        node_label: node_label.to_string(),
+        step,
+        state,
+        recorded_at_ms: now_ms(),
+    };
+    if let Err(e) = handle.store().save(checkpoint).await {
+        tracing::warn!(node_id = %node_id, "Failed to save checkpoint: {}", e);
+    }
+}
+
 fn persistence_auto_complete(bus: &Bus) -> bool {
     bus.read::<PersistenceAutoComplete>()
         .map(|v| v.0)
@@ -527,6 +647,58 @@ async fn load_persistence_version(
     }
 }
 
+/// Work a node runs after it faults, before the fault reaches DLQ reporting
+/// and the hooks. `()` is a plain node with nothing to undo.
+#[async_trait]
+trait FaultHook<Res, E>: Send {
+    /// Id and label of the node this hook runs as, or `None` to skip it.
+    fn node(&self) -> Option<(String, String)>;
+
+    async fn run(self, res: &Res, bus: &mut Bus) -> Outcome<(), E>;
+}
+
+#[async_trait]
+impl<Res, E> FaultHook<Res, E> for ()
+where
+    Res: Sync,
+    E: Send + 'static,
+{
+    fn node(&self) -> Option<(String, String)> {
+        None
+    }
+
+    async fn run(self, _res: &Res, _bus: &mut Bus) -> Outcome<(), E> {
+        Outcome::Next(())
+    }
+}
+
+/// Compensation of a `then_compensated` node, run on the node's own input.
+struct Compensation<'c, In, Comp> {
+    comp: &'c Comp,
+    node_id: &'c str,
+    input: In,
+}
+
+#[async_trait]
+impl<In, E, Res, Comp> FaultHook<Res, E> for Compensation<'_, In, Comp>
+where
+    In: Send + Sync + 'static,
+    E: Send + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+    Comp: Transition<In, (), Resources = Res, Error = E>,
+{
+    fn node(&self) -> Option<(String, String)> {
+        Some((
+            self.node_id.to_string(),
+            format!("Compensate: {}", self.comp.label()),
+        ))
+    }
+
+    async fn run(self, res: &Res, bus: &mut Bus) -> Outcome<(), E> {
+        self.comp.run(self.input, res, bus).await
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn run_this_step<In, Out, E, Res>(
     trans: &(impl Transition<In, Out, Resources = Res, Error = E> + Clone + 'static),
@@ -538,6 +710,80 @@ async fn run_this_step<In, Out, E, Res>(
     bus_policy: &Option<ranvier_core::bus::BusAccessPolicy>,
     step_idx: u64,
 ) -> Outcome<Out, E>
+where
+    In: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+    Out: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+    E: serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug + Send + Sync + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    run_node(
+        trans,
+        state,
+        res,
+        bus,
+        node_id,
+        node_label,
+        bus_policy,
+        step_idx,
+        (),
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_this_compensated_step<Out, Next, E, Res, Comp>(
+    trans: &(impl Transition<Out, Next, Resources = Res, Error = E> + Clone + 'static),
+    comp: &Comp,
+    state: Out,
+    res: &Res,
+    bus: &mut Bus,
+    node_id: &str,
+    comp_node_id: &str,
+    node_label: &str,
+    bus_policy: &Option<ranvier_core::bus::BusAccessPolicy>,
+    step_idx: u64,
+) -> Outcome<Next, E>
+where
+    Out: serde::Serialize + serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+    Next: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+    E: serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug + Send + Sync + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+    Comp: Transition<Out, (), Resources = Res, Error = E> + Clone + Send + Sync + 'static,
+{
+    let compensation = Compensation {
+        comp,
+        node_id: comp_node_id,
+        input: state.clone(),
+    };
+    run_node(
+        trans,
+        state,
+        res,
+        bus,
+        node_id,
+        node_label,
+        bus_policy,
+        step_idx,
+        compensation,
+    )
+    .await
+}
+
+/// Run one node: hooks, interceptors, retries, timeline, capture, saga,
+/// checkpoint, persistence and DLQ reporting. `on_fault` runs when the node
+/// faults and compensation is automatic.
+#[allow(clippy::too_many_arguments)]
+async fn run_node<In, Out, E, Res>(
+    trans: &(impl Transition<In, Out, Resources = Res, Error = E> + Clone + 'static),
+    state: In,
+    res: &Res,
+    bus: &mut Bus,
+    node_id: &str,
+    node_label: &str,
+    bus_policy: &Option<ranvier_core::bus::BusAccessPolicy>,
+    step_idx: u64,
+    on_fault: impl FaultHook<Res, E>,
+) -> Outcome<Out, E>
 where
     In: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
     Out: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
@@ -550,6 +796,32 @@ where
         .last()
         .unwrap_or("unknown");
 
+    if let Some(stopped) = stop_before_run(bus, node_id, node_label, step_idx) {
+        return stopped;
+    }
+
+    if let Some(plan) = DryRunPlan::from_bus(bus) {
+        bus.set_access_policy(label.clone(), bus_policy.clone());
+        let outcome = plan.step(trans, &state, res, bus, Some(node_id));
+        bus.clear_access_policy();
+        return outcome;
+    }
+
+    let node_hooks = match NodeHooks::start(bus, node_id, node_label, step_idx).await {
+        Ok(hooks) => hooks,
+        Err(rejection) => return Outcome::emit(EXECUTION_HOOK_REJECTED, Some(rejection)),
+    };
+    let intercepted = match Intercepted::enter(bus, node_id, node_label, step_idx).await {
+        Ok(intercepted) => intercepted,
+        Err(rejected) => {
+            let result = interceptor_rejected(bus, rejected, node_id, node_label, step_idx);
+            if let Some(hooks) = node_hooks {
+                hooks.end(&result, bus).await;
+            }
+            return result;
+        }
+    };
+
     // Debug pausing
     let should_pause = if let Some(debug) = bus.read::<ranvier_core::debug::DebugControl>() {
         debug.should_pause(node_id)
@@ -570,7 +842,8 @@ where
         if let Some(timeline) = bus.read_mut::<Timeline>() {
             timeline.push(TimelineEvent::NodePaused {
                 node_id: node_id.to_string(),
-                timestamp: now_ms(),
+                timestamp: Timestamp::now(),
+                trace: None,
             });
         }
         if let Some(debug) = bus.read::<ranvier_core::debug::DebugControl>() {
@@ -578,14 +851,22 @@ where
         }
     }
 
-    let enter_ts = now_ms();
+    let enter_ts = Timestamp::now();
     if let Some(timeline) = bus.read_mut::<Timeline>() {
         timeline.push(TimelineEvent::NodeEnter {
             node_id: node_id.to_string(),
             node_label: node_label.to_string(),
             timestamp: enter_ts,
+            trace: None,
         });
     }
+    let node_capture = bus
+        .read::<NodeCapturePolicy>()
+        .filter(|policy| policy.captures(node_id, node_label))
+        .cloned();
+    if let Some(policy) = &node_capture {
+        push_node_capture(bus, policy, node_id, CaptureDirection::NodeInput, &state);
+    }
 
     // Check DLQ retry policy and pre-serialize state for potential retries
     let dlq_retry_config = bus.read::<DlqPolicy>().and_then(|p| {
@@ -599,7 +880,15 @@ where
             None
         }
     });
-    let retry_state_snapshot = if dlq_retry_config.is_some() {
+    let outcome_retry_policy = bus.read::<OutcomeRetryPolicy>().copied();
+    let retry_state_snapshot = if dlq_retry_config.is_some() || outcome_retry_policy.is_some() {
+        serde_json::to_value(&state).ok()
+    } else {
+        None
+    };
+
+    // Input a resumed suspension restarts from when it carries no state
+    let suspension_input = if bus.has::<crate::suspend::CaptureSuspensionInput>() {
         serde_json::to_value(&state).ok()
     } else {
         None
@@ -620,15 +909,26 @@ where
         ranvier.outcome_target = tracing::field::Empty
     );
     let started = std::time::Instant::now();
+    bus.take_missing_resource();
+    let outer_node = bus.set_current_node(Some(node_id.to_string()));
     bus.set_access_policy(label.clone(), bus_policy.clone());
-    let result = trans
+    let mut result = trans
         .run(state, res, bus)
         .instrument(node_span.clone())
         .await;
     bus.clear_access_policy();
 
+    if let (Some(policy), Some(snapshot)) = (outcome_retry_policy, &retry_state_snapshot) {
+        result = retry_outcome(
+            result, policy, snapshot, trans, res, bus, node_id, node_label, bus_policy, step_idx,
+            &node_span,
+        )
+        .await;
+    }
+
     // DLQ Retry loop: if first attempt faulted and RetryThenDlq is configured,
     // retry with exponential backoff before giving up.
+    let mut fault_retries = Vec::new();
     let result = if let Outcome::Fault(_) = &result {
         if let (Some((max_attempts, backoff_ms)), Some(snapshot)) =
             (dlq_retry_config, &retry_state_snapshot)
@@ -637,6 +937,14 @@ where
             // attempt 1 already done; retry from 2..=max_attempts
             for attempt in 2..=max_attempts {
                 let delay = backoff_ms.saturating_mul(2u64.saturating_pow(attempt - 2));
+                if let Outcome::Fault(err) = &final_result {
+                    fault_retries.push(FaultRetry {
+                        attempt: attempt - 1,
+                        error: format!("{err:?}"),
+                        backoff_ms: delay,
+                        timestamp: Timestamp::now(),
+                    });
+                }
 
                 tracing::info!(
                     ranvier.node = %label,
@@ -652,13 +960,15 @@ where
                         attempt,
                         max_attempts,
                         backoff_ms: delay,
-                        timestamp: now_ms(),
+                        timestamp: Timestamp::now(),
+                        trace: None,
                     });
                 }
 
                 tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
 
                 if let Ok(retry_state) = serde_json::from_value::<In>(snapshot.clone()) {
+                    bus.take_missing_resource();
                     bus.set_access_policy(label.clone(), bus_policy.clone());
                     let retry_result = trans
                         .run(retry_state, res, bus)
@@ -689,6 +999,20 @@ where
         result
     };
 
+    if let Outcome::Jump(target, _) = &result
+        && let Some(schematic) = bus.read::<Schematic>()
+        && schematic.jump_target(&target.to_string()).is_none()
+    {
+        tracing::warn!(
+            node_id = %node_id,
+            target = %target,
+            "Jump target is not a node of this circuit; declare it with jump_target and validate()"
+        );
+    }
+
+    bus.set_current_node(outer_node);
+    let missing_resource = bus.take_missing_resource();
+
     node_span.record("ranvier.outcome_kind", outcome_kind_name(&result));
     if let Some(target) = outcome_target(&result) {
         node_span.record("ranvier.outcome_target", tracing::field::display(&target));
@@ -713,10 +1037,28 @@ where
             "Transition fault"
         );
         bus.insert(ctx);
+        // A fault that follows a failed `Bus::require` keeps the node's own
+        // error and carries the missing type as its category and cause.
+        let mut cause = FaultCause::new(node_id, node_label, format!("{err:?}"));
+        if let Some(missing) = missing_resource {
+            cause = cause.with_category(missing.category());
+            cause.causes.push(
+                FaultCause::new(node_id, node_label, missing.to_string())
+                    .with_category(missing.category()),
+            );
+        }
+        record_fault_cause(
+            bus,
+            cause.with_step_index(step_idx).with_retries(fault_retries),
+        );
+    }
+
+    if let (Some(policy), Outcome::Next(output)) = (&node_capture, &result) {
+        push_node_capture(bus, policy, node_id, CaptureDirection::NodeOutput, output);
     }
 
     let duration_ms = started.elapsed().as_millis() as u64;
-    let exit_ts = now_ms();
+    let exit_ts = Timestamp::now();
 
     if let Some(timeline) = bus.read_mut::<Timeline>() {
         timeline.push(TimelineEvent::NodeExit {
@@ -724,21 +1066,39 @@ where
             outcome_type: outcome_type_name(&result),
             duration_ms,
             timestamp: exit_ts,
+            trace: None,
         });
 
         if let Outcome::Branch(branch_id, _) = &result {
             timeline.push(TimelineEvent::Branchtaken {
                 branch_id: branch_id.clone(),
                 timestamp: exit_ts,
+                trace: None,
             });
         }
     }
 
     // Push to Saga Stack if Next outcome and snapshot taken
-    if let (Outcome::Next(_), Some(snapshot)) = (&result, saga_snapshot)
+    if let (Outcome::Next(output), Some(snapshot)) = (&result, saga_snapshot)
         && let Some(stack) = bus.read_mut::<SagaStack>()
     {
-        stack.push(node_id.to_string(), label.clone(), snapshot);
+        let output = serde_json::to_vec(output).unwrap_or_default();
+        stack.push_with_output(node_id.to_string(), label.clone(), snapshot, output);
+    }
+    record_suspension(
+        bus,
+        &result,
+        node_id,
+        node_label,
+        step_idx,
+        suspension_input,
+    );
+
+    if let Outcome::Next(state) = &result
+        && let Some(handle) = bus.read::<CheckpointHandle>()
+        && handle.checkpoints(node_label)
+    {
+        save_checkpoint(bus, handle, node_id, node_label, step_idx, state).await;
     }
 
     if let Some(handle) = bus.read::<PersistenceHandle>() {
@@ -765,6 +1125,70 @@ where
         .await;
     }
 
+    if let Outcome::Fault(err) = &result
+        && let Some((comp_node_id, comp_label)) = on_fault.node()
+        && compensation_auto_trigger(bus)
+    {
+        tracing::info!(
+            ranvier.node = %label,
+            ranvier.compensation.trigger = "saga",
+            error = ?err,
+            "Saga compensation triggered"
+        );
+
+        let comp_started = std::time::Instant::now();
+        if let Some(timeline) = bus.read_mut::<Timeline>() {
+            timeline.push(TimelineEvent::NodeEnter {
+                node_id: comp_node_id.clone(),
+                node_label: comp_label.clone(),
+                timestamp: Timestamp::now(),
+                trace: None,
+            });
+        }
+
+        // A failed compensation is recorded after the original fault.
+        if let Outcome::Fault(comp_err) = on_fault.run(res, bus).await {
+            record_fault_cause(
+                bus,
+                FaultCause::new(&comp_node_id, &comp_label, format!("{comp_err:?}")),
+            );
+        }
+
+        if let Some(timeline) = bus.read_mut::<Timeline>() {
+            timeline.push(TimelineEvent::NodeExit {
+                node_id: comp_node_id.clone(),
+                outcome_type: "Compensated".to_string(),
+                duration_ms: comp_started.elapsed().as_millis() as u64,
+                timestamp: Timestamp::now(),
+                trace: None,
+            });
+        }
+
+        if let Some(handle) = bus.read::<PersistenceHandle>() {
+            let trace_id = persistence_trace_id(bus);
+            let circuit = bus
+                .read::<ranvier_core::schematic::Schematic>()
+                .map(|s| s.name.clone())
+                .unwrap_or_default();
+            let version = bus
+                .read::<ranvier_core::schematic::Schematic>()
+                .map(|s| s.schema_version.clone())
+                .unwrap_or_default();
+
+            persist_execution_event(
+                handle,
+                &trace_id,
+                &circuit,
+                &version,
+                step_idx + 1, // Compensation node index
+                Some(comp_node_id),
+                "Compensated",
+                None,
+            )
+            .await;
+        }
+    }
+
     // DLQ reporting — only fires after all retries are exhausted (RetryThenDlq)
     // or immediately (SendToDlq). Drop policy skips entirely.
     if let Outcome::Fault(f) = &result {
@@ -785,7 +1209,8 @@ where
                 timeline.push(TimelineEvent::DlqExhausted {
                     node_id: node_id.to_string(),
                     total_attempts: max_attempts,
-                    timestamp: now_ms(),
+                    timestamp: Timestamp::now(),
+                    trace: None,
                 });
             }
 
@@ -806,229 +1231,113 @@ where
         }
     }
 
+    if let Some(intercepted) = intercepted {
+        intercepted.exit(&result, bus).await;
+    }
+    if let Some(hooks) = node_hooks {
+        hooks.end(&result, bus).await;
+    }
+
     result
 }
 
+/// Run a node again on the same input while it returns `Outcome::Retry`,
+/// within the [`OutcomeRetryPolicy`] bounds. A Retry left over is returned
+/// as-is; cancellation or an expired [`Deadline`] ends the retries.
 #[allow(clippy::too_many_arguments)]
-async fn run_this_compensated_step<Out, Next, E, Res, Comp>(
-    trans: &(impl Transition<Out, Next, Resources = Res, Error = E> + Clone + 'static),
-    comp: &Comp,
-    state: Out,
+async fn retry_outcome<In, Out, E, Res>(
+    mut result: Outcome<Out, E>,
+    policy: OutcomeRetryPolicy,
+    snapshot: &serde_json::Value,
+    trans: &impl Transition<In, Out, Resources = Res, Error = E>,
     res: &Res,
     bus: &mut Bus,
     node_id: &str,
-    comp_node_id: &str,
     node_label: &str,
     bus_policy: &Option<ranvier_core::bus::BusAccessPolicy>,
     step_idx: u64,
-) -> Outcome<Next, E>
+    node_span: &tracing::Span,
+) -> Outcome<Out, E>
 where
-    Out: serde::Serialize + serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
-    Next: serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
-    E: serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug + Send + Sync + 'static,
+    In: serde::de::DeserializeOwned + Send + Sync + 'static,
+    Out: Send + Sync + 'static,
+    E: serde::de::DeserializeOwned + Send + Sync + 'static,
     Res: ranvier_core::transition::ResourceRequirement,
-    Comp: Transition<Out, (), Resources = Res, Error = E> + Clone + Send + Sync + 'static,
 {
     let label = trans.label();
-
-    // Debug pausing
-    let should_pause = if let Some(debug) = bus.read::<ranvier_core::debug::DebugControl>() {
-        debug.should_pause(node_id)
-    } else {
-        false
-    };
-
-    if should_pause {
-        let trace_id = persistence_trace_id(bus);
-        tracing::event!(
-            target: "ranvier.debugger",
-            tracing::Level::INFO,
-            trace_id = %trace_id,
-            node_id = %node_id,
-            "Node paused (compensated)"
+    let mut retry = 0;
+    while let Outcome::Retry { after } = result {
+        if retry >= policy.max_retries {
+            tracing::warn!(
+                ranvier.node = %label,
+                max_retries = policy.max_retries,
+                "Node retries exhausted; returning Retry"
+            );
+            break;
+        }
+        if let Some(stopped) = stop_before_run(bus, node_id, node_label, step_idx) {
+            result = stopped;
+            break;
+        }
+        let Ok(state) = serde_json::from_value::<In>(snapshot.clone()) else {
+            break;
+        };
+        retry += 1;
+        let delay = after.min(policy.max_delay);
+        tracing::info!(
+            ranvier.node = %label,
+            attempt = retry,
+            delay_ms = delay.as_millis() as u64,
+            "Node asked to be retried"
         );
-
         if let Some(timeline) = bus.read_mut::<Timeline>() {
-            timeline.push(TimelineEvent::NodePaused {
+            timeline.push(TimelineEvent::NodeRetry {
                 node_id: node_id.to_string(),
-                timestamp: now_ms(),
+                attempt: retry,
+                max_attempts: policy.max_retries,
+                backoff_ms: delay.as_millis() as u64,
+                timestamp: Timestamp::now(),
+                trace: None,
             });
         }
-        if let Some(debug) = bus.read::<ranvier_core::debug::DebugControl>() {
-            debug.wait().await;
+        tokio::time::sleep(delay).await;
+        if let Some(stopped) = stop_before_run(bus, node_id, node_label, step_idx) {
+            result = stopped;
+            break;
         }
+        bus.take_missing_resource();
+        bus.set_access_policy(label.clone(), bus_policy.clone());
+        result = trans
+            .run(state, res, bus)
+            .instrument(node_span.clone())
+            .await;
+        bus.clear_access_policy();
     }
+    result
+}
 
-    let enter_ts = now_ms();
-    if let Some(timeline) = bus.read_mut::<Timeline>() {
-        timeline.push(TimelineEvent::NodeEnter {
-            node_id: node_id.to_string(),
-            node_label: node_label.to_string(),
-            timestamp: enter_ts,
-        });
-    }
-
-    // State capture for Saga - SERIALIZE BEFORE CONSUMPTION
-    let saga_snapshot = if let Some(SagaPolicy::Enabled) = bus.read::<SagaPolicy>() {
-        Some(serde_json::to_vec(&state).unwrap_or_default())
-    } else {
-        None
-    };
-
-    let node_span = tracing::info_span!("Node", ranvier.node = %label);
-    bus.set_access_policy(label.clone(), bus_policy.clone());
-    let result = trans
-        .run(state.clone(), res, bus)
-        .instrument(node_span)
-        .await;
-    bus.clear_access_policy();
-
-    let duration_ms = 0; // Simplified
-    let exit_ts = now_ms();
-
-    if let Some(timeline) = bus.read_mut::<Timeline>() {
-        timeline.push(TimelineEvent::NodeExit {
-            node_id: node_id.to_string(),
-            outcome_type: outcome_type_name(&result),
-            duration_ms,
-            timestamp: exit_ts,
-        });
-    }
-
-    // Automated Compensation Trigger
-    if let Outcome::Fault(ref err) = result {
-        if compensation_auto_trigger(bus) {
-            tracing::info!(
-                ranvier.node = %label,
-                ranvier.compensation.trigger = "saga",
-                error = ?err,
-                "Saga compensation triggered"
-            );
-
-            if let Some(timeline) = bus.read_mut::<Timeline>() {
-                timeline.push(TimelineEvent::NodeEnter {
-                    node_id: comp_node_id.to_string(),
-                    node_label: format!("Compensate: {}", comp.label()),
-                    timestamp: exit_ts,
-                });
-            }
-
-            // Run compensation
-            let _ = comp.run(state, res, bus).await;
-
-            if let Some(timeline) = bus.read_mut::<Timeline>() {
-                timeline.push(TimelineEvent::NodeExit {
-                    node_id: comp_node_id.to_string(),
-                    outcome_type: "Compensated".to_string(),
-                    duration_ms: 0,
-                    timestamp: now_ms(),
-                });
-            }
-
-            if let Some(handle) = bus.read::<PersistenceHandle>() {
-                let trace_id = persistence_trace_id(bus);
-                let circuit = bus
-                    .read::<ranvier_core::schematic::Schematic>()
-                    .map(|s| s.name.clone())
-                    .unwrap_or_default();
-                let version = bus
-                    .read::<ranvier_core::schematic::Schematic>()
-                    .map(|s| s.schema_version.clone())
-                    .unwrap_or_default();
-
-                persist_execution_event(
-                    handle,
-                    &trace_id,
-                    &circuit,
-                    &version,
-                    step_idx + 1, // Compensation node index
-                    Some(comp_node_id.to_string()),
-                    "Compensated",
-                    None,
-                )
-                .await;
-            }
-        }
-    } else if let (Outcome::Next(_), Some(snapshot)) = (&result, saga_snapshot) {
-        // Push to Saga Stack if Next outcome and snapshot taken
-        if let Some(stack) = bus.read_mut::<SagaStack>() {
-            stack.push(node_id.to_string(), label.clone(), snapshot);
-        }
-
-        if let Some(handle) = bus.read::<PersistenceHandle>() {
-            let trace_id = persistence_trace_id(bus);
-            let circuit = bus
-                .read::<ranvier_core::schematic::Schematic>()
-                .map(|s| s.name.clone())
-                .unwrap_or_default();
-            let version = bus
-                .read::<ranvier_core::schematic::Schematic>()
-                .map(|s| s.schema_version.clone())
-                .unwrap_or_default();
-
-            persist_execution_event(
-                handle,
-                &trace_id,
-                &circuit,
-                &version,
-                step_idx,
-                Some(node_id.to_string()),
-                outcome_kind_name(&result),
-                Some(result.to_json_value()),
-            )
-            .await;
-        }
-    }
-
-    // DLQ reporting for compensated steps
-    if let Outcome::Fault(f) = &result
-        && let (Some(sink), Some(policy)) =
-            (bus.read::<Arc<dyn DlqSink>>(), bus.read::<DlqPolicy>())
-    {
-        let should_dlq = !matches!(policy, DlqPolicy::Drop);
-        if should_dlq {
-            let trace_id = persistence_trace_id(bus);
-            let circuit = bus
-                .read::<ranvier_core::schematic::Schematic>()
-                .map(|s| s.name.clone())
-                .unwrap_or_default();
-            let _ = sink
-                .store_dead_letter(
-                    &trace_id,
-                    &circuit,
-                    node_id,
-                    &format!("{:?}", f),
-                    &serde_json::to_vec(&f).unwrap_or_default(),
-                )
-                .await;
-        }
-    }
-
-    result
-}
-
-#[allow(clippy::too_many_arguments)]
-pub async fn persist_execution_event(
-    handle: &PersistenceHandle,
-    trace_id: &str,
-    circuit: &str,
-    schematic_version: &str,
-    step: u64,
-    node_id: Option<String>,
-    outcome_kind: &str,
-    payload: Option<serde_json::Value>,
-) {
-    let store = handle.store();
-    let envelope = PersistenceEnvelope {
-        trace_id: trace_id.to_string(),
-        circuit: circuit.to_string(),
-        schematic_version: schematic_version.to_string(),
-        step,
-        node_id,
-        outcome_kind: outcome_kind.to_string(),
-        timestamp_ms: now_ms(),
-        payload_hash: None,
-        payload,
+#[allow(clippy::too_many_arguments)]
+pub async fn persist_execution_event(
+    handle: &PersistenceHandle,
+    trace_id: &str,
+    circuit: &str,
+    schematic_version: &str,
+    step: u64,
+    node_id: Option<String>,
+    outcome_kind: &str,
+    payload: Option<serde_json::Value>,
+) {
+    let store = handle.store();
+    let envelope = PersistenceEnvelope {
+        trace_id: trace_id.to_string(),
+        circuit: circuit.to_string(),
+        schematic_version: schematic_version.to_string(),
+        step,
+        node_id,
+        outcome_kind: outcome_kind.to_string(),
+        timestamp_ms: now_ms(),
+        payload_hash: None,
+        payload,
     };
 
     if let Err(err) = store.append(envelope).await {
@@ -1146,14 +1455,111 @@ fn ensure_timeline(bus: &mut Bus) -> bool {
     }
 }
 
-fn should_attach_timeline(bus: &Bus) -> bool {
+/// Append a circuit-level payload capture, attributed to the ingress node.
+fn push_capture<T: serde::Serialize + 'static>(
+    timeline: &mut Timeline,
+    schematic: &Schematic,
+    direction: CaptureDirection,
+    policy: CapturePolicy,
+    redaction: Option<&RedactionRules>,
+    value: &T,
+) {
+    let encoded = match redaction {
+        Some(rules) => redacted_json(rules, policy.format, value)
+            .and_then(|json| CapturedPayload::encode(policy.format, &json)),
+        None => CapturedPayload::encode(policy.format, value),
+    };
+    match encoded {
+        Ok(payload) => timeline.push(TimelineEvent::PayloadCaptured {
+            node_id: schematic
+                .nodes
+                .first()
+                .map(|node| node.id.clone())
+                .unwrap_or_default(),
+            direction,
+            payload,
+            timestamp: Timestamp::now(),
+            trace: None,
+        }),
+        Err(error) => tracing::warn!(
+            circuit = %schematic.name,
+            ?direction,
+            %error,
+            "Skipping payload capture"
+        ),
+    }
+}
+
+/// Append a node payload capture under a [`NodeCapturePolicy`].
+fn push_node_capture<T: serde::Serialize + 'static>(
+    bus: &mut Bus,
+    policy: &NodeCapturePolicy,
+    node_id: &str,
+    direction: CaptureDirection,
+    value: &T,
+) {
+    let encoded = match bus.read::<RedactionRules>() {
+        Some(rules) => {
+            redacted_json(rules, policy.format, value).and_then(|json| policy.encode(&json))
+        }
+        None => policy.encode(value),
+    };
+    let Some(timeline) = bus.read_mut::<Timeline>() else {
+        return;
+    };
+    match encoded {
+        Ok(payload) => timeline.push(TimelineEvent::PayloadCaptured {
+            node_id: node_id.to_string(),
+            direction,
+            payload,
+            timestamp: Timestamp::now(),
+            trace: None,
+        }),
+        Err(error) => tracing::warn!(
+            node_id = %node_id,
+            ?direction,
+            %error,
+            "Skipping node payload capture"
+        ),
+    }
+}
+
+/// `value` as JSON with the Bus's [`RedactionRules`] applied.
+fn redacted_json<T: serde::Serialize + 'static>(
+    rules: &RedactionRules,
+    format: CaptureFormat,
+    value: &T,
+) -> Result<serde_json::Value, CaptureError> {
+    rules.redact(value).map_err(|e| CaptureError::Encode {
+        format,
+        message: e.to_string(),
+    })
+}
+
+fn should_attach_timeline(bus: &Bus, circuit: &str) -> bool {
     // Respect explicitly provided timeline collector from caller.
     if bus.has::<Timeline>() {
         return true;
     }
 
-    // Attach timeline when runtime export path exists.
+    // Skip collection for executions a sampling policy will never emit.
+    if let Some(sampling) = bus.read::<TimelineSampling>()
+        && !sampling.collects(circuit, bus.id)
+    {
+        return false;
+    }
+
+    // Attach timeline when runtime export path or a timeline sink exists.
     has_timeline_output_path()
+        || bus.has::<TimelineStoreHandle>()
+        || bus.has::<TimelineWriter>()
+        || bus.has::<ranvier_core::timeline::tail::TimelineTail>()
+}
+
+/// Whether the finished execution hands its timeline to the sinks.
+fn timeline_emitted<Out, E>(bus: &Bus, circuit: &str, outcome: &Outcome<Out, E>) -> bool {
+    bus.read::<TimelineSampling>()
+        .is_none_or(|sampling| sampling.emits(circuit, bus.id, outcome))
 }
 
 fn has_timeline_output_path() -> bool {
@@ -1171,7 +1577,7 @@ fn timeline_sample_rate() -> f64 {
         .unwrap_or(1.0)
 }
 
-fn sampled_by_bus_id(bus_id: uuid::Uuid, rate: f64) -> bool {
+pub(crate) fn sampled_by_bus_id(bus_id: uuid::Uuid, rate: f64) -> bool {
     if rate <= 0.0 {
         return false;
     }
@@ -1182,6 +1588,19 @@ fn sampled_by_bus_id(bus_id: uuid::Uuid, rate: f64) -> bool {
     bucket < rate
 }
 
+/// Whether output contracts are checked for this execution.
+fn contract_enforced(bus: &Bus, production_sample_rate: f64) -> bool {
+    let enforcement = bus
+        .read::<ContractEnforcement>()
+        .copied()
+        .unwrap_or_else(ContractEnforcement::from_env);
+    match enforcement {
+        ContractEnforcement::Always => true,
+        ContractEnforcement::Sampled => sampled_by_bus_id(bus.id, production_sample_rate),
+        ContractEnforcement::Off => false,
+    }
+}
+
 fn timeline_adaptive_policy() -> String {
     std::env::var("RANVIER_TIMELINE_ADAPTIVE")
         .unwrap_or_else(|_| "fault_branch".to_string())
@@ -1293,6 +1712,9 @@ fn write_timeline_with_policy(
                 let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
                 match serde_json::from_str::<Timeline>(&content) {
                     Ok(mut existing) => {
+                        if existing.header.capture_format.is_none() {
+                            existing.header.capture_format = timeline.header.capture_format;
+                        }
                         existing.events.append(&mut timeline.events);
                         existing.sort();
                         if let Some(max_events) = max_events_limit() {
@@ -1400,88 +1822,482 @@ fn cleanup_rotated_files(base_path: &str, keep: usize) -> Result<(), String> {
     Ok(())
 }
 
-fn bus_capability_schema_from_policy(
-    policy: Option<ranvier_core::bus::BusAccessPolicy>,
-) -> Option<BusCapabilitySchema> {
-    let policy = policy?;
+/// Emitted by [`Axon::execute`] when the Bus cancellation token is cancelled
+/// before a node starts. [`Axon::execute_cancellable`] reports
+/// [`ExecutionTerminal::Cancelled`] instead.
+pub const EXECUTION_CANCELLED: &str = "execution.cancelled";
 
-    let mut allow = policy
-        .allow
-        .unwrap_or_default()
-        .into_iter()
-        .map(|entry| entry.type_name.to_string())
-        .collect::<Vec<_>>();
-    let mut deny = policy
-        .deny
-        .into_iter()
-        .map(|entry| entry.type_name.to_string())
-        .collect::<Vec<_>>();
-    allow.sort();
-    deny.sort();
+/// The outcome ending a node before it (re)runs: the Bus cancellation token
+/// was cancelled or the execution's [`Deadline`] has passed.
+fn stop_before_run<Out, E>(
+    bus: &mut Bus,
+    node_id: &str,
+    node_label: &str,
+    step_idx: u64,
+) -> Option<Outcome<Out, E>>
+where
+    E: serde::de::DeserializeOwned,
+{
+    if let Some(context) = bus.cancellation_token().and_then(|token| token.context()) {
+        tracing::info!(
+            node_id = %node_id,
+            node_label = %node_label,
+            reason = ?context.reason,
+            "Execution cancelled before node"
+        );
+        record_cancellation(bus, Some(node_id.to_string()), &context);
+        return Some(Outcome::emit(
+            EXECUTION_CANCELLED,
+            serde_json::to_value(&context).ok(),
+        ));
+    }
 
-    if allow.is_empty() && deny.is_empty() {
-        return None;
+    if let Some(deadline) = bus.read::<Deadline>().copied()
+        && deadline.is_expired()
+    {
+        return Some(deadline_exceeded(
+            bus, deadline, node_id, node_label, step_idx,
+        ));
     }
+    None
+}
 
-    Some(BusCapabilitySchema { allow, deny })
+/// Record where cancellation landed, once per execution.
+fn record_cancellation(bus: &mut Bus, node_id: Option<String>, context: &CancellationContext) {
+    if let Some(timeline) = bus.read_mut::<Timeline>() {
+        let already_recorded = timeline
+            .events
+            .iter()
+            .any(|event| matches!(event, TimelineEvent::ExecutionCancelled { .. }));
+        if !already_recorded {
+            timeline.push(TimelineEvent::ExecutionCancelled {
+                node_id,
+                reason: context.reason,
+                timestamp: Timestamp::now(),
+                trace: None,
+            });
+        }
+    }
 }
 
-fn now_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_millis() as u64)
-        .unwrap_or(0)
+/// The most recently entered node that has not exited yet.
+fn in_flight_node(timeline: &Timeline) -> Option<String> {
+    let mut exited = std::collections::HashSet::new();
+    timeline.events.iter().rev().find_map(|event| match event {
+        TimelineEvent::NodeExit { node_id, .. } => {
+            exited.insert(node_id.as_str());
+            None
+        }
+        TimelineEvent::NodeEnter { node_id, .. } if !exited.contains(node_id.as_str()) => {
+            Some(node_id.clone())
+        }
+        _ => None,
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{
-        Axon, ParallelBusPolicy, ParallelStrategy, inspector_dev_mode_from_value,
-        inspector_enabled_from_value, sampled_by_bus_id, should_force_export,
-    };
-    use crate::persistence::{
-        CompensationContext, CompensationHandle, CompensationHook, CompensationIdempotencyHandle,
-        CompensationIdempotencyStore, CompensationRetryPolicy, CompletionState,
-        InMemoryCompensationIdempotencyStore, InMemoryPersistenceStore, PersistenceAutoComplete,
-        PersistenceHandle, PersistenceStore, PersistenceTraceId,
+/// Fault a node that was reached after the execution's [`Deadline`].
+///
+/// `E` is built from the error message when it deserializes from a string
+/// (as `String` does); otherwise the execution ends with a
+/// `ranvier.deadline.exceeded` emit, as for panics.
+fn deadline_exceeded<Out, E>(
+    bus: &mut Bus,
+    deadline: Deadline,
+    node_id: &str,
+    node_label: &str,
+    step_idx: u64,
+) -> Outcome<Out, E>
+where
+    E: serde::de::DeserializeOwned,
+{
+    let exceeded = DeadlineExceeded {
+        node_label: node_label.to_string(),
+        overrun_ms: deadline.instant().elapsed().as_millis() as u64,
     };
-    use anyhow::Result;
-    use async_trait::async_trait;
-    use ranvier_core::event::{DlqPolicy, DlqSink};
-    use ranvier_core::saga::SagaStack;
-    #[cfg(feature = "inspector")]
-    use ranvier_core::telemetry::{AuditLogger, InterventionEvent};
-    use ranvier_core::timeline::{Timeline, TimelineEvent};
-    use ranvier_core::{Bus, BusAccessError, BusAccessPolicy, BusTypeRef, Outcome, Transition};
-    use serde::{Deserialize, Serialize};
-    use std::sync::Arc;
-    use std::sync::atomic::{AtomicUsize, Ordering};
-    use tokio::sync::Mutex;
-    use uuid::Uuid;
+    tracing::warn!(
+        node_id = %node_id,
+        node_label = %node_label,
+        overrun_ms = exceeded.overrun_ms,
+        "Deadline exceeded; skipping node"
+    );
+    record_fault_cause(
+        bus,
+        FaultCause::new(node_id, node_label, exceeded.to_string())
+            .with_step_index(step_idx)
+            .with_category(DEADLINE_EXCEEDED),
+    );
+    match serde_json::from_value::<E>(serde_json::Value::String(exceeded.to_string())) {
+        Ok(error) => Outcome::Fault(error),
+        Err(_) => Outcome::emit(
+            "ranvier.deadline.exceeded",
+            serde_json::to_value(&exceeded).ok(),
+        ),
+    }
+}
 
-    #[cfg(feature = "inspector")]
-    struct MockAuditLogger {
-        events: Arc<Mutex<Vec<InterventionEvent>>>,
+fn interceptor_rejected<Out, E>(
+    bus: &mut Bus,
+    rejected: InterceptorRejected,
+    node_id: &str,
+    node_label: &str,
+    step_idx: u64,
+) -> Outcome<Out, E>
+where
+    E: serde::de::DeserializeOwned,
+{
+    tracing::info!(
+        node_id = %node_id,
+        interceptor = %rejected.interceptor,
+        reason = %rejected.reason,
+        "Interceptor stopped node"
+    );
+    record_fault_cause(
+        bus,
+        FaultCause::new(node_id, node_label, rejected.to_string())
+            .with_step_index(step_idx)
+            .with_category(INTERCEPTOR_REJECTED),
+    );
+    match serde_json::from_value::<E>(serde_json::Value::String(rejected.to_string())) {
+        Ok(error) => Outcome::Fault(error),
+        Err(_) => Outcome::emit(
+            "ranvier.interceptor.rejected",
+            serde_json::to_value(&rejected).ok(),
+        ),
     }
+}
 
-    #[cfg(feature = "inspector")]
-    #[async_trait]
-    impl AuditLogger for MockAuditLogger {
-        async fn log_intervention(&self, event: InterventionEvent) -> Result<(), String> {
-            self.events.lock().await.push(event);
-            Ok(())
-        }
+/// Add a fault to the Bus [`FaultChain`] and mirror the resulting cause tree
+/// into the Timeline.
+fn record_fault_cause(bus: &mut Bus, cause: FaultCause) {
+    if !bus.has::<FaultChain>() {
+        bus.insert(FaultChain::new());
+    }
+    let Some(recorded) = bus
+        .read_mut::<FaultChain>()
+        .map(|chain| chain.record(cause).clone())
+    else {
+        return;
+    };
+    let mut event = TimelineEvent::FaultRecorded {
+        node_id: recorded.node_id.clone(),
+        timestamp: recorded.timestamp,
+        cause: recorded,
+        trace: None,
+    };
+    if let Some(rules) = bus.read::<RedactionRules>() {
+        rules.redact_event(&mut event);
+    }
+    if let Some(timeline) = bus.read_mut::<Timeline>() {
+        timeline.push(event);
     }
+}
 
-    #[cfg(feature = "inspector")]
-    #[tokio::test]
-    async fn execute_logs_audit_events_for_intervention() {
-        use ranvier_inspector::StateInspector;
+/// Remember which node suspended, so a
+/// [`ResumableAxon`](crate::suspend::ResumableAxon) can resume there.
+fn record_suspension<T, E>(
+    bus: &mut Bus,
+    outcome: &Outcome<T, E>,
+    node_id: &str,
+    node_label: &str,
+    step_idx: u64,
+    input: Option<serde_json::Value>,
+) {
+    if let Outcome::Suspend(token, _) = outcome {
+        bus.insert(crate::suspend::SuspensionPoint {
+            token: token.clone(),
+            node_id: node_id.to_string(),
+            node_label: node_label.to_string(),
+            step: step_idx,
+            input,
+        });
+    }
+}
 
-        let trace_id = "test-audit-trace";
-        let store_impl = InMemoryPersistenceStore::new();
-        let events = Arc::new(Mutex::new(Vec::new()));
-        let logger = MockAuditLogger {
+/// Prepend earlier attempts to the latest recorded fault, in the chain and
+/// in its Timeline event.
+fn attach_fault_retries(bus: &mut Bus, retries: Vec<FaultRetry>) {
+    if retries.is_empty() {
+        return;
+    }
+    let Some(cause) = bus
+        .read_mut::<FaultChain>()
+        .and_then(FaultChain::latest_mut)
+    else {
+        return;
+    };
+    cause.retries.splice(0..0, retries);
+    let updated = cause.clone();
+    if let Some(timeline) = bus.read_mut::<Timeline>()
+        && let Some(TimelineEvent::FaultRecorded { cause, .. }) =
+            timeline.events.iter_mut().rev().find(|event| {
+                matches!(event, TimelineEvent::FaultRecorded { node_id, .. } if *node_id == updated.node_id)
+            })
+    {
+        *cause = updated;
+    }
+}
+
+/// The node policies of the running Axon, carried on the Bus.
+#[derive(Clone)]
+struct NodePolicies(Arc<NodePoliciesConfig>);
+
+/// The running Axon's override for `label`. Applied on top of the policy a
+/// node was chained with, so policies set after chaining still take effect.
+fn running_node_policy<'a>(bus: &'a Bus, label: &str) -> Option<&'a NodePolicyConfig> {
+    node_policy_override(bus.read::<NodePolicies>().map(|p| p.0.as_ref()), label)
+}
+
+/// Record `policies` in the metadata of nodes that are already chained,
+/// including those of subgraphs.
+fn record_node_policies(schematic: &mut Schematic, policies: &NodePoliciesConfig) {
+    for node in &mut schematic.nodes {
+        if let NodeKind::Subgraph(inner) = &mut node.kind {
+            record_node_policies(inner, policies);
+            continue;
+        }
+        let Some(overrides) = node_policy_override(Some(policies), &node.label) else {
+            continue;
+        };
+        let Some(applied) = &mut node.metadata.policy else {
+            if matches!(node.kind, NodeKind::Atom) {
+                warn_unapplied_node_policy(
+                    &node.label,
+                    &[
+                        ("max_retries", overrides.max_retries.is_some()),
+                        ("backoff", overrides.backoff.is_some()),
+                        ("timeout_ms", overrides.timeout_ms.is_some()),
+                    ],
+                );
+            }
+            continue;
+        };
+        let retries = applied.max_retries.is_some();
+        let timeout = applied.timeout_ms.is_some();
+        if retries {
+            applied.max_retries = overrides.max_retries.or(applied.max_retries);
+            applied.backoff = overrides.backoff.clone().or(applied.backoff.take());
+        }
+        if timeout {
+            applied.timeout_ms = overrides.timeout_ms.or(applied.timeout_ms);
+        }
+        if (retries && (overrides.max_retries.is_some() || overrides.backoff.is_some()))
+            || (timeout && overrides.timeout_ms.is_some())
+        {
+            applied.source = NodePolicySource::Config;
+        }
+        warn_unapplied_node_policy(
+            &node.label,
+            &[
+                ("max_retries", !retries && overrides.max_retries.is_some()),
+                ("backoff", !retries && overrides.backoff.is_some()),
+                ("timeout_ms", !timeout && overrides.timeout_ms.is_some()),
+            ],
+        );
+    }
+}
+
+/// Look up the `[nodes.<label>]` override for a node, if any.
+fn node_policy_override<'a>(
+    policies: Option<&'a NodePoliciesConfig>,
+    label: &str,
+) -> Option<&'a NodePolicyConfig> {
+    policies
+        .and_then(|policies| policies.get(label))
+        .filter(|policy| !policy.is_empty())
+}
+
+/// Warn about override fields a node cannot honour; e.g. `timeout_ms` on a
+/// node chained without a timeout guard has no error factory to fault with.
+fn warn_unapplied_node_policy(label: &str, fields: &[(&str, bool)]) {
+    let ignored: Vec<&str> = fields
+        .iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| *name)
+        .collect();
+    if !ignored.is_empty() {
+        tracing::warn!(
+            node = %label,
+            fields = ?ignored,
+            "Node policy override ignored: the node does not declare this policy in code"
+        );
+    }
+}
+
+/// Schematic metadata for a transition node: its doc description and any
+/// retry/timeout policy applied to it.
+fn node_metadata(description: Option<String>, policy: Option<AppliedNodePolicy>) -> StepMetadata {
+    StepMetadata {
+        description,
+        policy,
+        ..Default::default()
+    }
+}
+
+/// ID for a transition's node at `position`: the one it fixes with
+/// [`Transition::node_id`], else one derived from circuit, label and position.
+fn transition_node_id<From, To, T>(transition: &T, schematic: &Schematic, position: usize) -> String
+where
+    From: Send + 'static,
+    To: Send + 'static,
+    T: Transition<From, To> + ?Sized,
+{
+    transition
+        .node_id()
+        .unwrap_or_else(|| stable_node_id(&schematic.name, &transition.label(), position))
+}
+
+/// Where a transition's node points: its definition when it reports one,
+/// else the builder call.
+fn transition_source<From, To, T>(
+    transition: &T,
+    caller: &std::panic::Location<'_>,
+) -> SourceLocation
+where
+    From: Send + 'static,
+    To: Send + 'static,
+    T: Transition<From, To> + ?Sized,
+{
+    transition
+        .source_location()
+        .unwrap_or_else(|| SourceLocation::from(caller))
+}
+
+/// Record a transition's [`required_resources`](Transition::required_resources)
+/// on its node metadata.
+fn with_required_resources(
+    mut metadata: StepMetadata,
+    required: Vec<ranvier_core::bus::BusTypeRef>,
+) -> StepMetadata {
+    let mut names: Vec<String> = required
+        .into_iter()
+        .map(|type_ref| type_ref.type_name.to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    metadata.required_resources = names;
+    metadata
+}
+
+/// Record the branch ids `node` declares and give each a `Branch` edge to an
+/// `Egress` exit, pushed before `node` so it stays the last node.
+/// [`Axon::branch`] swaps an exit for the arm that handles its id.
+fn push_branch_exits(schematic: &mut Schematic, node: &mut Node, branches: Vec<String>) {
+    for branch_id in branches {
+        if node.metadata.branches.contains(&branch_id) {
+            continue;
+        }
+        let exit_id = stable_node_id(&node.id, &branch_id, 0);
+        schematic.nodes.push(Node {
+            id: exit_id.clone(),
+            kind: NodeKind::Egress,
+            label: branch_id.clone(),
+            description: Some(format!("Ends with Outcome::Branch(\"{branch_id}\")")),
+            input_type: type_name_of::<Option<serde_json::Value>>(),
+            output_type: type_name_of::<Option<serde_json::Value>>(),
+            resource_type: node.resource_type.clone(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: node.source_location.clone(),
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: Some(true),
+        });
+        schematic.edges.push(Edge {
+            from: node.id.clone(),
+            to: exit_id,
+            kind: EdgeType::Branch(branch_id.clone()),
+            label: Some(branch_id.clone()),
+        });
+        node.metadata.branches.push(branch_id);
+    }
+}
+
+fn bus_capability_schema_from_policy(
+    policy: Option<ranvier_core::bus::BusAccessPolicy>,
+) -> Option<BusCapabilitySchema> {
+    let policy = policy?;
+
+    let mut allow = policy
+        .allow
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| entry.type_name.to_string())
+        .collect::<Vec<_>>();
+    let mut deny = policy
+        .deny
+        .into_iter()
+        .map(|entry| entry.type_name.to_string())
+        .collect::<Vec<_>>();
+    allow.sort();
+    deny.sort();
+
+    if allow.is_empty() && deny.is_empty() {
+        return None;
+    }
+
+    Some(BusCapabilitySchema { allow, deny })
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Axon, ParallelBusPolicy, ParallelStrategy, inspector_dev_mode_from_value,
+        inspector_enabled_from_value, sampled_by_bus_id, should_force_export,
+    };
+    use crate::persistence::{
+        CompensationContext, CompensationHandle, CompensationHook, CompensationIdempotencyHandle,
+        CompensationIdempotencyStore, CompensationRetryPolicy, CompletionState,
+        InMemoryCompensationIdempotencyStore, InMemoryPersistenceStore, PersistenceAutoComplete,
+        PersistenceHandle, PersistenceStore, PersistenceTraceId,
+    };
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use ranvier_core::event::{DlqPolicy, DlqSink};
+    use ranvier_core::saga::SagaStack;
+    use ranvier_core::telemetry::TraceContext;
+    #[cfg(feature = "inspector")]
+    use ranvier_core::telemetry::{AuditLogger, InterventionEvent};
+    use ranvier_core::timeline::{Timeline, TimelineEvent};
+    use ranvier_core::{Bus, BusAccessError, BusAccessPolicy, BusTypeRef, Outcome, Transition};
+    use serde::{Deserialize, Serialize};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    #[cfg(feature = "inspector")]
+    struct MockAuditLogger {
+        events: Arc<Mutex<Vec<InterventionEvent>>>,
+    }
+
+    #[cfg(feature = "inspector")]
+    #[async_trait]
+    impl AuditLogger for MockAuditLogger {
+        async fn log_intervention(&self, event: InterventionEvent) -> Result<(), String> {
+            self.events.lock().await.push(event);
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "inspector")]
+    #[tokio::test]
+    async fn execute_logs_audit_events_for_intervention() {
+        use ranvier_inspector::StateInspector;
+
+        let trace_id = "test-audit-trace";
+        let store_impl = InMemoryPersistenceStore::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let logger = MockAuditLogger {
             events: events.clone(),
         };
 
@@ -2279,6 +3095,62 @@ mod tests {
         assert_eq!(dlq_count, 1, "Should have 1 DlqExhausted event");
     }
 
+    #[tokio::test]
+    async fn retry_then_dlq_retries_a_compensated_node_before_compensating() {
+        use crate::closure_transition::ClosureTransition;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let refunds = Arc::new(AtomicU32::new(0));
+        let refund_counter = refunds.clone();
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+
+        let dlq_sink = MockDlqSink {
+            letters: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+        };
+
+        let axon = Axon::<i32, i32, String>::start("RetryCompensated")
+            .then_compensated(
+                AlwaysFault,
+                ClosureTransition::new("Refund", move |_n: i32, _bus: &mut Bus| {
+                    refund_counter.fetch_add(1, Ordering::SeqCst);
+                    Outcome::<(), String>::next(())
+                }),
+            )
+            .with_dlq_policy(DlqPolicy::RetryThenDlq {
+                max_attempts: 3,
+                backoff_ms: 1,
+            })
+            .with_dlq_sink(dlq_sink.clone());
+        let outcome = axon.execute(42, &(), &mut bus).await;
+
+        assert!(matches!(outcome, Outcome::Fault(ref msg) if msg == "boom"));
+        assert_eq!(refunds.load(Ordering::SeqCst), 1);
+        assert_eq!(dlq_sink.letters.lock().await.len(), 1);
+
+        let timeline = bus.read::<Timeline>().unwrap();
+        let retry_count = timeline
+            .events
+            .iter()
+            .filter(|e| matches!(e, TimelineEvent::NodeRetry { .. }))
+            .count();
+        assert_eq!(retry_count, 2);
+        assert!(
+            timeline
+                .events
+                .iter()
+                .any(|e| matches!(e, TimelineEvent::DlqExhausted { .. }))
+        );
+        assert!(timeline.events.iter().any(|e| matches!(
+            e,
+            TimelineEvent::NodeExit { outcome_type, .. } if outcome_type == "Compensated"
+        )));
+        assert!(
+            bus.read::<ranvier_core::error::TransitionErrorContext>()
+                .is_some()
+        );
+    }
+
     #[tokio::test]
     async fn send_to_dlq_policy_sends_immediately_without_retry() {
         let mut bus = Bus::new();
@@ -2847,6 +3719,193 @@ mod tests {
         assert!(exit_count >= 1, "Should have at least 1 NodeExit event");
     }
 
+    #[tokio::test]
+    async fn timeline_events_carry_the_trace_of_their_run() {
+        let axon = Axon::<i32, i32, TestInfallible>::start("Traced")
+            .then(AddOne)
+            .then(MultiplyByTwo);
+        let caller = TraceContext {
+            trace_id: "4bf92f3577b34da6a3ce929d0e0e4736".into(),
+            span_id: "00f067aa0ba902b7".into(),
+        };
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        bus.insert(caller.clone());
+        for run_id in ["run-1", "run-2"] {
+            bus.insert(PersistenceTraceId::new(run_id));
+            axon.execute(3, &(), &mut bus).await;
+        }
+        assert_eq!(bus.read::<TraceContext>(), Some(&caller));
+
+        let timeline = bus.read::<Timeline>().unwrap();
+        assert!(timeline.events.iter().all(|event| event.trace().is_some()));
+        let first = timeline.run("run-1");
+        let second = timeline.run("run-2");
+        assert!(!first.events.is_empty());
+        assert_eq!(
+            first.events.len() + second.events.len(),
+            timeline.events.len()
+        );
+        let span_of = |run: &Timeline| run.events[0].trace().unwrap().clone();
+        let (first, second) = (span_of(&first), span_of(&second));
+        assert_eq!(first.trace_id, caller.trace_id);
+        assert_eq!(second.trace_id, caller.trace_id);
+        assert_ne!(first.parent_span_id, second.parent_span_id);
+        assert_ne!(first.parent_span_id, caller.span_id);
+
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        axon.execute(3, &(), &mut bus).await;
+        assert!(bus.read::<TraceContext>().is_none());
+        let trace = bus.read::<Timeline>().unwrap().events[0]
+            .trace()
+            .unwrap()
+            .clone();
+        assert_eq!(trace.trace_id.len(), 32);
+        assert_eq!(trace.parent_span_id.len(), 16);
+    }
+
+    #[tokio::test]
+    async fn allowlisted_nodes_capture_their_payloads() {
+        use ranvier_core::capture::{CaptureFormat, NodeCapturePolicy};
+        use ranvier_core::timeline::CaptureDirection;
+
+        let axon = Axon::<i32, i32, TestInfallible>::start("NodeCapture")
+            .then(AddOne)
+            .then(MultiplyByTwo);
+        let node_id = |index: usize| axon.schematic.nodes[index].id.clone();
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        bus.insert(NodeCapturePolicy::new(CaptureFormat::Json).node(node_id(2)));
+        axon.execute(3, &(), &mut bus).await;
+
+        let timeline = bus.read::<Timeline>().unwrap();
+        assert_eq!(timeline.node_payloads(&node_id(1)).count(), 0);
+        let captured: Vec<_> = timeline
+            .node_payloads(&node_id(2))
+            .map(|(direction, payload)| (direction, payload.decode::<i32>().unwrap()))
+            .collect();
+        assert_eq!(
+            captured,
+            vec![
+                (CaptureDirection::NodeInput, 4),
+                (CaptureDirection::NodeOutput, 8)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn compensated_nodes_capture_their_payloads() {
+        use crate::closure_transition::ClosureTransition;
+        use ranvier_core::capture::{CaptureFormat, NodeCapturePolicy};
+        use ranvier_core::timeline::CaptureDirection;
+
+        let axon = Axon::<i32, i32, String>::new("NodeCapture").then_compensated(
+            ClosureTransition::new("Charge", |n: i32, _bus: &mut Bus| {
+                Outcome::<i32, String>::next(n * 10)
+            }),
+            ClosureTransition::new("Refund", |_n: i32, _bus: &mut Bus| {
+                Outcome::<(), String>::next(())
+            }),
+        );
+        let node_id = axon.schematic.nodes[1].id.clone();
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        bus.insert(NodeCapturePolicy::new(CaptureFormat::Json).node(node_id.clone()));
+        axon.execute(3, &(), &mut bus).await;
+
+        let captured: Vec<_> = bus
+            .read::<Timeline>()
+            .unwrap()
+            .node_payloads(&node_id)
+            .map(|(direction, payload)| (direction, payload.decode::<i32>().unwrap()))
+            .collect();
+        assert_eq!(
+            captured,
+            vec![
+                (CaptureDirection::NodeInput, 3),
+                (CaptureDirection::NodeOutput, 30)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn timeline_tail_streams_events_of_the_running_execution() {
+        use futures_util::{FutureExt, StreamExt};
+        use ranvier_core::timeline::tail::TimelineTail;
+
+        let axon = Axon::<i32, i32, String>::new("Tail")
+            .then_fn("Double", |n: i32, _bus| Outcome::next(n * 2));
+        let tail = TimelineTail::new();
+        let mut events = tail.subscribe();
+        let mut bus = Bus::new();
+        bus.insert(tail);
+        axon.execute(4, &(), &mut bus).await;
+
+        let mut streamed = Vec::new();
+        while let Some(Some(event)) = events.next().now_or_never() {
+            streamed.push(event);
+        }
+        let labels: Vec<_> = streamed
+            .iter()
+            .filter_map(|event| match event {
+                TimelineEvent::NodeEnter { node_label, .. } => Some(node_label.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(labels, ["Tail", "Double"]);
+        let run = streamed[0]
+            .trace()
+            .expect("streamed events carry the run")
+            .clone();
+        assert!(streamed.iter().all(|event| event.trace() == Some(&run)));
+        assert!(!bus.has::<Timeline>());
+    }
+
+    #[tokio::test]
+    async fn redaction_rules_on_the_bus_scrub_captured_payloads() {
+        use ranvier_core::capture::{CaptureFormat, CapturePolicy, NodeCapturePolicy};
+        use ranvier_core::redaction::{REDACTED, RedactionRules};
+
+        #[derive(Clone, serde::Serialize, serde::Deserialize)]
+        struct Login {
+            user: String,
+            password: String,
+        }
+
+        let axon = Axon::<Login, Login, String>::new("Login")
+            .then_fn("Check", |login: Login, _bus| Outcome::next(login))
+            .with_capture_policy(CapturePolicy::new(CaptureFormat::MessagePack));
+        let check = axon.schematic.nodes[1].id.clone();
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        bus.insert(NodeCapturePolicy::new(CaptureFormat::Json).node(check));
+        bus.insert(
+            RedactionRules::default().for_type::<Login>(|value| value["user"] = "u***".into()),
+        );
+        let login = Login {
+            user: "ada".into(),
+            password: "hunter2".into(),
+        };
+        axon.execute(login, &(), &mut bus).await;
+
+        let payloads: Vec<_> = bus
+            .read::<Timeline>()
+            .unwrap()
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                TimelineEvent::PayloadCaptured { payload, .. } => Some(payload.to_json().unwrap()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(payloads.len(), 4);
+        for payload in payloads {
+            assert_eq!(payload["password"], REDACTED);
+            assert_eq!(payload["user"], "u***");
+        }
+    }
+
     // ── Parallel Step Tests (M231) ───────────────────────────────
 
     #[derive(Debug)]
@@ -3127,21 +4186,749 @@ mod tests {
         assert!(fast_exit >= fast_enter);
     }
 
-    #[tokio::test]
-    async fn parallel_all_succeed_returns_first_next() {
-        use super::ParallelStrategy;
+    #[derive(Clone)]
+    struct Describe;
 
-        let mut bus = Bus::new();
-        let axon = Axon::<i32, i32, TestInfallible>::start("ParallelAllSucceed").parallel(
-            vec![
-                Arc::new(AddOne)
-                    as Arc<
-                        dyn Transition<i32, i32, Resources = (), Error = TestInfallible>
-                            + Send
-                            + Sync,
-                    >,
-                Arc::new(MultiplyByTwo),
-            ],
+    #[async_trait]
+    impl Transition<i32, String> for Describe {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(
+            &self,
+            state: i32,
+            _resources: &Self::Resources,
+            _bus: &mut Bus,
+        ) -> Outcome<String, Self::Error> {
+            if state < 0 {
+                Outcome::fault("negative".to_string())
+            } else {
+                Outcome::next(format!("#{state}"))
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    struct Square;
+
+    #[async_trait]
+    impl Transition<i32, i64> for Square {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(
+            &self,
+            state: i32,
+            _resources: &Self::Resources,
+            _bus: &mut Bus,
+        ) -> Outcome<i64, Self::Error> {
+            Outcome::next(i64::from(state) * i64::from(state))
+        }
+    }
+
+    #[tokio::test]
+    async fn join_merges_typed_branch_outputs_into_a_tuple() {
+        use ranvier_core::schematic::{EdgeType, NodeKind};
+
+        let axon = Axon::<i32, i32, String>::start("Join").join((Describe, Square));
+
+        let kinds: Vec<_> = axon.schematic.nodes.iter().map(|n| &n.kind).collect();
+        assert!(matches!(
+            kinds[kinds.len() - 4..],
+            [
+                NodeKind::FanOut,
+                NodeKind::Atom,
+                NodeKind::Atom,
+                NodeKind::FanIn
+            ]
+        ));
+        let fan_in = axon.schematic.nodes.last().expect("fan-in node");
+        assert!(fan_in.output_type.contains("String") && fan_in.output_type.contains("i64"));
+        let parallel_edges = axon
+            .schematic
+            .edges
+            .iter()
+            .filter(|edge| matches!(edge.kind, EdgeType::Parallel))
+            .count();
+        assert_eq!(parallel_edges, 4);
+
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        let outcome = axon.execute(7, &(), &mut bus).await;
+        assert!(matches!(outcome, Outcome::Next((ref text, 49)) if text == "#7"));
+        let entered = bus
+            .read::<Timeline>()
+            .expect("timeline")
+            .events
+            .iter()
+            .filter(|event| matches!(event, TimelineEvent::NodeEnter { .. }))
+            .count();
+        assert!(entered >= 4);
+
+        let outcome = axon.execute(-1, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Fault(ref e) if e == "negative"));
+    }
+
+    #[derive(Clone)]
+    struct RouteBySign;
+
+    #[async_trait]
+    impl Transition<i32, i32> for RouteBySign {
+        type Error = String;
+        type Resources = ();
+
+        fn branches(&self) -> Vec<String> {
+            vec!["negative".into(), "zero".into()]
+        }
+
+        async fn run(
+            &self,
+            state: i32,
+            _resources: &Self::Resources,
+            _bus: &mut Bus,
+        ) -> Outcome<i32, Self::Error> {
+            match state {
+                s if s < 0 => Outcome::branch("negative", Some(serde_json::json!(-s))),
+                0 => Outcome::branch("zero", None),
+                s => Outcome::next(s),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn branch_routes_outcomes_into_sub_chains() {
+        use ranvier_core::schematic::{EdgeType, NodeKind};
+
+        let axon = Axon::<i32, i32, String>::start("Router")
+            .then(RouteBySign)
+            .branch(|b| {
+                b.on(
+                    "negative",
+                    Axon::<i32, i32, String>::start("Negative").then(Describe),
+                )
+                .otherwise(Axon::<i32, i32, String>::start("Positive").then(Describe))
+            })
+            .then_fn("Shout", |text: String, _bus| {
+                Outcome::next(text.to_uppercase())
+            });
+
+        let branch_edges: Vec<_> = axon
+            .schematic
+            .edges
+            .iter()
+            .filter_map(|edge| match &edge.kind {
+                EdgeType::Branch(id) => Some(id.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(branch_edges, ["negative", "zero"]);
+        // "zero" has no arm, so it keeps the exit node it was declared with.
+        let exits = axon
+            .schematic
+            .nodes
+            .iter()
+            .filter(|node| matches!(node.kind, NodeKind::Egress))
+            .map(|node| node.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(exits, ["zero"]);
+        let subgraphs = axon
+            .schematic
+            .nodes
+            .iter()
+            .filter(|node| matches!(node.kind, NodeKind::Subgraph(_)))
+            .map(|node| node.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(subgraphs, ["Negative", "Positive"]);
+
+        let mut bus = Bus::new();
+        assert!(matches!(
+            axon.execute(4, &(), &mut bus).await,
+            Outcome::Next(ref text) if text == "#4"
+        ));
+        assert!(matches!(
+            axon.execute(-3, &(), &mut bus).await,
+            Outcome::Next(ref text) if text == "#3"
+        ));
+        assert!(matches!(
+            axon.execute(0, &(), &mut bus).await,
+            Outcome::Branch(ref id, None) if id == "zero"
+        ));
+    }
+
+    #[tokio::test]
+    async fn loop_while_repeats_body_until_predicate_fails_or_limit_hits() {
+        use crate::LOOP_LIMIT_EXCEEDED;
+        use ranvier_core::fault::FaultChain;
+        use ranvier_core::schematic::EdgeType;
+        use ranvier_core::schematic::analysis::CycleKind;
+
+        let doubling = |max_iterations| {
+            Axon::<i32, i32, String>::start("Grow")
+                .loop_while(
+                    |n: &i32| *n < 100,
+                    Axon::<i32, i32, String>::start("Double")
+                        .then_fn("Double", |n: i32, _bus| Outcome::next(n * 2)),
+                )
+                .max_iterations(max_iterations)
+        };
+
+        let axon = doubling(10);
+        let condition = axon.schematic.nodes.last().unwrap();
+        assert_eq!(condition.label, "LoopWhile(Double)");
+        assert!(
+            axon.schematic
+                .edges
+                .iter()
+                .any(|edge| { matches!(edge.kind, EdgeType::Jump) && edge.to == condition.id })
+        );
+        let analysis = axon.graph_analysis();
+        assert_eq!(analysis.cycles.len(), 1);
+        assert_eq!(analysis.cycles[0].kind, CycleKind::Loop);
+        assert!(analysis.cycles[0].nodes.contains(&condition.id));
+        assert!(analysis.topological_order.is_some());
+        assert!(axon.validate().is_ok());
+
+        let mut bus = Bus::new();
+        assert!(matches!(
+            axon.execute(3, &(), &mut bus).await,
+            Outcome::Next(192)
+        ));
+        assert!(matches!(
+            axon.execute(500, &(), &mut bus).await,
+            Outcome::Next(500)
+        ));
+
+        let mut bus = Bus::new();
+        let Outcome::Fault(error) = doubling(3).execute(3, &(), &mut bus).await else {
+            panic!("expected loop limit fault");
+        };
+        assert!(error.starts_with(LOOP_LIMIT_EXCEEDED));
+        let cause = bus
+            .read::<FaultChain>()
+            .and_then(FaultChain::latest)
+            .unwrap();
+        assert_eq!(cause.category.as_deref(), Some(LOOP_LIMIT_EXCEEDED));
+    }
+
+    #[tokio::test]
+    async fn catch_and_recover_handle_faults_inside_the_chain() {
+        use ranvier_core::schematic::EdgeType;
+
+        let caught = Axon::<i32, i32, String>::start("Caught")
+            .then(Describe)
+            .catch(|err, bus| {
+                if bus.read::<bool>().copied().unwrap_or(false) {
+                    Outcome::next("fallback".to_string())
+                } else {
+                    Outcome::fault(format!("re-raised: {err}"))
+                }
+            })
+            .then_fn("Shout", |text: String, _bus| {
+                Outcome::next(text.to_uppercase())
+            });
+        assert!(
+            caught
+                .schematic
+                .edges
+                .iter()
+                .any(|edge| matches!(edge.kind, EdgeType::Fault))
+        );
+
+        let mut bus = Bus::new();
+        assert!(matches!(
+            caught.execute(2, &(), &mut bus).await,
+            Outcome::Next(ref text) if text == "#2"
+        ));
+        assert!(matches!(
+            caught.execute(-5, &(), &mut bus).await,
+            Outcome::Fault(ref err) if err == "re-raised: negative"
+        ));
+        bus.insert(true);
+        assert!(matches!(
+            caught.execute(-1, &(), &mut bus).await,
+            Outcome::Next(ref text) if text == "FALLBACK"
+        ));
+
+        let recovered = Axon::<i32, i32, String>::start("Recovered")
+            .then(Describe)
+            .recover(
+                Axon::<String, String, String>::start("Fallback")
+                    .then_fn("Explain", |err: String, _bus| {
+                        Outcome::next(format!("recovered from {err}"))
+                    }),
+            );
+        assert!(matches!(
+            recovered.execute(-2, &(), &mut bus).await,
+            Outcome::Next(ref text) if text == "recovered from negative"
+        ));
+    }
+
+    #[derive(Clone)]
+    struct Pinned;
+
+    #[async_trait]
+    impl Transition<i32, i32> for Pinned {
+        type Error = String;
+        type Resources = ();
+
+        fn node_id(&self) -> Option<String> {
+            Some("pinned".into())
+        }
+
+        async fn run(
+            &self,
+            state: i32,
+            _resources: &Self::Resources,
+            _bus: &mut Bus,
+        ) -> Outcome<i32, Self::Error> {
+            Outcome::next(state)
+        }
+    }
+
+    #[test]
+    fn node_ids_are_stable_across_builds() {
+        fn build(extra_step: bool) -> Axon<i32, String, String> {
+            let axon = Axon::<i32, i32, String>::start("Stable");
+            let axon = if extra_step {
+                axon.then_fn("Extra", |n: i32, _bus| Outcome::next(n))
+            } else {
+                axon
+            };
+            axon.then(Pinned).then(RouteBySign).branch(|b| {
+                b.on(
+                    "negative",
+                    Axon::<i32, i32, String>::start("Negative").then(Describe),
+                )
+                .otherwise(Axon::<i32, i32, String>::start("Positive").then(Describe))
+            })
+        }
+        let ids = |axon: &Axon<i32, String, String>| {
+            axon.schematic
+                .nodes
+                .iter()
+                .map(|node| node.id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let first = build(false);
+        assert_eq!(ids(&first), ids(&build(false)));
+        let unique: std::collections::HashSet<_> = ids(&first).into_iter().collect();
+        assert_eq!(unique.len(), first.schematic.nodes.len());
+        assert!(ids(&first).contains(&"pinned".to_string()));
+
+        // Inserting a step moves the derived IDs downstream, not the fixed one.
+        let shifted = build(true);
+        assert_eq!(shifted.schematic.nodes[0].id, first.schematic.nodes[0].id);
+        assert!(ids(&shifted).contains(&"pinned".to_string()));
+        assert_ne!(
+            shifted.schematic.nodes.last().unwrap().id,
+            first.schematic.nodes.last().unwrap().id
+        );
+    }
+
+    #[tokio::test]
+    async fn then_axon_nests_the_child_schematic_and_runs_its_steps() {
+        let describe = Axon::<i32, i32, String>::start("Describe")
+            .then_fn("Square", |n: i32, _bus| Outcome::next(n * n))
+            .then(Describe);
+        let axon = Axon::<i32, i32, String>::start("Parent")
+            .then_fn("Negate", |n: i32, _bus| Outcome::next(-n))
+            .then_axon(describe)
+            .then_fn("Shout", |text: String, _bus| {
+                Outcome::next(text.to_uppercase())
+            });
+
+        let labels: Vec<_> = axon
+            .schematic
+            .nodes
+            .iter()
+            .map(|n| n.label.as_str())
+            .collect();
+        assert_eq!(labels, ["Parent", "Negate", "Describe", "Shout"]);
+        let ranvier_core::schematic::NodeKind::Subgraph(child) = &axon.schematic.nodes[2].kind
+        else {
+            panic!("expected a Subgraph node");
+        };
+        assert_eq!(child.nodes.len(), 3);
+        assert_eq!(axon.schematic.edges.len(), 3);
+
+        let mut bus = Bus::new();
+        assert!(matches!(
+            axon.execute(3, &(), &mut bus).await,
+            Outcome::Next(ref text) if text == "#9"
+        ));
+    }
+
+    #[tokio::test]
+    async fn map_and_map_err_convert_values_and_faults() {
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        enum AppError {
+            Invalid(String),
+        }
+
+        let axon = Axon::<i32, i32, String>::start("Adapt")
+            .then(Describe)
+            .map(|text: String| text.len())
+            .map_err(AppError::Invalid);
+
+        let labels: Vec<_> = axon
+            .schematic
+            .nodes
+            .iter()
+            .map(|n| n.label.as_str())
+            .collect();
+        assert_eq!(labels, ["Adapt", "Describe", "Map", "MapErr"]);
+
+        let mut bus = Bus::new();
+        assert!(matches!(
+            axon.execute(123, &(), &mut bus).await,
+            Outcome::Next(4)
+        ));
+        assert!(matches!(
+            axon.execute(-1, &(), &mut bus).await,
+            Outcome::Fault(AppError::Invalid(ref reason)) if reason == "negative"
+        ));
+    }
+
+    #[tokio::test]
+    async fn or_else_runs_the_fallback_on_the_original_input_after_a_fault() {
+        use ranvier_core::schematic::EdgeType;
+
+        let lookup = Axon::<i32, i32, String>::start("Lookup")
+            .then_fn("Cache", |n: i32, _bus| {
+                if n % 2 == 0 {
+                    Outcome::next(n * 100)
+                } else {
+                    Outcome::fault("miss".to_string())
+                }
+            })
+            .or_else(AddOneString);
+        assert!(lookup.schematic.edges.iter().any(|edge| {
+            matches!(edge.kind, EdgeType::Fault) && edge.label.as_deref() == Some("Fallback")
+        }));
+
+        let mut bus = Bus::new();
+        assert!(matches!(
+            lookup.execute(2, &(), &mut bus).await,
+            Outcome::Next(200)
+        ));
+        assert!(matches!(
+            lookup.execute(3, &(), &mut bus).await,
+            Outcome::Next(4)
+        ));
+    }
+
+    #[tokio::test]
+    async fn for_each_runs_the_item_axon_per_element() {
+        use super::ForEachReport;
+
+        let squares = |concurrency| {
+            Axon::<Vec<i32>, Vec<i32>, String>::start("Batch")
+                .for_each(
+                    Axon::<i32, i32, String>::start("Item")
+                        .then_fn("Square", |n: i32, _bus| Outcome::next(n * n))
+                        .then(Describe),
+                )
+                .concurrency(concurrency)
+        };
+        let collected = squares(3).collect();
+        assert_eq!(collected.schematic.nodes[1].label, "ForEach(Item)");
+
+        let mut bus = Bus::new();
+        assert!(matches!(
+            collected.execute(vec![1, 2, 3, 4], &(), &mut bus).await,
+            Outcome::Next(ref items) if items == &["#1", "#4", "#9", "#16"]
+        ));
+
+        let failing = Axon::<Vec<i32>, Vec<i32>, String>::start("Batch")
+            .for_each(Axon::<i32, i32, String>::start("Item").then(Describe))
+            .concurrency(2);
+        let report = failing.collect_settled();
+        let outcome = report.execute(vec![1, -2, 3], &(), &mut bus).await;
+        let Outcome::Next(report) = outcome else {
+            panic!("expected a report");
+        };
+        assert_eq!(
+            report,
+            ForEachReport {
+                succeeded: vec![(0, "#1".to_string()), (2, "#3".to_string())],
+                failed: vec![(1, "negative".to_string())],
+            }
+        );
+
+        let fail_fast = Axon::<Vec<i32>, Vec<i32>, String>::start("Batch")
+            .for_each(Axon::<i32, i32, String>::start("Item").then(Describe))
+            .collect();
+        assert!(matches!(
+            fail_fast.execute(vec![1, -2, 3], &(), &mut bus).await,
+            Outcome::Fault(ref err) if err == "negative"
+        ));
+    }
+
+    #[tokio::test]
+    async fn tap_observes_next_values_without_changing_them() {
+        let axon = Axon::<i32, i32, String>::start("Observed")
+            .tap(|n: &i32, bus| bus.insert(*n * 10))
+            .then(Describe)
+            .tap(|text: &String, bus| bus.insert(text.clone()));
+        assert!(matches!(
+            axon.schematic.nodes[1].kind,
+            ranvier_core::schematic::NodeKind::Tap
+        ));
+
+        let mut bus = Bus::new();
+        assert!(matches!(
+            axon.execute(4, &(), &mut bus).await,
+            Outcome::Next(ref text) if text == "#4"
+        ));
+        assert_eq!(bus.read::<i32>(), Some(&40));
+        assert_eq!(bus.read::<String>().map(String::as_str), Some("#4"));
+
+        let mut bus = Bus::new();
+        assert!(matches!(
+            axon.execute(-4, &(), &mut bus).await,
+            Outcome::Fault(_)
+        ));
+        assert_eq!(bus.read::<i32>(), Some(&-40));
+        assert!(bus.read::<String>().is_none());
+    }
+
+    #[tokio::test]
+    async fn race_takes_the_first_next_and_cancels_the_loser() {
+        use ranvier_core::schematic::EdgeType;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        #[derive(Clone)]
+        struct Replica {
+            name: &'static str,
+            delay_ms: u64,
+            fails: bool,
+            finished: Arc<AtomicBool>,
+        }
+
+        #[async_trait]
+        impl Transition<i32, String> for Replica {
+            type Error = String;
+            type Resources = ();
+
+            async fn run(&self, key: i32, _res: &(), _bus: &mut Bus) -> Outcome<String, String> {
+                tokio::time::sleep(std::time::Duration::from_millis(self.delay_ms)).await;
+                self.finished.store(true, Ordering::SeqCst);
+                if self.fails {
+                    Outcome::fault(format!("{} down", self.name))
+                } else {
+                    Outcome::next(format!("{}:{key}", self.name))
+                }
+            }
+
+            fn label(&self) -> String {
+                self.name.to_string()
+            }
+        }
+
+        let replica = |name, delay_ms, fails| Replica {
+            name,
+            delay_ms,
+            fails,
+            finished: Arc::new(AtomicBool::new(false)),
+        };
+
+        let (slow, fast) = (replica("primary", 200, false), replica("replica", 5, false));
+        let slow_finished = slow.finished.clone();
+        let axon = Axon::<i32, i32, String>::start("Read").race(slow, fast);
+        let race_lanes: Vec<_> = axon
+            .schematic
+            .edges
+            .iter()
+            .filter(|edge| matches!(edge.kind, EdgeType::Parallel))
+            .filter_map(|edge| edge.label.as_deref())
+            .filter(|label| label.starts_with("Race"))
+            .collect();
+        assert_eq!(race_lanes, ["Race primary", "Race hedge"]);
+
+        let outcome = axon.execute(7, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Next(ref value) if value == "replica:7"));
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        assert!(!slow_finished.load(Ordering::SeqCst));
+
+        // A fast fault does not win; the slower success still does.
+        let axon = Axon::<i32, i32, String>::start("Read")
+            .race(replica("primary", 30, false), replica("replica", 1, true));
+        let outcome = axon.execute(1, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Next(ref value) if value == "primary:1"));
+
+        let axon = Axon::<i32, i32, String>::start("Read")
+            .race(replica("primary", 10, true), replica("replica", 1, true));
+        let outcome = axon.execute(1, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Fault(ref err) if err == "primary down"));
+    }
+
+    #[tokio::test]
+    async fn retry_outcome_reruns_the_node_within_the_policy() {
+        use crate::retry::OutcomeRetryPolicy;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::time::Duration;
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let axon = Axon::<i32, i32, String>::new("Flaky").then_fn("Warmup", move |n: i32, _bus| {
+            if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                Outcome::retry_after(Duration::from_millis(1))
+            } else {
+                Outcome::next(n * 10)
+            }
+        });
+
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        bus.insert(OutcomeRetryPolicy::new(3, Duration::from_millis(5)));
+        let outcome = axon.execute(4, &(), &mut bus).await;
+        assert!(matches!(outcome, Outcome::Next(40)));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let retries = bus
+            .read::<Timeline>()
+            .unwrap()
+            .events
+            .iter()
+            .filter(|event| matches!(event, TimelineEvent::NodeRetry { .. }))
+            .count();
+        assert_eq!(retries, 2);
+
+        // Without a policy the Retry reaches the caller.
+        calls.store(0, Ordering::SeqCst);
+        let outcome = axon.execute(4, &(), &mut Bus::new()).await;
+        assert!(outcome.is_retry());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // An expired Deadline ends the retries instead of sleeping on.
+        let always = Axon::<i32, i32, String>::new("Stuck").then_fn("Poll", |_n: i32, _bus| {
+            Outcome::<i32, String>::retry_after(Duration::from_millis(10))
+        });
+        let mut bus = Bus::new();
+        bus.insert(OutcomeRetryPolicy::new(100, Duration::from_millis(10)));
+        bus.insert(ranvier_core::deadline::Deadline::after(
+            Duration::from_millis(25),
+        ));
+        let started = std::time::Instant::now();
+        let outcome = always.execute(1, &(), &mut bus).await;
+        assert!(matches!(outcome, Outcome::Fault(_)));
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn retry_outcome_reruns_a_compensated_node() {
+        use crate::closure_transition::ClosureTransition;
+        use crate::retry::OutcomeRetryPolicy;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::time::Duration;
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let refunds = Arc::new(AtomicU32::new(0));
+        let refund_counter = refunds.clone();
+        let axon = Axon::<i32, i32, String>::new("Payment").then_compensated(
+            ClosureTransition::new("Charge", move |n: i32, _bus: &mut Bus| {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Outcome::retry_after(Duration::from_millis(1))
+                } else {
+                    Outcome::<i32, String>::next(n * 10)
+                }
+            }),
+            ClosureTransition::new("Refund", move |_n: i32, _bus: &mut Bus| {
+                refund_counter.fetch_add(1, Ordering::SeqCst);
+                Outcome::<(), String>::next(())
+            }),
+        );
+
+        let mut bus = Bus::new();
+        bus.insert(OutcomeRetryPolicy::new(3, Duration::from_millis(5)));
+        let outcome = axon.execute(4, &(), &mut bus).await;
+
+        assert!(matches!(outcome, Outcome::Next(40)));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(refunds.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn expired_deadline_skips_the_remaining_nodes() {
+        use ranvier_core::deadline::{DEADLINE_EXCEEDED, Deadline};
+        use ranvier_core::fault::FaultChain;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+
+        let charged = Arc::new(AtomicBool::new(false));
+        let charged_flag = charged.clone();
+        let axon = Axon::<i32, i32, String>::new("Budget")
+            .then_fn("Spend", |n: i32, _bus| {
+                std::thread::sleep(Duration::from_millis(20));
+                Outcome::next(n)
+            })
+            .then_fn("Charge", move |n: i32, _bus| {
+                charged_flag.store(true, Ordering::SeqCst);
+                Outcome::next(n)
+            });
+        let mut bus = Bus::new();
+        bus.insert(Deadline::after(Duration::from_millis(5)));
+
+        let outcome = axon.execute(1, &(), &mut bus).await;
+
+        assert!(matches!(outcome, Outcome::Fault(ref e) if e.starts_with(DEADLINE_EXCEEDED)));
+        assert!(!charged.load(Ordering::SeqCst));
+        let cause = bus.read::<FaultChain>().unwrap().latest().unwrap();
+        assert_eq!(cause.node_label, "Charge");
+        assert_eq!(cause.category.as_deref(), Some(DEADLINE_EXCEEDED));
+
+        let mut bus = Bus::new();
+        bus.insert(Deadline::after(Duration::from_secs(60)));
+        assert!(matches!(
+            axon.execute(1, &(), &mut bus).await,
+            Outcome::Next(1)
+        ));
+    }
+
+    #[tokio::test]
+    async fn expired_deadline_skips_a_compensated_node() {
+        use crate::closure_transition::ClosureTransition;
+        use ranvier_core::deadline::{DEADLINE_EXCEEDED, Deadline};
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+
+        let charged = Arc::new(AtomicBool::new(false));
+        let charged_flag = charged.clone();
+        let axon = Axon::<i32, i32, String>::new("Budget").then_compensated(
+            ClosureTransition::new("Charge", move |n: i32, _bus: &mut Bus| {
+                charged_flag.store(true, Ordering::SeqCst);
+                Outcome::<i32, String>::next(n)
+            }),
+            ClosureTransition::new("Refund", |_n: i32, _bus: &mut Bus| {
+                Outcome::<(), String>::next(())
+            }),
+        );
+        let mut bus = Bus::new();
+        bus.insert(Deadline::after(Duration::from_millis(1)));
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let outcome = axon.execute(1, &(), &mut bus).await;
+
+        assert!(matches!(outcome, Outcome::Fault(ref e) if e.starts_with(DEADLINE_EXCEEDED)));
+        assert!(!charged.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn parallel_all_succeed_returns_first_next() {
+        use super::ParallelStrategy;
+
+        let mut bus = Bus::new();
+        let axon = Axon::<i32, i32, TestInfallible>::start("ParallelAllSucceed").parallel(
+            vec![
+                Arc::new(AddOne)
+                    as Arc<
+                        dyn Transition<i32, i32, Resources = (), Error = TestInfallible>
+                            + Send
+                            + Sync,
+                    >,
+                Arc::new(MultiplyByTwo),
+            ],
             ParallelStrategy::AllMustSucceed,
         );
 
@@ -3472,4 +5259,535 @@ mod tests {
         assert_eq!(axon.schematic.nodes.len(), 2);
         assert_eq!(axon.schematic.nodes[1].label, "my_custom_label");
     }
+
+    #[tokio::test]
+    async fn node_policies_override_code_defaults_and_surface_in_metadata() {
+        use crate::retry::RetryPolicy;
+        use ranvier_core::node_policy::{
+            BackoffConfig, NodePoliciesConfig, NodePolicyConfig, NodePolicySource,
+        };
+        use std::time::Duration;
+
+        let mut policies = NodePoliciesConfig::default();
+        policies.insert(
+            "AlwaysFault",
+            NodePolicyConfig {
+                max_retries: Some(2),
+                backoff: Some(BackoffConfig::Fixed { delay_ms: 1 }),
+                ..Default::default()
+            },
+        );
+        policies.insert(
+            "AddOneString",
+            NodePolicyConfig {
+                timeout_ms: Some(250),
+                ..Default::default()
+            },
+        );
+
+        let axon = Axon::<i32, i32, String>::new("Checkout")
+            .with_node_policies(policies)
+            .then_with_timeout(AddOneString, Duration::from_secs(10), || {
+                "timed out".to_string()
+            })
+            .then_with_retry(AlwaysFault, RetryPolicy::fixed(5, Duration::from_secs(1)));
+
+        let timeout_policy = axon.schematic.nodes[1].metadata.policy.clone().unwrap();
+        assert_eq!(timeout_policy.timeout_ms, Some(250));
+        assert_eq!(timeout_policy.source, NodePolicySource::Config);
+        let retry_policy = axon.schematic.nodes[2].metadata.policy.clone().unwrap();
+        assert_eq!(retry_policy.max_retries, Some(2));
+        assert_eq!(
+            retry_policy.backoff,
+            Some(BackoffConfig::Fixed { delay_ms: 1 })
+        );
+        assert_eq!(retry_policy.source, NodePolicySource::Config);
+
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        let result = axon.execute(1, &(), &mut bus).await;
+        assert!(matches!(result, Outcome::Fault(_)));
+        let retries = bus
+            .read::<Timeline>()
+            .unwrap()
+            .events
+            .iter()
+            .filter(|event| matches!(event, TimelineEvent::NodeRetry { .. }))
+            .count();
+        assert_eq!(retries, 2);
+    }
+
+    #[tokio::test]
+    async fn node_policies_set_after_chaining_still_apply() {
+        use crate::retry::RetryPolicy;
+        use ranvier_core::node_policy::{NodePoliciesConfig, NodePolicyConfig, NodePolicySource};
+        use std::time::Duration;
+
+        let mut policies = NodePoliciesConfig::default();
+        policies.insert(
+            "AlwaysFault",
+            NodePolicyConfig {
+                max_retries: Some(1),
+                ..Default::default()
+            },
+        );
+        let axon = Axon::<i32, i32, String>::new("Checkout")
+            .then_with_retry(AlwaysFault, RetryPolicy::fixed(5, Duration::from_millis(1)))
+            .with_node_policies(policies);
+
+        let policy = axon.schematic.nodes[1].metadata.policy.clone().unwrap();
+        assert_eq!(policy.max_retries, Some(1));
+        assert_eq!(policy.source, NodePolicySource::Config);
+
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        assert!(matches!(
+            axon.execute(1, &(), &mut bus).await,
+            Outcome::Fault(_)
+        ));
+        let retries = bus
+            .read::<Timeline>()
+            .unwrap()
+            .events
+            .iter()
+            .filter(|event| matches!(event, TimelineEvent::NodeRetry { .. }))
+            .count();
+        assert_eq!(retries, 1);
+    }
+
+    #[test]
+    fn code_declared_policy_is_recorded_without_config() {
+        use crate::retry::RetryPolicy;
+        use ranvier_core::node_policy::NodePolicySource;
+
+        let axon = Axon::<i32, i32, String>::new("NoConfig")
+            .then_with_retry(AlwaysFault, RetryPolicy::exponential_default(3, 50));
+        let policy = axon.schematic.nodes[1].metadata.policy.clone().unwrap();
+        assert_eq!(policy.max_retries, Some(3));
+        assert_eq!(policy.source, NodePolicySource::Code);
+        assert!(axon.schematic.nodes[0].metadata.policy.is_none());
+    }
+
+    #[tokio::test]
+    async fn then_timeout_faults_with_typed_timeout_error() {
+        use crate::timeout::NODE_TIMEOUT;
+        use ranvier_core::fault::FaultChain;
+        use std::time::Duration;
+
+        #[derive(Clone)]
+        struct Stall;
+
+        #[async_trait]
+        impl Transition<i32, i32> for Stall {
+            type Error = String;
+            type Resources = ();
+
+            async fn run(&self, state: i32, _res: &(), _bus: &mut Bus) -> Outcome<i32, String> {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Outcome::Next(state)
+            }
+        }
+
+        let axon = Axon::<i32, i32, String>::new("Deadline")
+            .then_timeout(Stall, Duration::from_millis(20));
+        let policy = axon.schematic.nodes[1].metadata.policy.clone().unwrap();
+        assert_eq!(policy.timeout_ms, Some(20));
+
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        let Outcome::Fault(error) = axon.execute(1, &(), &mut bus).await else {
+            panic!("expected timeout fault");
+        };
+        assert_eq!(error, "node_timeout: 'Stall' did not finish within 20ms");
+        let cause = bus
+            .read::<FaultChain>()
+            .and_then(FaultChain::latest)
+            .unwrap();
+        assert_eq!(cause.category.as_deref(), Some(NODE_TIMEOUT));
+    }
+
+    #[tokio::test]
+    async fn retried_fault_records_cause_with_retry_history() {
+        use crate::retry::RetryPolicy;
+        use ranvier_core::fault::FaultChain;
+        use std::time::Duration;
+
+        let axon = Axon::<i32, i32, String>::new("FaultChain")
+            .then(AddOneString)
+            .then_with_retry(AlwaysFault, RetryPolicy::fixed(2, Duration::from_millis(1)));
+
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        let result = axon.execute(1, &(), &mut bus).await;
+        assert!(matches!(result, Outcome::Fault(_)));
+
+        let cause = bus
+            .read::<FaultChain>()
+            .and_then(FaultChain::latest)
+            .expect("fault cause recorded")
+            .clone();
+        assert_eq!(cause.node_label, "AlwaysFault");
+        assert_eq!(cause.error, "\"boom\"");
+        assert_eq!(cause.step_index, Some(2));
+        assert_eq!(cause.retries.len(), 2);
+        assert_eq!(cause.retries[1].attempt, 2);
+        assert!(cause.causes.is_empty());
+
+        let timeline = bus.read::<Timeline>().unwrap();
+        assert_eq!(timeline.latest_fault(), Some(&cause));
+    }
+
+    #[tokio::test]
+    async fn failed_require_faults_with_missing_resource_category() {
+        use ranvier_core::bus::MISSING_RESOURCE;
+        use ranvier_core::fault::FaultChain;
+
+        struct Mailer;
+
+        let axon =
+            Axon::<i32, i32, String>::new("Notify").then_fn("SendMail", |n: i32, bus: &mut Bus| {
+                match bus.require::<Mailer>() {
+                    Ok(_) => Outcome::next(n),
+                    Err(missing) => Outcome::Fault(missing.into()),
+                }
+            });
+        let node_id = axon.schematic.nodes[1].id.clone();
+
+        let mut bus = Bus::new();
+        let Outcome::Fault(error) = axon.execute(1, &(), &mut bus).await else {
+            panic!("missing resource should fault");
+        };
+        assert!(error.starts_with(MISSING_RESOURCE));
+        let cause = bus
+            .read::<FaultChain>()
+            .and_then(FaultChain::latest)
+            .expect("fault cause recorded");
+        assert_eq!(cause.category.as_deref(), Some(MISSING_RESOURCE));
+        assert_eq!(cause.node_id, node_id);
+        assert!(cause.error.contains("Mailer"));
+        assert!(cause.error.contains(&node_id));
+        assert!(bus.current_node().is_none());
+
+        let mut bus = Bus::new();
+        bus.insert(Mailer);
+        assert!(matches!(
+            axon.execute(1, &(), &mut bus).await,
+            Outcome::Next(1)
+        ));
+    }
+
+    #[tokio::test]
+    async fn fault_after_a_failed_require_keeps_the_node_error() {
+        use ranvier_core::bus::MISSING_RESOURCE;
+        use ranvier_core::fault::FaultChain;
+
+        struct Mailer;
+
+        let axon = Axon::<i32, i32, String>::new("Notify").then_fn(
+            "SendMail",
+            |_n: i32, bus: &mut Bus| {
+                if bus.require::<Mailer>().is_err() {
+                    return Outcome::<i32, String>::fault("queue full".to_string());
+                }
+                Outcome::<i32, String>::fault("unreachable".to_string())
+            },
+        );
+
+        let mut bus = Bus::new();
+        assert!(axon.execute(1, &(), &mut bus).await.is_fault());
+        let cause = bus
+            .read::<FaultChain>()
+            .and_then(FaultChain::latest)
+            .expect("fault cause recorded");
+        assert!(cause.error.contains("queue full"));
+        assert_eq!(cause.category.as_deref(), Some(MISSING_RESOURCE));
+        assert_eq!(cause.causes.len(), 1);
+        assert!(cause.causes[0].error.contains("Mailer"));
+    }
+
+    #[tokio::test]
+    async fn missing_resource_of_an_earlier_attempt_is_cleared_on_retry() {
+        use crate::retry::OutcomeRetryPolicy;
+        use ranvier_core::fault::FaultChain;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::time::Duration;
+
+        struct Mailer;
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let axon = Axon::<i32, i32, String>::new("Notify").then_fn(
+            "SendMail",
+            move |_n: i32, bus: &mut Bus| {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    let _ = bus.require::<Mailer>();
+                    return Outcome::<i32, String>::retry_after(Duration::from_millis(1));
+                }
+                Outcome::<i32, String>::fault("declined".to_string())
+            },
+        );
+
+        let mut bus = Bus::new();
+        bus.insert(OutcomeRetryPolicy::new(3, Duration::from_millis(5)));
+        assert!(axon.execute(1, &(), &mut bus).await.is_fault());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let cause = bus
+            .read::<FaultChain>()
+            .and_then(FaultChain::latest)
+            .expect("fault cause recorded");
+        assert!(cause.error.contains("declined"));
+        assert!(cause.category.is_none());
+        assert!(cause.causes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_require_in_a_compensated_node_is_attributed_to_it() {
+        use crate::closure_transition::ClosureTransition;
+        use ranvier_core::bus::MISSING_RESOURCE;
+        use ranvier_core::fault::FaultChain;
+
+        struct Mailer;
+
+        let axon = Axon::<i32, i32, String>::new("Notify").then_compensated(
+            ClosureTransition::new("SendMail", |n: i32, bus: &mut Bus| {
+                match bus.require::<Mailer>() {
+                    Ok(_) => Outcome::next(n),
+                    Err(missing) => Outcome::Fault(missing.into()),
+                }
+            }),
+            ClosureTransition::new("Unsend", |_n: i32, _bus: &mut Bus| {
+                Outcome::<(), String>::next(())
+            }),
+        );
+        let node_id = axon.schematic.nodes[1].id.clone();
+
+        let mut bus = Bus::new();
+        assert!(axon.execute(1, &(), &mut bus).await.is_fault());
+        let cause = bus
+            .read::<FaultChain>()
+            .and_then(FaultChain::latest)
+            .expect("fault cause recorded");
+        assert_eq!(cause.category.as_deref(), Some(MISSING_RESOURCE));
+        assert_eq!(cause.node_id, node_id);
+        assert!(cause.error.contains(&node_id));
+        assert!(bus.current_node().is_none());
+    }
+
+    #[tokio::test]
+    async fn executed_and_registered_circuits_reach_the_global_registry() {
+        use ranvier_core::schematic_registry::SchematicRegistry;
+
+        let registry = SchematicRegistry::global();
+        let axon = Axon::<i32, i32, String>::new("registry-test-executed")
+            .then_fn("Double", |n: i32, _bus| Outcome::next(n * 2));
+        assert!(registry.get("registry-test-executed").is_none());
+        let _ = axon.execute(2, &(), &mut Bus::new()).await;
+        assert_eq!(
+            registry.get("registry-test-executed").unwrap().nodes.len(),
+            axon.schematic.nodes.len()
+        );
+        // Later executions of the same Axon do not register it again.
+        registry.remove("registry-test-executed");
+        let _ = axon.execute(2, &(), &mut Bus::new()).await;
+        assert!(registry.get("registry-test-executed").is_none());
+
+        let _listed = Axon::<i32, i32, String>::new("registry-test-listed").register();
+        assert!(
+            registry
+                .names()
+                .contains(&"registry-test-listed".to_string())
+        );
+    }
+
+    #[test]
+    fn check_resources_reports_undeclared_bus_entries() {
+        use ranvier_core::bus::BusTypeRef;
+        use ranvier_core::transition::ResourceRequirement;
+
+        struct Db;
+        struct Mailer;
+        struct AppResources;
+        impl ResourceRequirement for AppResources {
+            fn bus_requirements() -> Vec<BusTypeRef> {
+                vec![BusTypeRef::of::<Db>()]
+            }
+        }
+
+        #[derive(Clone)]
+        struct Load;
+        #[async_trait]
+        impl Transition<i32, i32> for Load {
+            type Error = String;
+            type Resources = AppResources;
+
+            async fn run(&self, n: i32, _: &AppResources, _: &mut Bus) -> Outcome<i32, String> {
+                Outcome::next(n)
+            }
+        }
+
+        #[derive(Clone)]
+        struct Notify;
+        #[async_trait]
+        impl Transition<i32, i32> for Notify {
+            type Error = String;
+            type Resources = AppResources;
+
+            fn required_resources(&self) -> Vec<BusTypeRef> {
+                vec![BusTypeRef::of::<Db>(), BusTypeRef::of::<Mailer>()]
+            }
+
+            async fn run(&self, n: i32, _: &AppResources, _: &mut Bus) -> Outcome<i32, String> {
+                Outcome::next(n)
+            }
+        }
+
+        let inner = Axon::<i32, i32, String, AppResources>::new("Inner").then(Notify);
+        let axon = Axon::<i32, i32, String, AppResources>::new("Signup")
+            .then(Load)
+            .then_axon(inner);
+        let required = axon.required_resources();
+        assert_eq!(
+            required[std::any::type_name::<Db>()],
+            vec!["Load".to_string(), "Notify".to_string()]
+        );
+
+        let mut bus = Bus::new();
+        bus.provide(Db);
+        let err = axon.check_resources(&bus).unwrap_err();
+        assert_eq!(err.missing.len(), 1);
+        assert_eq!(err.missing[0].resource, std::any::type_name::<Mailer>());
+        assert_eq!(err.missing[0].required_by, ["Notify"]);
+
+        bus.insert_named("backup", Mailer);
+        assert!(axon.check_resources(&bus).is_err());
+        bus.register_factory(|| async { Ok(Mailer) });
+        assert!(axon.check_resources(&bus).is_ok());
+        assert!(axon.check_resources(&bus.snapshot()).is_err());
+    }
+
+    #[tokio::test]
+    async fn output_contract_violation_faults_with_category() {
+        use crate::contract::{CONTRACT_VIOLATION, ContractEnforcement, OutputContract};
+        use ranvier_core::fault::FaultChain;
+
+        let axon = Axon::<i32, i32, String>::new("Contract")
+            .then(AddOneString)
+            .ensure_output(OutputContract::predicate("below_ten", |n: &i32| *n < 10));
+        assert_eq!(axon.schematic.nodes[1].metadata.contracts, ["below_ten"]);
+
+        let mut bus = Bus::new();
+        bus.insert(ContractEnforcement::Always);
+        assert!(matches!(
+            axon.execute(1, &(), &mut bus).await,
+            Outcome::Next(2)
+        ));
+
+        let mut bus = Bus::new();
+        bus.insert(ContractEnforcement::Always);
+        let Outcome::Fault(error) = axon.execute(9, &(), &mut bus).await else {
+            panic!("contract violation should fault");
+        };
+        assert!(error.starts_with(CONTRACT_VIOLATION));
+        let cause = bus
+            .read::<FaultChain>()
+            .and_then(FaultChain::latest)
+            .expect("fault cause recorded");
+        assert_eq!(cause.category.as_deref(), Some(CONTRACT_VIOLATION));
+        assert_eq!(cause.node_label, "AddOneString");
+
+        let mut bus = Bus::new();
+        bus.insert(ContractEnforcement::Off);
+        assert!(matches!(
+            axon.execute(9, &(), &mut bus).await,
+            Outcome::Next(10)
+        ));
+    }
+
+    #[tokio::test]
+    async fn journal_keeps_only_executions_that_never_finished() {
+        use crate::journal::{ExecutionJournal, InMemoryJournal, JournalEntry, JournalHandle};
+
+        let journal = InMemoryJournal::new().with_inputs();
+        journal
+            .record_start(JournalEntry {
+                trace_id: "crashed".to_string(),
+                circuit: "Journaled".to_string(),
+                input_digest: "fnv1a64:0".to_string(),
+                started_at_ms: 1,
+                input: Some(serde_json::json!(41)),
+            })
+            .await
+            .unwrap();
+
+        let axon = Axon::<i32, i32, String>::new("Journaled").then(AddOneString);
+        let mut bus = Bus::new();
+        bus.insert(JournalHandle::from_journal(journal.clone()));
+        assert!(matches!(
+            axon.execute(1, &(), &mut bus).await,
+            Outcome::Next(2)
+        ));
+
+        let unfinished = journal.unfinished().await.unwrap();
+        assert_eq!(unfinished.len(), 1);
+        assert_eq!(unfinished[0].trace_id, "crashed");
+        assert_eq!(unfinished[0].input_as::<i32>().unwrap(), 41);
+    }
+
+    #[test]
+    fn emitted_event_schemas_are_collected_from_the_schematic() {
+        use ranvier_core::event_schema::{EventSchemas, check_event_compatibility};
+
+        let schema =
+            serde_json::json!({"type": "object", "properties": {"n": {"type": "integer"}}});
+        let axon = Axon::<i32, i32, String>::new("Events")
+            .then(AddOneString)
+            .with_emitted_event_schema_value("counted", schema.clone());
+
+        let schemas = EventSchemas::from_schematic(&axon.schematic);
+        assert_eq!(schemas.0.get("counted"), Some(&schema));
+        assert!(
+            check_event_compatibility(&schemas, &schemas)
+                .changes
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn jump_targets_are_validated_and_resolve_at_run_time() {
+        let axon = Axon::<i32, i32, String>::new("Checkout")
+            .then_fn("Charge", |n: i32, bus: &mut Bus| {
+                let retry = bus
+                    .read::<ranvier_core::schematic::Schematic>()
+                    .and_then(|schematic| schematic.jump_target("Reserve"));
+                match retry {
+                    Some(target) if n < 0 => Outcome::jump(target, None),
+                    _ => Outcome::next(n),
+                }
+            })
+            .jump_target("Reserve")
+            .then_fn("Reserve", |n: i32, _bus| Outcome::next(n));
+        assert!(axon.validate().is_ok());
+
+        let reserve_id = axon.schematic.nodes[2].id.clone();
+        match axon.execute(-1, &(), &mut Bus::new()).await {
+            Outcome::Jump(target, _) => assert_eq!(target.to_string(), reserve_id),
+            other => panic!("expected a jump, got {other:?}"),
+        }
+
+        let broken = Axon::<i32, i32, String>::new("Checkout")
+            .then(AddOneString)
+            .jump_target("Refund");
+        let report = broken.validate().unwrap_err();
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(
+            report.diagnostics[0].node_label.as_deref(),
+            Some("AddOneString")
+        );
+        assert_eq!(
+            report.diagnostics[0].kind,
+            ranvier_core::validation::DiagnosticKind::UnresolvedJumpTarget {
+                target: "Refund".into()
+            }
+        );
+    }
 }
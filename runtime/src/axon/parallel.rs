@@ -1,7 +1,7 @@
 use ranvier_core::bus::Bus;
 use ranvier_core::outcome::Outcome;
-use ranvier_core::schematic::{Edge, EdgeType, Node, NodeKind, SourceLocation};
-use ranvier_core::timeline::{Timeline, TimelineEvent};
+use ranvier_core::schematic::{Edge, EdgeType, Node, NodeKind, SourceLocation, stable_node_id};
+use ranvier_core::timeline::{Timeline, TimelineEvent, Timestamp};
 use ranvier_core::transition::Transition;
 use serde::{Serialize, de::DeserializeOwned};
 use std::panic::Location;
@@ -12,7 +12,7 @@ use crate::persistence::PersistenceHandle;
 
 use super::*;
 use super::{
-    bus_capability_schema_from_policy, now_ms, outcome_kind_name, outcome_type_name,
+    bus_capability_schema_from_policy, outcome_kind_name, outcome_type_name,
     persist_execution_event, persistence_trace_id, type_name_of,
 };
 
@@ -21,14 +21,14 @@ struct ParallelBranchResult<Out, E> {
     node_id: String,
     label: String,
     outcome: Outcome<Out, E>,
-    entered_at_ms: u64,
-    exited_at_ms: u64,
+    entered_at: Timestamp,
+    exited_at: Timestamp,
     duration_ms: u64,
 }
 
-type KeyedTimelineEvent = (u64, u8, usize, TimelineEvent);
+pub(super) type KeyedTimelineEvent = (Timestamp, u8, usize, TimelineEvent);
 
-fn sort_parallel_branch_events(events: &mut [KeyedTimelineEvent]) {
+pub(super) fn sort_parallel_branch_events(events: &mut [KeyedTimelineEvent]) {
     events.sort_by_key(|(timestamp, phase, index, _)| (*timestamp, *phase, *index));
 }
 
@@ -118,11 +118,21 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
         } = self;
 
         // ── Schematic: FanOut node ─────────────────────────────────
-        let fanout_id = uuid::Uuid::new_v4().to_string();
-        let fanin_id = uuid::Uuid::new_v4().to_string();
+        let fanout_id = schematic.next_node_id("FanOut");
+        let fanin_id = stable_node_id(
+            &schematic.name,
+            "FanIn",
+            schematic.nodes.len() + 1 + transitions.len(),
+        );
 
         let last_node_id = schematic
             .nodes
@@ -145,7 +155,7 @@ where
             resource_type: type_name_of::<Res>(),
             metadata: Default::default(),
             bus_capability: None,
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+            source_location: Some(SourceLocation::from(caller)),
             position: None,
             compensation_node_id: None,
             input_schema: None,
@@ -165,7 +175,7 @@ where
         // ── Schematic: one Atom node per parallel branch ───────────
         let mut branch_node_ids = Vec::with_capacity(transitions.len());
         for (i, trans) in transitions.iter().enumerate() {
-            let branch_id = uuid::Uuid::new_v4().to_string();
+            let branch_id = transition_node_id(trans.as_ref(), &schematic, schematic.nodes.len());
             let branch_node = Node {
                 id: branch_id.clone(),
                 kind: NodeKind::Atom,
@@ -174,9 +184,15 @@ where
                 input_type: type_name_of::<Out>(),
                 output_type: type_name_of::<Out>(),
                 resource_type: type_name_of::<Res>(),
-                metadata: Default::default(),
+                metadata: StepMetadata {
+                    annotations: trans.annotations(),
+                    ..with_required_resources(
+                        node_metadata(trans.description(), None),
+                        trans.required_resources(),
+                    )
+                },
                 bus_capability: bus_capability_schema_from_policy(trans.bus_access_policy()),
-                source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+                source_location: Some(transition_source(trans.as_ref(), caller)),
                 position: None,
                 compensation_node_id: None,
                 input_schema: trans.input_schema(),
@@ -208,7 +224,7 @@ where
             resource_type: type_name_of::<Res>(),
             metadata: Default::default(),
             bus_capability: None,
-            source_location: Some(SourceLocation::new(caller.file(), caller.line())),
+            source_location: Some(SourceLocation::from(caller)),
             position: None,
             compensation_node_id: None,
             input_schema: None,
@@ -252,12 +268,13 @@ where
 
                     // Timeline: FanOut enter
                     let fanout_started = Instant::now();
-                    let fanout_enter_ts = now_ms();
+                    let fanout_enter_ts = Timestamp::now();
                     if let Some(timeline) = bus.read_mut::<Timeline>() {
                         timeline.push(TimelineEvent::NodeEnter {
                             node_id: fanout_id.clone(),
                             node_label: "FanOut".to_string(),
                             timestamp: fanout_enter_ts,
+                            trace: None,
                         });
                     }
 
@@ -266,6 +283,7 @@ where
                     // without &mut Bus aliasing. Only the explicit policy can
                     // add read-only inherited context.
                     let cancellation_token = bus.cancellation_token().cloned();
+                    let dry_run = DryRunPlan::from_bus(bus);
                     let futs: Vec<_> = branches
                         .iter()
                         .enumerate()
@@ -280,6 +298,7 @@ where
                             if let Some(token) = cancellation_token.clone() {
                                 branch_bus.set_cancellation_token(token);
                             }
+                            let dry_run = dry_run.clone();
 
                             async move {
                                 let mut branch_bus = branch_bus;
@@ -287,11 +306,20 @@ where
                                 let bus_policy = trans.bus_access_policy();
 
                                 branch_bus.set_access_policy(label.clone(), bus_policy);
-                                let entered_at_ms = now_ms();
+                                let entered_at = Timestamp::now();
                                 let started = Instant::now();
-                                let result = trans.run(branch_state, res, &mut branch_bus).await;
+                                let result = match dry_run {
+                                    Some(plan) => plan.step(
+                                        trans.as_ref(),
+                                        &branch_state,
+                                        res,
+                                        &branch_bus,
+                                        Some(&branch_node_id),
+                                    ),
+                                    None => trans.run(branch_state, res, &mut branch_bus).await,
+                                };
                                 let duration_ms = started.elapsed().as_millis() as u64;
-                                let exited_at_ms = now_ms().max(entered_at_ms);
+                                let exited_at = Timestamp::now();
                                 branch_bus.clear_access_policy();
 
                                 ParallelBranchResult {
@@ -299,8 +327,8 @@ where
                                     node_id: branch_node_id,
                                     label,
                                     outcome: result,
-                                    entered_at_ms,
-                                    exited_at_ms,
+                                    entered_at,
+                                    exited_at,
                                     duration_ms,
                                 }
                             }
@@ -318,24 +346,26 @@ where
                         let mut branch_events = Vec::with_capacity(results.len() * 2);
                         for result in &results {
                             branch_events.push((
-                                result.entered_at_ms,
+                                result.entered_at,
                                 0_u8,
                                 result.index,
                                 TimelineEvent::NodeEnter {
                                     node_id: result.node_id.clone(),
                                     node_label: result.label.clone(),
-                                    timestamp: result.entered_at_ms,
+                                    timestamp: result.entered_at,
+                                    trace: None,
                                 },
                             ));
                             branch_events.push((
-                                result.exited_at_ms,
+                                result.exited_at,
                                 1_u8,
                                 result.index,
                                 TimelineEvent::NodeExit {
                                     node_id: result.node_id.clone(),
                                     outcome_type: outcome_type_name(&result.outcome),
                                     duration_ms: result.duration_ms,
-                                    timestamp: result.exited_at_ms,
+                                    timestamp: result.exited_at,
+                                    trace: None,
                                 },
                             ));
                         }
@@ -347,24 +377,26 @@ where
 
                     // Timeline: FanOut exit
                     if let Some(timeline) = bus.read_mut::<Timeline>() {
-                        let fanout_exit_ts = now_ms().max(fanout_enter_ts);
+                        let fanout_exit_ts = Timestamp::now();
                         timeline.push(TimelineEvent::NodeExit {
                             node_id: fanout_id.clone(),
                             outcome_type: "Next".to_string(),
                             duration_ms: fanout_started.elapsed().as_millis() as u64,
                             timestamp: fanout_exit_ts,
+                            trace: None,
                         });
                     }
 
                     // Timeline: FanIn starts before deterministic strategy
                     // combination and exits after the result is selected.
                     let fanin_started = Instant::now();
-                    let fanin_enter_ts = now_ms();
+                    let fanin_enter_ts = Timestamp::now();
                     if let Some(timeline) = bus.read_mut::<Timeline>() {
                         timeline.push(TimelineEvent::NodeEnter {
                             node_id: fanin_id.clone(),
                             node_label: "FanIn".to_string(),
                             timestamp: fanin_enter_ts,
+                            trace: None,
                         });
                     }
 
@@ -434,7 +466,8 @@ where
                             node_id: fanin_id.clone(),
                             outcome_type: outcome_type_name(&combined),
                             duration_ms: fanin_started.elapsed().as_millis() as u64,
-                            timestamp: now_ms().max(fanin_enter_ts),
+                            timestamp: Timestamp::now().max(fanin_enter_ts),
+                            trace: None,
                         });
                     }
 
@@ -481,6 +514,12 @@ where
             dynamic_saga_policy,
             saga_compensation_registry,
             iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
         }
     }
 }
@@ -488,11 +527,11 @@ where
 #[cfg(test)]
 mod tests {
     use super::{KeyedTimelineEvent, sort_parallel_branch_events};
-    use ranvier_core::timeline::TimelineEvent;
+    use ranvier_core::timeline::{TimelineEvent, Timestamp};
 
     #[test]
     fn parallel_timeline_ties_order_enter_before_exit_then_by_declaration() {
-        let timestamp = 42;
+        let timestamp = Timestamp::from_millis(42);
         let mut events: Vec<KeyedTimelineEvent> = vec![
             (
                 timestamp,
@@ -503,6 +542,7 @@ mod tests {
                     outcome_type: "Next".to_string(),
                     duration_ms: 0,
                     timestamp,
+                    trace: None,
                 },
             ),
             (
@@ -513,6 +553,7 @@ mod tests {
                     node_id: "branch-1".to_string(),
                     node_label: "branch-1".to_string(),
                     timestamp,
+                    trace: None,
                 },
             ),
             (
@@ -524,6 +565,7 @@ mod tests {
                     outcome_type: "Next".to_string(),
                     duration_ms: 0,
                     timestamp,
+                    trace: None,
                 },
             ),
             (
@@ -534,6 +576,7 @@ mod tests {
                     node_id: "branch-0".to_string(),
                     node_label: "branch-0".to_string(),
                     timestamp,
+                    trace: None,
                 },
             ),
         ];
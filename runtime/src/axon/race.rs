@@ -0,0 +1,319 @@
+use futures_util::future::{Either, select};
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::schematic::{Edge, EdgeType, Node, NodeKind, SourceLocation, stable_node_id};
+use ranvier_core::timeline::{Timeline, TimelineEvent, Timestamp};
+use ranvier_core::transition::Transition;
+use serde::{Serialize, de::DeserializeOwned};
+use std::panic::Location;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::*;
+use super::{bus_capability_schema_from_policy, outcome_type_name, type_name_of};
+
+struct RaceResult<Next, E> {
+    index: usize,
+    outcome: Outcome<Next, E>,
+    entered_at: Timestamp,
+    exited_at: Timestamp,
+    duration_ms: u64,
+}
+
+async fn run_contender<T, Out, Next, E, Res>(
+    index: usize,
+    transition: &T,
+    input: Out,
+    res: &Res,
+    mut bus: Bus,
+) -> RaceResult<Next, E>
+where
+    T: Transition<Out, Next, Resources = Res, Error = E>,
+    Out: Send + 'static,
+    Next: Send + 'static,
+{
+    let dry_run = DryRunPlan::from_bus(&bus);
+    bus.set_access_policy(transition.label(), transition.bus_access_policy());
+    let entered_at = Timestamp::now();
+    let started = Instant::now();
+    let outcome = match dry_run {
+        Some(plan) => plan.step(transition, &input, res, &bus, None),
+        None => transition.run(input, res, &mut bus).await,
+    };
+    RaceResult {
+        index,
+        outcome,
+        entered_at,
+        exited_at: Timestamp::now(),
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+impl<In, Out, E, Res> Axon<In, Out, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Run `primary` and `hedge` concurrently on the current value and
+    /// continue with whichever returns `Next` first.
+    ///
+    /// The slower transition is cancelled (its future is dropped) as soon as
+    /// the other one succeeds. A contender that finishes with anything other
+    /// than `Next` does not win the race; if neither succeeds, the primary's
+    /// outcome is returned.
+    ///
+    /// Both contenders get a Bus forked with
+    /// [`Bus::fork_for_parallel`], so they see values inserted with
+    /// [`Bus::insert_shared`] and their own writes are discarded.
+    ///
+    /// In the Schematic the race is a `Race` fan-out and a `RaceJoin` fan-in
+    /// around the two contenders, connected by `Parallel` edges labelled
+    /// `Race primary` and `Race hedge`.
+    ///
+    /// ```rust,ignore
+    /// let axon = Axon::new("Profile")
+    ///     .then(ParseRequest)
+    ///     .race(ReadFromPrimary, ReadFromReplica)
+    ///     .then(Render);
+    /// ```
+    #[track_caller]
+    pub fn race<Next, P, H>(self, primary: P, hedge: H) -> Axon<In, Next, E, Res>
+    where
+        Out: Clone,
+        Next: Send + Sync + Serialize + DeserializeOwned + 'static,
+        P: Transition<Out, Next, Resources = Res, Error = E> + Clone + Send + Sync + 'static,
+        H: Transition<Out, Next, Resources = Res, Error = E> + Clone + Send + Sync + 'static,
+    {
+        let caller = Location::caller();
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = self;
+
+        let race_id = schematic.next_node_id("Race");
+        let join_id = stable_node_id(&schematic.name, "RaceJoin", schematic.nodes.len() + 3);
+        let plain = |id: &str, kind, label: &str, input_type, output_type| Node {
+            id: id.to_string(),
+            kind,
+            label: label.to_string(),
+            description: Some("Race group: the first Next wins".to_string()),
+            input_type,
+            output_type,
+            resource_type: type_name_of::<Res>(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: Some(SourceLocation::from(caller)),
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        };
+
+        if let Some(last) = schematic.nodes.last() {
+            schematic.edges.push(Edge {
+                from: last.id.clone(),
+                to: race_id.clone(),
+                kind: EdgeType::Linear,
+                label: Some("Next".to_string()),
+            });
+        }
+        schematic.nodes.push(plain(
+            &race_id,
+            NodeKind::FanOut,
+            "Race",
+            type_name_of::<Out>(),
+            type_name_of::<Out>(),
+        ));
+
+        let contenders = [
+            ("primary", primary.label(), primary.description()),
+            ("hedge", hedge.label(), hedge.description()),
+        ];
+        let bus_policies = [primary.bus_access_policy(), hedge.bus_access_policy()];
+        let input_schemas = [primary.input_schema(), hedge.input_schema()];
+        let node_ids = [primary.node_id(), hedge.node_id()];
+        let annotations = [primary.annotations(), hedge.annotations()];
+        let mut contender_ids = Vec::with_capacity(2);
+        for (((((lane, label, description), bus_policy), input_schema), node_id), annotations) in
+            contenders
+                .into_iter()
+                .zip(bus_policies)
+                .zip(input_schemas)
+                .zip(node_ids)
+                .zip(annotations)
+        {
+            let id = node_id.unwrap_or_else(|| schematic.next_node_id(&label));
+            let mut node = plain(
+                &id,
+                NodeKind::Atom,
+                &label,
+                type_name_of::<Out>(),
+                type_name_of::<Next>(),
+            );
+            node.metadata = node_metadata(description.clone(), None);
+            node.metadata.annotations = annotations;
+            node.description = description;
+            node.bus_capability = bus_capability_schema_from_policy(bus_policy);
+            node.input_schema = input_schema;
+            schematic.nodes.push(node);
+            schematic.edges.push(Edge {
+                from: race_id.clone(),
+                to: id.clone(),
+                kind: EdgeType::Parallel,
+                label: Some(format!("Race {lane}")),
+            });
+            schematic.edges.push(Edge {
+                from: id.clone(),
+                to: join_id.clone(),
+                kind: EdgeType::Parallel,
+                label: Some("First Next".to_string()),
+            });
+            contender_ids.push((id, label));
+        }
+        schematic.nodes.push(plain(
+            &join_id,
+            NodeKind::FanIn,
+            "RaceJoin",
+            type_name_of::<Next>(),
+            type_name_of::<Next>(),
+        ));
+
+        let contender_ids = Arc::new(contender_ids);
+        let next_executor: Executor<In, Next, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Next, E>> {
+                let prev = prev_executor.clone();
+                let primary = primary.clone();
+                let hedge = hedge.clone();
+                let race_id = race_id.clone();
+                let contender_ids = contender_ids.clone();
+
+                Box::pin(async move {
+                    let state = match prev(input, res, bus).await {
+                        Outcome::Next(state) => state,
+                        other => return other.map(|_| unreachable!()),
+                    };
+
+                    let race_started = Instant::now();
+                    if let Some(timeline) = bus.read_mut::<Timeline>() {
+                        timeline.push(TimelineEvent::NodeEnter {
+                            node_id: race_id.clone(),
+                            node_label: "Race".to_string(),
+                            timestamp: Timestamp::now(),
+                            trace: None,
+                        });
+                    }
+
+                    let fork = || {
+                        let mut fork = bus.fork_for_parallel();
+                        if let Some(token) = bus.cancellation_token().cloned() {
+                            fork.set_cancellation_token(token);
+                        }
+                        fork
+                    };
+                    let primary_run =
+                        Box::pin(run_contender(0, &primary, state.clone(), res, fork()));
+                    let hedge_run = Box::pin(run_contender(1, &hedge, state, res, fork()));
+
+                    let (first, other) = match select(primary_run, hedge_run).await {
+                        Either::Left((first, hedge_run)) => (first, Either::Right(hedge_run)),
+                        Either::Right((first, primary_run)) => (first, Either::Left(primary_run)),
+                    };
+                    let mut finished = vec![first];
+                    if matches!(finished[0].outcome, Outcome::Next(_)) {
+                        tracing::debug!(
+                            node_id = %race_id,
+                            winner = %contender_ids[finished[0].index].1,
+                            "Race won; cancelling the other contender"
+                        );
+                        drop(other);
+                    } else {
+                        finished.push(other.await);
+                    }
+
+                    if let Some(timeline) = bus.read_mut::<Timeline>() {
+                        finished.sort_by_key(|result| result.index);
+                        for result in &finished {
+                            let (node_id, node_label) = &contender_ids[result.index];
+                            timeline.push(TimelineEvent::NodeEnter {
+                                node_id: node_id.clone(),
+                                node_label: node_label.clone(),
+                                timestamp: result.entered_at,
+                                trace: None,
+                            });
+                            timeline.push(TimelineEvent::NodeExit {
+                                node_id: node_id.clone(),
+                                outcome_type: outcome_type_name(&result.outcome),
+                                duration_ms: result.duration_ms,
+                                timestamp: result.exited_at,
+                                trace: None,
+                            });
+                        }
+                    }
+
+                    let winner = finished
+                        .iter()
+                        .position(|result| matches!(result.outcome, Outcome::Next(_)))
+                        .unwrap_or_else(|| {
+                            finished
+                                .iter()
+                                .position(|result| result.index == 0)
+                                .unwrap_or_default()
+                        });
+                    let outcome = finished.swap_remove(winner).outcome;
+
+                    if let Some(timeline) = bus.read_mut::<Timeline>() {
+                        timeline.push(TimelineEvent::NodeExit {
+                            node_id: race_id.clone(),
+                            outcome_type: outcome_type_name(&outcome),
+                            duration_ms: race_started.elapsed().as_millis() as u64,
+                            timestamp: Timestamp::now(),
+                            trace: None,
+                        });
+                    }
+                    outcome
+                })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::schematic::{
+    Edge, EdgeType, Node, NodeKind, Schematic, SourceLocation, stable_node_id,
+};
+use ranvier_core::transition::Transition;
+use serde::{Serialize, de::DeserializeOwned};
+use std::panic::Location;
+use std::sync::Arc;
+
+use super::branch::subgraph_node;
+use super::*;
+use super::{bus_capability_schema_from_policy, run_this_step, type_name_of};
+
+type FaultHandler<Out, E, Res> =
+    Arc<dyn for<'a> Fn(E, &'a Res, &'a mut Bus) -> BoxFuture<'a, Outcome<Out, E>> + Send + Sync>;
+
+pub(super) fn plain_node<Res>(
+    id: String,
+    kind: NodeKind,
+    label: &str,
+    input_type: String,
+    output_type: String,
+    caller: &Location<'_>,
+) -> Node {
+    Node {
+        id,
+        kind,
+        label: label.to_string(),
+        description: None,
+        input_type,
+        output_type,
+        resource_type: type_name_of::<Res>(),
+        metadata: Default::default(),
+        bus_capability: None,
+        source_location: Some(SourceLocation::from(caller)),
+        position: None,
+        compensation_node_id: None,
+        input_schema: None,
+        output_schema: None,
+        item_type: None,
+        terminal: None,
+    }
+}
+
+/// Wire `handler` to the Fault edge of the last node and add the join node
+/// that the Next path and the handler both continue through.
+fn attach_fault_handler<Out, Res>(
+    schematic: &mut Schematic,
+    handler: Node,
+    fault_label: String,
+    join_label: &str,
+    caller: &Location<'_>,
+) {
+    let guarded = schematic.nodes.last().map(|n| n.id.clone());
+    let join = plain_node::<Res>(
+        stable_node_id(&schematic.name, join_label, schematic.nodes.len() + 1),
+        NodeKind::Synapse,
+        join_label,
+        type_name_of::<Out>(),
+        type_name_of::<Out>(),
+        caller,
+    );
+    if let Some(guarded) = guarded {
+        schematic.edges.push(Edge {
+            from: guarded.clone(),
+            to: handler.id.clone(),
+            kind: EdgeType::Fault,
+            label: Some(fault_label),
+        });
+        schematic.edges.push(Edge {
+            from: guarded,
+            to: join.id.clone(),
+            kind: EdgeType::Linear,
+            label: Some("Next".to_string()),
+        });
+    }
+    schematic.edges.push(Edge {
+        from: handler.id.clone(),
+        to: join.id.clone(),
+        kind: EdgeType::Linear,
+        label: Some("Recovered".to_string()),
+    });
+    schematic.nodes.push(handler);
+    schematic.nodes.push(join);
+}
+
+impl<In, Out, E, Res> Axon<In, Out, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    /// Handle an `Outcome::Fault` from the chain so far inside the graph.
+    ///
+    /// `handler` sees the error and decides what happens next: return
+    /// `Outcome::Next` to resume, `Outcome::Fault` to re-raise (the same or a
+    /// different error), or any other variant to leave the chain as usual.
+    /// Outcomes other than `Fault` never reach the handler.
+    ///
+    /// The recovered fault stays in the Bus
+    /// [`FaultChain`](ranvier_core::fault::FaultChain), so a later fault
+    /// reports it as its underlying cause.
+    ///
+    /// ```rust,ignore
+    /// let axon = Axon::new("Profile")
+    ///     .then(LoadFromCache)
+    ///     .catch(|err, _bus| match err {
+    ///         AppError::CacheMiss => Outcome::next(Profile::anonymous()),
+    ///         other => Outcome::fault(other),
+    ///     })
+    ///     .then(Render);
+    /// ```
+    #[track_caller]
+    pub fn catch<F>(self, handler: F) -> Self
+    where
+        F: Fn(E, &mut Bus) -> Outcome<Out, E> + Send + Sync + 'static,
+    {
+        let caller = Location::caller();
+        let handler = Arc::new(handler);
+        let node = plain_node::<Res>(
+            self.schematic.next_node_id("Catch"),
+            NodeKind::Atom,
+            "Catch",
+            type_name_of::<E>(),
+            type_name_of::<Out>(),
+            caller,
+        );
+        self.with_fault_handler(
+            node,
+            caller,
+            Arc::new(move |error: E, _res: &Res, bus: &mut Bus| {
+                let handler = handler.clone();
+                Box::pin(async move { handler(error, bus) })
+            }),
+        )
+    }
+
+    /// Route an `Outcome::Fault` from the chain so far into `recovery`,
+    /// which receives the error as its input.
+    ///
+    /// Whatever `recovery` returns replaces the fault: `Next` resumes the
+    /// chain, `Fault` re-raises. In the Schematic the recovery chain is a
+    /// `Subgraph` reached through a `Fault` edge.
+    ///
+    /// ```rust,ignore
+    /// let fallback = Axon::<PaymentError, Receipt, PaymentError>::new("Fallback")
+    ///     .then(ChargeBackupProvider);
+    /// let axon = Axon::new("Checkout")
+    ///     .then(ChargePrimaryProvider)
+    ///     .recover(fallback)
+    ///     .then(SendReceipt);
+    /// ```
+    #[track_caller]
+    pub fn recover(self, recovery: Axon<E, Out, E, Res>) -> Self {
+        let caller = Location::caller();
+        let node = subgraph_node::<Res>(
+            self.schematic.next_node_id(&recovery.schematic.name),
+            recovery.schematic,
+            type_name_of::<E>(),
+            type_name_of::<Out>(),
+            caller,
+        );
+        let recovery = recovery.executor;
+        self.with_fault_handler(
+            node,
+            caller,
+            Arc::new(move |error: E, res: &Res, bus: &mut Bus| recovery(error, res, bus)),
+        )
+    }
+
+    /// Run `fallback` on the chain's original input when the chain so far
+    /// returns `Outcome::Fault`.
+    ///
+    /// The primary's error is dropped; whatever the fallback returns is the
+    /// result, so a second fault propagates as usual. To put a fallback
+    /// around one step in the middle of a longer chain, give the step its
+    /// own Axon and embed it with [`then_axon`](Self::then_axon). In the
+    /// Schematic the fallback is reached through a `Fault` edge labelled
+    /// `Fallback`.
+    ///
+    /// ```rust,ignore
+    /// let lookup = Axon::<UserId, UserId, AppError>::new("Lookup")
+    ///     .then(ReadFromCache)
+    ///     .or_else(ReadFromDatabase);
+    /// let axon = Axon::new("Profile").then_axon(lookup).then(Render);
+    /// ```
+    #[track_caller]
+    pub fn or_else<Fb>(self, fallback: Fb) -> Self
+    where
+        In: Clone,
+        Fb: Transition<In, Out, Resources = Res, Error = E> + Clone + Send + Sync + 'static,
+    {
+        let caller = Location::caller();
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = self;
+
+        let label = fallback.label();
+        let bus_policy = fallback.bus_access_policy();
+        let mut node = plain_node::<Res>(
+            transition_node_id(&fallback, &schematic, schematic.nodes.len()),
+            NodeKind::Atom,
+            &label,
+            type_name_of::<In>(),
+            type_name_of::<Out>(),
+            caller,
+        );
+        node.description = fallback.description();
+        node.metadata = node_metadata(fallback.description(), None);
+        node.metadata.annotations = fallback.annotations();
+        node.bus_capability = bus_capability_schema_from_policy(bus_policy.clone());
+        node.input_schema = fallback.input_schema();
+        let node_id = node.id.clone();
+        attach_fault_handler::<Out, Res>(
+            &mut schematic,
+            node,
+            "Fallback".to_string(),
+            "FallbackJoin",
+            caller,
+        );
+        let step_idx = schematic.nodes.len().saturating_sub(2) as u64;
+
+        let next_executor: Executor<In, Out, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Out, E>> {
+                let prev = prev_executor.clone();
+                let fallback = fallback.clone();
+                let node_id = node_id.clone();
+                let label = label.clone();
+                let bus_policy = bus_policy.clone();
+
+                Box::pin(async move {
+                    match prev(input.clone(), res, bus).await {
+                        Outcome::Fault(error) => {
+                            tracing::debug!(
+                                node_id = %node_id,
+                                fallback = %label,
+                                error = ?error,
+                                "Primary faulted; running fallback"
+                            );
+                            run_this_step::<In, Out, E, Res>(
+                                &fallback,
+                                input,
+                                res,
+                                bus,
+                                &node_id,
+                                &label,
+                                &bus_policy,
+                                step_idx,
+                            )
+                            .await
+                        }
+                        other => other,
+                    }
+                })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+
+    fn with_fault_handler(
+        self,
+        node: Node,
+        caller: &Location<'_>,
+        handler: FaultHandler<Out, E, Res>,
+    ) -> Self {
+        let Axon {
+            mut schematic,
+            executor: prev_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: _,
+        } = self;
+
+        attach_fault_handler::<Out, Res>(
+            &mut schematic,
+            node,
+            format!("Fault({})", type_name_of::<E>()),
+            "CatchJoin",
+            caller,
+        );
+
+        let next_executor: Executor<In, Out, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Out, E>> {
+                let prev = prev_executor.clone();
+                let handler = handler.clone();
+
+                Box::pin(async move {
+                    match prev(input, res, bus).await {
+                        Outcome::Fault(error) => handler(error, res, bus).await,
+                        other => other,
+                    }
+                })
+            },
+        );
+
+        Axon {
+            schematic,
+            executor: next_executor,
+            execution_mode,
+            persistence_store,
+            audit_sink,
+            dlq_sink,
+            dlq_policy,
+            dynamic_dlq_policy,
+            saga_policy,
+            dynamic_saga_policy,
+            saga_compensation_registry,
+            iam_handle,
+            capture_policy,
+            concurrency,
+            node_policies,
+            execution_hooks,
+            interceptors,
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Per-node concurrency limits.
+//!
+//! [`Bulkhead`] wraps a Transition with a semaphore so that at most
+//! `max_concurrent` calls run at once, across every in-flight execution of
+//! the Axon. Extra calls wait for a permit; with
+//! [`Bulkhead::max_wait`] they fault with [`BulkheadFull`] instead of
+//! queueing indefinitely:
+//!
+//! ```rust,ignore
+//! let axon = Axon::new("Orders")
+//!     .then(Bulkhead::new(InsertOrder, 16).max_wait(Duration::from_millis(250)))
+//!     .then(Notify);
+//! ```
+//!
+//! Clones share their permits, so one Bulkhead can guard several nodes that
+//! use the same downstream resource. Error types opt in with
+//! `From<BulkheadFull>`; `String` and
+//! [`RanvierError`](ranvier_core::error::RanvierError) already do.
+
+use async_trait::async_trait;
+use ranvier_core::bus::{Bus, BusAccessPolicy};
+use ranvier_core::outcome::Outcome;
+use ranvier_core::transition::{SideEffect, Transition};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Fault category recorded when a call times out waiting for a permit.
+pub const BULKHEAD_FULL: &str = "bulkhead_full";
+
+/// A call that did not get a permit within the bulkhead's `max_wait`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BulkheadFull {
+    pub node_label: String,
+    pub max_concurrent: usize,
+    pub waited_ms: u64,
+}
+
+impl BulkheadFull {
+    pub fn category(&self) -> &'static str {
+        BULKHEAD_FULL
+    }
+}
+
+impl std::fmt::Display for BulkheadFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{BULKHEAD_FULL}: '{}' already runs {} calls; gave up after {}ms",
+            self.node_label, self.max_concurrent, self.waited_ms
+        )
+    }
+}
+
+impl std::error::Error for BulkheadFull {}
+
+impl From<BulkheadFull> for String {
+    fn from(full: BulkheadFull) -> Self {
+        full.to_string()
+    }
+}
+
+impl From<BulkheadFull> for ranvier_core::error::RanvierError {
+    fn from(full: BulkheadFull) -> Self {
+        Self::internal(full.to_string())
+    }
+}
+
+/// A wrapper Transition that limits concurrent calls of the inner Transition.
+pub struct Bulkhead<T> {
+    inner: T,
+    max_concurrent: usize,
+    max_wait: Option<Duration>,
+    permits: Arc<Semaphore>,
+}
+
+impl<T: Clone> Clone for Bulkhead<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            max_concurrent: self.max_concurrent,
+            max_wait: self.max_wait,
+            permits: self.permits.clone(),
+        }
+    }
+}
+
+impl<T> Bulkhead<T> {
+    /// Allow at most `max_concurrent` (at least 1) calls at once; others wait.
+    pub fn new(inner: T, max_concurrent: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            inner,
+            max_concurrent,
+            max_wait: None,
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+        }
+    }
+
+    /// Fault with [`BulkheadFull`] when no permit is free within `max_wait`.
+    /// `Duration::ZERO` rejects immediately instead of queueing.
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+
+    /// Calls currently running.
+    pub fn in_flight(&self) -> usize {
+        self.max_concurrent - self.permits.available_permits()
+    }
+}
+
+#[async_trait]
+impl<T, From, To> Transition<From, To> for Bulkhead<T>
+where
+    T: Transition<From, To>,
+    T::Error: std::convert::From<BulkheadFull>,
+    From: Send + 'static,
+    To: Send + 'static,
+{
+    type Error = T::Error;
+    type Resources = T::Resources;
+
+    async fn run(
+        &self,
+        input: From,
+        resources: &Self::Resources,
+        bus: &mut Bus,
+    ) -> Outcome<To, Self::Error> {
+        let permits = self.permits.clone();
+        let acquired = match self.max_wait {
+            None => permits.acquire_owned().await.ok(),
+            Some(wait) if wait.is_zero() => permits.try_acquire_owned().ok(),
+            Some(wait) => tokio::time::timeout(wait, permits.acquire_owned())
+                .await
+                .ok()
+                .and_then(Result::ok),
+        };
+        let Some(_permit) = acquired else {
+            let label = self.inner.label();
+            tracing::warn!(
+                node = %label,
+                max_concurrent = self.max_concurrent,
+                "Bulkhead full; rejecting call"
+            );
+            return Outcome::Fault(
+                BulkheadFull {
+                    node_label: label,
+                    max_concurrent: self.max_concurrent,
+                    waited_ms: self.max_wait.unwrap_or_default().as_millis() as u64,
+                }
+                .into(),
+            );
+        };
+        self.inner.run(input, resources, bus).await
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.inner.description()
+    }
+
+    fn position(&self) -> Option<(f32, f32)> {
+        self.inner.position()
+    }
+
+    fn bus_access_policy(&self) -> Option<BusAccessPolicy> {
+        self.inner.bus_access_policy()
+    }
+
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        self.inner.input_schema()
+    }
+
+    fn describe(&self) -> Vec<SideEffect> {
+        self.inner.describe()
+    }
+
+    fn estimate(
+        &self,
+        state: &From,
+        resources: &Self::Resources,
+        bus: &Bus,
+    ) -> Option<Outcome<To, Self::Error>> {
+        self.inner.estimate(state, resources, bus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone, Default)]
+    struct SlowInsert {
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Transition<u32, u32> for SlowInsert {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, id: u32, _res: &(), _bus: &mut Bus) -> Outcome<u32, String> {
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Outcome::Next(id)
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_concurrent_calls_across_executions() {
+        let inner = SlowInsert::default();
+        let bulkhead = Bulkhead::new(inner.clone(), 2);
+        let runs: Vec<_> = (0..6)
+            .map(|id| {
+                let bulkhead = bulkhead.clone();
+                tokio::spawn(async move { bulkhead.run(id, &(), &mut Bus::new()).await })
+            })
+            .collect();
+        for run in runs {
+            assert!(matches!(run.await.unwrap(), Outcome::Next(_)));
+        }
+        assert_eq!(inner.peak.load(Ordering::SeqCst), 2);
+        assert_eq!(bulkhead.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn max_wait_rejects_when_no_permit_frees_up() {
+        let bulkhead = Bulkhead::new(SlowInsert::default(), 1).max_wait(Duration::from_millis(5));
+        let busy = {
+            let bulkhead = bulkhead.clone();
+            tokio::spawn(async move { bulkhead.run(1, &(), &mut Bus::new()).await })
+        };
+        tokio::time::sleep(Duration::from_millis(2)).await;
+
+        let outcome = bulkhead.run(2, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Fault(ref e) if e.starts_with(BULKHEAD_FULL)));
+        assert!(matches!(busy.await.unwrap(), Outcome::Next(1)));
+    }
+}
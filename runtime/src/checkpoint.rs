@@ -0,0 +1,517 @@
+//! Per-node checkpoints of intermediate state.
+//!
+//! With a [`CheckpointHandle`] on the Bus, the Axon executor writes the
+//! serialized output of every node that returns `Next`, together with its
+//! node id and step, to a [`CheckpointStore`]. After a crash the last
+//! checkpoint of a trace tells how far the execution got and holds the state
+//! to continue from; the full list shows how the state evolved node by node.
+//!
+//! ```rust,ignore
+//! let checkpoints = CheckpointHandle::from_store(FileCheckpointStore::open("/var/lib/app/checkpoints"))
+//!     .for_nodes(["ReserveStock", "Charge"]);
+//!
+//! bus.insert(PersistenceTraceId::new(order.id.to_string()));
+//! bus.insert(checkpoints.clone());
+//! axon.execute(order, &resources, &mut bus).await;
+//!
+//! if let Some(last) = checkpoints.store().latest(&order.id.to_string()).await? {
+//!     let charged: ChargedOrder = last.state_as()?;
+//! }
+//! ```
+//!
+//! Checkpoints are keyed by [`PersistenceTraceId`] when one is on the Bus and
+//! by the Bus id otherwise. [`FileCheckpointStore`] keeps one NDJSON file per
+//! trace; `SqliteCheckpointStore` (feature `checkpoint-sqlite`) keeps them in
+//! a single table.
+//!
+//! [`PersistenceTraceId`]: crate::persistence::PersistenceTraceId
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+
+/// Serialized state after one node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub trace_id: String,
+    pub circuit: String,
+    pub node_id: String,
+    pub node_label: String,
+    pub step: u64,
+    /// The node's `Next` output.
+    pub state: Value,
+    pub recorded_at_ms: u64,
+}
+
+impl Checkpoint {
+    /// Decode the checkpointed state, e.g. to feed it to the following node.
+    pub fn state_as<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_value(self.state.clone())?)
+    }
+}
+
+/// Storage for checkpoints, grouped by trace.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    async fn save(&self, checkpoint: Checkpoint) -> Result<()>;
+
+    /// Every checkpoint of a trace, in the order they were saved.
+    async fn list(&self, trace_id: &str) -> Result<Vec<Checkpoint>>;
+
+    /// Drop the checkpoints of a trace, e.g. once it completed.
+    async fn clear(&self, trace_id: &str) -> Result<()>;
+
+    async fn latest(&self, trace_id: &str) -> Result<Option<Checkpoint>> {
+        Ok(self.list(trace_id).await?.pop())
+    }
+}
+
+/// Bus-insertable checkpoint handle read by the executor.
+#[derive(Clone)]
+pub struct CheckpointHandle {
+    inner: Arc<dyn CheckpointStore>,
+    nodes: Option<Arc<HashSet<String>>>,
+}
+
+impl std::fmt::Debug for CheckpointHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CheckpointHandle")
+            .field("nodes", &self.nodes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CheckpointHandle {
+    pub fn from_store<S>(store: S) -> Self
+    where
+        S: CheckpointStore + 'static,
+    {
+        Self {
+            inner: Arc::new(store),
+            nodes: None,
+        }
+    }
+
+    pub fn from_arc(store: Arc<dyn CheckpointStore>) -> Self {
+        Self {
+            inner: store,
+            nodes: None,
+        }
+    }
+
+    /// Only checkpoint the nodes with these labels instead of every node.
+    pub fn for_nodes<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.nodes = Some(Arc::new(labels.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    pub fn store(&self) -> Arc<dyn CheckpointStore> {
+        self.inner.clone()
+    }
+
+    pub(crate) fn checkpoints(&self, node_label: &str) -> bool {
+        self.nodes
+            .as_ref()
+            .is_none_or(|nodes| nodes.contains(node_label))
+    }
+}
+
+/// In-memory checkpoint store for tests and single-process tooling.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryCheckpointStore {
+    traces: Arc<Mutex<HashMap<String, Vec<Checkpoint>>>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn save(&self, checkpoint: Checkpoint) -> Result<()> {
+        self.traces
+            .lock()
+            .map_err(|_| anyhow::anyhow!("checkpoint store lock poisoned"))?
+            .entry(checkpoint.trace_id.clone())
+            .or_default()
+            .push(checkpoint);
+        Ok(())
+    }
+
+    async fn list(&self, trace_id: &str) -> Result<Vec<Checkpoint>> {
+        Ok(self
+            .traces
+            .lock()
+            .map_err(|_| anyhow::anyhow!("checkpoint store lock poisoned"))?
+            .get(trace_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn clear(&self, trace_id: &str) -> Result<()> {
+        self.traces
+            .lock()
+            .map_err(|_| anyhow::anyhow!("checkpoint store lock poisoned"))?
+            .remove(trace_id);
+        Ok(())
+    }
+}
+
+/// One newline-delimited JSON file per trace, fsynced after every checkpoint.
+///
+/// A torn last line (the process died mid-write) is ignored on read.
+#[derive(Debug)]
+pub struct FileCheckpointStore {
+    dir: PathBuf,
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl FileCheckpointStore {
+    /// Store checkpoints under `dir`, which is created on the first save.
+    pub fn open(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn trace_path(&self, trace_id: &str) -> PathBuf {
+        let file: String = trace_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{file}.ndjson"))
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn save(&self, checkpoint: Checkpoint) -> Result<()> {
+        let mut line = serde_json::to_vec(&checkpoint)?;
+        line.push(b'\n');
+        let _guard = self.write_lock.lock().await;
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.trace_path(&checkpoint.trace_id))
+            .await?;
+        file.write_all(&line).await?;
+        file.sync_data().await?;
+        Ok(())
+    }
+
+    async fn list(&self, trace_id: &str) -> Result<Vec<Checkpoint>> {
+        let content = match tokio::fs::read_to_string(self.trace_path(trace_id)).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<Checkpoint>(line).ok())
+            .filter(|checkpoint| checkpoint.trace_id == trace_id)
+            .collect())
+    }
+
+    async fn clear(&self, trace_id: &str) -> Result<()> {
+        let _guard = self.write_lock.lock().await;
+        match tokio::fs::remove_file(self.trace_path(trace_id)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// SQLite-backed checkpoint store.
+#[cfg(feature = "checkpoint-sqlite")]
+#[derive(Debug, Clone)]
+pub struct SqliteCheckpointStore {
+    pool: sqlx::Pool<sqlx::Sqlite>,
+    table: String,
+}
+
+#[cfg(feature = "checkpoint-sqlite")]
+#[derive(sqlx::FromRow)]
+struct SqliteCheckpointRow {
+    trace_id: String,
+    circuit: String,
+    node_id: String,
+    node_label: String,
+    step: i64,
+    state: String,
+    recorded_at_ms: i64,
+}
+
+#[cfg(feature = "checkpoint-sqlite")]
+impl SqliteCheckpointStore {
+    /// Use the default table `ranvier_checkpoints`. Call `ensure_schema()`
+    /// once at startup to create it.
+    pub fn new(pool: sqlx::Pool<sqlx::Sqlite>) -> Self {
+        Self::with_table(pool, "ranvier_checkpoints")
+    }
+
+    pub fn with_table(pool: sqlx::Pool<sqlx::Sqlite>, table: impl Into<String>) -> Self {
+        Self {
+            pool,
+            table: table.into(),
+        }
+    }
+
+    /// Create the checkpoint table when absent.
+    pub async fn ensure_schema(&self) -> Result<()> {
+        let create = format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                trace_id TEXT NOT NULL,
+                circuit TEXT NOT NULL,
+                node_id TEXT NOT NULL,
+                node_label TEXT NOT NULL,
+                step INTEGER NOT NULL,
+                state TEXT NOT NULL,
+                recorded_at_ms INTEGER NOT NULL
+            )",
+            table = self.table
+        );
+        sqlx::query(&create).execute(&self.pool).await?;
+        let index = format!(
+            "CREATE INDEX IF NOT EXISTS {table}_trace ON {table} (trace_id, seq)",
+            table = self.table
+        );
+        sqlx::query(&index).execute(&self.pool).await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "checkpoint-sqlite")]
+#[async_trait]
+impl CheckpointStore for SqliteCheckpointStore {
+    async fn save(&self, checkpoint: Checkpoint) -> Result<()> {
+        let insert = format!(
+            "INSERT INTO {} (trace_id, circuit, node_id, node_label, step, state, recorded_at_ms)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            self.table
+        );
+        sqlx::query(&insert)
+            .bind(&checkpoint.trace_id)
+            .bind(&checkpoint.circuit)
+            .bind(&checkpoint.node_id)
+            .bind(&checkpoint.node_label)
+            .bind(checkpoint.step as i64)
+            .bind(serde_json::to_string(&checkpoint.state)?)
+            .bind(checkpoint.recorded_at_ms as i64)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list(&self, trace_id: &str) -> Result<Vec<Checkpoint>> {
+        let select = format!(
+            "SELECT trace_id, circuit, node_id, node_label, step, state, recorded_at_ms
+             FROM {} WHERE trace_id = ? ORDER BY seq",
+            self.table
+        );
+        let rows: Vec<SqliteCheckpointRow> = sqlx::query_as(&select)
+            .bind(trace_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(Checkpoint {
+                    trace_id: row.trace_id,
+                    circuit: row.circuit,
+                    node_id: row.node_id,
+                    node_label: row.node_label,
+                    step: row.step as u64,
+                    state: serde_json::from_str(&row.state)?,
+                    recorded_at_ms: row.recorded_at_ms as u64,
+                })
+            })
+            .collect()
+    }
+
+    async fn clear(&self, trace_id: &str) -> Result<()> {
+        let delete = format!("DELETE FROM {} WHERE trace_id = ?", self.table);
+        sqlx::query(&delete)
+            .bind(trace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(trace_id: &str, step: u64, state: Value) -> Checkpoint {
+        Checkpoint {
+            trace_id: trace_id.to_string(),
+            circuit: "Orders".to_string(),
+            node_id: format!("node-{step}"),
+            node_label: format!("Step{step}"),
+            step,
+            state,
+            recorded_at_ms: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn executor_checkpoints_next_outputs_of_selected_nodes() {
+        use crate::axon::Axon;
+        use crate::persistence::PersistenceTraceId;
+        use ranvier_core::{Bus, Outcome};
+
+        let axon = Axon::<i32, i32, String>::new("Orders")
+            .then_fn("Reserve", |n: i32, _bus| Outcome::next(n + 1))
+            .then_fn("Charge", |n: i32, _bus| Outcome::next(n * 10))
+            .then_fn("Ship", |_n: i32, _bus| {
+                Outcome::<i32, String>::fault("no courier".to_string())
+            });
+        let store = InMemoryCheckpointStore::new();
+        let mut bus = Bus::new();
+        bus.insert(PersistenceTraceId::new("order-7"));
+        bus.insert(
+            CheckpointHandle::from_store(store.clone()).for_nodes(["Reserve", "Charge", "Ship"]),
+        );
+
+        assert!(axon.execute(1, &(), &mut bus).await.is_fault());
+
+        let checkpoints = store.list("order-7").await.unwrap();
+        let labels: Vec<&str> = checkpoints.iter().map(|c| c.node_label.as_str()).collect();
+        assert_eq!(labels, vec!["Reserve", "Charge"]);
+        assert_eq!(checkpoints[1].circuit, "Orders");
+        let latest = store.latest("order-7").await.unwrap().unwrap();
+        assert_eq!(latest.state_as::<i32>().unwrap(), 20);
+
+        let mut bus = Bus::new();
+        bus.insert(PersistenceTraceId::new("order-8"));
+        bus.insert(CheckpointHandle::from_store(store.clone()).for_nodes(["Charge"]));
+        axon.execute(1, &(), &mut bus).await;
+        assert_eq!(store.list("order-8").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn executor_checkpoints_compensated_nodes() {
+        use crate::axon::Axon;
+        use crate::closure_transition::ClosureTransition;
+        use crate::persistence::PersistenceTraceId;
+        use ranvier_core::{Bus, Outcome};
+
+        let axon = Axon::<i32, i32, String>::new("Orders")
+            .then_compensated(
+                ClosureTransition::new("Charge", |n: i32, _bus: &mut Bus| {
+                    Outcome::<i32, String>::next(n * 10)
+                }),
+                ClosureTransition::new("Refund", |_n: i32, _bus: &mut Bus| {
+                    Outcome::<(), String>::next(())
+                }),
+            )
+            .then_fn("Ship", |_n: i32, _bus| {
+                Outcome::<i32, String>::fault("no courier".to_string())
+            });
+        let store = InMemoryCheckpointStore::new();
+        let mut bus = Bus::new();
+        bus.insert(PersistenceTraceId::new("order-9"));
+        bus.insert(CheckpointHandle::from_store(store.clone()).for_nodes(["Charge"]));
+
+        assert!(axon.execute(2, &(), &mut bus).await.is_fault());
+
+        let latest = store.latest("order-9").await.unwrap().unwrap();
+        assert_eq!(latest.node_label, "Charge");
+        assert_eq!(latest.state_as::<i32>().unwrap(), 20);
+    }
+
+    #[tokio::test]
+    async fn file_store_keeps_checkpoints_per_trace_and_skips_torn_lines() {
+        let dir =
+            std::env::temp_dir().join(format!("ranvier-checkpoints-{}", uuid::Uuid::new_v4()));
+        let store = FileCheckpointStore::open(&dir);
+        store
+            .save(checkpoint("order:1", 1, serde_json::json!(10)))
+            .await
+            .unwrap();
+        store
+            .save(checkpoint("order:1", 2, serde_json::json!(20)))
+            .await
+            .unwrap();
+        store
+            .save(checkpoint("order:2", 1, serde_json::json!(99)))
+            .await
+            .unwrap();
+
+        let path = store.trace_path("order:1");
+        let mut torn = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap();
+        torn.write_all(b"{\"trace_id\":\"order:1\",\"ci")
+            .await
+            .unwrap();
+
+        let steps: Vec<u64> = store
+            .list("order:1")
+            .await
+            .unwrap()
+            .iter()
+            .map(|c| c.step)
+            .collect();
+        assert_eq!(steps, vec![1, 2]);
+        let latest = store.latest("order:1").await.unwrap().unwrap();
+        assert_eq!(latest.state_as::<i32>().unwrap(), 20);
+
+        store.clear("order:1").await.unwrap();
+        assert!(store.list("order:1").await.unwrap().is_empty());
+        assert_eq!(store.list("order:2").await.unwrap().len(), 1);
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[cfg(feature = "checkpoint-sqlite")]
+    #[tokio::test]
+    async fn sqlite_store_round_trips_checkpoints_in_order() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let store = SqliteCheckpointStore::new(pool);
+        store.ensure_schema().await.unwrap();
+        store
+            .save(checkpoint("t1", 1, serde_json::json!({"n": 1})))
+            .await
+            .unwrap();
+        store
+            .save(checkpoint("t1", 2, serde_json::json!({"n": 2})))
+            .await
+            .unwrap();
+
+        let listed = store.list("t1").await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[1], checkpoint("t1", 2, serde_json::json!({"n": 2})));
+        store.clear("t1").await.unwrap();
+        assert!(store.latest("t1").await.unwrap().is_none());
+    }
+}
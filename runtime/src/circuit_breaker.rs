@@ -0,0 +1,448 @@
+//! Circuit breaking for failing transitions.
+//!
+//! [`CircuitBreaker`] wraps a Transition and counts its faults over a window
+//! of recent calls. Once the failure rate reaches the threshold the breaker
+//! opens and calls fault immediately with [`CircuitOpen`], without running
+//! the inner Transition. After the cool-down one trial call is let through
+//! (half-open): success closes the breaker, a fault opens it again.
+//!
+//! ```rust,ignore
+//! let payments = CircuitBreaker::new(ChargeCard)
+//!     .failure_threshold(0.5)
+//!     .minimum_calls(10)
+//!     .cool_down(Duration::from_secs(30));
+//! let inspector = Inspector::new(schematic, 9090)
+//!     .with_circuit_breaker_reader(Arc::new(payments.clone()));
+//! let axon = Axon::new("Checkout").then(payments);
+//! ```
+//!
+//! Clones share their state, so a clone handed to the Inspector reports the
+//! breaker in use by the circuit. Error types opt in with
+//! `From<CircuitOpen>`; `String` and
+//! [`RanvierError`](ranvier_core::error::RanvierError) already do.
+
+use async_trait::async_trait;
+use ranvier_core::bus::{Bus, BusAccessPolicy};
+use ranvier_core::circuit_breaker::{CircuitBreakerState, CircuitBreakerStateReader, CircuitState};
+use ranvier_core::outcome::Outcome;
+use ranvier_core::timeline::Timestamp;
+use ranvier_core::transition::{SideEffect, Transition};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Fault category recorded when an open breaker rejects a call.
+pub const CIRCUIT_OPEN: &str = "circuit_open";
+
+/// A call rejected because the node's breaker is open.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CircuitOpen {
+    pub node_label: String,
+    /// Time left until the breaker lets a trial call through.
+    pub retry_after_ms: u64,
+}
+
+impl CircuitOpen {
+    pub fn category(&self) -> &'static str {
+        CIRCUIT_OPEN
+    }
+}
+
+impl std::fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{CIRCUIT_OPEN}: '{}' is unavailable, retry in {}ms",
+            self.node_label, self.retry_after_ms
+        )
+    }
+}
+
+impl std::error::Error for CircuitOpen {}
+
+impl From<CircuitOpen> for String {
+    fn from(open: CircuitOpen) -> Self {
+        open.to_string()
+    }
+}
+
+impl From<CircuitOpen> for ranvier_core::error::RanvierError {
+    fn from(open: CircuitOpen) -> Self {
+        Self::internal(open.to_string())
+    }
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    /// Recent call results, `true` for a fault; at most `window` entries.
+    results: VecDeque<bool>,
+    opened_at: Option<(Instant, Timestamp)>,
+    trial_in_flight: bool,
+    rejected: u64,
+    /// Label of the inner Transition, known once it has been called.
+    node: Option<String>,
+}
+
+/// A wrapper Transition that stops calling the inner Transition while it is
+/// failing.
+pub struct CircuitBreaker<T> {
+    inner: T,
+    failure_threshold: f64,
+    minimum_calls: u32,
+    window: u32,
+    cool_down: Duration,
+    state: Arc<Mutex<BreakerState>>,
+}
+
+impl<T: Clone> Clone for CircuitBreaker<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            failure_threshold: self.failure_threshold,
+            minimum_calls: self.minimum_calls,
+            window: self.window,
+            cool_down: self.cool_down,
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// Puts the breaker back to Open if a half-open trial is dropped before it
+/// finishes, so the next call can make a new trial.
+struct TrialGuard<'a> {
+    state: Option<&'a Mutex<BreakerState>>,
+}
+
+impl Drop for TrialGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(state) = self.state {
+            let mut state = state
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if state.state == CircuitState::HalfOpen && state.trial_in_flight {
+                state.trial_in_flight = false;
+                state.state = CircuitState::Open;
+            }
+        }
+    }
+}
+
+/// Whether a call may run, and if so whether it is the half-open trial.
+enum Admission {
+    Run { trial: bool },
+    Reject { retry_after: Duration },
+}
+
+impl<T> CircuitBreaker<T> {
+    /// Defaults: opens at a 50% failure rate over the last 20 calls, once at
+    /// least 5 calls were made, and cools down for 30 s.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            failure_threshold: 0.5,
+            minimum_calls: 5,
+            window: 20,
+            cool_down: Duration::from_secs(30),
+            state: Arc::new(Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                results: VecDeque::new(),
+                opened_at: None,
+                trial_in_flight: false,
+                rejected: 0,
+                node: None,
+            })),
+        }
+    }
+
+    /// Failure rate (`0.0..=1.0`) at which the breaker opens.
+    pub fn failure_threshold(mut self, rate: f64) -> Self {
+        self.failure_threshold = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Calls needed in the window before the failure rate is considered.
+    pub fn minimum_calls(mut self, calls: u32) -> Self {
+        self.minimum_calls = calls.max(1);
+        self
+    }
+
+    /// Number of most recent calls the failure rate is computed over.
+    pub fn window(mut self, calls: u32) -> Self {
+        self.window = calls.max(1);
+        self
+    }
+
+    /// How long the breaker stays open before a trial call.
+    pub fn cool_down(mut self, cool_down: Duration) -> Self {
+        self.cool_down = cool_down;
+        self
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn admit(&self, label: impl FnOnce() -> String) -> Admission {
+        let mut state = self.lock();
+        if state.node.is_none() {
+            state.node = Some(label());
+        }
+        match state.state {
+            CircuitState::Closed => Admission::Run { trial: false },
+            CircuitState::Open => {
+                let elapsed = state
+                    .opened_at
+                    .map(|(at, _)| at.elapsed())
+                    .unwrap_or(self.cool_down);
+                if elapsed >= self.cool_down {
+                    state.state = CircuitState::HalfOpen;
+                    state.trial_in_flight = true;
+                    Admission::Run { trial: true }
+                } else {
+                    state.rejected += 1;
+                    Admission::Reject {
+                        retry_after: self.cool_down - elapsed,
+                    }
+                }
+            }
+            CircuitState::HalfOpen if !state.trial_in_flight => {
+                state.trial_in_flight = true;
+                Admission::Run { trial: true }
+            }
+            CircuitState::HalfOpen => {
+                state.rejected += 1;
+                Admission::Reject {
+                    retry_after: Duration::ZERO,
+                }
+            }
+        }
+    }
+
+    fn record(&self, label: &str, trial: bool, faulted: bool) {
+        let mut state = self.lock();
+        if trial {
+            state.trial_in_flight = false;
+            if faulted {
+                tracing::warn!(node = %label, "Circuit breaker trial call faulted; reopening");
+                state.state = CircuitState::Open;
+                state.opened_at = Some((Instant::now(), Timestamp::now()));
+            } else {
+                tracing::info!(node = %label, "Circuit breaker closed");
+                state.state = CircuitState::Closed;
+                state.opened_at = None;
+                state.results.clear();
+            }
+            return;
+        }
+        if state.state != CircuitState::Closed {
+            // A call admitted before the breaker opened; it no longer counts.
+            return;
+        }
+
+        state.results.push_back(faulted);
+        while state.results.len() > self.window as usize {
+            state.results.pop_front();
+        }
+        let calls = state.results.len() as u32;
+        let failures = state.results.iter().filter(|faulted| **faulted).count() as u32;
+        if calls >= self.minimum_calls
+            && f64::from(failures) / f64::from(calls) >= self.failure_threshold
+        {
+            tracing::warn!(
+                node = %label,
+                calls,
+                failures,
+                cool_down_ms = self.cool_down.as_millis() as u64,
+                "Circuit breaker opened"
+            );
+            state.state = CircuitState::Open;
+            state.opened_at = Some((Instant::now(), Timestamp::now()));
+        }
+    }
+
+    /// Current state, as reported to the Inspector.
+    ///
+    /// The node is named after the inner Transition's type until its first
+    /// call, and after its label from then on.
+    pub fn snapshot(&self) -> CircuitBreakerState {
+        let state = self.lock();
+        let node = state.node.clone().unwrap_or_else(|| {
+            let full = std::any::type_name::<T>();
+            full.split("::").last().unwrap_or(full).to_string()
+        });
+        CircuitBreakerState {
+            node,
+            state: state.state,
+            calls: state.results.len() as u32,
+            failures: state.results.iter().filter(|faulted| **faulted).count() as u32,
+            failure_threshold: self.failure_threshold,
+            cool_down_ms: self.cool_down.as_millis() as u64,
+            opened_at: state.opened_at.map(|(_, at)| at),
+            rejected: state.rejected,
+        }
+    }
+}
+
+#[async_trait]
+impl<T, From, To> Transition<From, To> for CircuitBreaker<T>
+where
+    T: Transition<From, To>,
+    T::Error: std::convert::From<CircuitOpen>,
+    From: Send + 'static,
+    To: Send + 'static,
+{
+    type Error = T::Error;
+    type Resources = T::Resources;
+
+    async fn run(
+        &self,
+        input: From,
+        resources: &Self::Resources,
+        bus: &mut Bus,
+    ) -> Outcome<To, Self::Error> {
+        let trial = match self.admit(|| self.inner.label()) {
+            Admission::Run { trial } => trial,
+            Admission::Reject { retry_after } => {
+                return Outcome::Fault(
+                    CircuitOpen {
+                        node_label: self.inner.label(),
+                        retry_after_ms: retry_after.as_millis() as u64,
+                    }
+                    .into(),
+                );
+            }
+        };
+        let _guard = TrialGuard {
+            state: trial.then_some(&*self.state),
+        };
+        let outcome = self.inner.run(input, resources, bus).await;
+        self.record(
+            &self.inner.label(),
+            trial,
+            matches!(outcome, Outcome::Fault(_)),
+        );
+        outcome
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.inner.description()
+    }
+
+    fn position(&self) -> Option<(f32, f32)> {
+        self.inner.position()
+    }
+
+    fn bus_access_policy(&self) -> Option<BusAccessPolicy> {
+        self.inner.bus_access_policy()
+    }
+
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        self.inner.input_schema()
+    }
+
+    fn describe(&self) -> Vec<SideEffect> {
+        self.inner.describe()
+    }
+
+    fn estimate(
+        &self,
+        state: &From,
+        resources: &Self::Resources,
+        bus: &Bus,
+    ) -> Option<Outcome<To, Self::Error>> {
+        self.inner.estimate(state, resources, bus)
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync> CircuitBreakerStateReader for CircuitBreaker<T> {
+    async fn circuit_breaker_state(&self) -> CircuitBreakerState {
+        self.snapshot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Clone)]
+    struct ChargeCard {
+        calls: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl Transition<i32, i32> for ChargeCard {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, amount: i32, _res: &(), _bus: &mut Bus) -> Outcome<i32, String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if amount < 0 {
+                Outcome::Fault("declined".to_string())
+            } else {
+                Outcome::Next(amount)
+            }
+        }
+    }
+
+    fn breaker() -> (CircuitBreaker<ChargeCard>, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let breaker = CircuitBreaker::new(ChargeCard {
+            calls: calls.clone(),
+        })
+        .failure_threshold(0.5)
+        .minimum_calls(4)
+        .window(4)
+        .cool_down(Duration::from_millis(30));
+        (breaker, calls)
+    }
+
+    #[tokio::test]
+    async fn opens_at_the_threshold_and_fails_fast() {
+        let (breaker, calls) = breaker();
+        let mut bus = Bus::new();
+        for amount in [1, -1, 2, -1] {
+            let _ = breaker.run(amount, &(), &mut bus).await;
+        }
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+
+        let outcome = breaker.run(5, &(), &mut bus).await;
+        assert!(matches!(outcome, Outcome::Fault(ref e) if e.starts_with(CIRCUIT_OPEN)));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        let state = breaker.circuit_breaker_state().await;
+        assert_eq!(state.node, "ChargeCard");
+        assert_eq!((state.calls, state.failures, state.rejected), (4, 2, 1));
+        assert!(state.opened_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn half_open_trial_closes_or_reopens_the_breaker() {
+        let (breaker, calls) = breaker();
+        let mut bus = Bus::new();
+        for _ in 0..4 {
+            let _ = breaker.run(-1, &(), &mut bus).await;
+        }
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        let _ = breaker.run(-1, &(), &mut bus).await;
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        let outcome = breaker.run(7, &(), &mut bus).await;
+        assert!(matches!(outcome, Outcome::Next(7)));
+        let state = breaker.snapshot();
+        assert_eq!(state.state, CircuitState::Closed);
+        assert_eq!(state.calls, 0);
+    }
+}
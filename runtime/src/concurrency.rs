@@ -0,0 +1,427 @@
+//! Per-circuit concurrency quotas with weighted fair admission.
+//!
+//! A [`ConcurrencyScheduler`] is shared by every Axon that runs on the same
+//! process (HTTP routes, event consumers, background jobs). It bounds total
+//! in-flight executions and, per circuit, caps how many run at once. When
+//! capacity is saturated, freed slots go to the waiting circuit with the
+//! lowest `in_flight / weight` ratio, so a bursty background circuit cannot
+//! crowd out latency-sensitive API circuits.
+//!
+//! ```rust,ignore
+//! let scheduler = ConcurrencyScheduler::new(64)
+//!     .with_quota("checkout", CircuitQuota::new(48).weight(4))
+//!     .with_quota("reindex", CircuitQuota::new(8));
+//!
+//! let checkout = checkout_axon.with_concurrency(scheduler.clone());
+//! let reindex = reindex_axon.with_concurrency(scheduler.clone());
+//!
+//! println!("{:?}", scheduler.stats());
+//! ```
+//!
+//! Circuits are keyed by their Schematic name. A circuit that executes
+//! another Axon attached to the same scheduler holds two slots; size quotas
+//! accordingly.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+use tokio::sync::oneshot;
+
+/// Concurrency limit and scheduling weight for one circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitQuota {
+    /// Maximum executions of this circuit in flight at once.
+    pub max_concurrent: usize,
+    /// Relative share of contended capacity (default 1).
+    pub weight: u32,
+}
+
+impl CircuitQuota {
+    pub const fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent,
+            weight: 1,
+        }
+    }
+
+    pub const fn weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Quota usage for one circuit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitConcurrencyStats {
+    pub circuit: String,
+    pub max_concurrent: usize,
+    pub weight: u32,
+    pub in_flight: usize,
+    pub queued: usize,
+    /// Executions admitted since the scheduler was created.
+    pub admitted: u64,
+    /// Admissions that had to wait for a slot.
+    pub delayed: u64,
+    pub max_wait_ms: u64,
+}
+
+/// Snapshot of scheduler usage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConcurrencyStats {
+    pub capacity: usize,
+    pub in_flight: usize,
+    pub queued: usize,
+    /// Sorted by circuit name.
+    pub circuits: Vec<CircuitConcurrencyStats>,
+}
+
+struct Waiter {
+    enqueued_at: Instant,
+    notify: oneshot::Sender<()>,
+}
+
+struct CircuitState {
+    quota: CircuitQuota,
+    in_flight: usize,
+    waiters: VecDeque<Waiter>,
+    admitted: u64,
+    delayed: u64,
+    max_wait_ms: u64,
+}
+
+impl CircuitState {
+    fn new(quota: CircuitQuota) -> Self {
+        Self {
+            quota,
+            in_flight: 0,
+            waiters: VecDeque::new(),
+            admitted: 0,
+            delayed: 0,
+            max_wait_ms: 0,
+        }
+    }
+
+    fn has_room(&self) -> bool {
+        self.in_flight < self.quota.max_concurrent
+    }
+
+    /// Share of contended capacity already in use, scaled by weight.
+    fn load(&self) -> u128 {
+        // Cross-multiplied comparison key: in_flight / weight.
+        (self.in_flight as u128) * 1_000_000 / u128::from(self.quota.weight.max(1))
+    }
+}
+
+struct SchedulerState {
+    capacity: usize,
+    in_flight: usize,
+    default_quota: CircuitQuota,
+    circuits: HashMap<String, CircuitState>,
+}
+
+impl SchedulerState {
+    fn circuit(&mut self, name: &str) -> &mut CircuitState {
+        let default_quota = self.default_quota;
+        self.circuits
+            .entry(name.to_string())
+            .or_insert_with(|| CircuitState::new(default_quota))
+    }
+
+    /// Hand freed capacity to waiting circuits, least-loaded first.
+    fn dispatch(&mut self) {
+        while self.in_flight < self.capacity {
+            let Some(name) = self
+                .circuits
+                .iter()
+                .filter(|(_, c)| !c.waiters.is_empty() && c.has_room())
+                .min_by_key(|(name, c)| (c.load(), name.as_str()))
+                .map(|(name, _)| name.clone())
+            else {
+                return;
+            };
+            let circuit = self.circuit(&name);
+            let Some(waiter) = circuit.waiters.pop_front() else {
+                continue;
+            };
+            // A dropped acquire future leaves a closed channel; skip it.
+            if waiter.notify.send(()).is_ok() {
+                let waited = waiter.enqueued_at.elapsed().as_millis() as u64;
+                circuit.in_flight += 1;
+                circuit.admitted += 1;
+                circuit.delayed += 1;
+                circuit.max_wait_ms = circuit.max_wait_ms.max(waited);
+                self.in_flight += 1;
+            }
+        }
+    }
+}
+
+/// Shared admission controller for Axon executions.
+#[derive(Clone)]
+pub struct ConcurrencyScheduler {
+    state: Arc<Mutex<SchedulerState>>,
+}
+
+impl std::fmt::Debug for ConcurrencyScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConcurrencyScheduler")
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl ConcurrencyScheduler {
+    /// Create a scheduler admitting at most `capacity` executions overall.
+    ///
+    /// Circuits without an explicit quota may use the full capacity at weight 1.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            state: Arc::new(Mutex::new(SchedulerState {
+                capacity,
+                in_flight: 0,
+                default_quota: CircuitQuota::new(capacity),
+                circuits: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Set the quota for a circuit (by Schematic name).
+    pub fn with_quota(self, circuit: impl Into<String>, quota: CircuitQuota) -> Self {
+        self.set_quota(circuit, quota);
+        self
+    }
+
+    /// Quota applied to circuits without an explicit entry.
+    pub fn with_default_quota(self, quota: CircuitQuota) -> Self {
+        self.lock().default_quota = quota;
+        self
+    }
+
+    /// Update a circuit's quota at runtime; waiting executions are re-evaluated.
+    pub fn set_quota(&self, circuit: impl Into<String>, quota: CircuitQuota) {
+        let mut state = self.lock();
+        state.circuit(&circuit.into()).quota = quota;
+        state.dispatch();
+    }
+
+    /// Wait for a slot for `circuit`. The slot is released when the permit drops.
+    pub async fn acquire(&self, circuit: &str) -> ConcurrencyPermit {
+        let receiver = {
+            let mut state = self.lock();
+            let global_room = state.in_flight < state.capacity;
+            let entry = state.circuit(circuit);
+            if global_room && entry.has_room() && entry.waiters.is_empty() {
+                entry.in_flight += 1;
+                entry.admitted += 1;
+                state.in_flight += 1;
+                None
+            } else {
+                let (notify, receiver) = oneshot::channel();
+                entry.waiters.push_back(Waiter {
+                    enqueued_at: Instant::now(),
+                    notify,
+                });
+                Some(receiver)
+            }
+        };
+
+        if let Some(receiver) = receiver {
+            let mut pending = PendingAdmission {
+                receiver,
+                state: self.state.clone(),
+                circuit: circuit.to_string(),
+                admitted: false,
+            };
+            // The sender is only dropped after a successful send or when the
+            // scheduler itself is dropped, and we hold a reference to it.
+            let _ = (&mut pending.receiver).await;
+            pending.admitted = true;
+        }
+        ConcurrencyPermit {
+            state: self.state.clone(),
+            circuit: circuit.to_string(),
+        }
+    }
+
+    /// Current quota usage per circuit.
+    pub fn stats(&self) -> ConcurrencyStats {
+        let state = self.lock();
+        let mut circuits: Vec<_> = state
+            .circuits
+            .iter()
+            .map(|(name, c)| CircuitConcurrencyStats {
+                circuit: name.clone(),
+                max_concurrent: c.quota.max_concurrent,
+                weight: c.quota.weight,
+                in_flight: c.in_flight,
+                queued: c.waiters.len(),
+                admitted: c.admitted,
+                delayed: c.delayed,
+                max_wait_ms: c.max_wait_ms,
+            })
+            .collect();
+        circuits.sort_by(|a, b| a.circuit.cmp(&b.circuit));
+        ConcurrencyStats {
+            capacity: state.capacity,
+            in_flight: state.in_flight,
+            queued: circuits.iter().map(|c| c.queued).sum(),
+            circuits,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SchedulerState> {
+        lock_state(&self.state)
+    }
+}
+
+fn lock_state(state: &Mutex<SchedulerState>) -> MutexGuard<'_, SchedulerState> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// A queued `acquire` call; cleans up if the caller stops waiting.
+struct PendingAdmission {
+    receiver: oneshot::Receiver<()>,
+    state: Arc<Mutex<SchedulerState>>,
+    circuit: String,
+    admitted: bool,
+}
+
+impl Drop for PendingAdmission {
+    fn drop(&mut self) {
+        if self.admitted {
+            return;
+        }
+        self.receiver.close();
+        let granted = self.receiver.try_recv().is_ok();
+        let mut state = lock_state(&self.state);
+        let circuit = state.circuit(&self.circuit);
+        circuit.waiters.retain(|waiter| !waiter.notify.is_closed());
+        if granted {
+            // Admitted between wake-up and cancellation: return the slot.
+            circuit.in_flight = circuit.in_flight.saturating_sub(1);
+            state.in_flight = state.in_flight.saturating_sub(1);
+            state.dispatch();
+        }
+    }
+}
+
+/// An admitted execution slot. Dropping it frees the slot for the next waiter.
+pub struct ConcurrencyPermit {
+    state: Arc<Mutex<SchedulerState>>,
+    circuit: String,
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        let mut state = lock_state(&self.state);
+        state.in_flight = state.in_flight.saturating_sub(1);
+        let circuit = state.circuit(&self.circuit);
+        circuit.in_flight = circuit.in_flight.saturating_sub(1);
+        state.dispatch();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn circuit_quota_caps_in_flight() {
+        let scheduler = ConcurrencyScheduler::new(10).with_quota("batch", CircuitQuota::new(1));
+        let first = scheduler.acquire("batch").await;
+
+        let waiting = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.acquire("batch").await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(scheduler.stats().circuits[0].queued, 1);
+
+        drop(first);
+        let second = tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap();
+        let stats = scheduler.stats();
+        assert_eq!(stats.circuits[0].in_flight, 1);
+        assert_eq!(stats.circuits[0].delayed, 1);
+        drop(second);
+        assert_eq!(scheduler.stats().in_flight, 0);
+    }
+
+    #[tokio::test]
+    async fn freed_capacity_goes_to_least_loaded_circuit() {
+        let scheduler = ConcurrencyScheduler::new(2)
+            .with_quota("api", CircuitQuota::new(2).weight(4))
+            .with_quota("batch", CircuitQuota::new(2));
+        let b1 = scheduler.acquire("batch").await;
+        let b2 = scheduler.acquire("batch").await;
+
+        let queued_batch = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.acquire("batch").await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let queued_api = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.acquire("api").await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        // The batch waiter queued first, but the API circuit has no slots in use.
+        drop(b1);
+        let api = tokio::time::timeout(Duration::from_secs(1), queued_api)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(scheduler.stats().circuits[1].queued, 1);
+
+        drop(b2);
+        let batch = tokio::time::timeout(Duration::from_secs(1), queued_batch)
+            .await
+            .unwrap()
+            .unwrap();
+        drop((api, batch));
+    }
+
+    #[tokio::test]
+    async fn cancelled_waiter_does_not_leak_a_slot() {
+        let scheduler = ConcurrencyScheduler::new(1);
+        let held = scheduler.acquire("a").await;
+        let abandoned =
+            tokio::time::timeout(Duration::from_millis(10), scheduler.acquire("a")).await;
+        assert!(abandoned.is_err());
+        assert_eq!(scheduler.stats().queued, 0);
+
+        drop(held);
+        assert_eq!(scheduler.stats().in_flight, 0);
+        let _again = scheduler.acquire("a").await;
+        assert_eq!(scheduler.stats().in_flight, 1);
+    }
+
+    #[tokio::test]
+    async fn axon_executions_are_admitted_by_schematic_name() {
+        use crate::axon::Axon;
+        use ranvier_core::bus::Bus;
+        use ranvier_core::outcome::Outcome;
+
+        let scheduler = ConcurrencyScheduler::new(4).with_quota("sync", CircuitQuota::new(1));
+        let axon = Axon::<u8, u8, String>::new("sync")
+            .then_fn("echo", |n: u8, _bus: &mut Bus| Outcome::next(n))
+            .with_concurrency(scheduler.clone());
+
+        for n in 0..3 {
+            let outcome = axon.execute(n, &(), &mut Bus::new()).await;
+            assert!(matches!(outcome, Outcome::Next(v) if v == n));
+        }
+        let stats = scheduler.stats();
+        assert_eq!(stats.circuits[0].circuit, "sync");
+        assert_eq!(stats.circuits[0].admitted, 3);
+        assert_eq!(stats.in_flight, 0);
+    }
+}
@@ -0,0 +1,375 @@
+//! Per-node output validation contracts.
+//!
+//! An [`OutputContract`] is a postcondition on the value a node hands to the
+//! next one: either a predicate over the typed output or a JSON Schema checked
+//! against its serialized form. Contracts are attached with
+//! [`Axon::ensure_output`](crate::Axon::ensure_output) right after the node they guard:
+//!
+//! ```rust,ignore
+//! let axon = Axon::new("checkout")
+//!     .then(PriceCart)
+//!     .ensure_output(OutputContract::predicate("total_non_negative", |cart: &Cart| cart.total >= 0))
+//!     .then(ChargeCard);
+//! ```
+//!
+//! A violated contract turns the node's `Next` into a `Fault` built from
+//! [`ContractViolation`], and records a fault cause with the
+//! [`CONTRACT_VIOLATION`] category.
+//!
+//! Every output is checked outside production. When
+//! `RANVIER_RUNTIME_PROFILE=production`, only a sample of executions is
+//! checked (see [`OutputContract::sample_in_production`]). Insert a
+//! [`ContractEnforcement`] into the Bus to override this per execution.
+
+use ranvier_core::runtime_policy::RuntimeProfile;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Fault category recorded when an output contract fails.
+pub const CONTRACT_VIOLATION: &str = "contract_violation";
+
+const DEFAULT_PRODUCTION_SAMPLE_RATE: f64 = 0.01;
+
+/// How contracts are enforced for an execution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContractEnforcement {
+    /// Check every output.
+    Always,
+    /// Check a sample of executions at each contract's production rate.
+    Sampled,
+    /// Skip contract checks entirely.
+    Off,
+}
+
+impl ContractEnforcement {
+    /// `Sampled` under `RANVIER_RUNTIME_PROFILE=production`, otherwise `Always`.
+    pub fn from_env() -> Self {
+        match std::env::var("RANVIER_RUNTIME_PROFILE")
+            .ok()
+            .and_then(|value| value.parse::<RuntimeProfile>().ok())
+        {
+            Some(RuntimeProfile::Production) => Self::Sampled,
+            _ => Self::Always,
+        }
+    }
+}
+
+/// A failed output contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContractViolation {
+    pub contract: String,
+    pub node_label: String,
+    pub reason: String,
+}
+
+impl ContractViolation {
+    pub fn category(&self) -> &'static str {
+        CONTRACT_VIOLATION
+    }
+}
+
+impl std::fmt::Display for ContractViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{CONTRACT_VIOLATION}: output of '{}' violates '{}': {}",
+            self.node_label, self.contract, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ContractViolation {}
+
+impl From<ContractViolation> for String {
+    fn from(violation: ContractViolation) -> Self {
+        violation.to_string()
+    }
+}
+
+impl From<ContractViolation> for ranvier_core::error::RanvierError {
+    fn from(violation: ContractViolation) -> Self {
+        Self::validation(violation.to_string())
+    }
+}
+
+type Predicate<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+enum ContractCheck<T> {
+    Predicate(Predicate<T>),
+    Schema(Value),
+}
+
+impl<T> Clone for ContractCheck<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Predicate(predicate) => Self::Predicate(predicate.clone()),
+            Self::Schema(schema) => Self::Schema(schema.clone()),
+        }
+    }
+}
+
+/// A named postcondition on a node's output.
+pub struct OutputContract<T> {
+    name: String,
+    check: ContractCheck<T>,
+    production_sample_rate: f64,
+}
+
+impl<T> Clone for OutputContract<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            check: self.check.clone(),
+            production_sample_rate: self.production_sample_rate,
+        }
+    }
+}
+
+impl<T: Serialize> OutputContract<T> {
+    pub fn predicate<F>(name: impl Into<String>, predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            check: ContractCheck::Predicate(Arc::new(predicate)),
+            production_sample_rate: DEFAULT_PRODUCTION_SAMPLE_RATE,
+        }
+    }
+
+    /// Check the serialized output against a JSON Schema.
+    ///
+    /// Supports `type`, `enum`, `const`, `required`, `properties`,
+    /// `additionalProperties: false`, `items`, `minimum`/`maximum`,
+    /// `minLength`/`maxLength` and `minItems`/`maxItems`.
+    pub fn json_schema(name: impl Into<String>, schema: Value) -> Self {
+        Self {
+            name: name.into(),
+            check: ContractCheck::Schema(schema),
+            production_sample_rate: DEFAULT_PRODUCTION_SAMPLE_RATE,
+        }
+    }
+
+    /// Fraction of executions checked in production (default `0.01`).
+    pub fn sample_in_production(mut self, rate: f64) -> Self {
+        self.production_sample_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn production_sample_rate(&self) -> f64 {
+        self.production_sample_rate
+    }
+
+    /// Evaluate the contract; `Err` carries the reason.
+    pub fn evaluate(&self, output: &T) -> Result<(), String> {
+        match &self.check {
+            ContractCheck::Predicate(predicate) => {
+                if predicate(output) {
+                    Ok(())
+                } else {
+                    Err("predicate returned false".to_string())
+                }
+            }
+            ContractCheck::Schema(schema) => {
+                let value = serde_json::to_value(output)
+                    .map_err(|e| format!("output is not serializable: {e}"))?;
+                validate_schema(&value, schema, "$")
+            }
+        }
+    }
+}
+
+fn validate_schema(value: &Value, schema: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|name| matches_type(value, name)) {
+            return Err(format!(
+                "{path}: expected type {}, got {}",
+                allowed.join("|"),
+                type_name(value)
+            ));
+        }
+    }
+    if let Some(Value::Array(options)) = schema.get("enum")
+        && !options.contains(value)
+    {
+        return Err(format!(
+            "{path}: value is not one of the allowed enum values"
+        ));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        return Err(format!("{path}: value does not equal const {expected}"));
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+            && number < min
+        {
+            return Err(format!("{path}: {number} is less than minimum {min}"));
+        }
+        if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+            && number > max
+        {
+            return Err(format!("{path}: {number} is greater than maximum {max}"));
+        }
+    }
+
+    if let Some(text) = value.as_str() {
+        let len = text.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+            && len < min
+        {
+            return Err(format!("{path}: length {len} is shorter than {min}"));
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+            && len > max
+        {
+            return Err(format!("{path}: length {len} is longer than {max}"));
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        let len = items.len() as u64;
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+            && len < min
+        {
+            return Err(format!("{path}: {len} items, expected at least {min}"));
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+            && len > max
+        {
+            return Err(format!("{path}: {len} items, expected at most {max}"));
+        }
+        if let Some(item_schema) = schema.get("items") {
+            for (index, item) in items.iter().enumerate() {
+                validate_schema(item, item_schema, &format!("{path}[{index}]"))?;
+            }
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    return Err(format!("{path}: missing required property '{key}'"));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if let Some(properties) = properties {
+            for (key, property_schema) in properties {
+                if let Some(property) = object.get(key) {
+                    validate_schema(property, property_schema, &format!("{path}.{key}"))?;
+                }
+            }
+        }
+        if schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+            let unexpected = object
+                .keys()
+                .find(|key| !properties.is_some_and(|props| props.contains_key(*key)));
+            if let Some(key) = unexpected {
+                return Err(format!("{path}: unexpected property '{key}'"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn matches_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn predicate_contract_reports_failure() {
+        let contract = OutputContract::predicate("positive", |n: &i32| *n > 0);
+        assert!(contract.evaluate(&3).is_ok());
+        assert_eq!(
+            contract.evaluate(&-1).unwrap_err(),
+            "predicate returned false"
+        );
+    }
+
+    #[test]
+    fn schema_contract_checks_nested_fields() {
+        let contract = OutputContract::json_schema(
+            "order_shape",
+            json!({
+                "type": "object",
+                "required": ["id", "lines"],
+                "additionalProperties": false,
+                "properties": {
+                    "id": {"type": "integer", "minimum": 1},
+                    "status": {"enum": ["open", "paid"]},
+                    "lines": {"type": "array", "minItems": 1, "items": {"type": "string"}}
+                }
+            }),
+        );
+
+        assert!(contract.evaluate(&json!({"id": 1, "lines": ["a"]})).is_ok());
+        assert_eq!(
+            contract
+                .evaluate(&json!({"id": 1, "lines": ["a", 2]}))
+                .unwrap_err(),
+            "$.lines[1]: expected type string, got number"
+        );
+        assert!(
+            contract
+                .evaluate(&json!({"id": 0, "lines": ["a"]}))
+                .unwrap_err()
+                .contains("minimum")
+        );
+        assert!(
+            contract
+                .evaluate(&json!({"lines": ["a"]}))
+                .unwrap_err()
+                .contains("missing required property 'id'")
+        );
+        assert!(
+            contract
+                .evaluate(&json!({"id": 1, "lines": ["a"], "extra": true}))
+                .unwrap_err()
+                .contains("unexpected property 'extra'")
+        );
+        assert!(
+            contract
+                .evaluate(&json!({"id": 1, "lines": ["a"], "status": "lost"}))
+                .is_err()
+        );
+    }
+}
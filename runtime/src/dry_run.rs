@@ -0,0 +1,201 @@
+//! Dry runs: walk a circuit without running its transitions.
+//!
+//! [`Axon::dry_run`] follows the same path an execution would, but at each
+//! node it asks the transition for [`Transition::estimate`] instead of calling
+//! `run`, and collects what [`Transition::describe`] declares. The report
+//! lists the nodes that would be visited and their side effects, which is
+//! enough for pre-flight checks in CI or before an operator replays work:
+//!
+//! ```rust,ignore
+//! let report = checkout.dry_run(order, &resources, &mut Bus::new()).await;
+//! for step in &report.path {
+//!     println!("{} -> {:?}", step.label, step.estimate);
+//! }
+//! assert!(report.is_complete(), "stopped at {:?}", report.stopped_at());
+//! ```
+//!
+//! A transition without an estimate stops the walk with a
+//! [`DRY_RUN_UNESTIMATED`] emit, since nothing after it can be predicted.
+//! Dry runs skip idempotency, saga compensation, persistence and the other
+//! bookkeeping `execute` performs.
+//!
+//! [`Axon::dry_run`]: crate::axon::Axon::dry_run
+
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::transition::{SideEffect, Transition};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Emitted when a dry run reaches a transition that gives no estimate.
+pub const DRY_RUN_UNESTIMATED: &str = "execution.dry_run.unestimated";
+
+/// A node visited by a dry run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DryRunStep {
+    /// Schematic node id; `None` for branches of a join, race or parallel step.
+    pub node_id: Option<String>,
+    pub label: String,
+    /// Outcome kind (`"Next"`, `"Branch"`, ...) the transition estimated, or
+    /// `None` when it gave no estimate.
+    pub estimate: Option<String>,
+    pub side_effects: Vec<SideEffect>,
+}
+
+/// The path a dry run took and the outcome it predicts.
+#[derive(Debug)]
+pub struct DryRunReport<Out, E> {
+    pub circuit: String,
+    /// Visited nodes in the order they were reached. Concurrent branches
+    /// appear in completion order.
+    pub path: Vec<DryRunStep>,
+    pub outcome: Outcome<Out, E>,
+}
+
+impl<Out, E> DryRunReport<Out, E> {
+    /// `true` when every visited transition gave an estimate.
+    pub fn is_complete(&self) -> bool {
+        self.path.iter().all(|step| step.estimate.is_some())
+    }
+
+    /// The first transition that gave no estimate.
+    pub fn stopped_at(&self) -> Option<&DryRunStep> {
+        self.path.iter().find(|step| step.estimate.is_none())
+    }
+
+    /// Side effects declared along the path.
+    pub fn side_effects(&self) -> impl Iterator<Item = &SideEffect> {
+        self.path.iter().flat_map(|step| step.side_effects.iter())
+    }
+}
+
+/// Bus marker that switches node execution to estimates.
+#[derive(Clone, Default)]
+pub(crate) struct DryRunPlan {
+    steps: Arc<Mutex<Vec<DryRunStep>>>,
+}
+
+impl DryRunPlan {
+    pub(crate) fn from_bus(bus: &Bus) -> Option<Self> {
+        bus.read::<Self>().cloned()
+    }
+
+    /// Estimate `transition` on `state` and record it on the path.
+    pub(crate) fn step<T, From, To>(
+        &self,
+        transition: &T,
+        state: &From,
+        resources: &T::Resources,
+        bus: &Bus,
+        node_id: Option<&str>,
+    ) -> Outcome<To, T::Error>
+    where
+        T: Transition<From, To> + ?Sized,
+        From: Send + 'static,
+        To: Send + 'static,
+    {
+        let label = transition.label();
+        let estimate = transition.estimate(state, resources, bus);
+        let step = DryRunStep {
+            node_id: node_id.map(str::to_string),
+            label: label.clone(),
+            estimate: estimate.as_ref().map(outcome_kind),
+            side_effects: transition.describe(),
+        };
+        match self.steps.lock() {
+            Ok(mut steps) => steps.push(step),
+            Err(poisoned) => poisoned.into_inner().push(step),
+        }
+        estimate.unwrap_or_else(|| {
+            tracing::debug!(node = %label, "No estimate; dry run stops here");
+            Outcome::emit(
+                DRY_RUN_UNESTIMATED,
+                Some(serde_json::json!({ "node_id": node_id, "label": label })),
+            )
+        })
+    }
+
+    pub(crate) fn take(&self) -> Vec<DryRunStep> {
+        match self.steps.lock() {
+            Ok(mut steps) => std::mem::take(&mut *steps),
+            Err(poisoned) => std::mem::take(&mut *poisoned.into_inner()),
+        }
+    }
+}
+
+fn outcome_kind<To, E>(outcome: &Outcome<To, E>) -> String {
+    match outcome {
+        Outcome::Next(_) => "Next",
+        Outcome::Branch(_, _) => "Branch",
+        Outcome::Jump(_, _) => "Jump",
+        Outcome::Emit(_, _) => "Emit",
+        Outcome::Fault(_) => "Fault",
+        Outcome::Suspend(_, _) => "Suspend",
+        Outcome::Retry { .. } => "Retry",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axon::Axon;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone)]
+    struct Charge {
+        charges: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Transition<i32, i32> for Charge {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, amount: i32, _res: &(), _bus: &mut Bus) -> Outcome<i32, String> {
+            self.charges.fetch_add(1, Ordering::SeqCst);
+            Outcome::next(amount * 100)
+        }
+
+        fn describe(&self) -> Vec<SideEffect> {
+            vec![SideEffect::new(
+                "payment.charge",
+                "charge the customer's card",
+            )]
+        }
+
+        fn estimate(&self, amount: &i32, _res: &(), _bus: &Bus) -> Option<Outcome<i32, String>> {
+            Some(Outcome::next(amount * 100))
+        }
+    }
+
+    #[tokio::test]
+    async fn dry_run_follows_estimates_and_stops_at_unestimated_nodes() {
+        let charges = Arc::new(AtomicUsize::new(0));
+        let axon = Axon::<i32, i32, String>::new("Checkout")
+            .then(Charge {
+                charges: charges.clone(),
+            })
+            .then_fn("Notify", |n: i32, _bus| Outcome::next(n));
+
+        let report = axon.dry_run(7, &(), &mut Bus::new()).await;
+        assert_eq!(charges.load(Ordering::SeqCst), 0);
+        assert_eq!(report.circuit, "Checkout");
+        let labels: Vec<_> = report.path.iter().map(|step| step.label.as_str()).collect();
+        assert_eq!(labels, vec!["Charge", "Notify"]);
+        assert_eq!(report.path[0].estimate.as_deref(), Some("Next"));
+        assert_eq!(
+            report
+                .side_effects()
+                .map(|e| e.kind.as_str())
+                .collect::<Vec<_>>(),
+            vec!["payment.charge"]
+        );
+        assert!(!report.is_complete());
+        assert_eq!(report.stopped_at().unwrap().label, "Notify");
+        assert!(
+            matches!(report.outcome, Outcome::Emit(ref kind, _) if kind == DRY_RUN_UNESTIMATED)
+        );
+    }
+}
@@ -0,0 +1,881 @@
+//! Runtime stores backed by a [`DurabilityStore`].
+//!
+//! [`DurabilityAdapter`] implements [`SuspensionStore`], [`CheckpointStore`]
+//! and [`IdempotencyStore`] on top of any [`DurabilityStore`], so one backend
+//! (in memory, or [`PostgresDurabilityStore`] with `persistence-postgres`)
+//! holds suspended executions, checkpoints and idempotent results alike:
+//!
+//! ```rust,ignore
+//! let store = PostgresDurabilityStore::new(pool);
+//! store.ensure_schema().await?;
+//! let durable = DurabilityAdapter::new(store);
+//!
+//! let approvals = ResumableAxon::new(axon, Arc::new(durable.clone()));
+//! bus.insert(CheckpointHandle::from_store(durable.clone()));
+//! bus.insert(IdempotencyHandle::from_store(durable));
+//! ```
+//!
+//! A checkpointed trace is a run with the trace id as its id and one step
+//! per checkpoint; it stays pending until its checkpoints are cleared.
+//! Suspensions and idempotent results are runs with ids prefixed by
+//! `suspension:` and `idempotency:`; a claimed idempotency key is a running
+//! run until its result is saved or its lease runs out.
+
+use crate::checkpoint::{Checkpoint, CheckpointStore};
+use crate::idempotency::{IdempotencyClaim, IdempotencyRecord, IdempotencyStore};
+use crate::suspend::{SuspendedExecution, SuspensionStore};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use ranvier_core::durability::{DurabilityStore, DurableRun, DurableStep, RunStatus};
+use ranvier_core::timeline::Timestamp;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SUSPENSION_PREFIX: &str = "suspension:";
+const IDEMPOTENCY_PREFIX: &str = "idempotency:";
+
+/// How long a claimed idempotency key stays claimed by default.
+pub const DEFAULT_CLAIM_LEASE: Duration = Duration::from_secs(300);
+
+/// Serves the runtime's feature stores from one [`DurabilityStore`].
+#[derive(Clone)]
+pub struct DurabilityAdapter {
+    inner: Arc<dyn DurabilityStore>,
+    claim_lease: Duration,
+}
+
+impl std::fmt::Debug for DurabilityAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DurabilityAdapter").finish_non_exhaustive()
+    }
+}
+
+impl DurabilityAdapter {
+    pub fn new<S>(store: S) -> Self
+    where
+        S: DurabilityStore + 'static,
+    {
+        Self::from_arc(Arc::new(store))
+    }
+
+    pub fn from_arc(store: Arc<dyn DurabilityStore>) -> Self {
+        Self {
+            inner: store,
+            claim_lease: DEFAULT_CLAIM_LEASE,
+        }
+    }
+
+    /// How long a claimed idempotency key stays claimed. A claim left behind
+    /// by a process that died is taken over by the next caller once its
+    /// lease has run out, so set this above the longest execution.
+    pub fn with_claim_lease(mut self, lease: Duration) -> Self {
+        self.claim_lease = lease;
+        self
+    }
+
+    pub fn store(&self) -> Arc<dyn DurabilityStore> {
+        self.inner.clone()
+    }
+
+    async fn load_suspension(&self, run: DurableRun) -> Result<Option<SuspendedExecution>> {
+        let steps = self
+            .inner
+            .load_steps(&run.run_id)
+            .await
+            .map_err(|e| anyhow!(e))?;
+        Ok(suspension_from(run, steps))
+    }
+}
+
+fn suspension_from(run: DurableRun, steps: Vec<DurableStep>) -> Option<SuspendedExecution> {
+    let token = run.run_id.strip_prefix(SUSPENSION_PREFIX)?;
+    let mut steps = steps.into_iter();
+    let first = steps.next()?;
+    // A suspension with a recorded node input is saved as two steps: the
+    // node's input, then its suspension state.
+    let (node_input, step) = match steps.last() {
+        Some(step) => (first.state, step),
+        None => (None, first),
+    };
+    Some(SuspendedExecution {
+        token: token.to_string(),
+        circuit: run.circuit,
+        node_id: step.node_id,
+        node_label: step.node_label,
+        input: run.input.unwrap_or(Value::Null),
+        state: step.state,
+        node_input,
+        suspended_at: run.updated_at,
+    })
+}
+
+#[async_trait]
+impl SuspensionStore for DurabilityAdapter {
+    async fn save(&self, execution: SuspendedExecution) -> Result<()> {
+        let run_id = format!("{SUSPENSION_PREFIX}{}", execution.token);
+        let mut run = DurableRun::new(&run_id, execution.circuit, RunStatus::Suspended);
+        run.input = Some(execution.input);
+        run.started_at = execution.suspended_at;
+        run.updated_at = execution.suspended_at;
+        let step = |step, state| DurableStep {
+            run_id: run_id.clone(),
+            step,
+            node_id: execution.node_id.clone(),
+            node_label: execution.node_label.clone(),
+            state,
+            recorded_at: execution.suspended_at,
+        };
+        let steps = match execution.node_input {
+            Some(node_input) => vec![step(0, Some(node_input)), step(1, execution.state)],
+            None => vec![step(0, execution.state)],
+        };
+        self.inner
+            .replace_run(run, steps)
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+
+    async fn load(&self, token: &str) -> Result<Option<SuspendedExecution>> {
+        let run = self
+            .inner
+            .load_run(&format!("{SUSPENSION_PREFIX}{token}"))
+            .await
+            .map_err(|e| anyhow!(e))?;
+        match run {
+            Some(run) => self.load_suspension(run).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn take(&self, token: &str) -> Result<Option<SuspendedExecution>> {
+        let taken = self
+            .inner
+            .take_run(&format!("{SUSPENSION_PREFIX}{token}"))
+            .await
+            .map_err(|e| anyhow!(e))?;
+        Ok(taken.and_then(|(run, steps)| suspension_from(run, steps)))
+    }
+
+    async fn list(&self) -> Result<Vec<SuspendedExecution>> {
+        let runs = self
+            .inner
+            .load_pending_runs()
+            .await
+            .map_err(|e| anyhow!(e))?;
+        let mut executions = Vec::new();
+        for run in runs {
+            if run.status == RunStatus::Suspended
+                && let Some(execution) = self.load_suspension(run).await?
+            {
+                executions.push(execution);
+            }
+        }
+        Ok(executions)
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for DurabilityAdapter {
+    async fn save(&self, checkpoint: Checkpoint) -> Result<()> {
+        let existing = self
+            .inner
+            .load_run(&checkpoint.trace_id)
+            .await
+            .map_err(|e| anyhow!(e))?;
+        if existing.is_none() {
+            let run = DurableRun::new(&checkpoint.trace_id, checkpoint.circuit, RunStatus::Running);
+            self.inner.save_run(run).await.map_err(|e| anyhow!(e))?;
+        }
+        self.inner
+            .save_step(DurableStep {
+                run_id: checkpoint.trace_id,
+                step: checkpoint.step,
+                node_id: checkpoint.node_id,
+                node_label: checkpoint.node_label,
+                state: Some(checkpoint.state),
+                recorded_at: Timestamp::from_millis(checkpoint.recorded_at_ms),
+            })
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+
+    async fn list(&self, trace_id: &str) -> Result<Vec<Checkpoint>> {
+        let circuit = self
+            .inner
+            .load_run(trace_id)
+            .await
+            .map_err(|e| anyhow!(e))?
+            .map(|run| run.circuit)
+            .unwrap_or_default();
+        let steps = self
+            .inner
+            .load_steps(trace_id)
+            .await
+            .map_err(|e| anyhow!(e))?;
+        Ok(steps
+            .into_iter()
+            .map(|step| Checkpoint {
+                trace_id: step.run_id,
+                circuit: circuit.clone(),
+                node_id: step.node_id,
+                node_label: step.node_label,
+                step: step.step,
+                state: step.state.unwrap_or(Value::Null),
+                recorded_at_ms: step.recorded_at.as_millis(),
+            })
+            .collect())
+    }
+
+    async fn clear(&self, trace_id: &str) -> Result<()> {
+        self.inner
+            .delete_run(trace_id)
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+}
+
+/// The record of a completed idempotency run.
+fn idempotency_record(run: DurableRun, key: &str) -> Option<IdempotencyRecord> {
+    if run.status != RunStatus::Completed {
+        return None;
+    }
+    Some(IdempotencyRecord {
+        circuit: run.circuit,
+        key: key.to_string(),
+        input_digest: run.input_digest.unwrap_or_default(),
+        outcome: run.output?,
+        completed_at: run.updated_at,
+    })
+}
+
+#[async_trait]
+impl IdempotencyStore for DurabilityAdapter {
+    async fn load(&self, circuit: &str, key: &str) -> Result<Option<IdempotencyRecord>> {
+        let run = self
+            .inner
+            .load_run(&format!("{IDEMPOTENCY_PREFIX}{circuit}:{key}"))
+            .await
+            .map_err(|e| anyhow!(e))?;
+        Ok(run.and_then(|run| idempotency_record(run, key)))
+    }
+
+    async fn save(&self, record: IdempotencyRecord) -> Result<()> {
+        let run_id = format!("{IDEMPOTENCY_PREFIX}{}:{}", record.circuit, record.key);
+        let mut run = DurableRun::new(run_id, record.circuit, RunStatus::Completed);
+        run.input_digest = Some(record.input_digest);
+        run.output = Some(record.outcome);
+        run.started_at = record.completed_at;
+        run.updated_at = record.completed_at;
+        self.inner.save_run(run).await.map_err(|e| anyhow!(e))
+    }
+
+    /// A claim is a running run under the key's id, leased for
+    /// [`with_claim_lease`](DurabilityAdapter::with_claim_lease); an expired
+    /// claim is taken over.
+    async fn claim(
+        &self,
+        circuit: &str,
+        key: &str,
+        input_digest: &str,
+    ) -> Result<IdempotencyClaim> {
+        let run_id = format!("{IDEMPOTENCY_PREFIX}{circuit}:{key}");
+        loop {
+            let mut run = DurableRun::new(&run_id, circuit, RunStatus::Running);
+            run.input_digest = Some(input_digest.to_string());
+            run.lease_ms = Some(u64::try_from(self.claim_lease.as_millis()).unwrap_or(u64::MAX));
+            if self.inner.insert_run(run).await.map_err(|e| anyhow!(e))? {
+                return Ok(IdempotencyClaim::Claimed);
+            }
+            // The holder may have released the key since; claim it again.
+            let Some(run) = self.inner.load_run(&run_id).await.map_err(|e| anyhow!(e))? else {
+                continue;
+            };
+            let input_digest = run.input_digest.clone().unwrap_or_default();
+            return Ok(match idempotency_record(run, key) {
+                Some(record) => IdempotencyClaim::Completed(record),
+                None => IdempotencyClaim::InProgress { input_digest },
+            });
+        }
+    }
+
+    async fn release(&self, circuit: &str, key: &str) -> Result<()> {
+        self.inner
+            .delete_run(&format!("{IDEMPOTENCY_PREFIX}{circuit}:{key}"))
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+}
+
+/// PostgreSQL-backed [`DurabilityStore`].
+#[cfg(feature = "persistence-postgres")]
+#[derive(Debug, Clone)]
+pub struct PostgresDurabilityStore {
+    pool: sqlx::Pool<sqlx::Postgres>,
+    runs_table: String,
+    steps_table: String,
+}
+
+#[cfg(feature = "persistence-postgres")]
+#[derive(sqlx::FromRow)]
+struct PostgresRunRow {
+    run_id: String,
+    circuit: String,
+    status: String,
+    input: Option<Value>,
+    input_digest: Option<String>,
+    output: Option<Value>,
+    started_at: i64,
+    updated_at: i64,
+    lease_ms: Option<i64>,
+}
+
+#[cfg(feature = "persistence-postgres")]
+impl PostgresRunRow {
+    fn into_run(self) -> Result<DurableRun, String> {
+        Ok(DurableRun {
+            status: RunStatus::parse(&self.status)
+                .ok_or_else(|| format!("unknown run status value: {}", self.status))?,
+            run_id: self.run_id,
+            circuit: self.circuit,
+            input: self.input,
+            input_digest: self.input_digest,
+            output: self.output,
+            started_at: Timestamp::from_nanos(self.started_at as u64),
+            updated_at: Timestamp::from_nanos(self.updated_at as u64),
+            lease_ms: self.lease_ms.map(|ms| ms as u64),
+        })
+    }
+}
+
+#[cfg(feature = "persistence-postgres")]
+#[derive(sqlx::FromRow)]
+struct PostgresStepRow {
+    run_id: String,
+    step: i64,
+    node_id: String,
+    node_label: String,
+    state: Option<Value>,
+    recorded_at: i64,
+}
+
+#[cfg(feature = "persistence-postgres")]
+impl PostgresStepRow {
+    fn into_step(self) -> DurableStep {
+        DurableStep {
+            run_id: self.run_id,
+            step: self.step as u64,
+            node_id: self.node_id,
+            node_label: self.node_label,
+            state: self.state,
+            recorded_at: Timestamp::from_nanos(self.recorded_at as u64),
+        }
+    }
+}
+
+/// A step row with its insertion order, as returned by `DELETE ... RETURNING`.
+#[cfg(feature = "persistence-postgres")]
+#[derive(sqlx::FromRow)]
+struct PostgresSeqStepRow {
+    seq: i64,
+    #[sqlx(flatten)]
+    step: PostgresStepRow,
+}
+
+#[cfg(feature = "persistence-postgres")]
+impl PostgresDurabilityStore {
+    /// Create a store with the default table prefix `ranvier_durability`.
+    /// Call `ensure_schema()` once at startup to create the tables.
+    pub fn new(pool: sqlx::Pool<sqlx::Postgres>) -> Self {
+        Self::with_table_prefix(pool, "ranvier_durability")
+    }
+
+    pub fn with_table_prefix(pool: sqlx::Pool<sqlx::Postgres>, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        Self {
+            pool,
+            runs_table: format!("{}_runs", prefix),
+            steps_table: format!("{}_steps", prefix),
+        }
+    }
+
+    /// Initialize adapter tables when absent.
+    pub async fn ensure_schema(&self) -> Result<()> {
+        let create_runs = format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                run_id TEXT PRIMARY KEY,
+                circuit TEXT NOT NULL,
+                status TEXT NOT NULL,
+                input JSONB NULL,
+                input_digest TEXT NULL,
+                output JSONB NULL,
+                started_at BIGINT NOT NULL,
+                updated_at BIGINT NOT NULL,
+                lease_ms BIGINT NULL
+            )",
+            self.runs_table
+        );
+        sqlx::query(&create_runs).execute(&self.pool).await?;
+        let add_lease = format!(
+            "ALTER TABLE {} ADD COLUMN IF NOT EXISTS lease_ms BIGINT NULL",
+            self.runs_table
+        );
+        sqlx::query(&add_lease).execute(&self.pool).await?;
+
+        let create_steps = format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                seq BIGSERIAL PRIMARY KEY,
+                run_id TEXT NOT NULL,
+                step BIGINT NOT NULL,
+                node_id TEXT NOT NULL,
+                node_label TEXT NOT NULL,
+                state JSONB NULL,
+                recorded_at BIGINT NOT NULL
+            )",
+            self.steps_table
+        );
+        sqlx::query(&create_steps).execute(&self.pool).await?;
+
+        let index_steps = format!(
+            "CREATE INDEX IF NOT EXISTS {table}_run_idx ON {table} (run_id, seq)",
+            table = self.steps_table
+        );
+        sqlx::query(&index_steps).execute(&self.pool).await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "persistence-postgres")]
+#[async_trait]
+impl DurabilityStore for PostgresDurabilityStore {
+    async fn save_run(&self, run: DurableRun) -> Result<(), String> {
+        let upsert = format!(
+            "INSERT INTO {} (run_id, circuit, status, input, input_digest, output, started_at, updated_at, lease_ms)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             ON CONFLICT (run_id) DO UPDATE SET
+                circuit = EXCLUDED.circuit,
+                status = EXCLUDED.status,
+                input = EXCLUDED.input,
+                input_digest = EXCLUDED.input_digest,
+                output = EXCLUDED.output,
+                updated_at = EXCLUDED.updated_at,
+                lease_ms = EXCLUDED.lease_ms",
+            self.runs_table
+        );
+        sqlx::query(&upsert)
+            .bind(&run.run_id)
+            .bind(&run.circuit)
+            .bind(run.status.as_str())
+            .bind(&run.input)
+            .bind(&run.input_digest)
+            .bind(&run.output)
+            .bind(run.started_at.as_nanos() as i64)
+            .bind(run.updated_at.as_nanos() as i64)
+            .bind(run.lease_ms.map(|ms| ms as i64))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    async fn insert_run(&self, run: DurableRun) -> Result<bool, String> {
+        let insert = format!(
+            "INSERT INTO {table} (run_id, circuit, status, input, input_digest, output, started_at, updated_at, lease_ms)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             ON CONFLICT (run_id) DO UPDATE SET
+                circuit = EXCLUDED.circuit,
+                status = EXCLUDED.status,
+                input = EXCLUDED.input,
+                input_digest = EXCLUDED.input_digest,
+                output = EXCLUDED.output,
+                started_at = EXCLUDED.started_at,
+                updated_at = EXCLUDED.updated_at,
+                lease_ms = EXCLUDED.lease_ms
+             WHERE {table}.status = 'running'
+                AND {table}.lease_ms IS NOT NULL
+                AND {table}.updated_at + {table}.lease_ms * 1000000 < $10",
+            table = self.runs_table
+        );
+        let inserted = sqlx::query(&insert)
+            .bind(&run.run_id)
+            .bind(&run.circuit)
+            .bind(run.status.as_str())
+            .bind(&run.input)
+            .bind(&run.input_digest)
+            .bind(&run.output)
+            .bind(run.started_at.as_nanos() as i64)
+            .bind(run.updated_at.as_nanos() as i64)
+            .bind(run.lease_ms.map(|ms| ms as i64))
+            .bind(Timestamp::now().as_nanos() as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(inserted.rows_affected() == 1)
+    }
+
+    async fn save_step(&self, step: DurableStep) -> Result<(), String> {
+        let insert = format!(
+            "INSERT INTO {} (run_id, step, node_id, node_label, state, recorded_at)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            self.steps_table
+        );
+        sqlx::query(&insert)
+            .bind(&step.run_id)
+            .bind(step.step as i64)
+            .bind(&step.node_id)
+            .bind(&step.node_label)
+            .bind(&step.state)
+            .bind(step.recorded_at.as_nanos() as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    async fn load_run(&self, run_id: &str) -> Result<Option<DurableRun>, String> {
+        let select = format!(
+            "SELECT run_id, circuit, status, input, input_digest, output, started_at, updated_at, lease_ms
+             FROM {}
+             WHERE run_id = $1",
+            self.runs_table
+        );
+        let row: Option<PostgresRunRow> = sqlx::query_as(&select)
+            .bind(run_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        row.map(PostgresRunRow::into_run).transpose()
+    }
+
+    async fn load_steps(&self, run_id: &str) -> Result<Vec<DurableStep>, String> {
+        let select = format!(
+            "SELECT run_id, step, node_id, node_label, state, recorded_at
+             FROM {}
+             WHERE run_id = $1
+             ORDER BY seq ASC",
+            self.steps_table
+        );
+        let rows: Vec<PostgresStepRow> = sqlx::query_as(&select)
+            .bind(run_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(rows.into_iter().map(PostgresStepRow::into_step).collect())
+    }
+
+    async fn load_pending_runs(&self) -> Result<Vec<DurableRun>, String> {
+        let select = format!(
+            "SELECT run_id, circuit, status, input, input_digest, output, started_at, updated_at, lease_ms
+             FROM {}
+             WHERE status IN ('running', 'suspended')
+             ORDER BY started_at ASC",
+            self.runs_table
+        );
+        let rows: Vec<PostgresRunRow> = sqlx::query_as(&select)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        rows.into_iter().map(PostgresRunRow::into_run).collect()
+    }
+
+    async fn delete_run(&self, run_id: &str) -> Result<(), String> {
+        for table in [&self.steps_table, &self.runs_table] {
+            let delete = format!("DELETE FROM {} WHERE run_id = $1", table);
+            sqlx::query(&delete)
+                .bind(run_id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    async fn replace_run(&self, run: DurableRun, steps: Vec<DurableStep>) -> Result<(), String> {
+        let mut tx = self.pool.begin().await.map_err(|e| e.to_string())?;
+        let delete_steps = format!("DELETE FROM {} WHERE run_id = $1", self.steps_table);
+        sqlx::query(&delete_steps)
+            .bind(&run.run_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        let upsert = format!(
+            "INSERT INTO {} (run_id, circuit, status, input, input_digest, output, started_at, updated_at, lease_ms)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             ON CONFLICT (run_id) DO UPDATE SET
+                circuit = EXCLUDED.circuit,
+                status = EXCLUDED.status,
+                input = EXCLUDED.input,
+                input_digest = EXCLUDED.input_digest,
+                output = EXCLUDED.output,
+                started_at = EXCLUDED.started_at,
+                updated_at = EXCLUDED.updated_at,
+                lease_ms = EXCLUDED.lease_ms",
+            self.runs_table
+        );
+        sqlx::query(&upsert)
+            .bind(&run.run_id)
+            .bind(&run.circuit)
+            .bind(run.status.as_str())
+            .bind(&run.input)
+            .bind(&run.input_digest)
+            .bind(&run.output)
+            .bind(run.started_at.as_nanos() as i64)
+            .bind(run.updated_at.as_nanos() as i64)
+            .bind(run.lease_ms.map(|ms| ms as i64))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        let insert = format!(
+            "INSERT INTO {} (run_id, step, node_id, node_label, state, recorded_at)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            self.steps_table
+        );
+        for step in steps {
+            sqlx::query(&insert)
+                .bind(&run.run_id)
+                .bind(step.step as i64)
+                .bind(&step.node_id)
+                .bind(&step.node_label)
+                .bind(&step.state)
+                .bind(step.recorded_at.as_nanos() as i64)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        tx.commit().await.map_err(|e| e.to_string())
+    }
+
+    async fn take_run(
+        &self,
+        run_id: &str,
+    ) -> Result<Option<(DurableRun, Vec<DurableStep>)>, String> {
+        let mut tx = self.pool.begin().await.map_err(|e| e.to_string())?;
+        // The row lock makes a concurrent take wait, then find nothing.
+        let delete_run = format!(
+            "DELETE FROM {} WHERE run_id = $1
+             RETURNING run_id, circuit, status, input, input_digest, output, started_at, updated_at, lease_ms",
+            self.runs_table
+        );
+        let row: Option<PostgresRunRow> = sqlx::query_as(&delete_run)
+            .bind(run_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        let Some(row) = row else {
+            tx.commit().await.map_err(|e| e.to_string())?;
+            return Ok(None);
+        };
+        let delete_steps = format!(
+            "DELETE FROM {} WHERE run_id = $1
+             RETURNING seq, run_id, step, node_id, node_label, state, recorded_at",
+            self.steps_table
+        );
+        let mut steps: Vec<(i64, PostgresStepRow)> = sqlx::query_as(&delete_steps)
+            .bind(run_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|row: PostgresSeqStepRow| (row.seq, row.step))
+            .collect();
+        tx.commit().await.map_err(|e| e.to_string())?;
+        steps.sort_by_key(|(seq, _)| *seq);
+        Ok(Some((
+            row.into_run()?,
+            steps.into_iter().map(|(_, row)| row.into_step()).collect(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axon::Axon;
+    use crate::idempotency::{IdempotencyHandle, IdempotencyKey};
+    use crate::suspend::{ResumableAxon, ResumeSignal};
+    use ranvier_core::durability::InMemoryDurabilityStore;
+    use ranvier_core::{Bus, Outcome, Transition};
+
+    #[derive(Clone)]
+    struct AwaitRefund;
+
+    #[async_trait]
+    impl Transition<u32, u32> for AwaitRefund {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, n: u32, _res: &(), bus: &mut Bus) -> Outcome<u32, String> {
+            if bus.read::<ResumeSignal>().is_some() {
+                Outcome::next(n * 2)
+            } else {
+                Outcome::suspend(format!("refund-{n}"), Some(serde_json::json!(n)))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn one_durability_store_serves_suspensions_and_idempotency() {
+        let backend = InMemoryDurabilityStore::new();
+        let durable = DurabilityAdapter::new(backend.clone());
+
+        let flow = ResumableAxon::new(
+            Axon::<u32, u32, String>::new("Refund").then(AwaitRefund),
+            Arc::new(durable.clone()),
+        );
+        assert!(matches!(
+            flow.execute(5, &(), &mut Bus::new()).await.unwrap(),
+            Outcome::Suspend(..)
+        ));
+        let pending = backend.load_pending_runs().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].run_id, "suspension:refund-5");
+        let saved = SuspensionStore::list(&durable).await.unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].node_input, Some(serde_json::json!(5)));
+        let resumed = flow
+            .resume("refund-5", None, &(), &mut Bus::new())
+            .await
+            .unwrap();
+        assert!(matches!(resumed, Outcome::Next(10)));
+        assert!(backend.load_pending_runs().await.unwrap().is_empty());
+
+        let checkout = Axon::<u32, u32, String>::new("Checkout")
+            .then_fn("Charge", |n: u32, _bus| Outcome::next(n + 1));
+        let mut bus = Bus::new();
+        bus.insert(IdempotencyHandle::from_store(durable.clone()));
+        bus.insert(IdempotencyKey::new("k1"));
+        checkout.execute(1, &(), &mut bus).await;
+        let run = backend
+            .load_run("idempotency:Checkout:k1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.status, RunStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn concurrent_takes_hand_a_suspension_to_one_caller() {
+        let durable = DurabilityAdapter::new(InMemoryDurabilityStore::new());
+        for state in [1, 2] {
+            SuspensionStore::save(
+                &durable,
+                SuspendedExecution {
+                    token: "refund-7".into(),
+                    circuit: "Refund".into(),
+                    node_id: "n1".into(),
+                    node_label: "AwaitRefund".into(),
+                    input: serde_json::json!(7),
+                    state: Some(serde_json::json!(state)),
+                    node_input: None,
+                    suspended_at: Timestamp::now(),
+                },
+            )
+            .await
+            .unwrap();
+        }
+        let steps = durable
+            .store()
+            .load_steps("suspension:refund-7")
+            .await
+            .unwrap();
+        assert_eq!(steps.len(), 1);
+
+        let takes = (0..8).map(|_| {
+            let durable = durable.clone();
+            tokio::spawn(async move { durable.take("refund-7").await.unwrap() })
+        });
+        let mut taken = Vec::new();
+        for take in takes {
+            taken.extend(take.await.unwrap());
+        }
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].state, Some(serde_json::json!(2)));
+    }
+
+    #[tokio::test]
+    async fn concurrent_claims_hand_an_idempotency_key_to_one_caller() {
+        let durable = DurabilityAdapter::new(InMemoryDurabilityStore::new());
+        let claims = (0..8).map(|_| {
+            let durable = durable.clone();
+            tokio::spawn(async move { durable.claim("Checkout", "k1", "d1").await.unwrap() })
+        });
+        let mut claimed = 0;
+        for claim in claims {
+            match claim.await.unwrap() {
+                IdempotencyClaim::Claimed => claimed += 1,
+                IdempotencyClaim::InProgress { input_digest } => assert_eq!(input_digest, "d1"),
+                IdempotencyClaim::Completed(_) => panic!("nothing was recorded"),
+            }
+        }
+        assert_eq!(claimed, 1);
+        assert!(
+            IdempotencyStore::load(&durable, "Checkout", "k1")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        durable.release("Checkout", "k1").await.unwrap();
+        assert_eq!(
+            durable.claim("Checkout", "k1", "d2").await.unwrap(),
+            IdempotencyClaim::Claimed
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_idempotency_claims_are_taken_over() {
+        let durable = DurabilityAdapter::new(InMemoryDurabilityStore::new())
+            .with_claim_lease(Duration::from_millis(20));
+        assert_eq!(
+            durable.claim("Checkout", "k1", "d1").await.unwrap(),
+            IdempotencyClaim::Claimed
+        );
+        assert!(matches!(
+            durable.claim("Checkout", "k1", "d1").await.unwrap(),
+            IdempotencyClaim::InProgress { .. }
+        ));
+
+        // The first holder never finished; its claim lapses.
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert_eq!(
+            durable.claim("Checkout", "k1", "d1").await.unwrap(),
+            IdempotencyClaim::Claimed
+        );
+        assert!(matches!(
+            durable.claim("Checkout", "k1", "d1").await.unwrap(),
+            IdempotencyClaim::InProgress { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn checkpoints_round_trip_through_steps() {
+        let backend = InMemoryDurabilityStore::new();
+        let durable = DurabilityAdapter::new(backend.clone());
+        for step in 1..=2 {
+            CheckpointStore::save(
+                &durable,
+                Checkpoint {
+                    trace_id: "trace-1".into(),
+                    circuit: "Orders".into(),
+                    node_id: format!("n{step}"),
+                    node_label: format!("Step{step}"),
+                    step,
+                    state: serde_json::json!(step * 10),
+                    recorded_at_ms: 1_000,
+                },
+            )
+            .await
+            .unwrap();
+        }
+        let latest = CheckpointStore::latest(&durable, "trace-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((latest.circuit.as_str(), latest.step), ("Orders", 2));
+        assert_eq!(latest.recorded_at_ms, 1_000);
+        assert_eq!(backend.load_pending_runs().await.unwrap().len(), 1);
+
+        durable.clear("trace-1").await.unwrap();
+        assert!(backend.load_steps("trace-1").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,712 @@
+//! Circuits loaded from a declarative description.
+//!
+//! A [`FlowSpec`] lists nodes, each naming a transition registered in a
+//! [`FlowRegistry`], and the edges between them. [`FlowRegistry::load`]
+//! turns it into a runnable Axon whose Schematic keeps the spec's node ids,
+//! labels, annotations and positions, so a visual editor can render the
+//! Schematic and write its layout back with [`FlowSpec::with_positions_from`].
+//!
+//! ```rust,ignore
+//! let registry = FlowRegistry::<Order, String>::new()
+//!     .register_transition("validate", ValidateOrder)
+//!     .register("charge", |config| {
+//!         let provider = config["provider"].as_str().ok_or("missing provider")?;
+//!         Ok(ChargeCard::new(provider))
+//!     });
+//! let spec = FlowSpec::from_json(&std::fs::read_to_string("checkout.flow.json")?)?;
+//! let checkout = registry.load(&spec)?;
+//! ```
+//!
+//! ```json
+//! {
+//!   "name": "checkout",
+//!   "nodes": [
+//!     { "id": "validate", "transition": "validate" },
+//!     { "id": "charge", "transition": "charge", "config": { "provider": "stripe" } },
+//!     { "id": "review", "transition": "manual_review" }
+//!   ],
+//!   "edges": [
+//!     { "from": "validate", "to": "charge" },
+//!     { "from": "validate", "to": "review", "branch": "suspicious" }
+//!   ]
+//! }
+//! ```
+//!
+//! `FlowSpec` is plain serde, so YAML or TOML descriptions load with the
+//! matching serde crate (`serde_yaml::from_str::<FlowSpec>(..)`).
+//!
+//! Every node maps a `T` to a `T`. The graph must be a tree rooted at the
+//! one node without incoming edges: each node has at most one incoming edge,
+//! at most one plain edge (its `Next`) and any number of `branch` edges,
+//! which become [`Axon::branch`] arms.
+
+use crate::axon::Axon;
+use async_trait::async_trait;
+use ranvier_core::bus::{Bus, BusAccessPolicy, BusTypeRef};
+use ranvier_core::metadata::NodeAnnotations;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::schematic::{NodeKind, Position, Schematic, SourceLocation};
+use ranvier_core::transition::{ResourceRequirement, SideEffect, Transition};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+/// A circuit described as data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowSpec {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub nodes: Vec<FlowNodeSpec>,
+    #[serde(default)]
+    pub edges: Vec<FlowEdgeSpec>,
+}
+
+/// One node of a [`FlowSpec`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowNodeSpec {
+    /// Node id, kept as the Schematic node id.
+    pub id: String,
+    /// Name of the transition in the [`FlowRegistry`].
+    pub transition: String,
+    /// Node label; defaults to `id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Passed to the transition's factory.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub config: Value,
+    #[serde(default, skip_serializing_if = "NodeAnnotations::is_empty")]
+    pub annotations: NodeAnnotations,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Position>,
+}
+
+/// An edge of a [`FlowSpec`]: the `Next` of `from`, or the arm taken when
+/// `from` returns `Outcome::Branch(branch, ..)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowEdgeSpec {
+    pub from: String,
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+}
+
+/// Why a [`FlowSpec`] could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlowSpecError {
+    /// The description is not a valid `FlowSpec` document.
+    Parse(String),
+    /// The spec has no nodes.
+    Empty,
+    DuplicateNode(String),
+    /// A node names a transition the registry does not know.
+    UnknownTransition {
+        node: String,
+        transition: String,
+    },
+    /// The transition's factory rejected the node's `config`.
+    InvalidConfig {
+        node: String,
+        message: String,
+    },
+    /// An edge names a node the spec does not contain.
+    UnknownNode {
+        edge_from: String,
+        edge_to: String,
+    },
+    /// Not exactly one node lacks incoming edges.
+    StartNode {
+        candidates: Vec<String>,
+    },
+    /// A node has more than one incoming edge.
+    MergingEdges {
+        node: String,
+    },
+    /// A node has more than one plain (`Next`) edge.
+    MultipleNext {
+        node: String,
+    },
+    /// A node has two edges for the same branch id.
+    DuplicateBranch {
+        node: String,
+        branch: String,
+    },
+    /// Nodes no edge path reaches from the start node, e.g. on a cycle.
+    Unreachable {
+        nodes: Vec<String>,
+    },
+}
+
+impl fmt::Display for FlowSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "Invalid flow spec: {}", e),
+            Self::Empty => write!(f, "Flow spec has no nodes"),
+            Self::DuplicateNode(id) => write!(f, "Flow spec declares node '{}' twice", id),
+            Self::UnknownTransition { node, transition } => write!(
+                f,
+                "Node '{}' uses unregistered transition '{}'",
+                node, transition
+            ),
+            Self::InvalidConfig { node, message } => {
+                write!(f, "Invalid config for node '{}': {}", node, message)
+            }
+            Self::UnknownNode { edge_from, edge_to } => write!(
+                f,
+                "Edge {} -> {} names a node the spec does not declare",
+                edge_from, edge_to
+            ),
+            Self::StartNode { candidates } if candidates.is_empty() => {
+                write!(
+                    f,
+                    "Flow spec has no start node (every node has an incoming edge)"
+                )
+            }
+            Self::StartNode { candidates } => write!(
+                f,
+                "Flow spec has several start nodes: {}",
+                candidates.join(", ")
+            ),
+            Self::MergingEdges { node } => {
+                write!(f, "Node '{}' has more than one incoming edge", node)
+            }
+            Self::MultipleNext { node } => {
+                write!(f, "Node '{}' has more than one edge without a branch", node)
+            }
+            Self::DuplicateBranch { node, branch } => {
+                write!(f, "Node '{}' has two edges for branch '{}'", node, branch)
+            }
+            Self::Unreachable { nodes } => write!(
+                f,
+                "Nodes not reachable from the start node: {}",
+                nodes.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FlowSpecError {}
+
+impl FlowSpec {
+    pub fn from_json(json: &str) -> Result<Self, FlowSpecError> {
+        serde_json::from_str(json).map_err(|e| FlowSpecError::Parse(e.to_string()))
+    }
+
+    pub fn from_value(value: Value) -> Result<Self, FlowSpecError> {
+        serde_json::from_value(value).map_err(|e| FlowSpecError::Parse(e.to_string()))
+    }
+
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Copy node positions from a Schematic, e.g. one laid out in an editor,
+    /// onto the nodes with the same id. Branch arms are searched too.
+    pub fn with_positions_from(mut self, schematic: &Schematic) -> Self {
+        let mut positions = HashMap::new();
+        collect_positions(schematic, &mut positions);
+        for node in &mut self.nodes {
+            if let Some(position) = positions.get(node.id.as_str()) {
+                node.position = Some((*position).clone());
+            }
+        }
+        self
+    }
+
+    /// Check that the graph is a tree and index its edges.
+    fn plan(&self) -> Result<FlowPlan<'_>, FlowSpecError> {
+        if self.nodes.is_empty() {
+            return Err(FlowSpecError::Empty);
+        }
+        let mut nodes = HashSet::new();
+        for node in &self.nodes {
+            if !nodes.insert(node.id.as_str()) {
+                return Err(FlowSpecError::DuplicateNode(node.id.clone()));
+            }
+        }
+
+        let mut next: HashMap<&str, &str> = HashMap::new();
+        let mut branches: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+        let mut incoming: HashSet<&str> = HashSet::new();
+        for edge in &self.edges {
+            if !nodes.contains(edge.from.as_str()) || !nodes.contains(edge.to.as_str()) {
+                return Err(FlowSpecError::UnknownNode {
+                    edge_from: edge.from.clone(),
+                    edge_to: edge.to.clone(),
+                });
+            }
+            if !incoming.insert(edge.to.as_str()) {
+                return Err(FlowSpecError::MergingEdges {
+                    node: edge.to.clone(),
+                });
+            }
+            match &edge.branch {
+                None => {
+                    if next.insert(&edge.from, &edge.to).is_some() {
+                        return Err(FlowSpecError::MultipleNext {
+                            node: edge.from.clone(),
+                        });
+                    }
+                }
+                Some(branch) => {
+                    let arms = branches.entry(&edge.from).or_default();
+                    if arms.iter().any(|(id, _)| id == branch) {
+                        return Err(FlowSpecError::DuplicateBranch {
+                            node: edge.from.clone(),
+                            branch: branch.clone(),
+                        });
+                    }
+                    arms.push((branch, &edge.to));
+                }
+            }
+        }
+
+        let starts: Vec<&FlowNodeSpec> = self
+            .nodes
+            .iter()
+            .filter(|node| !incoming.contains(node.id.as_str()))
+            .collect();
+        let [start] = starts[..] else {
+            return Err(FlowSpecError::StartNode {
+                candidates: starts.iter().map(|node| node.id.clone()).collect(),
+            });
+        };
+
+        let plan = FlowPlan {
+            start: &start.id,
+            next,
+            branches,
+        };
+        let mut reached = HashSet::new();
+        let mut stack = vec![start.id.as_str()];
+        while let Some(id) = stack.pop() {
+            if reached.insert(id) {
+                stack.extend(plan.next.get(id));
+                stack.extend(plan.arms(id).iter().map(|(_, to)| *to));
+            }
+        }
+        if reached.len() < self.nodes.len() {
+            return Err(FlowSpecError::Unreachable {
+                nodes: self
+                    .nodes
+                    .iter()
+                    .filter(|node| !reached.contains(node.id.as_str()))
+                    .map(|node| node.id.clone())
+                    .collect(),
+            });
+        }
+        Ok(plan)
+    }
+}
+
+fn collect_positions<'s>(schematic: &'s Schematic, out: &mut HashMap<&'s str, &'s Position>) {
+    for node in &schematic.nodes {
+        if let Some(position) = &node.position {
+            out.insert(node.id.as_str(), position);
+        }
+        if let NodeKind::Subgraph(inner) = &node.kind {
+            collect_positions(inner, out);
+        }
+    }
+}
+
+struct FlowPlan<'a> {
+    start: &'a str,
+    next: HashMap<&'a str, &'a str>,
+    branches: HashMap<&'a str, Vec<(&'a str, &'a str)>>,
+}
+
+impl<'a> FlowPlan<'a> {
+    fn arms(&self, id: &str) -> &[(&'a str, &'a str)] {
+        self.branches.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+type DynTransition<T, E, Res> = Arc<dyn Transition<T, T, Error = E, Resources = Res>>;
+type TransitionFactory<T, E, Res> =
+    Arc<dyn Fn(&Value) -> Result<DynTransition<T, E, Res>, String> + Send + Sync>;
+
+/// Transitions a [`FlowSpec`] can name, by name.
+pub struct FlowRegistry<T, E, Res = ()> {
+    factories: HashMap<String, TransitionFactory<T, E, Res>>,
+}
+
+impl<T, E, Res> Default for FlowRegistry<T, E, Res> {
+    fn default() -> Self {
+        Self {
+            factories: HashMap::new(),
+        }
+    }
+}
+
+impl<T, E, Res> FlowRegistry<T, E, Res>
+where
+    T: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + fmt::Debug + 'static,
+    Res: ResourceRequirement,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a factory building the transition from a node's `config`.
+    pub fn register<F, Tr>(mut self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(&Value) -> Result<Tr, String> + Send + Sync + 'static,
+        Tr: Transition<T, T, Error = E, Resources = Res>,
+    {
+        let factory: TransitionFactory<T, E, Res> = Arc::new(move |config| {
+            factory(config).map(|transition| Arc::new(transition) as DynTransition<T, E, Res>)
+        });
+        self.factories.insert(name.into(), factory);
+        self
+    }
+
+    /// Register a transition that takes no configuration.
+    pub fn register_transition<Tr>(self, name: impl Into<String>, transition: Tr) -> Self
+    where
+        Tr: Transition<T, T, Error = E, Resources = Res> + Clone,
+    {
+        self.register(name, move |_| Ok(transition.clone()))
+    }
+
+    /// Registered transition names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Build the Axon described by `spec`.
+    pub fn load(&self, spec: &FlowSpec) -> Result<Axon<T, T, E, Res>, FlowSpecError> {
+        let plan = spec.plan()?;
+        let mut transitions = HashMap::new();
+        for node in &spec.nodes {
+            let factory = self.factories.get(&node.transition).ok_or_else(|| {
+                FlowSpecError::UnknownTransition {
+                    node: node.id.clone(),
+                    transition: node.transition.clone(),
+                }
+            })?;
+            let inner = factory(&node.config).map_err(|message| FlowSpecError::InvalidConfig {
+                node: node.id.clone(),
+                message,
+            })?;
+            let branches = plan.arms(&node.id).iter().map(|(id, _)| id.to_string());
+            transitions.insert(
+                node.id.as_str(),
+                SpecTransition {
+                    node: Arc::new(node.clone()),
+                    branches: branches.collect(),
+                    inner,
+                },
+            );
+        }
+
+        let mut axon = chain(&plan, &transitions, plan.start, Axon::new(&spec.name));
+        axon.schematic.description = spec.description.clone();
+        Ok(axon)
+    }
+}
+
+/// `axon` followed by `id` and everything after it.
+fn chain<T, E, Res>(
+    plan: &FlowPlan<'_>,
+    transitions: &HashMap<&str, SpecTransition<T, E, Res>>,
+    id: &str,
+    axon: Axon<T, T, E, Res>,
+) -> Axon<T, T, E, Res>
+where
+    T: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + fmt::Debug + 'static,
+    Res: ResourceRequirement,
+{
+    let axon = axon.then(transitions[id].clone());
+    let arms = plan.arms(id);
+    let next = plan.next.get(id).copied();
+    if arms.is_empty() {
+        return match next {
+            Some(next) => chain(plan, transitions, next, axon),
+            None => axon,
+        };
+    }
+    axon.branch(|mut builder| {
+        for &(branch, target) in arms {
+            let label = transitions[target].label();
+            builder = builder.on(
+                branch,
+                chain(plan, transitions, target, Axon::start(&label)),
+            );
+        }
+        builder.otherwise(match next {
+            Some(next) => {
+                let label = transitions[next].label();
+                chain(plan, transitions, next, Axon::start(&label))
+            }
+            None => Axon::start("Next"),
+        })
+    })
+}
+
+/// A registered transition placed on a spec node.
+struct SpecTransition<T, E, Res> {
+    node: Arc<FlowNodeSpec>,
+    /// Branch ids the spec routes, on top of the transition's own.
+    branches: Vec<String>,
+    inner: DynTransition<T, E, Res>,
+}
+
+impl<T, E, Res> Clone for SpecTransition<T, E, Res> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+            branches: self.branches.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl<T, E, Res> Transition<T, T> for SpecTransition<T, E, Res>
+where
+    T: Send + Sync + 'static,
+    E: Send + Sync + fmt::Debug + 'static,
+    Res: ResourceRequirement,
+{
+    type Error = E;
+    type Resources = Res;
+
+    fn label(&self) -> String {
+        self.node
+            .label
+            .clone()
+            .unwrap_or_else(|| self.node.id.clone())
+    }
+
+    fn description(&self) -> Option<String> {
+        self.node
+            .description
+            .clone()
+            .or_else(|| self.inner.description())
+    }
+
+    fn node_id(&self) -> Option<String> {
+        Some(self.node.id.clone())
+    }
+
+    fn position(&self) -> Option<(f32, f32)> {
+        self.node
+            .position
+            .as_ref()
+            .map(|position| (position.x, position.y))
+            .or_else(|| self.inner.position())
+    }
+
+    fn bus_access_policy(&self) -> Option<BusAccessPolicy> {
+        self.inner.bus_access_policy()
+    }
+
+    fn required_resources(&self) -> Vec<BusTypeRef> {
+        self.inner.required_resources()
+    }
+
+    fn source_location(&self) -> Option<SourceLocation> {
+        self.inner.source_location()
+    }
+
+    fn branches(&self) -> Vec<String> {
+        let mut branches = self.inner.branches();
+        for branch in &self.branches {
+            if !branches.contains(branch) {
+                branches.push(branch.clone());
+            }
+        }
+        branches
+    }
+
+    fn annotations(&self) -> NodeAnnotations {
+        if self.node.annotations.is_empty() {
+            self.inner.annotations()
+        } else {
+            self.node.annotations.clone()
+        }
+    }
+
+    fn input_schema(&self) -> Option<Value> {
+        self.inner.input_schema()
+    }
+
+    fn describe(&self) -> Vec<SideEffect> {
+        self.inner.describe()
+    }
+
+    fn estimate(&self, state: &T, resources: &Res, bus: &Bus) -> Option<Outcome<T, E>> {
+        self.inner.estimate(state, resources, bus)
+    }
+
+    async fn run(&self, state: T, resources: &Res, bus: &mut Bus) -> Outcome<T, E> {
+        self.inner.run(state, resources, bus).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::closure_transition::ClosureTransition;
+
+    fn registry() -> FlowRegistry<i64, String> {
+        FlowRegistry::new()
+            .register("add", |config| {
+                let by = config["by"].as_i64().ok_or("`by` must be an integer")?;
+                Ok(ClosureTransition::new(
+                    "add",
+                    move |n: i64, _bus: &mut Bus| Outcome::<i64, String>::next(n + by),
+                ))
+            })
+            .register_transition(
+                "route",
+                ClosureTransition::new("route", |n: i64, _bus: &mut Bus| {
+                    if n > 10 {
+                        Outcome::<i64, String>::branch("big", Some(serde_json::json!(n)))
+                    } else {
+                        Outcome::next(n)
+                    }
+                }),
+            )
+    }
+
+    fn spec() -> FlowSpec {
+        FlowSpec::from_json(
+            r#"{
+                "name": "Sizing",
+                "nodes": [
+                    { "id": "double", "transition": "add", "config": { "by": 5 } },
+                    { "id": "route", "transition": "route", "label": "Route",
+                      "annotations": { "owner": "sizing-oncall" }, "position": { "x": 1.0, "y": 2.0 } },
+                    { "id": "shrink", "transition": "add", "config": { "by": -10 } },
+                    { "id": "bump", "transition": "add", "config": { "by": 1 } }
+                ],
+                "edges": [
+                    { "from": "double", "to": "route" },
+                    { "from": "route", "to": "shrink", "branch": "big" },
+                    { "from": "route", "to": "bump" }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn loads_a_runnable_axon_keeping_spec_ids() {
+        let axon = registry().load(&spec()).unwrap();
+        let mut bus = Bus::new();
+        assert!(matches!(
+            axon.execute(1, &(), &mut bus).await,
+            Outcome::Next(7)
+        ));
+        assert!(matches!(
+            axon.execute(8, &(), &mut bus).await,
+            Outcome::Next(3)
+        ));
+
+        let route = axon
+            .schematic
+            .nodes
+            .iter()
+            .find(|node| node.id == "route")
+            .unwrap();
+        assert_eq!(route.label, "Route");
+        assert_eq!(route.metadata.branches, ["big"]);
+        assert_eq!(
+            route.metadata.annotations.owner.as_deref(),
+            Some("sizing-oncall")
+        );
+        assert!(axon.validate().is_ok());
+    }
+
+    #[test]
+    fn positions_round_trip_through_the_schematic() {
+        let spec = spec();
+        let mut schematic = registry().load(&spec).unwrap().schematic;
+        let mut moved = false;
+        for node in &mut schematic.nodes {
+            if let NodeKind::Subgraph(inner) = &mut node.kind {
+                for inner in &mut inner.nodes {
+                    if inner.id == "shrink" {
+                        inner.position = Some(Position { x: 9.0, y: 9.0 });
+                        moved = true;
+                    }
+                }
+            }
+        }
+        assert!(moved);
+
+        let spec = spec.with_positions_from(&schematic);
+        let position = |id: &str| {
+            let node = spec.nodes.iter().find(|node| node.id == id).unwrap();
+            node.position.as_ref().map(|p| (p.x, p.y))
+        };
+        assert_eq!(position("shrink"), Some((9.0, 9.0)));
+        assert_eq!(position("route"), Some((1.0, 2.0)));
+        assert_eq!(position("bump"), None);
+    }
+
+    #[test]
+    fn rejects_specs_that_are_not_trees_of_known_transitions() {
+        let load = |edit: &dyn Fn(&mut FlowSpec)| {
+            let mut spec = spec();
+            edit(&mut spec);
+            registry().load(&spec).err()
+        };
+
+        assert_eq!(
+            load(&|spec| spec.nodes[2].transition = "divide".into()),
+            Some(FlowSpecError::UnknownTransition {
+                node: "shrink".into(),
+                transition: "divide".into(),
+            })
+        );
+        assert_eq!(
+            load(&|spec| spec.nodes[0].config = serde_json::json!({ "by": "two" })),
+            Some(FlowSpecError::InvalidConfig {
+                node: "double".into(),
+                message: "`by` must be an integer".into(),
+            })
+        );
+        assert_eq!(
+            load(&|spec| spec.edges[1].branch = None),
+            Some(FlowSpecError::MultipleNext {
+                node: "route".into()
+            })
+        );
+        assert_eq!(
+            load(&|spec| spec.edges.push(FlowEdgeSpec {
+                from: "shrink".into(),
+                to: "bump".into(),
+                branch: None,
+            })),
+            Some(FlowSpecError::MergingEdges {
+                node: "bump".into()
+            })
+        );
+        assert_eq!(
+            load(&|spec| {
+                spec.edges.remove(0);
+            }),
+            Some(FlowSpecError::StartNode {
+                candidates: vec!["double".into(), "route".into()],
+            })
+        );
+        // A cycle leaves its nodes without a path from the start node.
+        assert_eq!(
+            load(&|spec| spec.edges[0].to = "double".into()),
+            Some(FlowSpecError::Unreachable {
+                nodes: vec!["double".into()]
+            })
+        );
+    }
+}
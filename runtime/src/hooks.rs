@@ -0,0 +1,284 @@
+//! Execution hooks called around every node.
+//!
+//! An [`ExecutionHook`] sees each node start and finish and the final outcome
+//! of each execution, which covers metrics, auditing and feature-flag gates
+//! without wrapping every transition. Hooks are attached to one Axon with
+//! [`Axon::with_hook`] or to every Axon in the process with
+//! [`register_global_hook`]; global hooks run first, then per-Axon hooks, each
+//! in registration order.
+//!
+//! ```rust,ignore
+//! struct Kill(FeatureFlags);
+//!
+//! #[async_trait]
+//! impl ExecutionHook for Kill {
+//!     async fn on_node_start(&self, node: &NodeContext, _bus: &Bus) -> HookDecision {
+//!         if self.0.disabled(&node.label) {
+//!             HookDecision::Reject(format!("{} is switched off", node.label))
+//!         } else {
+//!             HookDecision::Continue
+//!         }
+//!     }
+//! }
+//!
+//! let axon = Axon::new("checkout").with_hook(Kill(flags)).then(ChargeCard);
+//! ```
+//!
+//! A rejected node does not run; the execution ends with an
+//! [`EXECUTION_HOOK_REJECTED`] emit. Hooks apply to chained nodes, not to the
+//! branches inside a join, race or parallel step.
+//!
+//! [`Axon::with_hook`]: crate::axon::Axon::with_hook
+
+use async_trait::async_trait;
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::schematic::Schematic;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+/// Emitted when an [`ExecutionHook`] rejects a node.
+pub const EXECUTION_HOOK_REJECTED: &str = "execution.hook.rejected";
+
+/// The node a hook is called for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeContext {
+    pub circuit: String,
+    pub node_id: String,
+    pub label: String,
+    pub step: u64,
+}
+
+impl NodeContext {
+    pub(crate) fn on_bus(bus: &Bus, node_id: &str, label: &str, step: u64) -> Self {
+        Self {
+            circuit: bus
+                .read::<Schematic>()
+                .map(|s| s.name.clone())
+                .unwrap_or_default(),
+            node_id: node_id.to_string(),
+            label: label.to_string(),
+            step,
+        }
+    }
+}
+
+/// Whether a node may run, as decided by [`ExecutionHook::on_node_start`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookDecision {
+    Continue,
+    /// Skip the node and end the execution, with a reason for the emit payload.
+    Reject(String),
+}
+
+/// Callbacks around node execution. Every method defaults to a no-op.
+#[async_trait]
+pub trait ExecutionHook: Send + Sync {
+    /// Called before a node runs.
+    async fn on_node_start(&self, _node: &NodeContext, _bus: &Bus) -> HookDecision {
+        HookDecision::Continue
+    }
+
+    /// Called after a node ran, with its serialized `Outcome`.
+    async fn on_node_end(
+        &self,
+        _node: &NodeContext,
+        _outcome: &Value,
+        _duration: Duration,
+        _bus: &Bus,
+    ) {
+    }
+
+    /// Called once per execution with the circuit's serialized `Outcome`.
+    async fn on_outcome(&self, _circuit: &str, _outcome: &Value, _bus: &Bus) {}
+}
+
+static GLOBAL_HOOKS: OnceLock<RwLock<Vec<Arc<dyn ExecutionHook>>>> = OnceLock::new();
+
+fn global_hooks() -> &'static RwLock<Vec<Arc<dyn ExecutionHook>>> {
+    GLOBAL_HOOKS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Register a hook for every Axon executed in this process.
+pub fn register_global_hook<H>(hook: H)
+where
+    H: ExecutionHook + 'static,
+{
+    match global_hooks().write() {
+        Ok(mut hooks) => hooks.push(Arc::new(hook)),
+        Err(poisoned) => poisoned.into_inner().push(Arc::new(hook)),
+    }
+}
+
+/// Remove every globally registered hook.
+pub fn clear_global_hooks() {
+    match global_hooks().write() {
+        Ok(mut hooks) => hooks.clear(),
+        Err(poisoned) => poisoned.into_inner().clear(),
+    }
+}
+
+/// The hooks of one execution, carried on the Bus.
+#[derive(Clone)]
+pub(crate) struct ExecutionHooks(Arc<[Arc<dyn ExecutionHook>]>);
+
+impl ExecutionHooks {
+    /// Global hooks followed by `local`, or `None` when there are none.
+    pub(crate) fn collect(local: &[Arc<dyn ExecutionHook>]) -> Option<Self> {
+        let global = match global_hooks().read() {
+            Ok(hooks) => hooks.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        if global.is_empty() && local.is_empty() {
+            return None;
+        }
+        Some(Self(
+            global.into_iter().chain(local.iter().cloned()).collect(),
+        ))
+    }
+
+    pub(crate) async fn outcome(&self, circuit: &str, outcome: &Value, bus: &Bus) {
+        for hook in self.0.iter() {
+            hook.on_outcome(circuit, outcome, bus).await;
+        }
+    }
+}
+
+/// Hooks in flight for one node.
+pub(crate) struct NodeHooks {
+    hooks: ExecutionHooks,
+    node: NodeContext,
+    started: Instant,
+}
+
+impl NodeHooks {
+    /// Call `on_node_start` on the Bus's hooks. `Ok(None)` means there are
+    /// none; `Err` carries the emit payload of a rejection, after which
+    /// later hooks are not asked.
+    pub(crate) async fn start(
+        bus: &Bus,
+        node_id: &str,
+        node_label: &str,
+        step: u64,
+    ) -> Result<Option<Self>, Value> {
+        let Some(hooks) = bus.read::<ExecutionHooks>().cloned() else {
+            return Ok(None);
+        };
+        let node = NodeContext::on_bus(bus, node_id, node_label, step);
+        for hook in hooks.0.iter() {
+            if let HookDecision::Reject(reason) = hook.on_node_start(&node, bus).await {
+                tracing::info!(node_id = %node_id, reason = %reason, "Node rejected by execution hook");
+                return Err(serde_json::json!({
+                    "node_id": node.node_id,
+                    "label": node.label,
+                    "reason": reason,
+                }));
+            }
+        }
+        Ok(Some(Self {
+            hooks,
+            node,
+            started: Instant::now(),
+        }))
+    }
+
+    pub(crate) async fn end<Out, E>(self, outcome: &Outcome<Out, E>, bus: &Bus)
+    where
+        Out: Serialize,
+        E: Serialize,
+    {
+        let outcome = outcome.to_json_value();
+        let duration = self.started.elapsed();
+        for hook in self.hooks.0.iter() {
+            hook.on_node_end(&self.node, &outcome, duration, bus).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axon::Axon;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+        disabled: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl ExecutionHook for Arc<Recorder> {
+        async fn on_node_start(&self, node: &NodeContext, _bus: &Bus) -> HookDecision {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("start {}", node.label));
+            if self.disabled == Some(node.label.as_str()) {
+                return HookDecision::Reject("switched off".into());
+            }
+            HookDecision::Continue
+        }
+
+        async fn on_node_end(
+            &self,
+            node: &NodeContext,
+            _outcome: &Value,
+            _duration: Duration,
+            _bus: &Bus,
+        ) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("end {}", node.label));
+        }
+
+        async fn on_outcome(&self, circuit: &str, outcome: &Value, _bus: &Bus) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("outcome {circuit} {outcome}"));
+        }
+    }
+
+    fn pipeline(recorder: &Arc<Recorder>) -> Axon<i32, i32, String> {
+        Axon::<i32, i32, String>::new("Checkout")
+            .with_hook(recorder.clone())
+            .then_fn("Reserve", |n: i32, _bus| Outcome::next(n + 1))
+            .then_fn("Charge", |n: i32, _bus| Outcome::next(n * 10))
+    }
+
+    #[tokio::test]
+    async fn hooks_see_each_node_and_the_final_outcome() {
+        let recorder = Arc::new(Recorder::default());
+        let outcome = pipeline(&recorder).execute(1, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Next(20)));
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec![
+                "start Reserve",
+                "end Reserve",
+                "start Charge",
+                "end Charge",
+                r#"outcome Checkout {"Next":20}"#,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_node_does_not_run() {
+        let recorder = Arc::new(Recorder {
+            disabled: Some("Charge"),
+            ..Default::default()
+        });
+        let outcome = pipeline(&recorder).execute(1, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Emit(ref kind, _) if kind == EXECUTION_HOOK_REJECTED));
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(
+            events[..3],
+            ["start Reserve", "end Reserve", "start Charge"]
+        );
+    }
+}
@@ -0,0 +1,551 @@
+//! Idempotent executions keyed by a caller-supplied key.
+//!
+//! A caller that may send the same request twice (client retries, webhook
+//! redelivery) attaches an [`IdempotencyKey`] to the Bus. With an
+//! [`IdempotencyHandle`] in the Bus as well, [`Axon::execute`] looks the key
+//! up first and, if an execution with that key already completed, returns
+//! the recorded outcome without running any node:
+//!
+//! ```rust,ignore
+//! let store = IdempotencyHandle::from_store(InMemoryIdempotencyStore::new());
+//!
+//! let mut bus = Bus::new();
+//! bus.insert(store.clone());
+//! bus.insert(IdempotencyKey::new(request.headers["Idempotency-Key"]));
+//! let outcome = checkout.execute(order, &resources, &mut bus).await;
+//! ```
+//!
+//! Keys are scoped to the circuit name. Only `Next`, `Branch`, `Jump` and
+//! `Emit` outcomes of the circuit are recorded: a `Fault`, `Suspend` or
+//! `Retry`, or an emit the runtime raised instead of running the circuit
+//! (a panic, a failed resumption), leaves the key free, so the caller can
+//! try again with it. Reusing a key with a
+//! different input is refused with an [`IDEMPOTENCY_KEY_CONFLICT`] emit.
+//!
+//! An execution claims its key before running. While it runs, another
+//! execution with the same key gets an [`IDEMPOTENCY_KEY_IN_PROGRESS`] emit
+//! instead of running the circuit a second time.
+//!
+//! [`Axon::execute`]: crate::axon::Axon::execute
+
+use crate::journal::input_digest;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use ranvier_core::bus::Bus;
+pub use ranvier_core::idempotency::IdempotencyKey;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::timeline::Timestamp;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Emitted when an idempotency key is reused with a different input.
+pub const IDEMPOTENCY_KEY_CONFLICT: &str = "execution.idempotency.conflict";
+
+/// Emitted when an execution under the same key is still running.
+pub const IDEMPOTENCY_KEY_IN_PROGRESS: &str = "execution.idempotency.in_progress";
+
+/// A completed execution as recorded by an [`IdempotencyStore`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    pub circuit: String,
+    pub key: String,
+    /// Digest of the input the key was first used with.
+    pub input_digest: String,
+    /// The serialized `Outcome<Out, E>`.
+    pub outcome: Value,
+    pub completed_at: Timestamp,
+}
+
+/// What [`IdempotencyStore::claim`] found under a key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyClaim {
+    /// The key was free and is now held by the caller.
+    Claimed,
+    /// Another execution holds the key, with the digest of its input.
+    InProgress { input_digest: String },
+    /// An execution under the key already completed.
+    Completed(IdempotencyRecord),
+}
+
+/// Storage for completed executions, keyed by circuit and idempotency key.
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    async fn load(&self, circuit: &str, key: &str) -> Result<Option<IdempotencyRecord>>;
+
+    /// Save a record, replacing any earlier one for the same circuit and key
+    /// and ending its claim.
+    async fn save(&self, record: IdempotencyRecord) -> Result<()>;
+
+    /// Hold a key for an execution about to run, atomically: when several
+    /// callers claim a free key at once, only one of them gets
+    /// [`IdempotencyClaim::Claimed`].
+    async fn claim(&self, circuit: &str, key: &str, input_digest: &str)
+    -> Result<IdempotencyClaim>;
+
+    /// Free a claimed key without recording an outcome.
+    async fn release(&self, circuit: &str, key: &str) -> Result<()>;
+}
+
+/// Bus-insertable handle to the [`IdempotencyStore`] used by `execute`.
+#[derive(Clone)]
+pub struct IdempotencyHandle {
+    inner: Arc<dyn IdempotencyStore>,
+}
+
+impl std::fmt::Debug for IdempotencyHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdempotencyHandle").finish_non_exhaustive()
+    }
+}
+
+impl IdempotencyHandle {
+    pub fn from_store<S>(store: S) -> Self
+    where
+        S: IdempotencyStore + 'static,
+    {
+        Self {
+            inner: Arc::new(store),
+        }
+    }
+
+    pub fn from_arc(store: Arc<dyn IdempotencyStore>) -> Self {
+        Self { inner: store }
+    }
+
+    pub fn store(&self) -> Arc<dyn IdempotencyStore> {
+        self.inner.clone()
+    }
+}
+
+/// Process-local [`IdempotencyStore`], mainly for tests and single instances.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryIdempotencyStore {
+    inner: Arc<Mutex<InMemoryIdempotency>>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryIdempotency {
+    records: HashMap<(String, String), IdempotencyRecord>,
+    /// Input digests of the executions holding a key.
+    claims: HashMap<(String, String), String>,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> Result<std::sync::MutexGuard<'_, InMemoryIdempotency>> {
+        self.inner
+            .lock()
+            .map_err(|_| anyhow!("idempotency store lock poisoned"))
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn load(&self, circuit: &str, key: &str) -> Result<Option<IdempotencyRecord>> {
+        Ok(self
+            .state()?
+            .records
+            .get(&(circuit.to_string(), key.to_string()))
+            .cloned())
+    }
+
+    async fn save(&self, record: IdempotencyRecord) -> Result<()> {
+        let mut state = self.state()?;
+        let id = (record.circuit.clone(), record.key.clone());
+        state.claims.remove(&id);
+        state.records.insert(id, record);
+        Ok(())
+    }
+
+    async fn claim(
+        &self,
+        circuit: &str,
+        key: &str,
+        input_digest: &str,
+    ) -> Result<IdempotencyClaim> {
+        let mut state = self.state()?;
+        let id = (circuit.to_string(), key.to_string());
+        if let Some(record) = state.records.get(&id) {
+            return Ok(IdempotencyClaim::Completed(record.clone()));
+        }
+        if let Some(held) = state.claims.get(&id) {
+            return Ok(IdempotencyClaim::InProgress {
+                input_digest: held.clone(),
+            });
+        }
+        state.claims.insert(id, input_digest.to_string());
+        Ok(IdempotencyClaim::Claimed)
+    }
+
+    async fn release(&self, circuit: &str, key: &str) -> Result<()> {
+        self.state()?
+            .claims
+            .remove(&(circuit.to_string(), key.to_string()));
+        Ok(())
+    }
+}
+
+/// An execution that carries an idempotency key and a store.
+pub(crate) struct IdempotentExecution {
+    store: Arc<dyn IdempotencyStore>,
+    circuit: String,
+    key: String,
+    input_digest: String,
+}
+
+impl IdempotentExecution {
+    /// Prepare the lookup, or `None` when the Bus lacks a key or a store.
+    pub(crate) fn from_bus<In: Serialize>(bus: &Bus, circuit: &str, input: &In) -> Option<Self> {
+        let key = bus.read::<IdempotencyKey>()?.0.clone();
+        let store = bus.read::<IdempotencyHandle>()?.store();
+        let input = serde_json::to_value(input).unwrap_or(Value::Null);
+        Some(Self {
+            store,
+            circuit: circuit.to_string(),
+            key,
+            input_digest: input_digest(&input),
+        })
+    }
+
+    /// Claim the key, or the outcome to return instead of executing: the
+    /// recorded one, or a conflict or in-progress emit.
+    pub(crate) async fn claim<Out, E>(self) -> Result<ClaimedKey, Outcome<Out, E>>
+    where
+        Out: DeserializeOwned,
+        E: DeserializeOwned,
+    {
+        let claim = self
+            .store
+            .claim(&self.circuit, &self.key, &self.input_digest)
+            .await;
+        let (record_digest, outcome) = match claim {
+            Ok(IdempotencyClaim::Claimed) => return Ok(ClaimedKey::new(self, true)),
+            Ok(IdempotencyClaim::InProgress { input_digest }) => (input_digest, None),
+            Ok(IdempotencyClaim::Completed(record)) => (record.input_digest, Some(record.outcome)),
+            Err(error) => {
+                tracing::warn!(
+                    circuit = %self.circuit,
+                    key = %self.key,
+                    error = %error,
+                    "Idempotency claim failed; executing"
+                );
+                return Ok(ClaimedKey::new(self, false));
+            }
+        };
+        if record_digest != self.input_digest {
+            tracing::warn!(
+                circuit = %self.circuit,
+                key = %self.key,
+                "Idempotency key reused with a different input"
+            );
+            return Err(self.emit(IDEMPOTENCY_KEY_CONFLICT));
+        }
+        let Some(outcome) = outcome else {
+            tracing::info!(
+                circuit = %self.circuit,
+                key = %self.key,
+                "Execution with this idempotency key is still running"
+            );
+            return Err(self.emit(IDEMPOTENCY_KEY_IN_PROGRESS));
+        };
+        match serde_json::from_value(outcome) {
+            Ok(outcome) => {
+                tracing::info!(
+                    circuit = %self.circuit,
+                    key = %self.key,
+                    "Returning recorded outcome for idempotency key"
+                );
+                Err(outcome)
+            }
+            Err(error) => {
+                tracing::warn!(
+                    circuit = %self.circuit,
+                    key = %self.key,
+                    error = %error,
+                    "Recorded outcome no longer decodes; executing"
+                );
+                Ok(ClaimedKey::new(self, false))
+            }
+        }
+    }
+
+    fn emit<Out, E>(&self, event: &str) -> Outcome<Out, E> {
+        Outcome::emit(
+            event,
+            Some(serde_json::json!({
+                "circuit": self.circuit,
+                "key": self.key,
+            })),
+        )
+    }
+
+    async fn release(self) {
+        if let Err(error) = self.store.release(&self.circuit, &self.key).await {
+            tracing::warn!(
+                circuit = %self.circuit,
+                key = %self.key,
+                error = %error,
+                "Failed to release idempotency key"
+            );
+        }
+    }
+}
+
+/// A key held for a running execution. Dropping it without
+/// [`record`](Self::record), as a cancelled execution does, frees the key.
+pub(crate) struct ClaimedKey {
+    execution: Option<IdempotentExecution>,
+    held: bool,
+}
+
+impl ClaimedKey {
+    fn new(execution: IdempotentExecution, held: bool) -> Self {
+        Self {
+            execution: Some(execution),
+            held,
+        }
+    }
+
+    /// Record a completed outcome under the key, or free it for a retry.
+    pub(crate) async fn record<Out, E>(mut self, outcome: &Outcome<Out, E>)
+    where
+        Out: Serialize,
+        E: Serialize,
+    {
+        let Some(execution) = self.execution.take() else {
+            return;
+        };
+        let outcome = match outcome {
+            Outcome::Fault(_) | Outcome::Suspend(_, _) | Outcome::Retry { .. } => None,
+            Outcome::Emit(event, _) if is_runtime_emit(event) => None,
+            outcome => match serde_json::to_value(outcome) {
+                Ok(outcome) => Some(outcome),
+                Err(error) => {
+                    tracing::warn!(key = %execution.key, error = %error, "Outcome is not serializable");
+                    None
+                }
+            },
+        };
+        let Some(outcome) = outcome else {
+            if self.held {
+                execution.release().await;
+            }
+            return;
+        };
+        let record = IdempotencyRecord {
+            circuit: execution.circuit,
+            key: execution.key,
+            input_digest: execution.input_digest,
+            outcome,
+            completed_at: Timestamp::now(),
+        };
+        if let Err(error) = execution.store.save(record).await {
+            tracing::warn!(error = %error, "Failed to record idempotent outcome");
+        }
+    }
+}
+
+/// Emits the runtime raises in place of the circuit's own outcome, such as
+/// `ranvier.transition.panic` or `execution.resumption.payload_migration_failed`.
+fn is_runtime_emit(event: &str) -> bool {
+    ["ranvier.", "execution.", "iam.", "saga."]
+        .iter()
+        .any(|prefix| event.starts_with(prefix))
+}
+
+impl Drop for ClaimedKey {
+    fn drop(&mut self) {
+        if let Some(execution) = self.execution.take()
+            && self.held
+            && let Ok(runtime) = tokio::runtime::Handle::try_current()
+        {
+            runtime.spawn(execution.release());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axon::Axon;
+    use ranvier_core::iam::{IamError, IamIdentity, IamPolicy, IamToken, IamVerifier};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn charge(charges: Arc<AtomicUsize>) -> Axon<i32, i32, String> {
+        Axon::<i32, i32, String>::new("Checkout").then_fn("Charge", move |amount: i32, _bus| {
+            charges.fetch_add(1, Ordering::SeqCst);
+            Outcome::next(amount * 100)
+        })
+    }
+
+    fn bus_with(store: &IdempotencyHandle, key: &str) -> Bus {
+        let mut bus = Bus::new();
+        bus.insert(store.clone());
+        bus.insert(IdempotencyKey::new(key));
+        bus
+    }
+
+    #[tokio::test]
+    async fn completed_key_returns_the_recorded_outcome_without_rerunning() {
+        let charges = Arc::new(AtomicUsize::new(0));
+        let axon = charge(charges.clone());
+        let store = IdempotencyHandle::from_store(InMemoryIdempotencyStore::new());
+
+        let first = axon.execute(7, &(), &mut bus_with(&store, "order-1")).await;
+        let second = axon.execute(7, &(), &mut bus_with(&store, "order-1")).await;
+        assert!(matches!(first, Outcome::Next(700)));
+        assert!(matches!(second, Outcome::Next(700)));
+        assert_eq!(charges.load(Ordering::SeqCst), 1);
+
+        let conflict = axon.execute(8, &(), &mut bus_with(&store, "order-1")).await;
+        assert!(matches!(conflict, Outcome::Emit(ref kind, _) if kind == IDEMPOTENCY_KEY_CONFLICT));
+        assert_eq!(charges.load(Ordering::SeqCst), 1);
+
+        axon.execute(7, &(), &mut bus_with(&store, "order-2")).await;
+        assert_eq!(charges.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn faulted_executions_leave_the_key_free() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let axon =
+            Axon::<i32, i32, String>::new("Checkout").then_fn("Charge", move |n: i32, _bus| {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Outcome::fault("card declined".to_string())
+                } else {
+                    Outcome::next(n)
+                }
+            });
+        let store = IdempotencyHandle::from_store(InMemoryIdempotencyStore::new());
+
+        let first = axon.execute(1, &(), &mut bus_with(&store, "order-1")).await;
+        assert!(first.is_fault());
+        let retried = axon.execute(1, &(), &mut bus_with(&store, "order-1")).await;
+        assert!(matches!(retried, Outcome::Next(1)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct ChargeError {
+        code: u16,
+    }
+
+    #[tokio::test]
+    async fn runtime_emits_leave_the_key_free() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let axon = Axon::<i32, i32, ChargeError>::new("Checkout").then_fn(
+            "Charge",
+            move |n: i32, _bus| {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("gateway client crashed");
+                }
+                Outcome::next(n)
+            },
+        );
+        let store = IdempotencyHandle::from_store(InMemoryIdempotencyStore::new());
+
+        let first = axon.execute(1, &(), &mut bus_with(&store, "order-1")).await;
+        assert!(matches!(first, Outcome::Emit(ref kind, _) if kind == "ranvier.transition.panic"));
+        let retried = axon.execute(1, &(), &mut bus_with(&store, "order-1")).await;
+        assert!(matches!(retried, Outcome::Next(1)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[derive(Clone)]
+    struct AcceptToken(&'static str);
+
+    #[async_trait]
+    impl IamVerifier for AcceptToken {
+        async fn verify(&self, token: &str) -> Result<IamIdentity, IamError> {
+            if token == self.0 {
+                Ok(IamIdentity::new("alice"))
+            } else {
+                Err(IamError::InvalidToken(token.to_string()))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn iam_check_runs_before_the_key_is_looked_up() {
+        let charges = Arc::new(AtomicUsize::new(0));
+        let axon = charge(charges.clone()).with_iam(IamPolicy::RequireIdentity, AcceptToken("ok"));
+        let store = IdempotencyHandle::from_store(InMemoryIdempotencyStore::new());
+
+        let denied = axon.execute(7, &(), &mut bus_with(&store, "order-1")).await;
+        assert!(matches!(denied, Outcome::Emit(ref kind, _) if kind == "iam.missing_token"));
+
+        let mut bus = bus_with(&store, "order-1");
+        bus.insert(IamToken("ok".to_string()));
+        assert!(matches!(
+            axon.execute(7, &(), &mut bus).await,
+            Outcome::Next(700)
+        ));
+        assert_eq!(charges.load(Ordering::SeqCst), 1);
+
+        // A recorded outcome is not handed to a caller the IAM check refuses.
+        let mut bus = bus_with(&store, "order-1");
+        bus.insert(IamToken("forged".to_string()));
+        let replayed = axon.execute(7, &(), &mut bus).await;
+        assert!(
+            matches!(replayed, Outcome::Emit(ref kind, _) if kind == "iam.verification_failed")
+        );
+        assert_eq!(charges.load(Ordering::SeqCst), 1);
+    }
+
+    #[derive(Clone)]
+    struct SlowCharge(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl ranvier_core::transition::Transition<i32, i32> for SlowCharge {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, amount: i32, _res: &(), _bus: &mut Bus) -> Outcome<i32, String> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            Outcome::next(amount * 100)
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_executions_with_one_key_run_once() {
+        let charges = Arc::new(AtomicUsize::new(0));
+        let axon = Axon::<i32, i32, String>::new("Checkout").then(SlowCharge(charges.clone()));
+        let store = IdempotencyHandle::from_store(InMemoryIdempotencyStore::new());
+
+        let (mut first_bus, mut second_bus) =
+            (bus_with(&store, "order-1"), bus_with(&store, "order-1"));
+        let (first, second) = tokio::join!(
+            axon.execute(7, &(), &mut first_bus),
+            axon.execute(7, &(), &mut second_bus),
+        );
+        assert!(matches!(first, Outcome::Next(700)));
+        assert!(
+            matches!(second, Outcome::Emit(ref kind, _) if kind == IDEMPOTENCY_KEY_IN_PROGRESS)
+        );
+        assert_eq!(charges.load(Ordering::SeqCst), 1);
+        let replayed = axon.execute(7, &(), &mut bus_with(&store, "order-1")).await;
+        assert!(matches!(replayed, Outcome::Next(700)));
+        assert_eq!(charges.load(Ordering::SeqCst), 1);
+
+        // A dropped execution frees its key.
+        let mut bus = bus_with(&store, "order-2");
+        let dropped = tokio::time::timeout(
+            std::time::Duration::from_millis(10),
+            axon.execute(7, &(), &mut bus),
+        )
+        .await;
+        assert!(dropped.is_err());
+        tokio::task::yield_now().await;
+        let retried = axon.execute(7, &(), &mut bus_with(&store, "order-2")).await;
+        assert!(matches!(retried, Outcome::Next(700)));
+        assert_eq!(charges.load(Ordering::SeqCst), 3);
+    }
+}
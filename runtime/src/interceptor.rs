@@ -0,0 +1,244 @@
+//! Ordered interceptors wrapped around every node of an Axon.
+//!
+//! Where an [`ExecutionHook`](crate::hooks::ExecutionHook) only observes, an
+//! [`Interceptor`] takes part in execution: `before` runs ahead of each node
+//! with mutable access to the Bus and may stop the node, `after` runs once
+//! the node finished. Interceptors are registered with [`Axon::intercept`]
+//! and form an onion: `before` in registration order, `after` in reverse.
+//!
+//! ```rust,ignore
+//! struct TenantScope;
+//!
+//! #[async_trait]
+//! impl Interceptor for TenantScope {
+//!     async fn before(&self, _node: &NodeContext, bus: &mut Bus) -> Result<(), String> {
+//!         let tenant = bus.read::<Claims>().map(|c| c.tenant.clone()).ok_or("no tenant")?;
+//!         bus.insert(TenantId(tenant));
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let axon = Axon::new("Orders")
+//!     .intercept(RequireAuth)
+//!     .intercept(TenantScope)
+//!     .then(LoadOrder);
+//! ```
+//!
+//! A `before` error skips the node and faults the execution with
+//! [`InterceptorRejected`]; no `after` runs for that node. Error types opt in
+//! by deserializing from a string, as `String` does; other error types get a
+//! `ranvier.interceptor.rejected` emit instead. Like hooks, interceptors wrap
+//! chained nodes, not the branches inside a join, race or parallel step.
+//!
+//! [`Axon::intercept`]: crate::axon::Axon::intercept
+
+use crate::hooks::NodeContext;
+use async_trait::async_trait;
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Fault category recorded when an interceptor stops a node.
+pub const INTERCEPTOR_REJECTED: &str = "interceptor_rejected";
+
+/// A node stopped by an [`Interceptor::before`] error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InterceptorRejected {
+    pub interceptor: String,
+    pub node_label: String,
+    pub reason: String,
+}
+
+impl InterceptorRejected {
+    pub fn category(&self) -> &'static str {
+        INTERCEPTOR_REJECTED
+    }
+}
+
+impl std::fmt::Display for InterceptorRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{INTERCEPTOR_REJECTED}: {} stopped '{}': {}",
+            self.interceptor, self.node_label, self.reason
+        )
+    }
+}
+
+impl std::error::Error for InterceptorRejected {}
+
+impl From<InterceptorRejected> for String {
+    fn from(rejected: InterceptorRejected) -> Self {
+        rejected.to_string()
+    }
+}
+
+impl From<InterceptorRejected> for ranvier_core::error::RanvierError {
+    fn from(rejected: InterceptorRejected) -> Self {
+        Self::message(rejected.to_string())
+    }
+}
+
+/// Middleware around each node of an Axon.
+#[async_trait]
+pub trait Interceptor: Send + Sync {
+    /// Name used in [`InterceptorRejected`]. Defaults to the type name.
+    fn name(&self) -> String {
+        let full = std::any::type_name::<Self>();
+        full.split("::").last().unwrap_or(full).to_string()
+    }
+
+    /// Runs before the node. An error skips the node and faults the execution.
+    async fn before(&self, _node: &NodeContext, _bus: &mut Bus) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Runs after the node, with its serialized `Outcome`.
+    async fn after(&self, _node: &NodeContext, _outcome: &Value, _bus: &mut Bus) {}
+}
+
+/// The interceptor chain of one execution, carried on the Bus.
+#[derive(Clone)]
+pub(crate) struct Interceptors(Arc<[Arc<dyn Interceptor>]>);
+
+impl Interceptors {
+    pub(crate) fn new(chain: &[Arc<dyn Interceptor>]) -> Option<Self> {
+        (!chain.is_empty()).then(|| Self(chain.iter().cloned().collect()))
+    }
+}
+
+/// A node that passed every `before` and owes the chain its `after` calls.
+pub(crate) struct Intercepted {
+    chain: Interceptors,
+    node: NodeContext,
+}
+
+impl Intercepted {
+    /// Run the `before` chain. `Ok(None)` means the Bus has no interceptors.
+    pub(crate) async fn enter(
+        bus: &mut Bus,
+        node_id: &str,
+        node_label: &str,
+        step: u64,
+    ) -> Result<Option<Self>, InterceptorRejected> {
+        let Some(chain) = bus.read::<Interceptors>().cloned() else {
+            return Ok(None);
+        };
+        let node = NodeContext::on_bus(bus, node_id, node_label, step);
+        for interceptor in chain.0.iter() {
+            if let Err(reason) = interceptor.before(&node, bus).await {
+                return Err(InterceptorRejected {
+                    interceptor: interceptor.name(),
+                    node_label: node_label.to_string(),
+                    reason,
+                });
+            }
+        }
+        Ok(Some(Self { chain, node }))
+    }
+
+    pub(crate) async fn exit<Out, E>(self, outcome: &Outcome<Out, E>, bus: &mut Bus)
+    where
+        Out: Serialize,
+        E: Serialize,
+    {
+        let outcome = outcome.to_json_value();
+        for interceptor in self.chain.0.iter().rev() {
+            interceptor.after(&self.node, &outcome, bus).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axon::Axon;
+    use std::sync::Mutex;
+
+    struct Trace {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        deny: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl Interceptor for Trace {
+        fn name(&self) -> String {
+            self.name.to_string()
+        }
+
+        async fn before(&self, node: &NodeContext, _bus: &mut Bus) -> Result<(), String> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} before {}", self.name, node.label));
+            match self.deny {
+                Some(label) if label == node.label => Err("not allowed".into()),
+                _ => Ok(()),
+            }
+        }
+
+        async fn after(&self, node: &NodeContext, _outcome: &Value, _bus: &mut Bus) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} after {}", self.name, node.label));
+        }
+    }
+
+    fn pipeline(
+        log: &Arc<Mutex<Vec<String>>>,
+        deny: Option<&'static str>,
+    ) -> Axon<i32, i32, String> {
+        Axon::<i32, i32, String>::new("Orders")
+            .intercept(Trace {
+                name: "auth",
+                log: log.clone(),
+                deny,
+            })
+            .intercept(Trace {
+                name: "tenant",
+                log: log.clone(),
+                deny: None,
+            })
+            .then_fn("Load", |n: i32, _bus| Outcome::next(n + 1))
+            .then_fn("Save", |n: i32, _bus| Outcome::next(n * 2))
+    }
+
+    #[tokio::test]
+    async fn interceptors_wrap_each_node_in_onion_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let outcome = pipeline(&log, None).execute(1, &(), &mut Bus::new()).await;
+        assert!(matches!(outcome, Outcome::Next(4)));
+        assert_eq!(
+            log.lock().unwrap()[..4],
+            [
+                "auth before Load",
+                "tenant before Load",
+                "tenant after Load",
+                "auth after Load",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn before_error_faults_without_running_the_node() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let outcome = pipeline(&log, Some("Save"))
+            .execute(1, &(), &mut Bus::new())
+            .await;
+        match outcome {
+            Outcome::Fault(message) => {
+                assert!(
+                    message.contains("auth stopped 'Save': not allowed"),
+                    "{message}"
+                )
+            }
+            other => panic!("expected a fault, got {other:?}"),
+        }
+        let log = log.lock().unwrap();
+        assert_eq!(log.last().unwrap(), "auth before Save");
+    }
+}
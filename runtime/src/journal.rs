@@ -0,0 +1,346 @@
+//! Write-ahead execution journal for crash forensics.
+//!
+//! When a [`JournalHandle`] is on the Bus, [`Axon::execute`](crate::Axon::execute)
+//! appends a start record (trace ID, circuit, input digest) before the first
+//! node runs and a finish record once the outcome is known. A process that
+//! dies mid-execution leaves start records without a matching finish;
+//! [`ExecutionJournal::unfinished`] lists them on the next boot:
+//!
+//! ```rust,ignore
+//! let journal = JournalHandle::from_journal(FileJournal::open("/var/lib/app/journal.ndjson").with_inputs());
+//!
+//! for entry in journal.journal().unfinished().await? {
+//!     tracing::warn!(trace_id = %entry.trace_id, circuit = %entry.circuit, "execution never finished");
+//! }
+//! report_unfinished_to_dlq(journal.journal().as_ref(), dlq_sink.as_ref()).await?;
+//!
+//! bus.insert(journal);
+//! ```
+//!
+//! Journals that record inputs (see [`FileJournal::with_inputs`]) keep the
+//! serialized input next to its digest, so unfinished executions can be fed
+//! back through a [`Backfill`](crate::backfill::Backfill) with
+//! [`JournalEntry::input_as`].
+
+use anyhow::Result;
+use async_trait::async_trait;
+use ranvier_core::event::DlqSink;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+
+/// Start record of an execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub trace_id: String,
+    pub circuit: String,
+    /// FNV-1a digest of the JSON-serialized input (`fnv1a64:<hex>`).
+    pub input_digest: String,
+    pub started_at_ms: u64,
+    /// Serialized input, when the journal records inputs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<Value>,
+}
+
+impl JournalEntry {
+    /// Decode the recorded input, e.g. to replay it through a backfill.
+    pub fn input_as<T: DeserializeOwned>(&self) -> Result<T> {
+        let input = self.input.clone().ok_or_else(|| {
+            anyhow::anyhow!("journal entry {} has no recorded input", self.trace_id)
+        })?;
+        Ok(serde_json::from_value(input)?)
+    }
+}
+
+/// Append-only record of execution starts and finishes.
+#[async_trait]
+pub trait ExecutionJournal: Send + Sync {
+    /// Record that an execution is about to run. Must be durable on return.
+    async fn record_start(&self, entry: JournalEntry) -> Result<()>;
+
+    /// Record that the execution for `trace_id` produced an outcome.
+    async fn record_finish(&self, trace_id: &str, outcome_kind: &str) -> Result<()>;
+
+    /// Executions that started but never finished, oldest first.
+    async fn unfinished(&self) -> Result<Vec<JournalEntry>>;
+
+    /// Whether start records should carry the serialized input.
+    fn records_inputs(&self) -> bool {
+        false
+    }
+}
+
+/// Bus-insertable journal handle read by the executor.
+#[derive(Clone)]
+pub struct JournalHandle {
+    inner: Arc<dyn ExecutionJournal>,
+}
+
+impl std::fmt::Debug for JournalHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JournalHandle").finish_non_exhaustive()
+    }
+}
+
+impl JournalHandle {
+    pub fn from_journal<J>(journal: J) -> Self
+    where
+        J: ExecutionJournal + 'static,
+    {
+        Self {
+            inner: Arc::new(journal),
+        }
+    }
+
+    pub fn from_arc(journal: Arc<dyn ExecutionJournal>) -> Self {
+        Self { inner: journal }
+    }
+
+    pub fn journal(&self) -> Arc<dyn ExecutionJournal> {
+        self.inner.clone()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum JournalRecord {
+    Start(JournalEntry),
+    Finish {
+        trace_id: String,
+        outcome_kind: String,
+        finished_at_ms: u64,
+    },
+}
+
+/// Replay records in order, keeping starts that have no later finish.
+fn pending_from_records(records: impl IntoIterator<Item = JournalRecord>) -> Vec<JournalEntry> {
+    let mut pending: HashMap<String, JournalEntry> = HashMap::new();
+    for record in records {
+        match record {
+            JournalRecord::Start(entry) => {
+                pending.insert(entry.trace_id.clone(), entry);
+            }
+            JournalRecord::Finish { trace_id, .. } => {
+                pending.remove(&trace_id);
+            }
+        }
+    }
+    let mut entries: Vec<_> = pending.into_values().collect();
+    entries.sort_by_key(|entry| entry.started_at_ms);
+    entries
+}
+
+/// In-memory journal for tests and single-process tooling.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryJournal {
+    records: Arc<Mutex<Vec<JournalRecord>>>,
+    record_inputs: bool,
+}
+
+impl InMemoryJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_inputs(mut self) -> Self {
+        self.record_inputs = true;
+        self
+    }
+}
+
+#[async_trait]
+impl ExecutionJournal for InMemoryJournal {
+    async fn record_start(&self, entry: JournalEntry) -> Result<()> {
+        self.records
+            .lock()
+            .map_err(|_| anyhow::anyhow!("journal lock poisoned"))?
+            .push(JournalRecord::Start(entry));
+        Ok(())
+    }
+
+    async fn record_finish(&self, trace_id: &str, outcome_kind: &str) -> Result<()> {
+        self.records
+            .lock()
+            .map_err(|_| anyhow::anyhow!("journal lock poisoned"))?
+            .push(JournalRecord::Finish {
+                trace_id: trace_id.to_string(),
+                outcome_kind: outcome_kind.to_string(),
+                finished_at_ms: now_ms(),
+            });
+        Ok(())
+    }
+
+    async fn unfinished(&self) -> Result<Vec<JournalEntry>> {
+        let records = self
+            .records
+            .lock()
+            .map_err(|_| anyhow::anyhow!("journal lock poisoned"))?
+            .clone();
+        Ok(pending_from_records(records))
+    }
+
+    fn records_inputs(&self) -> bool {
+        self.record_inputs
+    }
+}
+
+/// Newline-delimited JSON journal, fsynced after every record.
+///
+/// A torn last line (the process died mid-write) is ignored on read.
+#[derive(Debug)]
+pub struct FileJournal {
+    path: PathBuf,
+    write_lock: tokio::sync::Mutex<()>,
+    record_inputs: bool,
+}
+
+impl FileJournal {
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            write_lock: tokio::sync::Mutex::new(()),
+            record_inputs: false,
+        }
+    }
+
+    pub fn with_inputs(mut self) -> Self {
+        self.record_inputs = true;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn append(&self, record: &JournalRecord) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        let _guard = self.write_lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(&line).await?;
+        file.sync_data().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ExecutionJournal for FileJournal {
+    async fn record_start(&self, entry: JournalEntry) -> Result<()> {
+        self.append(&JournalRecord::Start(entry)).await
+    }
+
+    async fn record_finish(&self, trace_id: &str, outcome_kind: &str) -> Result<()> {
+        self.append(&JournalRecord::Finish {
+            trace_id: trace_id.to_string(),
+            outcome_kind: outcome_kind.to_string(),
+            finished_at_ms: now_ms(),
+        })
+        .await
+    }
+
+    async fn unfinished(&self) -> Result<Vec<JournalEntry>> {
+        let content = match tokio::fs::read_to_string(&self.path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let records = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<JournalRecord>(line).ok());
+        Ok(pending_from_records(records))
+    }
+
+    fn records_inputs(&self) -> bool {
+        self.record_inputs
+    }
+}
+
+/// Store every unfinished execution as a dead letter; returns how many were stored.
+///
+/// The dead-letter payload is the JSON-encoded [`JournalEntry`].
+pub async fn report_unfinished_to_dlq(
+    journal: &dyn ExecutionJournal,
+    sink: &dyn DlqSink,
+) -> Result<usize> {
+    let entries = journal.unfinished().await?;
+    for entry in &entries {
+        let payload = serde_json::to_vec(entry)?;
+        sink.store_dead_letter(
+            &entry.trace_id,
+            &entry.circuit,
+            "",
+            "execution started but never finished",
+            &payload,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+    }
+    Ok(entries.len())
+}
+
+/// `fnv1a64:<hex>` digest of a serialized input.
+pub(crate) fn input_digest(input: &Value) -> String {
+    let bytes = serde_json::to_vec(input).unwrap_or_default();
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("fnv1a64:{hash:016x}")
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(trace_id: &str, started_at_ms: u64) -> JournalEntry {
+        JournalEntry {
+            trace_id: trace_id.to_string(),
+            circuit: "orders".to_string(),
+            input_digest: input_digest(&serde_json::json!({ "id": trace_id })),
+            started_at_ms,
+            input: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn file_journal_reports_starts_without_finish_and_skips_torn_lines() {
+        let path =
+            std::env::temp_dir().join(format!("ranvier-journal-{}.ndjson", uuid::Uuid::new_v4()));
+        let journal = FileJournal::open(&path);
+        journal.record_start(entry("a", 1)).await.unwrap();
+        journal.record_start(entry("b", 2)).await.unwrap();
+        journal.record_finish("a", "Next").await.unwrap();
+        journal.record_start(entry("c", 3)).await.unwrap();
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap()
+            .write_all(b"{\"record\":\"finish\",\"trace_")
+            .await
+            .unwrap();
+
+        let reopened = FileJournal::open(&path);
+        let unfinished = reopened.unfinished().await.unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let ids: Vec<_> = unfinished.iter().map(|e| e.trace_id.as_str()).collect();
+        assert_eq!(ids, ["b", "c"]);
+        assert_eq!(unfinished[0].input_digest, entry("b", 0).input_digest);
+        assert_ne!(unfinished[0].input_digest, unfinished[1].input_digest);
+    }
+}
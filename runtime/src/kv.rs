@@ -0,0 +1,1049 @@
+//! Typed key-value store synapse with versioned compare-and-swap.
+//!
+//! [`KvStore`] is the byte-level backend contract: every entry carries a
+//! version that changes on each write, and [`KvStore::compare_and_swap`]
+//! only writes when the caller's expected version still matches.
+//! [`KvSynapse`] layers JSON (de)serialization and an optional key prefix on
+//! top, and [`KvGet`], [`KvPut`] and [`KvCompareAndSwap`] expose it as
+//! transitions:
+//!
+//! ```rust,ignore
+//! let flags = KvSynapse::<FeatureFlags>::new(EtcdKvStore::new("http://etcd:2379"))
+//!     .with_prefix("config/flags/");
+//!
+//! let axon = Axon::<String, String, KvError>::new("ToggleFlags")
+//!     .then(KvGet::new(flags.clone()))
+//!     .then(ToggleBeta)                // Option<Versioned<FeatureFlags>> -> KvSwap<FeatureFlags>
+//!     .then(KvCompareAndSwap::new(flags));
+//! ```
+//!
+//! Backends:
+//!
+//! - [`InMemoryKvStore`] — always available, for tests and single-process use.
+//! - `EtcdKvStore` — etcd v3 JSON gateway (feature `kv-etcd`). Versions are
+//!   the key's `mod_revision`.
+//! - `DynamoDbKvStore` — DynamoDB JSON API with SigV4 signing (feature
+//!   `kv-dynamodb`). Versions are a numeric `version` attribute.
+//!   `DynamoDbKvStore::from_env` takes the region and credentials from the
+//!   default AWS provider chain.
+
+use async_trait::async_trait;
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::transition::Transition;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// Errors produced by key-value operations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KvError {
+    /// The backend rejected or failed the request.
+    Backend(String),
+    /// A stored value could not be encoded or decoded.
+    Serialization { key: String, message: String },
+    /// A compare-and-swap lost the race.
+    Conflict {
+        key: String,
+        expected_version: Option<u64>,
+        current_version: Option<u64>,
+    },
+}
+
+impl fmt::Display for KvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Backend(e) => write!(f, "KV backend error: {}", e),
+            Self::Serialization { key, message } => {
+                write!(f, "KV serialization error for '{}': {}", key, message)
+            }
+            Self::Conflict {
+                key,
+                expected_version,
+                current_version,
+            } => write!(
+                f,
+                "KV conflict on '{}': expected version {:?}, found {:?}",
+                key, expected_version, current_version
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KvError {}
+
+/// Raw stored value and its version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KvEntry {
+    pub value: Vec<u8>,
+    pub version: u64,
+}
+
+/// Result of a compare-and-swap.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CasOutcome<T> {
+    /// The write was applied; carries the new version.
+    Swapped { version: u64 },
+    /// The stored version did not match; carries the current entry, if any.
+    Conflict { current: Option<T> },
+}
+
+/// Byte-level key-value backend.
+#[async_trait]
+pub trait KvStore: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<KvEntry>, KvError>;
+
+    /// Unconditional write; returns the new version.
+    async fn put(&self, key: &str, value: Vec<u8>) -> Result<u64, KvError>;
+
+    /// Write only if the stored version equals `expected_version`
+    /// (`None`: only if the key does not exist).
+    async fn compare_and_swap(
+        &self,
+        key: &str,
+        expected_version: Option<u64>,
+        value: Vec<u8>,
+    ) -> Result<CasOutcome<KvEntry>, KvError>;
+
+    /// Returns `true` if a value was deleted.
+    async fn delete(&self, key: &str) -> Result<bool, KvError>;
+}
+
+/// In-memory backend with a store-wide version counter.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryKvStore {
+    inner: Arc<Mutex<InMemoryKvState>>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryKvState {
+    revision: u64,
+    entries: HashMap<String, KvEntry>,
+}
+
+impl InMemoryKvStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> Result<std::sync::MutexGuard<'_, InMemoryKvState>, KvError> {
+        self.inner
+            .lock()
+            .map_err(|_| KvError::Backend("in-memory store lock poisoned".to_string()))
+    }
+}
+
+impl InMemoryKvState {
+    fn write(&mut self, key: &str, value: Vec<u8>) -> u64 {
+        self.revision += 1;
+        let version = self.revision;
+        self.entries
+            .insert(key.to_string(), KvEntry { value, version });
+        version
+    }
+}
+
+#[async_trait]
+impl KvStore for InMemoryKvStore {
+    async fn get(&self, key: &str) -> Result<Option<KvEntry>, KvError> {
+        Ok(self.state()?.entries.get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> Result<u64, KvError> {
+        Ok(self.state()?.write(key, value))
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: &str,
+        expected_version: Option<u64>,
+        value: Vec<u8>,
+    ) -> Result<CasOutcome<KvEntry>, KvError> {
+        let mut state = self.state()?;
+        let current = state.entries.get(key).cloned();
+        if current.as_ref().map(|entry| entry.version) != expected_version {
+            return Ok(CasOutcome::Conflict { current });
+        }
+        Ok(CasOutcome::Swapped {
+            version: state.write(key, value),
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, KvError> {
+        Ok(self.state()?.entries.remove(key).is_some())
+    }
+}
+
+/// A decoded value and the version it was read at.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    pub value: T,
+    pub version: u64,
+}
+
+/// Typed, JSON-encoded view over a [`KvStore`].
+pub struct KvSynapse<T> {
+    store: Arc<dyn KvStore>,
+    prefix: String,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for KvSynapse<T> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            prefix: self.prefix.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for KvSynapse<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KvSynapse")
+            .field("prefix", &self.prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> KvSynapse<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    pub fn new<S>(store: S) -> Self
+    where
+        S: KvStore + 'static,
+    {
+        Self::from_arc(Arc::new(store))
+    }
+
+    pub fn from_arc(store: Arc<dyn KvStore>) -> Self {
+        Self {
+            store,
+            prefix: String::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Prefix prepended to every key (e.g. `config/flags/`).
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Option<Versioned<T>>, KvError> {
+        let key = self.full_key(key);
+        self.store
+            .get(&key)
+            .await?
+            .map(|entry| decode(&key, entry))
+            .transpose()
+    }
+
+    pub async fn put(&self, key: &str, value: &T) -> Result<u64, KvError> {
+        let key = self.full_key(key);
+        let bytes = encode(&key, value)?;
+        self.store.put(&key, bytes).await
+    }
+
+    pub async fn compare_and_swap(
+        &self,
+        key: &str,
+        expected_version: Option<u64>,
+        value: &T,
+    ) -> Result<CasOutcome<Versioned<T>>, KvError> {
+        let key = self.full_key(key);
+        let bytes = encode(&key, value)?;
+        match self
+            .store
+            .compare_and_swap(&key, expected_version, bytes)
+            .await?
+        {
+            CasOutcome::Swapped { version } => Ok(CasOutcome::Swapped { version }),
+            CasOutcome::Conflict { current } => Ok(CasOutcome::Conflict {
+                current: current.map(|entry| decode(&key, entry)).transpose()?,
+            }),
+        }
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<bool, KvError> {
+        self.store.delete(&self.full_key(key)).await
+    }
+
+    fn full_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+fn encode<T: Serialize>(key: &str, value: &T) -> Result<Vec<u8>, KvError> {
+    serde_json::to_vec(value).map_err(|e| KvError::Serialization {
+        key: key.to_string(),
+        message: e.to_string(),
+    })
+}
+
+fn decode<T: DeserializeOwned>(key: &str, entry: KvEntry) -> Result<Versioned<T>, KvError> {
+    let value = serde_json::from_slice(&entry.value).map_err(|e| KvError::Serialization {
+        key: key.to_string(),
+        message: e.to_string(),
+    })?;
+    Ok(Versioned {
+        value,
+        version: entry.version,
+    })
+}
+
+/// Input of [`KvPut`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KvWrite<T> {
+    pub key: String,
+    pub value: T,
+}
+
+/// Input of [`KvCompareAndSwap`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KvSwap<T> {
+    pub key: String,
+    /// Version the value was read at; `None` to create the key.
+    pub expected_version: Option<u64>,
+    pub value: T,
+}
+
+/// Reads the key given as input.
+pub struct KvGet<T> {
+    synapse: KvSynapse<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> KvGet<T> {
+    pub fn new(synapse: KvSynapse<T>) -> Self {
+        Self { synapse }
+    }
+}
+
+#[async_trait]
+impl<T> Transition<String, Option<Versioned<T>>> for KvGet<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    type Error = KvError;
+    type Resources = ();
+
+    fn label(&self) -> String {
+        "KvGet".to_string()
+    }
+
+    async fn run(
+        &self,
+        key: String,
+        _resources: &Self::Resources,
+        _bus: &mut Bus,
+    ) -> Outcome<Option<Versioned<T>>, Self::Error> {
+        match self.synapse.get(&key).await {
+            Ok(value) => Outcome::Next(value),
+            Err(e) => Outcome::Fault(e),
+        }
+    }
+}
+
+/// Writes unconditionally and passes the stored value on.
+pub struct KvPut<T> {
+    synapse: KvSynapse<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> KvPut<T> {
+    pub fn new(synapse: KvSynapse<T>) -> Self {
+        Self { synapse }
+    }
+}
+
+#[async_trait]
+impl<T> Transition<KvWrite<T>, Versioned<T>> for KvPut<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    type Error = KvError;
+    type Resources = ();
+
+    fn label(&self) -> String {
+        "KvPut".to_string()
+    }
+
+    async fn run(
+        &self,
+        write: KvWrite<T>,
+        _resources: &Self::Resources,
+        _bus: &mut Bus,
+    ) -> Outcome<Versioned<T>, Self::Error> {
+        match self.synapse.put(&write.key, &write.value).await {
+            Ok(version) => Outcome::Next(Versioned {
+                value: write.value,
+                version,
+            }),
+            Err(e) => Outcome::Fault(e),
+        }
+    }
+}
+
+/// Writes only if the version still matches; faults with
+/// [`KvError::Conflict`] otherwise.
+pub struct KvCompareAndSwap<T> {
+    synapse: KvSynapse<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> KvCompareAndSwap<T> {
+    pub fn new(synapse: KvSynapse<T>) -> Self {
+        Self { synapse }
+    }
+}
+
+#[async_trait]
+impl<T> Transition<KvSwap<T>, Versioned<T>> for KvCompareAndSwap<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    type Error = KvError;
+    type Resources = ();
+
+    fn label(&self) -> String {
+        "KvCompareAndSwap".to_string()
+    }
+
+    async fn run(
+        &self,
+        swap: KvSwap<T>,
+        _resources: &Self::Resources,
+        _bus: &mut Bus,
+    ) -> Outcome<Versioned<T>, Self::Error> {
+        match self
+            .synapse
+            .compare_and_swap(&swap.key, swap.expected_version, &swap.value)
+            .await
+        {
+            Ok(CasOutcome::Swapped { version }) => Outcome::Next(Versioned {
+                value: swap.value,
+                version,
+            }),
+            Ok(CasOutcome::Conflict { current }) => Outcome::Fault(KvError::Conflict {
+                key: swap.key,
+                expected_version: swap.expected_version,
+                current_version: current.map(|entry| entry.version),
+            }),
+            Err(e) => Outcome::Fault(e),
+        }
+    }
+}
+
+#[cfg(any(feature = "kv-etcd", feature = "kv-dynamodb"))]
+fn backend_error(error: impl fmt::Display) -> KvError {
+    KvError::Backend(error.to_string())
+}
+
+#[cfg(any(feature = "kv-etcd", feature = "kv-dynamodb"))]
+fn b64_encode(bytes: &[u8]) -> String {
+    use base64::Engine as _;
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+#[cfg(any(feature = "kv-etcd", feature = "kv-dynamodb"))]
+fn b64_decode(text: &str) -> Result<Vec<u8>, KvError> {
+    use base64::Engine as _;
+    base64::engine::general_purpose::STANDARD
+        .decode(text)
+        .map_err(backend_error)
+}
+
+/// etcd v3 backend over the JSON gRPC gateway (`/v3/kv/*`).
+#[cfg(feature = "kv-etcd")]
+#[derive(Debug, Clone)]
+pub struct EtcdKvStore {
+    client: reqwest::Client,
+    endpoint: String,
+    auth_token: Option<String>,
+}
+
+#[cfg(feature = "kv-etcd")]
+impl EtcdKvStore {
+    /// `endpoint` is the client URL, e.g. `http://127.0.0.1:2379`.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            auth_token: None,
+        }
+    }
+
+    /// Token from `/v3/auth/authenticate`, sent as the `Authorization` header.
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    async fn call(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+    ) -> Result<serde_json::Value, KvError> {
+        let mut request = self
+            .client
+            .post(format!("{}{}", self.endpoint, path))
+            .json(&body);
+        if let Some(token) = &self.auth_token {
+            request = request.header(reqwest::header::AUTHORIZATION, token);
+        }
+        let response = request.send().await.map_err(backend_error)?;
+        let status = response.status();
+        let text = response.text().await.map_err(backend_error)?;
+        if !status.is_success() {
+            return Err(KvError::Backend(format!(
+                "etcd {path} returned {status}: {text}"
+            )));
+        }
+        serde_json::from_str(&text)
+            .map_err(|error| KvError::Backend(format!("etcd {path} returned {status}: {error}")))
+    }
+
+    fn entry_from_kv(kv: &serde_json::Value) -> Result<KvEntry, KvError> {
+        Ok(KvEntry {
+            value: b64_decode(kv["value"].as_str().unwrap_or_default())?,
+            version: etcd_number(&kv["mod_revision"]),
+        })
+    }
+}
+
+/// etcd's gateway encodes int64 fields as JSON strings.
+#[cfg(feature = "kv-etcd")]
+fn etcd_number(value: &serde_json::Value) -> u64 {
+    value
+        .as_str()
+        .and_then(|text| text.parse().ok())
+        .or_else(|| value.as_u64())
+        .unwrap_or(0)
+}
+
+#[cfg(feature = "kv-etcd")]
+#[async_trait]
+impl KvStore for EtcdKvStore {
+    async fn get(&self, key: &str) -> Result<Option<KvEntry>, KvError> {
+        let response = self
+            .call(
+                "/v3/kv/range",
+                serde_json::json!({ "key": b64_encode(key.as_bytes()) }),
+            )
+            .await?;
+        response["kvs"]
+            .as_array()
+            .and_then(|kvs| kvs.first())
+            .map(Self::entry_from_kv)
+            .transpose()
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> Result<u64, KvError> {
+        let response = self
+            .call(
+                "/v3/kv/put",
+                serde_json::json!({
+                    "key": b64_encode(key.as_bytes()),
+                    "value": b64_encode(&value),
+                }),
+            )
+            .await?;
+        Ok(etcd_number(&response["header"]["revision"]))
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: &str,
+        expected_version: Option<u64>,
+        value: Vec<u8>,
+    ) -> Result<CasOutcome<KvEntry>, KvError> {
+        let encoded_key = b64_encode(key.as_bytes());
+        // A missing key has create_revision 0.
+        let compare = match expected_version {
+            Some(version) => serde_json::json!({
+                "key": encoded_key,
+                "target": "MOD",
+                "result": "EQUAL",
+                "mod_revision": version.to_string(),
+            }),
+            None => serde_json::json!({
+                "key": encoded_key,
+                "target": "CREATE",
+                "result": "EQUAL",
+                "create_revision": "0",
+            }),
+        };
+        let response = self
+            .call(
+                "/v3/kv/txn",
+                serde_json::json!({
+                    "compare": [compare],
+                    "success": [{ "request_put": { "key": encoded_key, "value": b64_encode(&value) } }],
+                    "failure": [{ "request_range": { "key": encoded_key } }],
+                }),
+            )
+            .await?;
+        if response["succeeded"].as_bool().unwrap_or(false) {
+            return Ok(CasOutcome::Swapped {
+                version: etcd_number(&response["header"]["revision"]),
+            });
+        }
+        let current = response["responses"][0]["response_range"]["kvs"]
+            .as_array()
+            .and_then(|kvs| kvs.first())
+            .map(Self::entry_from_kv)
+            .transpose()?;
+        Ok(CasOutcome::Conflict { current })
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, KvError> {
+        let response = self
+            .call(
+                "/v3/kv/deleterange",
+                serde_json::json!({ "key": b64_encode(key.as_bytes()) }),
+            )
+            .await?;
+        Ok(etcd_number(&response["deleted"]) > 0)
+    }
+}
+
+/// DynamoDB backend over the JSON API, signed with AWS SigV4.
+///
+/// Items use a string partition key attribute (default `pk`), a binary
+/// `value` attribute and a numeric `version` attribute.
+#[cfg(feature = "kv-dynamodb")]
+#[derive(Clone)]
+pub struct DynamoDbKvStore {
+    client: reqwest::Client,
+    table: String,
+    key_attribute: String,
+    region: String,
+    endpoint: String,
+    credentials: SharedCredentialsProvider,
+    cached_credentials: Arc<tokio::sync::Mutex<Option<AwsCredentials>>>,
+}
+
+/// Static AWS credentials, for [`DynamoDbKvStore::new`].
+#[cfg(feature = "kv-dynamodb")]
+pub use aws_credential_types::Credentials as AwsCredentials;
+#[cfg(feature = "kv-dynamodb")]
+use aws_credential_types::provider::{ProvideCredentials, SharedCredentialsProvider};
+
+/// Credentials this close to expiring are fetched again before signing.
+#[cfg(feature = "kv-dynamodb")]
+const CREDENTIALS_REFRESH_MARGIN: std::time::Duration = std::time::Duration::from_secs(300);
+
+#[cfg(feature = "kv-dynamodb")]
+impl fmt::Debug for DynamoDbKvStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamoDbKvStore")
+            .field("table", &self.table)
+            .field("region", &self.region)
+            .field("endpoint", &self.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "kv-dynamodb")]
+impl DynamoDbKvStore {
+    /// Sign requests with `credentials`: static [`AwsCredentials`] or any
+    /// other provider, such as one built with `aws-config`.
+    pub fn new(
+        table: impl Into<String>,
+        region: impl Into<String>,
+        credentials: impl ProvideCredentials + 'static,
+    ) -> Self {
+        let region = region.into();
+        Self {
+            client: reqwest::Client::new(),
+            table: table.into(),
+            key_attribute: "pk".to_string(),
+            endpoint: format!("https://dynamodb.{region}.amazonaws.com"),
+            region,
+            credentials: SharedCredentialsProvider::new(credentials),
+            cached_credentials: Arc::default(),
+        }
+    }
+
+    /// Region and credentials from the default AWS provider chain: the
+    /// `AWS_*` environment variables, the shared config and credentials
+    /// files, web identity tokens, and the ECS and EC2 instance metadata
+    /// endpoints.
+    pub async fn from_env(table: impl Into<String>) -> Result<Self, KvError> {
+        let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+        let region = config
+            .region()
+            .ok_or_else(|| KvError::Backend("no AWS region configured".to_string()))?;
+        let credentials = config
+            .credentials_provider()
+            .ok_or_else(|| KvError::Backend("no AWS credentials provider".to_string()))?;
+        Ok(Self::new(table, region.to_string(), credentials))
+    }
+
+    /// Override the endpoint, e.g. `http://localhost:8000` for DynamoDB Local.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_key_attribute(mut self, attribute: impl Into<String>) -> Self {
+        self.key_attribute = attribute.into();
+        self
+    }
+
+    fn key(&self, key: &str) -> serde_json::Value {
+        serde_json::json!({ self.key_attribute.as_str(): { "S": key } })
+    }
+
+    /// Credentials from the provider, reused until they near expiry.
+    async fn credentials(&self) -> Result<AwsCredentials, KvError> {
+        let mut cached = self.cached_credentials.lock().await;
+        let refresh_after = std::time::SystemTime::now() + CREDENTIALS_REFRESH_MARGIN;
+        if let Some(credentials) = cached.as_ref()
+            && credentials
+                .expiry()
+                .is_none_or(|expiry| expiry > refresh_after)
+        {
+            return Ok(credentials.clone());
+        }
+        let credentials = self
+            .credentials
+            .provide_credentials()
+            .await
+            .map_err(backend_error)?;
+        *cached = Some(credentials.clone());
+        Ok(credentials)
+    }
+
+    /// Headers of an `operation` request to `url`, SigV4-signed at `time`.
+    fn signed_headers(
+        &self,
+        url: &str,
+        operation: &str,
+        body: &[u8],
+        credentials: AwsCredentials,
+        time: std::time::SystemTime,
+    ) -> Result<Vec<(String, String)>, KvError> {
+        use aws_sigv4::http_request::{SignableBody, SignableRequest, SigningSettings, sign};
+
+        let target = format!("DynamoDB_20120810.{operation}");
+        let identity = credentials.into();
+        let params = aws_sigv4::sign::v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&self.region)
+            .name("dynamodb")
+            .time(time)
+            .settings(SigningSettings::default())
+            .build()
+            .map_err(backend_error)?
+            .into();
+        let headers = [
+            ("content-type", DYNAMODB_CONTENT_TYPE),
+            ("x-amz-target", target.as_str()),
+        ];
+        let signable =
+            SignableRequest::new("POST", url, headers.into_iter(), SignableBody::Bytes(body))
+                .map_err(backend_error)?;
+        let (instructions, _) = sign(signable, &params).map_err(backend_error)?.into_parts();
+        Ok(headers
+            .into_iter()
+            .chain(instructions.headers())
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect())
+    }
+
+    /// Send a signed request; `Ok(Err(type))` carries a DynamoDB error type.
+    async fn call(
+        &self,
+        operation: &str,
+        body: serde_json::Value,
+    ) -> Result<Result<serde_json::Value, String>, KvError> {
+        let body = serde_json::to_vec(&body).map_err(backend_error)?;
+        let url = format!("{}/", self.endpoint);
+        let credentials = self.credentials().await?;
+        let headers = self.signed_headers(
+            &url,
+            operation,
+            &body,
+            credentials,
+            std::time::SystemTime::now(),
+        )?;
+
+        let mut request = self.client.post(&url).body(body);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await.map_err(backend_error)?;
+        let status = response.status();
+        let text = response.text().await.map_err(backend_error)?;
+        if status.is_success() {
+            return serde_json::from_str(&text).map(Ok).map_err(|error| {
+                KvError::Backend(format!("DynamoDB {operation} returned {status}: {error}"))
+            });
+        }
+        let error: serde_json::Value = serde_json::from_str(&text).unwrap_or_default();
+        let error_type = error["__type"].as_str().unwrap_or_default();
+        let error_type = error_type.rsplit('#').next().unwrap_or(error_type);
+        if status.is_client_error() && error_type == "ConditionalCheckFailedException" {
+            return Ok(Err(error_type.to_string()));
+        }
+        Err(KvError::Backend(format!(
+            "DynamoDB {operation} returned {status}: {text}"
+        )))
+    }
+
+    async fn update(
+        &self,
+        key: &str,
+        value: &[u8],
+        condition: Option<(&str, Option<u64>)>,
+    ) -> Result<Result<u64, String>, KvError> {
+        let mut values = serde_json::json!({
+            ":value": { "B": b64_encode(value) },
+            ":one": { "N": "1" },
+        });
+        let mut body = serde_json::json!({
+            "TableName": self.table,
+            "Key": self.key(key),
+            "UpdateExpression": "SET #value = :value ADD #version :one",
+            "ExpressionAttributeNames": {
+                "#value": "value",
+                "#version": "version",
+            },
+            "ReturnValues": "UPDATED_NEW",
+        });
+        if let Some((expression, expected)) = condition {
+            if let Some(expected) = expected {
+                values[":expected"] = serde_json::json!({ "N": expected.to_string() });
+            }
+            // DynamoDB rejects declared names that the expressions never use.
+            if expression.contains("#key") {
+                body["ExpressionAttributeNames"]["#key"] = self.key_attribute.clone().into();
+            }
+            body["ConditionExpression"] = expression.into();
+        }
+        body["ExpressionAttributeValues"] = values;
+        Ok(self
+            .call("UpdateItem", body)
+            .await?
+            .map(|response| dynamo_number(&response["Attributes"]["version"])))
+    }
+}
+
+#[cfg(feature = "kv-dynamodb")]
+const DYNAMODB_CONTENT_TYPE: &str = "application/x-amz-json-1.0";
+
+#[cfg(feature = "kv-dynamodb")]
+fn dynamo_number(attribute: &serde_json::Value) -> u64 {
+    attribute["N"]
+        .as_str()
+        .and_then(|text| text.parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(feature = "kv-dynamodb")]
+#[async_trait]
+impl KvStore for DynamoDbKvStore {
+    async fn get(&self, key: &str) -> Result<Option<KvEntry>, KvError> {
+        let response = self
+            .call(
+                "GetItem",
+                serde_json::json!({
+                    "TableName": self.table,
+                    "Key": self.key(key),
+                    "ConsistentRead": true,
+                }),
+            )
+            .await?
+            .map_err(KvError::Backend)?;
+        let item = &response["Item"];
+        if item.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(KvEntry {
+            value: b64_decode(item["value"]["B"].as_str().unwrap_or_default())?,
+            version: dynamo_number(&item["version"]),
+        }))
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> Result<u64, KvError> {
+        self.update(key, &value, None)
+            .await?
+            .map_err(KvError::Backend)
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: &str,
+        expected_version: Option<u64>,
+        value: Vec<u8>,
+    ) -> Result<CasOutcome<KvEntry>, KvError> {
+        let condition = match expected_version {
+            Some(_) => "#version = :expected",
+            None => "attribute_not_exists(#key)",
+        };
+        match self
+            .update(key, &value, Some((condition, expected_version)))
+            .await?
+        {
+            Ok(version) => Ok(CasOutcome::Swapped { version }),
+            Err(_) => Ok(CasOutcome::Conflict {
+                current: self.get(key).await?,
+            }),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, KvError> {
+        let response = self
+            .call(
+                "DeleteItem",
+                serde_json::json!({
+                    "TableName": self.table,
+                    "Key": self.key(key),
+                    "ReturnValues": "ALL_OLD",
+                }),
+            )
+            .await?
+            .map_err(KvError::Backend)?;
+        Ok(response["Attributes"].is_object())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Flags {
+        beta: bool,
+    }
+
+    #[tokio::test]
+    async fn compare_and_swap_rejects_stale_versions() {
+        let flags = KvSynapse::<Flags>::new(InMemoryKvStore::new()).with_prefix("flags/");
+        let cas = KvCompareAndSwap::new(flags.clone());
+        let mut bus = Bus::new();
+
+        let created = cas
+            .run(
+                KvSwap {
+                    key: "web".into(),
+                    expected_version: None,
+                    value: Flags { beta: false },
+                },
+                &(),
+                &mut bus,
+            )
+            .await;
+        let Outcome::Next(created) = created else {
+            panic!("create should succeed");
+        };
+
+        let read = KvGet::new(flags.clone())
+            .run("web".into(), &(), &mut bus)
+            .await;
+        assert!(matches!(read, Outcome::Next(Some(ref v)) if v == &created));
+
+        flags.put("web", &Flags { beta: true }).await.unwrap();
+        let stale = cas
+            .run(
+                KvSwap {
+                    key: "web".into(),
+                    expected_version: Some(created.version),
+                    value: Flags { beta: false },
+                },
+                &(),
+                &mut bus,
+            )
+            .await;
+        let Outcome::Fault(KvError::Conflict {
+            current_version, ..
+        }) = stale
+        else {
+            panic!("stale swap should conflict");
+        };
+        assert!(current_version > Some(created.version));
+        assert!(flags.get("web").await.unwrap().unwrap().value.beta);
+        assert!(flags.delete("web").await.unwrap());
+        assert!(flags.get("web").await.unwrap().is_none());
+    }
+
+    #[cfg(feature = "kv-dynamodb")]
+    #[test]
+    fn dynamodb_signature_matches_a_known_sigv4_signature() {
+        let credentials = AwsCredentials::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            None,
+            None,
+            "test",
+        );
+        let store = DynamoDbKvStore::new("flags", "us-east-1", credentials.clone());
+        let body = br#"{"TableName":"flags","Key":{"pk":{"S":"web"}}}"#;
+        // 2015-08-30T12:36:00Z, the timestamp of the AWS SigV4 test suite.
+        let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_440_938_160);
+
+        let headers = store
+            .signed_headers(
+                "https://dynamodb.us-east-1.amazonaws.com/",
+                "GetItem",
+                body,
+                credentials,
+                time,
+            )
+            .unwrap();
+
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(header("x-amz-date"), Some("20150830T123600Z"));
+        assert_eq!(header("x-amz-target"), Some("DynamoDB_20120810.GetItem"));
+        assert_eq!(
+            header("authorization"),
+            Some(
+                "AWS4-HMAC-SHA256 \
+                 Credential=AKIDEXAMPLE/20150830/us-east-1/dynamodb/aws4_request, \
+                 SignedHeaders=content-type;host;x-amz-date;x-amz-target, \
+                 Signature=369206303a3954c6aa122701f91b25610ced351b8d257f642ff05fcffcdbc1f7"
+            )
+        );
+    }
+
+    #[cfg(feature = "kv-dynamodb")]
+    #[tokio::test]
+    async fn dynamodb_requests_are_signed_and_errors_carry_the_status() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 8192];
+            let read = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 503 Service Unavailable\r\ncontent-length: 8\r\nconnection: close\r\n\r\noverload",
+                )
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request[..read]).to_lowercase()
+        });
+
+        let credentials = AwsCredentials::new("AKIDEXAMPLE", "secret", None, None, "test");
+        let store = DynamoDbKvStore::new("flags", "us-east-1", credentials).with_endpoint(endpoint);
+        let error = store.get("web").await.unwrap_err();
+        assert!(
+            matches!(&error, KvError::Backend(message) if message.contains("503") && message.contains("overload")),
+            "{error:?}"
+        );
+
+        let request = server.await.unwrap();
+        assert!(request.contains("authorization: aws4-hmac-sha256 credential=akidexample/"));
+        assert!(request.contains("/us-east-1/dynamodb/aws4_request"));
+        assert!(request.contains("x-amz-target: dynamodb_20120810.getitem"));
+    }
+}
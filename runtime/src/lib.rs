@@ -1,27 +1,77 @@
 #![allow(deprecated)]
 
 pub mod axon;
+pub mod backfill;
+pub mod bulkhead;
+pub mod checkpoint;
+pub mod circuit_breaker;
 pub mod closure_transition;
 pub mod cluster;
+pub mod concurrency;
+pub mod contract;
 pub mod distributed;
+pub mod dry_run;
+pub mod durability;
+pub mod flow_spec;
+pub mod hooks;
+pub mod idempotency;
+pub mod interceptor;
+pub mod journal;
+pub mod kv;
 pub mod llm;
+pub mod local;
+#[cfg(feature = "otlp")]
+pub mod otlp;
 pub mod persistence;
+pub mod recording;
 pub mod replay;
 pub mod retry;
 #[cfg(feature = "streaming")]
 pub mod streaming_axon;
+pub mod suspend;
 pub mod testkit;
+pub mod timeline_sampling;
+pub mod timeline_store;
+pub mod timeline_writer;
+pub mod timeout;
 
 pub mod prelude {
     pub use crate::axon::{
-        Axon, BoxFuture, ExecutionMode, ExecutionTerminal, ParallelBusPolicy, ParallelStrategy,
+        Axon, BoxFuture, BranchBuilder, Branches, ExecutionMode, ExecutionTerminal, ForEachReport,
+        JoinBranches, LoopLimitExceeded, ParallelBusPolicy, ParallelStrategy,
         SchematicExportRequest,
     };
+    pub use crate::backfill::{
+        Backfill, BackfillError, BackfillReport, BackfillSource, DryRunLedger, IterSource,
+        JsonLinesSource,
+    };
+    pub use crate::bulkhead::{Bulkhead, BulkheadFull};
+    pub use crate::checkpoint::{
+        Checkpoint, CheckpointHandle, CheckpointStore, FileCheckpointStore, InMemoryCheckpointStore,
+    };
+    pub use crate::circuit_breaker::{CircuitBreaker, CircuitOpen};
     pub use crate::cluster::{ClusterManager, LeaderElection, LockBasedElection};
+    pub use crate::concurrency::{CircuitQuota, ConcurrencyScheduler, ConcurrencyStats};
+    pub use crate::contract::{ContractEnforcement, ContractViolation, OutputContract};
     pub use crate::distributed::{
         DistributedError, DistributedLock, DistributedStore, Guard, LockOptions,
     };
+    pub use crate::dry_run::{DryRunReport, DryRunStep};
+    pub use crate::durability::DurabilityAdapter;
+    pub use crate::hooks::{ExecutionHook, HookDecision, NodeContext, register_global_hook};
+    pub use crate::idempotency::{
+        IdempotencyHandle, IdempotencyKey, IdempotencyStore, InMemoryIdempotencyStore,
+    };
+    pub use crate::interceptor::{Interceptor, InterceptorRejected};
+    pub use crate::journal::{
+        ExecutionJournal, FileJournal, InMemoryJournal, JournalEntry, JournalHandle,
+    };
+    pub use crate::kv::{
+        InMemoryKvStore, KvCompareAndSwap, KvError, KvGet, KvPut, KvStore, KvSwap, KvSynapse,
+        KvWrite, Versioned,
+    };
     pub use crate::llm::{LlmError, LlmProvider, LlmTemplateVars, LlmTransition, MockLlmConfig};
+    pub use crate::local::{LocalAxon, LocalBus, LocalTransition};
     pub use crate::persistence::{
         CompensationAutoTrigger, CompensationContext, CompensationHandle, CompensationHook,
         CompensationIdempotencyHandle, CompensationIdempotencyStore, CompensationRetryPolicy,
@@ -33,13 +83,19 @@ pub mod prelude {
     pub use crate::persistence::{PostgresCompensationIdempotencyStore, PostgresPersistenceStore};
     #[cfg(feature = "persistence-redis")]
     pub use crate::persistence::{RedisCompensationIdempotencyStore, RedisPersistenceStore};
-    pub use crate::replay::ReplayEngine;
-    pub use crate::retry::{BackoffStrategy, RetryPolicy};
+    pub use crate::recording::{RecordedSynapse, SynapseCassette};
+    pub use crate::replay::{ReplayEngine, ReplayExecutor};
+    pub use crate::retry::{BackoffStrategy, OutcomeRetryPolicy, RetryPolicy};
     #[cfg(feature = "streaming")]
     pub use crate::streaming_axon::{
         CancellableStreamingError, StreamTimeoutKind, StreamingAxon, StreamingAxonError,
     };
+    pub use crate::suspend::{
+        InMemorySuspensionStore, ResumableAxon, ResumeSignal, SuspendedExecution, SuspensionStore,
+    };
     pub use crate::testkit::AxonTestKit;
+    pub use crate::timeline_sampling::TimelineSampling;
+    pub use crate::timeout::TimeoutError;
     pub use crate::{InfallibleAxon, SimpleAxon, TypedAxon};
 }
 
@@ -57,12 +113,59 @@ pub type TypedAxon<In, Out, Res = ()> = Axon<In, Out, ranvier_core::error::Ranvi
 pub type InfallibleAxon<In, Out, Res = ()> = Axon<In, Out, ranvier_core::Never, Res>;
 
 pub use axon::{
-    Axon, ExecutionTerminal, ParallelBusPolicy, ParallelStrategy, SchematicExportRequest,
+    Axon, BRANCH_PAYLOAD_INVALID, BranchBuilder, Branches, EXECUTION_CANCELLED, ExecutionTerminal,
+    ForEach, ForEachReport, JoinBranchInfo, JoinBranchTrace, JoinBranches, LOOP_LIMIT_EXCEEDED,
+    LoopLimitExceeded, LoopWhile, ParallelBusPolicy, ParallelStrategy, SchematicExportRequest,
+};
+pub use backfill::{
+    Backfill, BackfillError, BackfillFailure, BackfillReport, BackfillSource, DryRunLedger,
+    IterSource, JsonLinesSource, RecordedSideEffect, is_dry_run, record_side_effect,
 };
+pub use bulkhead::{BULKHEAD_FULL, Bulkhead, BulkheadFull};
+#[cfg(feature = "checkpoint-sqlite")]
+pub use checkpoint::SqliteCheckpointStore;
+pub use checkpoint::{
+    Checkpoint, CheckpointHandle, CheckpointStore, FileCheckpointStore, InMemoryCheckpointStore,
+};
+pub use circuit_breaker::{CIRCUIT_OPEN, CircuitBreaker, CircuitOpen};
 pub use closure_transition::ClosureTransition;
 pub use cluster::{ClusterManager, LeaderElection, LockBasedElection};
+pub use concurrency::{
+    CircuitConcurrencyStats, CircuitQuota, ConcurrencyPermit, ConcurrencyScheduler,
+    ConcurrencyStats,
+};
+pub use contract::{CONTRACT_VIOLATION, ContractEnforcement, ContractViolation, OutputContract};
 pub use distributed::{DistributedError, DistributedLock, DistributedStore, Guard, LockOptions};
+pub use dry_run::{DRY_RUN_UNESTIMATED, DryRunReport, DryRunStep};
+pub use durability::DurabilityAdapter;
+#[cfg(feature = "persistence-postgres")]
+pub use durability::PostgresDurabilityStore;
+pub use flow_spec::{FlowEdgeSpec, FlowNodeSpec, FlowRegistry, FlowSpec, FlowSpecError};
+pub use hooks::{
+    EXECUTION_HOOK_REJECTED, ExecutionHook, HookDecision, NodeContext, clear_global_hooks,
+    register_global_hook,
+};
+pub use idempotency::{
+    IDEMPOTENCY_KEY_CONFLICT, IDEMPOTENCY_KEY_IN_PROGRESS, IdempotencyClaim, IdempotencyHandle,
+    IdempotencyKey, IdempotencyRecord, IdempotencyStore, InMemoryIdempotencyStore,
+};
+pub use interceptor::{INTERCEPTOR_REJECTED, Interceptor, InterceptorRejected};
+pub use journal::{
+    ExecutionJournal, FileJournal, InMemoryJournal, JournalEntry, JournalHandle,
+    report_unfinished_to_dlq,
+};
+#[cfg(feature = "kv-etcd")]
+pub use kv::EtcdKvStore;
+#[cfg(feature = "kv-dynamodb")]
+pub use kv::{AwsCredentials, DynamoDbKvStore};
+pub use kv::{
+    CasOutcome, InMemoryKvStore, KvCompareAndSwap, KvEntry, KvError, KvGet, KvPut, KvStore, KvSwap,
+    KvSynapse, KvWrite, Versioned,
+};
 pub use llm::{LlmError, LlmProvider, LlmTemplateVars, LlmTransition, MockLlmConfig};
+pub use local::{LocalAxon, LocalBus, LocalTransition};
+#[cfg(feature = "otlp")]
+pub use otlp::{OtlpExportError, OtlpExporter};
 pub use persistence::{
     CompensationAutoTrigger, CompensationContext, CompensationHandle, CompensationHook,
     CompensationIdempotencyHandle, CompensationIdempotencyStore, CompensationRetryPolicy,
@@ -74,10 +177,25 @@ pub use persistence::{
 pub use persistence::{PostgresCompensationIdempotencyStore, PostgresPersistenceStore};
 #[cfg(feature = "persistence-redis")]
 pub use persistence::{RedisCompensationIdempotencyStore, RedisPersistenceStore};
-pub use replay::ReplayEngine;
-pub use retry::{BackoffStrategy, RetryPolicy};
+pub use recording::{
+    CassetteMode, RecordedSynapse, RecordedSynapseError, SynapseCassette, SynapseInteraction,
+};
+pub use replay::{
+    ReplayDivergence, ReplayEngine, ReplayExecutor, ReplayFrame, ReplayPlayback, ReplayReport,
+};
+pub use retry::{BackoffStrategy, OutcomeRetryPolicy, RetryPolicy};
 #[cfg(feature = "streaming")]
 pub use streaming_axon::{
     CancellableStreamingError, StreamTimeoutKind, StreamingAxon, StreamingAxonError,
 };
+pub use suspend::{
+    InMemorySuspensionStore, ResumableAxon, ResumeSignal, SuspendedExecution, SuspensionPoint,
+    SuspensionStore,
+};
 pub use testkit::AxonTestKit;
+pub use timeline_sampling::TimelineSampling;
+#[cfg(feature = "timeline-sqlite")]
+pub use timeline_store::SqliteTimelineStore;
+pub use timeline_store::TimelineStoreHandle;
+pub use timeline_writer::{TimelineWriter, TimelineWriterConfig};
+pub use timeout::{NODE_TIMEOUT, TimeoutError};
@@ -0,0 +1,408 @@
+//! Single-threaded Axons for `!Send` transitions and resources.
+//!
+//! [`Axon`](crate::axon::Axon) requires `Send + Sync` transitions and Bus
+//! resources so that executions can move between worker threads. A
+//! [`LocalAxon`] drops those bounds: its transitions implement
+//! [`LocalTransition`], its executions run on the calling thread, and its
+//! [`LocalBus`] can hold `Rc`, `RefCell`, FFI handles or wasm-bound clients
+//! next to the usual Bus entries.
+//!
+//! ```rust,ignore
+//! let counter = Rc::new(RefCell::new(0));
+//! let mut bus = LocalBus::new();
+//! bus.insert_local(counter.clone());
+//!
+//! let axon = LocalAxon::<i32, i32, String>::new("Count")
+//!     .then_fn("Add", |n: i32, bus: &mut LocalBus| {
+//!         *bus.read_local::<Rc<RefCell<i32>>>().unwrap().borrow_mut() += n;
+//!         Outcome::next(n)
+//!     });
+//!
+//! // On a current-thread runtime or a `tokio::task::LocalSet`.
+//! axon.execute(5, &(), &mut bus).await;
+//! ```
+//!
+//! Every [`Transition`] is also a `LocalTransition`, so existing transitions
+//! can be chained unchanged; they see the wrapped [`Bus`]. A `LocalAxon`
+//! chains nodes linearly and records them in its [`Schematic`] like an
+//! `Axon`, but it has none of the `Axon` execution machinery: no branch,
+//! parallel or saga combinators, persistence, hooks or Timeline.
+
+use async_trait::async_trait;
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::schematic::{Edge, EdgeType, Node, NodeKind, Schematic, SourceLocation};
+use ranvier_core::transition::Transition;
+use std::any::{Any, TypeId, type_name};
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::Location;
+use std::pin::Pin;
+use std::rc::Rc;
+use tracing::Instrument;
+
+/// A Bus that can also hold `!Send` resources.
+///
+/// Dereferences to the wrapped [`Bus`], so thread-safe entries are read and
+/// written as usual; `!Send` values go through the `*_local` methods.
+#[derive(Default)]
+pub struct LocalBus {
+    bus: Bus,
+    local: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl LocalBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap an existing Bus.
+    pub fn from_bus(bus: Bus) -> Self {
+        Self {
+            bus,
+            local: HashMap::new(),
+        }
+    }
+
+    /// Return the wrapped Bus, dropping the local entries.
+    pub fn into_bus(self) -> Bus {
+        self.bus
+    }
+
+    /// Insert a `!Send` resource, replacing one of the same type.
+    pub fn insert_local<T: 'static>(&mut self, resource: T) {
+        self.local.insert(TypeId::of::<T>(), Box::new(resource));
+    }
+
+    pub fn read_local<T: 'static>(&self) -> Option<&T> {
+        self.local.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    pub fn read_local_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.local.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+
+    pub fn remove_local<T: 'static>(&mut self) -> Option<T> {
+        let resource = self.local.remove(&TypeId::of::<T>())?;
+        resource.downcast().ok().map(|resource| *resource)
+    }
+}
+
+impl std::ops::Deref for LocalBus {
+    type Target = Bus;
+
+    fn deref(&self) -> &Self::Target {
+        &self.bus
+    }
+}
+
+impl std::ops::DerefMut for LocalBus {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.bus
+    }
+}
+
+/// A [`Transition`] without `Send + Sync` bounds, run by a [`LocalAxon`].
+#[async_trait(?Send)]
+pub trait LocalTransition<From, To>: 'static {
+    type Error: std::fmt::Debug + 'static;
+    type Resources: 'static;
+
+    /// Defaults to the type name.
+    fn label(&self) -> String {
+        let full = type_name::<Self>();
+        full.split("::").last().unwrap_or(full).to_string()
+    }
+
+    fn description(&self) -> Option<String> {
+        None
+    }
+
+    async fn run(
+        &self,
+        state: From,
+        resources: &Self::Resources,
+        bus: &mut LocalBus,
+    ) -> Outcome<To, Self::Error>;
+}
+
+#[async_trait(?Send)]
+impl<T, From, To> LocalTransition<From, To> for T
+where
+    T: Transition<From, To>,
+    From: Send + 'static,
+    To: Send + 'static,
+{
+    type Error = T::Error;
+    type Resources = T::Resources;
+
+    fn label(&self) -> String {
+        Transition::label(self)
+    }
+
+    fn description(&self) -> Option<String> {
+        Transition::description(self)
+    }
+
+    async fn run(
+        &self,
+        state: From,
+        resources: &Self::Resources,
+        bus: &mut LocalBus,
+    ) -> Outcome<To, Self::Error> {
+        Transition::run(self, state, resources, &mut bus.bus).await
+    }
+}
+
+/// Future returned by a [`LocalExecutor`]; unlike [`BoxFuture`](crate::axon::BoxFuture)
+/// it is not `Send`.
+pub type LocalBoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Executor type for [`LocalAxon`] steps.
+pub type LocalExecutor<In, Out, E, Res> =
+    Rc<dyn for<'a> Fn(In, &'a Res, &'a mut LocalBus) -> LocalBoxFuture<'a, Outcome<Out, E>>>;
+
+/// A single-threaded Axon. See the [module docs](self).
+pub struct LocalAxon<In, Out, E, Res = ()> {
+    pub schematic: Schematic,
+    executor: LocalExecutor<In, Out, E, Res>,
+}
+
+impl<In, Out, E, Res> Clone for LocalAxon<In, Out, E, Res> {
+    fn clone(&self) -> Self {
+        Self {
+            schematic: self.schematic.clone(),
+            executor: self.executor.clone(),
+        }
+    }
+}
+
+impl<In, E, Res> LocalAxon<In, In, E, Res>
+where
+    In: 'static,
+    E: 'static,
+    Res: 'static,
+{
+    /// Start a LocalAxon, mirroring [`Axon::new`](crate::axon::Axon::new).
+    #[track_caller]
+    pub fn new(label: &str) -> Self {
+        let caller = Location::caller();
+        let mut schematic = Schematic::new(label);
+        schematic.nodes.push(Node {
+            id: schematic.next_node_id(label),
+            kind: NodeKind::Ingress,
+            label: label.to_string(),
+            description: None,
+            input_type: "void".to_string(),
+            output_type: short_type_name::<In>(),
+            resource_type: short_type_name::<Res>(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: Some(SourceLocation::from(caller)),
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        });
+        Self {
+            schematic,
+            executor: Rc::new(|input, _res, _bus| {
+                Box::pin(std::future::ready(Outcome::Next(input)))
+            }),
+        }
+    }
+}
+
+impl<In, Out, E, Res> LocalAxon<In, Out, E, Res>
+where
+    In: 'static,
+    Out: 'static,
+    E: 'static,
+    Res: 'static,
+{
+    /// Chain a transition to run on this Axon's `Next` output.
+    #[track_caller]
+    pub fn then<Next, Trans>(self, transition: Trans) -> LocalAxon<In, Next, E, Res>
+    where
+        Next: 'static,
+        Trans: LocalTransition<Out, Next, Resources = Res, Error = E>,
+    {
+        let label = transition.label();
+        let description = transition.description();
+        let transition = Rc::new(transition);
+        self.push_step(
+            label,
+            description,
+            Location::caller(),
+            Rc::new(move |state, res, bus| {
+                let transition = transition.clone();
+                Box::pin(async move { transition.run(state, res, bus).await })
+            }),
+        )
+    }
+
+    /// Chain a closure step, like [`Axon::then_fn`](crate::axon::Axon::then_fn)
+    /// but without `Send + Sync` bounds.
+    #[track_caller]
+    pub fn then_fn<Next, F>(self, label: &str, f: F) -> LocalAxon<In, Next, E, Res>
+    where
+        Next: 'static,
+        F: Fn(Out, &mut LocalBus) -> Outcome<Next, E> + 'static,
+    {
+        self.push_step(
+            label.to_string(),
+            None,
+            Location::caller(),
+            Rc::new(move |state, _res, bus| Box::pin(std::future::ready(f(state, bus)))),
+        )
+    }
+
+    fn push_step<Next: 'static>(
+        self,
+        label: String,
+        description: Option<String>,
+        caller: &'static Location<'static>,
+        step: LocalExecutor<Out, Next, E, Res>,
+    ) -> LocalAxon<In, Next, E, Res> {
+        let LocalAxon {
+            mut schematic,
+            executor: prev,
+        } = self;
+
+        let node_id = schematic.next_node_id(&label);
+        let last_node_id = schematic
+            .nodes
+            .last()
+            .map(|n| n.id.clone())
+            .unwrap_or_default();
+        schematic.nodes.push(Node {
+            id: node_id.clone(),
+            kind: NodeKind::Atom,
+            label: label.clone(),
+            description,
+            input_type: short_type_name::<Out>(),
+            output_type: short_type_name::<Next>(),
+            resource_type: short_type_name::<Res>(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: Some(SourceLocation::from(caller)),
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: None,
+            terminal: None,
+        });
+        schematic.edges.push(Edge {
+            from: last_node_id,
+            to: node_id.clone(),
+            kind: EdgeType::Linear,
+            label: Some("Next".to_string()),
+        });
+
+        let executor: LocalExecutor<In, Next, E, Res> = Rc::new(move |input, res, bus| {
+            let prev = prev.clone();
+            let step = step.clone();
+            let span =
+                tracing::info_span!("Node", ranvier.node = %label, ranvier.node_id = %node_id);
+            Box::pin(async move {
+                let state = match prev(input, res, bus).await {
+                    Outcome::Next(state) => state,
+                    other => return other.map(|_| unreachable!()),
+                };
+                step(state, res, bus).instrument(span).await
+            })
+        });
+
+        LocalAxon {
+            schematic,
+            executor,
+        }
+    }
+
+    /// Run the chain on the current thread.
+    pub async fn execute(&self, input: In, resources: &Res, bus: &mut LocalBus) -> Outcome<Out, E> {
+        let span = tracing::info_span!("Circuit", ranvier.circuit = %self.schematic.name);
+        (self.executor)(input, resources, bus)
+            .instrument(span)
+            .await
+    }
+}
+
+fn short_type_name<T: ?Sized>() -> String {
+    let full = type_name::<T>();
+    full.split("::").last().unwrap_or(full).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Clone)]
+    struct Double;
+
+    #[async_trait]
+    impl Transition<i32, i32> for Double {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, n: i32, _res: &(), _bus: &mut Bus) -> Outcome<i32, String> {
+            Outcome::next(n * 2)
+        }
+    }
+
+    /// Holds an `Rc`, so it could not be an Axon transition.
+    struct Record(Rc<RefCell<Vec<i32>>>);
+
+    #[async_trait(?Send)]
+    impl LocalTransition<i32, i32> for Record {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, n: i32, _res: &(), bus: &mut LocalBus) -> Outcome<i32, String> {
+            self.0.borrow_mut().push(n);
+            let offset = *bus.read_local::<Rc<i32>>().unwrap().as_ref();
+            Outcome::next(n + offset)
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn local_axon_runs_send_and_non_send_steps() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let axon = LocalAxon::<i32, i32, String>::new("Local")
+            .then(Double)
+            .then(Record(seen.clone()))
+            .then_fn("Stop", |n: i32, _bus: &mut LocalBus| {
+                if n > 10 {
+                    Outcome::emit("too_big", None)
+                } else {
+                    Outcome::next(n)
+                }
+            })
+            .then(Double);
+
+        let mut bus = LocalBus::new();
+        bus.insert_local(Rc::new(1));
+        assert!(matches!(
+            axon.execute(2, &(), &mut bus).await,
+            Outcome::Next(10)
+        ));
+        assert!(matches!(
+            axon.execute(6, &(), &mut bus).await,
+            Outcome::Emit(ref kind, _) if kind == "too_big"
+        ));
+        assert_eq!(*seen.borrow(), vec![4, 12]);
+
+        let labels: Vec<_> = axon
+            .schematic
+            .nodes
+            .iter()
+            .map(|n| n.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["Local", "Double", "Record", "Stop", "Double"]);
+        assert_eq!(axon.schematic.edges.len(), 4);
+    }
+}
@@ -0,0 +1,100 @@
+//! Backfill of stored timelines into an OTLP collector.
+//!
+//! Services that run with live tracing disabled still keep their Timeline
+//! artifacts. An [`OtlpExporter`] converts them with
+//! [`Timeline::to_otlp`](ranvier_core::timeline::Timeline::to_otlp) and posts
+//! them to an OTLP/HTTP collector, so the traces show up after the fact:
+//!
+//! ```rust,ignore
+//! let exporter = OtlpExporter::new("http://otel-collector:4318", "checkout-service");
+//! for summary in store.list_runs(TimelineRunQuery::default()).await? {
+//!     if let Some(run) = store.fetch_run(&summary.run_id).await? {
+//!         exporter.export(&run.timeline).await?;
+//!     }
+//! }
+//! ```
+
+use ranvier_core::timeline::Timeline;
+use std::fmt;
+
+/// Error returned by [`OtlpExporter::export`].
+#[derive(Debug)]
+pub enum OtlpExportError {
+    /// The request could not be sent or its response read.
+    Transport(String),
+    /// The collector answered with a non-success status.
+    Rejected { status: u16, body: String },
+}
+
+impl fmt::Display for OtlpExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(message) => write!(f, "OTLP export failed: {}", message),
+            Self::Rejected { status, body } => {
+                write!(f, "OTLP collector rejected export ({}): {}", status, body)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OtlpExportError {}
+
+/// Posts timelines to an OTLP/HTTP collector as JSON-encoded spans.
+#[derive(Debug, Clone)]
+pub struct OtlpExporter {
+    client: reqwest::Client,
+    endpoint: String,
+    service_name: String,
+    headers: Vec<(String, String)>,
+}
+
+impl OtlpExporter {
+    /// `endpoint` is the collector's OTLP/HTTP base URL, e.g.
+    /// `http://127.0.0.1:4318`; spans are posted to `{endpoint}/v1/traces`.
+    pub fn new(endpoint: impl Into<String>, service_name: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            service_name: service_name.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Extra header sent with every export, e.g. a vendor API key.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
+    /// Convert `timeline` to spans and post them to the collector.
+    pub async fn export(&self, timeline: &Timeline) -> Result<(), OtlpExportError> {
+        if timeline.events.is_empty() {
+            return Ok(());
+        }
+        let mut request = self
+            .client
+            .post(format!("{}/v1/traces", self.endpoint))
+            .json(&timeline.to_otlp(&self.service_name));
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await.map_err(transport_error)?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.map_err(transport_error)?;
+            return Err(OtlpExportError::Rejected {
+                status: status.as_u16(),
+                body,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn transport_error(error: impl fmt::Display) -> OtlpExportError {
+    OtlpExportError::Transport(error.to_string())
+}
@@ -0,0 +1,347 @@
+//! VCR-style recording and replay of Synapse calls.
+//!
+//! [`RecordedSynapse`] wraps a [`Synapse`] and, in record mode, appends every
+//! request/response pair to a [`SynapseCassette`]. Saved next to the run's
+//! Timeline, the cassette lets the same circuit be executed again offline: in
+//! replay mode the wrapper answers from the cassette and never calls the
+//! wrapped Synapse.
+//!
+//! ```rust,ignore
+//! let cassette = SynapseCassette::new();
+//! let payments = RecordedSynapse::record("payments", PaymentsApi::new(url), cassette.clone());
+//! // ... run the circuit, then persist the cassette with the Timeline:
+//! std::fs::write("run.cassette.json", serde_json::to_vec(&cassette)?)?;
+//!
+//! // Later, offline:
+//! let cassette: SynapseCassette = serde_json::from_slice(&std::fs::read("run.cassette.json")?)?;
+//! let payments = RecordedSynapse::replay("payments", PaymentsApi::new(url), cassette);
+//! ```
+//!
+//! Replay matches calls by Synapse name and JSON-encoded request. Each recorded
+//! interaction answers one call, in recording order, so repeated identical
+//! requests get the responses they originally received.
+
+use async_trait::async_trait;
+use ranvier_core::synapse::Synapse;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// One recorded Synapse call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SynapseInteraction {
+    /// Name the [`RecordedSynapse`] was registered under.
+    pub synapse: String,
+    pub request: Value,
+    /// The output, or the error the Synapse returned.
+    pub response: Result<Value, Value>,
+}
+
+/// Shared, serializable log of Synapse interactions.
+///
+/// Clones share the same log, so one cassette can back every Synapse of a
+/// circuit. Serializes as the list of interactions.
+#[derive(Clone, Default)]
+pub struct SynapseCassette {
+    state: Arc<Mutex<CassetteState>>,
+}
+
+#[derive(Default)]
+struct CassetteState {
+    interactions: Vec<SynapseInteraction>,
+    /// Parallel to `interactions`: whether replay has used the entry.
+    played: Vec<bool>,
+}
+
+impl SynapseCassette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_interactions(interactions: Vec<SynapseInteraction>) -> Self {
+        let played = vec![false; interactions.len()];
+        Self {
+            state: Arc::new(Mutex::new(CassetteState {
+                interactions,
+                played,
+            })),
+        }
+    }
+
+    /// Snapshot of the recorded interactions.
+    pub fn interactions(&self) -> Vec<SynapseInteraction> {
+        self.lock().interactions.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().interactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().interactions.is_empty()
+    }
+
+    /// Number of interactions replay has not used yet.
+    pub fn remaining(&self) -> usize {
+        self.lock().played.iter().filter(|played| !**played).count()
+    }
+
+    /// Make every interaction available to replay again.
+    pub fn rewind(&self) {
+        self.lock().played.fill(false);
+    }
+
+    fn record(&self, interaction: SynapseInteraction) {
+        let mut state = self.lock();
+        state.interactions.push(interaction);
+        state.played.push(false);
+    }
+
+    /// Take the first unplayed response recorded for this call.
+    fn play(&self, synapse: &str, request: &Value) -> Option<Result<Value, Value>> {
+        let mut state = self.lock();
+        let CassetteState {
+            interactions,
+            played,
+        } = &mut *state;
+        let index = interactions
+            .iter()
+            .zip(played.iter())
+            .position(|(interaction, played)| {
+                !played && interaction.synapse == synapse && interaction.request == *request
+            })?;
+        played[index] = true;
+        Some(interactions[index].response.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CassetteState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl fmt::Debug for SynapseCassette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SynapseCassette")
+            .field("interactions", &self.len())
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+impl Serialize for SynapseCassette {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.lock().interactions.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SynapseCassette {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<SynapseInteraction>::deserialize(deserializer).map(Self::from_interactions)
+    }
+}
+
+/// Whether a [`RecordedSynapse`] records live calls or replays them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record,
+    Replay,
+}
+
+/// A [`Synapse`] wrapper that records calls to, or replays them from, a
+/// [`SynapseCassette`].
+#[derive(Debug, Clone)]
+pub struct RecordedSynapse<S> {
+    inner: S,
+    name: String,
+    cassette: SynapseCassette,
+    mode: CassetteMode,
+}
+
+impl<S> RecordedSynapse<S> {
+    /// Call `inner` and record each request/response pair under `name`.
+    pub fn record(name: impl Into<String>, inner: S, cassette: SynapseCassette) -> Self {
+        Self::new(name, inner, cassette, CassetteMode::Record)
+    }
+
+    /// Answer calls from `cassette`; `inner` is never called.
+    pub fn replay(name: impl Into<String>, inner: S, cassette: SynapseCassette) -> Self {
+        Self::new(name, inner, cassette, CassetteMode::Replay)
+    }
+
+    pub fn new(
+        name: impl Into<String>,
+        inner: S,
+        cassette: SynapseCassette,
+        mode: CassetteMode,
+    ) -> Self {
+        Self {
+            inner,
+            name: name.into(),
+            cassette,
+            mode,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn cassette(&self) -> &SynapseCassette {
+        &self.cassette
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+/// Errors returned by a [`RecordedSynapse`].
+#[derive(Debug)]
+pub enum RecordedSynapseError<E> {
+    /// The wrapped Synapse failed, live or as recorded.
+    Synapse(E),
+    /// Replay found no unplayed interaction for this call.
+    Unrecorded { synapse: String, request: Value },
+    /// A request or response could not be converted to or from JSON.
+    Serialization { synapse: String, message: String },
+}
+
+impl<E: fmt::Debug> fmt::Display for RecordedSynapseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Synapse(error) => write!(f, "Synapse error: {:?}", error),
+            Self::Unrecorded { synapse, request } => write!(
+                f,
+                "No recorded interaction for Synapse '{}' with request {}",
+                synapse, request
+            ),
+            Self::Serialization { synapse, message } => {
+                write!(
+                    f,
+                    "Recording serialization error for '{}': {}",
+                    synapse, message
+                )
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for RecordedSynapseError<E> {}
+
+#[async_trait]
+impl<S> Synapse for RecordedSynapse<S>
+where
+    S: Synapse,
+    S::Input: Serialize + Sync,
+    S::Output: Serialize + DeserializeOwned,
+    S::Error: Serialize + DeserializeOwned,
+{
+    type Input = S::Input;
+    type Output = S::Output;
+    type Error = RecordedSynapseError<S::Error>;
+
+    async fn call(&self, input: Self::Input) -> Result<Self::Output, Self::Error> {
+        let serialization = |error: serde_json::Error| RecordedSynapseError::Serialization {
+            synapse: self.name.clone(),
+            message: error.to_string(),
+        };
+        let request = serde_json::to_value(&input).map_err(serialization)?;
+
+        match self.mode {
+            CassetteMode::Record => {
+                let result = self.inner.call(input).await;
+                let response = match &result {
+                    Ok(output) => serde_json::to_value(output).map(Ok),
+                    Err(error) => serde_json::to_value(error).map(Err),
+                };
+                match response {
+                    Ok(response) => self.cassette.record(SynapseInteraction {
+                        synapse: self.name.clone(),
+                        request,
+                        response,
+                    }),
+                    Err(error) => tracing::warn!(
+                        synapse = %self.name,
+                        %error,
+                        "Skipping Synapse recording"
+                    ),
+                }
+                result.map_err(RecordedSynapseError::Synapse)
+            }
+            CassetteMode::Replay => match self.cassette.play(&self.name, &request) {
+                Some(Ok(output)) => serde_json::from_value(output).map_err(serialization),
+                Some(Err(error)) => Err(RecordedSynapseError::Synapse(
+                    serde_json::from_value(error).map_err(serialization)?,
+                )),
+                None => Err(RecordedSynapseError::Unrecorded {
+                    synapse: self.name.clone(),
+                    request,
+                }),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Returns an increasing counter, so replayed answers are distinguishable
+    /// from live ones.
+    #[derive(Default)]
+    struct Quotes {
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl Synapse for Quotes {
+        type Input = String;
+        type Output = u32;
+        type Error = String;
+
+        async fn call(&self, symbol: String) -> Result<u32, String> {
+            if symbol == "XXX" {
+                return Err("unknown symbol".into());
+            }
+            Ok(self.calls.fetch_add(1, Ordering::SeqCst) + 100)
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_returns_recorded_responses_without_calling_inner() {
+        let cassette = SynapseCassette::new();
+        let live = RecordedSynapse::record("quotes", Quotes::default(), cassette.clone());
+        assert_eq!(live.call("ABC".into()).await.unwrap(), 100);
+        assert_eq!(live.call("ABC".into()).await.unwrap(), 101);
+        assert!(live.call("XXX".into()).await.is_err());
+
+        let saved = serde_json::to_string(&cassette).unwrap();
+        let cassette: SynapseCassette = serde_json::from_str(&saved).unwrap();
+        assert_eq!(cassette.len(), 3);
+
+        let replayed = RecordedSynapse::replay("quotes", Quotes::default(), cassette.clone());
+        assert_eq!(replayed.call("ABC".into()).await.unwrap(), 100);
+        assert_eq!(replayed.call("ABC".into()).await.unwrap(), 101);
+        assert!(matches!(
+            replayed.call("XXX".into()).await,
+            Err(RecordedSynapseError::Synapse(error)) if error == "unknown symbol"
+        ));
+        assert!(matches!(
+            replayed.call("ABC".into()).await,
+            Err(RecordedSynapseError::Unrecorded { .. })
+        ));
+        assert_eq!(replayed.inner().calls.load(Ordering::SeqCst), 0);
+        assert_eq!(cassette.remaining(), 0);
+
+        cassette.rewind();
+        assert_eq!(replayed.call("ABC".into()).await.unwrap(), 100);
+    }
+}
@@ -1,13 +1,48 @@
+use crate::axon::Axon;
 use crate::persistence::PersistenceStore;
+use crate::recording::SynapseCassette;
 use anyhow::{Result, anyhow};
+use ranvier_core::bus::Bus;
+use ranvier_core::bus_snapshot::BusSnapshot;
+use ranvier_core::capture::{CaptureFormat, NodeCapturePolicy};
+use ranvier_core::outcome::Outcome;
 use ranvier_core::schematic::MigrationRegistry;
-use ranvier_core::timeline::{Timeline, TimelineEvent};
+use ranvier_core::timeline::{CaptureDirection, Timeline, TimelineEvent, Timestamp};
+use serde::{Serialize, de::DeserializeOwned};
 
 /// ReplayEngine reconstructs the execution state from a Timeline.
 /// It creates a "virtual" cursor that moves through the circuit based on recorded events.
+///
+/// The cursor can move in both directions: [`step_back`](Self::step_back)
+/// and [`seek_to`](Self::seek_to) rewind it, and [`play`](Self::play) steps
+/// forward in recorded time at an adjustable speed.
+///
+/// Breakpoints set with [`break_on_node`](Self::break_on_node) and
+/// [`break_on`](Self::break_on) stop [`run_to_break`](Self::run_to_break),
+/// so a long trace can be skipped through up to the node of interest.
+///
+/// A [`SynapseCassette`] recorded with the run can be attached with
+/// [`with_cassette`](Self::with_cassette), so the circuit can be executed again
+/// offline with [`RecordedSynapse::replay`](crate::recording::RecordedSynapse::replay).
 pub struct ReplayEngine {
     timeline: Timeline,
     cursor: usize,
+    breakpoints: Vec<Breakpoint>,
+    cassette: Option<SynapseCassette>,
+}
+
+enum Breakpoint {
+    Node(String),
+    When(Box<dyn Fn(&TimelineEvent) -> bool + Send + Sync>),
+}
+
+impl Breakpoint {
+    fn hit(&self, frame: &ReplayFrame) -> bool {
+        match self {
+            Self::Node(node_id) => frame.current_node_id.as_deref() == Some(node_id.as_str()),
+            Self::When(predicate) => predicate(&frame.event),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -16,12 +51,72 @@ pub struct ReplayFrame {
     pub event: TimelineEvent,
 }
 
+impl ReplayFrame {
+    /// Decode this frame's payload when it is a `PayloadCaptured` event.
+    pub fn decode_payload<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        match &self.event {
+            TimelineEvent::PayloadCaptured { payload, .. } => Ok(Some(payload.decode()?)),
+            _ => Ok(None),
+        }
+    }
+}
+
 impl ReplayEngine {
     pub fn new(timeline: Timeline) -> Self {
         Self {
             timeline,
             cursor: 0,
+            breakpoints: Vec::new(),
+            cassette: None,
+        }
+    }
+
+    /// Attach the Synapse interactions recorded during the run.
+    pub fn with_cassette(mut self, cassette: SynapseCassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// The attached cassette, for building replaying Synapses.
+    pub fn cassette(&self) -> Option<&SynapseCassette> {
+        self.cassette.as_ref()
+    }
+
+    /// Break on any event of the node with this id.
+    pub fn break_on_node(&mut self, node_id: impl Into<String>) -> &mut Self {
+        self.breakpoints.push(Breakpoint::Node(node_id.into()));
+        self
+    }
+
+    /// Break on every event matching `predicate`, e.g.
+    /// `|event| matches!(event, TimelineEvent::FaultRecorded { .. })`.
+    pub fn break_on<F>(&mut self, predicate: F) -> &mut Self
+    where
+        F: Fn(&TimelineEvent) -> bool + Send + Sync + 'static,
+    {
+        self.breakpoints.push(Breakpoint::When(Box::new(predicate)));
+        self
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Step forward until a frame hits a breakpoint and return that frame.
+    ///
+    /// The current frame is not checked, so calling this again continues to
+    /// the next hit. Returns None after running to the end without a hit.
+    pub fn run_to_break(&mut self) -> Option<ReplayFrame> {
+        while let Some(frame) = self.next_step() {
+            if self
+                .breakpoints
+                .iter()
+                .any(|breakpoint| breakpoint.hit(&frame))
+            {
+                return Some(frame);
+            }
         }
+        None
     }
 
     /// Advance the replay by one step.
@@ -30,24 +125,111 @@ impl ReplayEngine {
         if self.cursor >= self.timeline.events.len() {
             return None;
         }
-
-        let event = self.timeline.events[self.cursor].clone();
         self.cursor += 1;
+        self.current_frame()
+    }
 
-        let current_node_id = match &event {
-            TimelineEvent::NodeEnter { node_id, .. } => Some(node_id.clone()),
-            TimelineEvent::NodeExit { node_id, .. } => Some(node_id.clone()),
-            TimelineEvent::NodePaused { node_id, .. } => Some(node_id.clone()),
-            TimelineEvent::NodeRetry { node_id, .. } => Some(node_id.clone()),
-            TimelineEvent::DlqExhausted { node_id, .. } => Some(node_id.clone()),
-            TimelineEvent::Branchtaken { .. } => None, // Branches happen "between" nodes conceptually or part of outcome
-            TimelineEvent::NodeTimeout { node_id, .. } => Some(node_id.clone()),
-        };
-
-        Some(ReplayFrame {
-            current_node_id,
-            event,
-        })
+    /// Move the cursor back by one step.
+    ///
+    /// Returns the frame that is current afterwards, or None once the cursor
+    /// is back before the first event.
+    pub fn step_back(&mut self) -> Option<ReplayFrame> {
+        self.cursor = self.cursor.saturating_sub(1);
+        self.current_frame()
+    }
+
+    /// Move the cursor to the last event recorded at or before `timestamp`.
+    ///
+    /// Returns that frame, or None when `timestamp` precedes the first event.
+    /// Events are assumed to be in recording order (see [`Timeline::sort`]).
+    pub fn seek_to(&mut self, timestamp: Timestamp) -> Option<ReplayFrame> {
+        self.cursor = self
+            .timeline
+            .events
+            .partition_point(|event| event.timestamp() <= timestamp);
+        self.current_frame()
+    }
+
+    /// The most recently replayed frame, if any.
+    pub fn current_frame(&self) -> Option<ReplayFrame> {
+        let index = self.cursor.checked_sub(1)?;
+        self.timeline.events.get(index).map(frame_of)
+    }
+
+    /// Number of events replayed so far; the current frame is at
+    /// `position() - 1`.
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// Total number of events in the Timeline.
+    pub fn len(&self) -> usize {
+        self.timeline.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timeline.events.is_empty()
+    }
+
+    /// Replay the remaining events with their recorded spacing, divided by
+    /// `speed` (`2.0` plays twice as fast).
+    ///
+    /// A `speed` that is not a positive finite number plays without delay.
+    pub fn play(&mut self, speed: f64) -> ReplayPlayback<'_> {
+        ReplayPlayback {
+            engine: self,
+            speed,
+        }
+    }
+
+    /// Capture format recorded in the Timeline header, if any.
+    pub fn capture_format(&self) -> Option<CaptureFormat> {
+        self.timeline.header.capture_format
+    }
+
+    /// Decode the captured circuit input, whichever format it was recorded in.
+    pub fn captured_input<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        self.decode_captured(CaptureDirection::Input)
+    }
+
+    /// Decode the captured circuit output, whichever format it was recorded in.
+    pub fn captured_output<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        self.decode_captured(CaptureDirection::Output)
+    }
+
+    /// The Bus captured on entry, when the circuit's capture policy
+    /// included it.
+    pub fn captured_bus(&self) -> Option<&BusSnapshot> {
+        self.timeline.captured_bus()
+    }
+
+    /// Rebuild the resources the captured run saw on entry.
+    ///
+    /// Only types registered with
+    /// [`register_replay_type`](ranvier_core::bus_snapshot::register_replay_type)
+    /// are restored; skipped entries are logged.
+    pub fn reconstruct_bus(&self) -> Option<Bus> {
+        let snapshot = self.captured_bus()?;
+        let mut bus = Bus::new();
+        for entry in snapshot.restore_into(&mut bus) {
+            tracing::debug!(
+                resource = %entry.type_name,
+                name = ?entry.name,
+                "Captured Bus entry not restored"
+            );
+        }
+        Some(bus)
+    }
+
+    fn decode_captured<T: DeserializeOwned>(
+        &self,
+        direction: CaptureDirection,
+    ) -> Result<Option<T>> {
+        self.timeline
+            .captured_payload(direction)
+            .map(|payload| payload.decode())
+            .transpose()
+            .map_err(Into::into)
     }
 
     /// Reset replay to start
@@ -98,6 +280,318 @@ impl ReplayEngine {
     }
 }
 
+fn frame_of(event: &TimelineEvent) -> ReplayFrame {
+    let current_node_id = match event {
+        TimelineEvent::NodeEnter { node_id, .. } => Some(node_id.clone()),
+        TimelineEvent::NodeExit { node_id, .. } => Some(node_id.clone()),
+        TimelineEvent::NodePaused { node_id, .. } => Some(node_id.clone()),
+        TimelineEvent::NodeRetry { node_id, .. } => Some(node_id.clone()),
+        TimelineEvent::DlqExhausted { node_id, .. } => Some(node_id.clone()),
+        TimelineEvent::Branchtaken { .. } => None, // Branches happen "between" nodes conceptually or part of outcome
+        TimelineEvent::NodeTimeout { node_id, .. } => Some(node_id.clone()),
+        TimelineEvent::PayloadCaptured { node_id, .. } => Some(node_id.clone()),
+        TimelineEvent::FaultRecorded { node_id, .. } => Some(node_id.clone()),
+        TimelineEvent::ExecutionCancelled { node_id, .. } => node_id.clone(),
+        TimelineEvent::CacheLookup { node_id, .. } => Some(node_id.clone()),
+        TimelineEvent::BusCaptured { node_id, .. } => Some(node_id.clone()),
+    };
+
+    ReplayFrame {
+        current_node_id,
+        event: event.clone(),
+    }
+}
+
+/// Paced playback started by [`ReplayEngine::play`].
+///
+/// Each call to [`next`](Self::next) waits for the recorded gap to the next
+/// event, scaled by the playback speed, and then advances the engine. Dropping
+/// the playback pauses it; the engine keeps its cursor.
+pub struct ReplayPlayback<'a> {
+    engine: &'a mut ReplayEngine,
+    speed: f64,
+}
+
+impl ReplayPlayback<'_> {
+    /// Wait for and return the next frame, or None at the end of the Timeline.
+    pub async fn next(&mut self) -> Option<ReplayFrame> {
+        let upcoming = self.engine.timeline.events.get(self.engine.cursor)?;
+        if let Some(current) = self.engine.current_frame() {
+            let gap = upcoming
+                .timestamp()
+                .duration_since(current.event.timestamp());
+            if self.speed.is_finite() && self.speed > 0.0 && !gap.is_zero() {
+                tokio::time::sleep(gap.div_f64(self.speed)).await;
+            }
+        }
+        self.engine.next_step()
+    }
+
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed;
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+}
+
+/// Re-executes a recorded run through the real Axon and compares the new
+/// run with the recording, node by node.
+///
+/// The recording must include the circuit input (see
+/// [`CapturePolicy`](ranvier_core::capture::CapturePolicy)). A captured Bus is
+/// restored before the run, and nodes whose outputs were captured under a
+/// [`NodeCapturePolicy`] are captured again so their outputs can be compared.
+/// External calls should go through Synapses replaying the run's
+/// [`SynapseCassette`], passed in with the resources.
+///
+/// ```rust,ignore
+/// let report = ReplayExecutor::new(&checkout, recorded).run(&resources).await?;
+/// report.assert_identical();
+/// ```
+pub struct ReplayExecutor<'a, In, Out, E, Res> {
+    axon: &'a Axon<In, Out, E, Res>,
+    engine: ReplayEngine,
+}
+
+impl<'a, In, Out, E, Res> ReplayExecutor<'a, In, Out, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    pub fn new(axon: &'a Axon<In, Out, E, Res>, recorded: Timeline) -> Self {
+        Self {
+            axon,
+            engine: ReplayEngine::new(recorded),
+        }
+    }
+
+    /// The engine over the recorded Timeline.
+    pub fn engine(&self) -> &ReplayEngine {
+        &self.engine
+    }
+
+    /// Run the Axon on the recorded input and compare the two runs.
+    pub async fn run(&self, resources: &Res) -> Result<ReplayReport<Out, E>> {
+        let input: In = self.engine.captured_input()?.ok_or_else(|| {
+            anyhow!(
+                "recorded timeline has no captured circuit input; record it with a CapturePolicy"
+            )
+        })?;
+        let recorded = &self.engine.timeline;
+
+        let mut bus = self.engine.reconstruct_bus().unwrap_or_default();
+        bus.insert(Timeline::new());
+        let captured_nodes = captured_output_nodes(recorded);
+        if let Some((format, _)) = captured_nodes.first() {
+            let policy = captured_nodes
+                .iter()
+                .fold(NodeCapturePolicy::new(*format), |policy, (_, node_id)| {
+                    policy.node(node_id.clone())
+                })
+                .with_max_bytes(usize::MAX);
+            bus.insert(policy);
+        }
+
+        let outcome = self.axon.execute(input, resources, &mut bus).await;
+        let timeline = bus.remove::<Timeline>().unwrap_or_default();
+        let divergences = compare_runs(recorded, &timeline);
+        Ok(ReplayReport {
+            outcome,
+            timeline,
+            divergences,
+        })
+    }
+}
+
+/// Result of a [`ReplayExecutor`] run.
+#[derive(Debug)]
+pub struct ReplayReport<Out, E> {
+    /// Outcome of the re-execution.
+    pub outcome: Outcome<Out, E>,
+    /// Timeline of the re-execution.
+    pub timeline: Timeline,
+    /// Differences from the recording, in execution order.
+    pub divergences: Vec<ReplayDivergence>,
+}
+
+impl<Out, E> ReplayReport<Out, E> {
+    pub fn is_identical(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Panic with every divergence unless the runs match.
+    #[track_caller]
+    pub fn assert_identical(&self) {
+        if !self.is_identical() {
+            let lines: Vec<String> = self.divergences.iter().map(ToString::to_string).collect();
+            panic!(
+                "replay diverged from the recording:\n  {}",
+                lines.join("\n  ")
+            );
+        }
+    }
+}
+
+/// One difference between a recorded run and its re-execution.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayDivergence {
+    /// The `step`-th node to finish is a different node, or only one run has
+    /// it. Later nodes are not compared.
+    Path {
+        step: usize,
+        recorded: Option<String>,
+        replayed: Option<String>,
+    },
+    /// The node finished with a different outcome (`"Next"`, `"Branch:id"`, ...).
+    Outcome {
+        node_id: String,
+        recorded: String,
+        replayed: String,
+    },
+    /// The node, or the circuit for the ingress node, produced a different
+    /// output.
+    Output {
+        node_id: String,
+        recorded: serde_json::Value,
+        replayed: serde_json::Value,
+    },
+}
+
+impl std::fmt::Display for ReplayDivergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path {
+                step,
+                recorded,
+                replayed,
+            } => write!(
+                f,
+                "step {}: recorded node {:?}, replayed node {:?}",
+                step, recorded, replayed
+            ),
+            Self::Outcome {
+                node_id,
+                recorded,
+                replayed,
+            } => write!(
+                f,
+                "node {}: recorded outcome {}, replayed {}",
+                node_id, recorded, replayed
+            ),
+            Self::Output {
+                node_id,
+                recorded,
+                replayed,
+            } => write!(
+                f,
+                "node {}: recorded output {}, replayed {}",
+                node_id, recorded, replayed
+            ),
+        }
+    }
+}
+
+/// Nodes with a full (untruncated) output capture, with its format.
+fn captured_output_nodes(timeline: &Timeline) -> Vec<(CaptureFormat, String)> {
+    let mut nodes: Vec<(CaptureFormat, String)> = Vec::new();
+    for event in &timeline.events {
+        if let TimelineEvent::PayloadCaptured {
+            node_id,
+            direction: CaptureDirection::NodeOutput,
+            payload,
+            ..
+        } = event
+            && !nodes.iter().any(|(_, id)| id == node_id)
+        {
+            nodes.push((payload.format, node_id.clone()));
+        }
+    }
+    nodes
+}
+
+fn node_exits(timeline: &Timeline) -> Vec<(&str, &str)> {
+    timeline
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            TimelineEvent::NodeExit {
+                node_id,
+                outcome_type,
+                ..
+            } => Some((node_id.as_str(), outcome_type.as_str())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Outputs as JSON, keyed by node and by how often that node produced one.
+fn outputs(timeline: &Timeline) -> Vec<(String, usize, serde_json::Value)> {
+    let mut outputs: Vec<(String, usize, serde_json::Value)> = Vec::new();
+    for event in &timeline.events {
+        if let TimelineEvent::PayloadCaptured {
+            node_id,
+            direction: CaptureDirection::NodeOutput | CaptureDirection::Output,
+            payload,
+            ..
+        } = event
+            && !payload.is_truncated()
+            && let Ok(json) = payload.to_json()
+        {
+            let visit = outputs.iter().filter(|(id, _, _)| id == node_id).count();
+            outputs.push((node_id.clone(), visit, json));
+        }
+    }
+    outputs
+}
+
+fn compare_runs(recorded: &Timeline, replayed: &Timeline) -> Vec<ReplayDivergence> {
+    let mut divergences = Vec::new();
+    let (recorded_exits, replayed_exits) = (node_exits(recorded), node_exits(replayed));
+    for step in 0..recorded_exits.len().max(replayed_exits.len()) {
+        match (recorded_exits.get(step), replayed_exits.get(step)) {
+            (Some((recorded_node, recorded)), Some((replayed_node, replayed)))
+                if recorded_node == replayed_node =>
+            {
+                if recorded != replayed {
+                    divergences.push(ReplayDivergence::Outcome {
+                        node_id: recorded_node.to_string(),
+                        recorded: recorded.to_string(),
+                        replayed: replayed.to_string(),
+                    });
+                }
+            }
+            (recorded, replayed) => {
+                divergences.push(ReplayDivergence::Path {
+                    step,
+                    recorded: recorded.map(|(node_id, _)| node_id.to_string()),
+                    replayed: replayed.map(|(node_id, _)| node_id.to_string()),
+                });
+                break;
+            }
+        }
+    }
+
+    let replayed_outputs = outputs(replayed);
+    for (node_id, visit, recorded) in outputs(recorded) {
+        if let Some((_, _, replayed)) = replayed_outputs
+            .iter()
+            .find(|(id, v, _)| *id == node_id && *v == visit)
+            && *replayed != recorded
+        {
+            divergences.push(ReplayDivergence::Output {
+                node_id,
+                recorded,
+                replayed: replayed.clone(),
+            });
+        }
+    }
+    divergences
+}
+
 /// Result of an event-sourcing replay operation.
 #[derive(Debug, Clone)]
 pub struct ReplayRecoveryResult {
@@ -224,25 +718,261 @@ pub async fn validate_migration_path(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ranvier_core::timeline::{Timeline, TimelineEvent};
+    use ranvier_core::timeline::{Timeline, TimelineEvent, Timestamp};
 
     fn test_event(node_id: &str, enter: bool) -> TimelineEvent {
         if enter {
             TimelineEvent::NodeEnter {
                 node_id: node_id.to_string(),
                 node_label: node_id.to_string(),
-                timestamp: 0,
+                timestamp: Timestamp::UNIX_EPOCH,
+                trace: None,
             }
         } else {
             TimelineEvent::NodeExit {
                 node_id: node_id.to_string(),
                 outcome_type: "Next".to_string(),
                 duration_ms: 0,
-                timestamp: 0,
+                timestamp: Timestamp::UNIX_EPOCH,
+                trace: None,
             }
         }
     }
 
+    #[tokio::test]
+    async fn failed_run_bus_is_rebuilt_from_capture() {
+        use crate::axon::Axon;
+        use ranvier_core::bus_snapshot::register_replay_type;
+        use ranvier_core::capture::CapturePolicy;
+        use ranvier_core::outcome::Outcome;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct TenantConfig {
+            region: String,
+        }
+        struct PaymentsClient;
+        register_replay_type::<TenantConfig>();
+
+        let axon = Axon::<u32, u32, String>::new("checkout")
+            .then_fn("charge", |_: u32, _bus: &mut Bus| {
+                Outcome::<u32, String>::Fault("card declined".into())
+            })
+            .with_capture_policy(CapturePolicy::new(CaptureFormat::Json).with_bus());
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        bus.insert(TenantConfig {
+            region: "eu-west".into(),
+        });
+        bus.insert_named(
+            "fallback",
+            TenantConfig {
+                region: "us".into(),
+            },
+        );
+        bus.insert(PaymentsClient);
+        assert!(matches!(
+            axon.execute(7, &(), &mut bus).await,
+            Outcome::Fault(_)
+        ));
+
+        let json = serde_json::to_string(bus.read::<Timeline>().unwrap()).unwrap();
+        let engine = ReplayEngine::new(serde_json::from_str(&json).unwrap());
+        let snapshot = engine.captured_bus().expect("bus captured");
+        let client = snapshot
+            .get(std::any::type_name::<PaymentsClient>(), None)
+            .unwrap();
+        assert_eq!(client.value, None);
+
+        let replayed = engine.reconstruct_bus().unwrap();
+        assert_eq!(replayed.read::<TenantConfig>().unwrap().region, "eu-west");
+        assert_eq!(
+            replayed
+                .read_named::<TenantConfig>("fallback")
+                .unwrap()
+                .region,
+            "us"
+        );
+        assert!(replayed.read::<PaymentsClient>().is_none());
+    }
+
+    #[tokio::test]
+    async fn captured_payloads_decode_in_recorded_format() {
+        use crate::axon::Axon;
+        use ranvier_core::bus::Bus;
+        use ranvier_core::capture::CapturePolicy;
+        use ranvier_core::outcome::Outcome;
+
+        let axon = Axon::<Vec<u32>, Vec<u32>, String>::new("capture")
+            .then_fn("double", |items: Vec<u32>, _bus: &mut Bus| {
+                Outcome::next(items.into_iter().map(|n| n * 2).collect::<Vec<u32>>())
+            })
+            .with_capture_policy(CapturePolicy::new(CaptureFormat::MessagePack));
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        axon.execute(vec![1, 2], &(), &mut bus).await;
+
+        let timeline = bus.read::<Timeline>().unwrap().clone();
+        let engine = ReplayEngine::new(timeline);
+        assert_eq!(engine.capture_format(), Some(CaptureFormat::MessagePack));
+        assert_eq!(
+            engine.captured_input::<Vec<u32>>().unwrap(),
+            Some(vec![1, 2])
+        );
+        assert_eq!(
+            engine.captured_output::<Vec<u32>>().unwrap(),
+            Some(vec![2, 4])
+        );
+    }
+
+    fn at(millis: u64) -> TimelineEvent {
+        TimelineEvent::NodePaused {
+            node_id: format!("n{millis}"),
+            timestamp: Timestamp::from_millis(millis),
+            trace: None,
+        }
+    }
+
+    fn node_of(frame: Option<ReplayFrame>) -> Option<String> {
+        frame.and_then(|frame| frame.current_node_id)
+    }
+
+    #[tokio::test]
+    async fn replay_executor_reports_changed_outcomes_and_outputs() {
+        use ranvier_core::capture::CapturePolicy;
+
+        fn checkout(rate: u32) -> Axon<u32, u32, String> {
+            Axon::<u32, u32, String>::new("checkout")
+                .then_fn("price", move |quantity: u32, _bus: &mut Bus| {
+                    Outcome::next(quantity * rate)
+                })
+                .then_fn("limit", |total: u32, _bus: &mut Bus| {
+                    if total > 100 {
+                        Outcome::branch("review", None)
+                    } else {
+                        Outcome::next(total)
+                    }
+                })
+                .with_capture_policy(CapturePolicy::new(CaptureFormat::Json))
+        }
+
+        let original = checkout(10);
+        let mut bus = Bus::new();
+        bus.insert(Timeline::new());
+        bus.insert(NodeCapturePolicy::new(CaptureFormat::Json).node("price"));
+        original.execute(5, &(), &mut bus).await;
+        let recorded = bus.read::<Timeline>().unwrap().clone();
+
+        let report = ReplayExecutor::new(&original, recorded.clone())
+            .run(&())
+            .await
+            .unwrap();
+        report.assert_identical();
+        assert!(matches!(report.outcome, Outcome::Next(50)));
+
+        let refactored = checkout(30);
+        let report = ReplayExecutor::new(&refactored, recorded)
+            .run(&())
+            .await
+            .unwrap();
+        let price = refactored.schematic.nodes[1].id.clone();
+        let limit = refactored.schematic.nodes[2].id.clone();
+        assert!(report.divergences.contains(&ReplayDivergence::Output {
+            node_id: price,
+            recorded: 50.into(),
+            replayed: 150.into(),
+        }));
+        assert!(report.divergences.contains(&ReplayDivergence::Outcome {
+            node_id: limit,
+            recorded: "Next".into(),
+            replayed: "Branch:review".into(),
+        }));
+        assert!(
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| report.assert_identical()))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn cursor_seeks_and_steps_back() {
+        let mut timeline = Timeline::new();
+        for millis in [10, 20, 30, 40] {
+            timeline.push(at(millis));
+        }
+        let mut engine = ReplayEngine::new(timeline);
+        assert!(engine.current_frame().is_none());
+
+        assert_eq!(
+            node_of(engine.seek_to(Timestamp::from_millis(35))).unwrap(),
+            "n30"
+        );
+        assert_eq!(engine.position(), 3);
+        assert_eq!(node_of(engine.step_back()).unwrap(), "n20");
+        assert_eq!(node_of(engine.next_step()).unwrap(), "n30");
+        assert_eq!(node_of(engine.current_frame()).unwrap(), "n30");
+
+        assert!(engine.seek_to(Timestamp::from_millis(5)).is_none());
+        assert!(engine.step_back().is_none());
+        assert_eq!(node_of(engine.next_step()).unwrap(), "n10");
+        assert_eq!(
+            node_of(engine.seek_to(Timestamp::from_millis(99))).unwrap(),
+            "n40"
+        );
+        assert!(engine.next_step().is_none());
+    }
+
+    #[tokio::test]
+    async fn playback_scales_recorded_gaps_by_speed() {
+        let mut timeline = Timeline::new();
+        for millis in [0, 1_000, 2_000] {
+            timeline.push(at(millis));
+        }
+        let mut engine = ReplayEngine::new(timeline);
+        let started = std::time::Instant::now();
+        let mut playback = engine.play(100.0);
+        let mut played = Vec::new();
+        while let Some(frame) = playback.next().await {
+            played.extend(frame.current_node_id);
+        }
+        let elapsed = started.elapsed();
+        assert_eq!(played, ["n0", "n1000", "n2000"]);
+        assert!(
+            elapsed >= std::time::Duration::from_millis(20),
+            "{elapsed:?}"
+        );
+        assert!(elapsed < std::time::Duration::from_secs(1), "{elapsed:?}");
+        assert_eq!(engine.position(), engine.len());
+    }
+
+    #[test]
+    fn run_to_break_stops_at_matching_frames() {
+        let mut timeline = Timeline::new();
+        for node in ["A", "B", "C"] {
+            timeline.push(test_event(node, true));
+            timeline.push(test_event(node, false));
+        }
+        let mut engine = ReplayEngine::new(timeline);
+        engine.break_on_node("B").break_on(
+            |event| matches!(event, TimelineEvent::NodeExit { node_id, .. } if node_id == "C"),
+        );
+
+        let hit = engine.run_to_break().unwrap();
+        assert!(matches!(hit.event, TimelineEvent::NodeEnter { .. }));
+        assert_eq!(hit.current_node_id.as_deref(), Some("B"));
+        assert_eq!(engine.position(), 3);
+        assert!(matches!(
+            engine.run_to_break().unwrap().event,
+            TimelineEvent::NodeExit { .. }
+        ));
+        assert_eq!(engine.position(), 4);
+        assert_eq!(node_of(engine.run_to_break()).unwrap(), "C");
+        assert!(engine.run_to_break().is_none());
+
+        engine.reset();
+        engine.clear_breakpoints();
+        assert!(engine.run_to_break().is_none());
+        assert_eq!(engine.position(), engine.len());
+    }
+
     #[test]
     fn test_replay_fast_forward_to_active() {
         let mut timeline = Timeline::new();
@@ -2,6 +2,7 @@
 //!
 //! Provides `RetryPolicy` with fixed and exponential backoff strategies.
 
+use ranvier_core::node_policy::{BackoffConfig, NodePolicyConfig};
 use std::time::Duration;
 
 /// Configurable retry policy for individual Axon transitions.
@@ -86,6 +87,87 @@ impl RetryPolicy {
     }
 }
 
+/// Bounds for honoring [`Outcome::Retry`](ranvier_core::outcome::Outcome::Retry).
+///
+/// Insert into the Bus to let transitions ask for a re-run of their node.
+/// Without it a `Retry` outcome ends the execution and is returned to the
+/// caller as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutcomeRetryPolicy {
+    /// Re-runs allowed per node visit; a `Retry` after the last one is
+    /// returned to the caller.
+    pub max_retries: u32,
+    /// Upper bound on the delay a transition may ask for.
+    pub max_delay: Duration,
+}
+
+impl OutcomeRetryPolicy {
+    pub fn new(max_retries: u32, max_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            max_delay,
+        }
+    }
+}
+
+impl Default for OutcomeRetryPolicy {
+    /// 3 re-runs, each delay capped at 30 seconds.
+    fn default() -> Self {
+        Self::new(3, Duration::from_secs(30))
+    }
+}
+
+impl RetryPolicy {
+    /// Apply `max_retries` and `backoff` from a `[nodes.<label>]` entry.
+    ///
+    /// Fields the entry leaves unset keep their code-declared values.
+    pub fn with_overrides(mut self, overrides: &NodePolicyConfig) -> Self {
+        if let Some(max_retries) = overrides.max_retries {
+            self.max_retries = max_retries;
+        }
+        if let Some(backoff) = &overrides.backoff {
+            self.backoff = BackoffStrategy::from(backoff);
+        }
+        self
+    }
+}
+
+impl From<&BackoffConfig> for BackoffStrategy {
+    fn from(config: &BackoffConfig) -> Self {
+        match *config {
+            BackoffConfig::Fixed { delay_ms } => Self::Fixed(Duration::from_millis(delay_ms)),
+            BackoffConfig::Exponential {
+                initial_ms,
+                multiplier,
+                max_ms,
+            } => Self::Exponential {
+                initial: Duration::from_millis(initial_ms),
+                multiplier,
+                max: Duration::from_millis(max_ms),
+            },
+        }
+    }
+}
+
+impl From<&BackoffStrategy> for BackoffConfig {
+    fn from(strategy: &BackoffStrategy) -> Self {
+        match *strategy {
+            BackoffStrategy::Fixed(delay) => Self::Fixed {
+                delay_ms: delay.as_millis() as u64,
+            },
+            BackoffStrategy::Exponential {
+                initial,
+                multiplier,
+                max,
+            } => Self::Exponential {
+                initial_ms: initial.as_millis() as u64,
+                multiplier,
+                max_ms: max.as_millis() as u64,
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,4 +212,27 @@ mod tests {
         assert_eq!(policy.delay_for_attempt(0), Duration::from_millis(100));
         assert_eq!(policy.delay_for_attempt(1), Duration::from_millis(200));
     }
+
+    #[test]
+    fn config_overrides_replace_only_set_fields() {
+        let policy =
+            RetryPolicy::fixed(3, Duration::from_millis(100)).with_overrides(&NodePolicyConfig {
+                max_retries: Some(6),
+                ..Default::default()
+            });
+        assert_eq!(policy.max_retries, 6);
+        assert_eq!(policy.delay_for_attempt(2), Duration::from_millis(100));
+
+        let policy = policy.with_overrides(&NodePolicyConfig {
+            backoff: Some(BackoffConfig::Exponential {
+                initial_ms: 10,
+                multiplier: 3.0,
+                max_ms: 50,
+            }),
+            ..Default::default()
+        });
+        assert_eq!(policy.max_retries, 6);
+        assert_eq!(policy.delay_for_attempt(1), Duration::from_millis(30));
+        assert_eq!(policy.delay_for_attempt(2), Duration::from_millis(50));
+    }
 }
@@ -8,6 +8,10 @@
 //!
 //! A `StreamingAxon` is always terminal — you cannot chain `.then()` after it.
 //! Use `collect_into_vec()` to collapse the stream back into a regular `Axon`.
+//!
+//! Streams of per-item outcomes (from `Axon::then_stream_outcomes()`) can
+//! run further transitions on each item with `then_each()` and collapse with
+//! `collect_outcomes()`.
 
 use crate::axon::{Axon, BoxFuture};
 use futures_core::Stream;
@@ -16,8 +20,10 @@ use ranvier_core::bus::Bus;
 use ranvier_core::cancellation::{CancellationContext, CancellationToken};
 use ranvier_core::outcome::Outcome;
 use ranvier_core::schematic::Schematic;
-use ranvier_core::streaming::StreamTimeoutConfig;
-use ranvier_core::transition::ResourceRequirement;
+use ranvier_core::streaming::{
+    OutcomeStream, StreamTimeoutConfig, StreamTransition, StreamingTransition,
+};
+use ranvier_core::transition::{ResourceRequirement, Transition};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
@@ -262,7 +268,7 @@ where
 
         // Add map_items node to schematic
         let mut schematic = self.schematic;
-        let map_node_id = uuid::Uuid::new_v4().to_string();
+        let map_node_id = schematic.next_node_id("map_items");
         let last_node_id = schematic
             .nodes
             .last()
@@ -406,6 +412,248 @@ where
                 ranvier_core::saga::SagaCompensationRegistry::new(),
             )),
             iam_handle: None,
+            capture_policy: None,
+            concurrency: None,
+            node_policies: None,
+            execution_hooks: Vec::new(),
+            interceptors: Vec::new(),
+            schematic_observed: std::sync::OnceLock::new(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Per-item outcome streams
+// ---------------------------------------------------------------------------
+
+/// Runs a [`StreamTransition`] as a [`StreamingTransition`] whose items are
+/// outcomes, so it can reuse the `then_stream` plumbing.
+pub(crate) struct OutcomeStreamAdapter<S, Next> {
+    inner: S,
+    _next: std::marker::PhantomData<fn() -> Next>,
+}
+
+impl<S, Next> OutcomeStreamAdapter<S, Next> {
+    pub(crate) fn new(inner: S) -> Self {
+        Self {
+            inner,
+            _next: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<S: Clone, Next> Clone for OutcomeStreamAdapter<S, Next> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl<S, From, Next> StreamingTransition<From> for OutcomeStreamAdapter<S, Next>
+where
+    S: StreamTransition<From, Next>,
+    From: Send + 'static,
+    Next: Send + 'static,
+{
+    type Item = Outcome<Next, S::Error>;
+    type Error = std::convert::Infallible;
+    type Resources = S::Resources;
+
+    async fn run_stream(
+        &self,
+        input: From,
+        resources: &Self::Resources,
+        bus: &mut Bus,
+    ) -> Result<OutcomeStream<Next, S::Error>, Self::Error> {
+        Ok(self.inner.run_stream(input, resources, bus).await)
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.inner.description()
+    }
+}
+
+impl<In, T, E, Res> StreamingAxon<In, Outcome<T, E>, E, Res>
+where
+    In: Send + Sync + 'static,
+    T: Send + 'static,
+    E: Send + Sync + Debug + 'static,
+    Res: ResourceRequirement + Clone,
+{
+    /// Run `transition` on each `Next` item as it is pulled from the stream.
+    ///
+    /// Items are processed one at a time, so nothing is buffered between the
+    /// steps. Non-`Next` items pass through unchanged. The transition gets a
+    /// Bus forked from the execution's Bus with
+    /// [`Bus::fork_for_parallel`], shared by all items of one execution, and
+    /// a clone of the resources.
+    #[track_caller]
+    pub fn then_each<U, Trans>(self, transition: Trans) -> StreamingAxon<In, Outcome<U, E>, E, Res>
+    where
+        U: Send + 'static,
+        Trans: Transition<T, U, Resources = Res, Error = E> + Clone + Send + Sync + 'static,
+    {
+        let caller = std::panic::Location::caller();
+        let label = transition.label();
+        let description = transition.description();
+        let fixed_node_id = transition.node_id();
+        let prev_executor = self.stream_executor;
+
+        let new_executor: StreamExecutor<In, Outcome<U, E>, E, Res> = Arc::new(
+            move |input: In,
+                  res: &Res,
+                  bus: &mut Bus|
+                  -> BoxFuture<'_, Result<OutcomeStream<U, E>, StreamingAxonError<E>>> {
+                let prev = prev_executor.clone();
+                let transition = transition.clone();
+                Box::pin(async move {
+                    let stream = prev(input, res, bus).await?;
+                    let item_bus = bus.fork_for_parallel();
+                    let state = (stream, transition, res.clone(), item_bus);
+                    Ok(Box::pin(futures_util::stream::unfold(
+                        state,
+                        |(mut stream, transition, res, mut bus)| async move {
+                            let outcome = match stream.next().await? {
+                                Outcome::Next(item) => {
+                                    bus.set_access_policy(
+                                        transition.label(),
+                                        transition.bus_access_policy(),
+                                    );
+                                    let outcome = transition.run(item, &res, &mut bus).await;
+                                    bus.clear_access_policy();
+                                    outcome
+                                }
+                                other => other.map(|_| unreachable!()),
+                            };
+                            Some((outcome, (stream, transition, res, bus)))
+                        },
+                    )) as OutcomeStream<U, E>)
+                })
+            },
+        );
+
+        let mut schematic = self.schematic;
+        let node_id = fixed_node_id.unwrap_or_else(|| schematic.next_node_id(&label));
+        let last_node_id = schematic.nodes.last().map(|n| n.id.clone());
+        schematic.nodes.push(ranvier_core::schematic::Node {
+            id: node_id.clone(),
+            kind: ranvier_core::schematic::NodeKind::Atom,
+            label,
+            description,
+            input_type: std::any::type_name::<T>().to_string(),
+            output_type: std::any::type_name::<U>().to_string(),
+            resource_type: std::any::type_name::<Res>().to_string(),
+            metadata: Default::default(),
+            bus_capability: None,
+            source_location: Some(ranvier_core::schematic::SourceLocation::from(caller)),
+            position: None,
+            compensation_node_id: None,
+            input_schema: None,
+            output_schema: None,
+            item_type: Some(std::any::type_name::<U>().to_string()),
+            terminal: Some(true),
+        });
+        if let Some(from) = last_node_id {
+            schematic.edges.push(ranvier_core::schematic::Edge {
+                from,
+                to: node_id,
+                kind: ranvier_core::schematic::EdgeType::Linear,
+                label: Some("Each".to_string()),
+            });
+        }
+
+        StreamingAxon {
+            schematic,
+            stream_executor: new_executor,
+            timeout_config: self.timeout_config,
+            buffer_size: self.buffer_size,
+        }
+    }
+}
+
+impl<In, T, E, Res> StreamingAxon<In, Outcome<T, E>, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    T: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + Debug + 'static,
+    Res: ResourceRequirement,
+{
+    /// Collapse the stream into a `Vec` of its `Next` items.
+    ///
+    /// The first item that is not `Next` stops consumption and becomes the
+    /// outcome of the Axon. A fault before the stream starts is returned as
+    /// that fault; other start-up failures emit `execution.stream.error`.
+    pub fn collect_outcomes(self) -> Axon<In, Vec<T>, E, Res> {
+        let stream_executor = self.stream_executor.clone();
+        let timeout_config = self.timeout_config.clone();
+
+        let executor: crate::axon::Executor<In, Vec<T>, E, Res> = Arc::new(
+            move |input: In, res: &Res, bus: &mut Bus| -> BoxFuture<'_, Outcome<Vec<T>, E>> {
+                let stream_executor = stream_executor.clone();
+                let timeout_config = timeout_config.clone();
+
+                Box::pin(async move {
+                    let stream = match stream_executor(input, res, bus).await {
+                        Ok(stream) => stream,
+                        Err(StreamingAxonError::PipelineFault(error)) => {
+                            return Outcome::Fault(error);
+                        }
+                        Err(error) => {
+                            return Outcome::emit(
+                                "execution.stream.error",
+                                Some(serde_json::json!({ "error": error.to_string() })),
+                            );
+                        }
+                    };
+                    let mut stream = match &timeout_config {
+                        Some(config)
+                            if config.init.is_some()
+                                || config.idle.is_some()
+                                || config.total.is_some() =>
+                        {
+                            Box::pin(TimeoutStream::new(stream, config.clone()))
+                                as OutcomeStream<T, E>
+                        }
+                        _ => stream,
+                    };
+
+                    let mut items = Vec::new();
+                    while let Some(outcome) = stream.next().await {
+                        match outcome {
+                            Outcome::Next(item) => items.push(item),
+                            other => return other.map(|_| unreachable!()),
+                        }
+                    }
+                    Outcome::Next(items)
+                })
+            },
+        );
+
+        Axon {
+            schematic: self.schematic,
+            executor,
+            execution_mode: crate::axon::ExecutionMode::Local,
+            persistence_store: None,
+            audit_sink: None,
+            dlq_sink: None,
+            dlq_policy: Default::default(),
+            dynamic_dlq_policy: None,
+            saga_policy: Default::default(),
+            dynamic_saga_policy: None,
+            saga_compensation_registry: Arc::new(std::sync::RwLock::new(
+                ranvier_core::saga::SagaCompensationRegistry::new(),
+            )),
+            iam_handle: None,
+            capture_policy: None,
+            concurrency: None,
+            node_policies: None,
+            execution_hooks: Vec::new(),
+            interceptors: Vec::new(),
+            schematic_observed: std::sync::OnceLock::new(),
         }
     }
 }
@@ -724,4 +972,74 @@ mod tests {
         token.cancel(ranvier_core::cancellation::CancellationReason::Explicit);
         assert_eq!(stream.next().await, None);
     }
+
+    #[derive(Clone)]
+    struct Countdown;
+
+    #[async_trait::async_trait]
+    impl StreamTransition<u32, u32> for Countdown {
+        type Error = String;
+        type Resources = ();
+
+        async fn run_stream(
+            &self,
+            from: u32,
+            _res: &(),
+            _bus: &mut Bus,
+        ) -> OutcomeStream<u32, String> {
+            Box::pin(stream::iter((0..=from).rev().map(|n| {
+                if n == 0 {
+                    Outcome::Fault("liftoff".to_string())
+                } else {
+                    Outcome::Next(n)
+                }
+            })))
+        }
+    }
+
+    #[derive(Clone)]
+    struct Label;
+
+    #[async_trait::async_trait]
+    impl Transition<u32, String> for Label {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, n: u32, _res: &(), _bus: &mut Bus) -> Outcome<String, String> {
+            Outcome::Next(format!("T-{n}"))
+        }
+    }
+
+    #[tokio::test]
+    async fn then_each_runs_per_item_and_passes_faults_through() {
+        let streaming = Axon::<u32, u32, String>::new("Launch")
+            .then_stream_outcomes(Countdown)
+            .then_each(Label);
+        let labels: Vec<_> = streaming
+            .schematic
+            .nodes
+            .iter()
+            .map(|node| node.label.as_str())
+            .collect();
+        assert_eq!(labels, ["Launch", "Countdown", "Label"]);
+
+        let mut bus = Bus::new();
+        let items: Vec<_> = streaming
+            .execute(2, &(), &mut bus)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(matches!(&items[..], [
+            Outcome::Next(a),
+            Outcome::Next(b),
+            Outcome::Fault(e),
+        ] if a == "T-2" && b == "T-1" && e == "liftoff"));
+
+        let collected = streaming.collect_outcomes();
+        assert!(matches!(
+            collected.execute(3, &(), &mut bus).await,
+            Outcome::Fault(ref e) if e == "liftoff"
+        ));
+    }
 }
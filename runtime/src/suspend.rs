@@ -0,0 +1,413 @@
+//! Durable suspension and resumption of executions.
+//!
+//! A transition pauses its execution by returning
+//! [`Outcome::Suspend(token, state)`](ranvier_core::outcome::Outcome::Suspend),
+//! e.g. while it waits for a webhook or a human approval. A
+//! [`ResumableAxon`] saves every suspension to a [`SuspensionStore`] and later
+//! re-runs the suspended node with the saved state as its input:
+//!
+//! ```rust,ignore
+//! let approvals = ResumableAxon::new(axon, Arc::new(InMemorySuspensionStore::new()));
+//!
+//! // Day 1: `AwaitApproval` returns Outcome::suspend(request_id, Some(json!(request)))
+//! let outcome = approvals.execute(request, &res, &mut Bus::new()).await?;
+//!
+//! // Day 3: the approval webhook arrives.
+//! let outcome = approvals
+//!     .resume(&request_id, Some(json!({ "approved": true })), &res, &mut Bus::new())
+//!     .await?;
+//! ```
+//!
+//! The payload passed to `resume` is available to the resumed node as the
+//! [`ResumeSignal`] Bus resource. A node that suspends usually checks for it
+//! first and continues with `Outcome::Next` once it is present.
+//!
+//! Only nodes added with `then` or `then_compensated` can be resumed. Node
+//! ids are derived from the circuit name, label and position, so an Axon
+//! rebuilt after a restart resumes at the same node; resuming fails if the
+//! circuit no longer has a node with the suspended id.
+
+use crate::axon::{Axon, ManualJump};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use ranvier_core::bus::Bus;
+use ranvier_core::outcome::Outcome;
+use ranvier_core::timeline::Timestamp;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// The node that returned `Outcome::Suspend`, recorded in the Bus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuspensionPoint {
+    pub token: String,
+    pub node_id: String,
+    pub node_label: String,
+    pub step: u64,
+    /// The suspended node's input, recorded while a [`ResumableAxon`] runs.
+    #[serde(default)]
+    pub input: Option<Value>,
+}
+
+/// Bus marker asking nodes to record their input in [`SuspensionPoint`].
+pub(crate) struct CaptureSuspensionInput;
+
+/// Payload delivered with [`ResumableAxon::resume`], readable from the Bus
+/// by the resumed node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResumeSignal(pub Option<Value>);
+
+/// A suspended execution as persisted by a [`SuspensionStore`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuspendedExecution {
+    pub token: String,
+    pub circuit: String,
+    pub node_id: String,
+    pub node_label: String,
+    /// The circuit input of the original execution.
+    pub input: Value,
+    /// State from `Outcome::Suspend`, the suspended node's input on resume.
+    pub state: Option<Value>,
+    /// The suspended node's original input, used on resume when `state` is
+    /// `None`.
+    #[serde(default)]
+    pub node_input: Option<Value>,
+    pub suspended_at: Timestamp,
+}
+
+/// Storage for suspended executions, keyed by token.
+#[async_trait]
+pub trait SuspensionStore: Send + Sync {
+    /// Save a suspension, replacing any earlier one with the same token.
+    async fn save(&self, execution: SuspendedExecution) -> Result<()>;
+
+    async fn load(&self, token: &str) -> Result<Option<SuspendedExecution>>;
+
+    /// Remove and return a suspension.
+    async fn take(&self, token: &str) -> Result<Option<SuspendedExecution>>;
+
+    /// All stored suspensions, oldest first.
+    async fn list(&self) -> Result<Vec<SuspendedExecution>>;
+}
+
+/// Process-local [`SuspensionStore`], mainly for tests.
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySuspensionStore {
+    inner: Arc<Mutex<HashMap<String, SuspendedExecution>>>,
+}
+
+impl InMemorySuspensionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SuspensionStore for InMemorySuspensionStore {
+    async fn save(&self, execution: SuspendedExecution) -> Result<()> {
+        self.inner
+            .lock()
+            .map_err(|_| anyhow!("suspension store lock poisoned"))?
+            .insert(execution.token.clone(), execution);
+        Ok(())
+    }
+
+    async fn load(&self, token: &str) -> Result<Option<SuspendedExecution>> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|_| anyhow!("suspension store lock poisoned"))?
+            .get(token)
+            .cloned())
+    }
+
+    async fn take(&self, token: &str) -> Result<Option<SuspendedExecution>> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|_| anyhow!("suspension store lock poisoned"))?
+            .remove(token))
+    }
+
+    async fn list(&self) -> Result<Vec<SuspendedExecution>> {
+        let mut executions: Vec<_> = self
+            .inner
+            .lock()
+            .map_err(|_| anyhow!("suspension store lock poisoned"))?
+            .values()
+            .cloned()
+            .collect();
+        executions.sort_by_key(|execution| execution.suspended_at);
+        Ok(executions)
+    }
+}
+
+/// An Axon whose suspensions are persisted and can be resumed by token.
+pub struct ResumableAxon<In, Out, E, Res = ()> {
+    axon: Axon<In, Out, E, Res>,
+    store: Arc<dyn SuspensionStore>,
+}
+
+impl<In, Out, E, Res> ResumableAxon<In, Out, E, Res>
+where
+    In: Send + Sync + Serialize + DeserializeOwned + 'static,
+    Out: Send + Sync + Serialize + DeserializeOwned + 'static,
+    E: Send + Sync + Serialize + DeserializeOwned + std::fmt::Debug + 'static,
+    Res: ranvier_core::transition::ResourceRequirement,
+{
+    pub fn new(axon: Axon<In, Out, E, Res>, store: Arc<dyn SuspensionStore>) -> Self {
+        Self { axon, store }
+    }
+
+    pub fn axon(&self) -> &Axon<In, Out, E, Res> {
+        &self.axon
+    }
+
+    pub fn store(&self) -> &Arc<dyn SuspensionStore> {
+        &self.store
+    }
+
+    /// Execute the Axon, saving the execution if it suspends.
+    ///
+    /// Errors only when a suspension cannot be saved.
+    pub async fn execute(
+        &self,
+        input: In,
+        resources: &Res,
+        bus: &mut Bus,
+    ) -> Result<Outcome<Out, E>> {
+        let input_value = serde_json::to_value(&input)?;
+        bus.remove::<SuspensionPoint>();
+        bus.insert(CaptureSuspensionInput);
+        let outcome = self.axon.execute(input, resources, bus).await;
+        bus.remove::<CaptureSuspensionInput>();
+        self.save_if_suspended(&outcome, input_value, bus).await?;
+        Ok(outcome)
+    }
+
+    /// Resume the execution suspended under `token` at its suspended node.
+    ///
+    /// The stored suspension is consumed; if the node suspends again it is
+    /// saved again under the new token. The node receives the suspension
+    /// state, or its original input when the suspension carried no state.
+    /// Errors if the token is unknown, the stored input no longer decodes
+    /// into `In`, or the circuit no longer has the suspended node; the
+    /// suspension is kept in the last two cases.
+    pub async fn resume(
+        &self,
+        token: &str,
+        signal: Option<Value>,
+        resources: &Res,
+        bus: &mut Bus,
+    ) -> Result<Outcome<Out, E>> {
+        let suspended = self
+            .store
+            .take(token)
+            .await?
+            .ok_or_else(|| anyhow!("no suspended execution for token '{token}'"))?;
+        let input = match serde_json::from_value::<In>(suspended.input.clone()) {
+            Ok(input) => input,
+            Err(error) => {
+                self.store.save(suspended).await?;
+                return Err(anyhow!(
+                    "suspended input for token '{token}' no longer decodes: {error}"
+                ));
+            }
+        };
+
+        if !self
+            .axon
+            .schematic
+            .nodes
+            .iter()
+            .any(|node| node.id == suspended.node_id)
+        {
+            let error = anyhow!(
+                "circuit '{}' has no node '{}' ({}) to resume token '{token}' at",
+                self.axon.schematic.name,
+                suspended.node_id,
+                suspended.node_label
+            );
+            self.store.save(suspended).await?;
+            return Err(error);
+        }
+
+        tracing::info!(
+            token = %token,
+            node_id = %suspended.node_id,
+            node_label = %suspended.node_label,
+            "Resuming suspended execution"
+        );
+        let payload = suspended
+            .state
+            .clone()
+            .or_else(|| suspended.node_input.clone())
+            .unwrap_or(Value::Null);
+        bus.remove::<SuspensionPoint>();
+        bus.insert(ResumeSignal(signal));
+        bus.insert(CaptureSuspensionInput);
+        bus.insert(ManualJump {
+            target_node: suspended.node_id.clone(),
+            payload_override: Some(payload),
+        });
+        let outcome = self.axon.execute(input, resources, bus).await;
+        bus.remove::<ManualJump>();
+        bus.remove::<CaptureSuspensionInput>();
+        bus.remove::<ResumeSignal>();
+
+        self.save_if_suspended(&outcome, suspended.input, bus)
+            .await?;
+        Ok(outcome)
+    }
+
+    async fn save_if_suspended(
+        &self,
+        outcome: &Outcome<Out, E>,
+        input: Value,
+        bus: &Bus,
+    ) -> Result<()> {
+        let Outcome::Suspend(token, state) = outcome else {
+            return Ok(());
+        };
+        let point = bus.read::<SuspensionPoint>().filter(|p| p.token == *token);
+        let Some(point) = point else {
+            return Err(anyhow!(
+                "execution suspended with token '{token}' outside a resumable node"
+            ));
+        };
+        self.store
+            .save(SuspendedExecution {
+                token: token.clone(),
+                circuit: self.axon.schematic.name.clone(),
+                node_id: point.node_id.clone(),
+                node_label: point.node_label.clone(),
+                input,
+                state: state.clone(),
+                node_input: point.input.clone(),
+                suspended_at: Timestamp::now(),
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ranvier_core::transition::Transition;
+    use serde_json::json;
+
+    #[derive(Clone)]
+    struct AwaitApproval;
+
+    #[async_trait]
+    impl Transition<u32, u32> for AwaitApproval {
+        type Error = String;
+        type Resources = ();
+
+        async fn run(&self, amount: u32, _res: &(), bus: &mut Bus) -> Outcome<u32, String> {
+            match bus.read::<ResumeSignal>().and_then(|s| s.0.clone()) {
+                Some(signal) if signal["approved"] == true => Outcome::Next(amount),
+                Some(_) => Outcome::Fault("rejected".to_string()),
+                None => Outcome::suspend(format!("approval-{amount}"), Some(json!(amount))),
+            }
+        }
+    }
+
+    fn approval_flow() -> Axon<u32, u32, String> {
+        Axon::<u32, u32, String>::new("Refund")
+            .then_fn("Validate", |amount: u32, _bus| Outcome::next(amount))
+            .then(AwaitApproval)
+            .then_fn("Pay", |amount: u32, _bus| Outcome::next(amount * 100))
+    }
+
+    #[tokio::test]
+    async fn suspended_execution_resumes_at_the_suspended_node() {
+        let store = Arc::new(InMemorySuspensionStore::new());
+        let flow = ResumableAxon::new(approval_flow(), store.clone());
+
+        let outcome = flow.execute(42, &(), &mut Bus::new()).await.unwrap();
+        assert!(matches!(outcome, Outcome::Suspend(ref token, _) if token == "approval-42"));
+        let saved = store.load("approval-42").await.unwrap().unwrap();
+        assert_eq!(saved.node_label, "AwaitApproval");
+        assert_eq!(saved.state, Some(json!(42)));
+
+        // A rebuilt Axon derives the same node ids and resumes at the node.
+        let flow = ResumableAxon::new(approval_flow(), store.clone());
+        let outcome = flow
+            .resume(
+                "approval-42",
+                Some(json!({"approved": true})),
+                &(),
+                &mut Bus::new(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(outcome, Outcome::Next(4200)));
+        assert!(store.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stateless_suspension_resumes_with_the_node_input() {
+        let store = Arc::new(InMemorySuspensionStore::new());
+        let flow = ResumableAxon::new(
+            Axon::<u32, u32, String>::new("Refund")
+                .then_fn("Validate", |amount: u32, _bus| Outcome::next(amount + 1))
+                .then_fn("AwaitApproval", |amount: u32, bus: &mut Bus| {
+                    if bus.has::<ResumeSignal>() {
+                        Outcome::next(amount * 10)
+                    } else {
+                        Outcome::suspend("approval", None)
+                    }
+                }),
+            store.clone(),
+        );
+
+        flow.execute(4, &(), &mut Bus::new()).await.unwrap();
+        let saved = store.load("approval").await.unwrap().unwrap();
+        assert_eq!(saved.state, None);
+        assert_eq!(saved.node_input, Some(json!(5)));
+
+        let outcome = flow
+            .resume("approval", None, &(), &mut Bus::new())
+            .await
+            .unwrap();
+        assert!(matches!(outcome, Outcome::Next(50)));
+    }
+
+    #[tokio::test]
+    async fn resuming_at_a_node_the_circuit_no_longer_has_fails() {
+        let store = Arc::new(InMemorySuspensionStore::new());
+        let flow = ResumableAxon::new(approval_flow(), store.clone());
+        flow.execute(42, &(), &mut Bus::new()).await.unwrap();
+
+        // Same label, different position: a different node.
+        let changed = ResumableAxon::new(
+            Axon::<u32, u32, String>::new("Refund")
+                .then(AwaitApproval)
+                .then_fn("Pay", |amount: u32, _bus| Outcome::next(amount * 100)),
+            store.clone(),
+        );
+        let error = changed
+            .resume(
+                "approval-42",
+                Some(json!({"approved": true})),
+                &(),
+                &mut Bus::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("AwaitApproval"));
+        assert!(store.load("approval-42").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn resuming_an_unknown_token_fails() {
+        let flow = ResumableAxon::new(approval_flow(), Arc::new(InMemorySuspensionStore::new()));
+        let error = flow
+            .resume("missing", None, &(), &mut Bus::new())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("missing"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Sampling of which executions emit their timeline.
+//!
+//! A [`TimelineSampling`] on the Bus decides, per execution, whether the
+//! timeline reaches the configured sinks (`RANVIER_TIMELINE_OUTPUT`, a
+//! [`TimelineStoreHandle`](crate::TimelineStoreHandle), a
+//! [`TimelineWriter`](crate::TimelineWriter)):
+//!
+//! ```rust,ignore
+//! bus.insert(
+//!     TimelineSampling::ratio(0.01)
+//!         .with_circuit("checkout", 0.25)
+//!         .with_circuit("healthcheck", 0.0),
+//! );
+//! ```
+//!
+//! The decision is deterministic per Bus id. Executions outside the sample
+//! that cannot fault (`always_on_error` disabled) do not collect events at
+//! all; with `always_on_error`, events are collected and the timeline is
+//! dropped at the end unless the execution faulted.
+//!
+//! Without a policy on the Bus, file export keeps following
+//! `RANVIER_TIMELINE_SAMPLE_RATE` and `RANVIER_TIMELINE_ADAPTIVE`, and stores
+//! and writers receive every timeline.
+
+use ranvier_core::outcome::Outcome;
+use std::collections::HashMap;
+
+/// Which executions emit their timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineSampling {
+    /// Share of executions sampled, `0.0..=1.0`.
+    pub ratio: f64,
+    /// Emit every faulted execution, sampled or not.
+    pub always_on_error: bool,
+    /// Ratios overriding `ratio` for individual circuits, by circuit name.
+    pub circuits: HashMap<String, f64>,
+}
+
+impl Default for TimelineSampling {
+    /// Every execution.
+    fn default() -> Self {
+        Self::ratio(1.0)
+    }
+}
+
+impl TimelineSampling {
+    /// Sample `ratio` of executions, plus every faulted one.
+    pub fn ratio(ratio: f64) -> Self {
+        Self {
+            ratio: clamp_ratio(ratio),
+            always_on_error: true,
+            circuits: HashMap::new(),
+        }
+    }
+
+    /// Only emit faulted executions.
+    pub fn errors_only() -> Self {
+        Self::ratio(0.0)
+    }
+
+    /// `RANVIER_TIMELINE_SAMPLE_RATE` as the ratio (default `1.0`);
+    /// `always_on_error` unless `RANVIER_TIMELINE_ADAPTIVE=off`.
+    pub fn from_env() -> Self {
+        let ratio = std::env::var("RANVIER_TIMELINE_SAMPLE_RATE")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(1.0);
+        let adaptive = std::env::var("RANVIER_TIMELINE_ADAPTIVE").unwrap_or_default();
+        Self::ratio(ratio).with_always_on_error(!adaptive.eq_ignore_ascii_case("off"))
+    }
+
+    pub fn with_always_on_error(mut self, enabled: bool) -> Self {
+        self.always_on_error = enabled;
+        self
+    }
+
+    /// Use `ratio` for executions of `circuit`.
+    pub fn with_circuit(mut self, circuit: impl Into<String>, ratio: f64) -> Self {
+        self.circuits.insert(circuit.into(), clamp_ratio(ratio));
+        self
+    }
+
+    /// Ratio applied to `circuit`.
+    pub fn ratio_for(&self, circuit: &str) -> f64 {
+        self.circuits.get(circuit).copied().unwrap_or(self.ratio)
+    }
+
+    /// Whether the execution identified by `bus_id` is in the sample.
+    pub fn sampled(&self, circuit: &str, bus_id: uuid::Uuid) -> bool {
+        crate::axon::sampled_by_bus_id(bus_id, self.ratio_for(circuit))
+    }
+
+    /// Whether events must be collected, before the outcome is known.
+    pub fn collects(&self, circuit: &str, bus_id: uuid::Uuid) -> bool {
+        self.always_on_error || self.sampled(circuit, bus_id)
+    }
+
+    /// Whether the finished execution emits its timeline.
+    pub fn emits<Out, E>(
+        &self,
+        circuit: &str,
+        bus_id: uuid::Uuid,
+        outcome: &Outcome<Out, E>,
+    ) -> bool {
+        self.sampled(circuit, bus_id) || (self.always_on_error && outcome.is_fault())
+    }
+}
+
+fn clamp_ratio(ratio: f64) -> f64 {
+    if ratio.is_nan() {
+        1.0
+    } else {
+        ratio.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampling_honours_ratio_overrides_and_errors() {
+        let policy = TimelineSampling::ratio(0.0)
+            .with_circuit("checkout", 1.0)
+            .with_circuit("noisy", 7.0);
+        let id = uuid::Uuid::new_v4();
+        let ok: Outcome<(), String> = Outcome::Next(());
+        let fault: Outcome<(), String> = Outcome::Fault("boom".into());
+
+        assert_eq!(policy.ratio_for("noisy"), 1.0);
+        assert!(policy.emits("checkout", id, &ok));
+        assert!(!policy.emits("orders", id, &ok));
+        assert!(policy.emits("orders", id, &fault));
+        assert!(policy.collects("orders", id));
+
+        let policy = policy.with_always_on_error(false);
+        assert!(!policy.emits("orders", id, &fault));
+        assert!(!policy.collects("orders", id));
+
+        let half = TimelineSampling::ratio(0.5).with_always_on_error(false);
+        let sampled = (0..1_000)
+            .filter(|_| half.sampled("orders", uuid::Uuid::new_v4()))
+            .count();
+        assert!((350..650).contains(&sampled), "sampled {sampled} of 1000");
+    }
+
+    #[tokio::test]
+    async fn executor_only_stores_faulted_runs_when_sampling_errors() {
+        use crate::axon::Axon;
+        use crate::timeline_store::TimelineStoreHandle;
+        use ranvier_core::Bus;
+        use ranvier_core::timeline_store::{
+            InMemoryTimelineStore, TimelineRunQuery, TimelineStore,
+        };
+
+        let axon = Axon::<i32, i32, String>::new("Orders").then_fn("Charge", |n: i32, _bus| {
+            if n > 5 {
+                Outcome::fault("card declined".to_string())
+            } else {
+                Outcome::next(n * 10)
+            }
+        });
+        let store = InMemoryTimelineStore::new();
+        for input in [1, 9, 2] {
+            let mut bus = Bus::new();
+            bus.insert(TimelineStoreHandle::from_store(store.clone()));
+            bus.insert(TimelineSampling::errors_only());
+            axon.execute(input, &(), &mut bus).await;
+        }
+
+        let runs = store.list_runs(TimelineRunQuery::default()).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].outcome, "Fault");
+        assert!(runs[0].event_count > 0);
+    }
+}
@@ -0,0 +1,507 @@
+//! Recording execution timelines into a [`TimelineStore`].
+//!
+//! With a [`TimelineStoreHandle`] on the Bus, the Axon executor collects the
+//! timeline of every execution and appends it to the store as one run, keyed
+//! by the execution's trace id:
+//!
+//! ```rust,ignore
+//! let pool = SqlitePoolOptions::new().connect("sqlite://timelines.db?mode=rwc").await?;
+//! let store = SqliteTimelineStore::new(pool);
+//! store.ensure_schema().await?;
+//! let timelines = TimelineStoreHandle::from_store(store);
+//!
+//! bus.insert(timelines.clone());
+//! axon.execute(order, &resources, &mut bus).await;
+//!
+//! let recent = timelines.store().list_runs(TimelineRunQuery::new().limit(20)).await?;
+//! ```
+//!
+//! Unlike `RANVIER_TIMELINE_OUTPUT`, which rewrites one file, the store keeps
+//! every run; `SqliteTimelineStore` (feature `timeline-sqlite`) keeps them in
+//! a single embedded database. To keep a long-running service's store from
+//! growing without bound, compact it on an interval:
+//!
+//! ```rust,ignore
+//! let compaction = timelines.spawn_compaction(
+//!     TimelineRetention::new()
+//!         .max_runs(10_000)
+//!         .max_age(Duration::from_secs(7 * 24 * 3600))
+//!         .keep_faults(true),
+//!     Duration::from_secs(300),
+//!     shutdown.child_token(),
+//! );
+//! ```
+
+#[cfg(feature = "timeline-sqlite")]
+use async_trait::async_trait;
+use ranvier_core::cancellation::CancellationToken;
+use ranvier_core::timeline_store::{TimelineRetention, TimelineStore};
+#[cfg(feature = "timeline-sqlite")]
+use ranvier_core::timeline_store::{TimelineRun, TimelineRunQuery, TimelineRunSummary};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Bus-insertable timeline store handle read by the executor.
+#[derive(Clone)]
+pub struct TimelineStoreHandle {
+    inner: Arc<dyn TimelineStore>,
+}
+
+impl std::fmt::Debug for TimelineStoreHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimelineStoreHandle")
+            .finish_non_exhaustive()
+    }
+}
+
+impl TimelineStoreHandle {
+    pub fn from_store<S>(store: S) -> Self
+    where
+        S: TimelineStore + 'static,
+    {
+        Self {
+            inner: Arc::new(store),
+        }
+    }
+
+    pub fn from_arc(store: Arc<dyn TimelineStore>) -> Self {
+        Self { inner: store }
+    }
+
+    pub fn store(&self) -> Arc<dyn TimelineStore> {
+        self.inner.clone()
+    }
+
+    /// Compact the store with `retention` now and then every `interval` on
+    /// a background task, until `token` is cancelled.
+    ///
+    /// Compaction failures are logged and retried at the next interval.
+    pub fn spawn_compaction(
+        &self,
+        retention: TimelineRetention,
+        interval: Duration,
+        token: CancellationToken,
+    ) -> tokio::task::JoinHandle<()> {
+        let store = self.inner.clone();
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = token.cancelled() => break,
+                    _ = ticks.tick() => {}
+                }
+                match store.compact(&retention).await {
+                    Ok(0) => {}
+                    Ok(removed) => tracing::debug!(removed, "Compacted timeline store"),
+                    Err(error) => tracing::warn!(%error, "Timeline store compaction failed"),
+                }
+            }
+        })
+    }
+}
+
+/// SQLite-backed timeline store.
+#[cfg(feature = "timeline-sqlite")]
+#[derive(Debug, Clone)]
+pub struct SqliteTimelineStore {
+    pool: sqlx::Pool<sqlx::Sqlite>,
+    table: String,
+}
+
+#[cfg(feature = "timeline-sqlite")]
+#[derive(sqlx::FromRow)]
+struct SqliteTimelineRow {
+    run_id: String,
+    circuit: String,
+    outcome: String,
+    recorded_at: i64,
+    event_count: i64,
+}
+
+#[cfg(feature = "timeline-sqlite")]
+impl SqliteTimelineStore {
+    /// Use the default table `ranvier_timelines`. Call `ensure_schema()`
+    /// once at startup to create it.
+    pub fn new(pool: sqlx::Pool<sqlx::Sqlite>) -> Self {
+        Self::with_table(pool, "ranvier_timelines")
+    }
+
+    pub fn with_table(pool: sqlx::Pool<sqlx::Sqlite>, table: impl Into<String>) -> Self {
+        Self {
+            pool,
+            table: table.into(),
+        }
+    }
+
+    /// Create the timeline table when absent.
+    pub async fn ensure_schema(&self) -> Result<(), String> {
+        let create = format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id TEXT NOT NULL UNIQUE,
+                circuit TEXT NOT NULL,
+                outcome TEXT NOT NULL,
+                recorded_at INTEGER NOT NULL,
+                event_count INTEGER NOT NULL,
+                timeline TEXT NOT NULL
+            )",
+            table = self.table
+        );
+        sqlx::query(&create)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        let index = format!(
+            "CREATE INDEX IF NOT EXISTS {table}_circuit ON {table} (circuit, seq)",
+            table = self.table
+        );
+        sqlx::query(&index)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Start a query against this store.
+    pub fn query(&self) -> ranvier_core::timeline_store::TimelineQuery<'_> {
+        ranvier_core::timeline_store::TimelineQuery::new(self)
+    }
+}
+
+#[cfg(feature = "timeline-sqlite")]
+impl SqliteTimelineRow {
+    fn into_summary(self) -> TimelineRunSummary {
+        TimelineRunSummary {
+            run_id: self.run_id,
+            circuit: self.circuit,
+            outcome: self.outcome,
+            recorded_at: ranvier_core::timeline::Timestamp::from_nanos(self.recorded_at as u64),
+            event_count: self.event_count as usize,
+        }
+    }
+}
+
+#[cfg(feature = "timeline-sqlite")]
+#[async_trait]
+impl TimelineStore for SqliteTimelineStore {
+    async fn append_run(&self, run: TimelineRun) -> Result<(), String> {
+        // Replacing deletes and re-inserts so the run moves to the newest `seq`.
+        let mut tx = self.pool.begin().await.map_err(|e| e.to_string())?;
+        let delete = format!("DELETE FROM {} WHERE run_id = ?", self.table);
+        sqlx::query(&delete)
+            .bind(&run.run_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        let insert = format!(
+            "INSERT INTO {} (run_id, circuit, outcome, recorded_at, event_count, timeline)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            self.table
+        );
+        sqlx::query(&insert)
+            .bind(&run.run_id)
+            .bind(&run.circuit)
+            .bind(&run.outcome)
+            .bind(run.recorded_at.as_nanos() as i64)
+            .bind(run.timeline.events.len() as i64)
+            .bind(serde_json::to_string(&run.timeline).map_err(|e| e.to_string())?)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        tx.commit().await.map_err(|e| e.to_string())
+    }
+
+    async fn list_runs(&self, query: TimelineRunQuery) -> Result<Vec<TimelineRunSummary>, String> {
+        let select = format!(
+            "SELECT run_id, circuit, outcome, recorded_at, event_count FROM {}
+             WHERE (? IS NULL OR circuit = ?)
+               AND (? IS NULL OR outcome = ?)
+               AND (? IS NULL OR recorded_at >= ?)
+               AND (? IS NULL OR recorded_at < ?)
+             ORDER BY seq DESC LIMIT ?",
+            self.table
+        );
+        let since = query.since.map(|since| since.as_nanos() as i64);
+        let until = query.until.map(|until| until.as_nanos() as i64);
+        // SQLite treats a negative LIMIT as no limit.
+        let limit = query
+            .limit
+            .map(|limit| i64::try_from(limit).unwrap_or(i64::MAX))
+            .unwrap_or(-1);
+        let rows: Vec<SqliteTimelineRow> = sqlx::query_as(&select)
+            .bind(&query.circuit)
+            .bind(&query.circuit)
+            .bind(&query.outcome)
+            .bind(&query.outcome)
+            .bind(since)
+            .bind(since)
+            .bind(until)
+            .bind(until)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(rows
+            .into_iter()
+            .map(SqliteTimelineRow::into_summary)
+            .collect())
+    }
+
+    async fn fetch_run(&self, run_id: &str) -> Result<Option<TimelineRun>, String> {
+        let select = format!(
+            "SELECT run_id, circuit, outcome, recorded_at, timeline FROM {} WHERE run_id = ?",
+            self.table
+        );
+        let row: Option<(String, String, String, i64, String)> = sqlx::query_as(&select)
+            .bind(run_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        row.map(|(run_id, circuit, outcome, recorded_at, timeline)| {
+            Ok(TimelineRun {
+                run_id,
+                circuit,
+                outcome,
+                recorded_at: ranvier_core::timeline::Timestamp::from_nanos(recorded_at as u64),
+                timeline: serde_json::from_str(&timeline).map_err(|e| e.to_string())?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn delete_runs(&self, run_ids: &[String]) -> Result<usize, String> {
+        if run_ids.is_empty() {
+            return Ok(0);
+        }
+        let placeholders = vec!["?"; run_ids.len()].join(", ");
+        let delete = format!(
+            "DELETE FROM {} WHERE run_id IN ({placeholders})",
+            self.table
+        );
+        let mut query = sqlx::query(&delete);
+        for run_id in run_ids {
+            query = query.bind(run_id);
+        }
+        let result = query.execute(&self.pool).await.map_err(|e| e.to_string())?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    /// Deletes in SQL instead of listing every run first.
+    async fn compact(&self, retention: &TimelineRetention) -> Result<usize, String> {
+        // `prunable` is the set of runs retention may drop at all.
+        let prunable = if retention.keep_faults {
+            "outcome != 'Fault'"
+        } else {
+            "1 = 1"
+        };
+        let mut removed = 0;
+        if let Some(cutoff) = retention.cutoff(ranvier_core::timeline::Timestamp::now()) {
+            let delete = format!(
+                "DELETE FROM {} WHERE {prunable} AND recorded_at < ?",
+                self.table
+            );
+            removed += sqlx::query(&delete)
+                .bind(cutoff.as_nanos() as i64)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| e.to_string())?
+                .rows_affected();
+        }
+        if let Some(max_runs) = retention.max_runs {
+            let delete = format!(
+                "DELETE FROM {table} WHERE {prunable} AND seq NOT IN (
+                    SELECT seq FROM {table} WHERE {prunable} ORDER BY seq DESC LIMIT ?
+                )",
+                table = self.table
+            );
+            removed += sqlx::query(&delete)
+                .bind(i64::try_from(max_runs).unwrap_or(i64::MAX))
+                .execute(&self.pool)
+                .await
+                .map_err(|e| e.to_string())?
+                .rows_affected();
+        }
+        Ok(removed as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ranvier_core::timeline_store::{InMemoryTimelineStore, TimelineRunQuery};
+
+    #[tokio::test]
+    async fn executor_records_one_run_per_execution() {
+        use crate::axon::Axon;
+        use crate::persistence::PersistenceTraceId;
+        use ranvier_core::timeline::TimelineEvent;
+        use ranvier_core::{Bus, Outcome};
+
+        let axon = Axon::<i32, i32, String>::new("Orders")
+            .then_fn("Reserve", |n: i32, _bus| Outcome::next(n + 1))
+            .then_fn("Charge", |n: i32, _bus| {
+                if n > 5 {
+                    Outcome::fault("card declined".to_string())
+                } else {
+                    Outcome::next(n * 10)
+                }
+            });
+        let store = InMemoryTimelineStore::new();
+        let handle = TimelineStoreHandle::from_store(store.clone());
+
+        let mut bus = Bus::new();
+        bus.insert(PersistenceTraceId::new("order-1"));
+        bus.insert(handle.clone());
+        assert!(axon.execute(1, &(), &mut bus).await.is_next());
+
+        let mut bus = Bus::new();
+        bus.insert(PersistenceTraceId::new("order-2"));
+        bus.insert(handle.clone());
+        assert!(axon.execute(9, &(), &mut bus).await.is_fault());
+
+        let runs = store
+            .list_runs(TimelineRunQuery::new().circuit("Orders"))
+            .await
+            .unwrap();
+        let outcomes: Vec<(&str, &str)> = runs
+            .iter()
+            .map(|run| (run.run_id.as_str(), run.outcome.as_str()))
+            .collect();
+        assert_eq!(outcomes, [("order-2", "Fault"), ("order-1", "Next")]);
+
+        let first = store.fetch_run("order-1").await.unwrap().unwrap();
+        let entered: Vec<&str> = first
+            .timeline
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                TimelineEvent::NodeEnter { node_label, .. } => Some(node_label.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(entered.contains(&"Reserve") && entered.contains(&"Charge"));
+        assert!(bus.read::<ranvier_core::timeline::Timeline>().is_none());
+    }
+
+    #[tokio::test]
+    async fn compaction_task_applies_retention_until_cancelled() {
+        use ranvier_core::cancellation::CancellationReason;
+        use ranvier_core::timeline::Timeline;
+        use ranvier_core::timeline_store::TimelineRun;
+
+        let store = InMemoryTimelineStore::new();
+        for (run_id, outcome) in [("a", "Fault"), ("b", "Next"), ("c", "Next")] {
+            store
+                .append_run(TimelineRun::new(run_id, "Orders", outcome, Timeline::new()))
+                .await
+                .unwrap();
+        }
+        let token = CancellationToken::new();
+        let task = TimelineStoreHandle::from_store(store.clone()).spawn_compaction(
+            TimelineRetention::new().max_runs(1).keep_faults(true),
+            Duration::from_secs(3600),
+            token.clone(),
+        );
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while store.fetch_run("b").await.unwrap().is_some() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("first compaction runs immediately");
+        assert!(store.fetch_run("a").await.unwrap().is_some());
+        assert!(store.fetch_run("c").await.unwrap().is_some());
+
+        token.cancel(CancellationReason::OperatorShutdown);
+        task.await.unwrap();
+    }
+
+    #[cfg(feature = "timeline-sqlite")]
+    #[tokio::test]
+    async fn sqlite_store_lists_newest_first_and_round_trips_timelines() {
+        use ranvier_core::timeline::{Timeline, TimelineEvent, Timestamp};
+        use ranvier_core::timeline_store::TimelineRun;
+
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let store = SqliteTimelineStore::new(pool);
+        store.ensure_schema().await.unwrap();
+
+        let mut timeline = Timeline::new();
+        timeline.push(TimelineEvent::NodeEnter {
+            node_id: "n1".into(),
+            node_label: "Reserve".into(),
+            timestamp: Timestamp::from_millis(1),
+            trace: None,
+        });
+        for (run_id, circuit) in [("a", "Orders"), ("b", "Billing"), ("c", "Orders")] {
+            store
+                .append_run(TimelineRun::new(run_id, circuit, "Next", timeline.clone()))
+                .await
+                .unwrap();
+        }
+        store
+            .append_run(TimelineRun::new("a", "Orders", "Fault", Timeline::new()))
+            .await
+            .unwrap();
+
+        let all: Vec<String> = store
+            .list_runs(TimelineRunQuery::new())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|run| run.run_id)
+            .collect();
+        assert_eq!(all, ["a", "c", "b"]);
+        let orders = store
+            .list_runs(TimelineRunQuery::new().circuit("Orders").limit(1))
+            .await
+            .unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].outcome, "Fault");
+        assert_eq!(orders[0].event_count, 0);
+        let nexts = store
+            .query()
+            .outcome("Next")
+            .since(orders[0].recorded_at)
+            .list()
+            .await
+            .unwrap();
+        assert!(nexts.is_empty());
+        let recorded = store.query().outcome("Next").list().await.unwrap();
+        assert_eq!(recorded.len(), 2);
+        let before_a = store
+            .query()
+            .until(orders[0].recorded_at)
+            .list()
+            .await
+            .unwrap();
+        assert_eq!(before_a.len(), 2);
+
+        let fetched = store.fetch_run("c").await.unwrap().unwrap();
+        assert_eq!(fetched.circuit, "Orders");
+        assert_eq!(fetched.timeline.events.len(), 1);
+        assert!(store.fetch_run("missing").await.unwrap().is_none());
+
+        // Newest first: a (Fault), c, b.
+        let retention = TimelineRetention::new().max_runs(1).keep_faults(true);
+        assert_eq!(store.compact(&retention).await.unwrap(), 1);
+        let kept: Vec<String> = store
+            .list_runs(TimelineRunQuery::new())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|run| run.run_id)
+            .collect();
+        assert_eq!(kept, ["a", "c"]);
+        assert_eq!(
+            store.delete_runs(&["c".into(), "x".into()]).await.unwrap(),
+            1
+        );
+    }
+}
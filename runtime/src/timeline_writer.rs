@@ -0,0 +1,450 @@
+//! Buffered, rotating timeline files written off the execution path.
+//!
+//! `RANVIER_TIMELINE_OUTPUT` serializes each timeline with blocking `std::fs`
+//! calls at the end of the execution and, in `append` mode, rereads the whole
+//! file every time. A [`TimelineWriter`] instead hands timelines to a
+//! background task over a bounded channel. The task batches them and appends
+//! the events to numbered files, starting a new one when the current file
+//! grows past [`TimelineWriterConfig::max_bytes`] or gets older than
+//! [`TimelineWriterConfig::max_age`]:
+//!
+//! ```rust,ignore
+//! let writer = TimelineWriter::spawn(
+//!     "/var/log/app/timeline.json",
+//!     TimelineWriterConfig {
+//!         max_bytes: Some(16 * 1024 * 1024),
+//!         max_age: Some(Duration::from_secs(3600)),
+//!         ..TimelineWriterConfig::default()
+//!     },
+//! );
+//! bus.insert(writer.clone());
+//! axon.execute(order, &resources, &mut bus).await;
+//! // -> /var/log/app/timeline.0001.json, timeline.0002.json, ...
+//! ```
+//!
+//! Every file is a complete [`Timeline`] document after each write: events are
+//! appended by overwriting the closing `]}`. Numbering continues after the
+//! highest existing file, so a restarted process never overwrites history.
+
+use anyhow::{Result, anyhow};
+use ranvier_core::timeline::{Timeline, TimelineHeader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::{mpsc, oneshot};
+
+/// Batching and rotation settings of a [`TimelineWriter`].
+#[derive(Debug, Clone)]
+pub struct TimelineWriterConfig {
+    /// Start a new file once the current one would grow past this size.
+    pub max_bytes: Option<u64>,
+    /// Start a new file once the current one is this old.
+    pub max_age: Option<Duration>,
+    /// Write as soon as this many events are buffered.
+    pub batch_size: usize,
+    /// Write buffered events at least this often.
+    pub flush_interval: Duration,
+    /// Timelines that may wait in the channel; more are dropped.
+    pub capacity: usize,
+}
+
+impl Default for TimelineWriterConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: None,
+            max_age: None,
+            batch_size: 512,
+            flush_interval: Duration::from_secs(1),
+            capacity: 1024,
+        }
+    }
+}
+
+enum Command {
+    Write(Timeline),
+    Flush(oneshot::Sender<Result<()>>),
+}
+
+/// Bus-insertable handle to a background timeline file writer.
+///
+/// Clones share the same task, which writes the remaining buffer and stops
+/// once the last clone is dropped.
+#[derive(Clone)]
+pub struct TimelineWriter {
+    tx: mpsc::Sender<Command>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl std::fmt::Debug for TimelineWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimelineWriter")
+            .field("dropped", &self.dropped())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TimelineWriter {
+    /// Start the writer task on the current Tokio runtime.
+    ///
+    /// `path` names the series: `logs/timeline.json` is written as
+    /// `logs/timeline.0001.json`, `logs/timeline.0002.json`, and so on.
+    pub fn spawn(path: impl AsRef<Path>, config: TimelineWriterConfig) -> Self {
+        let (tx, rx) = mpsc::channel(config.capacity.max(1));
+        let files = RotatingFiles::new(path.as_ref(), &config);
+        tokio::spawn(run_writer(rx, files, config));
+        Self {
+            tx,
+            dropped: Arc::default(),
+        }
+    }
+
+    /// Queue a timeline without waiting. Returns `false`, and counts the
+    /// timeline as dropped, when the channel is full or the task has stopped.
+    pub fn submit(&self, timeline: Timeline) -> bool {
+        let queued = self.tx.try_send(Command::Write(timeline)).is_ok();
+        if !queued {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        queued
+    }
+
+    /// Write everything queued so far and wait for it to reach the file.
+    pub async fn flush(&self) -> Result<()> {
+        let (reply, done) = oneshot::channel();
+        self.tx
+            .send(Command::Flush(reply))
+            .await
+            .map_err(|_| anyhow!("timeline writer stopped"))?;
+        done.await.map_err(|_| anyhow!("timeline writer stopped"))?
+    }
+
+    /// Timelines rejected by [`submit`](Self::submit) so far.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+async fn run_writer(
+    mut rx: mpsc::Receiver<Command>,
+    mut files: RotatingFiles,
+    config: TimelineWriterConfig,
+) {
+    let mut pending: Vec<Timeline> = Vec::new();
+    let mut pending_events = 0;
+    let mut ticker = tokio::time::interval(config.flush_interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        let command = tokio::select! {
+            command = rx.recv() => command,
+            _ = ticker.tick() => {
+                if !pending.is_empty() {
+                    log_write_error(files.write(std::mem::take(&mut pending)).await);
+                    pending_events = 0;
+                }
+                continue;
+            }
+        };
+        match command {
+            Some(Command::Write(timeline)) => {
+                pending_events += timeline.events.len();
+                pending.push(timeline);
+                if pending_events >= config.batch_size {
+                    log_write_error(files.write(std::mem::take(&mut pending)).await);
+                    pending_events = 0;
+                }
+            }
+            Some(Command::Flush(reply)) => {
+                let _ = reply.send(files.write(std::mem::take(&mut pending)).await);
+                pending_events = 0;
+            }
+            None => {
+                log_write_error(files.write(pending).await);
+                return;
+            }
+        }
+    }
+}
+
+fn log_write_error(result: Result<()>) {
+    if let Err(e) = result {
+        tracing::warn!("Failed to write timeline batch: {}", e);
+    }
+}
+
+/// The numbered file series and the file currently appended to.
+struct RotatingFiles {
+    dir: PathBuf,
+    stem: String,
+    extension: String,
+    max_bytes: Option<u64>,
+    max_age: Option<Duration>,
+    /// Highest index in use; `None` until the directory has been scanned.
+    index: Option<u32>,
+    current: Option<CurrentFile>,
+}
+
+struct CurrentFile {
+    file: tokio::fs::File,
+    len: u64,
+    events: usize,
+    opened_at: Instant,
+}
+
+impl RotatingFiles {
+    fn new(path: &Path, config: &TimelineWriterConfig) -> Self {
+        Self {
+            dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            stem: path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("timeline")
+                .to_string(),
+            extension: path
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("json")
+                .to_string(),
+            max_bytes: config.max_bytes,
+            max_age: config.max_age,
+            index: None,
+            current: None,
+        }
+    }
+
+    fn path_for(&self, index: u32) -> PathBuf {
+        self.dir
+            .join(format!("{}.{index:04}.{}", self.stem, self.extension))
+    }
+
+    /// Append the events of `batch`, rotating between timelines as needed.
+    async fn write(&mut self, batch: Vec<Timeline>) -> Result<()> {
+        let mut chunk: Vec<String> = Vec::new();
+        let mut chunk_bytes = 0;
+        for timeline in batch {
+            let encoded = timeline
+                .events
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<Result<Vec<_>, _>>()?;
+            let bytes: u64 = encoded.iter().map(|e| e.len() as u64 + 1).sum();
+            if self.should_rotate(chunk.len(), chunk_bytes + bytes) {
+                self.append(&chunk).await?;
+                chunk.clear();
+                chunk_bytes = 0;
+                self.open_next(&timeline.header).await?;
+            }
+            chunk.extend(encoded);
+            chunk_bytes += bytes;
+        }
+        self.append(&chunk).await
+    }
+
+    fn should_rotate(&self, pending_events: usize, pending_bytes: u64) -> bool {
+        let Some(current) = &self.current else {
+            return true;
+        };
+        // Never leave a file empty, even if one timeline exceeds the limit.
+        if current.events + pending_events == 0 {
+            return false;
+        }
+        self.max_bytes
+            .is_some_and(|max| current.len + pending_bytes > max)
+            || self
+                .max_age
+                .is_some_and(|max| current.opened_at.elapsed() >= max)
+    }
+
+    async fn open_next(&mut self, header: &TimelineHeader) -> Result<()> {
+        let index = match self.index {
+            Some(index) => index + 1,
+            None => self.last_index().await? + 1,
+        };
+        if !self.dir.as_os_str().is_empty() {
+            tokio::fs::create_dir_all(&self.dir).await?;
+        }
+        let path = self.path_for(index);
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .await?;
+        let prefix = format!(
+            "{{\"header\":{},\"events\":[]}}",
+            serde_json::to_string(header)?
+        );
+        file.write_all(prefix.as_bytes()).await?;
+        file.flush().await?;
+        self.index = Some(index);
+        self.current = Some(CurrentFile {
+            file,
+            len: prefix.len() as u64,
+            events: 0,
+            opened_at: Instant::now(),
+        });
+        Ok(())
+    }
+
+    /// Highest index among existing files of this series, or 0.
+    async fn last_index(&self) -> Result<u32> {
+        let dir = if self.dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            self.dir.as_path()
+        };
+        let mut entries = match tokio::fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        let prefix = format!("{}.", self.stem);
+        let suffix = format!(".{}", self.extension);
+        let mut last = 0;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            let index = name
+                .to_str()
+                .and_then(|name| name.strip_prefix(&prefix))
+                .and_then(|rest| rest.strip_suffix(&suffix))
+                .and_then(|index| index.parse::<u32>().ok());
+            if let Some(index) = index {
+                last = last.max(index);
+            }
+        }
+        Ok(last)
+    }
+
+    /// Overwrite the closing `]}` of the current file with `events` and close
+    /// the document again.
+    async fn append(&mut self, events: &[String]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let current = self
+            .current
+            .as_mut()
+            .ok_or_else(|| anyhow!("no timeline file open"))?;
+        let mut buf = String::new();
+        for (i, event) in events.iter().enumerate() {
+            if current.events + i > 0 {
+                buf.push(',');
+            }
+            buf.push_str(event);
+        }
+        buf.push_str("]}");
+        let end = current.len - 2;
+        current.file.seek(std::io::SeekFrom::Start(end)).await?;
+        current.file.write_all(buf.as_bytes()).await?;
+        current.file.flush().await?;
+        current.len = end + buf.len() as u64;
+        current.events += events.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ranvier_core::timeline::{TimelineEvent, Timestamp};
+
+    fn timeline(events: usize) -> Timeline {
+        let mut timeline = Timeline::new();
+        for i in 0..events {
+            timeline.push(TimelineEvent::NodeEnter {
+                node_id: format!("n{i}"),
+                node_label: format!("Step{i}"),
+                timestamp: Timestamp::from_millis(i as u64),
+                trace: None,
+            });
+        }
+        timeline
+    }
+
+    fn read(path: &Path) -> Timeline {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn rotates_by_size_and_continues_numbering_after_restart() {
+        let dir = std::env::temp_dir().join(format!("ranvier-timelines-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("timeline.json");
+        let config = TimelineWriterConfig {
+            max_bytes: Some(400),
+            flush_interval: Duration::from_secs(60),
+            ..TimelineWriterConfig::default()
+        };
+        let writer = TimelineWriter::spawn(&path, config.clone());
+        for _ in 0..4 {
+            assert!(writer.submit(timeline(2)));
+        }
+        writer.flush().await.unwrap();
+
+        let first = read(&dir.join("timeline.0001.json"));
+        let second = read(&dir.join("timeline.0002.json"));
+        assert_eq!(first.events.len() + second.events.len(), 8);
+        assert!(first.events.len() >= 2 && second.events.len() >= 2);
+        assert!(
+            std::fs::metadata(dir.join("timeline.0001.json"))
+                .unwrap()
+                .len()
+                <= 400
+        );
+
+        // Appending to an open file keeps it a valid document.
+        assert!(writer.submit(timeline(1)));
+        writer.flush().await.unwrap();
+        let written: usize = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| read(&entry.unwrap().path()).events.len())
+            .sum();
+        assert_eq!(written, 9);
+        drop(writer);
+
+        let restarted = TimelineWriter::spawn(
+            &path,
+            TimelineWriterConfig {
+                max_age: Some(Duration::ZERO),
+                ..config
+            },
+        );
+        let before = std::fs::read_dir(&dir).unwrap().count();
+        assert!(restarted.submit(timeline(1)));
+        restarted.flush().await.unwrap();
+        assert!(restarted.submit(timeline(1)));
+        restarted.flush().await.unwrap();
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), before + 2);
+        assert_eq!(restarted.dropped(), 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn executor_submits_timelines_to_the_writer_on_the_bus() {
+        use crate::axon::Axon;
+        use ranvier_core::{Bus, Outcome};
+
+        let dir = std::env::temp_dir().join(format!("ranvier-timelines-{}", uuid::Uuid::new_v4()));
+        let writer = TimelineWriter::spawn(dir.join("orders.json"), Default::default());
+        let axon = Axon::<i32, i32, String>::new("Orders")
+            .then_fn("Reserve", |n: i32, _bus| Outcome::next(n + 1));
+        for n in 0..3 {
+            let mut bus = Bus::new();
+            bus.insert(writer.clone());
+            assert!(axon.execute(n, &(), &mut bus).await.is_next());
+            assert!(bus.read::<Timeline>().is_none());
+        }
+        writer.flush().await.unwrap();
+
+        let written = read(&dir.join("orders.0001.json"));
+        let reserves = written
+            .events
+            .iter()
+            .filter(|event| {
+                matches!(event, TimelineEvent::NodeEnter { node_label, .. } if node_label == "Reserve")
+            })
+            .count();
+        assert_eq!(reserves, 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,48 @@
+//! Typed faults for timeout-guarded nodes.
+//!
+//! [`Axon::then_timeout`](crate::Axon::then_timeout) drops a transition's
+//! future once its budget elapses and faults with [`TimeoutError`]. Error
+//! types opt in with `From<TimeoutError>`; `String` and
+//! [`RanvierError`](ranvier_core::error::RanvierError) already do.
+
+use serde::Serialize;
+
+/// Fault category recorded when a node exceeds its timeout.
+pub const NODE_TIMEOUT: &str = "node_timeout";
+
+/// A node that did not finish within its timeout budget.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimeoutError {
+    pub node_label: String,
+    pub timeout_ms: u64,
+}
+
+impl TimeoutError {
+    pub fn category(&self) -> &'static str {
+        NODE_TIMEOUT
+    }
+}
+
+impl std::fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{NODE_TIMEOUT}: '{}' did not finish within {}ms",
+            self.node_label, self.timeout_ms
+        )
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+impl From<TimeoutError> for String {
+    fn from(timeout: TimeoutError) -> Self {
+        timeout.to_string()
+    }
+}
+
+impl From<TimeoutError> for ranvier_core::error::RanvierError {
+    fn from(timeout: TimeoutError) -> Self {
+        Self::internal(timeout.to_string())
+    }
+}
@@ -336,3 +336,126 @@ async fn cancellation_rolls_back_completed_saga_steps_in_lifo_order() {
         "cleanup must be shielded from the workflow cancellation token"
     );
 }
+
+#[tokio::test]
+async fn in_flight_cancellation_records_the_cancelled_node_in_the_timeline() {
+    use ranvier_core::timeline::{Timeline, TimelineEvent};
+
+    let entered = Arc::new(Notify::new());
+    let token = CancellationToken::new();
+    let trigger = tokio::spawn(cancel_after_entry(entered.clone(), token.clone()));
+    let axon = Axon::<i32, i32, String>::start("CancellationTimeline").then(WaitForCancellation {
+        entered,
+        observed_token: Arc::new(AtomicUsize::new(0)),
+    });
+    let mut bus = Bus::new();
+    bus.insert(Timeline::new());
+
+    let terminal = tokio::time::timeout(
+        Duration::from_secs(2),
+        axon.execute_cancellable(1, &(), &mut bus, token),
+    )
+    .await
+    .expect("cancellable execution should terminate");
+    trigger.await.expect("cancellation trigger");
+    assert!(matches!(terminal, ExecutionTerminal::Cancelled(_)));
+
+    let waiting_node = axon
+        .schematic
+        .nodes
+        .iter()
+        .find(|node| node.label == "WaitForCancellation")
+        .map(|node| node.id.clone());
+    let cancelled: Vec<_> = bus
+        .read::<Timeline>()
+        .expect("caller-owned timeline")
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            TimelineEvent::ExecutionCancelled {
+                node_id, reason, ..
+            } => Some((node_id.clone(), *reason)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        cancelled,
+        vec![(waiting_node, CancellationReason::OperatorShutdown)]
+    );
+}
+
+#[tokio::test]
+async fn execute_stops_between_nodes_once_the_bus_token_is_cancelled() {
+    use ranvier_runtime::EXECUTION_CANCELLED;
+
+    let later_ran = Arc::new(AtomicUsize::new(0));
+    let later = later_ran.clone();
+    let axon = Axon::<i32, i32, String>::start("CancellationBetweenNodes")
+        .then_fn("Disconnect", |state: i32, bus: &mut Bus| {
+            if let Some(token) = bus.cancellation_token() {
+                token.cancel(CancellationReason::ClientDisconnected);
+            }
+            Outcome::next(state)
+        })
+        .then_fn("Charge", move |state: i32, _bus| {
+            later.fetch_add(1, Ordering::SeqCst);
+            Outcome::next(state)
+        });
+    let mut bus = Bus::new();
+    bus.set_cancellation_token(CancellationToken::new());
+
+    let outcome = axon.execute(1, &(), &mut bus).await;
+
+    assert!(matches!(outcome, Outcome::Emit(ref event, _) if event == EXECUTION_CANCELLED));
+    assert_eq!(later_ran.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn execute_stops_before_a_compensated_node_once_the_bus_token_is_cancelled() {
+    use ranvier_core::timeline::{Timeline, TimelineEvent};
+    use ranvier_runtime::{ClosureTransition, EXECUTION_CANCELLED};
+
+    let charged = Arc::new(AtomicUsize::new(0));
+    let charges = charged.clone();
+    let axon = Axon::<i32, i32, String>::start("CancellationBeforeCompensated")
+        .then_fn("Disconnect", |state: i32, bus: &mut Bus| {
+            if let Some(token) = bus.cancellation_token() {
+                token.cancel(CancellationReason::ClientDisconnected);
+            }
+            Outcome::next(state)
+        })
+        .then_compensated(
+            ClosureTransition::new("Charge", move |state: i32, _bus: &mut Bus| {
+                charges.fetch_add(1, Ordering::SeqCst);
+                Outcome::<i32, String>::next(state)
+            }),
+            ClosureTransition::new("Refund", |_state: i32, _bus: &mut Bus| {
+                Outcome::<(), String>::next(())
+            }),
+        );
+    let mut bus = Bus::new();
+    bus.insert(Timeline::new());
+    bus.set_cancellation_token(CancellationToken::new());
+
+    let outcome = axon.execute(1, &(), &mut bus).await;
+
+    assert!(matches!(outcome, Outcome::Emit(ref event, _) if event == EXECUTION_CANCELLED));
+    assert_eq!(charged.load(Ordering::SeqCst), 0);
+    let charge_node = axon
+        .schematic
+        .nodes
+        .iter()
+        .find(|node| node.label == "Charge")
+        .map(|node| node.id.clone());
+    let cancelled: Vec<_> = bus
+        .read::<Timeline>()
+        .expect("caller-owned timeline")
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            TimelineEvent::ExecutionCancelled { node_id, .. } => Some(node_id.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(cancelled, vec![charge_node]);
+}
@@ -0,0 +1,69 @@
+//! Global hooks live in process-wide state, so they are tested in their own
+//! binary where no other test registers hooks concurrently.
+
+use async_trait::async_trait;
+use ranvier_core::{Bus, Outcome};
+use ranvier_runtime::{
+    Axon, ExecutionHook, HookDecision, NodeContext, clear_global_hooks, register_global_hook,
+};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone)]
+struct Record {
+    name: &'static str,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl ExecutionHook for Record {
+    async fn on_node_start(&self, node: &NodeContext, _bus: &Bus) -> HookDecision {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{}:start:{}", self.name, node.label));
+        HookDecision::Continue
+    }
+
+    async fn on_outcome(&self, circuit: &str, _outcome: &Value, _bus: &Bus) {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{}:outcome:{circuit}", self.name));
+    }
+}
+
+#[tokio::test]
+async fn global_hooks_run_before_per_axon_hooks_until_cleared() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let hook = |name| Record {
+        name,
+        calls: calls.clone(),
+    };
+    register_global_hook(hook("global"));
+
+    let axon = Axon::<i32, i32, String>::new("Checkout")
+        .with_hook(hook("local"))
+        .then_fn("Charge", |n: i32, _bus| Outcome::next(n + 1));
+    assert!(matches!(
+        axon.execute(1, &(), &mut Bus::new()).await,
+        Outcome::Next(2)
+    ));
+    assert_eq!(
+        *calls.lock().unwrap(),
+        [
+            "global:start:Charge",
+            "local:start:Charge",
+            "global:outcome:Checkout",
+            "local:outcome:Checkout",
+        ]
+    );
+
+    clear_global_hooks();
+    calls.lock().unwrap().clear();
+    axon.execute(1, &(), &mut Bus::new()).await;
+    assert_eq!(
+        *calls.lock().unwrap(),
+        ["local:start:Charge", "local:outcome:Checkout"]
+    );
+}
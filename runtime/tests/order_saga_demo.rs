@@ -397,3 +397,62 @@ async fn order_saga_succeeds_end_to_end_without_failure() {
         "No compensation needed for successful order"
     );
 }
+
+#[derive(Clone)]
+struct ShipWithoutCourier;
+
+#[async_trait]
+impl Transition<OrderState, OrderState> for ShipWithoutCourier {
+    type Error = String;
+    type Resources = ();
+    fn label(&self) -> String {
+        "ConfirmShipment".to_string()
+    }
+
+    async fn run(
+        &self,
+        state: OrderState,
+        _res: &(),
+        _bus: &mut Bus,
+    ) -> Outcome<OrderState, String> {
+        Outcome::fault(format!("No courier available for order {}", state.order_id))
+    }
+}
+
+#[tokio::test]
+async fn compensate_with_rolls_back_completed_steps_from_their_outputs() {
+    let compensation_log = Arc::new(Mutex::new(Vec::<String>::new()));
+
+    let axon = Axon::<OrderState, OrderState, String, ()>::new("OrderSagaCompensateWith")
+        .then(ReserveInventory)
+        .compensate_with(UndoReserveInventory {
+            log: compensation_log.clone(),
+        })
+        .then(ChargePayment { should_fail: false })
+        .compensate_with(UndoChargePayment {
+            log: compensation_log.clone(),
+        })
+        .then(ShipWithoutCourier);
+
+    let mut bus = Bus::new();
+    let outcome = axon
+        .execute(OrderState::new("ORD-NO-COURIER", 42.0), &(), &mut bus)
+        .await;
+    assert!(matches!(outcome, Outcome::Fault(ref msg) if msg.contains("No courier")));
+
+    // LIFO, and each compensation sees the state its node produced.
+    let comp_log = { compensation_log.lock().unwrap().clone() };
+    assert_eq!(comp_log.len(), 2);
+    assert!(comp_log[0].contains("was_charged=true"), "{}", comp_log[0]);
+    assert!(comp_log[1].contains("was_reserved=true"), "{}", comp_log[1]);
+
+    let report = bus.read::<SagaRollbackReport>().expect("rollback report");
+    let compensated: Vec<&str> = report
+        .steps
+        .iter()
+        .filter(|step| step.status == SagaStepStatus::Compensated)
+        .map(|step| step.node_label.as_str())
+        .collect();
+    assert_eq!(compensated, vec!["ChargePayment", "ReserveInventory"]);
+    assert!(report.is_complete());
+}
@@ -176,3 +176,34 @@ async fn saga_compensation_snapshot_mismatch_does_not_panic() {
         "malformed compensation snapshot should emit an event instead of running compensation"
     );
 }
+
+#[tokio::test]
+async fn compensate_with_receives_the_output_of_a_compensated_step() {
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let axon = Axon::<SagaState, SagaState, String, ()>::new("SagaCompensateWith")
+        .then_compensated(
+            SuccStep {
+                id: "1".to_string(),
+            },
+            CompStep {
+                id: "input".to_string(),
+                results: results.clone(),
+            },
+        )
+        .compensate_with(CompStep {
+            id: "output".to_string(),
+            results: results.clone(),
+        })
+        .then(FailStep);
+
+    let initial_state = SagaState {
+        cnt: 0,
+        log: vec![],
+    };
+    let mut bus = Bus::new();
+    let outcome = axon.execute(initial_state, &(), &mut bus).await;
+
+    assert!(matches!(outcome, Outcome::Fault(_)));
+    // The handler sees the state Step 1 produced (cnt=1), not its input.
+    assert_eq!(results.lock().unwrap().as_slice(), ["Comp_output:1"]);
+}
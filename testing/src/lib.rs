@@ -244,6 +244,8 @@ pub fn __outcome_variant_name<T, E: std::fmt::Debug>(outcome: &Outcome<T, E>) ->
         Outcome::Branch(_, _) => "Branch",
         Outcome::Emit { .. } => "Emit",
         Outcome::Jump(_, _) => "Jump",
+        Outcome::Suspend(_, _) => "Suspend",
+        Outcome::Retry { .. } => "Retry",
     }
 }
 